@@ -12,11 +12,14 @@ use syn::{parse_file, Attribute, Item, Meta, Type};
 struct EntityMetadata {
     name: String,
     table_name: String,
+    schema_name: Option<String>,
     primary_key_field: String,
+    primary_key_fields: Vec<String>,
     foreign_key_fields: Vec<String>,
     relations: Vec<RelationMetadata>,
     #[allow(dead_code)]
     primary_key_type: String,
+    primary_key_types: Vec<(String, String)>,
     foreign_key_types: Vec<(String, String)>,
 }
 
@@ -26,7 +29,47 @@ struct RelationMetadata {
     target_entity: String,
     target_table_name: String,
     foreign_key_field: Option<String>,
-    relation_kind: String, // "HasMany" or "BelongsTo"
+    relation_kind: String, // "HasMany", "BelongsTo", "HasOne" or "Polymorphic"
+    discriminator_column: Option<String>,
+    discriminator_value: Option<String>,
+}
+
+#[path = "../../caustics-macros/src/entity/polymorphic_attr.rs"]
+mod polymorphic_attr;
+
+/// Parse `/// #[caustics(polymorphic(name = "...", id = "...", targets(tag = "super::entity")))]`
+/// on a Model field into one relation per target. Malformed attributes are skipped here;
+/// the entity macro reports them.
+fn parse_polymorphic_relations(field: &syn::Field) -> Vec<RelationMetadata> {
+    let Some(discriminator) = field.ident.as_ref().map(|ident| ident.to_string()) else {
+        return Vec::new();
+    };
+    let mut relations = Vec::new();
+    for attr in &field.attrs {
+        let Meta::NameValue(nv) = &attr.meta else { continue };
+        if !nv.path.is_ident("doc") {
+            continue;
+        }
+        let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = &nv.value else {
+            continue;
+        };
+        let Some(Ok(polymorphic)) = polymorphic_attr::parse_polymorphic_doc(&lit.value(), &discriminator) else {
+            continue;
+        };
+        for (tag, path) in polymorphic.targets {
+            let Some(target) = path.segments.last() else { continue };
+            relations.push(RelationMetadata {
+                name: polymorphic.name.clone(),
+                target_entity: target.ident.to_string(),
+                target_table_name: String::new(), // Will be resolved later
+                foreign_key_field: Some(polymorphic.id.clone()),
+                relation_kind: "Polymorphic".to_string(),
+                discriminator_column: Some(discriminator.clone()),
+                discriminator_value: Some(tag),
+            });
+        }
+    }
+    relations
 }
 
 /// Convert a TypeId back to a token stream for code generation
@@ -78,47 +121,63 @@ fn type_id_to_string(type_id: std::any::TypeId) -> String {
     }
 }
 
-/// Convert a syn::Type to a TypeId for comprehensive database types
-fn get_type_id_from_ty(ty: &Type) -> std::any::TypeId {
+/// Type name recorded in entity metadata for a field type.
+///
+/// Known database types map to their canonical names; custom column types are kept
+/// under their own name so key conversion goes through `FromCausticsValue` rather
+/// than being treated as `String`.
+fn type_name_from_ty(ty: &Type) -> String {
+    match get_type_id_from_ty(ty) {
+        Some(type_id) => type_id_to_string(type_id),
+        None => {
+            let (inner_type, _) = extract_type_info(ty);
+            inner_type.to_token_stream().to_string().replace(' ', "")
+        }
+    }
+}
+
+/// Convert a syn::Type to a TypeId for comprehensive database types.
+/// Returns `None` for custom types implementing `ToCausticsValue`.
+fn get_type_id_from_ty(ty: &Type) -> Option<std::any::TypeId> {
     match ty {
         Type::Path(type_path) => {
             if let Some(segment) = type_path.path.segments.last() {
                 match segment.ident.to_string().as_str() {
                     // Integer types
-                    "i8" => std::any::TypeId::of::<i8>(),
-                    "i16" => std::any::TypeId::of::<i16>(),
-                    "i32" => std::any::TypeId::of::<i32>(),
-                    "i64" => std::any::TypeId::of::<i64>(),
-                    "isize" => std::any::TypeId::of::<isize>(),
-                    "u8" => std::any::TypeId::of::<u8>(),
-                    "u16" => std::any::TypeId::of::<u16>(),
-                    "u32" => std::any::TypeId::of::<u32>(),
-                    "u64" => std::any::TypeId::of::<u64>(),
-                    "usize" => std::any::TypeId::of::<usize>(),
+                    "i8" => Some(std::any::TypeId::of::<i8>()),
+                    "i16" => Some(std::any::TypeId::of::<i16>()),
+                    "i32" => Some(std::any::TypeId::of::<i32>()),
+                    "i64" => Some(std::any::TypeId::of::<i64>()),
+                    "isize" => Some(std::any::TypeId::of::<isize>()),
+                    "u8" => Some(std::any::TypeId::of::<u8>()),
+                    "u16" => Some(std::any::TypeId::of::<u16>()),
+                    "u32" => Some(std::any::TypeId::of::<u32>()),
+                    "u64" => Some(std::any::TypeId::of::<u64>()),
+                    "usize" => Some(std::any::TypeId::of::<usize>()),
 
                     // Floating point types
-                    "f32" => std::any::TypeId::of::<f32>(),
-                    "f64" => std::any::TypeId::of::<f64>(),
+                    "f32" => Some(std::any::TypeId::of::<f32>()),
+                    "f64" => Some(std::any::TypeId::of::<f64>()),
 
                     // String and text types
-                    "String" => std::any::TypeId::of::<String>(),
-                    "str" => std::any::TypeId::of::<str>(),
+                    "String" => Some(std::any::TypeId::of::<String>()),
+                    "str" => Some(std::any::TypeId::of::<str>()),
 
                     // Boolean type
-                    "bool" => std::any::TypeId::of::<bool>(),
+                    "bool" => Some(std::any::TypeId::of::<bool>()),
 
                     // UUID type
-                    "Uuid" => std::any::TypeId::of::<uuid::Uuid>(),
+                    "Uuid" => Some(std::any::TypeId::of::<uuid::Uuid>()),
 
                     // DateTime types
-                    "DateTime" => std::any::TypeId::of::<caustics::chrono::DateTime<caustics::chrono::Utc>>(),
-                    "NaiveDateTime" => std::any::TypeId::of::<caustics::chrono::NaiveDateTime>(),
-                    "NaiveDate" => std::any::TypeId::of::<caustics::chrono::NaiveDate>(),
-                    "NaiveTime" => std::any::TypeId::of::<caustics::chrono::NaiveTime>(),
+                    "DateTime" => Some(std::any::TypeId::of::<caustics::chrono::DateTime<caustics::chrono::Utc>>()),
+                    "NaiveDateTime" => Some(std::any::TypeId::of::<caustics::chrono::NaiveDateTime>()),
+                    "NaiveDate" => Some(std::any::TypeId::of::<caustics::chrono::NaiveDate>()),
+                    "NaiveTime" => Some(std::any::TypeId::of::<caustics::chrono::NaiveTime>()),
 
                     // JSON type
-                    "Value" => std::any::TypeId::of::<caustics::serde_json::Value>(),
-                    "Json" => std::any::TypeId::of::<caustics::serde_json::Value>(),
+                    "Value" => Some(std::any::TypeId::of::<caustics::serde_json::Value>()),
+                    "Json" => Some(std::any::TypeId::of::<caustics::serde_json::Value>()),
 
                     // Option types - handle Option<T> by extracting the inner type
                     "Option" => {
@@ -132,13 +191,9 @@ fn get_type_id_from_ty(ty: &Type) -> std::any::TypeId {
                         panic!("Cannot extract inner type from Option<{}>. Please ensure the inner type is supported.", segment.ident);
                     }
 
-                    // For unknown types, treat them as ToSeaOrmValue implementations
-                    // This allows any custom type that implements ToSeaOrmValue to work
-                    _ => {
-                        // For ToSeaOrmValue types, we still use String as the underlying storage
-                        // but we'll handle them specially in the code generation
-                        std::any::TypeId::of::<String>()
-                    }
+                    // Any other type is a custom column type converted through
+                    // ToCausticsValue / FromCausticsValue
+                    _ => None,
                 }
             } else {
                 panic!("Cannot determine type from path with no segments. Please ensure the type is properly specified.");
@@ -170,6 +225,20 @@ fn extract_type_info(ty: &syn::Type) -> (syn::Type, bool) {
     }
 }
 
+/// Parse a relation column list such as `Column::AuthorId` or
+/// `(Column::Title, Column::AuthorId)` into snake_case field names.
+fn parse_column_list(column_str: &str) -> Vec<String> {
+    column_str
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .filter_map(|column| column.trim().rsplit("::").next())
+        .filter(|field_name| !field_name.is_empty())
+        .map(|field_name| field_name.to_snake_case())
+        .collect()
+}
+
 /// Find the type of a field in a struct by looking through the struct definition
 /// Returns (inner_type, is_optional)
 fn find_field_type_in_struct_with_optional(
@@ -194,7 +263,7 @@ fn find_field_type_in_struct_with_optional(
                                         // Check if the field name matches directly
                                         if ident.to_string() == field_name {
                                             let (inner_type, is_optional) = extract_type_info(&field.ty);
-                                            return Some((type_id_to_string(get_type_id_from_ty(&inner_type)), is_optional));
+                                            return Some((type_name_from_ty(&inner_type), is_optional));
                                         }
 
                                         // Also check if the field has a column_name attribute that matches
@@ -224,7 +293,7 @@ fn find_field_type_in_struct_with_optional(
                                                                                 + quote_end];
                                                                     if column_name == field_name {
                                                                         let (inner_type, is_optional) = extract_type_info(&field.ty);
-                                                                        return Some((type_id_to_string(get_type_id_from_ty(&inner_type)), is_optional));
+                                                                        return Some((type_name_from_ty(&inner_type), is_optional));
                                                                     }
                                                                 }
                                                             }
@@ -265,10 +334,11 @@ fn extract_entity_metadata(
 
     let mut foreign_key_fields = Vec::new();
     let mut relations = Vec::new();
-    let mut primary_key_field = None; // No default fallback - must be detected
-    let mut primary_key_type = None; // Must be detected from the actual field
+    // Primary key fields in declaration order; composite keys have more than one
+    let mut primary_key_fields: Vec<(String, String)> = Vec::new();
     let mut foreign_key_types = Vec::new();
     let mut table_name = None; // Extract from #[sea_orm(table_name = "...")]
+    let mut schema_name = None; // Optional #[sea_orm(schema_name = "...")]
 
     // Extract foreign key fields from Model struct (look inside modules)
     for item in &file.items {
@@ -303,6 +373,23 @@ fn extract_entity_metadata(
                                             }
                                         }
                                     }
+
+                                    // Look for schema_name = "value" pattern
+                                    if let Some(start) = attr_str.find("schema_name") {
+                                        if let Some(equals) = attr_str[start..].find('=') {
+                                            let after_equals = &attr_str[start + equals + 1..];
+                                            if let Some(quote_start) = after_equals.find('"') {
+                                                if let Some(quote_end) =
+                                                    after_equals[quote_start + 1..].find('"')
+                                                {
+                                                    let schema_name_value = &after_equals[quote_start
+                                                        + 1
+                                                        ..quote_start + 1 + quote_end];
+                                                    schema_name = Some(schema_name_value.to_string());
+                                                }
+                                            }
+                                        }
+                                    }
                                 }
                             }
 
@@ -310,7 +397,7 @@ fn extract_entity_metadata(
                                 for field in &fields.named {
                                     if let Some(field_name) = field.ident.as_ref() {
                                         let field_name_str = field_name.to_string();
-                                        let field_type_id = get_type_id_from_ty(&field.ty);
+                                        let field_type_name = type_name_from_ty(&field.ty);
 
                                         // Check if field is marked as primary key
                                         let is_primary_key = field.attrs.iter().any(|attr| {
@@ -323,10 +410,12 @@ fn extract_entity_metadata(
                                         });
 
                                         if is_primary_key {
-                                            primary_key_field = Some(field_name_str.clone());
-                                            primary_key_type = Some(field_type_id);
+                                            primary_key_fields
+                                                .push((field_name_str.clone(), field_type_name));
                                         }
 
+                                        relations.extend(parse_polymorphic_relations(field));
+
                                         // Foreign key detection is now handled by parsing Relation enum annotations
                                         // This ensures we only detect actual foreign keys, not just fields ending with _id
                                     }
@@ -356,6 +445,7 @@ fn extract_entity_metadata(
                                 // Parse the relation attributes to extract metadata
                                 let mut target_entity = String::new();
                                 let mut foreign_key_field = None;
+                                let mut relation_fields: Vec<String> = Vec::new();
                                 let mut relation_kind = String::new();
 
                                 for attr in &variant.attrs {
@@ -479,15 +569,10 @@ fn extract_entity_metadata(
                                                         let column_str = &after_equals[quote_start
                                                             + 1
                                                             ..quote_start + 1 + quote_end];
-                                                        if let Some(field_name) =
-                                                            column_str.split("::").nth(1)
-                                                        {
-                                                            // Convert PascalCase to snake_case for database field names
-                                                            let snake_case_name =
-                                                                field_name.to_snake_case();
-                                                            foreign_key_field =
-                                                                Some(snake_case_name);
-                                                        }
+                                                        relation_fields =
+                                                            parse_column_list(column_str);
+                                                        foreign_key_field =
+                                                            relation_fields.first().cloned();
                                                     }
                                                 }
                                             }
@@ -505,15 +590,10 @@ fn extract_entity_metadata(
                                                             let column_str = &after_equals[quote_start
                                                                 + 1
                                                                 ..quote_start + 1 + quote_end];
-                                                            if let Some(field_name) =
-                                                                column_str.split("::").nth(1)
-                                                            {
-                                                                // Convert PascalCase to snake_case for database field names
-                                                                let snake_case_name =
-                                                                    field_name.to_snake_case();
-                                                                foreign_key_field =
-                                                                    Some(snake_case_name);
-                                                            }
+                                                            relation_fields =
+                                                                parse_column_list(column_str);
+                                                            foreign_key_field =
+                                                                relation_fields.first().cloned();
                                                         }
                                                     }
                                                 }
@@ -528,7 +608,7 @@ fn extract_entity_metadata(
                                     // In has_one relations, the 'from' field refers to the primary key of the current entity
                                     // In belongs_to relations, the 'from' field refers to the foreign key in the current entity
                                     if relation_kind == "BelongsTo" {
-                                        if let Some(ref fk_field) = foreign_key_field {
+                                        for fk_field in &relation_fields {
                                             foreign_key_fields.push(fk_field.clone());
 
                                             // Find the type of this foreign key field by looking at the struct fields
@@ -551,6 +631,8 @@ fn extract_entity_metadata(
                                         target_table_name: String::new(), // Will be resolved later
                                         foreign_key_field: foreign_key_field.map(|s| s.to_string()),
                                         relation_kind,
+                                        discriminator_column: None,
+                                        discriminator_value: None,
                                     });
                                 }
                             }
@@ -566,14 +648,29 @@ fn extract_entity_metadata(
         table_name: table_name.unwrap_or_else(|| {
             panic!("No table_name found for entity '{}'. Please ensure the Model struct has #[sea_orm(table_name = \"...\")] attribute.", entity_name)
         }),
-        primary_key_field: primary_key_field.unwrap_or_else(|| {
-            panic!("No primary key field found for entity '{}'. Please ensure at least one field is marked with #[primary_key] attribute.", entity_name)
-        }),
+        schema_name,
+        primary_key_field: primary_key_fields
+            .first()
+            .map(|(field, _)| field.clone())
+            .unwrap_or_else(|| {
+                panic!("No primary key field found for entity '{}'. Please ensure at least one field is marked with #[primary_key] attribute.", entity_name)
+            }),
+        primary_key_fields: primary_key_fields
+            .iter()
+            .map(|(field, _)| field.clone())
+            .collect(),
         foreign_key_fields,
         relations,
-        primary_key_type: type_id_to_string(primary_key_type.unwrap_or_else(|| {
-            panic!("No primary key type found for entity '{}'. This should not happen if primary key field was detected.", entity_name)
-        })),
+        primary_key_type: primary_key_fields
+            .first()
+            .map(|(_, type_name)| type_name.clone())
+            .unwrap_or_else(|| {
+                panic!("No primary key type found for entity '{}'. This should not happen if primary key field was detected.", entity_name)
+            }),
+        primary_key_types: primary_key_fields
+            .iter()
+            .map(|(field, type_name)| (field.clone(), type_name.clone()))
+            .collect(),
         foreign_key_types,
     };
 
@@ -641,7 +738,8 @@ fn generate_client_code(
             // Use imported EntityClient with alias instead of fully qualified path
             quote! {
                 pub fn #method_name(&self) -> #entity_client_alias<'_, DatabaseConnection> {
-                    #entity_client_alias::new(&*self.db, self.database_backend)
+                    #entity_client_alias::with_read_conn(&*self.db, self.read_db(), self.database_backend)
+                        .with_options(self.options.clone())
                 }
             }
         })
@@ -658,6 +756,30 @@ fn generate_client_code(
             quote! {
                 pub fn #method_name(&self) -> #entity_client_alias<'_, DatabaseTransaction> {
                     #entity_client_alias::new(&*self.tx, self.database_backend)
+                        .with_options(self.options.clone())
+                }
+            }
+        })
+        .collect();
+
+    // Let `&CausticsClient` / `&TransactionCausticsClient` stand in for an entity client,
+    // e.g. `selected.refetch(&client)`
+    let entity_client_from_impls: Vec<_> = entities
+        .iter()
+        .map(|(name, _module_path)| {
+            let method_name = format_ident!("{}", name.to_lowercase());
+            let entity_client_alias = format_ident!("{}EntityClient", name.to_lowercase().to_pascal_case());
+            quote! {
+                impl<'a> From<&'a CausticsClient> for #entity_client_alias<'a, DatabaseConnection> {
+                    fn from(client: &'a CausticsClient) -> Self {
+                        client.#method_name()
+                    }
+                }
+
+                impl<'a> From<&'a TransactionCausticsClient> for #entity_client_alias<'a, DatabaseTransaction> {
+                    fn from(client: &'a TransactionCausticsClient) -> Self {
+                        client.#method_name()
+                    }
                 }
             }
         })
@@ -739,6 +861,14 @@ fn generate_client_code(
                     } else {
                         quote! { None }
                     };
+                    let discriminator_column_expr = match &rel.discriminator_column {
+                        Some(column) => quote! { Some(#column) },
+                        None => quote! { None },
+                    };
+                    let discriminator_value_expr = match &rel.discriminator_value {
+                        Some(value) => quote! { Some(#value) },
+                        None => quote! { None },
+                    };
                     quote! {
                         caustics::EntityRelationMetadata {
                             name: #rel_name,
@@ -746,6 +876,8 @@ fn generate_client_code(
                             target_table_name: #target_table_name,
                             foreign_key_field: #fk_field_expr,
                             relation_kind: #relation_kind,
+                            discriminator_column: #discriminator_column_expr,
+                            discriminator_value: #discriminator_value_expr,
                         }
                     }
                 })
@@ -753,7 +885,15 @@ fn generate_client_code(
 
             let primary_key_field_lit =
                 syn::LitStr::new(&metadata.primary_key_field, proc_macro2::Span::call_site());
+            let primary_key_fields_lit = &metadata.primary_key_fields;
             let primary_key_type_lit = &metadata.primary_key_type;
+            let primary_key_types_lit = metadata
+                .primary_key_types
+                .iter()
+                .map(|(field, type_id)| {
+                    quote! { (#field, #type_id) }
+                })
+                .collect::<Vec<_>>();
             let foreign_key_types_lit = metadata
                 .foreign_key_types
                 .iter()
@@ -763,14 +903,21 @@ fn generate_client_code(
                 .collect::<Vec<_>>();
 
             let table_name_lit = &metadata.table_name;
+            let schema_name_lit = match &metadata.schema_name {
+                Some(schema) => quote! { Some(#schema) },
+                None => quote! { None },
+            };
             quote! {
                 caustics::EntityMetadata {
                     name: #entity_name,
                     table_name: #table_name_lit,
+                    schema_name: #schema_name_lit,
                     primary_key_field: #primary_key_field_lit,
+                    primary_key_fields: &[#(#primary_key_fields_lit),*],
                     foreign_key_fields: &[#(#fk_fields_lit),*],
                     relations: &[#(#relations_lit),*],
                     primary_key_type: #primary_key_type_lit,
+                    primary_key_types: &[#(#primary_key_types_lit),*],
                     foreign_key_types: &[#(#foreign_key_types_lit),*],
                 }
             }
@@ -802,6 +949,20 @@ fn generate_client_code(
         })
         .collect();
 
+    // `crate::...::<entity>::Entity` for every entity, for creating their tables
+    let entity_types: Vec<proc_macro2::TokenStream> = entities
+        .iter()
+        .map(|(name, module_path)| {
+            let mut path = quote! { crate };
+            for part in module_path.split("::") {
+                let part_ident = format_ident!("{}", part);
+                path = quote! { #path::#part_ident };
+            }
+            let entity_ident = format_ident!("{}", name.to_lowercase());
+            quote! { #path::#entity_ident::Entity }
+        })
+        .collect();
+
     let client_code = quote! {
         #imports
         // Import entity clients and fetchers
@@ -861,6 +1022,9 @@ fn generate_client_code(
         pub struct CausticsClient {
             db: std::sync::Arc<DatabaseConnection>,
             database_backend: caustics::sea_orm::DatabaseBackend,
+            replicas: Vec<std::sync::Arc<DatabaseConnection>>,
+            next_replica: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+            options: caustics::ClientOptions,
         }
 
         #raw_block
@@ -869,18 +1033,51 @@ fn generate_client_code(
             db: std::sync::Arc<DatabaseConnection>,
             backend: caustics::sea_orm::DatabaseBackend,
             raw: Raw,
+            error_mapper: caustics::ErrorMapper,
             _marker: std::marker::PhantomData<T>,
         }
 
         impl<T> RawQuery<T> {
+            /// Stop waiting for the query after `timeout`; the deadline is client-side only, see
+            /// `caustics::TimeoutQueryBuilder`
+            pub fn timeout(self, timeout: std::time::Duration) -> caustics::TimeoutQueryBuilder<Self> {
+                caustics::TimeoutQueryBuilder { inner: self, timeout }
+            }
+
             pub async fn exec(self) -> Result<Vec<T>, caustics::sea_orm::DbErr>
             where
                 T: caustics::sea_orm::FromQueryResult + Send + Sync + 'static,
             {
                 use caustics::sea_orm::{Statement, SelectorRaw, SelectModel};
-                let stmt = Statement::from_sql_and_values(self.backend, self.raw.sql, self.raw.params);
-                let rows = SelectorRaw::<SelectModel<T>>::from_statement(stmt).all(self.db.as_ref()).await?;
-                Ok(rows)
+                let event = caustics::hooks::QueryEvent {
+                    builder: "RawQuery",
+                    entity: "raw",
+                    details: caustics::hooks::compose_details("query_raw", "raw"),
+                };
+                caustics::hooks::emit_before(&event);
+                let start = std::time::Instant::now();
+                let stmt = Statement::from_sql_and_values(self.backend, caustics::raw::backend_placeholders(self.backend, self.raw.sql), self.raw.params);
+                let result = self.error_mapper.run(SelectorRaw::<SelectModel<T>>::from_statement(stmt).all(self.db.as_ref())).await;
+                caustics::hooks::emit_after(
+                    &event,
+                    &caustics::hooks::QueryResultMeta {
+                        row_count: result.as_ref().ok().map(|rows| rows.len()),
+                        error: result.as_ref().err().map(|e| e.to_string()),
+                        elapsed_ms: Some(start.elapsed().as_millis()),
+                    },
+                );
+                result
+            }
+        }
+
+        impl<T> caustics::TimeoutExec for RawQuery<T>
+        where
+            T: caustics::sea_orm::FromQueryResult + Send + Sync + 'static,
+        {
+            type Output = Vec<T>;
+
+            fn exec_future(self) -> impl std::future::Future<Output = Result<Self::Output, caustics::sea_orm::DbErr>> {
+                self.exec()
             }
         }
 
@@ -888,14 +1085,44 @@ fn generate_client_code(
             db: std::sync::Arc<DatabaseConnection>,
             backend: caustics::sea_orm::DatabaseBackend,
             raw: Raw,
+            error_mapper: caustics::ErrorMapper,
         }
 
         impl RawExecute {
+            /// Stop waiting for the statement after `timeout`; the deadline is client-side only, see
+            /// `caustics::TimeoutQueryBuilder`
+            pub fn timeout(self, timeout: std::time::Duration) -> caustics::TimeoutQueryBuilder<Self> {
+                caustics::TimeoutQueryBuilder { inner: self, timeout }
+            }
+
             pub async fn exec(self) -> Result<caustics::sea_orm::ExecResult, caustics::sea_orm::DbErr> {
                 use caustics::sea_orm::{Statement, ConnectionTrait};
-                let stmt = Statement::from_sql_and_values(self.backend, self.raw.sql, self.raw.params);
-                let res = self.db.execute(stmt).await?;
-                Ok(res)
+                let event = caustics::hooks::QueryEvent {
+                    builder: "RawExecute",
+                    entity: "raw",
+                    details: caustics::hooks::compose_details("execute_raw", "raw"),
+                };
+                caustics::hooks::emit_before(&event);
+                let start = std::time::Instant::now();
+                let stmt = Statement::from_sql_and_values(self.backend, caustics::raw::backend_placeholders(self.backend, self.raw.sql), self.raw.params);
+                let result = self.error_mapper.run(self.db.execute(stmt)).await;
+                caustics::hooks::emit_after(
+                    &event,
+                    &caustics::hooks::QueryResultMeta {
+                        row_count: result.as_ref().ok().map(|res| res.rows_affected() as usize),
+                        error: result.as_ref().err().map(|e| e.to_string()),
+                        elapsed_ms: Some(start.elapsed().as_millis()),
+                    },
+                );
+                result
+            }
+        }
+
+        impl caustics::TimeoutExec for RawExecute {
+            type Output = caustics::sea_orm::ExecResult;
+
+            fn exec_future(self) -> impl std::future::Future<Output = Result<Self::Output, caustics::sea_orm::DbErr>> {
+                self.exec()
             }
         }
 
@@ -903,17 +1130,21 @@ fn generate_client_code(
         pub struct TransactionCausticsClient {
             tx: std::sync::Arc<DatabaseTransaction>,
             database_backend: caustics::sea_orm::DatabaseBackend,
+            options: caustics::ClientOptions,
         }
 
         pub struct TransactionBuilder {
             db: std::sync::Arc<DatabaseConnection>,
             database_backend: caustics::sea_orm::DatabaseBackend,
+            options: caustics::ClientOptions,
         }
 
         // Composite Entity Registry for relation fetching
         pub struct CompositeEntityRegistry;
 
         impl<C: caustics::sea_orm::ConnectionTrait> #registry_trait for CompositeEntityRegistry {
+            // A crate without entities leaves only the fallback arm
+            #[allow(clippy::match_single_binding)]
             fn get_fetcher(&self, entity_name: &str) -> Option<&dyn #fetcher_trait<C>> {
                 match entity_name {
                     #(#registry_match_arms)*
@@ -989,10 +1220,11 @@ fn generate_client_code(
             fn convert_key_for_foreign_key(&self, entity: &str, field: &str, key: caustics::CausticsKey) -> Box<dyn std::any::Any + Send + Sync> {
                 // Get the expected type for this entity's foreign key field
                 if let Some(metadata) = get_entity_metadata(entity) {
-                    // Find the type for this specific foreign key field
+                    // Find the type for this specific foreign key field (or composite primary key part)
                     let field_type = metadata.foreign_key_types.iter()
                         .find(|(field_name, _)| *field_name == field)
-                        .map(|(_, type_id)| *type_id);
+                        .map(|(_, type_id)| *type_id)
+                        .or_else(|| metadata.primary_key_type_of(field));
 
                     match field_type {
                         Some(type_id) => {
@@ -1078,8 +1310,8 @@ fn generate_client_code(
             // Get the expected type for this field
             let field_type = if let Some(metadata) = get_entity_metadata(entity) {
                 // Check if it's a primary key field
-                if field == metadata.primary_key_field {
-                    Some(metadata.primary_key_type)
+                if let Some(pk_type) = metadata.primary_key_type_of(field) {
+                    Some(pk_type)
                 } else {
                     // Check if it's a foreign key field
                     metadata.foreign_key_types.iter()
@@ -1106,8 +1338,8 @@ fn generate_client_code(
         pub fn __caustics_get_field_type<'a>(entity: &'a str, field: &'a str) -> Option<&'static str> {
             if let Some(metadata) = get_entity_metadata(entity) {
                 // Check if it's a primary key field
-                if field == metadata.primary_key_field {
-                    Some(metadata.primary_key_type)
+                if let Some(pk_type) = metadata.primary_key_type_of(field) {
+                    Some(pk_type)
                 } else {
                     // Check if it's a foreign key field
                     metadata.foreign_key_types.iter()
@@ -1246,8 +1478,8 @@ fn generate_client_code(
             // Get the expected type for this field
             let field_type = if let Some(metadata) = get_entity_metadata(entity) {
                 // Check if it's a primary key field
-                if field == metadata.primary_key_field {
-                    Some(metadata.primary_key_type)
+                if let Some(pk_type) = metadata.primary_key_type_of(field) {
+                    Some(pk_type)
                 } else {
                     // Check if it's a foreign key field
                     metadata.foreign_key_types.iter()
@@ -1351,56 +1583,14 @@ fn generate_client_code(
             }
         }
 
-        // Encapsulated helper function to convert CausticsKey to ActiveValue with dynamic type resolution
+        // Convert a CausticsKey to the boxed ActiveValue of a foreign key field, resolving the
+        // field's type through the registry; a key that doesn't fit the type is an error
         pub fn __caustics_convert_key_to_active_value(
             entity: &str,
             field: &str,
             key: caustics::CausticsKey,
-        ) -> Box<dyn std::any::Any + Send + Sync> {
-            let converted = __caustics_convert_key_for_foreign_key(entity, field, key);
-            let field_type = __caustics_get_foreign_key_type(entity, field)
-                .expect("Failed to get field type information");
-
-            // Return the appropriate ActiveValue based on field type
-            match field_type {
-                "i8" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<i8>().expect("Failed to convert to i8")))
-                },
-                "i16" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<i16>().expect("Failed to convert to i16")))
-                },
-                "i32" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<i32>().expect("Failed to convert to i32")))
-                },
-                "i64" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<i64>().expect("Failed to convert to i64")))
-                },
-                "u8" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<u8>().expect("Failed to convert to u8")))
-                },
-                "u16" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<u16>().expect("Failed to convert to u16")))
-                },
-                "u32" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<u32>().expect("Failed to convert to u32")))
-                },
-                "u64" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<u64>().expect("Failed to convert to u64")))
-                },
-                "String" | "str" => {
-                    let string_value = *converted.downcast::<String>().expect("Failed to convert to String");
-                    Box::new(caustics::sea_orm::ActiveValue::Set(string_value))
-                },
-                "uuid::Uuid" => {
-                    Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<uuid::Uuid>().expect("Failed to convert to Uuid")))
-                },
-                "Uuid" => {
-                    Box::new(caustics::sea_orm::ActiveValue::Set(*converted.downcast::<uuid::Uuid>().expect("Failed to convert to Uuid")))
-                },
-                _ => {
-                    panic!("Unsupported foreign key type '{}' for field {} in entity {}", field_type, field, entity);
-                }
-            }
+        ) -> Result<Box<dyn std::any::Any + Send + Sync>, caustics::CausticsError> {
+            __caustics_foreign_key_active_value(entity, field, key, false)
         }
 
         // Helper function for optional foreign keys (wraps in Some)
@@ -1408,49 +1598,66 @@ fn generate_client_code(
             entity: &str,
             field: &str,
             key: caustics::CausticsKey,
-        ) -> Box<dyn std::any::Any + Send + Sync> {
-            let converted = __caustics_convert_key_for_foreign_key(entity, field, key);
-            let field_type = __caustics_get_foreign_key_type(entity, field)
-                .expect("Failed to get field type information");
+        ) -> Result<Box<dyn std::any::Any + Send + Sync>, caustics::CausticsError> {
+            __caustics_foreign_key_active_value(entity, field, key, true)
+        }
 
-            // Return the appropriate ActiveValue with Some() wrapper for optional fields
-            match field_type {
-                "i8" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<i8>().expect("Failed to convert to i8"))))
-                },
-                "i16" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<i16>().expect("Failed to convert to i16"))))
-                },
-                "i32" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<i32>().expect("Failed to convert to i32"))))
-                },
-                "i64" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<i64>().expect("Failed to convert to i64"))))
-                },
-                "u8" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<u8>().expect("Failed to convert to u8"))))
-                },
-                "u16" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<u16>().expect("Failed to convert to u16"))))
-                },
-                "u32" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<u32>().expect("Failed to convert to u32"))))
-                },
-                "u64" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<u64>().expect("Failed to convert to u64"))))
-                },
-                "String" | "str" => {
-                    let string_value = *converted.downcast::<String>().expect("Failed to convert to String");
-                    Box::new(caustics::sea_orm::ActiveValue::Set(Some(string_value)))
-                },
-                "uuid::Uuid" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<uuid::Uuid>().expect("Failed to convert to Uuid"))))
-                },
-                "Uuid" => {
-                Box::new(caustics::sea_orm::ActiveValue::Set(Some(*converted.downcast::<uuid::Uuid>().expect("Failed to convert to Uuid"))))
-                },
-                _ => {
-                    panic!("Unsupported foreign key type '{}' for field {} in entity {}", field_type, field, entity);
+        fn __caustics_foreign_key_active_value(
+            entity: &str,
+            field: &str,
+            key: caustics::CausticsKey,
+            optional: bool,
+        ) -> Result<Box<dyn std::any::Any + Send + Sync>, caustics::CausticsError> {
+            type Boxed = Box<dyn std::any::Any + Send + Sync>;
+            fn set<T>(value: T, optional: bool) -> Boxed
+            where
+                T: Into<caustics::sea_orm::Value> + caustics::sea_query::Nullable + Send + Sync + 'static,
+            {
+                if optional {
+                    Box::new(caustics::sea_orm::ActiveValue::Set(Some(value)))
+                } else {
+                    Box::new(caustics::sea_orm::ActiveValue::Set(value))
+                }
+            }
+
+            let field_type = __caustics_get_foreign_key_type(entity, field).ok_or_else(|| {
+                caustics::CausticsError::MissingConfiguration {
+                    component: format!("{}.{}", entity, field),
+                    required: "foreign key type metadata".to_string(),
+                }
+            })?;
+            let mismatch = |actual: String| caustics::CausticsError::InvalidFieldType {
+                field: format!("{}.{}", entity, field),
+                expected: field_type.to_string(),
+                actual,
+            };
+            let actual = key.to_string();
+            let converted = caustics::try_convert_key_to_type_from_string(key, field_type)
+                .ok_or_else(|| mismatch(actual.clone()))?;
+
+            let active_value: Result<Boxed, Boxed> = match field_type {
+                "i8" => converted.downcast::<i8>().map(|v| set(*v, optional)),
+                "i16" => converted.downcast::<i16>().map(|v| set(*v, optional)),
+                "i32" => converted.downcast::<i32>().map(|v| set(*v, optional)),
+                "i64" => converted.downcast::<i64>().map(|v| set(*v, optional)),
+                "u8" => converted.downcast::<u8>().map(|v| set(*v, optional)),
+                "u16" => converted.downcast::<u16>().map(|v| set(*v, optional)),
+                "u32" => converted.downcast::<u32>().map(|v| set(*v, optional)),
+                "u64" => converted.downcast::<u64>().map(|v| set(*v, optional)),
+                "String" | "str" => converted.downcast::<String>().map(|v| set(*v, optional)),
+                "uuid::Uuid" | "Uuid" => converted.downcast::<uuid::Uuid>().map(|v| set(*v, optional)),
+                _ => Err(converted),
+            };
+            active_value.map_err(|_| mismatch(actual))
+        }
+
+        caustics::__caustics_testing_only! {
+            impl CausticsClient {
+                /// Create the table of every entity, skipping tables that already exist, so test
+                /// setup doesn't have to list them. Requires the `testing` feature.
+                pub async fn create_all_tables(&self) -> Result<(), caustics::sea_orm::DbErr> {
+                    let tables = vec![#(caustics::testing::table_for::<#entity_types>(self.database_backend)),*];
+                    caustics::testing::create_tables(&*self.db, tables).await
                 }
             }
         }
@@ -1463,6 +1670,82 @@ fn generate_client_code(
                 Self {
                     db: std::sync::Arc::new(db),
                     database_backend,
+                    replicas: Vec::new(),
+                    next_replica: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+                    options: caustics::ClientOptions::default(),
+                }
+            }
+
+            /// Client that sends read queries (`find_*`, `count`, `aggregate`, `group_by`)
+            /// round-robin to `replicas`, while writes, raw SQL and transactions use `primary`.
+            /// All connections must use the same database backend.
+            pub fn with_replicas(primary: DatabaseConnection, replicas: Vec<DatabaseConnection>) -> Result<Self, caustics::QueryError> {
+                use caustics::sea_orm::ConnectionTrait;
+                let mut client = Self::new(primary);
+                for replica in replicas {
+                    let backend = replica.get_database_backend();
+                    if backend != client.database_backend {
+                        return Err(caustics::CausticsError::QueryValidation {
+                            message: format!(
+                                "Replica backend {:?} does not match primary backend {:?}",
+                                backend, client.database_backend
+                            ),
+                        }
+                        .into());
+                    }
+                    client.replicas.push(std::sync::Arc::new(replica));
+                }
+                Ok(client)
+            }
+
+            /// View of this client restricted to rows where `field` equals `value`: every
+            /// `find_*`, `count`, `aggregate`, `group_by`, `update*` and `delete*` ANDs
+            /// `field = value` into its filter and every `create`/`upsert` sets the field.
+            /// Entities without that field are left unscoped.
+            pub fn scoped(&self, field: &str, value: impl Into<caustics::sea_orm::Value>) -> Self {
+                self.with_options(|options| {
+                    options.scope = Some(caustics::RowScope { field: field.to_string(), value: value.into() });
+                })
+            }
+
+            /// Prefix the SQL of reads, writes, counts and aggregates with `/* corr_id=... */`
+            /// whenever a correlation id is set on the thread (see `hooks::set_thread_correlation_id`),
+            /// so database-side views like `pg_stat_activity` can be matched with hook logs. Off by
+            /// default.
+            pub fn with_sql_comments(&self, enabled: bool) -> Self {
+                self.with_options(|options| options.sql_comments = enabled)
+            }
+
+            /// Safety valve against accidental full scans: a `find_many` without `take` that
+            /// matches more than `max_rows` rows fails with `CausticsError::RowLimitExceeded`
+            /// instead of loading them all. `None` (the default) disables the check.
+            pub fn max_rows(&self, max_rows: Option<u64>) -> Self {
+                self.with_options(|options| options.max_rows = max_rows)
+            }
+
+            /// Route database failures (constraint violations, deadlocks, ...) from every query,
+            /// raw statement and batch run through this client into `mapper`, which receives a
+            /// `CausticsError::Database` carrying the driver's error code and returns the error
+            /// the call should surface
+            pub fn with_error_mapper(
+                &self,
+                mapper: impl Fn(caustics::CausticsError) -> caustics::sea_orm::DbErr + Send + Sync + 'static,
+            ) -> Self {
+                self.with_options(|options| {
+                    options.error_mapper = caustics::ErrorMapper::new(Some(std::sync::Arc::new(mapper)));
+                })
+            }
+
+            /// Copy of this client sharing its connections, with `update` applied to its options
+            fn with_options(&self, update: impl FnOnce(&mut caustics::ClientOptions)) -> Self {
+                let mut options = self.options.clone();
+                update(&mut options);
+                Self {
+                    db: self.db.clone(),
+                    database_backend: self.database_backend,
+                    replicas: self.replicas.clone(),
+                    next_replica: self.next_replica.clone(),
+                    options,
                 }
             }
 
@@ -1470,14 +1753,35 @@ fn generate_client_code(
                 self.db.clone()
             }
 
+            /// Connection for the next read query: a replica in round-robin order, or the primary
+            fn read_db(&self) -> &DatabaseConnection {
+                if self.replicas.is_empty() {
+                    return &self.db;
+                }
+                let next = self.next_replica.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                &self.replicas[next % self.replicas.len()]
+            }
+
             pub fn database_backend(&self) -> caustics::sea_orm::DatabaseBackend {
                 self.database_backend
             }
 
+            /// Metadata for one entity, looked up as `"User"`, `"user"` or `"blog::User"`: table,
+            /// primary key, foreign key types and relations
+            pub fn entity_metadata(entity_name: &str) -> Option<&'static caustics::EntityMetadata> {
+                get_entity_metadata(entity_name)
+            }
+
+            /// Metadata for every entity this client was generated for
+            pub fn entities_metadata() -> &'static [caustics::EntityMetadata] {
+                ENTITY_METADATA
+            }
+
             pub fn _transaction(&self) -> TransactionBuilder {
                 TransactionBuilder {
                     db: self.db.clone(),
                     database_backend: self.database_backend,
+                    options: self.options.clone(),
                 }
             }
 
@@ -1488,13 +1792,21 @@ fn generate_client_code(
 
             // Raw SQL APIs
             pub fn _query_raw<T>(&self, raw: Raw) -> RawQuery<T> {
-                RawQuery { db: self.db.clone(), backend: self.database_backend, raw, _marker: std::marker::PhantomData }
+                RawQuery { db: self.db.clone(), backend: self.database_backend, raw, error_mapper: self.options.error_mapper.clone(), _marker: std::marker::PhantomData }
             }
 
             pub fn _execute_raw(&self, raw: Raw) -> RawExecute {
-                RawExecute { db: self.db.clone(), backend: self.database_backend, raw }
+                RawExecute { db: self.db.clone(), backend: self.database_backend, raw, error_mapper: self.options.error_mapper.clone() }
+            }
+
+            /// Run a lightweight `SELECT 1` to check the connection (e.g. for readiness probes)
+            pub async fn health_check(&self) -> Result<(), caustics::QueryError> {
+                self._execute_raw(Raw::new("SELECT 1", Vec::new())).exec().await.map(|_| ())
             }
 
+            /// Run a tuple of create/update/upsert/delete queries in one transaction.
+            /// A one-element tuple that is a single SQL statement (a plain create or a native
+            /// upsert) runs directly on the connection without `BEGIN`/`COMMIT`.
             pub async fn _batch<'a, Entity, ActiveModel, ModelWithRelations, T, Container>(
                 &self,
                 queries: Container,
@@ -1504,41 +1816,91 @@ fn generate_client_code(
                 ActiveModel: caustics::sea_orm::ActiveModelTrait<Entity = Entity> + caustics::sea_orm::ActiveModelBehavior + Send + 'static,
                 ModelWithRelations: #from_model<<Entity as caustics::sea_orm::EntityTrait>::Model>
                     + caustics::HasRelationMetadata<ModelWithRelations>
-                    + caustics::ApplyNestedIncludes<caustics::sea_orm::DatabaseTransaction>,
+                    + caustics::ApplyNestedIncludes<caustics::sea_orm::DatabaseTransaction>
+                    + caustics::ApplyNestedIncludes<caustics::sea_orm::DatabaseConnection>
+                    + Send
+                    + 'static,
                 T: #merge_into<ActiveModel>,
                 <Entity as caustics::sea_orm::EntityTrait>::Model: caustics::sea_orm::IntoActiveModel<ActiveModel>,
                 Container: #batch_container<'a, caustics::sea_orm::DatabaseConnection, Entity, ActiveModel, ModelWithRelations, T>,
             {
-                let txn = self.db.begin().await?;
-                let batch_queries = Container::into_queries(queries);
+                use #hooks_mod::TxEvent;
+
+                let mut batch_queries = Container::into_queries(queries);
+
+                // A single statement is atomic on its own and gains nothing from BEGIN/COMMIT;
+                // anything that writes more than once still needs the transaction
+                if batch_queries.len() == 1 && batch_queries[0].is_single_statement() {
+                    let res = match batch_queries.pop().expect("batch has one query") {
+                        #batch_query::Insert(q) => #batch_result::Insert(q.exec().await?),
+                        #batch_query::Update(q) => #batch_result::Update(q.exec().await?),
+                        #batch_query::Delete(q) => #batch_result::Delete(q.exec().await?),
+                        #batch_query::Upsert(q) => #batch_result::Upsert(q.exec().await?),
+                    };
+                    return Ok(Container::from_results(vec![res]));
+                }
+
+                let txn = self.options.error_mapper.run(self.db.begin()).await?;
+                let correlation_id = #hooks_mod::current_correlation_id();
+                let start = std::time::Instant::now();
+                #hooks_mod::emit_transaction(&TxEvent::Begin { correlation_id: correlation_id.clone() });
                 let mut results = Vec::with_capacity(batch_queries.len());
 
-                for query in batch_queries {
-                    let res = match query {
-                        #batch_query::Insert(q) => {
-                            // For Insert, use exec_in_txn to use the transaction
-                            let result = q.exec_in_txn(&txn).await?;
-                            #batch_result::Insert(result)
-                        }
-                        #batch_query::Update(q) => {
-                            let result = q.exec_in_txn(&txn).await?;
-                            #batch_result::Update(result)
-                        }
-                        #batch_query::Delete(q) => {
-                            let result = q.exec_in_txn(&txn).await?;
-                            #batch_result::Delete(result)
-                        }
-                        #batch_query::Upsert(q) => {
-                            // For Upsert, use exec_in_txn to use the transaction
-                            let result = q.exec_in_txn(&txn).await?;
-                            #batch_result::Upsert(result)
-                        }
-                    };
-                    results.push(res);
+                let run = async {
+                    for query in batch_queries {
+                        let res = match query {
+                            #batch_query::Insert(q) => {
+                                // For Insert, use exec_in_txn to use the transaction
+                                let result = q.exec_in_txn(&txn).await?;
+                                #batch_result::Insert(result)
+                            }
+                            #batch_query::Update(q) => {
+                                let result = q.exec_in_txn(&txn).await?;
+                                #batch_result::Update(result)
+                            }
+                            #batch_query::Delete(q) => {
+                                let result = q.exec_in_txn(&txn).await?;
+                                #batch_result::Delete(result)
+                            }
+                            #batch_query::Upsert(q) => {
+                                // For Upsert, use exec_in_txn to use the transaction
+                                let result = q.exec_in_txn(&txn).await?;
+                                #batch_result::Upsert(result)
+                            }
+                        };
+                        results.push(res);
+                    }
+                    Ok::<_, caustics::sea_orm::DbErr>(())
+                };
+
+                let outcome = match run.await {
+                    Ok(()) => self.options.error_mapper.run(txn.commit()).await,
+                    Err(e) => self.options.error_mapper.run(txn.rollback()).await.and(Err(e)),
+                };
+                let elapsed_ms = start.elapsed().as_millis();
+                match outcome {
+                    Ok(()) => {
+                        #hooks_mod::emit_transaction(&TxEvent::Commit { correlation_id, elapsed_ms });
+                        Ok(Container::from_results(results))
+                    }
+                    Err(e) => {
+                        #hooks_mod::emit_transaction(&TxEvent::Rollback { correlation_id, elapsed_ms, error: e.to_string() });
+                        Err(e)
+                    }
                 }
+            }
 
-                txn.commit().await?;
-                Ok(Container::from_results(results))
+            /// Run a tuple of create/update/upsert/delete queries, possibly on different
+            /// entities, in one transaction. Queries run in tuple order and the result tuple
+            /// matches it position by position; any error rolls the whole batch back.
+            pub async fn _batch_mixed<Container>(&self, queries: Container) -> Result<Container::ReturnType, caustics::sea_orm::DbErr>
+            where
+                Container: caustics::MixedBatchContainer,
+            {
+                let txn = self.options.error_mapper.run(self.db.begin()).await?;
+                let results = queries.exec_in_txn(&txn).await?;
+                self.options.error_mapper.run(txn.commit()).await?;
+                Ok(results)
             }
 
             #(#entity_methods)*
@@ -1550,18 +1912,18 @@ fn generate_client_code(
         #[allow(dead_code)]
         impl TransactionCausticsClient {
             pub fn new(tx: std::sync::Arc<DatabaseTransaction>, database_backend: caustics::sea_orm::DatabaseBackend) -> Self {
-                Self { tx, database_backend }
+                Self { tx, database_backend, options: caustics::ClientOptions::default() }
             }
 
             #(#tx_entity_methods)*
 
             // Raw SQL APIs within a transaction
             pub fn _query_raw<T>(&self, raw: Raw) -> TxRawQuery<T> {
-                TxRawQuery { tx: self.tx.clone(), backend: self.database_backend, raw, _marker: std::marker::PhantomData }
+                TxRawQuery { tx: self.tx.clone(), backend: self.database_backend, raw, error_mapper: self.options.error_mapper.clone(), _marker: std::marker::PhantomData }
             }
 
             pub fn _execute_raw(&self, raw: Raw) -> TxRawExecute {
-                TxRawExecute { tx: self.tx.clone(), backend: self.database_backend, raw }
+                TxRawExecute { tx: self.tx.clone(), backend: self.database_backend, raw, error_mapper: self.options.error_mapper.clone() }
             }
 
             // Transaction-scoped hook installer (overrides global while running in this thread)
@@ -1574,7 +1936,9 @@ fn generate_client_code(
                 Box::pin(async move {
                     #hooks_mod::set_thread_hook(Some(hook));
                     let _corr = #hooks_mod::set_new_correlation_id();
-                    let res = f(TransactionCausticsClient::new(self.tx.clone(), self.database_backend)).await;
+                    let mut tx_client = TransactionCausticsClient::new(self.tx.clone(), self.database_backend);
+                    tx_client.options = self.options.clone();
+                    let res = f(tx_client).await;
                     #hooks_mod::set_thread_hook(None);
                     #hooks_mod::set_thread_correlation_id(None);
                     res
@@ -1582,10 +1946,13 @@ fn generate_client_code(
             }
         }
 
+        #(#entity_client_from_impls)*
+
         pub struct TxRawQuery<T> {
             tx: std::sync::Arc<DatabaseTransaction>,
             backend: caustics::sea_orm::DatabaseBackend,
             raw: Raw,
+            error_mapper: caustics::ErrorMapper,
             _marker: std::marker::PhantomData<T>,
         }
 
@@ -1595,8 +1962,8 @@ fn generate_client_code(
                 T: caustics::sea_orm::FromQueryResult + Send + Sync + 'static,
             {
                 use caustics::sea_orm::{Statement, SelectorRaw, SelectModel};
-                let stmt = Statement::from_sql_and_values(self.backend, self.raw.sql, self.raw.params);
-                let rows = SelectorRaw::<SelectModel<T>>::from_statement(stmt).all(self.tx.as_ref()).await?;
+                let stmt = Statement::from_sql_and_values(self.backend, caustics::raw::backend_placeholders(self.backend, self.raw.sql), self.raw.params);
+                let rows = self.error_mapper.run(SelectorRaw::<SelectModel<T>>::from_statement(stmt).all(self.tx.as_ref())).await?;
                 Ok(rows)
             }
         }
@@ -1605,13 +1972,14 @@ fn generate_client_code(
             tx: std::sync::Arc<DatabaseTransaction>,
             backend: caustics::sea_orm::DatabaseBackend,
             raw: Raw,
+            error_mapper: caustics::ErrorMapper,
         }
 
         impl TxRawExecute {
             pub async fn exec(self) -> Result<caustics::sea_orm::ExecResult, caustics::sea_orm::DbErr> {
                 use caustics::sea_orm::{Statement, ConnectionTrait};
-                let stmt = Statement::from_sql_and_values(self.backend, self.raw.sql, self.raw.params);
-                let res = self.tx.execute(stmt).await?;
+                let stmt = Statement::from_sql_and_values(self.backend, caustics::raw::backend_placeholders(self.backend, self.raw.sql), self.raw.params);
+                let res = self.error_mapper.run(self.tx.execute(stmt)).await?;
                 Ok(res)
             }
         }
@@ -1622,18 +1990,51 @@ fn generate_client_code(
                 F: FnOnce(TransactionCausticsClient) -> Fut,
                 Fut: std::future::Future<Output = Result<T, caustics::sea_orm::DbErr>>,
             {
-                let tx = self.db.begin().await?;
+                use #hooks_mod::TxEvent;
+                // Keep a surrounding correlation id, otherwise tag this transaction with its own
+                let own_correlation_id = #hooks_mod::current_correlation_id().is_none();
+                if own_correlation_id {
+                    #hooks_mod::set_new_correlation_id();
+                }
+                let correlation_id = #hooks_mod::current_correlation_id();
+                let finish = |event: TxEvent| {
+                    #hooks_mod::emit_transaction(&event);
+                    if own_correlation_id {
+                        #hooks_mod::set_thread_correlation_id(None);
+                    }
+                };
+
+                let tx = match self.options.error_mapper.run(self.db.begin()).await {
+                    Ok(tx) => tx,
+                    Err(e) => {
+                        if own_correlation_id {
+                            #hooks_mod::set_thread_correlation_id(None);
+                        }
+                        return Err(e);
+                    }
+                };
+                let start = std::time::Instant::now();
+                #hooks_mod::emit_transaction(&TxEvent::Begin { correlation_id: correlation_id.clone() });
                 let tx_arc = std::sync::Arc::new(tx);
-                let tx_client = TransactionCausticsClient::new(tx_arc.clone(), self.database_backend);
+                let mut tx_client = TransactionCausticsClient::new(tx_arc.clone(), self.database_backend);
+                tx_client.options = self.options.clone();
                 let result = f(tx_client).await;
                 let tx = std::sync::Arc::try_unwrap(tx_arc).expect("Transaction Arc should be unique");
                 match result {
-                    Ok(val) => {
-                        tx.commit().await?;
-                        Ok(val)
-                    }
+                    Ok(val) => match self.options.error_mapper.run(tx.commit()).await {
+                        Ok(()) => {
+                            finish(TxEvent::Commit { correlation_id, elapsed_ms: start.elapsed().as_millis() });
+                            Ok(val)
+                        }
+                        Err(e) => {
+                            finish(TxEvent::Rollback { correlation_id, elapsed_ms: start.elapsed().as_millis(), error: e.to_string() });
+                            Err(e)
+                        }
+                    },
                     Err(e) => {
-                        tx.rollback().await?;
+                        let rolled_back = self.options.error_mapper.run(tx.rollback()).await;
+                        finish(TxEvent::Rollback { correlation_id, elapsed_ms: start.elapsed().as_millis(), error: e.to_string() });
+                        rolled_back?;
                         Err(e)
                     }
                 }
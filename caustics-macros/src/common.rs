@@ -23,3 +23,20 @@ pub fn extract_inner_type_from_option(ty: &syn::Type) -> &syn::Type {
     }
     ty
 }
+
+/// Extract the element type from Vec<T> (or Option<Vec<T>>), or return the original type
+pub fn extract_inner_type_from_vec(ty: &syn::Type) -> &syn::Type {
+    let ty = extract_inner_type_from_option(ty);
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == "Vec" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        return inner;
+                    }
+                }
+            }
+        }
+    }
+    ty
+}
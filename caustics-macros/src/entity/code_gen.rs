@@ -1,4 +1,7 @@
-use super::{extract_relations, generate_relation_submodules, RelationKind};
+use super::{
+    extract_polymorphic_relations, extract_relations, generate_polymorphic_code,
+    generate_relation_submodules, RelationKind,
+};
 use crate::common::is_option;
 use crate::name_resolution::EntityNameContext;
 use crate::primary_key::{
@@ -19,11 +22,60 @@ fn extract_field_type_info(field: &syn::Field) -> (bool, &syn::Type, &syn::Type)
     (is_opt, &field.ty, inner_ty)
 }
 
+/// `Result<ActiveValue<_>, CausticsError>` for a foreign key field assigned from the
+/// `CausticsKey` in `key`; a key the field can't hold is an error rather than a panic.
+///
+/// Scalar fields go through the registry conversion helpers; any other field type is
+/// only known to the registry by name, so it is rebuilt through `FromCausticsValue`.
+fn foreign_key_active_value(
+    entity_name: &str,
+    field_name: &str,
+    key: TokenStream,
+    inner_ty: &syn::Type,
+    is_optional: bool,
+) -> TokenStream {
+    let value_ty = if is_optional {
+        quote! { Option<#inner_ty> }
+    } else {
+        quote! { #inner_ty }
+    };
+    if matches!(
+        crate::where_param::detect_field_type(inner_ty),
+        crate::where_param::FieldType::Other
+    ) {
+        let value = if is_optional { quote! { Some(value) } } else { quote! { value } };
+        quote! {
+            {
+                let key: caustics::CausticsKey = #key;
+                let actual = key.to_string();
+                <#inner_ty as caustics::FromCausticsValue>::from_caustics_key(key)
+                    .map(|value| sea_orm::ActiveValue::<#value_ty>::Set(#value))
+                    .ok_or_else(|| caustics::CausticsError::InvalidFieldType {
+                        field: format!("{}.{}", #entity_name, #field_name),
+                        expected: stringify!(#inner_ty).to_string(),
+                        actual,
+                    })
+            }
+        }
+    } else {
+        let convert = if is_optional {
+            quote! { crate::__caustics_convert_key_to_active_value_optional }
+        } else {
+            quote! { crate::__caustics_convert_key_to_active_value }
+        };
+        quote! {
+            #convert(#entity_name, #field_name, #key).and_then(|boxed| {
+                caustics::downcast_active_value::<#value_ty>(boxed, #entity_name, #field_name)
+            })
+        }
+    }
+}
+
 /// Find a field by its foreign key name and extract type information
 fn find_field_and_extract_type_info<'a>(fields: &'a [&'a syn::Field], fk_field_name: &str) -> Option<(bool, &'a syn::Type, &'a syn::Type)> {
     // The fk_field_name is already in snake_case (like "department_id"), so we don't need to convert it
     fields.iter()
-        .find(|f| f.ident.as_ref().unwrap().to_string() == fk_field_name)
+        .find(|f| f.ident.as_ref().unwrap() == fk_field_name)
         .map(|field| extract_field_type_info(field))
 }
 
@@ -68,6 +120,682 @@ fn generate_select_code(all_field_idents_snake: &[proc_macro2::Ident]) -> TokenS
     }
 }
 
+/// Generate the entity client's `truncate()`, kept only when caustics has the `testing` feature
+fn generate_truncate_code() -> TokenStream {
+    quote! {
+        caustics::__caustics_testing_only! {
+            /// Remove every row, resetting id sequences where the backend supports it.
+            /// Scoped clients only delete the rows inside their scope.
+            pub async fn truncate(&self) -> Result<(), sea_orm::DbErr>
+            where
+                C: sea_orm::ConnectionTrait,
+            {
+                if self.options.scope.is_some() {
+                    return self.delete_many(vec![]).exec().await.map(|_| ());
+                }
+                caustics::testing::truncate::<C, Entity>(self.conn).await
+            }
+        }
+    }
+}
+
+/// Generate `serde::Deserialize` for the `Create` struct when the `deserialize` feature is on.
+///
+/// The payload is a flat object keyed by model field name. Required fields must be present,
+/// required relations are given by their foreign key field(s), and any other field becomes a
+/// `set` in `_params`. Unknown keys are rejected.
+fn generate_create_deserialize(
+    fields: &[&syn::Field],
+    required_fields: &[&&syn::Field],
+    create_relations: &[&super::Relation],
+    current_primary_key: &str,
+) -> TokenStream {
+    let required_idents: Vec<_> = required_fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Field has no identifier"))
+        .collect();
+    let required_names: Vec<_> = required_idents.iter().map(|ident| ident.to_string()).collect();
+    let required_types: Vec<_> = required_fields.iter().map(|field| &field.ty).collect();
+
+    let mut relation_fk_names: Vec<String> = Vec::new();
+    let relation_reads: Vec<_> = create_relations
+        .iter()
+        .map(|relation| {
+            let relation_ident = format_ident!("{}", relation.get_field_name());
+            let target_module = &relation.target;
+            let fk_names: Vec<String> = if !relation.foreign_key_fields.is_empty() {
+                relation.foreign_key_fields.iter().map(|f| f.to_snake_case()).collect()
+            } else {
+                vec![relation.get_first_fk_column_name().to_snake_case()]
+            };
+            let fk_idents: Vec<_> = fk_names.iter().map(|name| format_ident!("{}", name)).collect();
+            let fk_types: Vec<_> = fk_names
+                .iter()
+                .map(|name| {
+                    let (_, ty, _) = find_field_and_extract_type_info(fields, name)
+                        .unwrap_or_else(|| panic!("Foreign key field {} not found", name));
+                    ty
+                })
+                .collect();
+            relation_fk_names.extend(fk_names.iter().cloned());
+
+            let where_param = if relation.is_composite && relation.target_primary_key_fields.len() > 1 {
+                let composite_variant = format_ident!(
+                    "{}",
+                    relation
+                        .target_primary_key_fields
+                        .iter()
+                        .map(|field| field.to_pascal_case())
+                        .collect::<Vec<_>>()
+                        .join("And")
+                );
+                quote! { #target_module::UniqueWhereParam::#composite_variant(#(#fk_idents.into()),*) }
+            } else {
+                let primary_key_variant = if let Some(pk) = relation.target_primary_key_fields.first() {
+                    format_ident!("{}Equals", pk.to_pascal_case())
+                } else if let Some(pk) = &relation.primary_key_field {
+                    format_ident!("{}Equals", pk.to_pascal_case())
+                } else {
+                    format_ident!("{}Equals", current_primary_key.to_pascal_case())
+                };
+                let fk_ident = &fk_idents[0];
+                let fk_name = &fk_names[0];
+                quote! {
+                    #target_module::UniqueWhereParam::#primary_key_variant(
+                        caustics::CausticsKey::from_db_value(&(&#fk_ident).to_sea_orm_value())
+                            .ok_or_else(|| D::Error::custom(format!("invalid key for field `{}`", #fk_name)))?,
+                    )
+                }
+            };
+
+            quote! {
+                #(
+                    let #fk_idents: #fk_types = __take(&mut __map, #fk_names)?
+                        .ok_or_else(|| D::Error::missing_field(#fk_names))?;
+                )*
+                let #relation_ident = #where_param;
+            }
+        })
+        .collect();
+    let relation_idents: Vec<_> = create_relations
+        .iter()
+        .map(|relation| format_ident!("{}", relation.get_field_name()))
+        .collect();
+
+    let optional_fields: Vec<_> = fields
+        .iter()
+        .filter(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier").to_string();
+            !required_names.contains(&name) && !relation_fk_names.contains(&name)
+        })
+        .collect();
+    let optional_idents: Vec<_> = optional_fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Field has no identifier"))
+        .collect();
+    let optional_names: Vec<_> = optional_idents.iter().map(|ident| ident.to_string()).collect();
+    let optional_types: Vec<_> = optional_fields.iter().map(|field| &field.ty).collect();
+
+    quote! {
+        caustics::__caustics_deserialize_only! {
+            impl<'de> caustics::serde::Deserialize<'de> for Create {
+                fn deserialize<D: caustics::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    use caustics::serde::de::Error as _;
+                    #[allow(unused_imports)]
+                    use caustics::ToSeaOrmValue;
+
+                    const FIELDS: &[&str] = &[#(#required_names,)* #(#relation_fk_names,)* #(#optional_names,)*];
+
+                    fn __take<T: caustics::serde::de::DeserializeOwned, E: caustics::serde::de::Error>(
+                        map: &mut caustics::serde_json::Map<String, caustics::serde_json::Value>,
+                        name: &str,
+                    ) -> Result<Option<T>, E> {
+                        map.remove(name)
+                            .map(|value| caustics::serde_json::from_value(value).map_err(|e| E::custom(format!("field `{}`: {}", name, e))))
+                            .transpose()
+                    }
+
+                    let mut __map = <caustics::serde_json::Map<String, caustics::serde_json::Value> as caustics::serde::Deserialize>::deserialize(deserializer)?;
+                    #(
+                        let #required_idents: #required_types = __take(&mut __map, #required_names)?
+                            .ok_or_else(|| D::Error::missing_field(#required_names))?;
+                    )*
+                    #(#relation_reads)*
+                    #[allow(unused_mut)]
+                    let mut _params: Vec<SetParam> = Vec::new();
+                    #(
+                        if let Some(value) = __take::<#optional_types, D::Error>(&mut __map, #optional_names)? {
+                            _params.push(#optional_idents::set(value));
+                        }
+                    )*
+                    if let Some(__unknown) = __map.keys().next() {
+                        return Err(D::Error::unknown_field(__unknown, FIELDS));
+                    }
+                    Ok(Create {
+                        #(#required_idents,)*
+                        #(#relation_idents,)*
+                        _params,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Generate `TryFrom<serde_json::Value>` for the `Model` when the `deserialize` feature is on.
+///
+/// The object is keyed by model field name, like the `Create` payload. Missing `Option` fields
+/// become `None`; a missing required field or a value of the wrong type is reported as
+/// `CausticsError::InvalidFieldType` naming the field. Unknown keys are ignored.
+fn generate_model_try_from_json(fields: &[&syn::Field], entity_name: &str) -> TokenStream {
+    let idents: Vec<_> = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Field has no identifier"))
+        .collect();
+    let names: Vec<_> = idents.iter().map(|ident| ident.to_string()).collect();
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let type_names: Vec<_> = types
+        .iter()
+        .map(|ty| quote! { #ty }.to_string().replace(' ', ""))
+        .collect();
+    let reads: Vec<_> = fields
+        .iter()
+        .zip(names.iter().zip(type_names.iter()))
+        .map(|(field, (name, type_name))| {
+            let ident = field.ident.as_ref().expect("Field has no identifier");
+            let ty = &field.ty;
+            let missing = if is_option(ty) {
+                quote! { None }
+            } else {
+                quote! { return Err(caustics::CausticsError::invalid_field_type(#name, #type_name, "missing")) }
+            };
+            quote! {
+                let #ident: #ty = match __map.remove(#name) {
+                    Some(value) => {
+                        let actual = __kind(&value);
+                        caustics::serde_json::from_value(value).map_err(|e| {
+                            caustics::CausticsError::invalid_field_type(#name, #type_name, format!("{} ({})", actual, e))
+                        })?
+                    }
+                    None => #missing,
+                };
+            }
+        })
+        .collect();
+
+    quote! {
+        caustics::__caustics_deserialize_only! {
+            impl TryFrom<caustics::serde_json::Value> for Model {
+                type Error = caustics::CausticsError;
+
+                fn try_from(value: caustics::serde_json::Value) -> Result<Self, Self::Error> {
+                    fn __kind(value: &caustics::serde_json::Value) -> &'static str {
+                        match value {
+                            caustics::serde_json::Value::Null => "null",
+                            caustics::serde_json::Value::Bool(_) => "boolean",
+                            caustics::serde_json::Value::Number(_) => "number",
+                            caustics::serde_json::Value::String(_) => "string",
+                            caustics::serde_json::Value::Array(_) => "array",
+                            caustics::serde_json::Value::Object(_) => "object",
+                        }
+                    }
+
+                    let mut __map = match value {
+                        caustics::serde_json::Value::Object(map) => map,
+                        other => {
+                            return Err(caustics::CausticsError::type_conversion_error(__kind(&other), #entity_name, other.to_string()));
+                        }
+                    };
+                    #(#reads)*
+                    Ok(Model { #(#idents,)* })
+                }
+            }
+        }
+    }
+}
+
+/// Generate `TryFrom<Model>` for the `Create` struct, for "duplicate this row" workflows.
+///
+/// Required fields are copied as-is and required relations point at the row's current foreign
+/// key(s). Optional fields are not copied and `_params` starts empty; an auto-generated primary
+/// key is never part of `Create`, so the copy gets a fresh one on insert. A foreign key whose
+/// value can't be used as a lookup key fails with `InvalidFieldType` naming the field.
+fn generate_create_from_model(
+    fields: &[&syn::Field],
+    required_fields: &[&&syn::Field],
+    create_relations: &[&super::Relation],
+    current_primary_key: &str,
+) -> TokenStream {
+    let required_idents: Vec<_> = required_fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Field has no identifier"))
+        .collect();
+
+    let relation_inits: Vec<_> = create_relations
+        .iter()
+        .map(|relation| {
+            let relation_ident = format_ident!("{}", relation.get_field_name());
+            let target_module = &relation.target;
+            let fk_idents: Vec<_> = if !relation.foreign_key_fields.is_empty() {
+                relation
+                    .foreign_key_fields
+                    .iter()
+                    .map(|f| format_ident!("{}", f.to_snake_case()))
+                    .collect()
+            } else {
+                vec![format_ident!("{}", relation.get_first_fk_column_name().to_snake_case())]
+            };
+            // Make sure each foreign key names a real field
+            for ident in &fk_idents {
+                find_field_and_extract_type_info(fields, &ident.to_string())
+                    .unwrap_or_else(|| panic!("Foreign key field {} not found", ident));
+            }
+
+            if relation.is_composite && relation.target_primary_key_fields.len() > 1 {
+                let composite_variant = format_ident!(
+                    "{}",
+                    relation
+                        .target_primary_key_fields
+                        .iter()
+                        .map(|field| field.to_pascal_case())
+                        .collect::<Vec<_>>()
+                        .join("And")
+                );
+                quote! {
+                    #relation_ident: #target_module::UniqueWhereParam::#composite_variant(#(model.#fk_idents.into()),*)
+                }
+            } else {
+                let primary_key_variant = if let Some(pk) = relation.target_primary_key_fields.first() {
+                    format_ident!("{}Equals", pk.to_pascal_case())
+                } else if let Some(pk) = &relation.primary_key_field {
+                    format_ident!("{}Equals", pk.to_pascal_case())
+                } else {
+                    format_ident!("{}Equals", current_primary_key.to_pascal_case())
+                };
+                let fk_ident = &fk_idents[0];
+                let fk_name = fk_ident.to_string();
+                quote! {
+                    #relation_ident: {
+                        let value = (&model.#fk_ident).to_sea_orm_value();
+                        let key = caustics::CausticsKey::from_db_value(&value).ok_or_else(|| {
+                            caustics::CausticsError::invalid_field_type(#fk_name, "a key value", format!("{:?}", value))
+                        })?;
+                        #target_module::UniqueWhereParam::#primary_key_variant(key)
+                    }
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        impl TryFrom<<Entity as sea_orm::EntityTrait>::Model> for Create {
+            type Error = caustics::CausticsError;
+
+            fn try_from(model: <Entity as sea_orm::EntityTrait>::Model) -> Result<Self, Self::Error> {
+                #[allow(unused_imports)]
+                use caustics::ToSeaOrmValue;
+                Ok(Create {
+                    #(#required_idents: model.#required_idents,)*
+                    #(#relation_inits,)*
+                    _params: Vec::new(),
+                })
+            }
+        }
+    }
+}
+
+/// Generate the Create-time assignment for a required belongs_to relation whose target has a
+/// composite primary key.
+///
+/// `where_param` evaluates to the target's `UniqueWhereParam`. Its composite variant is copied
+/// straight into the foreign key columns; any other unique lookup is resolved through a deferred lookup that returns a
+/// `CausticsKey::Composite` with one part per foreign key column.
+fn generate_composite_foreign_key_assign(
+    rel: &super::Relation,
+    fields: &[&syn::Field],
+    entity_name: &str,
+    where_param: TokenStream,
+) -> TokenStream {
+    let target_module = &rel.target;
+    let fk_idents: Vec<_> = rel
+        .foreign_key_fields
+        .iter()
+        .map(|field| format_ident!("{}", field.to_snake_case()))
+        .collect();
+    let fk_names: Vec<_> = rel
+        .foreign_key_fields
+        .iter()
+        .map(|field| field.to_snake_case())
+        .collect();
+    let target_pk_idents: Vec<_> = rel
+        .target_primary_key_fields
+        .iter()
+        .map(|field| format_ident!("{}", field.to_snake_case()))
+        .collect();
+    let target_pk_names: Vec<_> = rel
+        .target_primary_key_fields
+        .iter()
+        .map(|field| field.to_snake_case())
+        .collect();
+    let composite_variant = format_ident!(
+        "{}",
+        rel.target_primary_key_fields
+            .iter()
+            .map(|field| field.to_pascal_case())
+            .collect::<Vec<_>>()
+            .join("And")
+    );
+    let part_active_values: Vec<_> = rel
+        .foreign_key_fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let (is_optional, _, inner_ty) = find_field_and_extract_type_info(fields, field)
+                .expect("Foreign key field not found in fields");
+            let index = syn::Index::from(index);
+            foreign_key_active_value(
+                entity_name,
+                &field.to_snake_case(),
+                quote! { parts[#index].1.clone() },
+                inner_ty,
+                is_optional,
+            )
+        })
+        .collect();
+    let part_count = fk_idents.len();
+    let fk_label = rel
+        .foreign_key_fields
+        .iter()
+        .map(|field| field.to_snake_case())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let bindings: Vec<_> = (0..part_count)
+        .map(|i| format_ident!("part_{}", i))
+        .collect();
+
+    let param_mismatch = deferred_param_mismatch(target_module);
+    let resolve = |conn_ty: TokenStream| {
+        quote! {
+            |conn: &#conn_ty, param| {
+                let param = param.downcast_ref::<#target_module::UniqueWhereParam>().cloned();
+                Box::pin(async move {
+                    let Some(param) = param else {
+                        return Err(#param_mismatch.into());
+                    };
+                    let condition: sea_query::Condition = param.clone().into();
+                    let entity = #target_module::Entity::find()
+                        .filter::<sea_query::Condition>(condition)
+                        .one(conn)
+                        .await?
+                        .ok_or_else(|| caustics::CausticsError::NotFoundForCondition {
+                            entity: stringify!(#target_module).to_string(),
+                            condition: format!("{:?}", param),
+                        })?;
+                    use caustics::ToSeaOrmValue;
+                    Ok(caustics::CausticsKey::Composite(vec![
+                        #((
+                            #target_pk_names.to_string(),
+                            caustics::CausticsKey::try_from_db_value(
+                                #target_pk_names,
+                                &(&entity.#target_pk_idents).to_sea_orm_value(),
+                            )?,
+                        )),*
+                    ]))
+                })
+            }
+        }
+    };
+    let resolve_conn = resolve(quote! { sea_orm::DatabaseConnection });
+    let resolve_txn = resolve(quote! { sea_orm::DatabaseTransaction });
+
+    quote! {
+        match #where_param {
+            #target_module::UniqueWhereParam::#composite_variant(#(#bindings),*) => {
+                #(model.#fk_idents = sea_orm::ActiveValue::Set(#bindings.into());)*
+            }
+            other => {
+                deferred_lookups.push(caustics::DeferredLookup::new(
+                    Box::new(other.clone()),
+                    |model, value| {
+                        let Some(model) = model.downcast_mut::<ActiveModel>() else {
+                            return Err(caustics::CausticsError::DeferredLookupFailed {
+                                target: #entity_name.to_string(),
+                                detail: "assigned model is not this entity's ActiveModel".to_string(),
+                            }
+                            .into());
+                        };
+                        let Some(parts) = value.as_composite().filter(|parts| parts.len() == #part_count) else {
+                            return Err(caustics::CausticsError::InvalidFieldType {
+                                field: format!("{}.({})", #entity_name, #fk_label),
+                                expected: format!("a composite key with {} parts", #part_count),
+                                actual: value.to_string(),
+                            }
+                            .into());
+                        };
+                        #(model.#fk_idents = #part_active_values?;)*
+                        Ok(())
+                    },
+                    #resolve_conn,
+                    #resolve_txn,
+                ));
+            }
+        }
+    }
+}
+
+/// The error a deferred lookup reports when handed a unique param of another entity.
+fn deferred_param_mismatch(target_module: &syn::Path) -> TokenStream {
+    quote! {
+        caustics::CausticsError::DeferredLookupFailed {
+            target: stringify!(#target_module).to_string(),
+            detail: "unique param is not this entity's UniqueWhereParam".to_string(),
+        }
+    }
+}
+
+/// `fn() -> bool` telling whether the relation's foreign key column accepts NULL. For
+/// has_many/has_one the column lives on the target, so unless `nullable` overrides it the
+/// answer comes from the target's column definition.
+fn foreign_key_nullable_fn(rel: &super::Relation) -> TokenStream {
+    match (&rel.kind, rel.target_fk_is_optional, &rel.foreign_key_column) {
+        (RelationKind::BelongsTo, ..) => {
+            let nullable = rel.is_nullable;
+            quote! { || #nullable }
+        }
+        (_, Some(nullable), _) => quote! { || #nullable },
+        (_, None, Some(column)) => {
+            let target = &rel.target;
+            let column = format_ident!("{}", column);
+            quote! { || sea_orm::ColumnTrait::def(&#target::Column::#column).is_null() }
+        }
+        (_, None, None) => {
+            let nullable = rel.is_nullable;
+            quote! { || #nullable }
+        }
+    }
+}
+
+/// Fields on the current entity whose values form the key of a composite relation: the
+/// foreign key columns for belongs_to, the referenced local columns otherwise.
+fn composite_relation_key_fields(rel: &super::Relation) -> Vec<String> {
+    let fields = match rel.kind {
+        RelationKind::BelongsTo => &rel.foreign_key_fields,
+        RelationKind::HasMany | RelationKind::HasOne => &rel.target_primary_key_fields,
+    };
+    fields.iter().map(|field| field.to_snake_case()).collect()
+}
+
+/// Build the `get_foreign_key` closure of a composite relation, producing a
+/// `CausticsKey::Composite` (or `None` when any nullable part is unset).
+fn composite_relation_key_closure(rel: &super::Relation, fields: &[&syn::Field]) -> TokenStream {
+    let parts = composite_relation_key_fields(rel).into_iter().map(|field_name| {
+        let field_ident = format_ident!("{}", field_name);
+        let is_optional = find_field_and_extract_type_info(fields, &field_name)
+            .map(|(is_optional, _, _)| is_optional)
+            .unwrap_or(false);
+        let value = if is_optional {
+            quote! { model.#field_ident.as_ref()?.to_sea_orm_value() }
+        } else {
+            quote! { model.#field_ident.to_sea_orm_value() }
+        };
+        quote! {
+            (#field_name.to_string(), caustics::CausticsKey::from_db_value(&#value)?)
+        }
+    });
+    quote! {
+        |model| {
+            use caustics::ToSeaOrmValue;
+            Some(caustics::CausticsKey::Composite(vec![#(#parts),*]))
+        }
+    }
+}
+
+/// Generate the fetcher body for a relation joined on more than one column, e.g.
+/// `from = "(Column::BookTitle, Column::BookAuthorId)"`.
+///
+/// The foreign key arrives as a `CausticsKey::Composite` whose parts line up, in order,
+/// with the columns of the target entity. Results are returned as `Selected` values,
+/// which both relation descriptor flavours accept in `set_field`; `nest_optional_has_one`
+/// selects the `Option<Option<_>>` shape the `ModelWithRelations` descriptors expect.
+fn generate_composite_relation_fetcher(rel: &super::Relation, nest_optional_has_one: bool) -> TokenStream {
+    let target = &rel.target;
+    // Target-side columns matched against the composite key parts
+    let target_fields = match rel.kind {
+        RelationKind::BelongsTo => &rel.target_primary_key_fields,
+        RelationKind::HasMany | RelationKind::HasOne => &rel.foreign_key_fields,
+    };
+    let target_columns: Vec<_> = target_fields
+        .iter()
+        .map(|field| format_ident!("{}", field.to_pascal_case()))
+        .collect();
+    let part_count = target_columns.len();
+    let part_indices: Vec<_> = (0..part_count).map(syn::Index::from).collect();
+
+    let key_filter = quote! {
+        let parts = match fk_value.as_composite() {
+            Some(parts) if parts.len() == #part_count => parts,
+            _ => {
+                return Err(caustics::CausticsError::QueryValidation {
+                    message: format!(
+                        "relation '{}' is joined on {} columns but received key {}",
+                        relation_name, #part_count, fk_value
+                    ),
+                }
+                .into());
+            }
+        };
+        #(
+            query = query.filter(#target::Column::#target_columns.eq(parts[#part_indices].1.to_db_value()));
+        )*
+    };
+
+    let selected_aliases = quote! {
+        let selected_aliases: Vec<&str> = filter
+            .nested_select_aliases
+            .iter()
+            .flatten()
+            .map(|alias| alias.as_str())
+            .collect();
+    };
+
+    match rel.kind {
+        RelationKind::HasMany => quote! {
+            let Some(fk_value) = foreign_key_value else {
+                return Ok(Box::new(None::<Vec<#target::Selected>>) as Box<dyn std::any::Any + Send>);
+            };
+            let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref());
+            #key_filter
+
+            // Apply child-level filters from RelationFilter
+            for f in &filter.filters {
+                if let Some(col) = #target::column_from_str(&f.field) {
+                    use sea_orm::IntoSimpleExpr;
+                    let col_expr = Expr::expr(col.into_simple_expr());
+                    query = match &f.operation {
+                        caustics::FieldOp::Equals(v) => query.filter(col_expr.eq(v.clone())),
+                        caustics::FieldOp::NotEquals(v) => query.filter(col_expr.ne(v.clone())),
+                        caustics::FieldOp::Gt(v) => query.filter(col_expr.gt(v.clone())),
+                        caustics::FieldOp::Gte(v) => query.filter(col_expr.gte(v.clone())),
+                        caustics::FieldOp::Lt(v) => query.filter(col_expr.lt(v.clone())),
+                        caustics::FieldOp::Lte(v) => query.filter(col_expr.lte(v.clone())),
+                        caustics::FieldOp::InVec(vs) => query.filter(col_expr.is_in(vs.clone())),
+                        caustics::FieldOp::NotInVec(vs) => query.filter(col_expr.is_not_in(vs.clone())),
+                        caustics::FieldOp::Contains(_)
+                        | caustics::FieldOp::StartsWith(_)
+                        | caustics::FieldOp::EndsWith(_)
+                        | caustics::FieldOp::Like(_) => {
+                            let pattern = f.operation.like_pattern().expect("LIKE operation has a pattern");
+                            query.filter(col_expr.like(caustics::like_expr(pattern)))
+                        }
+                        caustics::FieldOp::ContainsAny(_) => {
+                            let patterns = f.operation.like_any_patterns().expect("contains_any has patterns");
+                            query.filter(caustics::like_any_expr(col.into_simple_expr(), patterns))
+                        }
+                        caustics::FieldOp::IsNull => query.filter(col_expr.is_null()),
+                        caustics::FieldOp::IsNotNull => query.filter(col_expr.is_not_null()),
+                        other => {
+                            return Err(caustics::CausticsError::QueryValidation {
+                                message: format!(
+                                    "relation '{}' cannot filter '{}' with {:?}",
+                                    relation_name, f.field, other
+                                ),
+                            }
+                            .into());
+                        }
+                    };
+                }
+            }
+
+            for (field, dir) in &filter.order_by {
+                if let Some(col) = #target::column_from_str(field) {
+                    let ord = match dir {
+                        caustics::SortOrder::Desc => sea_orm::Order::Desc,
+                        _ => sea_orm::Order::Asc,
+                    };
+                    query = query.order_by(col, ord);
+                }
+            }
+            if let Some(offset) = filter.skip { if offset > 0 { query = query.offset(offset as u64); } }
+            if let Some(limit) = filter.take { if limit >= 0 { query = query.limit(limit as u64); } }
+            if filter.distinct {
+                query = query.distinct();
+            }
+
+            #selected_aliases
+            let children = query
+                .all(conn)
+                .await?
+                .into_iter()
+                .map(|model| #target::Selected::from_model(model, &selected_aliases))
+                .collect::<Vec<_>>();
+            Ok(Box::new(Some(children)) as Box<dyn std::any::Any + Send>)
+        },
+        RelationKind::BelongsTo | RelationKind::HasOne => {
+            let is_optional_has_one = nest_optional_has_one
+                && matches!(rel.kind, RelationKind::HasOne)
+                && rel.target_fk_is_optional.unwrap_or(rel.is_nullable);
+            let wrap_result = if is_optional_has_one {
+                quote! { Some(with_rel) }
+            } else {
+                quote! { with_rel }
+            };
+            quote! {
+                let Some(fk_value) = foreign_key_value else {
+                    return Ok(Box::new(None::<Box<#target::Selected>>) as Box<dyn std::any::Any + Send>);
+                };
+                let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref());
+                #key_filter
+
+                #selected_aliases
+                let with_rel: Option<Box<#target::Selected>> = query
+                    .one(conn)
+                    .await?
+                    .map(|model| Box::new(#target::Selected::from_model(model, &selected_aliases)));
+                Ok(Box::new(#wrap_result) as Box<dyn std::any::Any + Send>)
+            }
+        }
+    }
+}
 
 #[allow(clippy::cmp_owned)]
 #[allow(clippy::type_complexity)]
@@ -128,6 +856,10 @@ pub fn generate_entity(
         for rel in &relations {
             let rel_name_snake = rel.name.to_snake_case();
             relation_names.push(quote! { #rel_name_snake });
+            if rel.is_composite {
+                relation_fetcher_bodies.push(generate_composite_relation_fetcher(rel, true));
+                continue;
+            }
             let target = &rel.target;
             let foreign_key_column = if !rel.foreign_key_columns.is_empty() {
                 validate_foreign_key_columns(
@@ -265,7 +997,7 @@ pub fn generate_entity(
             
             let fetcher_body = if matches!(rel.kind, RelationKind::HasMany) {
                 quote! {
-                let mut query = #target::Entity::find();
+                let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref());
                 if let Some(fk_value) = foreign_key_value {
                     if #rel_is_composite {
                         // Sophisticated composite foreign key handling
@@ -291,17 +1023,16 @@ pub fn generate_entity(
                                 caustics::FieldOp::NotEquals(v) => {
                                     cond = cond.add(Expr::expr(col_expr.clone()).ne(v.clone()));
                                 }
-                                caustics::FieldOp::Contains(s) => {
-                                    let pat = format!("%{}%", s);
-                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
-                                }
-                                caustics::FieldOp::StartsWith(s) => {
-                                    let pat = format!("{}%", s);
-                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
+                                caustics::FieldOp::Contains(_)
+                                | caustics::FieldOp::StartsWith(_)
+                                | caustics::FieldOp::EndsWith(_)
+                                | caustics::FieldOp::Like(_) => {
+                                    let pattern = f.operation.like_pattern().expect("LIKE operation has a pattern");
+                                    cond = cond.add(Expr::expr(col_expr.clone()).like(caustics::like_expr(pattern)));
                                 }
-                                caustics::FieldOp::EndsWith(s) => {
-                                    let pat = format!("%{}", s);
-                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
+                                caustics::FieldOp::ContainsAny(_) => {
+                                    let patterns = f.operation.like_any_patterns().expect("contains_any has patterns");
+                                    cond = cond.add(caustics::like_any_expr(col_expr.clone(), patterns));
                                 }
                                 caustics::FieldOp::IsNull => {
                                     cond = cond.add(Expr::expr(col_expr.clone()).is_null());
@@ -525,7 +1256,7 @@ pub fn generate_entity(
                     quote! {
                         if let Some(fk_value) = foreign_key_value {
                     let condition = #target_unique_param::#primary_key_variant(fk_value);
-                                let mut query = <#target_entity_type as EntityTrait>::find().filter::<sea_query::Condition>(condition.into());
+                                let mut query = #target::apply_row_scope(<#target_entity_type as EntityTrait>::find(), filter.scope.as_ref()).filter::<sea_query::Condition>(condition.into());
 
                                 // Check if field selection is being used
                                 let has_field_selection = filter.nested_select_aliases.as_ref()
@@ -616,14 +1347,13 @@ pub fn generate_entity(
                     
                     if is_optional {
                         quote! {
-                        let mut query = #target::Entity::find();
+                        let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref());
                         if let Some(fk_value) = foreign_key_value {
                             let value = fk_value.to_db_value();
-                            // Use raw SQL expression to bypass SeaORM's typed API
-                            query = query.filter(sea_query::Expr::cust_with_values(
-                                &format!("{} = ?", sea_orm::Iden::to_string(&#target::Column::#foreign_key_column_ident)),
-                                [value]
-                            ));
+                            // Table-qualified so schema-qualified targets resolve, bound as a plain value
+                            query = query.filter(
+                                sea_query::Expr::col((#target::Entity, #target::Column::#foreign_key_column_ident)).eq(value),
+                            );
                         }
 
                         // For has_one, we only want the first result
@@ -640,14 +1370,13 @@ pub fn generate_entity(
                         }
                     } else {
                         quote! {
-                        let mut query = #target::Entity::find();
+                        let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref());
                         if let Some(fk_value) = foreign_key_value {
                             let value = fk_value.to_db_value();
-                            // Use raw SQL expression to bypass SeaORM's typed API
-                            query = query.filter(sea_query::Expr::cust_with_values(
-                                &format!("{} = ?", sea_orm::Iden::to_string(&#target::Column::#foreign_key_column_ident)),
-                                [value]
-                            ));
+                            // Table-qualified so schema-qualified targets resolve, bound as a plain value
+                            query = query.filter(
+                                sea_query::Expr::col((#target::Entity, #target::Column::#foreign_key_column_ident)).eq(value),
+                            );
                         }
 
                         // For has_one, we only want the first result
@@ -663,7 +1392,7 @@ pub fn generate_entity(
                     quote! {
                     if let Some(fk_value) = foreign_key_value {
                             let condition = #target_unique_param::#primary_key_variant(fk_value);
-                            let mut query = <#target_entity_type as EntityTrait>::find().filter::<sea_query::Condition>(condition.into());
+                            let mut query = #target::apply_row_scope(<#target_entity_type as EntityTrait>::find(), filter.scope.as_ref()).filter::<sea_query::Condition>(condition.into());
 
                             // Check if field selection is being used
                             let has_field_selection = filter.nested_select_aliases.as_ref()
@@ -757,6 +1486,10 @@ pub fn generate_entity(
     // Handle empty relations gracefully for Selected types
     if !relations.is_empty() {
         for rel in &relations {
+            if rel.is_composite {
+                relation_fetcher_bodies_selected.push(generate_composite_relation_fetcher(rel, false));
+                continue;
+            }
             let rel_name_snake = rel.name.to_snake_case();
             let target = &rel.target;
             let foreign_key_column = if !rel.foreign_key_columns.is_empty() {
@@ -848,14 +1581,13 @@ pub fn generate_entity(
                 let is_has_one = matches!(rel.kind, RelationKind::HasOne);
 
                 quote! {
-                let mut query = #target::Entity::find();
+                let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref());
                 if let Some(fk_value) = foreign_key_value {
                     let value = fk_value.to_db_value();
-                    // Use raw SQL expression to bypass SeaORM's typed API
-                    query = query.filter(sea_query::Expr::cust_with_values(
-                        &format!("{} = ?", sea_orm::Iden::to_string(&#target::Column::#foreign_key_column_ident)),
-                        [value]
-                    ));
+                    // Table-qualified so schema-qualified targets resolve, bound as a plain value
+                    query = query.filter(
+                        sea_query::Expr::col((#target::Entity, #target::Column::#foreign_key_column_ident)).eq(value),
+                    );
                 }
                 use sea_orm::QueryTrait;
                 let query_sql = query.build(conn.get_database_backend());
@@ -879,17 +1611,16 @@ pub fn generate_entity(
                                 caustics::FieldOp::NotEquals(v) => {
                                     cond = cond.add(Expr::expr(col_expr.clone()).ne(v.clone()));
                                 }
-                                caustics::FieldOp::Contains(s) => {
-                                    let pat = format!("%{}%", s);
-                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
+                                caustics::FieldOp::Contains(_)
+                                | caustics::FieldOp::StartsWith(_)
+                                | caustics::FieldOp::EndsWith(_)
+                                | caustics::FieldOp::Like(_) => {
+                                    let pattern = f.operation.like_pattern().expect("LIKE operation has a pattern");
+                                    cond = cond.add(Expr::expr(col_expr.clone()).like(caustics::like_expr(pattern)));
                                 }
-                                caustics::FieldOp::StartsWith(s) => {
-                                    let pat = format!("{}%", s);
-                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
-                                }
-                                caustics::FieldOp::EndsWith(s) => {
-                                    let pat = format!("%{}", s);
-                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
+                                caustics::FieldOp::ContainsAny(_) => {
+                                    let patterns = f.operation.like_any_patterns().expect("contains_any has patterns");
+                                    cond = cond.add(caustics::like_any_expr(col_expr.clone(), patterns));
                                 }
                                 caustics::FieldOp::IsNull => {
                                     cond = cond.add(Expr::expr(col_expr.clone()).is_null());
@@ -1057,14 +1788,13 @@ pub fn generate_entity(
                 
                 if is_optional {
                     quote! {
-                    let mut query = #target::Entity::find();
+                    let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref());
                     if let Some(fk_value) = foreign_key_value {
                         let value = fk_value.to_db_value();
-                        // Use raw SQL expression to bypass SeaORM's typed API
-                        query = query.filter(sea_query::Expr::cust_with_values(
-                            &format!("{} = ?", sea_orm::Iden::to_string(&#target::Column::#foreign_key_column_ident)),
-                            [value]
-                        ));
+                        // Table-qualified so schema-qualified targets resolve, bound as a plain value
+                        query = query.filter(
+                            sea_query::Expr::col((#target::Entity, #target::Column::#foreign_key_column_ident)).eq(value),
+                        );
                     }
 
                     // For has_one, we only want the first result
@@ -1082,14 +1812,13 @@ pub fn generate_entity(
                     }
                 } else {
                     quote! {
-                    let mut query = #target::Entity::find();
+                    let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref());
                     if let Some(fk_value) = foreign_key_value {
                         let value = fk_value.to_db_value();
-                        // Use raw SQL expression to bypass SeaORM's typed API
-                        query = query.filter(sea_query::Expr::cust_with_values(
-                            &format!("{} = ?", sea_orm::Iden::to_string(&#target::Column::#foreign_key_column_ident)),
-                            [value]
-                        ));
+                        // Table-qualified so schema-qualified targets resolve, bound as a plain value
+                        query = query.filter(
+                            sea_query::Expr::col((#target::Entity, #target::Column::#foreign_key_column_ident)).eq(value),
+                        );
                     }
 
                     // For has_one, we only want the first result
@@ -1179,7 +1908,7 @@ pub fn generate_entity(
                                     #target_unique_param::#primary_key_variant(fk_value)
                                 };
                                 
-                                let mut query = <#target_entity_type as EntityTrait>::find().filter(condition);
+                                let mut query = #target::apply_row_scope(<#target_entity_type as EntityTrait>::find(), filter.scope.as_ref()).filter(condition);
 
                                 // Check if field selection is being used
                                 let has_field_selection = filter.nested_select_aliases.as_ref()
@@ -1289,7 +2018,7 @@ pub fn generate_entity(
                     quote! {
                             if let Some(fk_value) = foreign_key_value {
                                 let condition = #target_unique_param::#primary_key_variant(fk_value);
-                                let mut query = <#target_entity_type as EntityTrait>::find().filter::<sea_query::Condition>(condition.into());
+                                let mut query = #target::apply_row_scope(<#target_entity_type as EntityTrait>::find(), filter.scope.as_ref()).filter::<sea_query::Condition>(condition.into());
 
                     // Apply database-level field selection optimization
                     // For relation fetchers, we need all fields to properly construct the target entity
@@ -1494,7 +2223,8 @@ pub fn generate_entity(
     
     // Only non-nullable, non-foreign-key fields are required
     // Include primary key fields if they are not auto-increment or if we have composite keys
-    // Exclude fields marked with #[caustics(default)]
+    // Exclude fields marked with #[caustics(default)], and #[caustics(db_default)] fields that
+    // stay unset so the database fills them in
     let required_fields: Vec<_> = fields
         .iter()
         .filter(|field| {
@@ -1507,11 +2237,12 @@ pub fn generate_entity(
             let is_primary_key = primary_key_fields.contains(field);
             let is_auto_increment = is_primary_key && crate::primary_key::is_auto_increment_field_impl(field);
             let has_caustics_default = crate::primary_key::has_caustics_default_attr(field);
+            let has_db_default = crate::primary_key::has_caustics_db_default_attr(field);
             
             let is_foreign_key = foreign_key_fields.contains(&field_name);
             
-            let should_include = if has_caustics_default {
-                // Fields marked with #[caustics(default)] should be excluded from Create struct
+            if has_caustics_default || has_db_default {
+                // Fields marked with #[caustics(default)] or #[caustics(db_default)] should be excluded from Create struct
                 false
             } else if is_primary_key {
                 // For primary keys, include them if they are not auto-increment
@@ -1523,9 +2254,7 @@ pub fn generate_entity(
             } else {
                 // For regular fields, include them if they are not nullable
                 !is_option(&field.ty)
-            };
-            
-            should_include
+            }
         })
         .collect();
 
@@ -1576,21 +2305,23 @@ pub fn generate_entity(
         for pk_info in &all_primary_key_info {
             let field_ident = pk_info.field_ident();
             let field_name = pk_info.field_name();
+            let field_label = format!("{}.{}", entity_name, field_name);
             let key_part = quote! {
-                (#field_name.to_string(), caustics::CausticsKey::from_db_value(&(&m.#field_ident).to_sea_orm_value()).unwrap_or_else(|| caustics::CausticsKey::I32(0)))
+                (#field_name.to_string(), caustics::CausticsKey::try_from_db_value(#field_label, &(&m.#field_ident).to_sea_orm_value())?)
             };
             key_parts.push(key_part);
         }
         
         quote! {
             let key_parts = vec![#(#key_parts),*];
-            caustics::CausticsKey::Composite(key_parts)
+            Ok(caustics::CausticsKey::Composite(key_parts))
         }
     } else {
         // For single primary keys, use the existing logic
+        let field_label = format!("{}.{}", entity_name, current_primary_key);
         quote! {
             let val = (&m.#current_primary_key_ident).to_sea_orm_value();
-            caustics::CausticsKey::from_db_value(&val).unwrap_or_else(|| caustics::CausticsKey::I32(0))
+            caustics::CausticsKey::try_from_db_value(#field_label, &val)
         }
     };
 
@@ -1733,6 +2464,24 @@ pub fn generate_entity(
             }
         })
         .collect::<Vec<_>>();
+    // Required belongs_to relations, taken by the Create struct as target unique lookups
+    let create_relations: Vec<_> = relations
+        .iter()
+        .filter(|relation| {
+            matches!(relation.kind, RelationKind::BelongsTo)
+                && (!relation.foreign_key_fields.is_empty() || relation.foreign_key_field.is_some())
+                && fields
+                    .iter()
+                    .find(|f| f.ident.as_ref().expect("Field has no identifier") == &relation.get_first_fk_column_name().to_snake_case())
+                    .is_some_and(|field| !is_option(&field.ty) && !primary_key_fields.contains(&field))
+        })
+        .collect();
+    let create_deserialize_impl =
+        generate_create_deserialize(&fields, &required_fields, &create_relations, &current_primary_key);
+    let create_from_model_impl =
+        generate_create_from_model(&fields, &required_fields, &create_relations, &current_primary_key);
+    let model_try_from_json_impl = generate_model_try_from_json(&fields, &entity_name);
+
     // Generate foreign key relation function arguments
     let foreign_key_relation_args = relations
         .iter()
@@ -1878,6 +2627,15 @@ pub fn generate_entity(
             }
         })
         .map(|relation| {
+            if relation.is_composite && relation.target_primary_key_fields.len() > 1 {
+                let relation_name = format_ident!("{}", relation.get_field_name());
+                return generate_composite_foreign_key_assign(
+                    relation,
+                    &fields,
+                    entity_context.registry_name(),
+                    quote! { self.#relation_name },
+                );
+            }
             let fk_field = relation.get_first_fk_column_name();
             let fk_field_snake = fk_field.to_snake_case();
             let fk_field_ident = format_ident!("{}", fk_field_snake);
@@ -1891,9 +2649,12 @@ pub fn generate_entity(
             let is_nullable = relation.is_nullable;
             
             // Get the field type for the foreign key and check if it's optional
-            let (is_fk_optional, fk_field_type, fk_field_type_inner) = find_field_and_extract_type_info(&fields, &foreign_key_field_name)
+            let (is_fk_optional, _fk_field_type, fk_field_type_inner) = find_field_and_extract_type_info(&fields, &foreign_key_field_name)
                 .expect("Foreign key field not found in fields");
 
+            let key_active_value = foreign_key_active_value(entity_name, &foreign_key_field_name_snake, quote! { key }, fk_field_type_inner, is_fk_optional);
+            let value_active_value = foreign_key_active_value(entity_name, &foreign_key_field_name_snake, quote! { value }, fk_field_type_inner, is_fk_optional);
+
             // Get the primary key field name from the relation definition or use dynamic detection
             // For belongs_to relations, we need the primary key of the TARGET entity, not the current entity
             let primary_key_variant = if relation.is_composite && !relation.target_primary_key_fields.is_empty() {
@@ -1919,46 +2680,18 @@ pub fn generate_entity(
             };
             let primary_key_field_name = relation.primary_key_field.clone().unwrap_or_else(|| "id".to_string());
             let primary_key_field_ident = format_ident!("{}", primary_key_field_name.to_snake_case());
+            let param_mismatch = deferred_param_mismatch(target_module);
 
-            let (conversion_call_key, downcast_type_key) = if is_fk_optional {
-                (
-                    quote! {
-                        let active_value_boxed = crate::__caustics_convert_key_to_active_value_optional(#entity_name, #foreign_key_field_name_snake, key);
-                    },
-                    quote! { sea_orm::ActiveValue<Option<#fk_field_type_inner>> }
-                )
-            } else {
-                (
-                    quote! {
-                        let active_value_boxed = crate::__caustics_convert_key_to_active_value(#entity_name, #foreign_key_field_name_snake, key);
-                    },
-                    quote! { sea_orm::ActiveValue<#fk_field_type> }
-                )
-            };
-            
-            let (conversion_call_value, downcast_type_value) = if is_fk_optional {
-                (
-                    quote! {
-                        let active_value_boxed = crate::__caustics_convert_key_to_active_value_optional(#entity_name, #foreign_key_field_name_snake, value);
-                    },
-                    quote! { sea_orm::ActiveValue<Option<#fk_field_type_inner>> }
-                )
-            } else {
-                (
-                    quote! {
-                        let active_value_boxed = crate::__caustics_convert_key_to_active_value(#entity_name, #foreign_key_field_name_snake, value);
-                    },
-                    quote! { sea_orm::ActiveValue<#fk_field_type> }
-                )
-            };
-            
             quote! {
                 // Handle foreign key value from UniqueWhereParam
                 match self.#relation_name {
                     #target_module::UniqueWhereParam::#primary_key_variant(key) => {
-                        // Extract the value from CausticsKey for database field assignment
-                        #conversion_call_key
-                        model.#fk_field_ident = *active_value_boxed.downcast::<#downcast_type_key>().expect("Failed to downcast to ActiveValue");
+                        // Extract the value from CausticsKey for database field assignment;
+                        // a key of the wrong type fails the create when it runs
+                        match #key_active_value {
+                            Ok(value) => model.#fk_field_ident = value,
+                            Err(error) => deferred_lookups.push(caustics::DeferredLookup::failed(error)),
+                        }
                     }
                     other => {
                         // For complex foreign key resolution, we need to add to deferred lookups
@@ -1967,56 +2700,54 @@ pub fn generate_entity(
                             Box::new(other.clone()),
                             |model, value| {
                                 let Some(model) = model.downcast_mut::<ActiveModel>() else {
-                                    panic!("SetParam relation assign: ActiveModel type mismatch");
+                                    return Err(caustics::CausticsError::DeferredLookupFailed {
+                                        target: #entity_name.to_string(),
+                                        detail: "assigned model is not this entity's ActiveModel".to_string(),
+                                    }
+                                    .into());
                                 };
                                 // Extract the value from CausticsKey for database field assignment
-                                #conversion_call_value
-                                model.#fk_field_ident = *active_value_boxed.downcast::<#downcast_type_value>().expect("Failed to downcast to ActiveValue");
+                                model.#fk_field_ident = #value_active_value?;
+                                Ok(())
                             },
                             |conn: & sea_orm::DatabaseConnection, param| {
-                                let Some(param) = param.downcast_ref::<#target_module::UniqueWhereParam>() else {
-                                    panic!("Deferred FK: UniqueWhereParam type mismatch");
-                                };
-                                let param = param.clone();
+                                let param = param.downcast_ref::<#target_module::UniqueWhereParam>().cloned();
                                 Box::pin(async move {
+                                    let Some(param) = param else {
+                                        return Err(#param_mismatch.into());
+                                    };
                                     let condition: sea_query::Condition = param.clone().into();
-                                    let result = #target_module::Entity::find()
-                                        .filter::<sea_query::Condition>(condition)
-                                        .one(conn)
-                                        .await?;
-                                    result.map(|entity| {
-                                        use caustics::ToSeaOrmValue;
-                                        let val = (&entity.#primary_key_field_ident).to_sea_orm_value();
-                                        caustics::CausticsKey::from_db_value(&val).unwrap_or_else(|| caustics::CausticsKey::I32(0))
-                                    }).ok_or_else(|| {
-                                        caustics::CausticsError::NotFoundForCondition {
+                                    let entity = #target_module::Entity::find()
+                                        .filter::<sea_query::Condition>(condition)
+                                        .one(conn)
+                                        .await?
+                                        .ok_or_else(|| caustics::CausticsError::NotFoundForCondition {
                                             entity: stringify!(#target_module).to_string(),
                                             condition: format!("{:?}", param),
-                                        }.into()
-                                    })
+                                        })?;
+                                    use caustics::ToSeaOrmValue;
+                                    let val = (&entity.#primary_key_field_ident).to_sea_orm_value();
+                                    caustics::CausticsKey::try_from_db_value(#primary_key_field_name, &val).map_err(Into::into)
                                 })
                             },
                             |txn: & sea_orm::DatabaseTransaction, param| {
-                                let Some(param) = param.downcast_ref::<#target_module::UniqueWhereParam>() else {
-                                    panic!("Deferred FK: UniqueWhereParam type mismatch");
-                                };
-                                let param = param.clone();
+                                let param = param.downcast_ref::<#target_module::UniqueWhereParam>().cloned();
                                 Box::pin(async move {
+                                    let Some(param) = param else {
+                                        return Err(#param_mismatch.into());
+                                    };
                                     let condition: sea_query::Condition = param.clone().into();
-                                    let result = #target_module::Entity::find()
+                                    let entity = #target_module::Entity::find()
                                         .filter::<sea_query::Condition>(condition)
                                         .one(txn)
-                                        .await?;
-                                    result.map(|entity| {
-                                        use caustics::ToSeaOrmValue;
-                                        let val = (&entity.#primary_key_field_ident).to_sea_orm_value();
-                                        caustics::CausticsKey::from_db_value(&val).unwrap_or_else(|| caustics::CausticsKey::I32(0))
-                                    }).ok_or_else(|| {
-                                        caustics::CausticsError::NotFoundForCondition {
+                                        .await?
+                                        .ok_or_else(|| caustics::CausticsError::NotFoundForCondition {
                                             entity: stringify!(#target_module).to_string(),
                                             condition: format!("{:?}", param),
-                                        }.into()
-                                    })
+                                        })?;
+                                    use caustics::ToSeaOrmValue;
+                                    let val = (&entity.#primary_key_field_ident).to_sea_orm_value();
+                                    caustics::CausticsKey::try_from_db_value(#primary_key_field_name, &val).map_err(Into::into)
                                 })
                             },
                         ));
@@ -2039,6 +2770,17 @@ pub fn generate_entity(
         })
         .collect::<Vec<_>>();
 
+    // Column-to-column copy variants for SetParam enum (`<Field>FromColumn`)
+    let from_column_variants: Vec<_> = fields
+        .iter()
+        .filter(|field| !primary_key_fields.contains(field))
+        .map(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier");
+            let from_column_name = format_ident!("{}FromColumn", name.to_string().to_pascal_case());
+            quote! { #from_column_name(<Entity as EntityTrait>::Column) }
+        })
+        .collect();
+
     // Generate atomic operation variants for SetParam enum (for numeric fields only)
     let atomic_variants: Vec<_> = fields
         .iter()
@@ -2069,6 +2811,45 @@ pub fn generate_entity(
                     quote! { #multiply_name(#inner_ty) },
                     quote! { #divide_name(#inner_ty) },
                 ])
+            } else if matches!(
+                field_type,
+                crate::where_param::FieldType::String | crate::where_param::FieldType::OptionString
+            ) {
+                let append_name = format_ident!("{}Append", pascal_name);
+                let prepend_name = format_ident!("{}Prepend", pascal_name);
+
+                Some(vec![
+                    quote! { #append_name(String) },
+                    quote! { #prepend_name(String) },
+                ])
+            } else if matches!(
+                field_type,
+                crate::where_param::FieldType::Json | crate::where_param::FieldType::OptionJson
+            ) {
+                let merge_name = format_ident!("{}Merge", pascal_name);
+                let remove_key_name = format_ident!("{}RemoveKey", pascal_name);
+
+                let push_name = format_ident!("{}Push", pascal_name);
+                let remove_element_name = format_ident!("{}RemoveElement", pascal_name);
+
+                Some(vec![
+                    quote! { #merge_name(caustics::serde_json::Value) },
+                    quote! { #remove_key_name(Vec<String>) },
+                    quote! { #push_name(caustics::serde_json::Value) },
+                    quote! { #remove_element_name(caustics::serde_json::Value) },
+                ])
+            } else if matches!(
+                field_type,
+                crate::where_param::FieldType::Vec | crate::where_param::FieldType::OptionVec
+            ) {
+                let element_ty = crate::common::extract_inner_type_from_vec(ty);
+                let push_name = format_ident!("{}Push", pascal_name);
+                let remove_element_name = format_ident!("{}RemoveElement", pascal_name);
+
+                Some(vec![
+                    quote! { #push_name(#element_ty) },
+                    quote! { #remove_element_name(#element_ty) },
+                ])
             } else {
                 None
             }
@@ -2143,8 +2924,32 @@ pub fn generate_entity(
         })
         .collect::<Vec<_>>();
 
+    // Generate has_many disconnect variants (only when the target's foreign key is optional)
+    let has_many_disconnect_variants = relations
+        .iter()
+        .filter(|relation| {
+            // Whether the target's foreign key is optional is checked against its column when run
+            matches!(relation.kind, RelationKind::HasMany) && relation.target_fk_is_optional != Some(false)
+        })
+        .map(|relation| {
+            let relation_name = format_ident!("Disconnect{}", relation.name.to_pascal_case());
+            (relation.name.clone(), relation_name, relation.target.clone())
+        })
+        .collect::<Vec<_>>();
+
+    // has_one connect replaces the current child, so it runs with the relation operations
+    let has_one_connect_variants = relations
+        .iter()
+        .filter(|relation| matches!(relation.kind, RelationKind::HasOne))
+        .map(|relation| {
+            let relation_name = format_ident!("Connect{}", relation.name.to_pascal_case());
+            (relation.get_field_name(), relation_name, relation.target.clone())
+        })
+        .collect::<Vec<_>>();
+
     let has_many_set_variant_tokens = has_many_set_variants
         .iter()
+        .chain(has_many_disconnect_variants.iter())
         .map(|(_, relation_name, target_module)| {
             quote! {
                 #relation_name(Vec<#target_module::UniqueWhereParam>)
@@ -2274,12 +3079,12 @@ pub fn generate_entity(
                                 let lookups: Vec<_> = child_lookups.iter().collect();
                                 for lookup in lookups {
                                     let v = (lookup.resolve_on_conn)(conn, &*lookup.unique_param).await?;
-                                    (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v);
+                                    (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v)?;
                                 }
                                 // Set the foreign key to the parent id before insert
                                 child_am.set(<#target_module::Entity as sea_orm::EntityTrait>::Column::#fk_col_ident_pascal, parent_id.to_db_value());
                                 let inserted_child = child_am.insert(conn).await?;
-                                let child_id = #target_module::__extract_id(&inserted_child);
+                                let child_id = #target_module::__extract_id(&inserted_child)?;
                                 for op in child_post_ops {
                                     (op.run_on_conn)(conn, child_id.clone()).await?;
                                 }
@@ -2303,12 +3108,12 @@ pub fn generate_entity(
                                 let lookups: Vec<_> = child_lookups.iter().collect();
                                 for lookup in lookups {
                                     let v = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
-                                    (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v);
+                                    (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v)?;
                                 }
                                 // Set the foreign key to the parent id before insert
                                 child_am.set(<#target_module::Entity as sea_orm::EntityTrait>::Column::#fk_col_ident_pascal, parent_id.to_db_value());
                                 let inserted_child = child_am.insert(txn).await?;
-                                let child_id = #target_module::__extract_id(&inserted_child);
+                                let child_id = #target_module::__extract_id(&inserted_child)?;
                                 for op in child_post_ops {
                                     (op.run_on_txn)(txn, child_id.clone()).await?;
                                 }
@@ -2346,12 +3151,12 @@ pub fn generate_entity(
                                 let lookups: Vec<_> = child_lookups.iter().collect();
                                 for lookup in lookups {
                                     let v = (lookup.resolve_on_conn)(conn, &*lookup.unique_param).await?;
-                                    (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v);
+                                    (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v)?;
                                 }
                                 // Set the foreign key to the parent id before insert
                                 child_am.set(<#target_module::Entity as sea_orm::EntityTrait>::Column::#fk_col_ident_pascal, parent_id.to_db_value());
                                 let inserted_child = child_am.insert(conn).await?;
-                                let child_id = #target_module::__extract_id(&inserted_child);
+                                let child_id = #target_module::__extract_id(&inserted_child)?;
                                 for op in child_post_ops {
                                     (op.run_on_conn)(conn, child_id.clone()).await?;
                                 }
@@ -2375,12 +3180,12 @@ pub fn generate_entity(
                                 let lookups: Vec<_> = child_lookups.iter().collect();
                                 for lookup in lookups {
                                     let v = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
-                                    (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v);
+                                    (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v)?;
                                 }
                                 // Set the foreign key to the parent id before insert
                                 child_am.set(<#target_module::Entity as sea_orm::EntityTrait>::Column::#fk_col_ident_pascal, parent_id.to_db_value());
                                 let inserted_child = child_am.insert(txn).await?;
-                                let child_id = #target_module::__extract_id(&inserted_child);
+                                let child_id = #target_module::__extract_id(&inserted_child)?;
                                 for op in child_post_ops {
                                     (op.run_on_txn)(txn, child_id.clone()).await?;
                                 }
@@ -2422,11 +3227,11 @@ pub fn generate_entity(
                         let lookups: Vec<_> = child_lookups.iter().collect();
                         for lookup in lookups {
                             let v = (lookup.resolve_on_conn)(conn, &*lookup.unique_param).await?;
-                            (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v);
+                            (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v)?;
                         }
                         child_am.set(<#target_module::Entity as sea_orm::EntityTrait>::Column::#fk_col_ident_pascal, parent_id.to_db_value());
                         let inserted_child = child_am.insert(conn).await?;
-                        let child_id = #target_module::__extract_id(&inserted_child);
+                        let child_id = #target_module::__extract_id(&inserted_child)?;
                         for op in child_post_ops { (op.run_on_conn)(conn, child_id.clone()).await?; }
                     }
                     Ok(())
@@ -2447,11 +3252,11 @@ pub fn generate_entity(
                         let lookups: Vec<_> = child_lookups.iter().collect();
                         for lookup in lookups {
                             let v = (lookup.resolve_on_conn)(conn, &*lookup.unique_param).await?;
-                            (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v);
+                            (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v)?;
                         }
                         child_am.set(<#target_module::Entity as sea_orm::EntityTrait>::Column::#fk_col_ident_pascal, parent_id.to_db_value());
                         let inserted_child = child_am.insert(conn).await?;
-                        let child_id = #target_module::__extract_id(&inserted_child);
+                        let child_id = #target_module::__extract_id(&inserted_child)?;
                         for op in child_post_ops { (op.run_on_conn)(conn, child_id.clone()).await?; }
                     }
                     Ok(())
@@ -2472,12 +3277,12 @@ pub fn generate_entity(
                         let lookups: Vec<_> = child_lookups.iter().collect();
                         for lookup in lookups {
                             let v = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
-                            (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v);
+                            (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v)?;
                         }
                         // Use parent_id directly with to_db_value()
                         child_am.set(<#target_module::Entity as sea_orm::EntityTrait>::Column::#fk_col_ident_pascal, parent_id.to_db_value());
                         let inserted_child = child_am.insert(txn).await?;
-                        let child_id = #target_module::__extract_id(&inserted_child);
+                        let child_id = #target_module::__extract_id(&inserted_child)?;
                         for op in child_post_ops { (op.run_on_txn)(txn, child_id.clone()).await?; }
                     }
                     Ok(())
@@ -2497,12 +3302,12 @@ pub fn generate_entity(
                         let lookups: Vec<_> = child_lookups.iter().collect();
                         for lookup in lookups {
                             let v = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
-                            (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v);
+                            (lookup.assign)(&mut child_am as &mut (dyn std::any::Any + 'static), v)?;
                         }
                         // Use parent_id directly with to_db_value()
                         child_am.set(<#target_module::Entity as sea_orm::EntityTrait>::Column::#fk_col_ident_pascal, parent_id.to_db_value());
                         let inserted_child = child_am.insert(txn).await?;
-                        let child_id = #target_module::__extract_id(&inserted_child);
+                        let child_id = #target_module::__extract_id(&inserted_child)?;
                         for op in child_post_ops { (op.run_on_txn)(txn, child_id.clone()).await?; }
                     }
                     Ok(())
@@ -2511,11 +3316,27 @@ pub fn generate_entity(
         })
         .collect();
 
+    // Per-field comparisons for `diff`, skipping primary keys (they identify the row)
+    let diff_checks = fields
+        .iter()
+        .filter(|field| !primary_key_fields.contains(field))
+        .map(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier");
+            let pascal_name = format_ident!("{}", name.to_string().to_pascal_case());
+            quote! {
+                if old.#name != new.#name {
+                    changes.push(SetParam::#pascal_name(sea_orm::ActiveValue::Set(new.#name.clone())));
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
     // Combine all SetParam variants as a flat Vec
     let all_set_param_variants: Vec<_> = field_variants
         .clone()
         .into_iter()
         .chain(atomic_variants)
+        .chain(from_column_variants)
         .chain(relation_connect_variants)
         .chain(relation_disconnect_variants)
         .chain(has_many_set_variant_tokens)
@@ -2534,6 +3355,23 @@ pub fn generate_entity(
         entity_context.registry_name(),
     );
 
+    // Non-key unique String fields also get a case-insensitive lookup (`<Field>EqualsInsensitive`)
+    let insensitive_unique_fields: Vec<_> = unique_fields
+        .iter()
+        .filter(|field| !primary_key_fields.contains(field))
+        .filter(|field| {
+            matches!(
+                crate::where_param::detect_field_type(&field.ty),
+                crate::where_param::FieldType::String
+            )
+        })
+        .map(|field| format_ident!("{}", field.ident.as_ref().unwrap().to_string().to_pascal_case()))
+        .collect();
+    let insensitive_variants: Vec<_> = insensitive_unique_fields
+        .iter()
+        .map(|pascal_name| format_ident!("{}EqualsInsensitive", pascal_name))
+        .collect();
+
     // Generate match arms for UniqueWhereParam
     let mut unique_where_match_arms = unique_fields
         .iter()
@@ -2611,6 +3449,66 @@ pub fn generate_entity(
         
         unique_where_match_arms.push(composite_match_arm);
     }
+    unique_where_match_arms.extend(insensitive_unique_fields.iter().zip(&insensitive_variants).map(
+        |(pascal_name, variant)| {
+            quote! {
+                UniqueWhereParam::#variant(value) => Condition::all().add(
+                    sea_query::Expr::expr(sea_query::Func::lower(sea_query::Expr::col(<Entity as EntityTrait>::Column::#pascal_name)))
+                        .eq(sea_query::Func::lower(sea_query::Expr::val(value)))
+                )
+            }
+        },
+    ));
+
+    // Generate match arms mapping UniqueWhereParam to the columns of its unique constraint.
+    // A single part of a composite primary key is not unique on its own.
+    let mut unique_columns_match_arms = unique_fields
+        .iter()
+        .map(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier");
+            let pascal_name = format_ident!("{}", name.to_string().to_pascal_case());
+            let equals_variant = format_ident!("{}Equals", pascal_name);
+            if has_composite_pk && primary_key_fields.contains(&field) {
+                quote! { UniqueWhereParam::#equals_variant(..) => Vec::new() }
+            } else {
+                quote! { UniqueWhereParam::#equals_variant(..) => vec![<Entity as EntityTrait>::Column::#pascal_name] }
+            }
+        })
+        .collect::<Vec<_>>();
+    if has_composite_pk {
+        let all_primary_key_info = crate::primary_key::extract_all_primary_key_info(&fields);
+        let composite_variant_ident = format_ident!(
+            "{}",
+            all_primary_key_info
+                .iter()
+                .map(|info| info.field_name().to_pascal_case())
+                .collect::<Vec<_>>()
+                .join("And")
+        );
+        let pk_columns: Vec<_> = all_primary_key_info
+            .iter()
+            .map(|info| format_ident!("{}", info.field_name().to_pascal_case()))
+            .collect();
+        unique_columns_match_arms.push(quote! {
+            UniqueWhereParam::#composite_variant_ident(..) => vec![#(<Entity as EntityTrait>::Column::#pk_columns),*]
+        });
+    }
+    // A plain unique index does not cover LOWER(col), so insensitive lookups never target one
+    unique_columns_match_arms.extend(insensitive_variants.iter().map(|variant| {
+        quote! { UniqueWhereParam::#variant(..) => Vec::new() }
+    }));
+
+    // Generate match arms reading the primary key out of a UniqueWhereParam; other unique
+    // lookups (and a single part of a composite key) don't identify the row by its key
+    let unique_primary_key_match_arms = unique_fields
+        .iter()
+        .filter(|field| !has_composite_pk && primary_key_fields.contains(field))
+        .map(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier");
+            let equals_variant = format_ident!("{}Equals", name.to_string().to_pascal_case());
+            quote! { UniqueWhereParam::#equals_variant(key) => Some(key.clone()) }
+        })
+        .collect::<Vec<_>>();
 
     // Generate match arms to convert UniqueWhereParam into a cursor (expr, value)
     // Each arm evaluates to a new builder (Self)
@@ -2708,6 +3606,33 @@ pub fn generate_entity(
         
         unique_cursor_match_arms.push(composite_cursor_arm);
     }
+    unique_cursor_match_arms.extend(insensitive_unique_fields.iter().zip(&insensitive_variants).map(
+        |(pascal_name, variant)| {
+            quote! {
+                UniqueWhereParam::#variant(value) => {
+                    let expr = sea_query::SimpleExpr::FunctionCall(sea_query::Func::lower(
+                        <Entity as EntityTrait>::Column::#pascal_name.into_simple_expr(),
+                    ));
+                    self.with_cursor(expr, sea_orm::Value::from(value.to_lowercase()))
+                },
+            }
+        },
+    ));
+
+    // Cursor parts read from a fetched row, one per primary key column, for pagination
+    let row_cursor_parts = primary_key_fields
+        .iter()
+        .map(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier");
+            let pascal_name = format_ident!("{}", name.to_string().to_pascal_case());
+            quote! {
+                (
+                    <Entity as EntityTrait>::Column::#pascal_name.into_simple_expr(),
+                    sea_orm::Value::from(row.#name.clone()),
+                )
+            }
+        })
+        .collect::<Vec<_>>();
 
     // Generate parallel lists of equals-variants and their columns for Into<(expr, value)>
     let unique_where_equals_variants = unique_fields
@@ -2814,6 +3739,18 @@ pub fn generate_entity(
         
         unique_where_to_expr_value_arms.push(composite_expr_value_arm);
     }
+    unique_where_to_expr_value_arms.extend(insensitive_unique_fields.iter().zip(&insensitive_variants).map(
+        |(pascal_name, variant)| {
+            quote! {
+                UniqueWhereParam::#variant(value) => {
+                    let expr = sea_query::SimpleExpr::FunctionCall(sea_query::Func::lower(
+                        <Entity as EntityTrait>::Column::#pascal_name.into_simple_expr(),
+                    ));
+                    (expr, sea_orm::Value::from(value.to_lowercase()))
+                }
+            }
+        },
+    ));
 
     // Generate field variants for OrderByParam enum (all fields)
     let order_by_field_variants = fields
@@ -2870,6 +3807,35 @@ pub fn generate_entity(
         })
         .collect::<Vec<_>>();
 
+    // Non-optional field types, for reading `count_by` group keys
+    let count_by_key_types: Vec<_> = fields
+        .iter()
+        .map(|field| extract_field_type_info(field).2.clone())
+        .collect();
+    let count_by_field_names: Vec<String> = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Field has no identifier").to_string())
+        .collect();
+
+    // Numeric fields summarized by `profile()`
+    let (profile_field_names, profile_field_variants): (Vec<String>, Vec<syn::Ident>) = fields
+        .iter()
+        .filter(|field| {
+            matches!(
+                crate::where_param::detect_field_type(&field.ty),
+                crate::where_param::FieldType::Integer
+                    | crate::where_param::FieldType::OptionInteger
+                    | crate::where_param::FieldType::Float
+                    | crate::where_param::FieldType::OptionFloat
+            )
+        })
+        .map(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier").to_string();
+            let variant = format_ident!("{}", name.to_pascal_case());
+            (name, variant)
+        })
+        .unzip();
+
     // Generate snake_case function idents for per-entity select helpers
     let snake_field_fn_idents = fields
         .iter()
@@ -2947,7 +3913,7 @@ pub fn generate_entity(
                     WhereParam::#pascal_name(op) => {
                         let field = ToString::to_string(&#field_name_lit);
                         let operation = match op {
-                            caustics::FieldOp::Some(_) | caustics::FieldOp::Every(_) | caustics::FieldOp::None(_) => {
+                            caustics::FieldOp::Some(_) | caustics::FieldOp::Every(_) | caustics::FieldOp::None(_) | caustics::FieldOp::Count(_) => {
                                 // These operations are not supported in this context
                                 continue;
                             },
@@ -2961,7 +3927,7 @@ pub fn generate_entity(
                     WhereParam::#pascal_name(op) => {
                         let field = ToString::to_string(&#field_name_lit);
                         let operation = match op {
-                            caustics::FieldOp::Some(_) | caustics::FieldOp::Every(_) | caustics::FieldOp::None(_) => {
+                            caustics::FieldOp::Some(_) | caustics::FieldOp::Every(_) | caustics::FieldOp::None(_) | caustics::FieldOp::Count(_) => {
                                 // These operations are not supported in this context
                                 continue;
                             },
@@ -3024,6 +3990,7 @@ pub fn generate_entity(
         
         unique_where_variants.push(composite_variant);
     }
+    unique_where_variants.extend(insensitive_variants.iter().map(|variant| quote! { #variant(String) }));
 
     // Generate all unique field variant id idents (e.g., IdEquals, EmailEquals)
     let unique_where_variant_idents: Vec<_> = unique_fields
@@ -3045,6 +4012,41 @@ pub fn generate_entity(
 
     // Generate relation submodules
     let relation_submodules = generate_relation_submodules(&relations, &fields);
+    let polymorphic_code = generate_polymorphic_code(&extract_polymorphic_relations(&fields)?);
+    // Composite keys keep the trait's default, which reports that they can't be looked up
+    let fetch_by_primary_keys_fn = if crate::primary_key::has_composite_primary_key(&fields) {
+        quote! {}
+    } else {
+        let primary_key_variant = format_ident!("{}Equals", current_primary_key.to_pascal_case());
+        let primary_key_ident = format_ident!("{}", current_primary_key);
+        quote! {
+            fn fetch_by_primary_keys<'a>(
+                &'a self,
+                conn: &'a C,
+                keys: Vec<caustics::CausticsKey>,
+                scope: Option<&'a caustics::RowScope>,
+            ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<(caustics::CausticsKey, Box<dyn std::any::Any + Send>)>, sea_orm::DbErr>> + Send + 'a>> {
+                Box::pin(async move {
+                    if keys.is_empty() {
+                        return Ok(Vec::new());
+                    }
+                    let mut condition = Condition::any();
+                    for key in keys {
+                        condition = condition.add(Condition::from(UniqueWhereParam::#primary_key_variant(key)));
+                    }
+                    let models = apply_row_scope(<Entity as EntityTrait>::find().filter(condition), scope).all(conn).await?;
+                    Ok(models
+                        .into_iter()
+                        .filter_map(|m| {
+                            use caustics::ToSeaOrmValue;
+                            let key = caustics::CausticsKey::from_db_value(&(&m.#primary_key_ident).to_sea_orm_value())?;
+                            Some((key, Box::new(ModelWithRelations::from_model(m)) as Box<dyn std::any::Any + Send>))
+                        })
+                        .collect())
+                })
+            }
+        }
+    };
 
     // Precompute nested-include pattern helpers
     let relation_names_snake_lits: Vec<_> = relations
@@ -3546,6 +4548,7 @@ pub fn generate_entity(
             pub order_by: Vec<(String, caustics::SortOrder)>,
             pub cursor_id: Option<caustics::CausticsKey>,
             pub include_count: bool,
+            pub count_filters: Vec<Filter>,
             pub distinct: bool,
         }
 
@@ -3571,7 +4574,9 @@ pub fn generate_entity(
                     order_by: relation_filter.order_by,
                     cursor_id: relation_filter.cursor_id,
                     include_count: relation_filter.include_count,
+                    count_filters: relation_filter.count_filters,
                     distinct: relation_filter.distinct,
+                    scope: None,
                 }
             }
         }
@@ -3602,6 +4607,18 @@ pub fn generate_entity(
         .iter()
         .map(|field| field.ident.as_ref().unwrap())
         .collect::<Vec<_>>();
+    // Values read off `model`, with `#[caustics(utc)]` timestamps moved to the UTC offset
+    let model_field_values = fields
+        .iter()
+        .map(|field| {
+            let name = field.ident.as_ref().unwrap();
+            if crate::primary_key::has_caustics_utc_attr(field) {
+                quote! { caustics::NormalizeUtc::normalize_utc(model.#name) }
+            } else {
+                quote! { model.#name }
+            }
+        })
+        .collect::<Vec<_>>();
 
     // Generate field metadata for FromQueryResult implementation
     let field_inner_types = fields
@@ -3628,19 +4645,24 @@ pub fn generate_entity(
             let inner_ty = crate::common::extract_inner_type_from_option(&field.ty);
             let alias = syn::LitStr::new(&name.to_string(), proc_macro2::Span::call_site());
             let is_nullable = crate::common::is_option(&field.ty);
+            let normalize = if crate::primary_key::has_caustics_utc_attr(field) {
+                quote! { .map(caustics::NormalizeUtc::normalize_utc) }
+            } else {
+                quote! {}
+            };
 
             if is_nullable {
                 // For nullable fields: Option<Option<InnerType>> - first Option for "fetched?", second for "null?"
                 quote! {
                     if fields.contains(&stringify!(#name)) || stringify!(#name) == stringify!(#current_primary_key_ident) {
-                        s.#name = Some(row.try_get::<#inner_ty>("", #alias).ok());
+                        s.#name = Some(row.try_get::<#inner_ty>("", #alias).ok()#normalize);
                     }
                 }
             } else {
                 // For non-nullable fields: Option<InnerType> - Option for "fetched?"
                 quote! {
                     if fields.contains(&stringify!(#name)) || stringify!(#name) == stringify!(#current_primary_key_ident) {
-                        s.#name = row.try_get::<#inner_ty>("", #alias).ok();
+                        s.#name = row.try_get::<#inner_ty>("", #alias).ok()#normalize;
                     }
                 }
             }
@@ -3811,6 +4833,67 @@ pub fn generate_entity(
             }
         })
         .collect::<Vec<_>>();
+    let counts_iter_pairs = relations
+        .iter()
+        .filter(|relation| matches!(relation.kind, RelationKind::HasMany))
+        .map(|relation| {
+            let field_name = relation.get_field_name();
+            let name = format_ident!("{}", field_name);
+            quote! { (#field_name, self.#name.map(i64::from)) }
+        })
+        .collect::<Vec<_>>();
+
+    // Relation-existence virtual fields for select: e.g. `has_posts` for has_many/has_one
+    let existence_relations: Vec<_> = relations
+        .iter()
+        .filter(|relation| matches!(relation.kind, RelationKind::HasMany | RelationKind::HasOne))
+        .collect();
+    let existence_field_idents: Vec<syn::Ident> = existence_relations
+        .iter()
+        .map(|relation| format_ident!("has_{}", relation.get_field_name()))
+        .collect();
+    let existence_field_names: Vec<syn::LitStr> = existence_field_idents
+        .iter()
+        .map(|ident| syn::LitStr::new(&ident.to_string(), proc_macro2::Span::call_site()))
+        .collect();
+    let existence_pk_col = get_primary_key_column_name(&fields);
+    let existence_exprs: Vec<TokenStream> = existence_relations
+        .iter()
+        .map(|relation| {
+            let current_table_name = relation
+                .current_table_name
+                .clone()
+                .unwrap_or_else(|| entity_name.to_snake_case());
+            let fk_col_snake = relation
+                .foreign_key_column
+                .as_ref()
+                .map(|s| s.to_snake_case())
+                .unwrap_or_else(|| existence_pk_col.clone());
+            let target_entity = &relation.target;
+            let current_table_lit = syn::LitStr::new(&current_table_name, proc_macro2::Span::call_site());
+            let fk_col_lit = syn::LitStr::new(&fk_col_snake, proc_macro2::Span::call_site());
+            let pk_col_lit = syn::LitStr::new(&existence_pk_col, proc_macro2::Span::call_site());
+            quote! {{
+                let target_table = sea_orm::EntityName::table_name(&#target_entity::Entity);
+                sea_query::Expr::cust(&format!(
+                    "EXISTS(SELECT 1 FROM {} WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
+                    caustics::quote_table_ref(sea_orm::EntityName::schema_name(&#target_entity::Entity), target_table),
+                    target_table, #fk_col_lit, #current_table_lit, #pk_col_lit
+                ))
+            }}
+        })
+        .collect();
+
+    // Primary key fields every selection keeps, so `Selected::refetch` can reload the row
+    let selected_pk_names: Vec<syn::LitStr> = all_primary_key_info
+        .iter()
+        .map(|info| syn::LitStr::new(info.field_name(), proc_macro2::Span::call_site()))
+        .collect();
+    let selected_pk_idents: Vec<_> = all_primary_key_info.iter().map(|info| info.field_ident().clone()).collect();
+    let selected_pk_columns: Vec<_> = all_primary_key_info
+        .iter()
+        .map(|info| format_ident!("{}", info.field_name().to_pascal_case()))
+        .collect();
 
     // Relation-aggregate orderBy support: variants and match arms
     let relation_order_by_variants = relations
@@ -3864,9 +4947,6 @@ pub fn generate_entity(
         .filter_map(|relation| {
             if matches!(relation.kind, RelationKind::HasMany) {
                 let variant = format_ident!("{}Count", relation.name.to_pascal_case());
-                // Use the resolved target table name from build-time metadata
-                let relation_name_snake = relation.get_field_name();
-                let target_table_name_expr = quote! { #relation_name_snake };
                 let current_table_name = relation
                     .current_table_name.clone()
                     .unwrap_or_else(|| {
@@ -3877,7 +4957,7 @@ pub fn generate_entity(
                     .as_ref()
                     .map(|s| s.to_snake_case())
                     .unwrap_or_else(|| current_pk_column_name.clone());
-                let target_table_lit = target_table_name_expr;
+                let target_entity = &relation.target;
                 let current_table_lit = syn::LitStr::new(&current_table_name, proc_macro2::Span::call_site());
                 let fk_col_lit = syn::LitStr::new(&fk_col_snake, proc_macro2::Span::call_site());
                 let pk_col_lit = syn::LitStr::new(&current_pk_column_name, proc_macro2::Span::call_site());
@@ -3888,11 +4968,17 @@ pub fn generate_entity(
                             caustics::SortOrder::Desc => sea_orm::Order::Desc,
                             _ => sea_orm::Order::Desc
                         };
+                        let target_table = sea_orm::EntityName::table_name(&#target_entity::Entity);
                         let expr = sea_orm::sea_query::Expr::cust(&format!(
-                            "(SELECT COUNT(*) FROM \"{}\" WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
-                            #target_table_lit, #target_table_lit, #fk_col_lit, #current_table_lit, #pk_col_lit
+                            "(SELECT COUNT(*) FROM {} WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
+                            caustics::quote_table_ref(sea_orm::EntityName::schema_name(&#target_entity::Entity), target_table),
+                            target_table, #fk_col_lit, #current_table_lit, #pk_col_lit
                         ));
-                        self.pending_order_bys.push((expr, sea_order));
+                        self.pending_order_bys.push(caustics::OrderByDescriptor {
+                            expr,
+                            order: sea_order,
+                            nulls: None,
+                        });
                     }
                 })
             } else if matches!(relation.kind, RelationKind::BelongsTo) {
@@ -3904,13 +4990,8 @@ pub fn generate_entity(
                         entity_name.to_snake_case()
                     });
                 let current_table_lit = syn::LitStr::new(&current_table_name, proc_macro2::Span::call_site());
-                // Use entity metadata at runtime to get correct table name
-                let fk_col_snake = relation
-                    .foreign_key_column
-                    .as_ref()
-                    .map(|s| s.to_snake_case())
-                    .unwrap_or_else(|| current_pk_column_name.clone());
-                let fk_col_lit = syn::LitStr::new(&fk_col_snake, proc_macro2::Span::call_site());
+                // The foreign key lives on this entity; read its column name from the Column enum
+                let fk_col_variant = format_ident!("{}", relation.get_first_fk_column_name().to_pascal_case());
                 Some(quote! {
                     RelationOrderByParam::#variant(field_name, order) => {
                         let sea_order = match order { 
@@ -3919,8 +5000,8 @@ pub fn generate_entity(
                             _ => sea_orm::Order::Desc
                         };
                         // Get the target table name and primary key from entity metadata at runtime
-                        let target_table_name = if let Some(metadata) = crate::get_entity_metadata(#relation_name_snake) {
-                            metadata.table_name
+                        let (target_table_name, target_table_ref) = if let Some(metadata) = crate::get_entity_metadata(#relation_name_snake) {
+                            (metadata.table_name, metadata.qualified_table_name())
                         } else {
                             panic!("Missing table name for relation '{}'. This indicates a bug in relation extraction.\n\nPlease ensure the relation is properly configured with all required attributes.", #relation_name_snake)
                         };
@@ -3930,10 +5011,15 @@ pub fn generate_entity(
                             panic!("Missing primary key field for relation '{}'. This indicates a bug in relation extraction.\n\nPlease ensure the relation is properly configured with all required attributes.", #relation_name_snake)
                         };
                         let expr = sea_orm::sea_query::Expr::cust(&format!(
-                            "(SELECT \"{}\" FROM \"{}\" WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
-                            field_name, target_table_name, target_table_name, target_pk_col, #current_table_lit, #fk_col_lit
+                            "(SELECT \"{}\" FROM {} WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
+                            field_name, target_table_ref, target_table_name, target_pk_col, #current_table_lit,
+                            sea_orm::IdenStatic::as_str(&Column::#fk_col_variant)
                         ));
-                        self.pending_order_bys.push((expr, sea_order));
+                        self.pending_order_bys.push(caustics::OrderByDescriptor {
+                            expr,
+                            order: sea_order,
+                            nulls: None,
+                        });
                     }
                 })
             } else { None }
@@ -3945,9 +5031,6 @@ pub fn generate_entity(
         .filter_map(|relation| {
             if matches!(relation.kind, RelationKind::HasMany) {
                 let variant = format_ident!("{}Count", relation.name.to_pascal_case());
-                // Use the resolved target table name from build-time metadata
-                let relation_name_snake = relation.get_field_name();
-                let target_table_name_expr = quote! { #relation_name_snake };
                 let current_table_name = relation
                     .current_table_name.clone()
                     .unwrap_or_else(|| {
@@ -3958,7 +5041,7 @@ pub fn generate_entity(
                     .as_ref()
                     .map(|s| s.to_snake_case())
                     .unwrap_or_else(|| current_pk_column_name.clone());
-                let target_table_lit = target_table_name_expr;
+                let target_entity = &relation.target;
                 let current_table_lit = syn::LitStr::new(&current_table_name, proc_macro2::Span::call_site());
                 let fk_col_lit = syn::LitStr::new(&fk_col_snake, proc_macro2::Span::call_site());
                 let pk_col_lit = syn::LitStr::new(&current_pk_column_name, proc_macro2::Span::call_site());
@@ -3969,11 +5052,17 @@ pub fn generate_entity(
                             caustics::SortOrder::Desc => sea_orm::Order::Desc,
                             _ => sea_orm::Order::Desc
                         };
+                        let target_table = sea_orm::EntityName::table_name(&#target_entity::Entity);
                         let expr = sea_orm::sea_query::Expr::cust(&format!(
-                            "(SELECT COUNT(*) FROM \"{}\" WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
-                            #target_table_lit, #target_table_lit, #fk_col_lit, #current_table_lit, #pk_col_lit
+                            "(SELECT COUNT(*) FROM {} WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
+                            caustics::quote_table_ref(sea_orm::EntityName::schema_name(&#target_entity::Entity), target_table),
+                            target_table, #fk_col_lit, #current_table_lit, #pk_col_lit
                         ));
-                        self.pending_order_bys.push((expr, sea_order));
+                        self.pending_order_bys.push(caustics::OrderByDescriptor {
+                            expr,
+                            order: sea_order,
+                            nulls: None,
+                        });
                     }
                 })
             } else if matches!(relation.kind, RelationKind::BelongsTo) {
@@ -3985,13 +5074,8 @@ pub fn generate_entity(
                         entity_name.to_snake_case()
                     });
                 let current_table_lit = syn::LitStr::new(&current_table_name, proc_macro2::Span::call_site());
-                // Use entity metadata at runtime to get correct table name
-                let fk_col_snake = relation
-                    .foreign_key_column
-                    .as_ref()
-                    .map(|s| s.to_snake_case())
-                    .unwrap_or_else(|| current_pk_column_name.clone());
-                let fk_col_lit = syn::LitStr::new(&fk_col_snake, proc_macro2::Span::call_site());
+                // The foreign key lives on this entity; read its column name from the Column enum
+                let fk_col_variant = format_ident!("{}", relation.get_first_fk_column_name().to_pascal_case());
                 Some(quote! {
                     RelationOrderByParam::#variant(field_name, order) => {
                         let sea_order = match order { 
@@ -4000,11 +5084,11 @@ pub fn generate_entity(
                             _ => sea_orm::Order::Desc
                         };
                         // Get the target table name and primary key from entity metadata at runtime
-                        let target_table_name = if let Some(metadata) = crate::get_entity_metadata(#relation_name_snake) {
-                            metadata.table_name
+                        let (target_table_name, target_table_ref) = if let Some(metadata) = crate::get_entity_metadata(#relation_name_snake) {
+                            (metadata.table_name, metadata.qualified_table_name())
                         } else {
                             // Fallback to relation name if metadata not found
-                            #relation_name_snake
+                            (#relation_name_snake, caustics::quote_table_ref(None, #relation_name_snake))
                         };
                         let target_pk_col = if let Some(metadata) = crate::get_entity_metadata(#relation_name_snake) {
                             metadata.primary_key_field
@@ -4013,10 +5097,15 @@ pub fn generate_entity(
                             #relation_name_snake
                         };
                         let expr = sea_orm::sea_query::Expr::cust(&format!(
-                            "(SELECT \"{}\" FROM \"{}\" WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
-                            field_name, target_table_name, target_table_name, target_pk_col, #current_table_lit, #fk_col_lit
+                            "(SELECT \"{}\" FROM {} WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
+                            field_name, target_table_ref, target_table_name, target_pk_col, #current_table_lit,
+                            sea_orm::IdenStatic::as_str(&Column::#fk_col_variant)
                         ));
-                        self.pending_order_bys.push((expr, sea_order));
+                        self.pending_order_bys.push(caustics::OrderByDescriptor {
+                            expr,
+                            order: sea_order,
+                            nulls: None,
+                        });
                     }
                 })
             } else { None }
@@ -4029,9 +5118,6 @@ pub fn generate_entity(
         .filter_map(|relation| {
             if matches!(relation.kind, RelationKind::HasMany) {
                 let variant = format_ident!("{}Count", relation.name.to_pascal_case());
-                // Use the resolved target table name from build-time metadata
-                let relation_name_snake = relation.get_field_name();
-                let target_table_name_expr = quote! { #relation_name_snake };
                 let current_table_name = relation
                     .current_table_name.clone()
                     .unwrap_or_else(|| {
@@ -4042,7 +5128,7 @@ pub fn generate_entity(
                     .as_ref()
                     .map(|s| s.to_snake_case())
                     .unwrap_or_else(|| current_pk_column_name.clone());
-                let target_table_lit = target_table_name_expr;
+                let target_entity = &relation.target;
                 let current_table_lit = syn::LitStr::new(&current_table_name, proc_macro2::Span::call_site());
                 let fk_col_lit = syn::LitStr::new(&fk_col_snake, proc_macro2::Span::call_site());
                 let pk_col_lit = syn::LitStr::new(&current_pk_column_name, proc_macro2::Span::call_site());
@@ -4053,9 +5139,11 @@ pub fn generate_entity(
                             caustics::SortOrder::Desc => sea_orm::Order::Desc,
                             _ => sea_orm::Order::Desc
                         };
+                        let target_table = sea_orm::EntityName::table_name(&#target_entity::Entity);
                         let expr = sea_orm::sea_query::Expr::cust(&format!(
-                            "(SELECT COUNT(*) FROM \"{}\" WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
-                            #target_table_lit, #target_table_lit, #fk_col_lit, #current_table_lit, #pk_col_lit
+                            "(SELECT COUNT(*) FROM {} WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
+                            caustics::quote_table_ref(sea_orm::EntityName::schema_name(&#target_entity::Entity), target_table),
+                            target_table, #fk_col_lit, #current_table_lit, #pk_col_lit
                         ));
                         (expr, sea_order)
                     }
@@ -4069,13 +5157,8 @@ pub fn generate_entity(
                         entity_name.to_snake_case()
                     });
                 let current_table_lit = syn::LitStr::new(&current_table_name, proc_macro2::Span::call_site());
-                // Use entity metadata at runtime to get correct table name
-                let fk_col_snake = relation
-                    .foreign_key_column
-                    .as_ref()
-                    .map(|s| s.to_snake_case())
-                    .unwrap_or_else(|| current_pk_column_name.clone());
-                let fk_col_lit = syn::LitStr::new(&fk_col_snake, proc_macro2::Span::call_site());
+                // The foreign key lives on this entity; read its column name from the Column enum
+                let fk_col_variant = format_ident!("{}", relation.get_first_fk_column_name().to_pascal_case());
                 Some(quote! {
                     RelationOrderByParam::#variant(field_name, order) => {
                         let sea_order = match order { 
@@ -4084,11 +5167,11 @@ pub fn generate_entity(
                             _ => sea_orm::Order::Desc
                         };
                         // Get the target table name and primary key from entity metadata at runtime
-                        let target_table_name = if let Some(metadata) = crate::get_entity_metadata(#relation_name_snake) {
-                            metadata.table_name
+                        let (target_table_name, target_table_ref) = if let Some(metadata) = crate::get_entity_metadata(#relation_name_snake) {
+                            (metadata.table_name, metadata.qualified_table_name())
                         } else {
                             // Fallback to relation name if metadata not found
-                            #relation_name_snake
+                            (#relation_name_snake, caustics::quote_table_ref(None, #relation_name_snake))
                         };
                         let target_pk_col = if let Some(metadata) = crate::get_entity_metadata(#relation_name_snake) {
                             metadata.primary_key_field
@@ -4097,8 +5180,9 @@ pub fn generate_entity(
                             #relation_name_snake
                         };
                         let expr = sea_orm::sea_query::Expr::cust(&format!(
-                            "(SELECT \"{}\" FROM \"{}\" WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
-                            field_name, target_table_name, target_table_name, target_pk_col, #current_table_lit, #fk_col_lit
+                            "(SELECT \"{}\" FROM {} WHERE \"{}\".\"{}\" = \"{}\".\"{}\")",
+                            field_name, target_table_ref, target_table_name, target_pk_col, #current_table_lit,
+                            sea_orm::IdenStatic::as_str(&Column::#fk_col_variant)
                         ));
                         (expr, sea_order)
                     }
@@ -4145,12 +5229,12 @@ pub fn generate_entity(
                         #relation_name_lit => {
                             if let Some(fkv) = foreign_key_value {
                                 // Build a count query applying the same filter semantics as the fetcher (ignoring pagination)
-                                let mut query = #target::Entity::find()
+                                let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref())
                                     .filter(#target::Column::#foreign_key_column_ident.eq(fkv));
 
-                                if !filter.filters.is_empty() {
+                                if !filter.filters.is_empty() || !filter.count_filters.is_empty() {
                                     let mut cond = Condition::all();
-                                    for f in &filter.filters {
+                                    for f in filter.filters.iter().chain(&filter.count_filters) {
                                         if let Some(col) = #target::column_from_str(&f.field) {
                                             use sea_orm::IntoSimpleExpr;
                                             let col_expr = col.into_simple_expr();
@@ -4161,17 +5245,16 @@ pub fn generate_entity(
                                                 caustics::FieldOp::NotEquals(v) => {
                                                     cond = cond.add(Expr::expr(col_expr.clone()).ne((*v).clone()));
                                                 }
-                                                caustics::FieldOp::Contains(s) => {
-                                                    let pat = format!("%{}%", s);
-                                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
-                                                }
-                                                caustics::FieldOp::StartsWith(s) => {
-                                                    let pat = format!("{}%", s);
-                                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
+                                                caustics::FieldOp::Contains(_)
+                                                | caustics::FieldOp::StartsWith(_)
+                                                | caustics::FieldOp::EndsWith(_)
+                                                | caustics::FieldOp::Like(_) => {
+                                                    let pattern = f.operation.like_pattern().expect("LIKE operation has a pattern");
+                                                    cond = cond.add(Expr::expr(col_expr.clone()).like(caustics::like_expr(pattern)));
                                                 }
-                                                caustics::FieldOp::EndsWith(s) => {
-                                                    let pat = format!("%{}", s);
-                                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
+                                                caustics::FieldOp::ContainsAny(_) => {
+                                                    let patterns = f.operation.like_any_patterns().expect("contains_any has patterns");
+                                                    cond = cond.add(caustics::like_any_expr(col_expr.clone(), patterns));
                                                 }
                                                 caustics::FieldOp::IsNull => {
                                                     cond = cond.add(Expr::expr(col_expr.clone()).is_null());
@@ -4241,12 +5324,12 @@ pub fn generate_entity(
                             if let Some(fkv) = foreign_key_value_any.clone() {
                                 // Build a count query applying the same filter semantics as the fetcher (ignoring pagination)
                                 let col_expr = <#target::Entity as sea_orm::EntityTrait>::Column::#foreign_key_column_ident.into_simple_expr();
-                                let mut query = #target::Entity::find()
+                                let mut query = #target::apply_row_scope(#target::Entity::find(), filter.scope.as_ref())
                                     .filter(Expr::expr(col_expr).eq(fkv));
 
-                                if !filter.filters.is_empty() {
+                                if !filter.filters.is_empty() || !filter.count_filters.is_empty() {
                                     let mut cond = Condition::all();
-                                    for f in &filter.filters {
+                                    for f in filter.filters.iter().chain(&filter.count_filters) {
                                         if let Some(col) = #target::column_from_str(&f.field) {
                                             use sea_orm::IntoSimpleExpr;
                                             let col_expr = col.into_simple_expr();
@@ -4257,17 +5340,16 @@ pub fn generate_entity(
                                                 caustics::FieldOp::NotEquals(v) => {
                                                     cond = cond.add(Expr::expr(col_expr.clone()).ne((*v).clone()));
                                                 }
-                                                caustics::FieldOp::Contains(s) => {
-                                                    let pat = format!("%{}%", s);
-                                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
+                                                caustics::FieldOp::Contains(_)
+                                                | caustics::FieldOp::StartsWith(_)
+                                                | caustics::FieldOp::EndsWith(_)
+                                                | caustics::FieldOp::Like(_) => {
+                                                    let pattern = f.operation.like_pattern().expect("LIKE operation has a pattern");
+                                                    cond = cond.add(Expr::expr(col_expr.clone()).like(caustics::like_expr(pattern)));
                                                 }
-                                                caustics::FieldOp::StartsWith(s) => {
-                                                    let pat = format!("{}%", s);
-                                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
-                                                }
-                                                caustics::FieldOp::EndsWith(s) => {
-                                                    let pat = format!("%{}", s);
-                                                    cond = cond.add(Expr::expr(col_expr.clone()).like(pat));
+                                                caustics::FieldOp::ContainsAny(_) => {
+                                                    let patterns = f.operation.like_any_patterns().expect("contains_any has patterns");
+                                                    cond = cond.add(caustics::like_any_expr(col_expr.clone(), patterns));
                                                 }
                                                 caustics::FieldOp::IsNull => {
                                                     cond = cond.add(Expr::expr(col_expr.clone()).is_null());
@@ -4308,6 +5390,14 @@ pub fn generate_entity(
             #(#counts_struct_fields,)*
         }
 
+        impl Counts {
+            /// `(relation_name, count)` pairs in declaration order; `None` where the count wasn't requested
+            pub fn iter(&self) -> impl Iterator<Item = (&'static str, Option<i64>)> {
+                let pairs: Vec<(&'static str, Option<i64>)> = vec![#(#counts_iter_pairs),*];
+                pairs.into_iter()
+            }
+        }
+
         #[derive(Debug, Clone, PartialEq, Eq, Hash)]
         pub struct ModelWithRelations {
             #(#model_with_relations_fields,)*
@@ -4329,12 +5419,19 @@ pub fn generate_entity(
 
             pub fn from_model(model: Model) -> Self {
                 Self {
-                    #(#field_names: model.#field_names,)*
+                    #(#field_names: #model_field_values,)*
                     #(#relation_defaults,)*
                     _count: None,
                 }
             }
 
+            /// This row's primary key (composite keys included), usable in sets and maps
+            pub fn primary_key(&self) -> Result<caustics::CausticsKey, caustics::CausticsError> {
+                use caustics::ToSeaOrmValue;
+                let m = self;
+                #composite_key_extraction
+            }
+
             pub fn __caustics_apply_relation_filter<'a, C: sea_orm::ConnectionTrait>(
                 &'a mut self,
                 conn: &'a C,
@@ -4342,6 +5439,17 @@ pub fn generate_entity(
                 registry: &'a (dyn caustics::EntityRegistry<C> + Sync),
             ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), sea_orm::DbErr>> + Send + 'a>> {
                 Box::pin(async move {
+                    // Full rows hold full related rows: a field selection (e.g. `include_fk_only`)
+                    // only narrows `Selected` includes, and would leave defaults in these
+                    if filter.nested_select_aliases.is_some() {
+                        return Err(caustics::CausticsError::QueryValidation {
+                            message: format!(
+                                "relation '{}' selects fields, which only `select` queries support",
+                                filter.relation
+                            ),
+                        }
+                        .into());
+                    }
                     let descriptor = <Self as caustics::HasRelationMetadata<Self>>::get_relation_descriptor(filter.relation)
                         .ok_or_else(|| caustics::CausticsError::InvalidIncludePath { relation: filter.relation.to_string() })?;
                     let foreign_key_value = (descriptor.get_foreign_key)(self);
@@ -4412,6 +5520,12 @@ pub fn generate_entity(
             }
         }
 
+        impl<C: sea_orm::ConnectionTrait> caustics::RegistryProvider<C> for ModelWithRelations {
+            fn registry() -> &'static (dyn caustics::EntityRegistry<C> + Sync) {
+                get_registry()
+            }
+        }
+
         impl<C: sea_orm::ConnectionTrait> caustics::ApplyNestedIncludes<C> for ModelWithRelations {
             fn apply_relation_filter<'a>(
                 &'a mut self,
@@ -4445,6 +5559,7 @@ pub fn generate_entity(
         pub struct Selected {
             #(#selected_scalar_fields,)*
             #(#selected_relation_fields,)*
+            #(pub #existence_field_idents: Option<bool>,)*
             pub _count: Option<Counts>,
         }
 
@@ -4457,6 +5572,9 @@ pub fn generate_entity(
                     #(
                         #selected_all_field_names => Some(<Entity as sea_orm::EntityTrait>::Column::#selected_all_field_idents.into_simple_expr()),
                     )*
+                    #(
+                        #existence_field_names => Some(#existence_exprs),
+                    )*
                     _ => None,
                 }
             }
@@ -4473,7 +5591,7 @@ pub fn generate_entity(
                     // No field selection - try to populate all fields safely
                     // Use a match pattern to only access fields that exist in the model
                     #(
-                        selected.#field_names = Some(model.#field_names);
+                        selected.#field_names = Some(#model_field_values);
                     )*
                 } else {
                     // Field selection is used - only access selected fields plus defensive fields
@@ -4500,7 +5618,7 @@ pub fn generate_entity(
                     // Only populate fields that were actually fetched from the database
                     #(
                         if accessible_fields.contains(&stringify!(#field_names)) {
-                            selected.#field_names = Some(model.#field_names);
+                            selected.#field_names = Some(#model_field_values);
                         }
                     )*
                 }
@@ -4529,6 +5647,22 @@ pub fn generate_entity(
 
                 model_with_relations
             }
+
+            /// Reload the full row this selection came from, using the primary key every
+            /// selection keeps, e.g. `selected.refetch(&client).await?`
+            pub async fn refetch<'a, C: sea_orm::ConnectionTrait + sea_orm::TransactionTrait + 'a>(
+                &self,
+                client: impl Into<EntityClient<'a, C>>,
+            ) -> Result<Option<ModelWithRelations>, sea_orm::DbErr> {
+                let mut condition = Condition::all();
+                #(
+                    let value = self.#selected_pk_idents.clone().ok_or_else(|| caustics::CausticsError::QueryValidation {
+                        message: format!("refetch needs the primary key field '{}' in the selection", #selected_pk_names),
+                    })?;
+                    condition = condition.add(<Entity as EntityTrait>::Column::#selected_pk_columns.eq(value));
+                )*
+                client.into().find_by_condition(condition, None).exec().await
+            }
         }
 
 
@@ -4536,6 +5670,15 @@ pub fn generate_entity(
             fn fill_from_row(row: &sea_orm::QueryResult, fields: &[&str]) -> Self {
                 let mut s = Selected::new();
                 #(#selected_fill_stmts)*
+                #(
+                    if fields.contains(&#existence_field_names) {
+                        // SQLite and MySQL report EXISTS as an integer
+                        s.#existence_field_idents = row
+                            .try_get::<bool>("", #existence_field_names)
+                            .ok()
+                            .or_else(|| row.try_get::<i64>("", #existence_field_names).ok().map(|v| v != 0));
+                    }
+                )*
                 s
             }
 
@@ -4560,9 +5703,15 @@ pub fn generate_entity(
                     #(
                         #selected_all_field_names => Some(<Entity as sea_orm::EntityTrait>::Column::#selected_all_field_idents.into_simple_expr()),
                     )*
+                    #(
+                        #existence_field_names => Some(#existence_exprs),
+                    )*
                     _ => None,
                 }
             }
+            fn primary_key_aliases() -> &'static [&'static str] {
+                &[#(#selected_pk_names),*]
+            }
             fn get_value_as_db_value(&self, field_name: &str) -> Option<sea_orm::Value> {
                 match field_name {
                     #(
@@ -4782,6 +5931,11 @@ pub fn generate_entity(
                 )
             }
         };
+        let get_foreign_key_closure = if relation.is_composite {
+            composite_relation_key_closure(relation, &fields)
+        } else {
+            get_foreign_key_closure
+        };
         // Use the lowercase module name as the registry key (e.g., "post")
         let target_entity_module_name_lower = relation
             .target
@@ -4827,8 +5981,7 @@ pub fn generate_entity(
         };
         let target_primary_key_column_lit =
             syn::LitStr::new(&target_primary_key_column, proc_macro2::Span::call_site());
-        let is_foreign_key_nullable_lit =
-            syn::LitBool::new(relation.is_nullable, proc_macro2::Span::call_site());
+        let is_foreign_key_nullable_fn = foreign_key_nullable_fn(relation);
 
         let fk_field_name_lit = match relation.kind {
             RelationKind::HasMany | RelationKind::HasOne => syn::LitStr::new(&current_primary_key_field_name, proc_macro2::Span::call_site()),
@@ -5032,7 +6185,7 @@ pub fn generate_entity(
                 current_primary_key_field_name: #current_primary_key_field_name_lit,
                 target_primary_key_column: #target_primary_key_column_lit,
                 target_entity_name: #target_entity_name_lit,
-                is_foreign_key_nullable: #is_foreign_key_nullable_lit,
+                is_foreign_key_nullable: #is_foreign_key_nullable_fn,
                 is_has_many: #is_has_many_lit,
                 is_has_one: #is_has_one_lit,
             }
@@ -5099,8 +6252,7 @@ pub fn generate_entity(
             RelationKind::HasOne => syn::LitBool::new(true, proc_macro2::Span::call_site()),
             RelationKind::HasMany | RelationKind::BelongsTo => syn::LitBool::new(false, proc_macro2::Span::call_site()),
         };
-        let is_foreign_key_nullable_lit =
-            syn::LitBool::new(relation.is_nullable, proc_macro2::Span::call_site());
+        let is_foreign_key_nullable_fn = foreign_key_nullable_fn(relation);
 
         // Generate the correct set_field implementation based on relation type
         let set_field_impl = match relation.kind {
@@ -5146,17 +6298,37 @@ pub fn generate_entity(
             quote! { None }
         };
 
+        let selected_get_foreign_key = if relation.is_composite {
+            let key_fields = composite_relation_key_fields(relation);
+            quote! {
+                |model: &Selected| {
+                    let mut parts = Vec::new();
+                    for field_name in [#(#key_fields),*] {
+                        parts.push((
+                            field_name.to_string(),
+                            <Selected as caustics::EntitySelection>::get_key(model, field_name)?,
+                        ));
+                    }
+                    Some(caustics::CausticsKey::Composite(parts))
+                }
+            }
+        } else {
+            quote! {
+                |model: &Selected| {
+                    // For has_many, use current id; for belongs_to, use FK field on Selected
+                    let field_name = match #is_has_many_lit { true => #current_primary_key_field_name_lit, false => #fk_field_name_lit };
+                    <Selected as caustics::EntitySelection>::get_key(model, field_name)
+                }
+            }
+        };
+
         quote! {
             caustics::RelationDescriptor::<Selected> {
                 name: #name,
                 set_field: |model, value| {
                     #set_field_impl
                 },
-                get_foreign_key: |model: &Selected| {
-                    // For has_many, use current id; for belongs_to, use FK field on Selected
-                    let field_name = match #is_has_many_lit { true => #current_primary_key_field_name_lit, false => #fk_field_name_lit };
-                    <Selected as caustics::EntitySelection>::get_key(model, field_name)
-                },
+                get_foreign_key: #selected_get_foreign_key,
                 target_entity: #target_entity,
                 foreign_key_column: #foreign_key_column,
                 foreign_key_field_name: #fk_field_name_lit,
@@ -5165,7 +6337,7 @@ pub fn generate_entity(
                 current_primary_key_field_name: #current_primary_key_field_name_lit,
                 target_primary_key_column: #target_primary_key_column_lit,
                 target_entity_name: #target_entity_name_lit,
-                is_foreign_key_nullable: #is_foreign_key_nullable_lit,
+                is_foreign_key_nullable: #is_foreign_key_nullable_fn,
                 is_has_many: #is_has_many_lit,
                 is_has_one: #is_has_one_lit,
             }
@@ -5206,6 +6378,19 @@ pub fn generate_entity(
         })
         .map(|relation| {
             let relation_name = format_ident!("Connect{}", relation.name.to_pascal_case());
+            if relation.is_composite && relation.target_primary_key_fields.len() > 1 {
+                let assign = generate_composite_foreign_key_assign(
+                    relation,
+                    &fields,
+                    entity_context.registry_name(),
+                    quote! { where_param },
+                );
+                return quote! {
+                    SetParam::#relation_name(where_param) => {
+                        #assign
+                    }
+                };
+            }
             let foreign_key_field = format_ident!("{}", 
                 if !relation.foreign_key_fields.is_empty() {
                     &relation.foreign_key_fields[0]
@@ -5250,17 +6435,22 @@ pub fn generate_entity(
             let primary_key_field_ident = format_ident!("{}", primary_key_field_name.to_snake_case());
 
             // Check if this is an optional relation and get field type
-            let (is_optional, fk_field_type, fk_field_type_inner) = find_field_and_extract_type_info(&fields, &fk_field_name)
+            let (is_optional, _fk_field_type, fk_field_type_inner) = find_field_and_extract_type_info(&fields, &fk_field_name)
                 .unwrap_or_else(|| (false, &fields[0].ty, &fields[0].ty)); // fallback, should not happen
 
+            let key_active_value = foreign_key_active_value(entity_name, &foreign_key_field_name_snake, quote! { key }, fk_field_type_inner, is_optional);
+            let value_active_value = foreign_key_active_value(entity_name, &foreign_key_field_name_snake, quote! { value }, fk_field_type_inner, is_optional);
+
             if is_optional {
                 quote! {
                     SetParam::#relation_name(where_param) => {
                         match where_param {
                             #target_module::UniqueWhereParam::#primary_key_variant(key) => {
                                 // Extract the value from CausticsKey for database field assignment
-                                let active_value_boxed = crate::__caustics_convert_key_to_active_value_optional(#entity_name, #foreign_key_field_name_snake, key);
-                                model.#foreign_key_field = *active_value_boxed.downcast::<sea_orm::ActiveValue<Option<#fk_field_type_inner>>>().expect("Failed to downcast to ActiveValue");
+                                match #key_active_value {
+                                    Ok(value) => model.#foreign_key_field = value,
+                                    Err(error) => deferred_lookups.push(caustics::DeferredLookup::failed(error)),
+                                }
                             }
                             other => {
                                 // Store deferred lookup instead of executing (optional FK -> wrap in Some)
@@ -5269,8 +6459,8 @@ pub fn generate_entity(
                                     |model, value| {
                                         let model = model.downcast_mut::<ActiveModel>().unwrap();
                                         // Extract the value from CausticsKey for database field assignment
-                                        let active_value_boxed = crate::__caustics_convert_key_to_active_value_optional(#entity_name, #foreign_key_field_name_snake, value);
-                                        model.#foreign_key_field = *active_value_boxed.downcast::<sea_orm::ActiveValue<Option<#fk_field_type_inner>>>().expect("Failed to downcast to ActiveValue");
+                                        model.#foreign_key_field = #value_active_value?;
+                                        Ok(())
                                     },
                                     |conn: & sea_orm::DatabaseConnection, param| {
                                         let param = param.downcast_ref::<#target_module::UniqueWhereParam>().unwrap().clone();
@@ -5323,8 +6513,10 @@ pub fn generate_entity(
                         match where_param {
                             #target_module::UniqueWhereParam::#primary_key_variant(key) => {
                                 // Extract the value from CausticsKey for database field assignment
-                                let active_value_boxed = crate::__caustics_convert_key_to_active_value(#entity_name, #foreign_key_field_name_snake, key);
-                                model.#foreign_key_field = *active_value_boxed.downcast::<sea_orm::ActiveValue<#fk_field_type>>().expect("Failed to downcast to ActiveValue");
+                                match #key_active_value {
+                                    Ok(value) => model.#foreign_key_field = value,
+                                    Err(error) => deferred_lookups.push(caustics::DeferredLookup::failed(error)),
+                                }
                             }
                             other => {
                                 // Store deferred lookup instead of executing
@@ -5333,8 +6525,8 @@ pub fn generate_entity(
                             |model, value| {
                                 let model = model.downcast_mut::<ActiveModel>().unwrap();
                                 // Extract the value from CausticsKey for database field assignment
-                                let active_value_boxed = crate::__caustics_convert_key_to_active_value(#entity_name, #foreign_key_field_name_snake, value);
-                                model.#foreign_key_field = *active_value_boxed.downcast::<sea_orm::ActiveValue<#fk_field_type>>().expect("Failed to downcast to ActiveValue");
+                                model.#foreign_key_field = #value_active_value?;
+                                Ok(())
                             },
                                      |conn: & sea_orm::DatabaseConnection, param| {
                                         let param = param.downcast_ref::<#target_module::UniqueWhereParam>().unwrap().clone();
@@ -5396,6 +6588,38 @@ pub fn generate_entity(
         })
         .map(|relation| {
             let connect_variant = format_ident!("Connect{}", relation.name.to_pascal_case());
+            if relation.is_composite && relation.target_primary_key_fields.len() > 1 {
+                let target_module = &relation.target;
+                let composite_variant = format_ident!(
+                    "{}",
+                    relation
+                        .target_primary_key_fields
+                        .iter()
+                        .map(|field| field.to_pascal_case())
+                        .collect::<Vec<_>>()
+                        .join("And")
+                );
+                let fk_field_variants: Vec<_> = relation
+                    .foreign_key_fields
+                    .iter()
+                    .map(|field| format_ident!("{}", field.to_pascal_case()))
+                    .collect();
+                let bindings: Vec<_> = (0..fk_field_variants.len())
+                    .map(|i| format_ident!("part_{}", i))
+                    .collect();
+                return quote! {
+                    SetParam::#connect_variant(where_param) => {
+                        match where_param {
+                            #target_module::UniqueWhereParam::#composite_variant(#(#bindings),*) => {
+                                #(normal_changes.push(SetParam::#fk_field_variants(sea_orm::ActiveValue::Set(#bindings.into())));)*
+                            }
+                            other => {
+                                normal_changes.push(SetParam::#connect_variant(other));
+                            }
+                        }
+                    }
+                };
+            }
             let foreign_key_field_ident = format_ident!("{}",
                 if !relation.foreign_key_fields.is_empty() { &relation.foreign_key_fields[0] } else { relation.foreign_key_field.as_ref().unwrap() }
             );
@@ -5428,13 +6652,17 @@ pub fn generate_entity(
                 find_field_and_extract_type_info(&fields, &fk_field_name)
                     .unwrap_or_else(|| (false, &fields[0].ty, &fields[0].ty));
 
+            let key_active_value = foreign_key_active_value(entity_name, &foreign_key_field_name_snake, quote! { key }, fk_field_type_inner, is_optional);
+
             if is_optional {
                 quote! {
                     SetParam::#connect_variant(where_param) => {
                         match where_param {
                             #target_module::UniqueWhereParam::#primary_key_variant(key) => {
-                                let active_value_boxed = crate::__caustics_convert_key_to_active_value_optional(#entity_name, #foreign_key_field_name_snake, key);
-                                normal_changes.push(SetParam::#fk_field_variant(*active_value_boxed.downcast::<sea_orm::ActiveValue<Option<#fk_field_type_inner>>>().expect("Failed to downcast to ActiveValue")));
+                                match #key_active_value {
+                                    Ok(value) => normal_changes.push(SetParam::#fk_field_variant(value)),
+                                    Err(error) => deferred_lookups.push(caustics::DeferredLookup::failed(error)),
+                                }
                             }
                             other => {
                                 normal_changes.push(SetParam::#connect_variant(other));
@@ -5447,8 +6675,10 @@ pub fn generate_entity(
                     SetParam::#connect_variant(where_param) => {
                         match where_param {
                             #target_module::UniqueWhereParam::#primary_key_variant(key) => {
-                                let active_value_boxed = crate::__caustics_convert_key_to_active_value(#entity_name, #foreign_key_field_name_snake, key);
-                                normal_changes.push(SetParam::#fk_field_variant(*active_value_boxed.downcast::<sea_orm::ActiveValue<#fk_field_type_inner>>().expect("Failed to downcast to ActiveValue")));
+                                match #key_active_value {
+                                    Ok(value) => normal_changes.push(SetParam::#fk_field_variant(value)),
+                                    Err(error) => deferred_lookups.push(caustics::DeferredLookup::failed(error)),
+                                }
                             }
                             other => {
                                 normal_changes.push(SetParam::#connect_variant(other));
@@ -5507,21 +6737,26 @@ pub fn generate_entity(
                 find_field_and_extract_type_info(&fields, &fk_field_name)
                     .unwrap_or_else(|| (false, &fields[0].ty, &fields[0].ty));
 
+            let key_active_value = foreign_key_active_value(entity_name, &foreign_key_field_name_snake, quote! { key }, fk_field_type_inner, is_optional);
+            let value_active_value = foreign_key_active_value(entity_name, &foreign_key_field_name_snake, quote! { value }, fk_field_type_inner, is_optional);
+
             if is_optional {
                 quote! {
                     SetParam::#relation_name(where_param) => {
                         match where_param {
                             #target_module::UniqueWhereParam::#primary_key_variant(key) => {
-                                let active_value_boxed = crate::__caustics_convert_key_to_active_value_optional(#entity_name, #foreign_key_field_name_snake, key);
-                                normal_changes.push(SetParam::#fk_field_variant(*active_value_boxed.downcast::<sea_orm::ActiveValue<Option<#fk_field_type_inner>>>().expect("Failed to downcast to ActiveValue")));
+                                match #key_active_value {
+                                    Ok(value) => normal_changes.push(SetParam::#fk_field_variant(value)),
+                                    Err(error) => deferred_lookups.push(caustics::DeferredLookup::failed(error)),
+                                }
                             }
                             other => {
                                 deferred_lookups.push(caustics::DeferredLookup::new(
                                     Box::new(other.clone()),
                                     |model, value| {
                                         let model = model.downcast_mut::<ActiveModel>().unwrap();
-                                        let active_value_boxed = crate::__caustics_convert_key_to_active_value_optional(#entity_name, #foreign_key_field_name_snake, value);
-                                        model.#foreign_key_field_ident = *active_value_boxed.downcast::<sea_orm::ActiveValue<Option<#fk_field_type_inner>>>().expect("Failed to downcast to ActiveValue");
+                                        model.#foreign_key_field_ident = #value_active_value?;
+                                        Ok(())
                                     },
                                     |conn: & sea_orm::DatabaseConnection, param| {
                                         let param = param.downcast_ref::<#target_module::UniqueWhereParam>().unwrap().clone();
@@ -5567,16 +6802,18 @@ pub fn generate_entity(
                     SetParam::#relation_name(where_param) => {
                         match where_param {
                             #target_module::UniqueWhereParam::#primary_key_variant(key) => {
-                                let active_value_boxed = crate::__caustics_convert_key_to_active_value(#entity_name, #foreign_key_field_name_snake, key);
-                                normal_changes.push(SetParam::#fk_field_variant(*active_value_boxed.downcast::<sea_orm::ActiveValue<#fk_field_type_inner>>().expect("Failed to downcast to ActiveValue")));
+                                match #key_active_value {
+                                    Ok(value) => normal_changes.push(SetParam::#fk_field_variant(value)),
+                                    Err(error) => deferred_lookups.push(caustics::DeferredLookup::failed(error)),
+                                }
                             }
                             other => {
                                 deferred_lookups.push(caustics::DeferredLookup::new(
                                     Box::new(other.clone()),
                                     |model, value| {
                                         let model = model.downcast_mut::<ActiveModel>().unwrap();
-                                        let active_value_boxed = crate::__caustics_convert_key_to_active_value(#entity_name, #foreign_key_field_name_snake, value);
-                                        model.#foreign_key_field_ident = *active_value_boxed.downcast::<sea_orm::ActiveValue<#fk_field_type_inner>>().expect("Failed to downcast to ActiveValue");
+                                        model.#foreign_key_field_ident = #value_active_value?;
+                                        Ok(())
                                     },
                                     |conn: & sea_orm::DatabaseConnection, param| {
                                         let param = param.downcast_ref::<#target_module::UniqueWhereParam>().unwrap().clone();
@@ -5868,9 +7105,125 @@ pub fn generate_entity(
         .flatten()
         .collect();
 
+    // Generate server-side column expression match arms for SetParam (atomic string/JSON/list ops)
+    let from_column_expr_match_arms = fields
+        .iter()
+        .filter(|field| !primary_key_fields.contains(field))
+        .map(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier");
+            let pascal_name = format_ident!("{}", name.to_string().to_pascal_case());
+            let from_column_name = format_ident!("{}FromColumn", pascal_name);
+            quote! {
+                SetParam::#from_column_name(source) => Some((
+                    sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                    sea_orm::sea_query::Expr::col(*source).into(),
+                ))
+            }
+        })
+        .collect::<Vec<_>>();
+    let column_expr_match_arms: Vec<proc_macro2::TokenStream> = fields
+        .iter()
+        .filter(|field| !primary_key_fields.contains(field))
+        .filter_map(|field| {
+            let name = field.ident.as_ref().expect("Field has no identifier");
+            let pascal_name = format_ident!("{}", name.to_string().to_pascal_case());
+            let field_type = crate::where_param::detect_field_type(&field.ty);
+            match field_type {
+                crate::where_param::FieldType::String
+                | crate::where_param::FieldType::OptionString => {
+                    let append_name = format_ident!("{}Append", pascal_name);
+                    let prepend_name = format_ident!("{}Prepend", pascal_name);
+                    Some(quote! {
+                        SetParam::#append_name(value) => Some((
+                            sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                            caustics::atomic::string_append(
+                                backend,
+                                &<Entity as EntityTrait>::Column::#pascal_name.to_string(),
+                                value.clone(),
+                            ),
+                        )),
+                        SetParam::#prepend_name(value) => Some((
+                            sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                            caustics::atomic::string_prepend(
+                                backend,
+                                &<Entity as EntityTrait>::Column::#pascal_name.to_string(),
+                                value.clone(),
+                            ),
+                        ))
+                    })
+                }
+                crate::where_param::FieldType::Json | crate::where_param::FieldType::OptionJson => {
+                    let merge_name = format_ident!("{}Merge", pascal_name);
+                    let remove_key_name = format_ident!("{}RemoveKey", pascal_name);
+                    let push_name = format_ident!("{}Push", pascal_name);
+                    let remove_element_name = format_ident!("{}RemoveElement", pascal_name);
+                    Some(quote! {
+                        SetParam::#merge_name(patch) => Some((
+                            sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                            caustics::atomic::json_merge(
+                                backend,
+                                &<Entity as EntityTrait>::Column::#pascal_name.to_string(),
+                                patch.clone(),
+                            ),
+                        )),
+                        SetParam::#remove_key_name(path) => Some((
+                            sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                            caustics::atomic::json_remove_path(
+                                backend,
+                                &<Entity as EntityTrait>::Column::#pascal_name.to_string(),
+                                path,
+                            ),
+                        )),
+                        SetParam::#push_name(element) => Some((
+                            sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                            caustics::atomic::json_array_push(
+                                backend,
+                                &<Entity as EntityTrait>::Column::#pascal_name.to_string(),
+                                element.clone(),
+                            ),
+                        )),
+                        SetParam::#remove_element_name(element) => Some((
+                            sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                            caustics::atomic::json_array_remove(
+                                backend,
+                                &<Entity as EntityTrait>::Column::#pascal_name.to_string(),
+                                element.clone(),
+                            ),
+                        ))
+                    })
+                }
+                crate::where_param::FieldType::Vec | crate::where_param::FieldType::OptionVec => {
+                    let push_name = format_ident!("{}Push", pascal_name);
+                    let remove_element_name = format_ident!("{}RemoveElement", pascal_name);
+                    Some(quote! {
+                        SetParam::#push_name(element) => Some((
+                            sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                            caustics::atomic::array_push(
+                                backend,
+                                &<Entity as EntityTrait>::Column::#pascal_name.to_string(),
+                                sea_orm::Value::from(element.clone()),
+                            ),
+                        )),
+                        SetParam::#remove_element_name(element) => Some((
+                            sea_orm::sea_query::IntoIden::into_iden(<Entity as EntityTrait>::Column::#pascal_name),
+                            caustics::atomic::array_remove(
+                                backend,
+                                &<Entity as EntityTrait>::Column::#pascal_name.to_string(),
+                                sea_orm::Value::from(element.clone()),
+                            ),
+                        ))
+                    })
+                }
+                _ => None,
+            }
+        })
+        .collect();
+
     // Generate SetParamInfo trait match arms
     let has_many_set_match_arms = has_many_set_variants
         .iter()
+        .chain(has_many_disconnect_variants.iter())
+        .chain(has_one_connect_variants.iter())
         .map(|(_, relation_name, _)| {
             quote! { SetParam::#relation_name(_) => true }
         })
@@ -5878,6 +7231,8 @@ pub fn generate_entity(
 
     let relation_name_match_arms = has_many_set_variants
         .iter()
+        .chain(has_many_disconnect_variants.iter())
+        .chain(has_one_connect_variants.iter())
         .map(|(relation_name, variant_name, _)| {
             let relation_name_lit = syn::LitStr::new(
                 &relation_name.to_lowercase(),
@@ -5887,51 +7242,41 @@ pub fn generate_entity(
         })
         .collect::<Vec<_>>();
 
-    let target_ids_match_arms = has_many_set_variants
+    let has_one_connect_match_arms = has_one_connect_variants
         .iter()
-        .map(|(_, variant_name, target_module)| {
-            quote! {
-                SetParam::#variant_name(unique_params) => {
-                    // Extract IDs from Vec<#target_module::UniqueWhereParam>
-                    // Parse each UniqueWhereParam to extract the ID
-                    let mut target_ids = Vec::new();
-                    for unique_param in unique_params {
-                        // Convert UniqueWhereParam to string and extract ID
-                        let param_str = format!("{:?}", unique_param);
-                        // Handle CausticsKey format: IdEquals(Int(1)) or IdEquals(String("abc"))
-                        if let Some(id_start) = param_str.find("Equals(") {
-                            let after_equals = &param_str[id_start + 7..];
-                            // Find the matching closing parenthesis, accounting for nested parentheses
-                            let mut paren_count = 0;
-                            let mut id_end = None;
-                            for (i, ch) in after_equals.char_indices() {
-                                match ch {
-                                    '(' => paren_count += 1,
-                                    ')' => {
-                                        if paren_count == 0 {
-                                            id_end = Some(i);
-                                            break;
-                                        }
-                                        paren_count -= 1;
-                                    }
-                                    _ => {}
-                                }
-                            }
+        .map(|(_, relation_name, _)| {
+            quote! { SetParam::#relation_name(_) => true }
+        })
+        .collect::<Vec<_>>();
 
-                            if let Some(id_end) = id_end {
-                                let key_str = &after_equals[..id_end];
+    let has_many_disconnect_match_arms = has_many_disconnect_variants
+        .iter()
+        .map(|(_, relation_name, _)| {
+            quote! { SetParam::#relation_name(_) => true }
+        })
+        .collect::<Vec<_>>();
 
-                                // Parse using CausticsKey for robust type handling
-                                if let Ok(caustics_key) = key_str.parse::<caustics::CausticsKey>() {
-                                    target_ids.push(caustics_key.to_db_value());
-                                }
-                            }
-                        }
-                    }
-                    target_ids
-                }
+    let target_ids_match_arms = has_many_set_variants
+        .iter()
+        .chain(has_many_disconnect_variants.iter())
+        .map(|(_, variant_name, _)| {
+            quote! {
+                SetParam::#variant_name(unique_params) => unique_params
+                    .iter()
+                    .filter_map(|unique_param| unique_param.primary_key())
+                    .map(|key| key.to_db_value())
+                    .collect()
             }
         })
+        .chain(has_one_connect_variants.iter().map(|(_, variant_name, _)| {
+            quote! {
+                SetParam::#variant_name(unique_param) => unique_param
+                    .primary_key()
+                    .map(|key| key.to_db_value())
+                    .into_iter()
+                    .collect()
+            }
+        }))
         .collect::<Vec<_>>();
 
     // Combine all match arms
@@ -6012,6 +7357,48 @@ pub fn generate_entity(
                 _ => None,
             }
         }
+
+        /// `query` restricted to the rows inside `scope`; unchanged when this entity has no
+        /// field of that name
+        pub(crate) fn apply_row_scope<Q: sea_orm::QueryFilter>(query: Q, scope: Option<&caustics::RowScope>) -> Q {
+            match scope.and_then(|scope| column_from_str(&scope.field).map(|col| (col, scope.value.clone()))) {
+                Some((col, value)) => query.filter(sea_orm::ColumnTrait::eq(&col, value)),
+                None => query,
+            }
+        }
+    };
+
+    let foreign_key_field_names: std::collections::HashSet<&str> = relations
+        .iter()
+        .filter(|relation| matches!(relation.kind, RelationKind::BelongsTo))
+        .flat_map(|relation| relation.foreign_key_fields.iter().map(String::as_str))
+        .collect();
+    let field_infos = fields.iter().map(|field| {
+        let name = field.ident.as_ref().expect("Field has no identifier").to_string();
+        let column_name = crate::primary_key::field_column_name(field);
+        let ty = &field.ty;
+        let rust_type = quote!(#ty).to_string().replace(' ', "");
+        let nullable = is_option(&field.ty);
+        let is_primary_key = crate::primary_key::is_primary_key_field(field);
+        let is_foreign_key = foreign_key_field_names.contains(name.as_str());
+        quote! {
+            caustics::FieldInfo {
+                name: #name,
+                column_name: #column_name,
+                rust_type: #rust_type,
+                nullable: #nullable,
+                is_primary_key: #is_primary_key,
+                is_foreign_key: #is_foreign_key,
+            }
+        }
+    });
+    let fields_fn = quote! {
+        static FIELDS: &[caustics::FieldInfo] = &[#(#field_infos),*];
+
+        /// Every Model field in declaration order, for generic forms and validation
+        pub fn fields() -> &'static [caustics::FieldInfo] {
+            FIELDS
+        }
     };
 
     let namespace_ident = format_ident!("{}", namespace);
@@ -6130,7 +7517,14 @@ pub fn generate_entity(
 
     // Conditionally generate select-related code only when the feature is enabled
     // Use environment variable set by build.rs to check macro crate's own features
-    let select_macro_code = generate_select_code(&all_field_idents_snake);
+    // select! also accepts the relation-existence fields (`has_<relation>`)
+    let selectable_idents: Vec<syn::Ident> = all_field_idents_snake
+        .iter()
+        .chain(existence_field_idents.iter())
+        .cloned()
+        .collect();
+    let select_macro_code = generate_select_code(&selectable_idents);
+    let truncate_code = generate_truncate_code();
 
     let expanded = quote! {
         #[allow(clippy::cmp_owned)]
@@ -6154,7 +7548,9 @@ pub fn generate_entity(
 
         pub struct EntityClient<'a, C: sea_orm::ConnectionTrait> {
             conn: &'a C,
+            read_conn: &'a C,
             database_backend: sea_orm::DatabaseBackend,
+            options: caustics::ClientOptions,
         }
 
         pub fn get_registry<'a>() -> &'a crate::CompositeEntityRegistry {
@@ -6177,22 +7573,31 @@ pub fn generate_entity(
             #(#unique_where_variants,)*
         }
 
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub enum GroupByFieldParam {
             #(#group_by_field_variants,)*
         }
 
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub enum DistinctFieldParam {
             #(#group_by_field_variants,)*
         }
 
         // Scalar field enum alias
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub enum ScalarField {
             #(#group_by_field_variants,)*
         }
 
+        impl caustics::IntoDistinctOnKey<Entity> for ScalarField {
+            fn into_distinct_on_key(self) -> (SimpleExpr, Option<<Entity as EntityTrait>::Column>) {
+                let column = match self {
+                    #(ScalarField::#group_by_field_variants => <Entity as EntityTrait>::Column::#group_by_field_variants,)*
+                };
+                (column.into_simple_expr(), Some(column))
+            }
+        }
+
 
         // Helper to map snake_case field name to ScalarField variant
         pub fn scalar_field_from_str(name: &str) -> Option<ScalarField> {
@@ -6238,12 +7643,19 @@ pub fn generate_entity(
                     database_backend: self.database_backend,
                     reverse_order: self.reverse_order,
                     pending_order_bys: self.pending_order_bys,
-                    pending_nulls: self.pending_nulls,
                     cursor: self.cursor,
                     is_distinct: self.is_distinct,
                     distinct_on_fields: self.distinct_on_fields,
                     distinct_on_columns: None,
-                    skip_is_negative: self.skip_is_negative,
+                    distinct_on_ordered: self.distinct_on_ordered,
+                    invalid_pagination: self.invalid_pagination,
+                    invalid_filter: self.invalid_filter,
+                    sql_comments: self.sql_comments,
+                    max_rows: self.max_rows,
+                    has_limit: self.has_limit,
+                    shape: self.shape,
+                    scope: self.scope,
+                    error_mapper: self.error_mapper,
                     _phantom: std::marker::PhantomData,
                 };
                 let aliases = spec.collect_aliases();
@@ -6284,6 +7696,8 @@ pub fn generate_entity(
                     relations_to_fetch: self.relations_to_fetch,
                     registry: self.registry,
                     database_backend: self.conn.get_database_backend(),
+                    scope: self.scope,
+                    error_mapper: self.error_mapper,
                     _phantom: std::marker::PhantomData,
                 };
                 let aliases = spec.collect_aliases();
@@ -6324,7 +7738,9 @@ pub fn generate_entity(
                     registry: self.registry,
                     database_backend: self.database_backend,
                     pending_order_bys: self.pending_order_bys,
-                    pending_nulls: self.pending_nulls,
+                    invalid_filter: self.invalid_filter,
+                    scope: self.scope,
+                    error_mapper: self.error_mapper,
                     _phantom: std::marker::PhantomData,
                 };
                 let aliases = spec.collect_aliases();
@@ -6440,6 +7856,14 @@ pub fn generate_entity(
 
         #(#field_ops)*
 
+        /// Set params for every non-key field whose value differs between `old` and `new`,
+        /// ready to pass to `update`
+        pub fn diff(old: &Model, new: &Model) -> Vec<SetParam> {
+            let mut changes = Vec::new();
+            #(#diff_checks)*
+            changes
+        }
+
         // Typed conversion of WhereParam list to Filters (no string parsing)
         #[allow(dead_code)]
         pub fn where_params_to_filters(params: Vec<WhereParam>) -> Vec<caustics::Filter> {
@@ -6469,6 +7893,18 @@ pub fn generate_entity(
                     }
                 }
             }
+
+            fn column_expr(
+                &self,
+                backend: sea_orm::DatabaseBackend,
+            ) -> Option<(sea_orm::sea_query::DynIden, sea_orm::sea_query::SimpleExpr)> {
+                let _ = backend;
+                match self {
+                    #(#column_expr_match_arms,)*
+                    #(#from_column_expr_match_arms,)*
+                    _ => None,
+                }
+            }
         }
 
         impl caustics::SetParamInfo for SetParam {
@@ -6479,6 +7915,28 @@ pub fn generate_entity(
                 }
             }
 
+            fn is_has_many_disconnect_operation(&self) -> bool {
+                match self {
+                    #(#has_many_disconnect_match_arms,)*
+                    _ => false,
+                }
+            }
+
+            fn is_has_one_connect_operation(&self) -> bool {
+                match self {
+                    #(#has_one_connect_match_arms,)*
+                    _ => false,
+                }
+            }
+
+            fn is_scalar_set(&self) -> bool {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(SetParam::#all_field_idents(_) => true,)*
+                    _ => false,
+                }
+            }
+
             fn extract_relation_name(&self) -> Option<&'static str> {
                 match self {
                     #(#relation_name_match_arms,)*
@@ -6548,6 +8006,31 @@ pub fn generate_entity(
             }
         }
 
+        impl UniqueWhereParam {
+            /// Columns of the unique constraint this lookup targets; empty if it matches none
+            pub fn unique_columns(&self) -> Vec<<Entity as EntityTrait>::Column> {
+                match self {
+                    #(#unique_columns_match_arms,)*
+                }
+            }
+
+            /// Primary key this lookup matches on, if it is a primary key lookup
+            pub fn primary_key(&self) -> Option<caustics::CausticsKey> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#unique_primary_key_match_arms,)*
+                    _ => None,
+                }
+            }
+        }
+
+        // A single key where a list of them is accepted, e.g. include `order_by`
+        impl From<OrderByParam> for Vec<OrderByParam> {
+            fn from(param: OrderByParam) -> Self {
+                vec![param]
+            }
+        }
+
         impl From<OrderByParam> for (<Entity as EntityTrait>::Column, sea_orm::Order) {
             fn from(param: OrderByParam) -> Self {
                 match param {
@@ -6583,9 +8066,60 @@ pub fn generate_entity(
             }
         }
 
+        pub trait ManyPaginateExt<'a, C: sea_orm::ConnectionTrait> {
+            /// Fetch results `page_size` rows at a time, keyed on the primary key
+            fn paginate(self, page_size: i64) -> caustics::ManyPaginator<'a, C, Entity, ModelWithRelations>;
+        }
+
+        impl<'a, C: sea_orm::ConnectionTrait> ManyPaginateExt<'a, C>
+            for caustics::ManyQueryBuilder<'a, C, Entity, ModelWithRelations>
+        {
+            fn paginate(self, page_size: i64) -> caustics::ManyPaginator<'a, C, Entity, ModelWithRelations> {
+                fn row_cursor(row: &ModelWithRelations) -> caustics::RowCursor {
+                    use sea_orm::IntoSimpleExpr;
+                    vec![#(#row_cursor_parts),*]
+                }
+                caustics::ManyPaginator::new(self, page_size, row_cursor)
+            }
+        }
+
+        pub trait UpsertConflictExt {
+            /// Conflict on `columns` of a partial unique index whose `WHERE` is `predicate`,
+            /// e.g. `on_conflict(vec![Column::Email], Some(vec![deleted_at::is_null()]))`
+            fn on_conflict(self, columns: Vec<<Entity as EntityTrait>::Column>, predicate: Option<Vec<WhereParam>>) -> Self;
+        }
+
+        impl<'a, C: sea_orm::ConnectionTrait> UpsertConflictExt
+            for caustics::UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, SetParam>
+        {
+            fn on_conflict(self, columns: Vec<<Entity as EntityTrait>::Column>, predicate: Option<Vec<WhereParam>>) -> Self {
+                let backend = self.conn.get_database_backend();
+                let predicate = predicate.map(|params| where_params_to_condition(params, backend));
+                self.on_conflict_condition(columns, predicate)
+            }
+        }
+
+        pub trait DeleteFilterExt {
+            /// Only delete if the record also matches `guard`, e.g. `filter(vec![deleted_at::is_null()])`;
+            /// otherwise `exec` fails with `RecordNotFound`
+            fn filter(self, guard: Vec<WhereParam>) -> Self;
+        }
+
+        impl<'a, C: sea_orm::ConnectionTrait> DeleteFilterExt
+            for caustics::DeleteQueryBuilder<'a, C, Entity, ModelWithRelations>
+        {
+            fn filter(self, guard: Vec<WhereParam>) -> Self {
+                let backend = self.conn.get_database_backend();
+                self.filter_condition(where_params_to_condition(guard, backend))
+            }
+        }
+
         // Contribute to prelude module for this entity
         pub mod prelude {
             pub use super::ManyCursorExt;
+            pub use super::UpsertConflictExt;
+            pub use super::DeleteFilterExt;
+            pub use super::ManyPaginateExt;
             pub use super::DistinctFieldsExt;
             pub use super::SelectManyDistinctFieldsExt;
             // AggregateSelectorExt and GroupBySelectorExt removed - use select! syntax instead
@@ -6709,9 +8243,16 @@ pub fn generate_entity(
             pub _params: Vec<SetParam>,
         }
 
-        pub(crate) fn __extract_id(m: &<Entity as sea_orm::EntityTrait>::Model) -> caustics::CausticsKey {
+        #create_deserialize_impl
+
+        #create_from_model_impl
+
+        #model_try_from_json_impl
+
+        pub(crate) fn __extract_id(m: &<Entity as sea_orm::EntityTrait>::Model) -> Result<caustics::CausticsKey, sea_orm::DbErr> {
             use caustics::ToSeaOrmValue;
-            #composite_key_extraction
+            let key: Result<caustics::CausticsKey, caustics::CausticsError> = { #composite_key_extraction };
+            key.map_err(Into::into)
         }
         impl Create {
             pub(crate) fn into_active_model<C: sea_orm::ConnectionTrait>(mut self) -> (ActiveModel, Vec<caustics::DeferredLookup>, Vec<caustics::PostInsertOp<'static>>) {
@@ -6754,6 +8295,15 @@ pub fn generate_entity(
         // Typed distinct extension for ManyQueryBuilder at module scope
         pub trait DistinctFieldsExt<'a, C: sea_orm::ConnectionTrait> {
             fn distinct(self, fields: Vec<ScalarField>) -> Self;
+
+            /// Keep one row per distinct combination of `fields`: the first one according to
+            /// the current `order_by` (primary key breaks ties)
+            fn distinct_on_fields(self, fields: Vec<ScalarField>) -> Self
+            where
+                Self: Sized,
+            {
+                self.distinct(fields)
+            }
         }
 
         impl<'a, C: sea_orm::ConnectionTrait> DistinctFieldsExt<'a, C>
@@ -6772,14 +8322,25 @@ pub fn generate_entity(
                     exprs.push(e);
                     cols.push(c);
                 }
-                self = self.distinct_on(exprs);
-                self.distinct_on_columns(cols)
+                self.distinct_on_fields = Some(exprs);
+                self.distinct_on_columns = Some(cols);
+                self.is_distinct = true;
+                self
             }
         }
 
         // Expose distinct(fields) on SelectManyQueryBuilder as well
         pub trait SelectManyDistinctFieldsExt<'a, C: sea_orm::ConnectionTrait> {
             fn distinct(self, fields: Vec<ScalarField>) -> Self;
+
+            /// Keep one row per distinct combination of `fields`: the first one according to
+            /// the current `order_by` (primary key breaks ties)
+            fn distinct_on_fields(self, fields: Vec<ScalarField>) -> Self
+            where
+                Self: Sized,
+            {
+                self.distinct(fields)
+            }
         }
 
         impl<'a, C: sea_orm::ConnectionTrait> SelectManyDistinctFieldsExt<'a, C>
@@ -6807,52 +8368,135 @@ pub fn generate_entity(
 
         impl<'a, C: sea_orm::ConnectionTrait + sea_orm::TransactionTrait> EntityClient<'a, C> {
             pub fn new(conn: &'a C, database_backend: sea_orm::DatabaseBackend) -> Self {
-                Self { conn, database_backend }
+                Self { conn, read_conn: conn, database_backend, options: caustics::ClientOptions::default() }
+            }
+
+            /// Client whose read queries (`find_*`, `count`, `aggregate`, `group_by`) run on
+            /// `read_conn` while writes stay on `conn`
+            pub fn with_read_conn(conn: &'a C, read_conn: &'a C, database_backend: sea_orm::DatabaseBackend) -> Self {
+                Self { conn, read_conn, database_backend, options: caustics::ClientOptions::default() }
+            }
+
+            /// Apply a client's settings: its row scope (every query is restricted to rows whose
+            /// `scope.field` equals `scope.value`, and every create sets that field; entities
+            /// without the field are left unscoped), SQL comments, row cap and error mapper
+            pub fn with_options(mut self, options: caustics::ClientOptions) -> Self {
+                self.options = options;
+                self
+            }
+
+            fn scope_column(&self) -> Option<(<Entity as EntityTrait>::Column, sea_orm::Value)> {
+                let scope = self.options.scope.as_ref()?;
+                column_from_str(&scope.field).map(|col| (col, scope.value.clone()))
+            }
+
+            fn scoped(&self, condition: Condition) -> Condition {
+                match self.scope_column() {
+                    Some((col, value)) => Condition::all().add(condition).add(col.eq(value)),
+                    None => condition,
+                }
+            }
+
+            fn stamp_scope(&self, model: &mut ActiveModel) {
+                if let Some((col, value)) = self.scope_column() {
+                    sea_orm::ActiveModelTrait::set(model, col, value);
+                }
             }
 
             pub fn find_unique(&self, condition: UniqueWhereParam) -> caustics::UniqueQueryBuilder<'a, C, Entity, ModelWithRelations> {
+                // Each unique variant always renders the same lookup
+                let shape = self.cache_shape(|state| {
+                    std::hash::Hash::hash(&std::mem::discriminant(&condition), state)
+                });
+                self.find_by_condition(condition.into(), shape)
+            }
+
+            /// Statement-cache shape of a query built by this client, seeded by `hash`
+            fn cache_shape(&self, hash: impl FnOnce(&mut std::collections::hash_map::DefaultHasher)) -> Option<u64> {
+                use std::hash::Hasher;
+                let mut hasher = caustics::statement_cache::shape_hasher();
+                hash(&mut hasher);
+                // The tenant scope adds one more column comparison
+                let scope_column = self.scope_column().map(|(col, _)| sea_orm::IdenStatic::as_str(&col).to_string());
+                std::hash::Hash::hash(&scope_column, &mut hasher);
+                Some(hasher.finish())
+            }
+
+            fn find_by_condition(&self, condition: Condition, shape: Option<u64>) -> caustics::UniqueQueryBuilder<'a, C, Entity, ModelWithRelations> {
                 let registry = get_registry();
                 caustics::UniqueQueryBuilder {
-                    query: <Entity as EntityTrait>::find().filter::<Condition>(condition.clone().into()),
-                    conn: self.conn,
+                    query: <Entity as EntityTrait>::find().filter::<Condition>(self.scoped(condition)),
+                    conn: self.read_conn,
                     relations_to_fetch: vec![],
                     registry,
+                    sql_comments: self.options.sql_comments,
+                    shape,
+                    scope: self.options.scope.clone(),
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
 
             pub fn find_first(&self, conditions: Vec<WhereParam>) -> caustics::FirstQueryBuilder<'a, C, Entity, ModelWithRelations> {
+                let invalid_filter = unsupported_filter(&conditions, self.database_backend);
+                let shape = self.cache_shape(|state| hash_filter_shape(&conditions, state));
                 let registry = get_registry();
-                let query = <Entity as EntityTrait>::find().filter::<Condition>(where_params_to_condition(conditions, self.database_backend));
+                let query = <Entity as EntityTrait>::find().filter::<Condition>(self.scoped(where_params_to_condition(conditions, self.database_backend)));
                 caustics::FirstQueryBuilder {
                     query,
-                    conn: self.conn,
+                    conn: self.read_conn,
                     relations_to_fetch: vec![],
                     registry,
                     database_backend: self.database_backend,
                     pending_order_bys: Vec::new(),
-                    pending_nulls: None,
+                    sql_comments: self.options.sql_comments,
+                    invalid_filter,
+                    shape,
+                    scope: self.options.scope.clone(),
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
 
+            /// The matching row with the greatest `column`, e.g. the newest by
+            /// `user::created_at::column()`; rows where it is NULL come last
+            pub fn find_latest<T>(&self, conditions: Vec<WhereParam>, column: caustics::TypedColumn<Entity, T>) -> caustics::FirstQueryBuilder<'a, C, Entity, ModelWithRelations> {
+                self.find_first(conditions)
+                    .order_by(((column.column, caustics::SortOrder::Desc), caustics::NullsOrder::Last))
+            }
+
+            /// The matching row with the smallest `column`; rows where it is NULL come last
+            pub fn find_earliest<T>(&self, conditions: Vec<WhereParam>, column: caustics::TypedColumn<Entity, T>) -> caustics::FirstQueryBuilder<'a, C, Entity, ModelWithRelations> {
+                self.find_first(conditions)
+                    .order_by(((column.column, caustics::SortOrder::Asc), caustics::NullsOrder::Last))
+            }
+
             pub fn find_many(&self, conditions: Vec<WhereParam>) -> caustics::ManyQueryBuilder<'a, C, Entity, ModelWithRelations> {
+                let invalid_filter = unsupported_filter(&conditions, self.database_backend);
+                let shape = self.cache_shape(|state| hash_filter_shape(&conditions, state));
                 let registry = get_registry();
-                let query = <Entity as EntityTrait>::find().filter::<Condition>(where_params_to_condition(conditions, self.database_backend));
+                let query = <Entity as EntityTrait>::find().filter::<Condition>(self.scoped(where_params_to_condition(conditions, self.database_backend)));
                 caustics::ManyQueryBuilder {
                     query,
-                    conn: self.conn,
+                    conn: self.read_conn,
                     relations_to_fetch: vec![],
                     registry,
                     database_backend: self.database_backend,
                     reverse_order: false,
                     pending_order_bys: Vec::new(),
-                    pending_nulls: None,
                     cursor: None,
                     is_distinct: false,
                     distinct_on_fields: None,
                     distinct_on_columns: None,
-                    skip_is_negative: false,
+                    distinct_on_ordered: false,
+                    invalid_pagination: None,
+                    sql_comments: self.options.sql_comments,
+                    max_rows: self.options.max_rows,
+                    has_limit: false,
+                    invalid_filter,
+                    shape,
+                    scope: self.options.scope.clone(),
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
@@ -6869,7 +8513,9 @@ pub fn generate_entity(
                     };
                     exprs.push(e);
                 }
-                builder.distinct_on(exprs)
+                builder.distinct_on_fields = Some(exprs);
+                builder.is_distinct = true;
+                builder
             }
 
             // NOTE: Aggregation and distinct builder facades will be added incrementally
@@ -6877,21 +8523,39 @@ pub fn generate_entity(
 
 
             pub fn count(&self, conditions: Vec<WhereParam>) -> caustics::CountQueryBuilder<'a, C, Entity> {
-                let condition = where_params_to_condition(conditions, self.database_backend);
+                let invalid_filter = unsupported_filter(&conditions, self.database_backend);
+                let condition = self.scoped(where_params_to_condition(conditions, self.database_backend));
                 caustics::CountQueryBuilder {
                     condition,
-                    conn: self.conn,
+                    conn: self.read_conn,
+                    invalid_filter,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
 
+            /// Approximate number of rows, for dashboards that only need a ballpark figure.
+            /// On Postgres it is the planner's estimate from `pg_class.reltuples`, which is only
+            /// as fresh as the last `ANALYZE`; other backends and scoped clients count exactly.
+            pub async fn count_estimate(&self) -> Result<i64, sea_orm::DbErr> {
+                if self.options.scope.is_some() {
+                    return self.count(vec![]).exec().await;
+                }
+                caustics::count_estimate::<C, Entity>(self.read_conn, self.options.error_mapper.clone()).await
+            }
+
             pub fn aggregate(&self, conditions: Vec<WhereParam>) -> caustics::AggregateQueryBuilder<'a, C, Entity> {
-                let condition = where_params_to_condition(conditions, self.database_backend);
+                let invalid_filter = unsupported_filter(&conditions, self.database_backend);
+                let condition = self.scoped(where_params_to_condition(conditions, self.database_backend));
                 caustics::AggregateQueryBuilder {
                     condition,
-                    conn: self.conn,
+                    conn: self.read_conn,
                     selections: caustics::query_builders::aggregate::AggregateSelections::default(),
                     aggregates: Vec::new(),
+                    invalid_filter,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
@@ -6906,7 +8570,8 @@ pub fn generate_entity(
                 having: Option<sea_orm::sea_query::Condition>,
             ) -> caustics::GroupByQueryBuilder<'a, C, Entity> {
                 use sea_orm::IntoSimpleExpr;
-                let condition = where_params_to_condition(r#where, self.database_backend);
+                let invalid_filter = unsupported_filter(&r#where, self.database_backend);
+                let condition = self.scoped(where_params_to_condition(r#where, self.database_backend));
                 let mut exprs: Vec<SimpleExpr> = Vec::with_capacity(by.len());
                 let mut group_cols: Vec<String> = Vec::with_capacity(by.len());
                 for b in by {
@@ -6920,7 +8585,7 @@ pub fn generate_entity(
                 }
                 let mut builder = caustics::GroupByQueryBuilder {
                     condition,
-                    conn: self.conn,
+                    conn: self.read_conn,
                     group_by_exprs: exprs,
                     group_by_columns: group_cols,
                     having: Vec::new(),
@@ -6929,6 +8594,10 @@ pub fn generate_entity(
                     take: None,
                     skip: None,
                     aggregates: Vec::new(),
+                    rollup: false,
+                    invalid_filter,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 };
                 for (field, dir) in order_by {
@@ -6948,6 +8617,69 @@ pub fn generate_entity(
                 builder
             }
 
+            /// Group rows matching `conditions` by the text value at `path` inside a JSON column,
+            /// e.g. `group_by_json(post::Column::CustomData, vec!["category"], vec![]).count("count")`.
+            /// The key is read back under the path joined with `_`.
+            pub fn group_by_json<P: Into<String>>(
+                &self,
+                column: <Entity as EntityTrait>::Column,
+                path: impl IntoIterator<Item = P>,
+                conditions: Vec<WhereParam>,
+            ) -> caustics::GroupByQueryBuilder<'a, C, Entity> {
+                self.group_by(vec![], conditions, vec![], None, None, None)
+                    .by_json(column, path)
+            }
+
+            /// Min, max, average and NULL count of every numeric field, in a single query
+            pub fn profile(&self) -> caustics::ProfileQueryBuilder<'a, C, Entity> {
+                caustics::ProfileQueryBuilder {
+                    condition: self.scoped(Condition::all()),
+                    conn: self.read_conn,
+                    columns: vec![#((#profile_field_names, <Entity as EntityTrait>::Column::#profile_field_variants),)*],
+                    error_mapper: self.options.error_mapper.clone(),
+                }
+            }
+
+            /// Number of rows per value of `field` among rows matching `conditions`, e.g. posts
+            /// per author with `count_by(post::ScalarField::UserId, vec![])`. Rows where `field`
+            /// is NULL are not counted.
+            pub fn count_by(&self, field: ScalarField, conditions: Vec<WhereParam>) -> caustics::CountByQueryBuilder<'a, C, Entity> {
+                use sea_orm::IntoSimpleExpr;
+                let (column, name, read_key): (<Entity as EntityTrait>::Column, &'static str, caustics::ReadGroupKey) = match field {
+                    #(ScalarField::#group_by_field_variants => (
+                        <Entity as EntityTrait>::Column::#group_by_field_variants,
+                        #count_by_field_names,
+                        |row, alias| {
+                            use caustics::ToSeaOrmValue;
+                            let value = row.try_get::<Option<#count_by_key_types>>("", alias)?;
+                            Ok(value.and_then(|v| caustics::CausticsKey::from_db_value(&v.to_sea_orm_value())))
+                        },
+                    ),)*
+                };
+                let invalid_filter = unsupported_filter(&conditions, self.database_backend);
+                let condition = Condition::all()
+                    .add(self.scoped(where_params_to_condition(conditions, self.database_backend)))
+                    .add(column.is_not_null());
+                let group_by = caustics::GroupByQueryBuilder {
+                    condition,
+                    conn: self.read_conn,
+                    group_by_exprs: vec![column.into_simple_expr()],
+                    group_by_columns: vec!["key".to_string()],
+                    having: Vec::new(),
+                    having_condition: None,
+                    order_by: vec![(column.into_simple_expr(), sea_orm::Order::Asc)],
+                    take: None,
+                    skip: None,
+                    aggregates: vec![(sea_query::Expr::cust("COUNT(*)"), "count")],
+                    rollup: false,
+                    invalid_filter,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
+                    _phantom: std::marker::PhantomData,
+                };
+                caustics::CountByQueryBuilder { group_by, field: name, read_key }
+            }
+
             pub fn group_by_order_by(
                 &self,
                 builder: caustics::GroupByQueryBuilder<'a, C, Entity>,
@@ -6968,42 +8700,71 @@ pub fn generate_entity(
 
 
             pub fn create(&self, #(#required_fn_args,)* #(#foreign_key_relation_args,)* _params: Vec<SetParam>) -> caustics::CreateQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations> {
-                let create = Create {
+                self.create_from(Create {
                     #(#required_inits,)*
                     #(#foreign_key_relation_inits,)*
                     _params,
-                };
-                let (model, deferred_lookups, post_ops) = create.into_active_model::<C>();
+                })
+            }
+
+            /// Create from an already assembled `Create`, e.g. one deserialized from a request body
+            pub fn create_from(&self, create: Create) -> caustics::CreateQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations> {
+                let (mut model, deferred_lookups, post_ops) = create.into_active_model::<C>();
+                self.stamp_scope(&mut model);
                 let registry = get_registry();
                 caustics::CreateQueryBuilder {
                     model,
                     conn: self.conn,
                     deferred_lookups,
                     post_insert_ops: post_ops,
-                    id_extractor: (__extract_id as fn(&<Entity as sea_orm::EntityTrait>::Model) -> caustics::CausticsKey),
+                    id_extractor: (__extract_id as fn(&<Entity as sea_orm::EntityTrait>::Model) -> Result<caustics::CausticsKey, sea_orm::DbErr>),
                     relations_to_fetch: vec![],
                     registry,
+                    scope: self.options.scope.clone(),
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
 
+            /// Copy the rows matched by `source` into this table in a single `INSERT ... SELECT`,
+            /// matching columns by name
+            pub fn create_from_select<'b, SC, SourceEntity, SourceModelWithRelations>(
+                &self,
+                source: caustics::ManyQueryBuilder<'b, SC, SourceEntity, SourceModelWithRelations>,
+            ) -> caustics::InsertSelectQueryBuilder<'a, C, Entity>
+            where
+                SC: sea_orm::ConnectionTrait,
+                SourceEntity: sea_orm::EntityTrait,
+                SourceModelWithRelations: caustics::FromModel<<SourceEntity as sea_orm::EntityTrait>::Model>
+                    + caustics::HasRelationMetadata<SourceModelWithRelations>
+                    + caustics::ApplyNestedIncludes<SC>
+                    + Send
+                    + 'static,
+            {
+                caustics::InsertSelectQueryBuilder::new(self.conn, source)
+            }
+
             pub fn create_many(&self, creates: Vec<Create>) -> caustics::CreateManyQueryBuilder<'a, C, Entity, ActiveModel>
             where
                 C: sea_orm::ConnectionTrait,
             {
                 let mut items = Vec::with_capacity(creates.len());
                 for c in creates {
-                    let (model, deferred_lookups, post_ops) = c.into_active_model::<C>();
+                    let (mut model, deferred_lookups, post_ops) = c.into_active_model::<C>();
+                    self.stamp_scope(&mut model);
                     items.push((
                         model,
                         deferred_lookups,
                         post_ops,
-                        (__extract_id as fn(&<Entity as sea_orm::EntityTrait>::Model) -> caustics::CausticsKey),
+                        (__extract_id as fn(&<Entity as sea_orm::EntityTrait>::Model) -> Result<caustics::CausticsKey, sea_orm::DbErr>),
                     ));
                 }
                 caustics::CreateManyQueryBuilder {
                     items,
                     conn: self.conn,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
@@ -7016,7 +8777,7 @@ pub fn generate_entity(
                     + 'static,
             {
                 let metadata_provider = get_registry();
-                let cond: Condition = condition.into();
+                let cond: Condition = self.scoped(condition.into());
                 let cond_arc = std::sync::Arc::new(cond.clone());
                 let resolver: Box<
                     dyn for<'b> Fn(
@@ -7065,11 +8826,16 @@ pub fn generate_entity(
                         relations_to_fetch: vec![],
                         registry,
                         entity_id_resolver: Some(resolver),
+                        scope: self.options.scope.clone(),
+                        sql_comments: self.options.sql_comments,
+                        error_mapper: self.options.error_mapper.clone(),
                         _phantom: std::marker::PhantomData,
                     })
                 } else {
                     // Rewrite PK-equals connects into direct field sets to ensure DB updates include FK column
                     let mut normal_changes: Vec<SetParam> = Vec::new();
+                    // Keys that don't fit their column fail the update when it runs
+                    let mut deferred_lookups: Vec<caustics::DeferredLookup> = Vec::new();
                     for param in changes {
                         match param {
                             #(#relation_connect_pk_convert_match_arms,)*
@@ -7081,9 +8847,12 @@ pub fn generate_entity(
                         condition: cond,
                         changes: normal_changes,
                         conn: self.conn,
-                        deferred_lookups: Vec::new(),
+                        deferred_lookups,
                         relations_to_fetch: vec![],
                         registry,
+                        scope: self.options.scope.clone(),
+                        sql_comments: self.options.sql_comments,
+                        error_mapper: self.options.error_mapper.clone(),
                         _phantom: std::marker::PhantomData,
                     })
                 }
@@ -7093,48 +8862,87 @@ pub fn generate_entity(
             where
                 C: sea_orm::ConnectionTrait,
             {
-                let cond = where_params_to_condition(conditions, self.database_backend);
+                let invalid_filter = unsupported_filter(&conditions, self.database_backend);
+                let cond = self.scoped(where_params_to_condition(conditions, self.database_backend));
                 caustics::UpdateManyQueryBuilder {
                     condition: cond,
                     changes,
                     conn: self.conn,
+                    invalid_filter,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
 
             pub fn delete(&self, condition: UniqueWhereParam) -> caustics::DeleteQueryBuilder<'a, C, Entity, ModelWithRelations> {
                 caustics::DeleteQueryBuilder {
-                    condition: condition.into(),
+                    condition: self.scoped(condition.into()),
                     conn: self.conn,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
 
             pub fn delete_many(&self, conditions: Vec<WhereParam>) -> caustics::DeleteManyQueryBuilder<'a, C, Entity> {
-                let cond = where_params_to_condition(conditions, self.database_backend);
+                let invalid_filter = unsupported_filter(&conditions, self.database_backend);
+                let cond = self.scoped(where_params_to_condition(conditions, self.database_backend));
                 caustics::DeleteManyQueryBuilder {
                     condition: cond,
                     conn: self.conn,
+                    invalid_filter,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
 
+            #truncate_code
+
             pub fn upsert(&self, condition: UniqueWhereParam, create: Create, update: Vec<SetParam>) -> caustics::UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, SetParam> {
-                let (model, deferred_lookups, post_insert_ops) = create.into_active_model::<C>();
+                let (mut model, deferred_lookups, post_insert_ops) = create.into_active_model::<C>();
+                self.stamp_scope(&mut model);
+                // A single INSERT ... ON CONFLICT needs a real unique constraint, plain column sets
+                // and no follow-up writes; scoped clients keep the filtered find-then-write path
+                let conflict_columns = condition.unique_columns();
+                let native_conflict = (!conflict_columns.is_empty()
+                    && self.options.scope.is_none()
+                    && post_insert_ops.is_empty()
+                    && update.iter().all(<SetParam as caustics::SetParamInfo>::is_scalar_set))
+                    .then_some(conflict_columns);
                 caustics::UpsertQueryBuilder {
-                    condition: condition.into(),
+                    native_conflict,
+                    conflict_predicate: None,
+                    condition: self.scoped(condition.into()),
                     create: (
                         model,
                         deferred_lookups,
                         post_insert_ops,
-                        (__extract_id as fn(&<Entity as sea_orm::EntityTrait>::Model) -> caustics::CausticsKey),
+                        (__extract_id as fn(&<Entity as sea_orm::EntityTrait>::Model) -> Result<caustics::CausticsKey, sea_orm::DbErr>),
                     ),
                     update,
+                    update_only: None,
                     conn: self.conn,
+                    sql_comments: self.options.sql_comments,
+                    error_mapper: self.options.error_mapper.clone(),
                     _phantom: std::marker::PhantomData,
                 }
             }
 
+            /// Upsert each `(condition, create, update)` in one transaction, as a single
+            /// multi-row `INSERT ... ON CONFLICT` where the backend and rows allow it
+            pub fn upsert_many(&self, items: Vec<(UniqueWhereParam, Create, Vec<SetParam>)>) -> caustics::UpsertManyQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, SetParam> {
+                caustics::UpsertManyQueryBuilder {
+                    items: items
+                        .into_iter()
+                        .map(|(condition, create, update)| self.upsert(condition, create, update))
+                        .collect(),
+                    conn: self.conn,
+                    error_mapper: self.options.error_mapper.clone(),
+                }
+            }
+
         pub async fn _batch<Container>(
             &self,
             queries: Container,
@@ -7162,9 +8970,15 @@ pub fn generate_entity(
         // Include the generated relation submodules
         #relation_submodules
 
+        // Polymorphic relations dispatched on a discriminator column
+        #polymorphic_code
+
         // Generate column_from_str function
         #column_from_str_fn
 
+        // Field reflection
+        #fields_fn
+
         // --- Begin entity fetcher and registry generation ---
         pub struct EntityFetcherImpl;
 
@@ -7214,6 +9028,8 @@ pub fn generate_entity(
                     }
                 })
             }
+
+            #fetch_by_primary_keys_fn
         }
 
         // Implement FromModel<Model> for Model
@@ -1,10 +1,13 @@
 mod code_gen;
+mod polymorphic;
+mod polymorphic_attr;
 mod relation_extraction;
 mod relation_submodules;
 mod table_name;
 mod types;
 
 pub use code_gen::generate_entity;
+pub use polymorphic::{extract_polymorphic_relations, generate_polymorphic_code};
 pub use relation_extraction::extract_relations;
 pub use relation_submodules::generate_relation_submodules;
 pub use types::{Relation, RelationKind};
@@ -0,0 +1,202 @@
+use super::polymorphic_attr::{parse_polymorphic_doc, PolymorphicAttr};
+use crate::common::is_option;
+use heck::{ToPascalCase, ToSnakeCase};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// A relation whose target entity is picked per row by a type-discriminator column.
+///
+/// Declared on the discriminator field of the Model:
+/// `/// #[caustics(polymorphic(name = "commentable", id = "commentable_id", targets(post = "super::post", user = "super::user")))]`
+#[derive(Debug, Clone)]
+pub struct PolymorphicRelation {
+    pub name: String,
+    pub discriminator_field: syn::Ident,
+    pub discriminator_is_optional: bool,
+    pub id_field: syn::Ident,
+    pub id_is_optional: bool,
+    /// (discriminator value, target module path)
+    pub targets: Vec<(String, syn::Path)>,
+}
+
+/// Parse the `polymorphic(...)` doc attributes on the Model's fields
+pub fn extract_polymorphic_relations(
+    fields: &[&syn::Field],
+) -> Result<Vec<PolymorphicRelation>, TokenStream> {
+    let mut relations = Vec::new();
+    for field in fields {
+        for attr in &field.attrs {
+            let syn::Meta::NameValue(nv) = &attr.meta else { continue };
+            if !nv.path.is_ident("doc") {
+                continue;
+            }
+            let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = &nv.value else {
+                continue;
+            };
+            let field_ident = field.ident.clone().expect("Field has no identifier");
+            let Some(parsed) = parse_polymorphic_doc(&lit.value(), &field_ident.to_string()) else {
+                continue;
+            };
+            let relation = parsed
+                .and_then(|attr| resolve_relation(attr, field, fields))
+                .map_err(|message| quote! { compile_error!(#message); })?;
+            relations.push(relation);
+        }
+    }
+    Ok(relations)
+}
+
+fn resolve_relation(
+    attr: PolymorphicAttr,
+    field: &syn::Field,
+    fields: &[&syn::Field],
+) -> Result<PolymorphicRelation, String> {
+    let PolymorphicAttr { name, id, targets } = attr;
+    let id_field = fields
+        .iter()
+        .find(|f| f.ident.as_ref().is_some_and(|ident| *ident == id))
+        .ok_or_else(|| format!("polymorphic relation `{}` refers to unknown field `{}`", name, id))?;
+
+    Ok(PolymorphicRelation {
+        name,
+        discriminator_is_optional: is_option(&field.ty),
+        discriminator_field: field.ident.clone().expect("Field has no identifier"),
+        id_field: id_field.ident.clone().expect("Field has no identifier"),
+        id_is_optional: is_option(&id_field.ty),
+        targets,
+    })
+}
+
+/// Generate the target enum and the `fetch_<name>` client methods for each polymorphic relation
+pub fn generate_polymorphic_code(relations: &[PolymorphicRelation]) -> TokenStream {
+    let items = relations.iter().map(|relation| {
+        let enum_ident = format_ident!("{}", relation.name.to_pascal_case());
+        let fetch_ident = format_ident!("fetch_{}", relation.name.to_snake_case());
+        let fetch_many_ident = format_ident!("fetch_{}_many", relation.name.to_snake_case());
+        let discriminator = &relation.discriminator_field;
+        let id_field = &relation.id_field;
+        let relation_name = &relation.name;
+
+        let variants = relation.targets.iter().map(|(tag, path)| {
+            let variant = format_ident!("{}", tag.to_pascal_case());
+            quote! { #variant(Box<#path::ModelWithRelations>) }
+        });
+        let dispatch_arms = relation.targets.iter().map(|(tag, path)| {
+            let variant = format_ident!("{}", tag.to_pascal_case());
+            let entity_name = path
+                .segments
+                .last()
+                .expect("Invalid target path")
+                .ident
+                .to_string()
+                .to_lowercase();
+            quote! {
+                #tag => {
+                    let fetcher = caustics::EntityRegistry::<C>::get_fetcher(registry, #entity_name)
+                        .ok_or_else(|| caustics::CausticsError::EntityFetcherMissing {
+                            entity: #entity_name.to_string(),
+                        })?;
+                    let mut targets = std::collections::HashMap::new();
+                    for (key, boxed) in fetcher.fetch_by_primary_keys(self.read_conn, keys, self.options.scope.as_ref()).await? {
+                        let model = boxed.downcast::<#path::ModelWithRelations>().map_err(|_| {
+                            caustics::CausticsError::QueryValidation {
+                                message: format!("fetcher for '{}' returned an unexpected type", #entity_name),
+                            }
+                        })?;
+                        targets.insert(key, #enum_ident::#variant(model));
+                    }
+                    targets
+                }
+            }
+        });
+
+        let tag_expr = if relation.discriminator_is_optional {
+            quote! { model.#discriminator.as_deref() }
+        } else {
+            quote! { Some(model.#discriminator.as_str()) }
+        };
+        // Keys go through the database value on both sides, so they compare like the columns do
+        let key_expr = if relation.id_is_optional {
+            quote! {
+                model.#id_field.as_ref().and_then(|id| {
+                    caustics::CausticsKey::from_db_value(&caustics::ToSeaOrmValue::to_sea_orm_value(id))
+                })
+            }
+        } else {
+            quote! {
+                caustics::CausticsKey::from_db_value(&caustics::ToSeaOrmValue::to_sea_orm_value(&model.#id_field))
+            }
+        };
+        let doc = format!(
+            "Target of the polymorphic `{}` relation, chosen by `{}`",
+            relation_name, discriminator
+        );
+        let fetch_doc = format!(
+            "Load the `{}` target of `model` from the entity named by its `{}`",
+            relation_name, discriminator
+        );
+        let fetch_many_doc = format!(
+            "Load the `{}` target of each of `models`, in order, with one query per target entity",
+            relation_name
+        );
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, PartialEq)]
+            pub enum #enum_ident {
+                #(#variants,)*
+            }
+
+            impl<'a, C: sea_orm::ConnectionTrait + sea_orm::TransactionTrait> EntityClient<'a, C> {
+                #[doc = #fetch_doc]
+                pub async fn #fetch_ident(&self, model: &ModelWithRelations) -> Result<Option<#enum_ident>, sea_orm::DbErr> {
+                    Ok(self.#fetch_many_ident(std::slice::from_ref(model)).await?.pop().flatten())
+                }
+
+                #[doc = #fetch_many_doc]
+                ///
+                /// An unknown discriminator on any of them fails the whole call.
+                pub async fn #fetch_many_ident(&self, models: &[ModelWithRelations]) -> Result<Vec<Option<#enum_ident>>, sea_orm::DbErr> {
+                    let mut row_keys = Vec::with_capacity(models.len());
+                    let mut keys_by_tag: std::collections::HashMap<&str, Vec<caustics::CausticsKey>> =
+                        std::collections::HashMap::new();
+                    for model in models {
+                        let row_key = (#tag_expr).zip(#key_expr);
+                        if let Some((tag, key)) = &row_key {
+                            keys_by_tag.entry(*tag).or_default().push(key.clone());
+                        }
+                        row_keys.push(row_key);
+                    }
+
+                    let registry = get_registry();
+                    let mut loaded = std::collections::HashMap::new();
+                    for (tag, keys) in keys_by_tag {
+                        let targets = match tag {
+                            #(#dispatch_arms)*
+                            other => {
+                                return Err(caustics::CausticsError::QueryValidation {
+                                    message: format!(
+                                        "unknown {} discriminator '{}' for relation '{}'",
+                                        stringify!(#discriminator),
+                                        other,
+                                        #relation_name
+                                    ),
+                                }
+                                .into())
+                            }
+                        };
+                        loaded.insert(tag, targets);
+                    }
+                    Ok(row_keys
+                        .into_iter()
+                        .map(|row_key| {
+                            let (tag, key) = row_key?;
+                            loaded.get(tag)?.get(&key).cloned()
+                        })
+                        .collect())
+                }
+            }
+        }
+    });
+    quote! { #(#items)* }
+}
@@ -0,0 +1,72 @@
+//! Parser for the `polymorphic(...)` doc attribute.
+//!
+//! caustics-build includes this file through `#[path]`, so the entity macro and the client
+//! generator read the attribute the same way; it must only depend on `syn`.
+
+/// `#[caustics(polymorphic(name = "...", id = "...", targets(tag = "super::entity")))]` as written
+pub struct PolymorphicAttr {
+    pub name: String,
+    /// Field holding the target's primary key
+    pub id: String,
+    /// (discriminator value, target module path)
+    pub targets: Vec<(String, syn::Path)>,
+}
+
+/// Parse one doc-comment line of the field named `field`; `None` when the line is not a
+/// `polymorphic` attribute
+pub fn parse_polymorphic_doc(value: &str, field: &str) -> Option<Result<PolymorphicAttr, String>> {
+    let value = value.trim();
+    if !(value.starts_with("#[caustics(") && value.contains("polymorphic")) {
+        return None;
+    }
+    Some(parse_attr(value, field))
+}
+
+fn parse_attr(value: &str, field: &str) -> Result<PolymorphicAttr, String> {
+    let inner = value
+        .strip_prefix("#[")
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or_else(|| format!("malformed caustics attribute on `{}`", field))?;
+    let meta: syn::Meta = syn::parse_str(inner).map_err(|e| e.to_string())?;
+    let syn::Meta::List(list) = meta else {
+        return Err(format!("malformed caustics attribute on `{}`", field));
+    };
+
+    let mut name = None;
+    let mut id = None;
+    let mut targets = Vec::new();
+    list.parse_nested_meta(|outer| {
+        if !outer.path.is_ident("polymorphic") {
+            return Ok(());
+        }
+        outer.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else if meta.path.is_ident("id") {
+                id = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else if meta.path.is_ident("targets") {
+                meta.parse_nested_meta(|target| {
+                    let tag = target
+                        .path
+                        .get_ident()
+                        .ok_or_else(|| target.error("expected a discriminator value"))?
+                        .to_string();
+                    let path = target.value()?.parse::<syn::LitStr>()?.parse::<syn::Path>()?;
+                    targets.push((tag, path));
+                    Ok(())
+                })?;
+            } else {
+                return Err(meta.error("unknown polymorphic option"));
+            }
+            Ok(())
+        })
+    })
+    .map_err(|e| e.to_string())?;
+
+    let name = name.ok_or_else(|| format!("polymorphic relation on `{}` needs `name`", field))?;
+    let id = id.ok_or_else(|| format!("polymorphic relation `{}` needs `id`", name))?;
+    if targets.is_empty() {
+        return Err(format!("polymorphic relation `{}` needs at least one target", name));
+    }
+    Ok(PolymorphicAttr { name, id, targets })
+}
@@ -60,8 +60,8 @@ pub fn extract_relations(
             // Process all sea_orm attributes for this variant
             for attrs in relation_attrs {
                 let mut belongs_to_target = None;
-                let mut from_field = None;
-                let mut to_field = None;
+                let mut from_fields: Vec<String> = Vec::new();
+                let mut to_fields: Vec<String> = Vec::new();
                 
                 for meta in attrs {
                     if let syn::Meta::NameValue(nv) = &meta {
@@ -86,12 +86,12 @@ pub fn extract_relations(
                             }
                             Some("from") => {
                                 if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = &nv.value {
-                                    from_field = Some(extract_field_name(&lit.value()));
+                                    from_fields = extract_field_names(&lit.value());
                                 }
                             }
                             Some("to") => {
                                 if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = &nv.value {
-                                    to_field = Some(extract_field_name(&lit.value()));
+                                    to_fields = extract_field_names(&lit.value());
                                 }
                             }
                             Some("nullable") => {
@@ -109,47 +109,54 @@ pub fn extract_relations(
                     }
                 }
                 
-                // If we found a complete relation (belongs_to or has_many), add it to the composite fields
-                if let (Some(from), Some(to), Some(target)) = (from_field, to_field, belongs_to_target) {
+                // If we found a complete relation (belongs_to or has_many), add it to the composite fields.
+                // Tuple columns (`from = "(Column::A, Column::B)"`) yield one pair per column.
+                if let Some(target) = belongs_to_target.filter(|_| {
+                    !from_fields.is_empty() && from_fields.len() == to_fields.len()
+                }) {
                     relation.target = target;
-                    // from and to are already Column enum variant names (PascalCase)
-                    let from_column_variant = from;
-                    let to_column_variant = to;
-                    let from_snake = from_column_variant.to_snake_case();
-                    let to_snake = to_column_variant.to_snake_case();
+                    for (from, to) in from_fields.into_iter().zip(to_fields) {
+                        // from and to are already Column enum variant names (PascalCase)
+                        let from_column_variant = from;
+                        let to_column_variant = to;
+                        let from_snake = from_column_variant.to_snake_case();
+                        let to_snake = to_column_variant.to_snake_case();
                     
-                    // For belongs_to: from=FK in current entity, to=PK in target entity
-                    // For has_many: from=PK in current entity, to=FK in target entity
-                    if relation.kind == RelationKind::BelongsTo {
-                        relation.foreign_key_fields.push(from_snake.clone());
-                        relation.target_primary_key_fields.push(to_snake.clone());
-                        relation.foreign_key_columns.push(from_column_variant.clone());
-                        relation.target_primary_key_columns.push(to_column_variant.clone());
-                    } else {
-                        // For has_many, store in both places for compatibility
-                        relation.foreign_key_field = Some(to_snake.clone());
-                        relation.foreign_key_column = Some(to_column_variant.clone());
-                        relation.primary_key_field = Some(from_snake.clone());
-                        // Also populate the vec fields for code generation compatibility
-                        relation.foreign_key_fields.push(to_snake.clone());
-                        relation.foreign_key_columns.push(to_column_variant.clone());
-                        // For has_many, the "to" field is the target's FK, and "from" is current entity's PK
-                        relation.target_primary_key_fields.push(from_snake.clone());
-                    }
+                        // For belongs_to: from=FK in current entity, to=PK in target entity
+                        // For has_many: from=PK in current entity, to=FK in target entity
+                        if relation.kind == RelationKind::BelongsTo {
+                            relation.foreign_key_fields.push(from_snake.clone());
+                            relation.target_primary_key_fields.push(to_snake.clone());
+                            relation.foreign_key_columns.push(from_column_variant.clone());
+                            relation.target_primary_key_columns.push(to_column_variant.clone());
+                        } else {
+                            // For has_many, store in both places for compatibility
+                            if relation.foreign_key_field.is_none() {
+                                relation.foreign_key_field = Some(to_snake.clone());
+                                relation.foreign_key_column = Some(to_column_variant.clone());
+                                relation.primary_key_field = Some(from_snake.clone());
+                            }
+                            // Also populate the vec fields for code generation compatibility
+                            relation.foreign_key_fields.push(to_snake.clone());
+                            relation.foreign_key_columns.push(to_column_variant.clone());
+                            // For has_many, the "to" field is the target's FK, and "from" is current entity's PK
+                            relation.target_primary_key_fields.push(from_snake.clone());
+                        }
                     
-                    relation.composite_key_mapping.push((from_snake, to_snake));
-                    // Don't set is_composite for single-field relations
-                    // is_composite will be set to true later if we find multiple from/to pairs
+                        relation.composite_key_mapping.push((from_snake, to_snake));
+                        // Don't set is_composite for single-field relations
+                        // is_composite will be set to true later if we find multiple from/to pairs
                     
-                            // Check if the foreign key field is optional
-                            let from_field_name = from_column_variant.to_snake_case();
-                            if let Some(field) = model_fields.iter().find(|f| {
-                                f.ident.as_ref().unwrap().to_string() == from_field_name
-                            }) {
-                                if is_option(&field.ty) {
-                                    relation.is_nullable = true;
-                                }
+                        // Check if the foreign key field is optional
+                        let from_field_name = from_column_variant.to_snake_case();
+                        if let Some(field) = model_fields.iter().find(|f| {
+                            f.ident.as_ref().unwrap().to_string() == from_field_name
+                        }) {
+                            if is_option(&field.ty) {
+                                relation.is_nullable = true;
                             }
+                        }
+                    }
                 }
             }
             
@@ -200,12 +207,12 @@ pub fn extract_relations(
                     }
                 }
                 
-                for field_name in &relation.foreign_key_fields {
-                    relation.foreign_key_columns.push(field_name.clone());
+                if relation.foreign_key_columns.is_empty() {
+                    relation.foreign_key_columns = relation.foreign_key_fields.clone();
                 }
-                
-                for field_name in &relation.target_primary_key_fields {
-                    relation.target_primary_key_columns.push(field_name.clone());
+
+                if relation.target_primary_key_columns.is_empty() {
+                    relation.target_primary_key_columns = relation.target_primary_key_fields.clone();
                 }
                 
                 // Extract target entity name
@@ -254,6 +261,19 @@ fn parse_target_path(target_str: &str) -> syn::Path {
     new_path
 }
 
+fn extract_field_names(column_str: &str) -> Vec<String> {
+    // Composite relations list their columns as a tuple: "(Column::BookTitle, Column::AuthorId)"
+    column_str
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(extract_field_name)
+        .collect()
+}
+
 fn extract_field_name(column_str: &str) -> String {
     // Extract field name from column reference like "Column::StudentId" -> "StudentId"
     // This returns the Column enum variant name (PascalCase)
@@ -50,6 +50,7 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
         };
 
         // Generate disconnect only for optional belongs_to (nullable FK on current entity)
+        // or has_many
         let disconnect_fn = if matches!(relation.kind, RelationKind::BelongsTo)
             && (!relation.foreign_key_fields.is_empty() || relation.foreign_key_field.is_some())
         {
@@ -71,6 +72,16 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
             } else {
                 quote! {}
             }
+        } else if matches!(relation.kind, RelationKind::HasMany)
+            && relation.target_fk_is_optional != Some(false)
+        {
+            // has_many disconnect nulls the children's foreign key; a required one is refused
+            // when the update runs
+            quote! {
+                pub fn disconnect(where_params: Vec<super::#target::UniqueWhereParam>) -> super::SetParam {
+                    super::SetParam::#disconnect_variant(where_params)
+                }
+            }
         } else {
             quote! {}
         };
@@ -131,6 +142,30 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
             quote! {}
         };
 
+        // Generate relation count threshold filters for has_many relations
+        let count_filter_fns = if matches!(relation.kind, RelationKind::HasMany) {
+            quote! {
+                // Filter parents by the number of related records
+                pub fn count_eq(n: i64) -> super::WhereParam {
+                    super::WhereParam::RelationCondition(caustics::RelationCondition::count(#relation_name_lit, caustics::FieldOp::Equals(n.into())))
+                }
+                pub fn count_gt(n: i64) -> super::WhereParam {
+                    super::WhereParam::RelationCondition(caustics::RelationCondition::count(#relation_name_lit, caustics::FieldOp::Gt(n.into())))
+                }
+                pub fn count_gte(n: i64) -> super::WhereParam {
+                    super::WhereParam::RelationCondition(caustics::RelationCondition::count(#relation_name_lit, caustics::FieldOp::Gte(n.into())))
+                }
+                pub fn count_lt(n: i64) -> super::WhereParam {
+                    super::WhereParam::RelationCondition(caustics::RelationCondition::count(#relation_name_lit, caustics::FieldOp::Lt(n.into())))
+                }
+                pub fn count_lte(n: i64) -> super::WhereParam {
+                    super::WhereParam::RelationCondition(caustics::RelationCondition::count(#relation_name_lit, caustics::FieldOp::Lte(n.into())))
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         // Generate fetch() function conditionally based on relation type
         let fetch_fn = if matches!(relation.kind, RelationKind::HasMany) {
             // For has_many relations, fetch() accepts filters
@@ -147,6 +182,7 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                         order_by: vec![],
                         cursor_id: None,
                         include_count: false,
+                        count_filters: vec![],
                         distinct: false,
                     }
                 }
@@ -165,13 +201,137 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                         order_by: vec![],
                         cursor_id: None,
                         include_count: false,
+                        count_filters: vec![],
                         distinct: false,
                     }
                 }
             }
         };
 
+        // belongs_to relations with a single foreign key expose a link function, so fields on the
+        // target can filter by the keys referenced from here: `user::id::in_related(post::user, ...)`
+        let (key_subquery_fn, relation_link_const) = if matches!(relation.kind, RelationKind::BelongsTo)
+            && !relation.is_composite
+            && (!relation.foreign_key_fields.is_empty() || relation.foreign_key_field.is_some())
+        {
+            let fk_column_ident = format_ident!("{}", relation.get_first_fk_column_name().to_pascal_case());
+            (
+                quote! {
+                    pub fn key_subquery(filters: Vec<super::WhereParam>) -> sea_query::SelectStatement {
+                        use sea_orm::{EntityName, QueryFilter, QuerySelect, QueryTrait};
+                        let table_name = super::Entity.table_name();
+                        let mut query = super::Entity::find()
+                            .select_only()
+                            .column(super::Column::#fk_column_ident);
+                        for filter in super::where_params_to_filters(filters) {
+                            query = query.filter(super::convert_filter_to_condition::<super::Entity>(&filter, table_name));
+                        }
+                        query.into_query()
+                    }
+                },
+                quote! {
+                    pub fn #relation_name_ident() -> caustics::RelationLink<#target::Entity, WhereParam> {
+                        caustics::RelationLink {
+                            key_subquery: #relation_name_ident::key_subquery,
+                            _target: std::marker::PhantomData,
+                        }
+                    }
+                },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+
+        // belongs_to relations can be included for presence alone, loading only the parent's key
+        let include_fk_only_fn = if matches!(relation.kind, RelationKind::BelongsTo) && !relation.is_composite {
+            let target_pk_name = if !relation.target_primary_key_columns.is_empty() {
+                relation.target_primary_key_columns[0].to_snake_case()
+            } else {
+                relation.primary_key_field.as_deref().unwrap_or("id").to_snake_case()
+            };
+            quote! {
+                /// Include the parent with only its primary key read, to confirm it exists without
+                /// transferring its other columns, which stay `None`. Only `select` queries support
+                /// it; full-row queries fail with `QueryValidation`
+                pub fn include_fk_only() -> super::RelationFilter {
+                    super::RelationFilter {
+                        nested_select_aliases: Some(vec![::std::string::ToString::to_string(#target_pk_name)]),
+                        ..fetch()
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // belongs_to relations can filter and sort on fields of the parent they point at
+        let (parent_filter_fns, parent_order_fn) = if matches!(relation.kind, RelationKind::BelongsTo)
+            && !relation.is_composite
+            && (!relation.foreign_key_fields.is_empty() || relation.foreign_key_field.is_some())
+        {
+            let fk_field_name = relation.get_first_fk_column_name();
+            let fk_variant = format_ident!("{}", fk_field_name.to_pascal_case());
+            let fk_is_optional = fields
+                .iter()
+                .find(|f| f.ident.as_ref().unwrap().to_string() == *fk_field_name)
+                .map(|field| is_option(&field.ty))
+                .unwrap_or(false);
+            let target_pk_variant = if !relation.target_primary_key_columns.is_empty() {
+                format_ident!("{}", relation.target_primary_key_columns[0].to_pascal_case())
+            } else {
+                format_ident!("{}", relation.primary_key_field.as_deref().unwrap_or("id").to_pascal_case())
+            };
+            // A missing parent never matches `is`, so it counts as a mismatch for `is_not`
+            let is_not_body = if fk_is_optional {
+                quote! {
+                    super::WhereParam::Or(vec![
+                        super::WhereParam::#fk_variant(caustics::FieldOp::IsNull),
+                        super::WhereParam::Not(vec![is(filters)]),
+                    ])
+                }
+            } else {
+                quote! { super::WhereParam::Not(vec![is(filters)]) }
+            };
+            (
+                quote! {
+                    /// Keep rows whose parent matches every filter: `<fk> IN (SELECT <pk> FROM <parent> WHERE ...)`
+                    pub fn is(filters: Vec<super::#target::WhereParam>) -> super::WhereParam {
+                        use sea_orm::{EntityName, QueryFilter, QuerySelect, QueryTrait};
+                        let table_name = super::#target::Entity.table_name();
+                        let mut query = super::#target::Entity::find()
+                            .select_only()
+                            .column(super::#target::Column::#target_pk_variant);
+                        for filter in super::#target::where_params_to_filters(filters) {
+                            query = query.filter(super::convert_filter_to_condition::<super::#target::Entity>(&filter, table_name));
+                        }
+                        super::WhereParam::#fk_variant(caustics::FieldOp::InSubquery(Box::new(query.into_query())))
+                    }
+
+                    /// Keep rows without a parent matching the filters
+                    pub fn is_not(filters: Vec<super::#target::WhereParam>) -> super::WhereParam {
+                        #is_not_body
+                    }
+                },
+                quote! {
+                    /// Order by a field of the parent: `post::user::order_by(user::name::order(SortOrder::Asc))`
+                    pub fn order_by(order: super::#target::OrderByParam) -> super::RelationOrderByParam {
+                        use sea_orm::IdenStatic;
+                        let (column, order): (super::#target::Column, sea_orm::Order) = order.into();
+                        let order = match order {
+                            sea_orm::Order::Asc => caustics::SortOrder::Asc,
+                            _ => caustics::SortOrder::Desc,
+                        };
+                        field(column.as_str(), order)
+                    }
+                },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+
         let submodule = quote! {
+            #relation_link_const
+
             pub mod #relation_name_ident {
                 use super::*;
 
@@ -187,6 +347,7 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                 // Basic relation functions
                 #fetch_fn
 
+                #include_fk_only_fn
 
                 // Helper to convert typed WhereParams into generic Filters
                 pub fn filters_from_where(params: Vec<super::#target::WhereParam>) -> Vec<super::Filter> {
@@ -243,16 +404,18 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                         order_by: g.order_by,
                         cursor_id: g.cursor_id,
                         include_count: g.include_count,
+                        count_filters: g.count_filters,
                         distinct: g.distinct,
                     }
                 }
 
                 pub struct RelBuilder { core: caustics::IncludeBuilderCore }
                 impl RelBuilder {
-                    // For relation includes, keep field-based order API
-                    pub fn order_by(mut self, params: Vec<super::#target::OrderByParam>) -> Self {
+                    /// Order the included rows by one key or several, e.g.
+                    /// `order_by(post::created_at::order(SortOrder::Desc))`; each parent's rows keep this order
+                    pub fn order_by(mut self, params: impl Into<Vec<super::#target::OrderByParam>>) -> Self {
                         let mut pairs = Vec::new();
-                        for p in params.into_iter() {
+                        for p in params.into().into_iter() {
                             let (col, ord): (<super::#target::Entity as EntityTrait>::Column, sea_orm::Order) = p.into();
                             let name = format!("{:?}", col).to_string().to_snake_case();
                             pairs.push((name, match ord { sea_orm::Order::Asc => caustics::SortOrder::Asc, _ => caustics::SortOrder::Desc }));
@@ -279,6 +442,10 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                         self
                     }
                     pub fn count(mut self) -> Self { self.core.enable_count(); self }
+                    pub fn count_where(mut self, filters: Vec<super::#target::WhereParam>) -> Self {
+                        self.core.enable_count_where(super::#target::where_params_to_filters(filters));
+                        self
+                    }
                     pub fn distinct(mut self) -> Self { self.core.enable_distinct(); self }
                 }
 
@@ -296,6 +463,7 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                         order_by: vec![],
                         cursor_id: None,
                         include_count: false,
+                        count_filters: vec![],
                         distinct: false,
                     }
                 }
@@ -318,6 +486,7 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
 
                 // Field ordering functions for relation fields
                 #relation_field_order_fns
+                #parent_order_fn
 
                 pub fn connect(where_param: super::#target::UniqueWhereParam) -> super::SetParam {
                     super::SetParam::#connect_variant(where_param)
@@ -328,6 +497,7 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                 #disconnect_fn
 
                 // Advanced relation operations for filtering
+                /// At least one related record matches every filter: `EXISTS (...)`
                 pub fn some(filters: Vec<super::#target::WhereParam>) -> super::WhereParam {
                     // Convert WhereParam filters to Filter format for relation conditions
                     let mut relation_filters = Vec::new();
@@ -343,6 +513,8 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                     super::WhereParam::RelationCondition(caustics::RelationCondition::some(#relation_name_lit, relation_filters))
                 }
 
+                /// No related record misses the filters: `NOT EXISTS (... AND NOT (filters))`.
+                /// Rows without related records match.
                 pub fn every(filters: Vec<super::#target::WhereParam>) -> super::WhereParam {
                     // Convert WhereParam filters to Filter format for relation conditions
                     let mut relation_filters = Vec::new();
@@ -357,6 +529,7 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                     super::WhereParam::RelationCondition(caustics::RelationCondition::every(#relation_name_lit, relation_filters))
                 }
 
+                /// No related record matches the filters: `NOT EXISTS (...)`, the same as `not(vec![some(filters)])`
                 pub fn none(filters: Vec<super::#target::WhereParam>) -> super::WhereParam {
                     // Convert WhereParam filters to Filter format for relation conditions
                     let mut relation_filters = Vec::new();
@@ -370,6 +543,18 @@ pub fn generate_relation_submodules(relations: &[Relation], fields: &[&syn::Fiel
                     }
                     super::WhereParam::RelationCondition(caustics::RelationCondition::none(#relation_name_lit, relation_filters))
                 }
+
+                /// At least one related record misses the filters; same as `not(vec![every(filters)])`.
+                /// Rows without related records never match, since `every` holds for them vacuously.
+                pub fn not_every(filters: Vec<super::#target::WhereParam>) -> super::WhereParam {
+                    super::WhereParam::Not(vec![every(filters)])
+                }
+
+                #count_filter_fns
+
+                #parent_filter_fns
+
+                #key_subquery_fn
             }
         };
         submodules.push(submodule);
@@ -70,6 +70,85 @@ pub fn has_caustics_default_attr(field: &Field) -> bool {
     result
 }
 
+/// Check if a field is marked with #[sea_orm(caustics_db_default)] or // #[caustics(db_default)]
+pub fn has_caustics_db_default_attr(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| match &attr.meta {
+        syn::Meta::List(meta) if meta.path.is_ident("sea_orm") => {
+            meta.tokens.to_string().contains("caustics_db_default")
+        }
+        syn::Meta::NameValue(nv) if nv.path.is_ident("doc") => {
+            if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = &nv.value {
+                lit.value().trim().starts_with("#[caustics(db_default)]")
+            } else {
+                false
+            }
+        }
+        _ => false,
+    })
+}
+
+/// Check if a field is marked with #[sea_orm(caustics_utc)] or // #[caustics(utc)]
+pub fn has_caustics_utc_attr(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| match &attr.meta {
+        syn::Meta::List(meta) if meta.path.is_ident("sea_orm") => {
+            meta.tokens.to_string().contains("caustics_utc")
+        }
+        syn::Meta::NameValue(nv) if nv.path.is_ident("doc") => {
+            if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = &nv.value {
+                lit.value().trim().starts_with("#[caustics(utc)]")
+            } else {
+                false
+            }
+        }
+        _ => false,
+    })
+}
+
+/// Database column of a Model field: `#[sea_orm(column_name = "...")]`, else the field name
+pub fn field_column_name(field: &Field) -> String {
+    let field_name = field.ident.as_ref().unwrap().to_string();
+    field
+        .attrs
+        .iter()
+        .find_map(|attr| {
+            if let syn::Meta::List(meta) = &attr.meta {
+                if meta.path.is_ident("sea_orm") {
+                    // Parse sea_orm attributes to find column name
+                    // Look for column_name = "..." in the attribute tokens
+                    let tokens = meta.tokens.to_string();
+                    if let Some(start) = tokens.find("column_name = \"") {
+                        let start = start + "column_name = \"".len();
+                        if let Some(end) = tokens[start..].find('"') {
+                            let column_name = &tokens[start..start + end];
+                            Some(column_name.to_string())
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        })
+        .unwrap_or(field_name)
+}
+
+/// Whether a Model field is part of the primary key
+pub fn is_primary_key_field(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| {
+        if let syn::Meta::List(meta) = &attr.meta {
+            (meta.path.is_ident("sea_orm") && meta.tokens.to_string().contains("primary_key"))
+                || meta.path.is_ident("primary_key")
+        } else {
+            false
+        }
+    })
+}
+
 /// Information about a primary key field
 #[derive(Debug, Clone)]
 pub struct PrimaryKeyInfo {
@@ -85,37 +164,7 @@ impl PrimaryKeyInfo {
     pub fn from_field(field: &Field) -> Self {
         let field_ident = field.ident.as_ref().unwrap().clone();
         let field_name = field_ident.to_string();
-        let column_name = field_name.clone(); // Default to field name
-
-        // Check for custom column name in attributes
-        let column_name = field
-            .attrs
-            .iter()
-            .find_map(|attr| {
-                if let syn::Meta::List(meta) = &attr.meta {
-                    if meta.path.is_ident("sea_orm") {
-                        // Parse sea_orm attributes to find column name
-                        // Look for column_name = "..." in the attribute tokens
-                        let tokens = meta.tokens.to_string();
-                        if let Some(start) = tokens.find("column_name = \"") {
-                            let start = start + "column_name = \"".len();
-                            if let Some(end) = tokens[start..].find('"') {
-                                let column_name = &tokens[start..start + end];
-                                Some(column_name.to_string())
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(column_name);
+        let column_name = field_column_name(field);
 
         Self {
             field_name,
@@ -160,16 +209,7 @@ impl PrimaryKeyInfo {
 /// Extract primary key information from a list of fields
 pub fn extract_primary_key_info(fields: &[&Field]) -> Option<PrimaryKeyInfo> {
     // First, try to find a field explicitly marked as primary key
-    let explicit_pk_field = fields.iter().find(|field| {
-        field.attrs.iter().any(|attr| {
-            if let syn::Meta::List(meta) = &attr.meta {
-                (meta.path.is_ident("sea_orm") && meta.tokens.to_string().contains("primary_key"))
-                    || meta.path.is_ident("primary_key")
-            } else {
-                false
-            }
-        })
-    });
+    let explicit_pk_field = fields.iter().find(|field| is_primary_key_field(field));
 
     if let Some(field) = explicit_pk_field {
         return Some(PrimaryKeyInfo::from_field(field));
@@ -184,16 +224,7 @@ pub fn extract_primary_key_info(fields: &[&Field]) -> Option<PrimaryKeyInfo> {
 pub fn extract_all_primary_key_info(fields: &[&Field]) -> Vec<PrimaryKeyInfo> {
     fields
         .iter()
-        .filter(|field| {
-            field.attrs.iter().any(|attr| {
-                if let syn::Meta::List(meta) = &attr.meta {
-                    (meta.path.is_ident("sea_orm") && meta.tokens.to_string().contains("primary_key"))
-                        || meta.path.is_ident("primary_key")
-                } else {
-                    false
-                }
-            })
-        })
+        .filter(|field| is_primary_key_field(field))
         .map(|field| PrimaryKeyInfo::from_field(field))
         .collect()
 }
@@ -45,9 +45,13 @@ impl Parse for SelectStructInput {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let name: Ident = input.parse()?;
 
-        // Always require explicit source type - no more inference
-        input.parse::<from>()?;
-        let source_type = input.parse::<Type>()?;
+        // Without `from`, the struct is read straight from query rows (e.g. group_by results)
+        let source_type = if input.peek(from) {
+            input.parse::<from>()?;
+            Some(input.parse::<Type>()?)
+        } else {
+            None
+        };
 
         let content;
         braced!(content in input);
@@ -59,7 +63,7 @@ impl Parse for SelectStructInput {
 
         Ok(SelectStructInput {
             name,
-            source_type: Some(source_type),
+            source_type,
             fields,
         })
     }
@@ -167,6 +171,10 @@ impl Parse for FieldType {
 
 /// Generate the complete select_struct implementation
 fn generate_select_struct(input: &SelectStructInput) -> syn::Result<TokenStream2> {
+    if input.source_type.is_none() {
+        return generate_row_struct(input);
+    }
+
     let mut all_structs = Vec::new();
     let mut all_from_impls = Vec::new();
 
@@ -186,6 +194,43 @@ fn generate_select_struct(input: &SelectStructInput) -> syn::Result<TokenStream2
     })
 }
 
+/// Generate a flat struct populated from a query row, one column per field (matched by name)
+fn generate_row_struct(input: &SelectStructInput) -> syn::Result<TokenStream2> {
+    for field in &input.fields {
+        let is_scalar = match &field.field_type {
+            FieldType::Primitive(_) => true,
+            FieldType::Option(inner) => matches!(**inner, FieldType::Primitive(_)),
+            _ => false,
+        };
+        if !is_scalar {
+            return Err(syn::Error::new(
+                field.name.span(),
+                format!(
+                    "Field '{}' must be a scalar or Option of a scalar when no source type is given. Use: select_struct!({} from YourSourceType {{ ... }}) for nested data",
+                    field.name, input.name
+                ),
+            ));
+        }
+    }
+
+    let name = &input.name;
+    let row_struct = generate_struct(name, &input.fields);
+    let field_names: Vec<_> = input.fields.iter().map(|field| &field.name).collect();
+    let column_names: Vec<_> = field_names.iter().map(|name| name.to_string()).collect();
+
+    Ok(quote! {
+        #row_struct
+
+        impl caustics::sea_orm::FromQueryResult for #name {
+            fn from_query_result(res: &caustics::sea_orm::QueryResult, pre: &str) -> Result<Self, caustics::sea_orm::DbErr> {
+                Ok(Self {
+                    #(#field_names: res.try_get(pre, #column_names)?,)*
+                })
+            }
+        }
+    })
+}
+
 /// Generate a struct definition
 fn generate_struct(name: &Ident, fields: &[FieldDefinition]) -> TokenStream2 {
     let field_definitions = fields.iter().map(|field| {
@@ -38,20 +38,42 @@ pub fn generate_where_param_logic(
         where_field_variants.push(quote! { #pascal_name(caustics::FieldOp) });
 
         // Field operator module
+        let from_column_fn = if is_primary_key {
+            quote! {}
+        } else {
+            let from_column_name = format_ident!("{}FromColumn", pascal_name);
+            quote! {
+                /// Set the column to another same-typed column of the same row, server-side
+                pub fn set_from_column(source: caustics::TypedColumn<Entity, #ty>) -> super::SetParam {
+                    super::SetParam::#from_column_name(source.column)
+                }
+            }
+        };
         let set_fn = quote! {
             pub fn set<T: Into<#ty>>(value: T) -> super::SetParam {
                 super::SetParam::#pascal_name(sea_orm::ActiveValue::Set(value.into()))
             }
+            /// Typed handle to this column, e.g. as the source of `set_from_column`
+            pub fn column() -> caustics::TypedColumn<Entity, #ty> {
+                caustics::TypedColumn::new(<Entity as EntityTrait>::Column::#pascal_name)
+            }
+            /// This field of a fetched row, e.g. as the key for `index_by`
+            pub fn key(model: &super::ModelWithRelations) -> #ty {
+                model.#name.clone()
+            }
+            #from_column_fn
         };
 
         // Unique where function
         let unique_where_fn = if is_unique {
             let equals_variant = format_ident!("{}Equals", pascal_name);
             if is_primary_key {
-                // For primary key fields that are also unique, accept CausticsKey directly
+                // For primary key fields that are also unique, accept CausticsKey directly;
+                // custom key types are keyed through ToCausticsValue
+                let (key_bound, to_key) = primary_key_conversion(&field_type, ty);
                 quote! {
-                    pub fn equals<T: From<Equals>>(value: impl Into<caustics::CausticsKey>) -> T {
-                        let key = value.into();
+                    pub fn equals<T: From<Equals>>(value: impl #key_bound) -> T {
+                        let key = #to_key(value.into());
                         Equals(key).into()
                     }
                     pub struct Equals(pub caustics::CausticsKey);
@@ -67,8 +89,22 @@ pub fn generate_where_param_logic(
                     }
                 }
             } else {
+                // Unique String fields can also be looked up case-insensitively
+                let insensitive_fn = if matches!(field_type, FieldType::String) {
+                    let insensitive_variant = format_ident!("{}EqualsInsensitive", pascal_name);
+                    quote! {
+                        /// Unique lookup comparing `LOWER(col) = LOWER(value)`. Only truly unique when the
+                        /// table has a unique index on `LOWER(col)`; `upsert` never uses `ON CONFLICT` for it.
+                        pub fn equals_insensitive(value: impl Into<String>) -> super::UniqueWhereParam {
+                            super::UniqueWhereParam::#insensitive_variant(value.into())
+                        }
+                    }
+                } else {
+                    quote! {}
+                };
                 // For other unique fields, use the field's actual type
                 quote! {
+                    #insensitive_fn
                     pub fn equals<T: From<Equals>>(value: impl Into<#ty>) -> T {
                         Equals(value.into()).into()
                     }
@@ -99,6 +135,22 @@ pub fn generate_where_param_logic(
             }
         };
 
+        // Custom value order for string/enum fields: `order_by(user::name::order_by_cases(vec!["b", "a"]))`
+        let order_cases_fn = match field_type {
+            FieldType::String | FieldType::OptionString | FieldType::Other => quote! {
+                /// Order rows by the position of their value in `values`; other values come last
+                pub fn order_by_cases<T: caustics::ToSeaOrmValue>(values: Vec<T>) -> (sea_query::SimpleExpr, caustics::SortOrder) {
+                    use sea_orm::IntoSimpleExpr;
+                    let values = values.iter().map(caustics::ToSeaOrmValue::to_sea_orm_value).collect();
+                    (
+                        caustics::case_position(<Entity as EntityTrait>::Column::#pascal_name.into_simple_expr(), values),
+                        caustics::SortOrder::Asc,
+                    )
+                }
+            },
+            _ => quote! {},
+        };
+
         // Relation-aggregate helper: count order (returns SortOrder to feed relation::order_by)
         let count_fn = quote! {
             pub fn count(order: caustics::SortOrder) -> caustics::SortOrder { order }
@@ -133,12 +185,20 @@ pub fn generate_where_param_logic(
                     pub fn contains<T: Into<String>>(value: T) -> WhereParam {
                         WhereParam::#pascal_name(caustics::FieldOp::Contains(value.into()))
                     }
+                    /// Match if any of `values` is a substring (`ILIKE ANY` on Postgres in insensitive mode)
+                    pub fn contains_any<T: Into<String>>(values: Vec<T>) -> WhereParam {
+                        WhereParam::#pascal_name(caustics::FieldOp::ContainsAny(values.into_iter().map(Into::into).collect()))
+                    }
                     pub fn starts_with<T: Into<String>>(value: T) -> WhereParam {
                         WhereParam::#pascal_name(caustics::FieldOp::StartsWith(value.into()))
                     }
                     pub fn ends_with<T: Into<String>>(value: T) -> WhereParam {
                         WhereParam::#pascal_name(caustics::FieldOp::EndsWith(value.into()))
                     }
+                    /// Match a raw LIKE pattern, where `%` and `_` are wildcards and `\` escapes them
+                    pub fn like<T: Into<String>>(pattern: T) -> WhereParam {
+                        WhereParam::#pascal_name(caustics::FieldOp::Like(pattern.into()))
+                    }
                 }
             }
             _ => quote! {},
@@ -220,6 +280,31 @@ pub fn generate_where_param_logic(
             _ => quote! {},
         };
 
+        // Boolean sugar: `is_true()` / `is_false()`, plus `is_unknown()` (NULL) when nullable
+        let bool_ops = match field_type {
+            FieldType::Boolean | FieldType::OptionBoolean if !is_primary_key => {
+                let unknown_fn = if matches!(field_type, FieldType::OptionBoolean) {
+                    quote! {
+                        pub fn is_unknown() -> WhereParam {
+                            WhereParam::#pascal_name(caustics::FieldOp::IsNull)
+                        }
+                    }
+                } else {
+                    quote! {}
+                };
+                quote! {
+                    pub fn is_true() -> WhereParam {
+                        WhereParam::#pascal_name(caustics::FieldOp::Equals(sea_orm::Value::Bool(Some(true))))
+                    }
+                    pub fn is_false() -> WhereParam {
+                        WhereParam::#pascal_name(caustics::FieldOp::Equals(sea_orm::Value::Bool(Some(false))))
+                    }
+                    #unknown_fn
+                }
+            }
+            _ => quote! {},
+        };
+
         // JSON-specific operations (only for JSON types)
         let json_ops = match field_type {
             FieldType::Json => {
@@ -248,6 +333,11 @@ pub fn generate_where_param_logic(
                     pub fn json_object_contains(key: String) -> WhereParam {
                         WhereParam::#pascal_name(caustics::FieldOp::JsonObjectContains(key))
                     }
+                    /// Match rows where a Postgres JSONPath predicate holds, e.g. `$.tags[*] ? (@ == "rust")`.
+                    /// Postgres only; other backends fail with `QueryValidation`.
+                    pub fn json_path_match(path: impl Into<String>) -> WhereParam {
+                        WhereParam::#pascal_name(caustics::FieldOp::JsonPathMatch(path.into()))
+                    }
                     pub fn db_null() -> WhereParam { WhereParam::#pascal_name(caustics::FieldOp::JsonNull(caustics::JsonNullValueFilter::DbNull)) }
                     pub fn json_null() -> WhereParam { WhereParam::#pascal_name(caustics::FieldOp::JsonNull(caustics::JsonNullValueFilter::JsonNull)) }
                     pub fn any_null() -> WhereParam { WhereParam::#pascal_name(caustics::FieldOp::JsonNull(caustics::JsonNullValueFilter::AnyNull)) }
@@ -279,6 +369,11 @@ pub fn generate_where_param_logic(
                     pub fn json_object_contains(key: String) -> WhereParam {
                         WhereParam::#pascal_name(caustics::FieldOp::JsonObjectContains(key))
                     }
+                    /// Match rows where a Postgres JSONPath predicate holds, e.g. `$.tags[*] ? (@ == "rust")`.
+                    /// Postgres only; other backends fail with `QueryValidation`.
+                    pub fn json_path_match(path: impl Into<String>) -> WhereParam {
+                        WhereParam::#pascal_name(caustics::FieldOp::JsonPathMatch(path.into()))
+                    }
                     pub fn db_null() -> WhereParam { WhereParam::#pascal_name(caustics::FieldOp::JsonNull(caustics::JsonNullValueFilter::DbNull)) }
                     pub fn json_null() -> WhereParam { WhereParam::#pascal_name(caustics::FieldOp::JsonNull(caustics::JsonNullValueFilter::JsonNull)) }
                     pub fn any_null() -> WhereParam { WhereParam::#pascal_name(caustics::FieldOp::JsonNull(caustics::JsonNullValueFilter::AnyNull)) }
@@ -287,7 +382,7 @@ pub fn generate_where_param_logic(
             _ => quote! {},
         };
 
-        // Atomic operations (only for numeric types)
+        // Atomic operations (numeric arithmetic, string append/prepend, JSON and list edits)
         let atomic_ops = if !is_unique
             && matches!(
                 field_type,
@@ -318,14 +413,93 @@ pub fn generate_where_param_logic(
                     super::SetParam::#divide_name(value.into())
                 }
             }
+        } else if !is_unique && matches!(field_type, FieldType::String | FieldType::OptionString) {
+            let append_name = format_ident!("{}Append", pascal_name);
+            let prepend_name = format_ident!("{}Prepend", pascal_name);
+
+            quote! {
+                /// Atomically append to the column (`col = COALESCE(col, '') || value`)
+                pub fn append<T: Into<String>>(value: T) -> super::SetParam {
+                    super::SetParam::#append_name(value.into())
+                }
+                /// Atomically prepend to the column (`col = value || COALESCE(col, '')`)
+                pub fn prepend<T: Into<String>>(value: T) -> super::SetParam {
+                    super::SetParam::#prepend_name(value.into())
+                }
+            }
+        } else if matches!(field_type, FieldType::Json | FieldType::OptionJson) {
+            let merge_name = format_ident!("{}Merge", pascal_name);
+            let remove_key_name = format_ident!("{}RemoveKey", pascal_name);
+            let push_name = format_ident!("{}Push", pascal_name);
+            let remove_element_name = format_ident!("{}RemoveElement", pascal_name);
+
+            quote! {
+                /// Atomically merge `patch` into the JSON column, treating `NULL` as `{}`
+                pub fn merge(patch: caustics::serde_json::Value) -> super::SetParam {
+                    super::SetParam::#merge_name(patch)
+                }
+                /// Atomically remove the key at `path` (e.g. `vec!["metadata", "priority"]`)
+                pub fn remove_key<I, S>(path: I) -> super::SetParam
+                where
+                    I: IntoIterator<Item = S>,
+                    S: Into<String>,
+                {
+                    super::SetParam::#remove_key_name(path.into_iter().map(Into::into).collect())
+                }
+                /// Atomically append `element` to the JSON array column, treating `NULL` as `[]`
+                pub fn push<T: Into<caustics::serde_json::Value>>(element: T) -> super::SetParam {
+                    super::SetParam::#push_name(element.into())
+                }
+                /// Atomically remove every occurrence of `element` from the JSON array column
+                pub fn remove_element<T: Into<caustics::serde_json::Value>>(element: T) -> super::SetParam {
+                    super::SetParam::#remove_element_name(element.into())
+                }
+            }
+        } else if !is_unique && matches!(field_type, FieldType::Vec | FieldType::OptionVec) {
+            let element_ty = crate::common::extract_inner_type_from_vec(ty);
+            let push_name = format_ident!("{}Push", pascal_name);
+            let remove_element_name = format_ident!("{}RemoveElement", pascal_name);
+
+            quote! {
+                /// Atomically append `element` to the list column
+                pub fn push<T: Into<#element_ty>>(element: T) -> super::SetParam {
+                    super::SetParam::#push_name(element.into())
+                }
+                /// Atomically remove every occurrence of `element` from the list column
+                pub fn remove_element<T: Into<#element_ty>>(element: T) -> super::SetParam {
+                    super::SetParam::#remove_element_name(element.into())
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // Relation-driven membership: `user::id::in_related(post::user, post_filters)`
+        let related_ops = if is_unique {
+            quote! {
+                /// Keep rows whose value is among the keys referenced through `link` by the rows matching `filters`
+                pub fn in_related<W>(link: fn() -> caustics::RelationLink<Entity, W>, filters: Vec<W>) -> WhereParam {
+                    WhereParam::#pascal_name(caustics::FieldOp::InSubquery(Box::new((link().key_subquery)(filters))))
+                }
+            }
         } else {
             quote! {}
         };
 
+        // Membership in another query's results: `user::id::in_subquery(client.post().find_many(..).select(..))`
+        let subquery_ops = quote! {
+            /// Keep rows whose value is returned by `query`, which selects a single column;
+            /// the database evaluates it as `IN (SELECT ...)`
+            pub fn in_subquery<Q: caustics::IntoSubquery>(query: Q) -> WhereParam {
+                WhereParam::#pascal_name(caustics::FieldOp::InSubquery(Box::new(query.into_subquery())))
+            }
+        };
+
         let mut field_mod_items = vec![
             set_fn,
             unique_where_fn,
             order_fn,
+            order_cases_fn,
             count_fn,
             type_specific_ops,
             field_not_alias,
@@ -333,8 +507,11 @@ pub fn generate_where_param_logic(
             comparison_ops,
             collection_ops,
             null_ops,
+            bool_ops,
             json_ops,
             atomic_ops,
+            related_ops,
+            subquery_ops,
         ];
 
         // If this is a string field, add a Mode variant and mode function
@@ -357,6 +534,56 @@ pub fn generate_where_param_logic(
             }
         });
     }
+    // Composite primary keys get an `id` module so lookups read like single-key entities:
+    // `entity::id::equals((a, b))`
+    let has_id_field = fields
+        .iter()
+        .any(|field| field.ident.as_ref().unwrap() == "id");
+    if primary_key_fields.len() > 1 && !has_id_field {
+        let pk_idents: Vec<_> = primary_key_fields
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap())
+            .collect();
+        let pk_pascal_idents: Vec<_> = pk_idents
+            .iter()
+            .map(|ident| format_ident!("{}", ident.to_string().to_pascal_case()))
+            .collect();
+        let pk_types: Vec<_> = primary_key_fields.iter().map(|field| &field.ty).collect();
+        let pk_generics: Vec<_> = (0..pk_idents.len())
+            .map(|i| format_ident!("K{}", i))
+            .collect();
+        let composite_variant = format_ident!(
+            "{}",
+            pk_pascal_idents
+                .iter()
+                .map(|ident| ident.to_string())
+                .collect::<Vec<_>>()
+                .join("And")
+        );
+        field_ops.push(quote! {
+            pub mod id {
+                use super::*;
+                pub fn equals<T: From<Equals>, #(#pk_generics: Into<#pk_types>),*>(value: (#(#pk_generics,)*)) -> T {
+                    let (#(#pk_idents,)*) = value;
+                    Equals(#(#pk_idents.into()),*).into()
+                }
+                pub struct Equals(#(pub #pk_types),*);
+                impl From<Equals> for super::UniqueWhereParam {
+                    fn from(Equals(#(#pk_idents),*): Equals) -> Self {
+                        super::UniqueWhereParam::#composite_variant(#(#pk_idents),*)
+                    }
+                }
+                impl From<Equals> for super::WhereParam {
+                    fn from(Equals(#(#pk_idents),*): Equals) -> Self {
+                        super::WhereParam::And(vec![
+                            #(super::WhereParam::#pk_pascal_idents(caustics::FieldOp::equals(#pk_idents)),)*
+                        ])
+                    }
+                }
+            }
+        });
+    }
+
     // Logical operator helpers
     field_ops.push(quote! {
         pub fn and(params: Vec<WhereParam>) -> WhereParam {
@@ -428,12 +655,22 @@ fn generate_where_params_to_condition_function(
 ) -> proc_macro2::TokenStream {
     let mut field_handlers = Vec::new();
     let mut mode_handlers = Vec::new();
+    let mut subquery_handlers = Vec::new();
+    let mut json_fields = Vec::new();
+    let mut shape_fields = Vec::new();
 
     for field in fields.iter() {
         let name = field.ident.as_ref().unwrap();
         let pascal_name = format_ident!("{}", name.to_string().to_pascal_case());
+        shape_fields.push(pascal_name.clone());
         let ty = &field.ty;
 
+        subquery_handlers.push(quote! {
+            WhereParam::#pascal_name(caustics::FieldOp::InSubquery(subquery)) => {
+                Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.in_subquery(*subquery))
+            },
+        });
+
         // Comprehensive type detection
         let field_type = detect_field_type(ty);
 
@@ -542,6 +779,7 @@ fn generate_where_params_to_condition_function(
                     false,
                     is_primary_key,
                 ));
+                json_fields.push(pascal_name.clone());
             }
             FieldType::OptionJson => {
                 field_handlers.push(generate_json_field_handler(
@@ -549,6 +787,7 @@ fn generate_where_params_to_condition_function(
                     true,
                     is_primary_key,
                 ));
+                json_fields.push(pascal_name.clone());
             }
             FieldType::Vec => {
                 field_handlers.push(generate_vec_field_handler(
@@ -666,18 +905,19 @@ fn generate_where_params_to_condition_function(
                                 Vec::<sea_orm::Value>::new()
                             ));
 
-                        // Apply filters for 'every' operation
-                        // We need to find records where there are NO related records that DON'T match the filter
-                        // This means: NOT EXISTS (SELECT 1 FROM target WHERE fk = current.id AND NOT (filter))
-                        let mut filtered_subquery = subquery;
-                        for filter in &relation_condition.filters {
-                            let condition = convert_filter_to_condition::<#target::Entity>(filter, #target_table_name_str);
-                            // For 'every', we want records where there are NO related records that DON'T match
-                            // So we add the condition as-is, then negate the entire EXISTS
-                            filtered_subquery = filtered_subquery.filter(condition.not());
+                        // A related record fails when it misses any filter, so the filters are
+                        // negated together: NOT (f1 AND f2), not NOT f1 AND NOT f2
+                        if relation_condition.filters.is_empty() {
+                            // Every related record trivially matches no filters
+                            Condition::all()
+                        } else {
+                            let mut matched = Condition::all();
+                            for filter in &relation_condition.filters {
+                                matched = matched.add(convert_filter_to_condition::<#target::Entity>(filter, #target_table_name_str));
+                            }
+                            let failing = subquery.filter(matched.not());
+                            Condition::all().add(sea_query::Expr::exists(failing.into_query()).not())
                         }
-
-                        Condition::all().add(sea_query::Expr::exists(filtered_subquery.into_query()).not())
                     },
                     caustics::FieldOp::None(()) => {
                         // Phase 3: Use SeaORM query builder instead of raw SQL
@@ -698,6 +938,29 @@ fn generate_where_params_to_condition_function(
 
                         Condition::all().add(sea_query::Expr::exists(filtered_subquery.into_query()).not())
                     },
+                    caustics::FieldOp::Count(comparison) => {
+                        // (SELECT COUNT(*) FROM target WHERE fk = current.id) <op> n
+                        let subquery = #target::Entity::find()
+                            .select_only()
+                            .expr(sea_query::Expr::cust("COUNT(*)"))
+                            .filter(sea_query::Expr::cust_with_values(
+                                &format!("\"{}\".\"{}\" = \"{}\".\"id\"", #target_table_name_str, #foreign_key_column_str, #current_table_name_str),
+                                Vec::<sea_orm::Value>::new()
+                            ))
+                            .into_query();
+                        let count = sea_query::Expr::expr(sea_query::SimpleExpr::SubQuery(
+                            None,
+                            Box::new(sea_query::SubQueryStatement::SelectStatement(subquery)),
+                        ));
+                        match *comparison {
+                            caustics::FieldOp::Equals(n) => Condition::all().add(count.eq(n)),
+                            caustics::FieldOp::Gt(n) => Condition::all().add(count.gt(n)),
+                            caustics::FieldOp::Gte(n) => Condition::all().add(count.gte(n)),
+                            caustics::FieldOp::Lt(n) => Condition::all().add(count.lt(n)),
+                            caustics::FieldOp::Lte(n) => Condition::all().add(count.lte(n)),
+                            _ => Condition::all(),
+                        }
+                    },
                     // Catch-all for unsupported relation operations: no-op condition
                     _ => Condition::all(),
                 }
@@ -782,22 +1045,20 @@ fn generate_where_params_to_condition_function(
                         [value.clone()]
                     ))
                 },
-                caustics::FieldOp::Contains(value) => {
-                    Condition::all().add(sea_query::Expr::cust_with_values(
-                        &format!("\"{}\".{} LIKE ?", table_name, filter.field),
-                        [sea_orm::Value::from(format!("%{}%", value))]
-                    ))
-                },
-                caustics::FieldOp::StartsWith(value) => {
-                    Condition::all().add(sea_query::Expr::cust_with_values(
-                        &format!("\"{}\".{} LIKE ?", table_name, filter.field),
-                        [sea_orm::Value::from(format!("{}%", value))]
-                    ))
-                },
-                caustics::FieldOp::EndsWith(value) => {
-                    Condition::all().add(sea_query::Expr::cust_with_values(
-                        &format!("\"{}\".{} LIKE ?", table_name, filter.field),
-                        [sea_orm::Value::from(format!("%{}", value))]
+                caustics::FieldOp::Contains(_)
+                | caustics::FieldOp::StartsWith(_)
+                | caustics::FieldOp::EndsWith(_)
+                | caustics::FieldOp::Like(_) => {
+                    let pattern = filter.operation.like_pattern().expect("LIKE operation has a pattern");
+                    Condition::all().add(
+                        sea_query::Expr::cust(format!("\"{}\".{}", table_name, filter.field)).like(caustics::like_expr(pattern))
+                    )
+                },
+                caustics::FieldOp::ContainsAny(_) => {
+                    let patterns = filter.operation.like_any_patterns().expect("contains_any has patterns");
+                    Condition::all().add(caustics::like_any_expr(
+                        sea_query::Expr::cust(format!("\"{}\".{}", table_name, filter.field)),
+                        patterns,
                     ))
                 },
                 caustics::FieldOp::InVec(values) => {
@@ -886,12 +1147,64 @@ fn generate_where_params_to_condition_function(
                             &format!("(\"{}\".{} IS NULL OR json_type(\"{}\".{}, '$') = 'null')", table_name, filter.field, table_name, filter.field), Vec::<sea_orm::Value>::new())),
                     }
                 },
+                caustics::FieldOp::InSubquery(subquery) => {
+                    Condition::all().add(sea_query::Expr::expr(sea_query::Expr::cust(
+                        format!("\"{}\".{}", table_name, filter.field)
+                    )).in_subquery((**subquery).clone()))
+                },
                 // Relation operations (should not be used in field mappings) -> no-op
-                caustics::FieldOp::Some(_) | caustics::FieldOp::Every(_) | caustics::FieldOp::None(_) => Condition::all(),
+                caustics::FieldOp::Some(_) | caustics::FieldOp::Every(_) | caustics::FieldOp::None(_) | caustics::FieldOp::Count(_) => Condition::all(),
                 _ => Condition::all(),
             }
         }
 
+        /// Message for the first filter in `params` that `database_backend` cannot run
+        pub fn unsupported_filter(params: &[WhereParam], database_backend: sea_orm::DatabaseBackend) -> Option<String> {
+            params.iter().find_map(|param| match param {
+                #(
+                    WhereParam::#json_fields(caustics::FieldOp::JsonPathMatch(_))
+                        if database_backend != sea_orm::DatabaseBackend::Postgres =>
+                    {
+                        Some("json_path_match requires Postgres".to_string())
+                    }
+                )*
+                WhereParam::And(params) | WhereParam::Or(params) | WhereParam::Not(params) => {
+                    unsupported_filter(params, database_backend)
+                }
+                _ => None,
+            })
+        }
+
+        /// Hash the shape of `params` for the statement cache: fields, operators and nesting,
+        /// but not the values they compare against
+        pub fn hash_filter_shape<H: std::hash::Hasher>(params: &[WhereParam], state: &mut H) {
+            use std::hash::Hash;
+            params.len().hash(state);
+            for param in params {
+                match param {
+                    #(
+                        WhereParam::#shape_fields(op) => {
+                            stringify!(#shape_fields).hash(state);
+                            op.hash_shape(state);
+                        }
+                    )*
+                    WhereParam::And(params) => {
+                        "and".hash(state);
+                        hash_filter_shape(params, state);
+                    }
+                    WhereParam::Or(params) => {
+                        "or".hash(state);
+                        hash_filter_shape(params, state);
+                    }
+                    WhereParam::Not(params) => {
+                        "not".hash(state);
+                        hash_filter_shape(params, state);
+                    }
+                    other => format!("{:?}", other).hash(state),
+                }
+            }
+        }
+
         /// Convert a vector of WhereParams to a SeaORM Condition, properly handling QueryMode
         pub fn where_params_to_condition(params: Vec<WhereParam>, database_backend: sea_orm::DatabaseBackend) -> sea_query::Condition {
             use std::collections::HashMap;
@@ -915,6 +1228,7 @@ fn generate_where_params_to_condition_function(
             // Second pass: apply conditions with collected query modes
             for param in deferred_params {
                 let condition = match param {
+                    #(#subquery_handlers)*
                     #(#field_handlers)*
                     WhereParam::And(params) => {
                         let mut cond = Condition::all();
@@ -1081,71 +1395,26 @@ fn generate_string_field_handler(
                             Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.ne(v))
                         }
                     },
-                    caustics::FieldOp::Contains(s) => {
-                        if query_mode == caustics::QueryMode::Insensitive {
-                            match database_backend {
-                                sea_orm::DatabaseBackend::Postgres => {
-                                    // PostgreSQL: column ILIKE '%value%'
-                                Condition::all().add(
-                                    sea_query::Expr::cust_with_values(
-                                            &format!("{} ILIKE ?", <Entity as EntityTrait>::Column::#pascal_name.to_string()),
-                                            [format!("%{}%", s)]
-                                        )
-                                    )
-                                },
-                                _ => {
-                                    // MySQL, MariaDB, SQLite: UPPER(column) LIKE UPPER('%value%')
-                                    Condition::all().add(
-                                            sea_query::Expr::cust_with_values(
-                                            &format!("UPPER({}) LIKE UPPER(?)", <Entity as EntityTrait>::Column::#pascal_name.to_string()),
-                                            [format!("%{}%", s)]
-                                        )
-                                    )
-                                }
-                            }
-                        } else {
-                            // Use SeaORM's built-in contains method
-                            Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.contains(s))
-                        }
-                    },
-                    caustics::FieldOp::StartsWith(s) => {
-                        if query_mode == caustics::QueryMode::Insensitive {
-                            match database_backend {
-                                sea_orm::DatabaseBackend::Postgres => {
-                                    Condition::all().add(
-                                        sea_query::Expr::cust_with_values(
-                                            &format!("{} ILIKE ?", <Entity as EntityTrait>::Column::#pascal_name.to_string()),
-                                            [format!("{}%", s)]
-                                        )
-                                    )
-                                },
-                                _ => {
-                                    Condition::all().add(
-                                        sea_query::Expr::cust_with_values(
-                                            &format!("UPPER({}) LIKE UPPER(?)", <Entity as EntityTrait>::Column::#pascal_name.to_string()),
-                                            [format!("{}%", s)]
-                                        )
-                                    )
-                                }
-                            }
-                        } else {
-                            // Use SeaORM's built-in starts_with method
-                            Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.starts_with(s))
-                        }
+                    caustics::FieldOp::Contains(_)
+                    | caustics::FieldOp::StartsWith(_)
+                    | caustics::FieldOp::EndsWith(_)
+                    | caustics::FieldOp::Like(_) => {
+                        let pattern = op.like_pattern().expect("LIKE operation has a pattern");
+                        Condition::all().add(caustics::like_condition(
+                            <Entity as EntityTrait>::Column::#pascal_name.into_simple_expr(),
+                            pattern,
+                            query_mode,
+                            database_backend,
+                        ))
                     },
-                    caustics::FieldOp::EndsWith(s) => {
-                        if query_mode == caustics::QueryMode::Insensitive {
-                            // Database-agnostic case insensitive ends with using UPPER()
-                                Condition::all().add(
-                                    sea_query::Expr::cust_with_values(
-                                        &format!("UPPER({}) LIKE UPPER(?)", <Entity as EntityTrait>::Column::#pascal_name.to_string()),
-                                        [format!("%{}", s)]
-                                    )
-                                )
-                        } else {
-                            // Use SeaORM's built-in ends_with method
-                            Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.ends_with(s))
-                        }
+                    caustics::FieldOp::ContainsAny(_) => {
+                        let patterns = op.like_any_patterns().expect("contains_any has patterns");
+                        Condition::all().add(caustics::like_any_condition(
+                            <Entity as EntityTrait>::Column::#pascal_name.into_simple_expr(),
+                            patterns,
+                            query_mode,
+                            database_backend,
+                        ))
                     },
                     caustics::FieldOp::Gt(v) => {
                         Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.gt(v))
@@ -1199,47 +1468,26 @@ fn generate_string_field_handler(
                             Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.ne(val))
                         }
                     },
-                    caustics::FieldOp::Contains(s) => {
-                        if query_mode == caustics::QueryMode::Insensitive {
-                            // Database-agnostic case insensitive contains using UPPER()
-                                Condition::all().add(
-                                    sea_query::Expr::cust_with_values(
-                                        &format!("UPPER({}) LIKE UPPER(?)", <Entity as EntityTrait>::Column::#pascal_name.to_string()),
-                                        [format!("%{}%", s)]
-                                    )
-                                )
-                        } else {
-                            // Use SeaORM's built-in contains method
-                            Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.contains(s))
-                        }
-                    },
-                    caustics::FieldOp::StartsWith(s) => {
-                        if query_mode == caustics::QueryMode::Insensitive {
-                            // Database-agnostic case insensitive starts with using UPPER()
-                                Condition::all().add(
-                                    sea_query::Expr::cust_with_values(
-                                        &format!("UPPER({}) LIKE UPPER(?)", <Entity as EntityTrait>::Column::#pascal_name.to_string()),
-                                        [format!("{}%", s)]
-                                    )
-                                )
-                        } else {
-                            // Use SeaORM's built-in starts_with method
-                            Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.starts_with(s))
-                        }
+                    caustics::FieldOp::Contains(_)
+                    | caustics::FieldOp::StartsWith(_)
+                    | caustics::FieldOp::EndsWith(_)
+                    | caustics::FieldOp::Like(_) => {
+                        let pattern = op.like_pattern().expect("LIKE operation has a pattern");
+                        Condition::all().add(caustics::like_condition(
+                            <Entity as EntityTrait>::Column::#pascal_name.into_simple_expr(),
+                            pattern,
+                            query_mode,
+                            database_backend,
+                        ))
                     },
-                    caustics::FieldOp::EndsWith(s) => {
-                        if query_mode == caustics::QueryMode::Insensitive {
-                            // Database-agnostic case insensitive ends with using UPPER()
-                                Condition::all().add(
-                                    sea_query::Expr::cust_with_values(
-                                        &format!("UPPER({}) LIKE UPPER(?)", <Entity as EntityTrait>::Column::#pascal_name.to_string()),
-                                        [format!("%{}", s)]
-                                    )
-                                )
-                        } else {
-                            // Use SeaORM's built-in ends_with method
-                            Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.ends_with(s))
-                        }
+                    caustics::FieldOp::ContainsAny(_) => {
+                        let patterns = op.like_any_patterns().expect("contains_any has patterns");
+                        Condition::all().add(caustics::like_any_condition(
+                            <Entity as EntityTrait>::Column::#pascal_name.into_simple_expr(),
+                            patterns,
+                            query_mode,
+                            database_backend,
+                        ))
                     },
                     caustics::FieldOp::Gt(val) => Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.gt(val)),
                     caustics::FieldOp::Lt(val) => Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.lt(val)),
@@ -1807,6 +2055,17 @@ fn generate_json_field_handler(
                         [format!("$.{}", key)]
                     ))
                 },
+                caustics::FieldOp::JsonPathMatch(path) => {
+                    match database_backend {
+                        sea_orm::DatabaseBackend::Postgres => Condition::all().add(sea_query::SimpleExpr::from(
+                            sea_query::Func::cust(sea_query::Alias::new("jsonb_path_exists"))
+                                .arg(sea_query::Expr::col(<Entity as EntityTrait>::Column::#pascal_name).cast_as(sea_query::Alias::new("jsonb")))
+                                .arg(sea_query::Expr::val(path).cast_as(sea_query::Alias::new("jsonpath")))
+                        )),
+                        // Exec rejects the filter through `unsupported_filter` before this SQL is sent
+                        _ => Condition::any(),
+                    }
+                },
                 caustics::FieldOp::JsonNull(flag) => {
                     match flag {
                         caustics::JsonNullValueFilter::DbNull => Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.is_null()),
@@ -1884,6 +2143,17 @@ fn generate_json_field_handler(
                         [format!("$.{}", key)]
                     ))
                 },
+                caustics::FieldOp::JsonPathMatch(path) => {
+                    match database_backend {
+                        sea_orm::DatabaseBackend::Postgres => Condition::all().add(sea_query::SimpleExpr::from(
+                            sea_query::Func::cust(sea_query::Alias::new("jsonb_path_exists"))
+                                .arg(sea_query::Expr::col(<Entity as EntityTrait>::Column::#pascal_name).cast_as(sea_query::Alias::new("jsonb")))
+                                .arg(sea_query::Expr::val(path).cast_as(sea_query::Alias::new("jsonpath")))
+                        )),
+                        // Exec rejects the filter through `unsupported_filter` before this SQL is sent
+                        _ => Condition::any(),
+                    }
+                },
                 caustics::FieldOp::JsonNull(flag) => {
                     match flag {
                         caustics::JsonNullValueFilter::DbNull => Condition::all().add(<Entity as EntityTrait>::Column::#pascal_name.is_null()),
@@ -1945,22 +2215,43 @@ fn generate_primary_key_operations(
 ) -> proc_macro2::TokenStream {
     // For primary key fields, generate operations that accept CausticsKey and convert to sea_orm::Value
     // Note: equals is handled by unique_where_fn for unique fields, so we don't generate it here
+    let (key_bound, to_key) = primary_key_conversion(field_type, ty);
     quote! {
-        pub fn not_equals<T: Into<caustics::CausticsKey>>(value: T) -> WhereParam {
-            let key = value.into();
+        pub fn not_equals<T: #key_bound>(value: T) -> WhereParam {
+            let key = #to_key(value.into());
             WhereParam::#pascal_name(caustics::FieldOp::not_equals(key))
         }
-        pub fn in_vec<T: Into<caustics::CausticsKey>>(values: Vec<T>) -> WhereParam {
-            let keys: Vec<caustics::CausticsKey> = values.into_iter().map(|v| v.into()).collect();
+        pub fn in_vec<T: #key_bound>(values: Vec<T>) -> WhereParam {
+            let keys: Vec<caustics::CausticsKey> = values.into_iter().map(|v| #to_key(v.into())).collect();
             WhereParam::#pascal_name(caustics::FieldOp::in_vec(keys))
         }
-        pub fn not_in_vec<T: Into<caustics::CausticsKey>>(values: Vec<T>) -> WhereParam {
-            let keys: Vec<caustics::CausticsKey> = values.into_iter().map(|v| v.into()).collect();
+        pub fn not_in_vec<T: #key_bound>(values: Vec<T>) -> WhereParam {
+            let keys: Vec<caustics::CausticsKey> = values.into_iter().map(|v| #to_key(v.into())).collect();
             WhereParam::#pascal_name(caustics::FieldOp::not_in_vec(keys))
         }
     }
 }
 
+/// Bound accepted by primary key operations and the function turning it into a `CausticsKey`.
+///
+/// Known scalar keys convert straight into `CausticsKey`; any other field type is
+/// expected to implement `ToCausticsValue`.
+fn primary_key_conversion(
+    field_type: &FieldType,
+    ty: &syn::Type,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    match field_type {
+        FieldType::Other => (
+            quote! { Into<#ty> },
+            quote! { (|value: #ty| caustics::ToCausticsValue::to_caustics_key(&value)) },
+        ),
+        _ => (
+            quote! { Into<caustics::CausticsKey> },
+            quote! { caustics::CausticsKey::from },
+        ),
+    }
+}
+
 /// Detect the specific datetime type from the syn::Type
 pub fn detect_datetime_type(ty: &syn::Type) -> Option<&'static str> {
     match ty {
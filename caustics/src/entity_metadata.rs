@@ -4,13 +4,40 @@
 pub struct EntityMetadata {
     pub name: &'static str,
     pub table_name: &'static str,
+    /// Schema from `#[sea_orm(schema_name = "...")]`, if the table is schema-qualified
+    pub schema_name: Option<&'static str>,
+    /// First primary key field; for composite keys see `primary_key_fields`
     pub primary_key_field: &'static str,
+    /// All primary key fields in declaration order
+    pub primary_key_fields: &'static [&'static str],
     pub foreign_key_fields: &'static [&'static str],
     pub relations: &'static [EntityRelationMetadata],
     pub primary_key_type: &'static str,
+    /// Type of each primary key field, in the same order as `primary_key_fields`
+    pub primary_key_types: &'static [(&'static str, &'static str)],
     pub foreign_key_types: &'static [(&'static str, &'static str)],
 }
 
+impl EntityMetadata {
+    /// Quoted table reference for raw SQL, e.g. `"analytics"."events"`
+    pub fn qualified_table_name(&self) -> String {
+        quote_table_ref(self.schema_name, self.table_name)
+    }
+
+    /// Whether the entity's primary key spans more than one field
+    pub fn is_composite_primary_key(&self) -> bool {
+        self.primary_key_fields.len() > 1
+    }
+
+    /// Type of the given primary key field, if it is part of the primary key
+    pub fn primary_key_type_of(&self, field: &str) -> Option<&'static str> {
+        self.primary_key_types
+            .iter()
+            .find(|(field_name, _)| *field_name == field)
+            .map(|(_, type_id)| *type_id)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct EntityRelationMetadata {
     pub name: &'static str,
@@ -18,6 +45,32 @@ pub struct EntityRelationMetadata {
     pub target_table_name: &'static str,
     pub foreign_key_field: Option<&'static str>,
     pub relation_kind: &'static str,
+    /// Column holding the type tag, for `Polymorphic` relations
+    pub discriminator_column: Option<&'static str>,
+    /// Tag value selecting this relation's target, for `Polymorphic` relations
+    pub discriminator_value: Option<&'static str>,
+}
+
+/// One Model field as reflected by the generated `<entity>::fields()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    pub name: &'static str,
+    pub column_name: &'static str,
+    /// The field's Rust type as written, e.g. `Option<i32>`
+    pub rust_type: &'static str,
+    pub nullable: bool,
+    pub is_primary_key: bool,
+    /// Whether the field is the local side of a `belongs_to` relation
+    pub is_foreign_key: bool,
+}
+
+/// Quote a table name for raw SQL, qualifying it with its schema when one is set
+pub fn quote_table_ref(schema_name: Option<&str>, table_name: &str) -> String {
+    let quote = |ident: &str| format!("\"{}\"", ident.replace('"', "\"\""));
+    match schema_name {
+        Some(schema) => format!("{}.{}", quote(schema), quote(table_name)),
+        None => quote(table_name),
+    }
 }
 
 // Static entity metadata registry - empty by default
@@ -9,7 +9,10 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 /// A flexible key type that can represent different primary key types
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Keys are `Eq` and `Hash`, so they can be collected into sets and maps. Float keys
+/// compare by bit pattern (a total order): `NaN` equals itself and `0.0` differs from `-0.0`.
+#[derive(Debug, Clone)]
 pub enum CausticsKey {
     // Integer types
     I8(i8),
@@ -116,6 +119,15 @@ impl CausticsKey {
         Self::Json(value)
     }
 
+    /// Unwrap into the native type `T` when the key holds exactly that variant, e.g.
+    /// `key.try_into_typed::<Uuid>()`. Numeric variants are not widened or narrowed.
+    pub fn try_into_typed<T>(self) -> Result<T, T::Error>
+    where
+        T: TryFrom<CausticsKey>,
+    {
+        T::try_from(self)
+    }
+
     /// Convert to a sea_orm::Value for database operations
     pub fn to_db_value(&self) -> Value {
         match self {
@@ -233,6 +245,16 @@ impl CausticsKey {
         }
     }
 
+    /// Like `from_db_value`, but reports a value with no key form (`NULL`, or a type keys
+    /// don't cover) as `InvalidFieldType` on `field`
+    pub fn try_from_db_value(field: &str, value: &Value) -> Result<Self, crate::CausticsError> {
+        Self::from_db_value(value).ok_or_else(|| crate::CausticsError::InvalidFieldType {
+            field: field.to_string(),
+            expected: "a key value".to_string(),
+            actual: format!("{:?}", value),
+        })
+    }
+
     // Composite key methods
     pub fn composite(fields: Vec<(String, CausticsKey)>) -> Self {
         Self::Composite(fields)
@@ -281,10 +303,43 @@ impl CausticsKey {
     }
 }
 
+impl PartialEq for CausticsKey {
+    fn eq(&self, other: &Self) -> bool {
+        use CausticsKey::*;
+        match (self, other) {
+            (I8(a), I8(b)) => a == b,
+            (I16(a), I16(b)) => a == b,
+            (I32(a), I32(b)) => a == b,
+            (I64(a), I64(b)) => a == b,
+            (ISize(a), ISize(b)) => a == b,
+            (U8(a), U8(b)) => a == b,
+            (U16(a), U16(b)) => a == b,
+            (U32(a), U32(b)) => a == b,
+            (U64(a), U64(b)) => a == b,
+            (USize(a), USize(b)) => a == b,
+            // Bitwise, to stay reflexive and agree with `Hash`
+            (F32(a), F32(b)) => a.to_bits() == b.to_bits(),
+            (F64(a), F64(b)) => a.to_bits() == b.to_bits(),
+            (String(a), String(b)) => a == b,
+            (Uuid(a), Uuid(b)) => a == b,
+            (Bool(a), Bool(b)) => a == b,
+            (DateTimeUtc(a), DateTimeUtc(b)) => a == b,
+            (NaiveDateTime(a), NaiveDateTime(b)) => a == b,
+            (NaiveDate(a), NaiveDate(b)) => a == b,
+            (NaiveTime(a), NaiveTime(b)) => a == b,
+            (Json(a), Json(b)) => a == b,
+            (Composite(a), Composite(b)) => a == b,
+            (OptionalComposite(a), OptionalComposite(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 impl Eq for CausticsKey {}
 
 impl std::hash::Hash for CausticsKey {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
         match self {
             // Integer types
             CausticsKey::I8(value) => value.hash(state),
@@ -856,6 +911,56 @@ impl TryFrom<CausticsKey> for Uuid {
     }
 }
 
+impl TryFrom<CausticsKey> for chrono::DateTime<chrono::Utc> {
+    type Error = String;
+    fn try_from(key: CausticsKey) -> Result<Self, Self::Error> {
+        match key {
+            CausticsKey::DateTimeUtc(value) => Ok(value),
+            _ => Err(format!("Cannot convert {:?} to DateTime<Utc>", key)),
+        }
+    }
+}
+
+impl TryFrom<CausticsKey> for chrono::NaiveDateTime {
+    type Error = String;
+    fn try_from(key: CausticsKey) -> Result<Self, Self::Error> {
+        match key {
+            CausticsKey::NaiveDateTime(value) => Ok(value),
+            _ => Err(format!("Cannot convert {:?} to NaiveDateTime", key)),
+        }
+    }
+}
+
+impl TryFrom<CausticsKey> for chrono::NaiveDate {
+    type Error = String;
+    fn try_from(key: CausticsKey) -> Result<Self, Self::Error> {
+        match key {
+            CausticsKey::NaiveDate(value) => Ok(value),
+            _ => Err(format!("Cannot convert {:?} to NaiveDate", key)),
+        }
+    }
+}
+
+impl TryFrom<CausticsKey> for chrono::NaiveTime {
+    type Error = String;
+    fn try_from(key: CausticsKey) -> Result<Self, Self::Error> {
+        match key {
+            CausticsKey::NaiveTime(value) => Ok(value),
+            _ => Err(format!("Cannot convert {:?} to NaiveTime", key)),
+        }
+    }
+}
+
+impl TryFrom<CausticsKey> for serde_json::Value {
+    type Error = String;
+    fn try_from(key: CausticsKey) -> Result<Self, Self::Error> {
+        match key {
+            CausticsKey::Json(value) => Ok(value),
+            _ => Err(format!("Cannot convert {:?} to serde_json::Value", key)),
+        }
+    }
+}
+
 // Implement From<CausticsKey> for sea_orm::Value
 impl From<CausticsKey> for sea_orm::Value {
     fn from(key: CausticsKey) -> Self {
@@ -1368,6 +1473,88 @@ pub fn convert_key_to_type_from_string<T: 'static + Default + Send + Sync>(
     }
 }
 
+/// Like `convert_key_to_type_from_string`, but `None` when `key` doesn't fit `target_type_str`
+/// instead of substituting a placeholder (`0`, a fresh UUID, the current time): unparseable
+/// strings, out-of-range or fractional numbers, mismatched variants and composite keys.
+pub fn try_convert_key_to_type_from_string(
+    key: CausticsKey,
+    target_type_str: &str,
+) -> Option<Box<dyn std::any::Any + Send + Sync>> {
+    if !key_fits_type(&key, target_type_str) {
+        return None;
+    }
+    Some(convert_key_to_type_from_string::<()>(key, target_type_str))
+}
+
+fn key_fits_type(key: &CausticsKey, target_type_str: &str) -> bool {
+    fn integer(key: &CausticsKey) -> Option<i128> {
+        Some(match key {
+            CausticsKey::I8(value) => *value as i128,
+            CausticsKey::I16(value) => *value as i128,
+            CausticsKey::I32(value) => *value as i128,
+            CausticsKey::I64(value) => *value as i128,
+            CausticsKey::ISize(value) => *value as i128,
+            CausticsKey::U8(value) => *value as i128,
+            CausticsKey::U16(value) => *value as i128,
+            CausticsKey::U32(value) => *value as i128,
+            CausticsKey::U64(value) => *value as i128,
+            CausticsKey::USize(value) => *value as i128,
+            CausticsKey::F32(value) if value.fract() == 0.0 => *value as i128,
+            CausticsKey::F64(value) if value.fract() == 0.0 => *value as i128,
+            CausticsKey::Bool(value) => *value as i128,
+            _ => return None,
+        })
+    }
+    fn fits_integer<T: TryFrom<i128> + FromStr>(key: &CausticsKey) -> bool {
+        match key {
+            CausticsKey::String(value) => value.parse::<T>().is_ok(),
+            _ => integer(key).is_some_and(|value| T::try_from(value).is_ok()),
+        }
+    }
+    fn fits_parsed<T: FromStr>(key: &CausticsKey, same_variant: bool) -> bool {
+        match key {
+            CausticsKey::String(value) => value.parse::<T>().is_ok(),
+            _ => same_variant,
+        }
+    }
+
+    let is_number = integer(key).is_some()
+        || matches!(key, CausticsKey::F32(_) | CausticsKey::F64(_));
+    match target_type_str {
+        "i8" => fits_integer::<i8>(key),
+        "i16" => fits_integer::<i16>(key),
+        "i32" => fits_integer::<i32>(key),
+        "i64" => fits_integer::<i64>(key),
+        "isize" => fits_integer::<isize>(key),
+        "u8" => fits_integer::<u8>(key),
+        "u16" => fits_integer::<u16>(key),
+        "u32" => fits_integer::<u32>(key),
+        "u64" => fits_integer::<u64>(key),
+        "usize" => fits_integer::<usize>(key),
+        "f32" => fits_parsed::<f32>(key, is_number),
+        "f64" => fits_parsed::<f64>(key, is_number),
+        "bool" => fits_parsed::<bool>(key, is_number),
+        "uuid::Uuid" | "Uuid" => fits_parsed::<Uuid>(key, matches!(key, CausticsKey::Uuid(_))),
+        "chrono::DateTime<chrono::Utc>" | "caustics::chrono::DateTime<caustics::chrono::Utc>" => {
+            fits_parsed::<chrono::DateTime<chrono::Utc>>(key, matches!(key, CausticsKey::DateTimeUtc(_)))
+        }
+        "chrono::NaiveDateTime" | "caustics::chrono::NaiveDateTime" => {
+            fits_parsed::<chrono::NaiveDateTime>(key, matches!(key, CausticsKey::NaiveDateTime(_)))
+        }
+        "chrono::NaiveDate" | "caustics::chrono::NaiveDate" => {
+            fits_parsed::<chrono::NaiveDate>(key, matches!(key, CausticsKey::NaiveDate(_)))
+        }
+        "chrono::NaiveTime" | "caustics::chrono::NaiveTime" => {
+            fits_parsed::<chrono::NaiveTime>(key, matches!(key, CausticsKey::NaiveTime(_)))
+        }
+        "serde_json::Value" | "caustics::serde_json::Value" => match key {
+            CausticsKey::String(value) => serde_json::from_str::<serde_json::Value>(value).is_ok(),
+            _ => !matches!(key, CausticsKey::Composite(_) | CausticsKey::OptionalComposite(_)),
+        },
+        _ => !matches!(key, CausticsKey::Composite(_) | CausticsKey::OptionalComposite(_)),
+    }
+}
+
 pub fn convert_key_to_type<T: 'static + Default + Send + Sync>(
     key: CausticsKey,
     target_type_id: std::any::TypeId,
@@ -1786,6 +1973,94 @@ mod tests {
         assert_eq!(db_value, Value::Uuid(Some(Box::new(uuid))));
     }
 
+    #[test]
+    fn test_float_keys_are_eq_and_hash() {
+        use std::collections::HashSet;
+
+        let nan = CausticsKey::F64(f64::NAN);
+        assert_eq!(nan, nan.clone());
+        assert_ne!(CausticsKey::F64(0.0), CausticsKey::F64(-0.0));
+        assert_ne!(CausticsKey::I32(1), CausticsKey::I64(1));
+
+        let keys: HashSet<CausticsKey> = [
+            CausticsKey::F64(f64::NAN),
+            CausticsKey::F64(f64::NAN),
+            CausticsKey::F32(1.5),
+            CausticsKey::F32(1.5),
+            CausticsKey::Composite(vec![("a".to_string(), CausticsKey::F64(2.0))]),
+            CausticsKey::Composite(vec![("a".to_string(), CausticsKey::F64(2.0))]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(keys.len(), 3);
+    }
+
+    #[test]
+    fn test_scalars_round_trip_through_key() {
+        fn round_trip<T>(value: T)
+        where
+            T: Into<CausticsKey> + TryFrom<CausticsKey, Error = String> + Clone + PartialEq + fmt::Debug,
+        {
+            let key: CausticsKey = value.clone().into();
+            assert_eq!(key.try_into_typed::<T>().unwrap(), value);
+        }
+
+        round_trip(-8i8);
+        round_trip(-16i16);
+        round_trip(-32i32);
+        round_trip(-64i64);
+        round_trip(-1isize);
+        round_trip(8u8);
+        round_trip(16u16);
+        round_trip(32u32);
+        round_trip(64u64);
+        round_trip(1usize);
+        round_trip(1.5f32);
+        round_trip(2.5f64);
+        round_trip(true);
+        round_trip("key".to_string());
+        round_trip(Uuid::new_v4());
+        round_trip(chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000, 0).unwrap());
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let time = chrono::NaiveTime::from_hms_opt(12, 30, 5).unwrap();
+        round_trip(date);
+        round_trip(time);
+        round_trip(date.and_time(time));
+        round_trip(serde_json::json!({"tenant": 7}));
+
+        // No silent widening: an i32 key is not an i64
+        let err = CausticsKey::from(7i32).try_into_typed::<i64>().unwrap_err();
+        assert!(err.contains("Cannot convert I32(7) to i64"), "{}", err);
+    }
+
+    #[test]
+    fn test_try_convert_rejects_mismatched_keys() {
+        let converted = try_convert_key_to_type_from_string(CausticsKey::I64(7), "i32").unwrap();
+        assert_eq!(*converted.downcast::<i32>().unwrap(), 7);
+        let converted =
+            try_convert_key_to_type_from_string(CausticsKey::String("12".into()), "u8").unwrap();
+        assert_eq!(*converted.downcast::<u8>().unwrap(), 12);
+
+        assert!(try_convert_key_to_type_from_string(CausticsKey::String("abc".into()), "i32").is_none());
+        assert!(try_convert_key_to_type_from_string(CausticsKey::I64(i64::MAX), "i32").is_none());
+        assert!(try_convert_key_to_type_from_string(CausticsKey::F64(1.5), "i64").is_none());
+        assert!(try_convert_key_to_type_from_string(CausticsKey::I32(1), "uuid::Uuid").is_none());
+        assert!(try_convert_key_to_type_from_string(
+            CausticsKey::Composite(vec![("id".into(), CausticsKey::I32(1))]),
+            "String"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_try_from_db_value_rejects_values_without_key_form() {
+        let key = CausticsKey::try_from_db_value("user.id", &Value::Int(Some(3))).unwrap();
+        assert_eq!(key, CausticsKey::I32(3));
+
+        let err = CausticsKey::try_from_db_value("user.id", &Value::Int(None)).unwrap_err();
+        assert!(err.to_string().contains("user.id"), "{}", err);
+    }
+
     #[test]
     fn test_key_convertible() {
         let key = 42i32.to_caustics_key();
@@ -16,17 +16,17 @@ pub mod chrono {
 
 // Internal serde_json re-export for macro use
 pub mod serde_json {
-    pub use serde_json::Value;
+    pub use serde_json::{from_value, Map, Value};
 }
 
 // Internal serde re-export for macro use
 pub mod serde {
-    pub use serde::{Serialize, Deserialize};
+    pub use serde::{de, Deserialize, Deserializer, Serialize};
 }
 
 // Internal sea-query re-export for macro use
 pub mod sea_query {
-    pub use sea_query::{Condition, Expr, SimpleExpr};
+    pub use sea_query::{Condition, Expr, Nullable, SimpleExpr};
 }
 
 // Internal sea-orm re-export for macro use
@@ -35,7 +35,7 @@ pub mod sea_orm {
         DatabaseConnection, DatabaseTransaction, TransactionTrait, ConnectionTrait,
         EntityTrait, ActiveModelTrait, ActiveModelBehavior, IntoActiveModel, FromQueryResult,
         ExecResult, DbErr, DatabaseBackend, Statement, SelectorRaw, SelectModel,
-        Value, ActiveValue
+        Value, ActiveValue, QueryResult
     };
 }
 
@@ -56,7 +56,7 @@ pub mod prelude {
     pub use heck::{ToSnakeCase, ToPascalCase};
     
     // Re-export caustics traits
-    pub use crate::ToSeaOrmValue;
+    pub use crate::{FromCausticsValue, ToCausticsValue, ToSeaOrmValue};
 }
 
 pub mod hooks {
@@ -76,9 +76,29 @@ pub mod hooks {
         pub elapsed_ms: Option<u128>,
     }
 
+    /// A transaction boundary from `transaction().run(...)` or a multi-query `_batch`, tagged
+    /// with the correlation id its queries carry
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum TxEvent {
+        Begin {
+            correlation_id: Option<String>,
+        },
+        Commit {
+            correlation_id: Option<String>,
+            elapsed_ms: u128,
+        },
+        /// The closure (or the commit) failed with `error`
+        Rollback {
+            correlation_id: Option<String>,
+            elapsed_ms: u128,
+            error: String,
+        },
+    }
+
     pub trait QueryHook: Send + Sync {
         fn before(&self, _event: &QueryEvent) {}
         fn after(&self, _event: &QueryEvent, _meta: &QueryResultMeta) {}
+        fn on_transaction(&self, _event: &TxEvent) {}
     }
 
     static QUERY_HOOKS: RwLock<Vec<Arc<dyn QueryHook>>> = RwLock::new(Vec::new());
@@ -146,6 +166,10 @@ pub mod hooks {
         id
     }
 
+    pub fn current_correlation_id() -> Option<String> {
+        TX_CORR_ID.with(|cell| cell.borrow().clone())
+    }
+
     pub fn current_correlation_detail() -> Option<String> {
         let mut out: Option<String> = None;
         TX_CORR_ID.with(|cell| {
@@ -156,6 +180,108 @@ pub mod hooks {
         out
     }
 
+    /// `/* corr_id=... */ ` prefix for SQL when a correlation id is set on this thread.
+    ///
+    /// Only `[A-Za-z0-9_.:-]` survive from the id, so the comment can never be closed early
+    /// or read as a placeholder.
+    pub fn correlation_sql_comment() -> Option<String> {
+        TX_CORR_ID.with(|cell| {
+            cell.borrow().as_ref().map(|id| {
+                let safe: String = id
+                    .chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':' | '-'))
+                    .collect();
+                format!("/* corr_id={} */ ", safe)
+            })
+        })
+    }
+
+    /// Connection that prefixes every statement run through it with the correlation comment,
+    /// for builders whose SQL SeaORM renders itself: writes, counts and aggregates
+    pub struct CommentedConnection<'c, C> {
+        inner: &'c C,
+        comment: Option<String>,
+    }
+
+    impl<'c, C: sea_orm::ConnectionTrait> CommentedConnection<'c, C> {
+        /// Wrap `conn`; statements pass through unchanged unless `sql_comment` is set and this
+        /// thread has a correlation id
+        pub fn new(conn: &'c C, sql_comment: bool) -> Self {
+            Self {
+                inner: conn,
+                comment: sql_comment.then(correlation_sql_comment).flatten(),
+            }
+        }
+
+        fn commented(&self, mut stmt: sea_orm::Statement) -> sea_orm::Statement {
+            if let Some(comment) = &self.comment {
+                stmt.sql.insert_str(0, comment);
+            }
+            stmt
+        }
+    }
+
+    type ConnFuture<'f, T> =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, sea_orm::DbErr>> + Send + 'f>>;
+
+    // Written out by hand: `ConnectionTrait` is an `async_trait`, and this crate doesn't
+    // depend on the macro
+    impl<C: sea_orm::ConnectionTrait> sea_orm::ConnectionTrait for CommentedConnection<'_, C> {
+        fn get_database_backend(&self) -> sea_orm::DbBackend {
+            self.inner.get_database_backend()
+        }
+
+        fn execute<'s, 'f>(&'s self, stmt: sea_orm::Statement) -> ConnFuture<'f, sea_orm::ExecResult>
+        where
+            's: 'f,
+            Self: 'f,
+        {
+            self.inner.execute(self.commented(stmt))
+        }
+
+        fn execute_unprepared<'s, 'q, 'f>(&'s self, sql: &'q str) -> ConnFuture<'f, sea_orm::ExecResult>
+        where
+            's: 'f,
+            'q: 'f,
+            Self: 'f,
+        {
+            match &self.comment {
+                Some(comment) => {
+                    let sql = format!("{}{}", comment, sql);
+                    Box::pin(async move { self.inner.execute_unprepared(&sql).await })
+                }
+                None => self.inner.execute_unprepared(sql),
+            }
+        }
+
+        fn query_one<'s, 'f>(
+            &'s self,
+            stmt: sea_orm::Statement,
+        ) -> ConnFuture<'f, Option<sea_orm::QueryResult>>
+        where
+            's: 'f,
+            Self: 'f,
+        {
+            self.inner.query_one(self.commented(stmt))
+        }
+
+        fn query_all<'s, 'f>(&'s self, stmt: sea_orm::Statement) -> ConnFuture<'f, Vec<sea_orm::QueryResult>>
+        where
+            's: 'f,
+            Self: 'f,
+        {
+            self.inner.query_all(self.commented(stmt))
+        }
+
+        fn support_returning(&self) -> bool {
+            self.inner.support_returning()
+        }
+
+        fn is_mock_connection(&self) -> bool {
+            self.inner.is_mock_connection()
+        }
+    }
+
     pub fn compose_details(op: &str, entity: &str) -> Option<String> {
         match current_correlation_detail() {
             Some(c) => Some(format!("{} op={} entity={}", c, op, entity)),
@@ -163,6 +289,107 @@ pub mod hooks {
         }
     }
 
+    /// `compose_details` plus a `filter=(...)` summary of the WHERE clause in `sql`.
+    ///
+    /// `sql` should be rendered with placeholders: the summary names columns and operators,
+    /// e.g. `filter=(age >= ? AND name LIKE ?)`, and any inline literal is masked as `?` too.
+    pub fn compose_details_with_filter(op: &str, entity: &str, sql: &str) -> Option<String> {
+        let details = compose_details(op, entity);
+        match describe_filter(sql) {
+            Some(filter) => details.map(|d| format!("{} filter=({})", d, filter)),
+            None => details,
+        }
+    }
+
+    /// Value-free description of the top-level WHERE clause of `sql`, or `None` when it has no
+    /// real filter.
+    ///
+    /// Identifier quoting and table qualifiers are dropped, and string literals and
+    /// numbered placeholders (`$1`) all become `?`.
+    pub fn describe_filter(sql: &str) -> Option<String> {
+        let sanitized = sanitize_sql(sql);
+        let start = find_top_level(&sanitized, 0, &[" WHERE "])? + " WHERE ".len();
+        let end = find_top_level(
+            &sanitized,
+            start,
+            &[" GROUP BY ", " HAVING ", " ORDER BY ", " LIMIT ", " OFFSET ", " FOR ", " UNION "],
+        )
+        .unwrap_or(sanitized.len());
+        let filter = sanitized[start..end].trim();
+        // An empty filter list renders as `WHERE TRUE`
+        (!filter.is_empty() && filter != "TRUE").then(|| filter.to_string())
+    }
+
+    /// Unquote identifiers, drop `table.` qualifiers and mask literals and placeholders
+    fn sanitize_sql(sql: &str) -> String {
+        let mut out = String::with_capacity(sql.len());
+        let mut chars = sql.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' | '`' => {
+                    let mut ident = String::new();
+                    for n in chars.by_ref() {
+                        if n == c {
+                            break;
+                        }
+                        ident.push(n);
+                    }
+                    if chars.peek() == Some(&'.') {
+                        chars.next();
+                    } else {
+                        out.push_str(&ident);
+                    }
+                }
+                '\'' => {
+                    // '' escapes a quote inside the literal
+                    while let Some(n) = chars.next() {
+                        if n == '\'' {
+                            if chars.peek() == Some(&'\'') {
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    out.push('?');
+                }
+                '$' if chars.peek().is_some_and(|n| n.is_ascii_digit()) => {
+                    while chars.peek().is_some_and(|n| n.is_ascii_digit()) {
+                        chars.next();
+                    }
+                    out.push('?');
+                }
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// Byte offset of the first of `needles` at parenthesis depth zero, from `from` on
+    fn find_top_level(sql: &str, from: usize, needles: &[&str]) -> Option<usize> {
+        let mut depth = 0i32;
+        for (i, c) in sql[from..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ if depth == 0 => {
+                    let rest = &sql[from + i..];
+                    if needles.iter().any(|n| rest.starts_with(n)) {
+                        return Some(from + i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Whether any query hook is installed, so callers can skip building details nobody reads
+    pub(crate) fn has_hooks() -> bool {
+        TX_HOOKS.with(|cell| !cell.borrow().is_empty())
+            || QUERY_HOOKS.read().map(|guard| !guard.is_empty()).unwrap_or(false)
+    }
+
     fn iter_hooks<F: Fn(&Arc<dyn QueryHook>)>(f: F) {
         // Transaction hooks first (FIFO), then global hooks (FIFO)
         TX_HOOKS.with(|cell| {
@@ -183,6 +410,531 @@ pub mod hooks {
 
     pub fn emit_after(event: &QueryEvent, meta: &QueryResultMeta) {
         iter_hooks(|h| h.after(event, meta));
+        #[cfg(feature = "metrics")]
+        metrics::record(event, meta);
+    }
+
+    pub fn emit_transaction(event: &TxEvent) {
+        iter_hooks(|h| h.on_transaction(event));
+    }
+
+    /// Query metrics reported through the [`metrics`](https://docs.rs/metrics) facade from the
+    /// `after` hook of every instrumented query.
+    ///
+    /// Each finished query bumps `caustics_queries_total{entity,op}`, failures also bump
+    /// `caustics_query_errors_total{entity,op}`, and the elapsed time lands in the
+    /// `caustics_query_duration_seconds{entity,op}` histogram. They go to whichever recorder
+    /// (e.g. a Prometheus exporter) the application installs; without one they are dropped.
+    #[cfg(feature = "metrics")]
+    pub mod metrics {
+        use super::{QueryEvent, QueryResultMeta};
+
+        pub const QUERIES_TOTAL: &str = "caustics_queries_total";
+        pub const QUERY_ERRORS_TOTAL: &str = "caustics_query_errors_total";
+        pub const QUERY_DURATION_SECONDS: &str = "caustics_query_duration_seconds";
+
+        pub(crate) fn record(event: &QueryEvent, meta: &QueryResultMeta) {
+            let (entity, op) = (event.entity, event.builder);
+            ::metrics::counter!(QUERIES_TOTAL, "entity" => entity, "op" => op).increment(1);
+            if meta.error.is_some() {
+                ::metrics::counter!(QUERY_ERRORS_TOTAL, "entity" => entity, "op" => op).increment(1);
+            }
+            if let Some(ms) = meta.elapsed_ms {
+                ::metrics::histogram!(QUERY_DURATION_SECONDS, "entity" => entity, "op" => op)
+                    .record(ms as f64 / 1000.0);
+            }
+        }
+    }
+}
+
+/// Opt-in cache of built SQL templates for read queries.
+///
+/// Queries are keyed by the shape of the builder that made them: which filters, orderings
+/// and limits it was given, without their values (see `FieldOp::hash_shape`). A hit reuses
+/// the cached SQL and only collects the new parameters from the statement, so repeated
+/// identical-shape queries skip rendering it. Builders without a shape (`shape: None`) are
+/// always rendered.
+pub mod statement_cache {
+    use sea_orm::sea_query::{QueryBuilder, SelectStatement, SqlWriter, Value};
+    use sea_orm::{
+        ConnectionTrait, DatabaseBackend, EntityTrait, FromQueryResult, QuerySelect, QueryTrait, Select,
+        Statement,
+    };
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, RwLock};
+
+    type ShapeKey = (&'static str, &'static str, u8, u64);
+
+    struct Cache {
+        capacity: usize,
+        templates: HashMap<ShapeKey, Arc<str>>,
+    }
+
+    static CACHE: RwLock<Option<Cache>> = RwLock::new(None);
+    static BUILDS: AtomicU64 = AtomicU64::new(0);
+    static HITS: AtomicU64 = AtomicU64::new(0);
+
+    /// Counters for statement-cache activity across the process
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct CacheStats {
+        /// SQL templates rendered because no cached shape matched
+        pub builds: u64,
+        /// Queries served from a cached template
+        pub hits: u64,
+    }
+
+    /// Enable the cache, holding at most `capacity` distinct query shapes
+    pub fn enable(capacity: usize) {
+        if let Ok(mut guard) = CACHE.write() {
+            *guard = Some(Cache { capacity, templates: HashMap::new() });
+        }
+    }
+
+    /// Disable the cache and drop every cached template
+    pub fn disable() {
+        if let Ok(mut guard) = CACHE.write() {
+            *guard = None;
+        }
+    }
+
+    pub fn is_enabled() -> bool {
+        CACHE.read().map(|guard| guard.is_some()).unwrap_or(false)
+    }
+
+    /// Connect to Postgres with sqlx's per-connection prepared-statement cache holding up to
+    /// `capacity` statements.
+    ///
+    /// sqlx prepares every query as a named statement on the connection that runs it and
+    /// reuses it for the same SQL text, so the server keeps the plan of a hot query shape
+    /// without caustics sending `PREPARE` itself. Past `capacity` the least recently used
+    /// statement is closed; `capacity` is at least 1. Pool sizes and timeouts come from `options`.
+    pub async fn connect_postgres(
+        options: sea_orm::ConnectOptions,
+        capacity: usize,
+    ) -> Result<sea_orm::DatabaseConnection, sea_orm::DbErr> {
+        use sea_orm::sqlx::postgres::{PgConnectOptions, PgPool};
+        let conn_err = |err| sea_orm::DbErr::Conn(sea_orm::RuntimeErr::SqlxError(err));
+        let connect = options
+            .get_url()
+            .parse::<PgConnectOptions>()
+            .map_err(conn_err)?
+            .statement_cache_capacity(capacity.max(1));
+        let pool: PgPool = options.sqlx_pool_options().connect_with(connect).await.map_err(conn_err)?;
+        Ok(sea_orm::SqlxPostgresConnector::from_sqlx_postgres_pool(pool))
+    }
+
+    pub fn stats() -> CacheStats {
+        CacheStats {
+            builds: BUILDS.load(Ordering::Relaxed),
+            hits: HITS.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Hasher for a builder's shape, seeded by the generated `find_*` methods
+    pub fn shape_hasher() -> std::collections::hash_map::DefaultHasher {
+        std::collections::hash_map::DefaultHasher::new()
+    }
+
+    /// Fold `part` into `shape`; a builder without a shape stays without one
+    pub fn extend_shape(shape: Option<u64>, part: impl Hash) -> Option<u64> {
+        shape.map(|shape| {
+            let mut hasher = shape_hasher();
+            shape.hash(&mut hasher);
+            part.hash(&mut hasher);
+            hasher.finish()
+        })
+    }
+
+    /// Collects the bound values of a statement, dropping its SQL text
+    #[derive(Default)]
+    struct ValueCollector {
+        values: Vec<Value>,
+    }
+
+    impl std::fmt::Write for ValueCollector {
+        fn write_str(&mut self, _s: &str) -> std::fmt::Result {
+            Ok(())
+        }
+    }
+
+    impl std::fmt::Display for ValueCollector {
+        fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            Ok(())
+        }
+    }
+
+    impl SqlWriter for ValueCollector {
+        fn push_param(&mut self, value: Value, _query_builder: &dyn QueryBuilder) {
+            self.values.push(value);
+        }
+
+        fn as_writer(&mut self) -> &mut dyn std::fmt::Write {
+            self as _
+        }
+    }
+
+    fn backend_tag(backend: DatabaseBackend) -> u8 {
+        match backend {
+            DatabaseBackend::MySql => 0,
+            DatabaseBackend::Postgres => 1,
+            DatabaseBackend::Sqlite => 2,
+        }
+    }
+
+    /// Build `statement` for entity `E`, reusing the SQL template cached for `shape` when the
+    /// cache is enabled
+    pub fn build<E: EntityTrait>(
+        builder: &'static str,
+        backend: DatabaseBackend,
+        statement: &SelectStatement,
+        shape: Option<u64>,
+    ) -> Statement {
+        let capacity = CACHE.read().ok().and_then(|guard| guard.as_ref().map(|c| c.capacity));
+        let (Some(capacity), Some(shape)) = (capacity, shape) else {
+            return backend.build(statement);
+        };
+        let query_builder = backend.get_query_builder();
+        let key = (builder, std::any::type_name::<E>(), backend_tag(backend), shape);
+
+        let cached = CACHE
+            .read()
+            .ok()
+            .and_then(|guard| guard.as_ref().and_then(|c| c.templates.get(&key).cloned()));
+        if let Some(sql) = cached {
+            HITS.fetch_add(1, Ordering::Relaxed);
+            let mut collector = ValueCollector::default();
+            statement.build_collect_any_into(&*query_builder, &mut collector);
+            return Statement::from_sql_and_values(backend, sql.as_ref(), collector.values);
+        }
+
+        BUILDS.fetch_add(1, Ordering::Relaxed);
+        let (sql, values) = statement.build_any(&*query_builder);
+        if let Ok(mut guard) = CACHE.write() {
+            if let Some(cache) = guard.as_mut() {
+                if cache.templates.len() < capacity {
+                    cache.templates.insert(key, sql.as_str().into());
+                }
+            }
+        }
+        Statement::from_sql_and_values(backend, sql, values)
+    }
+
+    /// Statement for `query` with the correlation comment prepended when `sql_comment` is set
+    /// and a correlation id is active; `None` when plain SeaORM execution is equivalent
+    fn prepared<E: EntityTrait>(
+        builder: &'static str,
+        backend: DatabaseBackend,
+        query: &SelectStatement,
+        shape: Option<u64>,
+        sql_comment: bool,
+    ) -> Option<Statement> {
+        let comment = sql_comment.then(crate::hooks::correlation_sql_comment).flatten();
+        if comment.is_none() && (shape.is_none() || !is_enabled()) {
+            return None;
+        }
+        let mut stmt = build::<E>(builder, backend, query, shape);
+        if let Some(comment) = comment {
+            stmt.sql.insert_str(0, &comment);
+        }
+        Some(stmt)
+    }
+
+    /// SQL text `query` would be sent as, including the correlation comment if enabled
+    pub fn to_sql(backend: DatabaseBackend, query: &SelectStatement, sql_comment: bool) -> String {
+        let comment = sql_comment.then(crate::hooks::correlation_sql_comment).flatten();
+        let (sql, _) = query.build_any(&*backend.get_query_builder());
+        match comment {
+            Some(comment) => comment + &sql,
+            None => sql,
+        }
+    }
+
+    /// `query.all(conn)` through the statement cache
+    pub async fn all<E: EntityTrait, C: ConnectionTrait>(
+        builder: &'static str,
+        mut query: Select<E>,
+        shape: Option<u64>,
+        conn: &C,
+        sql_comment: bool,
+    ) -> Result<Vec<E::Model>, sea_orm::DbErr> {
+        match prepared::<E>(builder, conn.get_database_backend(), QueryTrait::query(&mut query), shape, sql_comment) {
+            Some(stmt) => query.from_raw_sql(stmt).all(conn).await,
+            None => query.all(conn).await,
+        }
+    }
+
+    /// `query.into_model::<T>().all(conn)` through the statement cache
+    pub async fn all_into<E: EntityTrait, T: FromQueryResult, C: ConnectionTrait>(
+        builder: &'static str,
+        mut query: Select<E>,
+        shape: Option<u64>,
+        conn: &C,
+        sql_comment: bool,
+    ) -> Result<Vec<T>, sea_orm::DbErr> {
+        match prepared::<E>(builder, conn.get_database_backend(), QueryTrait::query(&mut query), shape, sql_comment) {
+            Some(stmt) => query.from_raw_sql(stmt).into_model::<T>().all(conn).await,
+            None => query.into_model::<T>().all(conn).await,
+        }
+    }
+
+    /// `query.one(conn)` through the statement cache
+    pub async fn one<E: EntityTrait, C: ConnectionTrait>(
+        builder: &'static str,
+        query: Select<E>,
+        shape: Option<u64>,
+        conn: &C,
+        sql_comment: bool,
+    ) -> Result<Option<E::Model>, sea_orm::DbErr> {
+        let mut query = query.limit(1);
+        let shape = extend_shape(shape, "limit 1");
+        match prepared::<E>(builder, conn.get_database_backend(), QueryTrait::query(&mut query), shape, sql_comment) {
+            Some(stmt) => query.from_raw_sql(stmt).one(conn).await,
+            None => query.one(conn).await,
+        }
+    }
+}
+
+/// Backend-aware SQL expressions for atomic (server-side) column updates
+pub mod atomic {
+    use sea_orm::sea_query::{Expr, SimpleExpr};
+    use sea_orm::{DatabaseBackend, Value};
+
+    /// Quote a column name for the given backend
+    fn column_ref(backend: DatabaseBackend, column: &str) -> String {
+        crate::raw::ident_for(backend, column)
+    }
+
+    /// Placeholder for the `index`-th (1-based) bound value of a custom expression
+    fn param(backend: DatabaseBackend, index: usize) -> String {
+        match backend {
+            DatabaseBackend::Postgres => format!("${}", index),
+            _ => "?".to_string(),
+        }
+    }
+
+    /// `COALESCE(col, '') || ?` (`CONCAT` on MySQL)
+    pub fn string_append(backend: DatabaseBackend, column: &str, value: String) -> SimpleExpr {
+        let col = column_ref(backend, column);
+        let sql = match backend {
+            DatabaseBackend::MySql => format!("CONCAT(COALESCE({}, ''), ?)", col),
+            _ => format!("COALESCE({}, '') || {}", col, param(backend, 1)),
+        };
+        Expr::cust_with_values(sql, [Value::from(value)])
+    }
+
+    /// `? || COALESCE(col, '')` (`CONCAT` on MySQL)
+    pub fn string_prepend(backend: DatabaseBackend, column: &str, value: String) -> SimpleExpr {
+        let col = column_ref(backend, column);
+        let sql = match backend {
+            DatabaseBackend::MySql => format!("CONCAT(?, COALESCE({}, ''))", col),
+            _ => format!("{} || COALESCE({}, '')", param(backend, 1), col),
+        };
+        Expr::cust_with_values(sql, [Value::from(value)])
+    }
+
+    /// Merge `patch` into a JSON column, treating `NULL` as `{}`.
+    ///
+    /// Postgres uses `jsonb ||` (top-level keys are replaced); SQLite and MySQL apply
+    /// an RFC 7396 merge patch via `json_patch` / `JSON_MERGE_PATCH`.
+    pub fn json_merge(
+        backend: DatabaseBackend,
+        column: &str,
+        patch: serde_json::Value,
+    ) -> SimpleExpr {
+        let col = column_ref(backend, column);
+        let sql = match backend {
+            DatabaseBackend::Postgres => {
+                format!("COALESCE({}::jsonb, '{{}}'::jsonb) || $1::jsonb", col)
+            }
+            DatabaseBackend::MySql => format!("JSON_MERGE_PATCH(COALESCE({}, '{{}}'), ?)", col),
+            DatabaseBackend::Sqlite => format!("json_patch(COALESCE({}, '{{}}'), ?)", col),
+        };
+        Expr::cust_with_values(sql, [Value::Json(Some(Box::new(patch)))])
+    }
+
+    /// Remove the key at `path` (e.g. `["metadata", "priority"]`) from a JSON column.
+    ///
+    /// Postgres uses `#-` with a `text[]` path; SQLite and MySQL use `json_remove` with
+    /// a `$."metadata"."priority"` path.
+    pub fn json_remove_path(backend: DatabaseBackend, column: &str, path: &[String]) -> SimpleExpr {
+        let col = column_ref(backend, column);
+        match backend {
+            DatabaseBackend::Postgres => Expr::cust_with_values(
+                format!("{}::jsonb #- $1::text[]", col),
+                [Value::from(pg_text_array(path))],
+            ),
+            _ => {
+                let function = match backend {
+                    DatabaseBackend::MySql => "JSON_REMOVE",
+                    _ => "json_remove",
+                };
+                Expr::cust_with_values(
+                    format!("{}({}, ?)", function, col),
+                    [Value::from(json_path_literal(path))],
+                )
+            }
+        }
+    }
+
+    /// Numeric value at `path` inside a JSON column, for ordering by a document field.
+    ///
+    /// SQLite emits `CAST(json_extract(col, '$."a"') AS NUMERIC)`, MySQL casts
+    /// `JSON_EXTRACT` to `DECIMAL`, and Postgres casts `col #>> '{a}'` to `numeric`.
+    /// Rows without the key (or with a non-numeric value) sort as NULL on Postgres and MySQL.
+    pub fn json_path_number(backend: DatabaseBackend, column: &str, path: &[String]) -> SimpleExpr {
+        let col = column_ref(backend, column);
+        match backend {
+            DatabaseBackend::Postgres => Expr::cust_with_values(
+                format!("({}::jsonb #>> $1::text[])::numeric", col),
+                [Value::from(pg_text_array(path))],
+            ),
+            DatabaseBackend::MySql => Expr::cust_with_values(
+                format!("CAST(JSON_EXTRACT({}, ?) AS DECIMAL(65, 30))", col),
+                [Value::from(json_path_literal(path))],
+            ),
+            DatabaseBackend::Sqlite => Expr::cust_with_values(
+                format!("CAST(json_extract({}, ?) AS NUMERIC)", col),
+                [Value::from(json_path_literal(path))],
+            ),
+        }
+    }
+
+    /// Text value at `path` inside a JSON column, for grouping by a document field.
+    ///
+    /// The path is inlined as a literal rather than bound, so the expression renders
+    /// identically in the select list and in `GROUP BY`. SQLite uses `json_extract`, MySQL
+    /// unquotes `JSON_EXTRACT`, and Postgres reads `col #>> '{a}'`.
+    pub fn json_path_text(backend: DatabaseBackend, column: &str, path: &[String]) -> SimpleExpr {
+        let col = column_ref(backend, column);
+        let query_builder = backend.get_query_builder();
+        let sql = match backend {
+            DatabaseBackend::Postgres => format!(
+                "({}::jsonb #>> {}::text[])",
+                col,
+                query_builder.value_to_string(&Value::from(pg_text_array(path)))
+            ),
+            DatabaseBackend::MySql => format!(
+                "JSON_UNQUOTE(JSON_EXTRACT({}, {}))",
+                col,
+                query_builder.value_to_string(&Value::from(json_path_literal(path)))
+            ),
+            DatabaseBackend::Sqlite => format!(
+                "json_extract({}, {})",
+                col,
+                query_builder.value_to_string(&Value::from(json_path_literal(path)))
+            ),
+        };
+        Expr::cust(sql)
+    }
+
+    /// `$."a"."b"` path for SQLite/MySQL JSON functions
+    fn json_path_literal(path: &[String]) -> String {
+        let mut json_path = String::from("$");
+        for key in path {
+            json_path.push_str(&format!(".\"{}\"", key.replace('"', "\\\"")));
+        }
+        json_path
+    }
+
+    /// `{"a","b"}` literal for a Postgres `text[]` (a JSON path, `LIKE ANY` patterns)
+    pub(crate) fn pg_text_array(items: &[String]) -> String {
+        let elements: Vec<String> = items
+            .iter()
+            .map(|key| format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect();
+        format!("{{{}}}", elements.join(","))
+    }
+
+    /// Append `element` to a JSON array column, treating `NULL` as `[]`
+    pub fn json_array_push(
+        backend: DatabaseBackend,
+        column: &str,
+        element: serde_json::Value,
+    ) -> SimpleExpr {
+        let col = column_ref(backend, column);
+        let sql = match backend {
+            DatabaseBackend::Postgres => format!(
+                "COALESCE({}::jsonb, '[]'::jsonb) || jsonb_build_array($1::jsonb)",
+                col
+            ),
+            DatabaseBackend::MySql => format!(
+                "JSON_ARRAY_APPEND(COALESCE({}, JSON_ARRAY()), '$', CAST(? AS JSON))",
+                col
+            ),
+            DatabaseBackend::Sqlite => {
+                format!("json_insert(COALESCE({}, '[]'), '$[#]', json(?))", col)
+            }
+        };
+        Expr::cust_with_values(sql, [json_param(backend, element)])
+    }
+
+    /// Remove every occurrence of `element` from a JSON array column
+    pub fn json_array_remove(
+        backend: DatabaseBackend,
+        column: &str,
+        element: serde_json::Value,
+    ) -> SimpleExpr {
+        let col = column_ref(backend, column);
+        let sql = match backend {
+            DatabaseBackend::Postgres => format!(
+                "(SELECT COALESCE(jsonb_agg(e.value ORDER BY e.ordinality), '[]'::jsonb) \
+                 FROM jsonb_array_elements(COALESCE({}::jsonb, '[]'::jsonb)) WITH ORDINALITY AS e(value, ordinality) \
+                 WHERE e.value <> $1::jsonb)",
+                col
+            ),
+            DatabaseBackend::MySql => format!(
+                "(SELECT COALESCE(JSON_ARRAYAGG(e.value), JSON_ARRAY()) \
+                 FROM JSON_TABLE(COALESCE({}, JSON_ARRAY()), '$[*]' COLUMNS (value JSON PATH '$')) AS e \
+                 WHERE e.value <> CAST(? AS JSON))",
+                col
+            ),
+            DatabaseBackend::Sqlite => format!(
+                "(SELECT COALESCE(json_group_array(value), '[]') \
+                 FROM json_each(COALESCE({}, '[]')) WHERE json_quote(value) <> json(?))",
+                col
+            ),
+        };
+        Expr::cust_with_values(sql, [json_param(backend, element)])
+    }
+
+    /// Append `element` to a list column: `array_append` on Postgres, a JSON array elsewhere
+    pub fn array_push(backend: DatabaseBackend, column: &str, element: Value) -> SimpleExpr {
+        match backend {
+            DatabaseBackend::Postgres => Expr::cust_with_values(
+                format!("array_append({}, $1)", column_ref(backend, column)),
+                [element],
+            ),
+            _ => json_array_push(
+                backend,
+                column,
+                sea_orm::sea_query::sea_value_to_json_value(&element),
+            ),
+        }
+    }
+
+    /// Remove `element` from a list column: `array_remove` on Postgres, a JSON array elsewhere
+    pub fn array_remove(backend: DatabaseBackend, column: &str, element: Value) -> SimpleExpr {
+        match backend {
+            DatabaseBackend::Postgres => Expr::cust_with_values(
+                format!("array_remove({}, $1)", column_ref(backend, column)),
+                [element],
+            ),
+            _ => json_array_remove(
+                backend,
+                column,
+                sea_orm::sea_query::sea_value_to_json_value(&element),
+            ),
+        }
+    }
+
+    /// JSON values are bound natively on Postgres and as JSON text elsewhere
+    fn json_param(backend: DatabaseBackend, element: serde_json::Value) -> Value {
+        match backend {
+            DatabaseBackend::Postgres => Value::Json(Some(Box::new(element))),
+            _ => Value::from(element.to_string()),
+        }
     }
 }
 
@@ -195,6 +947,14 @@ pub mod raw {
         format!("\"{}\"", escaped)
     }
 
+    /// Quote an identifier for `backend`: backticks on MySQL, double quotes elsewhere
+    pub fn ident_for(backend: DatabaseBackend, name: &str) -> String {
+        match backend {
+            DatabaseBackend::MySql => format!("`{}`", name.replace('`', "``")),
+            _ => ident(name),
+        }
+    }
+
     pub fn in_list_params<T>(items: &[T]) -> (String, Vec<Value>)
     where
         T: Clone + Into<Value>,
@@ -214,6 +974,35 @@ pub mod raw {
         (placeholders, params)
     }
 
+    /// Most bind parameters a single Postgres statement accepts
+    pub const POSTGRES_MAX_PARAMS: usize = 65_535;
+    /// Most bind parameters a single MySQL prepared statement accepts
+    pub const MYSQL_MAX_PARAMS: usize = 65_535;
+    /// SQLite's default `SQLITE_MAX_VARIABLE_NUMBER` (3.32 and later)
+    pub const SQLITE_MAX_PARAMS: usize = 32_766;
+
+    /// Most bind parameters a single statement accepts on `backend`
+    pub const fn max_params(backend: DatabaseBackend) -> usize {
+        match backend {
+            DatabaseBackend::Postgres => POSTGRES_MAX_PARAMS,
+            DatabaseBackend::MySql => MYSQL_MAX_PARAMS,
+            DatabaseBackend::Sqlite => SQLITE_MAX_PARAMS,
+        }
+    }
+
+    /// Split `items` into `in_list_params` chunks of at most `max` placeholders each, to OR
+    /// together (`col IN (..) OR col IN (..)`) or run as separate statements.
+    /// An empty `items` yields a single `NULL` chunk; `max` is treated as at least 1.
+    pub fn in_list_chunks<T>(items: &[T], max: usize) -> Vec<(String, Vec<Value>)>
+    where
+        T: Clone + Into<Value>,
+    {
+        if items.is_empty() {
+            return vec![in_list_params(items)];
+        }
+        items.chunks(max.max(1)).map(in_list_params).collect()
+    }
+
     pub fn bind_param<T>(value: T) -> (String, Vec<Value>)
     where
         T: Into<Value>,
@@ -303,6 +1092,159 @@ pub mod raw {
         n
     }
 
+    /// Rewrite `?` placeholders as `$1, $2, ...` on Postgres.
+    ///
+    /// String literals (including `E'...'` escapes), quoted identifiers, comments and
+    /// dollar-quoted bodies are copied as they are, and so are the jsonb `?|` and `?&`
+    /// operators. The bare jsonb `?` operator is written `??` and comes out as a single `?`.
+    pub fn backend_placeholders(backend: DatabaseBackend, sql: String) -> String {
+        if backend != DatabaseBackend::Postgres {
+            return sql;
+        }
+        let chars: Vec<char> = sql.chars().collect();
+        let mut out = String::with_capacity(sql.len() + 8);
+        let mut index = 0usize;
+        let mut i = 0usize;
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+            let end = match c {
+                '\'' => {
+                    // E'...' takes backslash escapes, unless the E ends a longer word
+                    let escapes = i > 0
+                        && matches!(chars[i - 1], 'E' | 'e')
+                        && !(i > 1 && is_ident_char(Some(chars[i - 2])));
+                    quoted_end(&chars, i, '\'', escapes)
+                }
+                '"' => quoted_end(&chars, i, '"', false),
+                '-' if next == Some('-') => {
+                    chars[i..].iter().position(|&c| c == '\n').map_or(chars.len(), |n| i + n + 1)
+                }
+                '/' if next == Some('*') => block_comment_end(&chars, i),
+                '$' if !(i > 0 && is_ident_char(Some(chars[i - 1]))) => dollar_quote_end(&chars, i).unwrap_or(i + 1),
+                '?' if next == Some('?') => {
+                    out.push('?');
+                    i += 2;
+                    continue;
+                }
+                '?' if (next == Some('|') && chars.get(i + 2) != Some(&'|'))
+                    || (next == Some('&') && chars.get(i + 2) != Some(&'&')) =>
+                {
+                    i + 2
+                }
+                '?' => {
+                    index += 1;
+                    out.push('$');
+                    out.push_str(&index.to_string());
+                    i += 1;
+                    continue;
+                }
+                _ => i + 1,
+            };
+            out.extend(&chars[i..end]);
+            i = end;
+        }
+        out
+    }
+
+    fn is_ident_char(c: Option<char>) -> bool {
+        c.is_some_and(|c| c.is_alphanumeric() || c == '_')
+    }
+
+    /// End of the literal or identifier opened by `quote` at `start`; a doubled quote (or a
+    /// backslash when `escapes`) does not close it
+    fn quoted_end(chars: &[char], start: usize, quote: char, escapes: bool) -> usize {
+        let mut i = start + 1;
+        while i < chars.len() {
+            if escapes && chars[i] == '\\' {
+                i += 2;
+            } else if chars[i] == quote {
+                if chars.get(i + 1) == Some(&quote) {
+                    i += 2;
+                } else {
+                    return i + 1;
+                }
+            } else {
+                i += 1;
+            }
+        }
+        chars.len()
+    }
+
+    /// End of the `/* ... */` comment at `start`; Postgres comments nest
+    fn block_comment_end(chars: &[char], start: usize) -> usize {
+        let mut depth = 0usize;
+        let mut i = start;
+        while i + 1 < chars.len() {
+            match (chars[i], chars[i + 1]) {
+                ('/', '*') => {
+                    depth += 1;
+                    i += 2;
+                }
+                ('*', '/') => {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        return i;
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+        chars.len()
+    }
+
+    /// End of the `$tag$ ... $tag$` body at `start`, or `None` when the `$` opens no tag
+    /// (e.g. a `$1` parameter)
+    fn dollar_quote_end(chars: &[char], start: usize) -> Option<usize> {
+        let tag_len = chars[start + 1..].iter().position(|&c| c == '$')?;
+        let tag = &chars[start + 1..start + 1 + tag_len];
+        if tag.first().is_some_and(|c| c.is_ascii_digit()) || !tag.iter().all(|&c| is_ident_char(Some(c))) {
+            return None;
+        }
+        let delimiter = &chars[start..start + tag_len + 2];
+        let body = start + delimiter.len();
+        Some(
+            (body..=chars.len().saturating_sub(delimiter.len()))
+                .find(|&i| &chars[i..i + delimiter.len()] == delimiter)
+                .map_or(chars.len(), |i| i + delimiter.len()),
+        )
+    }
+
+    /// A trusted raw SQL predicate with `?` placeholders bound to `params`, parenthesized so it
+    /// composes with other conditions. Backs `and_raw`/`and_raw_bind` on the find builders.
+    pub fn condition_expr(
+        backend: DatabaseBackend,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> sea_orm::sea_query::SimpleExpr {
+        let sql = format!("({})", sql);
+        if params.is_empty() {
+            sea_orm::sea_query::Expr::cust(sql)
+        } else {
+            sea_orm::sea_query::Expr::cust_with_values(backend_placeholders(backend, sql), params)
+        }
+    }
+
+    /// Bind `items` as a single array parameter on Postgres (`("?", [Value::Array])`, for use as
+    /// `col = ANY({})`), so the statement text does not depend on the number of items.
+    /// Other backends get the expanded placeholder list from `in_list_params` (`col IN ({})`).
+    pub fn array_param<T>(backend: DatabaseBackend, items: &[T]) -> (String, Vec<Value>)
+    where
+        T: Clone + Into<Value> + sea_orm::sea_query::ValueType,
+    {
+        match backend {
+            DatabaseBackend::Postgres => {
+                let values = items.iter().cloned().map(Into::into).collect();
+                (
+                    "?".to_string(),
+                    vec![Value::Array(T::array_type(), Some(Box::new(values)))],
+                )
+            }
+            _ => in_list_params(items),
+        }
+    }
+
     // Backend-aware ANY/IN helper: on Postgres emit ANY(ARRAY[?,..]), otherwise IN (?,..)
     pub fn any_or_in_params<T>(backend: DatabaseBackend, items: &[T]) -> (String, Vec<Value>)
     where
@@ -340,6 +1282,120 @@ pub mod raw {
     }
 }
 
+/// Helpers for resetting state in test suites, only built with the `testing` feature
+#[cfg(feature = "testing")]
+pub mod testing {
+    use sea_orm::sea_query::{TableCreateStatement, TableRef};
+    use sea_orm::{ConnectionTrait, DatabaseBackend, DbErr, EntityTrait};
+
+    /// Remove every row from `Entity`'s table.
+    ///
+    /// Postgres runs `TRUNCATE ... RESTART IDENTITY`, so sequences start over; MySQL's
+    /// `TRUNCATE` resets auto-increment counters too. SQLite falls back to `DELETE FROM`.
+    pub async fn truncate<C, Entity>(conn: &C) -> Result<(), DbErr>
+    where
+        C: ConnectionTrait,
+        Entity: EntityTrait + Default,
+    {
+        let entity = Entity::default();
+        let backend = conn.get_database_backend();
+        let quote = |name: &str| crate::raw::ident_for(backend, name);
+        let table = match entity.schema_name() {
+            Some(schema) => format!("{}.{}", quote(schema), quote(entity.table_name())),
+            None => quote(entity.table_name()),
+        };
+        let sql = match backend {
+            DatabaseBackend::Postgres => format!("TRUNCATE TABLE {} RESTART IDENTITY", table),
+            DatabaseBackend::MySql => format!("TRUNCATE TABLE {}", table),
+            DatabaseBackend::Sqlite => format!("DELETE FROM {}", table),
+        };
+        conn.execute(sea_orm::Statement::from_string(backend, sql)).await?;
+        Ok(())
+    }
+
+    /// `CREATE TABLE` for `Entity`, columns, indexes and foreign keys included
+    pub fn table_for<Entity: EntityTrait + Default>(backend: DatabaseBackend) -> TableCreateStatement {
+        sea_orm::Schema::new(backend).create_table_from_entity(Entity::default())
+    }
+
+    /// Run `tables` with `IF NOT EXISTS`, ordered so a table comes after the tables its
+    /// foreign keys reference. Tables in a reference cycle keep their given order.
+    pub async fn create_tables<C: ConnectionTrait>(
+        conn: &C,
+        tables: Vec<TableCreateStatement>,
+    ) -> Result<(), DbErr> {
+        let backend = conn.get_database_backend();
+        let mut pending: Vec<(Option<String>, Vec<String>, TableCreateStatement)> = tables
+            .into_iter()
+            .map(|table| {
+                let name = table.get_table_name().map(table_name);
+                let references = table
+                    .get_foreign_key_create_stmts()
+                    .iter()
+                    .filter_map(|fk| fk.get_foreign_key().get_ref_table().map(table_name))
+                    .collect();
+                (name, references, table)
+            })
+            .collect();
+        while !pending.is_empty() {
+            let ready = pending
+                .iter()
+                .position(|(name, references, _)| {
+                    references.iter().all(|referenced| {
+                        Some(referenced) == name.as_ref()
+                            || !pending.iter().any(|(other, _, _)| other.as_ref() == Some(referenced))
+                    })
+                })
+                .unwrap_or(0);
+            let (_, _, mut table) = pending.remove(ready);
+            conn.execute(backend.build(table.if_not_exists())).await?;
+        }
+        Ok(())
+    }
+
+    fn table_name(table: &TableRef) -> String {
+        match table {
+            TableRef::Table(name) | TableRef::TableAlias(name, _) => name.to_string(),
+            TableRef::SchemaTable(_, name) | TableRef::SchemaTableAlias(_, name, _) => name.to_string(),
+            TableRef::DatabaseSchemaTable(_, _, name)
+            | TableRef::DatabaseSchemaTableAlias(_, _, name, _) => name.to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Expands to its input only when caustics is built with the `testing` feature, so the
+/// generated client can carry test helpers without a feature flag of its own
+#[cfg(feature = "testing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __caustics_testing_only {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(not(feature = "testing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __caustics_testing_only {
+    ($($item:tt)*) => {};
+}
+
+/// Expands to its input only when caustics is built with the `deserialize` feature, so the
+/// entity macro can emit JSON impls without checking a feature of its own
+#[cfg(feature = "deserialize")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __caustics_deserialize_only {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(not(feature = "deserialize"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __caustics_deserialize_only {
+    ($($item:tt)*) => {};
+}
+
 // Re-export DeferredLookup for use in macros
 pub use query_builders::DeferredLookup;
 
@@ -372,6 +1428,10 @@ macro_rules! ident {
     ($name:expr) => {{
         $crate::raw::Inline($crate::raw::ident($name))
     }};
+    // Quote for a specific backend, e.g. `ident!(client.database_backend(), "users")`
+    ($backend:expr, $name:expr) => {{
+        $crate::raw::Inline($crate::raw::ident_for($backend, $name))
+    }};
 }
 
 #[macro_export]
@@ -75,4 +75,87 @@ pub trait IntoAndParam: Sized {
 /// Trait for types that can be converted to NOT operations
 pub trait IntoNotParam: Sized {
     fn from_not_params(params: Vec<Self>) -> Self;
-}
\ No newline at end of file
+}
+
+/// Accumulates conditions at runtime (e.g. from API query parameters) into one WhereParam,
+/// nesting OR and NOT groups without building the vectors by hand
+///
+/// # Example
+/// ```rust
+/// use caustics::operator::FilterBuilder;
+///
+/// // let mut filter = FilterBuilder::all();
+/// // if let Some(min_age) = params.min_age {
+/// //     filter = filter.push(user::age::gte(Some(min_age)));
+/// // }
+/// // let names = params.names.iter().map(|n| user::name::equals(n)).collect();
+/// // filter = filter.push_group(FilterBuilder::any().extend(names));
+/// // client.user().find_many(vec![filter.build()])
+/// ```
+#[derive(Debug, Clone)]
+pub struct FilterBuilder<T> {
+    any: bool,
+    params: Vec<T>,
+}
+
+impl<T> FilterBuilder<T>
+where
+    T: IntoAndParam + IntoOrParam + IntoNotParam,
+{
+    /// A group whose conditions must all hold
+    pub fn all() -> Self {
+        Self { any: false, params: Vec::new() }
+    }
+
+    /// A group of which at least one condition must hold
+    pub fn any() -> Self {
+        Self { any: true, params: Vec::new() }
+    }
+
+    pub fn push(mut self, param: T) -> Self {
+        self.params.push(param);
+        self
+    }
+
+    pub fn extend(mut self, params: impl IntoIterator<Item = T>) -> Self {
+        self.params.extend(params);
+        self
+    }
+
+    /// Add the negation of `param`
+    pub fn push_not(self, param: T) -> Self {
+        self.push(T::from_not_params(vec![param]))
+    }
+
+    /// Add a nested group; a group with no conditions is left out
+    pub fn push_group(self, group: FilterBuilder<T>) -> Self {
+        if group.is_empty() {
+            self
+        } else {
+            self.push(group.build())
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// The combined condition. An empty `all()` group matches every row and an empty
+    /// `any()` group matches none.
+    pub fn build(self) -> T {
+        if self.any {
+            T::from_or_params(self.params)
+        } else {
+            T::from_and_params(self.params)
+        }
+    }
+}
+
+impl<T> Default for FilterBuilder<T>
+where
+    T: IntoAndParam + IntoOrParam + IntoNotParam,
+{
+    fn default() -> Self {
+        Self::all()
+    }
+}
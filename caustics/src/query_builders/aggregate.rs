@@ -16,6 +16,11 @@ pub struct AggregateQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
     pub conn: &'a C,
     pub selections: AggregateSelections,
     pub aggregates: Vec<(SimpleExpr, &'static str, &'static str)>,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<Entity>,
 }
 
@@ -38,94 +43,122 @@ where
         self
     }
 
+    /// Stop waiting for the query after `timeout`; the deadline is client-side only, see
+    /// [`TimeoutQueryBuilder`](super::TimeoutQueryBuilder)
+    pub fn timeout(self, timeout: std::time::Duration) -> super::TimeoutQueryBuilder<Self> {
+        super::TimeoutQueryBuilder {
+            inner: self,
+            timeout,
+        }
+    }
+
     pub async fn exec(self) -> Result<AggregateTypedResult, sea_orm::DbErr> {
-        let db_backend = self.conn.get_database_backend();
-        let mut select = Entity::find().filter(self.condition).select_only();
+        self.error_mapper.clone().run(async move {
+            super::check_filter(&self.invalid_filter)?;
+            let db_backend = self.conn.get_database_backend();
+            let mut select = Entity::find().filter(self.condition).select_only();
 
-        if self.selections.count {
-            select = select.expr_as(Expr::cust("COUNT(*)"), "count");
-        }
+            if self.selections.count {
+                select = select.expr_as(Expr::cust("COUNT(*)"), "count");
+            }
 
-        if self.selections.min || self.selections.max || self.selections.sum || self.selections.avg
-        {
-            use sea_orm::Iterable;
-            if let Some(first_col) = <Entity as EntityTrait>::Column::iter().next() {
-                let expr = first_col.into_simple_expr();
-                if self.selections.min {
-                    select =
-                        select.expr_as(SimpleExpr::FunctionCall(Func::min(expr.clone())), "min");
-                }
-                if self.selections.max {
-                    select =
-                        select.expr_as(SimpleExpr::FunctionCall(Func::max(expr.clone())), "max");
-                }
-                if self.selections.sum {
-                    select =
-                        select.expr_as(SimpleExpr::FunctionCall(Func::sum(expr.clone())), "sum");
-                }
-                if self.selections.avg {
-                    select =
-                        select.expr_as(SimpleExpr::FunctionCall(Func::avg(expr.clone())), "avg");
+            if self.selections.min || self.selections.max || self.selections.sum || self.selections.avg
+            {
+                use sea_orm::Iterable;
+                if let Some(first_col) = <Entity as EntityTrait>::Column::iter().next() {
+                    let expr = first_col.into_simple_expr();
+                    if self.selections.min {
+                        select =
+                            select.expr_as(SimpleExpr::FunctionCall(Func::min(expr.clone())), "min");
+                    }
+                    if self.selections.max {
+                        select =
+                            select.expr_as(SimpleExpr::FunctionCall(Func::max(expr.clone())), "max");
+                    }
+                    if self.selections.sum {
+                        select =
+                            select.expr_as(SimpleExpr::FunctionCall(Func::sum(expr.clone())), "sum");
+                    }
+                    if self.selections.avg {
+                        select =
+                            select.expr_as(SimpleExpr::FunctionCall(Func::avg(expr.clone())), "avg");
+                    }
                 }
             }
-        }
 
-        for (expr, alias, _) in &self.aggregates {
-            select = select.expr_as(expr.clone(), *alias);
-        }
+            for (expr, alias, _) in &self.aggregates {
+                select = select.expr_as(expr.clone(), *alias);
+            }
 
-        let stmt = select.build(db_backend);
-        let row = self.conn.query_one(stmt).await?;
+            let stmt = select.build(db_backend);
+            let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            let row = conn.query_one(stmt).await?;
 
-        let mut typed = AggregateTypedResult::default();
-        if let Some(r) = row {
-            if self.selections.count {
-                if let Ok(v) = r.try_get::<i64>("", "count") {
-                    typed.count = Some(v);
+            let mut typed = AggregateTypedResult::default();
+            if let Some(r) = row {
+                if self.selections.count {
+                    if let Ok(v) = r.try_get::<i64>("", "count") {
+                        typed.count = Some(v);
+                    }
                 }
-            }
-            if self.selections.min {
-                if let Ok(v) = r.try_get::<String>("", "min") {
-                    typed.min.insert("_first".to_string(), v);
+                if self.selections.min {
+                    if let Ok(v) = r.try_get::<String>("", "min") {
+                        typed.min.insert("_first".to_string(), v);
+                    }
                 }
-            }
-            if self.selections.max {
-                if let Ok(v) = r.try_get::<String>("", "max") {
-                    typed.max.insert("_first".to_string(), v);
+                if self.selections.max {
+                    if let Ok(v) = r.try_get::<String>("", "max") {
+                        typed.max.insert("_first".to_string(), v);
+                    }
                 }
-            }
-            if self.selections.sum {
-                if let Ok(v) = r.try_get::<String>("", "sum") {
-                    typed.sum.insert("_first".to_string(), v);
+                if self.selections.sum {
+                    if let Ok(v) = r.try_get::<String>("", "sum") {
+                        typed.sum.insert("_first".to_string(), v);
+                    }
                 }
-            }
-            if self.selections.avg {
-                if let Ok(v) = r.try_get::<String>("", "avg") {
-                    typed.avg.insert("_first".to_string(), v);
+                if self.selections.avg {
+                    if let Ok(v) = r.try_get::<String>("", "avg") {
+                        typed.avg.insert("_first".to_string(), v);
+                    }
                 }
-            }
-            for (_, alias, kind) in &self.aggregates {
-                let as_string = crate::extract_db_value_as_string(&r, alias);
+                for (_, alias, kind) in &self.aggregates {
+                    let as_string = crate::extract_db_value_as_string(&r, alias);
 
-                if let Some(vs) = as_string {
-                    match *kind {
-                        "sum" => {
-                            typed.sum.insert((*alias).to_string(), vs);
-                        }
-                        "avg" => {
-                            typed.avg.insert((*alias).to_string(), vs);
+                    if let Some(vs) = as_string {
+                        match *kind {
+                            "sum" => {
+                                typed.sum.insert((*alias).to_string(), vs);
+                            }
+                            "avg" => {
+                                typed.avg.insert((*alias).to_string(), vs);
+                            }
+                            "min" => {
+                                typed.min.insert((*alias).to_string(), vs);
+                            }
+                            "max" => {
+                                typed.max.insert((*alias).to_string(), vs);
+                            }
+                            _ => {}
                         }
-                        "min" => {
-                            typed.min.insert((*alias).to_string(), vs);
-                        }
-                        "max" => {
-                            typed.max.insert((*alias).to_string(), vs);
-                        }
-                        _ => {}
                     }
                 }
             }
-        }
-        Ok(typed)
+            Ok(typed)
+        })
+        .await
+    }
+}
+
+impl<'a, C, Entity> super::TimeoutExec for AggregateQueryBuilder<'a, C, Entity>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+{
+    type Output = AggregateTypedResult;
+
+    fn exec_future(
+        self,
+    ) -> impl std::future::Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        self.exec()
     }
 }
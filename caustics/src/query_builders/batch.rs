@@ -1,9 +1,11 @@
-use crate::MergeInto;
-use sea_orm::{ConnectionTrait, EntityTrait};
+use std::future::Future;
+
+use crate::{FromModel, MergeInto};
+use sea_orm::{ConnectionTrait, DatabaseTransaction, EntityTrait};
 
 use super::{
-    create::CreateQueryBuilder, delete::DeleteQueryBuilder, update::UpdateQueryBuilder,
-    upsert::UpsertQueryBuilder,
+    create::CreateQueryBuilder, delete::DeleteQueryBuilder, update::UnifiedUpdateQueryBuilder,
+    update::UpdateQueryBuilder, upsert::UpsertQueryBuilder,
 };
 
 /// Batch query types that can be executed in a transaction
@@ -21,6 +23,31 @@ pub enum BatchQuery<
     Upsert(UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>),
 }
 
+impl<'a, C, Entity, ActiveModel, ModelWithRelations, T>
+    BatchQuery<'a, C, Entity, ActiveModel, ModelWithRelations, T>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel: sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    T: MergeInto<ActiveModel>,
+{
+    /// Whether the query runs as one SQL statement, which is atomic without a transaction.
+    ///
+    /// Updates and deletes read the row before writing it, and lookups or nested writes add
+    /// statements of their own; only a plain insert or a native upsert qualifies.
+    pub fn is_single_statement(&self) -> bool {
+        match self {
+            BatchQuery::Insert(q) => q.deferred_lookups.is_empty() && q.post_insert_ops.is_empty(),
+            BatchQuery::Upsert(q) => {
+                q.native_conflict.is_some()
+                    && super::upsert::supports_native_upsert(q.conn.get_database_backend())
+                    && q.create.1.is_empty()
+            }
+            BatchQuery::Update(_) | BatchQuery::Delete(_) => false,
+        }
+    }
+}
+
 /// Result types for batch operations
 pub enum BatchResult<ModelWithRelations> {
     Insert(ModelWithRelations),
@@ -28,3 +55,160 @@ pub enum BatchResult<ModelWithRelations> {
     Delete(ModelWithRelations),
     Upsert(ModelWithRelations),
 }
+
+/// A query that can run as one element of a `_batch_mixed` tuple.
+///
+/// Unlike [`BatchQuery`], elements are not tied to a single entity type, so one tuple can
+/// mix creates, updates, upserts and deletes across entities.
+pub trait MixedBatchElement {
+    type Output;
+
+    fn exec_in_txn(
+        self,
+        txn: &DatabaseTransaction,
+    ) -> impl Future<Output = Result<Self::Output, sea_orm::DbErr>>;
+}
+
+impl<'a, C, Entity, ActiveModel, ModelWithRelations> MixedBatchElement
+    for CreateQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    type Output = ModelWithRelations;
+
+    fn exec_in_txn(
+        self,
+        txn: &DatabaseTransaction,
+    ) -> impl Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        CreateQueryBuilder::exec_in_txn(self, txn)
+    }
+}
+
+impl<'a, C, Entity, ActiveModel, ModelWithRelations, T> MixedBatchElement
+    for UpdateQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+    T: MergeInto<ActiveModel>,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    type Output = ModelWithRelations;
+
+    fn exec_in_txn(
+        self,
+        txn: &DatabaseTransaction,
+    ) -> impl Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        UpdateQueryBuilder::exec_in_txn(self, txn)
+    }
+}
+
+impl<'a, C, Entity, ActiveModel, ModelWithRelations, T, P> MixedBatchElement
+    for UnifiedUpdateQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T, P>
+where
+    C: ConnectionTrait + sea_orm::TransactionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>
+        + crate::types::HasRelationMetadata<ModelWithRelations>
+        + 'static,
+    T: MergeInto<ActiveModel> + std::fmt::Debug + crate::types::SetParamInfo,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+    P: crate::EntityMetadataProvider,
+{
+    type Output = ModelWithRelations;
+
+    fn exec_in_txn(
+        self,
+        txn: &DatabaseTransaction,
+    ) -> impl Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        UnifiedUpdateQueryBuilder::exec_in_txn(self, txn)
+    }
+}
+
+impl<'a, C, Entity, ActiveModel, ModelWithRelations, T> MixedBatchElement
+    for UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+    T: MergeInto<ActiveModel>,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    type Output = ModelWithRelations;
+
+    fn exec_in_txn(
+        self,
+        txn: &DatabaseTransaction,
+    ) -> impl Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        UpsertQueryBuilder::exec_in_txn(self, txn)
+    }
+}
+
+impl<'a, C, Entity, ModelWithRelations> MixedBatchElement
+    for DeleteQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+{
+    type Output = ModelWithRelations;
+
+    fn exec_in_txn(
+        self,
+        txn: &DatabaseTransaction,
+    ) -> impl Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        DeleteQueryBuilder::exec_in_txn(self, txn)
+    }
+}
+
+/// Tuple of [`MixedBatchElement`]s, run in order with results returned in the same positions
+pub trait MixedBatchContainer {
+    type ReturnType;
+
+    fn exec_in_txn(
+        self,
+        txn: &DatabaseTransaction,
+    ) -> impl Future<Output = Result<Self::ReturnType, sea_orm::DbErr>>;
+}
+
+macro_rules! impl_tuple_mixed_batch_container {
+    ( $( $name:ident ),+ ) => {
+        #[allow(non_camel_case_types)]
+        impl<$( $name: MixedBatchElement ),+> MixedBatchContainer for ( $( $name ),+ , ) {
+            type ReturnType = ( $( $name::Output ),+ , );
+
+            async fn exec_in_txn(self, txn: &DatabaseTransaction) -> Result<Self::ReturnType, sea_orm::DbErr> {
+                let ( $( $name ),+ , ) = self;
+                Ok(( $( $name.exec_in_txn(txn).await? ),+ , ))
+            }
+        }
+    };
+}
+
+impl_tuple_mixed_batch_container!(a);
+impl_tuple_mixed_batch_container!(a, b);
+impl_tuple_mixed_batch_container!(a, b, c);
+impl_tuple_mixed_batch_container!(a, b, c, d);
+impl_tuple_mixed_batch_container!(a, b, c, d, e);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h, i);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h, i, j);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h, i, j, k);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h, i, j, k, l);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h, i, j, k, l, m);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h, i, j, k, l, m, n);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o);
+impl_tuple_mixed_batch_container!(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p);
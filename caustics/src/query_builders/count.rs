@@ -6,6 +6,11 @@ use sea_orm::{ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, QueryTrait
 pub struct CountQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
     pub condition: SeaQueryCondition,
     pub conn: &'a C,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<Entity>,
 }
 
@@ -14,16 +19,106 @@ where
     C: ConnectionTrait,
     Entity: EntityTrait,
 {
+    /// Stop waiting for the query after `timeout`; the deadline is client-side only, see
+    /// [`TimeoutQueryBuilder`](super::TimeoutQueryBuilder)
+    pub fn timeout(self, timeout: std::time::Duration) -> super::TimeoutQueryBuilder<Self> {
+        super::TimeoutQueryBuilder {
+            inner: self,
+            timeout,
+        }
+    }
+
+    /// Execute the count on `conn` instead of the client's connection, e.g. a transaction
+    /// managed outside caustics
+    pub async fn exec_on<C2: ConnectionTrait>(self, conn: &C2) -> Result<i64, sea_orm::DbErr> {
+        CountQueryBuilder::<C2, Entity> {
+            condition: self.condition,
+            conn,
+            invalid_filter: self.invalid_filter,
+            sql_comments: self.sql_comments,
+            error_mapper: self.error_mapper,
+            _phantom: std::marker::PhantomData,
+        }
+        .exec()
+        .await
+    }
+
     pub async fn exec(self) -> Result<i64, sea_orm::DbErr> {
-        let db_backend = self.conn.get_database_backend();
-        let select = Entity::find().filter(self.condition).select_only();
-        let select = select.expr_as(Expr::cust("COUNT(*)"), "count");
-        let stmt = select.build(db_backend);
-        let row = self.conn.query_one(stmt).await?;
-        let count = match row {
-            Some(r) => r.try_get::<i64>("", "count").unwrap_or(0),
-            None => 0,
+        self.error_mapper.clone().run(async move {
+            super::check_filter(&self.invalid_filter)?;
+            let db_backend = self.conn.get_database_backend();
+            let select = Entity::find().filter(self.condition).select_only();
+            let select = select.expr_as(Expr::cust("COUNT(*)"), "count");
+            let stmt = select.build(db_backend);
+            let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            let row = conn.query_one(stmt).await?;
+            let count = match row {
+                Some(r) => r.try_get::<i64>("", "count").unwrap_or(0),
+                None => 0,
+            };
+            Ok(count)
+        })
+        .await
+    }
+}
+
+impl<'a, C, Entity> super::TimeoutExec for CountQueryBuilder<'a, C, Entity>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+{
+    type Output = i64;
+
+    fn exec_future(
+        self,
+    ) -> impl std::future::Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        self.exec()
+    }
+}
+
+/// Approximate row count of `Entity`'s table.
+///
+/// On Postgres this reads the planner's `pg_class.reltuples`, which `ANALYZE`, `VACUUM` and
+/// autovacuum keep up to date, so it can lag behind recent writes. A table that has never
+/// been analyzed has no estimate and is counted exactly, as are tables on other backends.
+pub async fn count_estimate<C, Entity>(
+    conn: &C,
+    error_mapper: crate::ErrorMapper,
+) -> Result<i64, sea_orm::DbErr>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait + Default,
+{
+    let backend = conn.get_database_backend();
+    if backend == sea_orm::DatabaseBackend::Postgres {
+        let entity = Entity::default();
+        let quote = crate::raw::ident;
+        let table = match entity.schema_name() {
+            Some(schema) => format!("{}.{}", quote(schema), quote(entity.table_name())),
+            None => quote(entity.table_name()),
         };
-        Ok(count)
+        let stmt = sea_orm::Statement::from_sql_and_values(
+            backend,
+            "SELECT reltuples::bigint AS estimate FROM pg_class WHERE oid = to_regclass($1)",
+            [table.into()],
+        );
+        let estimate = error_mapper
+            .run(conn.query_one(stmt))
+            .await?
+            .and_then(|row| row.try_get::<i64>("", "estimate").ok());
+        // reltuples is -1 until the table is first analyzed
+        if let Some(estimate) = estimate.filter(|estimate| *estimate >= 0) {
+            return Ok(estimate);
+        }
+    }
+    CountQueryBuilder::<C, Entity> {
+        condition: SeaQueryCondition::all(),
+        conn,
+        error_mapper,
+        invalid_filter: None,
+        sql_comments: false,
+        _phantom: std::marker::PhantomData,
     }
+    .exec()
+    .await
 }
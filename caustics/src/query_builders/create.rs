@@ -1,8 +1,29 @@
 use super::deferred_lookup::DeferredLookup;
 use crate::{FromModel, PostInsertOp, RelationFilter, ApplyNestedIncludes, HasRelationMetadata, EntityRegistry};
-use sea_orm::{ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, QueryTrait};
 use std::any::Any;
 
+/// Primary key value of an entity, e.g. `i32` or `(String, i32)` for a composite key
+pub type PrimaryKeyValue<Entity> =
+    <<Entity as EntityTrait>::PrimaryKey as sea_orm::PrimaryKeyTrait>::ValueType;
+
+/// Read the primary key back out of an inserted row
+fn primary_key_of<Entity: EntityTrait>(model: &<Entity as EntityTrait>::Model) -> PrimaryKeyValue<Entity> {
+    use sea_orm::{Iterable, ModelTrait, PrimaryKeyToColumn};
+    use sea_orm::sea_query::{FromValueTuple, ValueTuple};
+    let mut values: Vec<sea_orm::Value> = <Entity as EntityTrait>::PrimaryKey::iter()
+        .map(|pk| model.get(pk.into_column()))
+        .collect();
+    let tuple = match values.len() {
+        1 => ValueTuple::One(values.remove(0)),
+        2 => ValueTuple::Two(values.remove(0), values.remove(0)),
+        3 => ValueTuple::Three(values.remove(0), values.remove(0), values.remove(0)),
+        _ => ValueTuple::Many(values),
+    };
+    FromValueTuple::from_value_tuple(tuple)
+}
+
 /// Query builder for creating a new entity record
 pub struct CreateQueryBuilder<
     'a,
@@ -15,9 +36,14 @@ pub struct CreateQueryBuilder<
     pub conn: &'a C,
     pub deferred_lookups: Vec<DeferredLookup>,
     pub post_insert_ops: Vec<PostInsertOp<'a>>,
-    pub id_extractor: fn(&<Entity as EntityTrait>::Model) -> crate::CausticsKey,
+    pub id_extractor: fn(&<Entity as EntityTrait>::Model) -> Result<crate::CausticsKey, sea_orm::DbErr>,
     pub relations_to_fetch: Vec<RelationFilter>,
     pub registry: &'a (dyn EntityRegistry<C> + Sync),
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<(Entity, ModelWithRelations)>,
 }
 
@@ -32,9 +58,24 @@ where
 {
     /// Add a relation to fetch with the query
     pub fn with<T: Into<RelationFilter>>(mut self, relation: T) -> Self {
-        self.relations_to_fetch.push(relation.into());
+        self.relations_to_fetch.push(super::scoped_relation(&self.scope, relation.into()));
         self
     }
+
+    /// Skip the insert instead of failing when it conflicts with an existing row on any
+    /// unique constraint; `exec` then returns `None`. `with` includes are ignored.
+    pub fn on_conflict_ignore(self) -> CreateIgnoreQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations> {
+        CreateIgnoreQueryBuilder {
+            model: self.model,
+            conn: self.conn,
+            deferred_lookups: self.deferred_lookups,
+            post_insert_ops: self.post_insert_ops,
+            id_extractor: self.id_extractor,
+            sql_comments: self.sql_comments,
+            error_mapper: self.error_mapper,
+            _phantom: std::marker::PhantomData,
+        }
+    }
     /// Execute the query within a transaction
     pub async fn exec_in_txn(
         self,
@@ -43,27 +84,30 @@ where
     where
         <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
     {
-        let mut model = self.model;
+        self.error_mapper.clone().run(async move {
+            let mut model = self.model;
 
-        // Execute all deferred lookups in batch using the transaction
-        for lookup in &self.deferred_lookups {
-            let lookup_result = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
-            (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result);
-        }
+            // Execute all deferred lookups in batch using the transaction
+            for lookup in &self.deferred_lookups {
+                let lookup_result = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
+                (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
+            }
 
-        let inserted = model.insert(txn).await?;
-        let parent_id = (self.id_extractor)(&inserted);
-        for op in self.post_insert_ops {
-            (op.run_on_txn)(txn, parent_id.clone()).await?;
-        }
+            let inserted = model.insert(&crate::hooks::CommentedConnection::new(txn, self.sql_comments)).await?;
+            let parent_id = (self.id_extractor)(&inserted)?;
+            for op in self.post_insert_ops {
+                (op.run_on_txn)(txn, parent_id.clone()).await?;
+            }
 
-        let model_with_relations = ModelWithRelations::from_model(inserted);
+            let model_with_relations = ModelWithRelations::from_model(inserted);
 
-        // Note: Relation fetching in exec_in_txn is not yet supported
-        // due to type constraints between EntityRegistry<C> and DatabaseTransaction
-        // Relations will need to be fetched separately after the transaction
+            // Note: Relation fetching in exec_in_txn is not yet supported
+            // due to type constraints between EntityRegistry<C> and DatabaseTransaction
+            // Relations will need to be fetched separately after the transaction
 
-        Ok(model_with_relations)
+            Ok(model_with_relations)
+        })
+        .await
     }
 }
 
@@ -79,30 +123,61 @@ where
         + Send
         + 'static,
 {
-    pub async fn exec(self) -> Result<ModelWithRelations, sea_orm::DbErr>
+    /// Insert the row and return only its primary key, without reading the rest of it back.
+    ///
+    /// Nested creates still run, which needs the full row; `with` includes are ignored.
+    pub async fn exec_returning_id(self) -> Result<PrimaryKeyValue<Entity>, sea_orm::DbErr>
     where
         <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
     {
-        if self.relations_to_fetch.is_empty() {
+        self.error_mapper.clone().run(async move {
             let mut model = self.model;
-
-            // Execute all deferred lookups in batch
+            let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
             for lookup in &self.deferred_lookups {
                 let lookup_result = (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
-                (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result);
+                (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
             }
-
-            let inserted = model.insert(self.conn).await?;
-            let parent_id = (self.id_extractor)(&inserted);
+            if self.post_insert_ops.is_empty() {
+                return Ok(Entity::insert(model).exec(&commented).await?.last_insert_id);
+            }
+            let inserted = model.insert(&commented).await?;
+            let parent_id = (self.id_extractor)(&inserted)?;
             for op in self.post_insert_ops {
                 (op.run_on_conn)(self.conn, parent_id.clone()).await?;
             }
+            Ok(primary_key_of::<Entity>(&inserted))
+        })
+        .await
+    }
 
-            let model_with_relations = ModelWithRelations::from_model(inserted);
-            Ok(model_with_relations)
-        } else {
-            self.exec_with_relations().await
-        }
+    pub async fn exec(self) -> Result<ModelWithRelations, sea_orm::DbErr>
+    where
+        <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+    {
+        self.error_mapper.clone().run(async move {
+            if self.relations_to_fetch.is_empty() {
+                let mut model = self.model;
+                let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+
+                // Execute all deferred lookups in batch
+                for lookup in &self.deferred_lookups {
+                    let lookup_result = (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
+                    (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
+                }
+
+                let inserted = model.insert(&commented).await?;
+                let parent_id = (self.id_extractor)(&inserted)?;
+                for op in self.post_insert_ops {
+                    (op.run_on_conn)(self.conn, parent_id.clone()).await?;
+                }
+
+                let model_with_relations = ModelWithRelations::from_model(inserted);
+                Ok(model_with_relations)
+            } else {
+                self.exec_with_relations().await
+            }
+        })
+        .await
     }
 
     async fn exec_with_relations(self) -> Result<ModelWithRelations, sea_orm::DbErr>
@@ -120,16 +195,18 @@ where
             id_extractor,
             relations_to_fetch,
             registry,
+            sql_comments,
             ..
         } = self;
+        let commented = crate::hooks::CommentedConnection::new(conn, sql_comments);
 
         for lookup in &deferred_lookups {
             let lookup_result = (lookup.resolve_on_conn)(conn, &*lookup.unique_param).await?;
-            (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result);
+            (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
         }
 
-        let inserted = model.insert(conn).await?;
-        let parent_id = (id_extractor)(&inserted);
+        let inserted = model.insert(&commented).await?;
+        let parent_id = (id_extractor)(&inserted)?;
         for op in post_insert_ops {
             (op.run_on_conn)(conn, parent_id.clone()).await?;
         }
@@ -162,29 +239,60 @@ where
         + Send
         + 'static,
 {
-    pub async fn exec(self) -> Result<ModelWithRelations, sea_orm::DbErr>
+    /// Insert the row and return only its primary key, without reading the rest of it back.
+    ///
+    /// Nested creates still run, which needs the full row; `with` includes are ignored.
+    pub async fn exec_returning_id(self) -> Result<PrimaryKeyValue<Entity>, sea_orm::DbErr>
     where
         <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
     {
-        if self.relations_to_fetch.is_empty() {
+        self.error_mapper.clone().run(async move {
             let mut model = self.model;
-
+            let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
             for lookup in &self.deferred_lookups {
                 let lookup_result = (lookup.resolve_on_txn)(self.conn, &*lookup.unique_param).await?;
-                (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result);
+                (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
             }
-
-            let inserted = model.insert(self.conn).await?;
-            let parent_id = (self.id_extractor)(&inserted);
+            if self.post_insert_ops.is_empty() {
+                return Ok(Entity::insert(model).exec(&commented).await?.last_insert_id);
+            }
+            let inserted = model.insert(&commented).await?;
+            let parent_id = (self.id_extractor)(&inserted)?;
             for op in self.post_insert_ops {
                 (op.run_on_txn)(self.conn, parent_id.clone()).await?;
             }
+            Ok(primary_key_of::<Entity>(&inserted))
+        })
+        .await
+    }
 
-            let model_with_relations = ModelWithRelations::from_model(inserted);
-            Ok(model_with_relations)
-        } else {
-            self.exec_with_relations().await
-        }
+    pub async fn exec(self) -> Result<ModelWithRelations, sea_orm::DbErr>
+    where
+        <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+    {
+        self.error_mapper.clone().run(async move {
+            if self.relations_to_fetch.is_empty() {
+                let mut model = self.model;
+                let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+
+                for lookup in &self.deferred_lookups {
+                    let lookup_result = (lookup.resolve_on_txn)(self.conn, &*lookup.unique_param).await?;
+                    (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
+                }
+
+                let inserted = model.insert(&commented).await?;
+                let parent_id = (self.id_extractor)(&inserted)?;
+                for op in self.post_insert_ops {
+                    (op.run_on_txn)(self.conn, parent_id.clone()).await?;
+                }
+
+                let model_with_relations = ModelWithRelations::from_model(inserted);
+                Ok(model_with_relations)
+            } else {
+                self.exec_with_relations().await
+            }
+        })
+        .await
     }
 
     async fn exec_with_relations(self) -> Result<ModelWithRelations, sea_orm::DbErr>
@@ -202,16 +310,18 @@ where
             id_extractor,
             relations_to_fetch,
             registry,
+            sql_comments,
             ..
         } = self;
+        let commented = crate::hooks::CommentedConnection::new(conn, sql_comments);
 
         for lookup in &deferred_lookups {
             let lookup_result = (lookup.resolve_on_txn)(conn, &*lookup.unique_param).await?;
-            (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result);
+            (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
         }
 
-        let inserted = model.insert(conn).await?;
-        let parent_id = (id_extractor)(&inserted);
+        let inserted = model.insert(&commented).await?;
+        let parent_id = (id_extractor)(&inserted)?;
         for op in post_insert_ops {
             (op.run_on_txn)(conn, parent_id.clone()).await?;
         }
@@ -231,3 +341,115 @@ where
         Ok(model_with_relations)
     }
 }
+
+/// Single-row insert that yields `None` instead of a unique-constraint error, from
+/// `create(...).on_conflict_ignore()`
+pub struct CreateIgnoreQueryBuilder<
+    'a,
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel: sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations,
+> {
+    pub model: ActiveModel,
+    pub conn: &'a C,
+    pub deferred_lookups: Vec<DeferredLookup>,
+    pub post_insert_ops: Vec<PostInsertOp<'a>>,
+    pub id_extractor: fn(&<Entity as EntityTrait>::Model) -> Result<crate::CausticsKey, sea_orm::DbErr>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
+    pub _phantom: std::marker::PhantomData<(Entity, ModelWithRelations)>,
+}
+
+/// Insert `model` with `ON CONFLICT DO NOTHING` on Postgres and SQLite; elsewhere a unique
+/// violation from a plain insert stands in for the conflict
+async fn insert_or_ignore<Conn, Entity, ActiveModel>(
+    conn: &Conn,
+    model: ActiveModel,
+) -> Result<Option<<Entity as EntityTrait>::Model>, sea_orm::DbErr>
+where
+    Conn: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    let backend = conn.get_database_backend();
+    if super::upsert::supports_native_upsert(backend) {
+        let mut insert = Entity::insert(model)
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .into_query();
+        insert.returning_all();
+        return Entity::find().from_raw_sql(backend.build(&insert)).one(conn).await;
+    }
+    match model.insert(conn).await {
+        Ok(inserted) => Ok(Some(inserted)),
+        Err(err) if matches!(err.sql_err(), Some(sea_orm::SqlErr::UniqueConstraintViolation(_))) => {
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+impl<'a, Entity, ActiveModel, ModelWithRelations>
+    CreateIgnoreQueryBuilder<'a, DatabaseConnection, Entity, ActiveModel, ModelWithRelations>
+where
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    /// Insert the row, or return `None` when a conflicting row already exists
+    pub async fn exec(self) -> Result<Option<ModelWithRelations>, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            let mut model = self.model;
+            let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            for lookup in &self.deferred_lookups {
+                let lookup_result = (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
+                (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
+            }
+            let Some(inserted) = insert_or_ignore(&commented, model).await? else {
+                return Ok(None);
+            };
+            let parent_id = (self.id_extractor)(&inserted)?;
+            for op in self.post_insert_ops {
+                (op.run_on_conn)(self.conn, parent_id.clone()).await?;
+            }
+            Ok(Some(ModelWithRelations::from_model(inserted)))
+        })
+        .await
+    }
+}
+
+impl<'a, Entity, ActiveModel, ModelWithRelations>
+    CreateIgnoreQueryBuilder<'a, DatabaseTransaction, Entity, ActiveModel, ModelWithRelations>
+where
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    /// Insert the row, or return `None` when a conflicting row already exists
+    pub async fn exec(self) -> Result<Option<ModelWithRelations>, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            let mut model = self.model;
+            let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            for lookup in &self.deferred_lookups {
+                let lookup_result = (lookup.resolve_on_txn)(self.conn, &*lookup.unique_param).await?;
+                (lookup.assign)(&mut model as &mut (dyn Any + 'static), lookup_result)?;
+            }
+            let Some(inserted) = insert_or_ignore(&commented, model).await? else {
+                return Ok(None);
+            };
+            let parent_id = (self.id_extractor)(&inserted)?;
+            for op in self.post_insert_ops {
+                (op.run_on_txn)(self.conn, parent_id.clone()).await?;
+            }
+            Ok(Some(ModelWithRelations::from_model(inserted)))
+        })
+        .await
+    }
+}
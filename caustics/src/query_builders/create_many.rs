@@ -16,9 +16,12 @@ where
         ActiveModel,
         Vec<DeferredLookup>,
         Vec<PostInsertOp<'a>>,
-        fn(&<Entity as EntityTrait>::Model) -> crate::CausticsKey,
+        fn(&<Entity as EntityTrait>::Model) -> Result<crate::CausticsKey, sea_orm::DbErr>,
     )>,
     pub conn: &'a C,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<(Entity, ActiveModel)>,
 }
 
@@ -34,20 +37,24 @@ where
         <Entity as EntityTrait>::Model: IntoActiveModel<ActiveModel>,
         DeferredLookup: DeferredResolveFor<DatabaseConnection>,
     {
-        let mut affected: i64 = 0;
-        for (mut model, lookups, post_ops, id_extractor) in self.items {
-            for lookup in &lookups {
-                let value = lookup.resolve_for(self.conn).await?;
-                (lookup.assign)(&mut model as &mut (dyn Any + 'static), value);
+        self.error_mapper.clone().run(async move {
+            let mut affected: i64 = 0;
+            let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            for (mut model, lookups, post_ops, id_extractor) in self.items {
+                for lookup in &lookups {
+                    let value = lookup.resolve_for(self.conn).await?;
+                    (lookup.assign)(&mut model as &mut (dyn Any + 'static), value)?;
+                }
+                let inserted = model.insert(&commented).await?;
+                let parent_id = (id_extractor)(&inserted)?;
+                for op in post_ops {
+                    (op.run_on_conn)(self.conn, parent_id.clone()).await?;
+                }
+                affected += 1;
             }
-            let inserted = model.insert(self.conn).await?;
-            let parent_id = (id_extractor)(&inserted);
-            for op in post_ops {
-                (op.run_on_conn)(self.conn, parent_id.clone()).await?;
-            }
-            affected += 1;
-        }
-        Ok(affected)
+            Ok(affected)
+        })
+        .await
     }
 }
 
@@ -63,19 +70,23 @@ where
         <Entity as EntityTrait>::Model: IntoActiveModel<ActiveModel>,
         DeferredLookup: DeferredResolveFor<DatabaseTransaction>,
     {
-        let mut affected: i64 = 0;
-        for (mut model, lookups, post_ops, id_extractor) in self.items {
-            for lookup in &lookups {
-                let value = lookup.resolve_for(self.conn).await?;
-                (lookup.assign)(&mut model as &mut (dyn Any + 'static), value);
-            }
-            let inserted = model.insert(self.conn).await?;
-            let parent_id = (id_extractor)(&inserted);
-            for op in post_ops {
-                (op.run_on_txn)(self.conn, parent_id.clone()).await?;
+        self.error_mapper.clone().run(async move {
+            let mut affected: i64 = 0;
+            let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            for (mut model, lookups, post_ops, id_extractor) in self.items {
+                for lookup in &lookups {
+                    let value = lookup.resolve_for(self.conn).await?;
+                    (lookup.assign)(&mut model as &mut (dyn Any + 'static), value)?;
+                }
+                let inserted = model.insert(&commented).await?;
+                let parent_id = (id_extractor)(&inserted)?;
+                for op in post_ops {
+                    (op.run_on_txn)(self.conn, parent_id.clone()).await?;
+                }
+                affected += 1;
             }
-            affected += 1;
-        }
-        Ok(affected)
+            Ok(affected)
+        })
+        .await
     }
 }
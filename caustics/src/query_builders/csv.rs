@@ -0,0 +1,161 @@
+use std::io::Write;
+
+use futures_util::TryStreamExt;
+use heck::ToSnakeCase;
+use sea_orm::{ConnectionTrait, EntityTrait, Iterable, ModelTrait, QuerySelect, StreamTrait};
+
+use super::ManyQueryBuilder;
+use crate::{FromModel, HasRelationMetadata};
+
+impl<'a, C, Entity, ModelWithRelations> ManyQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    C: ConnectionTrait + StreamTrait + Send,
+    Entity: EntityTrait,
+    Entity::Column: std::fmt::Debug,
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+{
+    /// Stream the matching rows into `writer` as CSV, one header row of model field names followed
+    /// by one record per row. Rows are written as they arrive rather than collected first.
+    ///
+    /// NULLs become empty cells; cells containing a comma, quote or line break are quoted.
+    /// Relations can't be flattened into a row, so `with`/`include` is rejected. A negative `take`
+    /// reads its rows in full before writing them, to restore the requested order; past the
+    /// client's `max_rows` cap the write stops with `RowLimitExceeded`.
+    pub async fn write_csv<W: Write>(self, writer: &mut W) -> Result<u64, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            if let Some(message) = self.invalid_pagination {
+                return Err(crate::types::CausticsError::InvalidPagination { message }.into());
+            }
+            super::check_filter(&self.invalid_filter)?;
+            if !self.relations_to_fetch.is_empty() {
+                return Err(crate::types::CausticsError::QueryValidation {
+                    message: "write_csv does not support relations; drop with/include".to_string(),
+                }
+                .into());
+            }
+            if self.distinct_on_ordered {
+                super::many::check_distinct_on_order(
+                    self.distinct_on_fields.as_deref(),
+                    &self.pending_order_bys,
+                )?;
+            }
+            let conn = self.conn;
+            let mut query = self.built_select();
+            // Without an explicit take, fetch one row past the cap so exceeding it can be detected
+            let row_cap = self.max_rows.filter(|_| !self.has_limit);
+            if let Some(cap) = row_cap {
+                query = query.limit(cap.saturating_add(1));
+            }
+
+            let columns: Vec<Entity::Column> = Entity::Column::iter().collect();
+            // Column variants mirror the model fields, which may differ from the SQL column names
+            let header: Vec<Option<String>> = columns
+                .iter()
+                .map(|column| Some(format!("{:?}", column).to_snake_case()))
+                .collect();
+            write_record(writer, &header)?;
+
+            let row_limit_exceeded = |cap: u64| -> sea_orm::DbErr {
+                crate::types::CausticsError::RowLimitExceeded {
+                    entity: core::any::type_name::<Entity>().to_string(),
+                    limit: cap,
+                }
+                .into()
+            };
+            let mut write_model = |model: &Entity::Model| {
+                let cells: Vec<Option<String>> =
+                    columns.iter().map(|column| csv_cell(&model.get(*column))).collect();
+                write_record(writer, &cells)
+            };
+
+            let mut written = 0u64;
+            if self.reverse_order {
+                // Rows arrive in reversed order (negative take), so they are read in full and
+                // written back in the requested order
+                let mut models: Vec<Entity::Model> = query.stream(conn).await?.try_collect().await?;
+                if let Some(cap) = row_cap.filter(|cap| models.len() as u64 > *cap) {
+                    return Err(row_limit_exceeded(cap));
+                }
+                models.reverse();
+                for model in &models {
+                    write_model(model)?;
+                    written += 1;
+                }
+            } else {
+                let mut rows = query.stream(conn).await?;
+                while let Some(model) = rows.try_next().await? {
+                    if let Some(cap) = row_cap.filter(|cap| written >= *cap) {
+                        return Err(row_limit_exceeded(cap));
+                    }
+                    write_model(&model)?;
+                    written += 1;
+                }
+            }
+            writer.flush().map_err(io_err)?;
+            Ok(written)
+        })
+        .await
+    }
+}
+
+fn io_err(err: std::io::Error) -> sea_orm::DbErr {
+    sea_orm::DbErr::Custom(format!("write_csv: {}", err))
+}
+
+fn write_record<W: Write>(writer: &mut W, cells: &[Option<String>]) -> Result<(), sea_orm::DbErr> {
+    let mut line = String::new();
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 {
+            line.push(',');
+        }
+        if let Some(cell) = cell {
+            line.push_str(&quote_cell(cell));
+        }
+    }
+    line.push_str("\r\n");
+    writer.write_all(line.as_bytes()).map_err(io_err)
+}
+
+/// RFC 4180 quoting: wrap in quotes and double any embedded quotes when needed
+fn quote_cell(cell: &str) -> std::borrow::Cow<'_, str> {
+    if cell.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\"")).into()
+    } else {
+        cell.into()
+    }
+}
+
+/// Render a column value as a CSV cell, `None` for NULL
+fn csv_cell(value: &sea_orm::Value) -> Option<String> {
+    use sea_orm::Value;
+    match value {
+        Value::Bool(v) => v.map(|v| v.to_string()),
+        Value::TinyInt(v) => v.map(|v| v.to_string()),
+        Value::SmallInt(v) => v.map(|v| v.to_string()),
+        Value::Int(v) => v.map(|v| v.to_string()),
+        Value::BigInt(v) => v.map(|v| v.to_string()),
+        Value::TinyUnsigned(v) => v.map(|v| v.to_string()),
+        Value::SmallUnsigned(v) => v.map(|v| v.to_string()),
+        Value::Unsigned(v) => v.map(|v| v.to_string()),
+        Value::BigUnsigned(v) => v.map(|v| v.to_string()),
+        Value::Float(v) => v.map(|v| v.to_string()),
+        Value::Double(v) => v.map(|v| v.to_string()),
+        Value::String(v) => v.as_ref().map(|v| v.to_string()),
+        Value::Char(v) => v.map(|v| v.to_string()),
+        Value::Bytes(v) => v.as_ref().map(|v| String::from_utf8_lossy(v).into_owned()),
+        Value::Json(v) => v.as_ref().map(|v| v.to_string()),
+        Value::Uuid(v) => v.as_ref().map(|v| v.to_string()),
+        Value::ChronoDate(v) => v.as_ref().map(|v| v.format("%Y-%m-%d").to_string()),
+        Value::ChronoTime(v) => v.as_ref().map(|v| v.format("%H:%M:%S%.f").to_string()),
+        Value::ChronoDateTime(v) => v.as_ref().map(|v| v.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+        Value::ChronoDateTimeUtc(v) => v.as_ref().map(|v| v.to_rfc3339()),
+        Value::ChronoDateTimeLocal(v) => v.as_ref().map(|v| v.to_rfc3339()),
+        Value::ChronoDateTimeWithTimeZone(v) => v.as_ref().map(|v| v.to_rfc3339()),
+        // Anything rarer falls back to its SQL literal
+        other => Some(other.to_string()).filter(|s| s != "NULL"),
+    }
+}
@@ -4,7 +4,7 @@ use std::any::Any;
 /// Internal structure for storing deferred foreign key lookups
 pub struct DeferredLookup {
     pub unique_param: Box<dyn Any + Send + Sync>,
-    pub assign: fn(&mut (dyn Any + 'static), crate::CausticsKey),
+    pub assign: fn(&mut (dyn Any + 'static), crate::CausticsKey) -> Result<(), sea_orm::DbErr>,
     #[allow(clippy::type_complexity)]
     pub resolve_on_conn: Box<
         dyn for<'a> Fn(
@@ -38,7 +38,7 @@ pub struct DeferredLookup {
 impl DeferredLookup {
     pub fn new(
         unique_param: Box<dyn Any + Send + Sync>,
-        assign: fn(&mut (dyn Any + 'static), crate::CausticsKey),
+        assign: fn(&mut (dyn Any + 'static), crate::CausticsKey) -> Result<(), sea_orm::DbErr>,
         resolve_on_conn: impl for<'a> Fn(
                 &'a DatabaseConnection,
                 &dyn Any,
@@ -71,6 +71,41 @@ impl DeferredLookup {
             resolve_on_txn: Box::new(resolve_on_txn),
         }
     }
+
+    /// A lookup that fails with `error` when resolved, so a foreign key that couldn't be
+    /// converted while building the statement is reported by `exec` instead of panicking
+    pub fn failed(error: crate::CausticsError) -> Self {
+        let on_txn = error.clone();
+        Self::new(
+            Box::new(()),
+            |_, _| Ok(()),
+            move |_: &DatabaseConnection, _| {
+                let error = error.clone();
+                Box::pin(async move { Err(error.into()) })
+            },
+            move |_: &DatabaseTransaction, _| {
+                let error = on_txn.clone();
+                Box::pin(async move { Err(error.into()) })
+            },
+        )
+    }
+}
+
+/// Take the `ActiveValue<T>` out of a boxed foreign key conversion result, reporting a
+/// registry type that disagrees with the field's declared type as `InvalidFieldType`
+pub fn downcast_active_value<T: Into<sea_orm::Value> + 'static>(
+    boxed: Box<dyn Any + Send + Sync>,
+    entity: &str,
+    field: &str,
+) -> Result<sea_orm::ActiveValue<T>, crate::CausticsError> {
+    boxed
+        .downcast::<sea_orm::ActiveValue<T>>()
+        .map(|value| *value)
+        .map_err(|_| crate::CausticsError::InvalidFieldType {
+            field: format!("{}.{}", entity, field),
+            expected: std::any::type_name::<T>().to_string(),
+            actual: "a value of the registry's foreign key type".to_string(),
+        })
 }
 
 pub trait DeferredResolveFor<C: ConnectionTrait> {
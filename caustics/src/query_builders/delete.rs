@@ -5,6 +5,9 @@ use sea_orm::{ConnectionTrait, DatabaseTransaction, EntityTrait, QueryFilter};
 pub struct DeleteQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> {
     pub condition: sea_orm::Condition,
     pub conn: &'a C,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<(Entity, ModelWithRelations)>,
 }
 
@@ -14,26 +17,43 @@ where
     Entity: EntityTrait,
     ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
 {
+    /// AND extra guard predicates into the unique condition; a record the guard excludes is
+    /// reported as not found and left in place. Generated code exposes this as `filter`.
+    pub fn filter_condition(mut self, guard: sea_orm::Condition) -> Self {
+        self.condition = sea_orm::Condition::all().add(self.condition).add(guard);
+        self
+    }
+
     /// Delete the uniquely-matching record and return it; error if not found
     pub async fn exec(self) -> Result<ModelWithRelations, sea_orm::DbErr> {
-        // Fetch the record first so we can return it after deletion
-        let found = <Entity as EntityTrait>::find()
-            .filter::<sea_orm::Condition>(self.condition.clone())
-            .one(self.conn)
-            .await?;
-
-        if let Some(model) = found {
-            // Delete the record using the same unique condition
-            Entity::delete_many()
-                .filter::<sea_orm::Condition>(self.condition)
-                .exec(self.conn)
+        self.error_mapper.clone().run(async move {
+            let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            // Fetch the record first so we can return it after deletion
+            let found = <Entity as EntityTrait>::find()
+                .filter::<sea_orm::Condition>(self.condition.clone())
+                .one(&conn)
                 .await?;
-            Ok(ModelWithRelations::from_model(model))
-        } else {
-            Err(sea_orm::DbErr::RecordNotFound(
-                "No record found to delete".to_string(),
-            ))
-        }
+
+            if let Some(model) = found {
+                // Delete the record using the same unique condition
+                let result = Entity::delete_many()
+                    .filter::<sea_orm::Condition>(self.condition)
+                    .exec(&conn)
+                    .await?;
+                // The row changed between the lookup and the delete and no longer matches
+                if result.rows_affected == 0 {
+                    return Err(sea_orm::DbErr::RecordNotFound(
+                        "No record found to delete".to_string(),
+                    ));
+                }
+                Ok(ModelWithRelations::from_model(model))
+            } else {
+                Err(sea_orm::DbErr::RecordNotFound(
+                    "No record found to delete".to_string(),
+                ))
+            }
+        })
+        .await
     }
 
     /// Execute the query within a transaction
@@ -41,21 +61,31 @@ where
         self,
         txn: &DatabaseTransaction,
     ) -> Result<ModelWithRelations, sea_orm::DbErr> {
-        let found = <Entity as EntityTrait>::find()
-            .filter::<sea_orm::Condition>(self.condition.clone())
-            .one(txn)
-            .await?;
-
-        if let Some(model) = found {
-            Entity::delete_many()
-                .filter::<sea_orm::Condition>(self.condition)
-                .exec(txn)
+        self.error_mapper.clone().run(async move {
+            let txn = crate::hooks::CommentedConnection::new(txn, self.sql_comments);
+            let found = <Entity as EntityTrait>::find()
+                .filter::<sea_orm::Condition>(self.condition.clone())
+                .one(&txn)
                 .await?;
-            Ok(ModelWithRelations::from_model(model))
-        } else {
-            Err(sea_orm::DbErr::RecordNotFound(
-                "No record found to delete".to_string(),
-            ))
-        }
+
+            if let Some(model) = found {
+                let result = Entity::delete_many()
+                    .filter::<sea_orm::Condition>(self.condition)
+                    .exec(&txn)
+                    .await?;
+                // The row changed between the lookup and the delete and no longer matches
+                if result.rows_affected == 0 {
+                    return Err(sea_orm::DbErr::RecordNotFound(
+                        "No record found to delete".to_string(),
+                    ));
+                }
+                Ok(ModelWithRelations::from_model(model))
+            } else {
+                Err(sea_orm::DbErr::RecordNotFound(
+                    "No record found to delete".to_string(),
+                ))
+            }
+        })
+        .await
     }
 }
@@ -1,9 +1,14 @@
-use sea_orm::{ConnectionTrait, DatabaseTransaction, EntityTrait, QueryFilter};
+use sea_orm::{ConnectionTrait, DatabaseTransaction, EntityTrait, QueryFilter, QueryTrait};
 
 /// Query builder for deleting multiple entity records matching a condition
 pub struct DeleteManyQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
     pub condition: sea_orm::Condition,
     pub conn: &'a C,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<Entity>,
 }
 
@@ -14,19 +19,51 @@ where
 {
     /// Delete all matching records and return the number of rows affected
     pub async fn exec(self) -> Result<i64, sea_orm::DbErr> {
-        let res = Entity::delete_many()
-            .filter::<sea_orm::Condition>(self.condition)
-            .exec(self.conn)
-            .await?;
-        Ok(res.rows_affected as i64)
+        self.error_mapper.clone().run(async move {
+            super::check_filter(&self.invalid_filter)?;
+            let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            let res = Entity::delete_many()
+                .filter::<sea_orm::Condition>(self.condition)
+                .exec(&conn)
+                .await?;
+            Ok(res.rows_affected as i64)
+        })
+        .await
+    }
+
+    /// Delete all matching records and return them as they were, e.g. for emitting change
+    /// events. Needs `RETURNING` (Postgres, SQLite); other backends get a `QueryValidation` error.
+    pub async fn exec_returning(self) -> Result<Vec<<Entity as EntityTrait>::Model>, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            super::check_filter(&self.invalid_filter)?;
+            let backend = self.conn.get_database_backend();
+            if !super::update_many::supports_returning(backend) {
+                return Err(crate::types::CausticsError::QueryValidation {
+                    message: format!("delete_many exec_returning needs RETURNING, which {:?} lacks", backend),
+                }
+                .into());
+            }
+            let mut delete = Entity::delete_many()
+                .filter::<sea_orm::Condition>(self.condition)
+                .into_query();
+            delete.returning_all();
+            let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            Entity::find().from_raw_sql(backend.build(&delete)).all(&conn).await
+        })
+        .await
     }
 
     /// Execute the query within a transaction
     pub async fn exec_in_txn(self, txn: &DatabaseTransaction) -> Result<i64, sea_orm::DbErr> {
-        let res = Entity::delete_many()
-            .filter::<sea_orm::Condition>(self.condition)
-            .exec(txn)
-            .await?;
-        Ok(res.rows_affected as i64)
+        self.error_mapper.clone().run(async move {
+            super::check_filter(&self.invalid_filter)?;
+            let txn = crate::hooks::CommentedConnection::new(txn, self.sql_comments);
+            let res = Entity::delete_many()
+                .filter::<sea_orm::Condition>(self.condition)
+                .exec(&txn)
+                .await?;
+            Ok(res.rows_affected as i64)
+        })
+        .await
     }
 }
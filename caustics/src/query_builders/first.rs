@@ -1,11 +1,12 @@
 use crate::types::ApplyNestedIncludes;
 use crate::types::EntityRegistry;
 use crate::types::SelectionSpec;
-use crate::types::{IntoOrderSpec, NullsOrder};
+use crate::query_builders::order_by::apply_order_bys;
+use crate::query_builders::OrderByDescriptor;
+use crate::types::IntoOrderSpec;
 use crate::EntitySelection;
 use crate::{FromModel, HasRelationMetadata, RelationFilter};
-use sea_orm::sea_query::{Expr, SimpleExpr};
-use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, QueryOrder, Select};
+use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, Select};
 
 /// Query builder for finding the first entity record matching conditions
 pub struct FirstQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> {
@@ -14,8 +15,17 @@ pub struct FirstQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelW
     pub relations_to_fetch: Vec<RelationFilter>,
     pub registry: &'a (dyn EntityRegistry<C> + Sync),
     pub database_backend: DatabaseBackend,
-    pub pending_order_bys: Vec<(SimpleExpr, sea_orm::Order)>,
-    pub pending_nulls: Option<NullsOrder>,
+    pub pending_order_bys: Vec<OrderByDescriptor>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Value-free fingerprint of how the query was built, keying the statement cache;
+    /// `None` keeps it out of the cache, e.g. after editing `query` directly
+    pub shape: Option<u64>,
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<ModelWithRelations>,
 }
 
@@ -45,7 +55,9 @@ where
             registry: self.registry,
             database_backend: self.database_backend,
             pending_order_bys: self.pending_order_bys,
-            pending_nulls: self.pending_nulls,
+            invalid_filter: self.invalid_filter,
+            scope: self.scope,
+            error_mapper: self.error_mapper,
             _phantom: std::marker::PhantomData,
         };
         let aliases = spec.collect_aliases();
@@ -58,51 +70,95 @@ where
         builder
     }
 
+    /// AND a raw SQL predicate into the filter, e.g. `.and_raw("age % 2 = 0")`.
+    ///
+    /// The fragment is inlined verbatim (not parameterized), so it must be trusted input; pass
+    /// user-supplied values through `and_raw_bind` instead. Column names are not checked.
+    pub fn and_raw(self, sql: &str) -> Self {
+        self.and_raw_bind(sql, Vec::new())
+    }
+
+    /// AND a raw SQL predicate with `?` placeholders bound to `params`, e.g.
+    /// `.and_raw_bind("created_at > ?", vec![since.into()])`. Placeholders are rewritten for the
+    /// backend; the SQL text itself must still be trusted input.
+    pub fn and_raw_bind(mut self, sql: &str, params: Vec<sea_orm::Value>) -> Self {
+        let param_count = params.len();
+        self.query = QueryFilter::filter(
+            self.query,
+            crate::raw::condition_expr(self.database_backend, sql, params),
+        );
+        self.shape = crate::statement_cache::extend_shape(self.shape, (sql, param_count));
+        self
+    }
+
     /// Order the result deterministically when multiple rows match
     pub fn order_by<T>(mut self, order_spec: T) -> Self
     where
         T: IntoOrderSpec,
     {
-        let (expr, order, nulls) = order_spec.into_order_spec();
-        self.pending_order_bys.push((expr, order));
-        if nulls.is_some() {
-            self.pending_nulls = nulls;
-        }
+        self.pending_order_bys.push(order_spec.into());
         self
     }
+    /// Stop waiting for the query after `timeout`; the deadline is client-side only, see
+    /// [`TimeoutQueryBuilder`](super::TimeoutQueryBuilder)
+    pub fn timeout(self, timeout: std::time::Duration) -> super::TimeoutQueryBuilder<Self> {
+        super::TimeoutQueryBuilder {
+            inner: self,
+            timeout,
+        }
+    }
+
+    /// Execute the query on `conn` instead of the client's connection, e.g. a transaction
+    /// managed outside caustics
+    pub async fn exec_on<C2: ConnectionTrait + 'static>(self, conn: &C2) -> Result<Option<ModelWithRelations>, sea_orm::DbErr>
+    where
+        ModelWithRelations: crate::types::ApplyNestedIncludes<C2> + crate::types::RegistryProvider<C2>,
+    {
+        FirstQueryBuilder {
+            query: self.query,
+            conn,
+            relations_to_fetch: self.relations_to_fetch,
+            registry: <ModelWithRelations as crate::types::RegistryProvider<C2>>::registry(),
+            database_backend: conn.get_database_backend(),
+            pending_order_bys: self.pending_order_bys,
+            sql_comments: self.sql_comments,
+            invalid_filter: self.invalid_filter,
+            shape: self.shape,
+            scope: self.scope,
+            error_mapper: self.error_mapper,
+            _phantom: std::marker::PhantomData,
+        }
+        .exec()
+        .await
+    }
+
     /// Execute the query and return a single result
     pub async fn exec(self) -> Result<Option<ModelWithRelations>, sea_orm::DbErr> {
-        if self.relations_to_fetch.is_empty() {
-            let mut query = self.query;
-            // Apply NULLS ordering hint if provided, before actual order clauses
-            if let Some(n) = self.pending_nulls {
-                if let Some((first_expr, _)) = self.pending_order_bys.first() {
-                    let nulls_expr = Expr::expr(first_expr.clone()).is_null();
-                    match n {
-                        NullsOrder::First => {
-                            query = query.order_by(nulls_expr, sea_orm::Order::Desc);
-                        }
-                        NullsOrder::Last => {
-                            query = query.order_by(nulls_expr, sea_orm::Order::Asc);
-                        }
-                    }
-                }
+        self.error_mapper.clone().run(async move {
+            super::check_filter(&self.invalid_filter)?;
+            if self.relations_to_fetch.is_empty() {
+                let mut query = self.query;
+                query = apply_order_bys(query, &self.pending_order_bys);
+                let shape = ordered_shape(self.shape, &self.pending_order_bys);
+                crate::statement_cache::one("FirstQueryBuilder", query, shape, self.conn, self.sql_comments)
+                    .await
+                    .map(|opt| opt.map(|model| ModelWithRelations::from_model(model)))
+            } else {
+                self.exec_with_relations().await
             }
-            for (expr, order) in &self.pending_order_bys {
-                query = query.order_by(expr.clone(), order.clone());
-            }
-            query
-                .one(self.conn)
-                .await
-                .map(|opt| opt.map(|model| ModelWithRelations::from_model(model)))
-        } else {
-            self.exec_with_relations().await
-        }
+        })
+        .await
     }
 
     /// Add a relation to fetch with the query
     pub fn with<T: Into<RelationFilter>>(mut self, relation: T) -> Self {
-        self.relations_to_fetch.push(relation.into());
+        self.relations_to_fetch.push(super::scoped_relation(&self.scope, relation.into()));
+        self
+    }
+
+    /// Fetch every relation declared on the entity, one level deep
+    pub fn with_all(mut self) -> Self {
+        super::push_all_relations::<ModelWithRelations>(&mut self.relations_to_fetch, &self.scope);
         self
     }
 
@@ -117,28 +173,14 @@ where
             relations_to_fetch,
             registry,
             pending_order_bys,
-            pending_nulls,
+            sql_comments,
+            shape,
             ..
         } = self;
         // Apply ordering to ensure deterministic first row
-        let mut ordered = query;
-        if let Some(n) = pending_nulls {
-            if let Some((first_expr, _)) = pending_order_bys.first() {
-                let nulls_expr = Expr::expr(first_expr.clone()).is_null();
-                match n {
-                    NullsOrder::First => {
-                        ordered = ordered.order_by(nulls_expr, sea_orm::Order::Desc);
-                    }
-                    NullsOrder::Last => {
-                        ordered = ordered.order_by(nulls_expr, sea_orm::Order::Asc);
-                    }
-                }
-            }
-        }
-        for (expr, order) in &pending_order_bys {
-            ordered = ordered.order_by(expr.clone(), order.clone());
-        }
-        let main_result = ordered.one(conn).await?;
+        let ordered = apply_order_bys(query, &pending_order_bys);
+        let shape = ordered_shape(shape, &pending_order_bys);
+        let main_result = crate::statement_cache::one("FirstQueryBuilder", ordered, shape, conn, sql_comments).await?;
 
         if let Some(main_model) = main_result {
             let mut model_with_relations = ModelWithRelations::from_model(main_model);
@@ -160,3 +202,26 @@ where
         }
     }
 }
+
+impl<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> super::TimeoutExec
+    for FirstQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+{
+    type Output = Option<ModelWithRelations>;
+
+    fn exec_future(
+        self,
+    ) -> impl std::future::Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        self.exec()
+    }
+}
+
+/// `shape` with the orderings applied at exec folded in
+fn ordered_shape(shape: Option<u64>, order_bys: &[OrderByDescriptor]) -> Option<u64> {
+    crate::statement_cache::extend_shape(shape, format!("{:?}", order_bys))
+}
@@ -1,5 +1,9 @@
-use sea_orm::sea_query::{Expr, Func, SimpleExpr};
-use sea_orm::{ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, QueryTrait};
+use heck::ToSnakeCase;
+use sea_orm::sea_query::{Alias, Expr, Func, SimpleExpr};
+use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QuerySelect, QueryTrait};
+
+/// Alias of the `GROUPING(...)` bitmask selected alongside a native `ROLLUP`
+const GROUPING_ALIAS: &str = "__caustics_grouping";
 
 pub struct GroupByQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
     pub condition: sea_orm::sea_query::Condition,
@@ -12,6 +16,13 @@ pub struct GroupByQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
     pub take: Option<u64>,
     pub skip: Option<u64>,
     pub aggregates: Vec<(SimpleExpr, &'static str)>,
+    /// Also return subtotal rows for each prefix of the group keys, down to a grand total
+    pub rollup: bool,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<Entity>,
 }
 
@@ -19,6 +30,9 @@ pub struct GroupByQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
 pub struct GroupByTypedRow {
     pub keys: std::collections::HashMap<String, String>,
     pub aggregates: std::collections::HashMap<String, String>,
+    /// A `with_rollup` subtotal row: the rolled-up keys are absent from `keys`, and with
+    /// every key absent the row is the grand total
+    pub is_total: bool,
 }
 
 impl<'a, C, Entity> GroupByQueryBuilder<'a, C, Entity>
@@ -36,6 +50,27 @@ where
         self
     }
 
+    /// Group by the text value at `path` inside a JSON column, e.g.
+    /// `by_json(post::Column::CustomData, vec!["category"])`.
+    ///
+    /// The key is returned under the path segments joined with `_` (`category` here); rows
+    /// without the key form their own group with no key value.
+    pub fn by_json<P: Into<String>>(
+        mut self,
+        column: <Entity as EntityTrait>::Column,
+        path: impl IntoIterator<Item = P>,
+    ) -> Self {
+        let path: Vec<String> = path.into_iter().map(Into::into).collect();
+        let expr = crate::atomic::json_path_text(
+            self.conn.get_database_backend(),
+            sea_orm::IdenStatic::as_str(&column),
+            &path,
+        );
+        self.group_by_exprs.push(expr);
+        self.group_by_columns.push(path.join("_"));
+        self
+    }
+
     pub fn having(mut self, cond: SimpleExpr) -> Self {
         self.having.push(cond);
         self
@@ -93,6 +128,14 @@ where
         self
     }
 
+    /// Append subtotal rows over each prefix of the group keys and a grand total row, marked
+    /// `is_total`. Postgres runs a single `GROUP BY ROLLUP(...)`; other backends run one
+    /// extra grouped query per subtotal level. Cannot be combined with `take`/`skip`.
+    pub fn with_rollup(mut self) -> Self {
+        self.rollup = true;
+        self
+    }
+
     pub fn count(mut self, alias: &'static str) -> Self {
         self.aggregates.push((Expr::cust("COUNT(*)"), alias));
         self
@@ -130,16 +173,43 @@ where
         self
     }
 
-    pub async fn exec(self) -> Result<Vec<GroupByTypedRow>, sea_orm::DbErr> {
+    /// Build the statement, naming each group key column with `key_alias`
+    fn build_statement(&self, key_alias: fn(&str) -> String) -> sea_orm::Statement {
+        self.build_level(key_alias, self.group_by_exprs.len(), false)
+    }
+
+    /// Build the statement grouped by the first `depth` keys; the remaining key columns are
+    /// selected as NULL. `native_rollup` groups by `ROLLUP(...)` over every key instead.
+    fn build_level(
+        &self,
+        key_alias: fn(&str) -> String,
+        depth: usize,
+        native_rollup: bool,
+    ) -> sea_orm::Statement {
         let db_backend = self.conn.get_database_backend();
-        let mut select = Entity::find().filter(self.condition).select_only();
+        let mut select = Entity::find().filter(self.condition.clone()).select_only();
 
-        if !self.group_by_exprs.is_empty() {
-            for (idx, expr) in self.group_by_exprs.iter().enumerate() {
-                sea_orm::QueryTrait::query(&mut select).add_group_by(std::iter::once(expr.clone()));
-                if let Some(alias) = self.group_by_columns.get(idx) {
-                    select = select.expr_as(expr.clone(), alias.as_str());
+        if native_rollup {
+            let keys = self.group_by_exprs.iter().cloned();
+            sea_orm::QueryTrait::query(&mut select).add_group_by(std::iter::once(
+                SimpleExpr::FunctionCall(Func::cust(Alias::new("ROLLUP")).args(keys.clone())),
+            ));
+            select = select.expr_as(
+                SimpleExpr::FunctionCall(Func::cust(Alias::new("GROUPING")).args(keys)),
+                GROUPING_ALIAS,
+            );
+        }
+        for (idx, expr) in self.group_by_exprs.iter().enumerate() {
+            let key = if native_rollup || idx < depth {
+                if !native_rollup {
+                    sea_orm::QueryTrait::query(&mut select).add_group_by(std::iter::once(expr.clone()));
                 }
+                expr.clone()
+            } else {
+                Expr::cust("NULL")
+            };
+            if let Some(alias) = self.group_by_columns.get(idx) {
+                select = select.expr_as(key, key_alias(alias));
             }
         }
 
@@ -154,8 +224,15 @@ where
             sea_orm::QueryTrait::query(&mut select).cond_having(cond.clone());
         }
 
-        for (expr, ord) in &self.order_by {
-            sea_orm::QueryTrait::query(&mut select).order_by_expr(expr.clone(), ord.clone());
+        // Subtotal levels may have rolled up the ordered keys; order by what they group
+        if depth == self.group_by_exprs.len() {
+            for (expr, ord) in &self.order_by {
+                sea_orm::QueryTrait::query(&mut select).order_by_expr(expr.clone(), ord.clone());
+            }
+        } else {
+            for expr in &self.group_by_exprs[..depth] {
+                sea_orm::QueryTrait::query(&mut select).order_by_expr(expr.clone(), sea_orm::Order::Asc);
+            }
         }
 
         if let Some(n) = self.take {
@@ -165,11 +242,66 @@ where
             sea_orm::QueryTrait::query(&mut select).offset(n);
         }
 
-        let stmt = select.build(db_backend);
-        let rows = self.conn.query_all(stmt).await?;
+        select.build(db_backend)
+    }
+
+    /// Run the grouping, pairing each row with whether it is a rollup subtotal
+    async fn fetch_rows(
+        &self,
+        key_alias: fn(&str) -> String,
+    ) -> Result<Vec<(sea_orm::QueryResult, bool)>, sea_orm::DbErr> {
+        super::check_filter(&self.invalid_filter)?;
+        let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+        let depth = self.group_by_exprs.len();
+        if !self.rollup || depth == 0 {
+            let rows = conn.query_all(self.build_level(key_alias, depth, false)).await?;
+            return Ok(rows.into_iter().map(|row| (row, false)).collect());
+        }
+        if self.take.is_some() || self.skip.is_some() {
+            return Err(crate::types::CausticsError::QueryValidation {
+                message: "with_rollup cannot be combined with take/skip".to_string(),
+            }
+            .into());
+        }
+        if self.conn.get_database_backend() == DatabaseBackend::Postgres {
+            let rows = conn.query_all(self.build_level(key_alias, depth, true)).await?;
+            return rows
+                .into_iter()
+                .map(|row| {
+                    let grouping = row.try_get::<i32>("", GROUPING_ALIAS)?;
+                    Ok((row, grouping != 0))
+                })
+                .collect();
+        }
+        let mut out: Vec<_> = conn
+            .query_all(self.build_level(key_alias, depth, false))
+            .await?
+            .into_iter()
+            .map(|row| (row, false))
+            .collect();
+        for level in (0..depth).rev() {
+            let rows = conn.query_all(self.build_level(key_alias, level, false)).await?;
+            out.extend(rows.into_iter().map(|row| (row, true)));
+        }
+        Ok(out)
+    }
+
+    /// Map each group into `T`, reading group keys by snake_case field name (e.g. `age`)
+    /// and aggregates by their alias. Pairs with `select_struct!(Name { ... })`.
+    pub async fn exec_as<T: sea_orm::FromQueryResult>(self) -> Result<Vec<T>, sea_orm::DbErr> {
+        self.error_mapper
+            .run(self.fetch_rows(|key| key.to_snake_case()))
+            .await?
+            .iter()
+            .map(|(row, _)| T::from_query_result(row, ""))
+            .collect()
+    }
+
+    pub async fn exec(self) -> Result<Vec<GroupByTypedRow>, sea_orm::DbErr> {
+        let rows = self.error_mapper.run(self.fetch_rows(str::to_string)).await?;
 
         let mut out: Vec<GroupByTypedRow> = Vec::with_capacity(rows.len());
-        for r in rows {
+        for (r, is_total) in rows {
             let mut keys = std::collections::HashMap::new();
             for k in &self.group_by_columns {
                 if let Ok(v) = r.try_get::<i64>("", k) {
@@ -200,8 +332,58 @@ where
             out.push(GroupByTypedRow {
                 keys,
                 aggregates: aggs,
+                is_total,
             });
         }
         Ok(out)
     }
 }
+
+/// Reads a row's group key into a `CausticsKey`; `None` when the value has no key form
+pub type ReadGroupKey = fn(&sea_orm::QueryResult, &str) -> Result<Option<crate::CausticsKey>, sea_orm::DbErr>;
+
+/// Row counts per value of a single column, built by the generated `count_by`
+pub struct CountByQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
+    pub group_by: GroupByQueryBuilder<'a, C, Entity>,
+    /// Rust name of the grouped field, for error messages
+    pub field: &'static str,
+    pub read_key: ReadGroupKey,
+}
+
+impl<'a, C, Entity> CountByQueryBuilder<'a, C, Entity>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+{
+    /// `(key, count)` per distinct value, in ascending key order
+    pub async fn exec(self) -> Result<Vec<(crate::CausticsKey, i64)>, sea_orm::DbErr> {
+        super::check_filter(&self.group_by.invalid_filter)?;
+        let stmt = self.group_by.build_statement(str::to_string);
+        let conn = crate::hooks::CommentedConnection::new(self.group_by.conn, self.group_by.sql_comments);
+        let rows = self.group_by.error_mapper.run(conn.query_all(stmt)).await?;
+        rows.iter()
+            .map(|row| {
+                let key = (self.read_key)(row, "key")?.ok_or_else(|| {
+                    crate::types::CausticsError::QueryValidation {
+                        message: format!("count_by: values of '{}' have no CausticsKey form", self.field),
+                    }
+                })?;
+                Ok((key, row.try_get::<i64>("", "count")?))
+            })
+            .collect()
+    }
+}
+
+impl<'a, C, Entity> super::TimeoutExec for CountByQueryBuilder<'a, C, Entity>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+{
+    type Output = Vec<(crate::CausticsKey, i64)>;
+
+    fn exec_future(
+        self,
+    ) -> impl std::future::Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        self.exec()
+    }
+}
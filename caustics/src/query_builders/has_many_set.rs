@@ -32,6 +32,11 @@ where
                 > + Send + Sync,
         >,
     >,
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<(Entity, ActiveModel, ModelWithRelations)>,
 }
 
@@ -50,74 +55,81 @@ where
 {
     /// Execute the update with has_many set operations
     pub async fn exec(mut self) -> Result<ModelWithRelations, sea_orm::DbErr> {
-        // Separate has_many set operations from regular changes
-        let mut has_many_set_changes = Vec::new();
-        let mut has_many_create_changes = Vec::new();
-        let mut regular_changes = Vec::new();
-
-        for change in std::mem::take(&mut self.changes) {
-            if self.is_has_many_set_operation(&change) {
-                has_many_set_changes.push(change);
-            } else if change.is_has_many_create_operation() {
-                has_many_create_changes.push(change);
-            } else {
-                regular_changes.push(change);
+        self.error_mapper.clone().run(async move {
+            // Separate has_many set operations from regular changes
+            let mut has_many_set_changes = Vec::new();
+            let mut has_many_create_changes = Vec::new();
+            let mut regular_changes = Vec::new();
+
+            for change in std::mem::take(&mut self.changes) {
+                if self.is_has_many_set_operation(&change) {
+                    has_many_set_changes.push(change);
+                } else if change.is_has_many_create_operation() {
+                    has_many_create_changes.push(change);
+                } else {
+                    regular_changes.push(change);
+                }
             }
-        }
 
-        // Resolve entity ID using typed resolver
-        let entity_id = match &self.entity_id_resolver {
-            Some(resolver) => (resolver)(self.conn).await?,
-            None => {
-                return Err(crate::types::CausticsError::QueryValidation {
-                    message: "Missing entity id resolver for has_many set".to_string(),
+            // Resolve entity ID using typed resolver
+            let entity_id = match &self.entity_id_resolver {
+                Some(resolver) => (resolver)(self.conn).await?,
+                None => {
+                    return Err(crate::types::CausticsError::QueryValidation {
+                        message: "Missing entity id resolver for has_many set".to_string(),
+                    }
+                    .into())
                 }
-                .into())
-            }
-        };
-        // Convert entity_id to CausticsKey dynamically
-        let parent_id_key = match &entity_id {
-            sea_orm::Value::Int(Some(id)) => crate::CausticsKey::I32(*id),
-            sea_orm::Value::String(Some(s)) => crate::CausticsKey::String(s.to_string()),
-            sea_orm::Value::Uuid(Some(uuid)) => crate::CausticsKey::Uuid(**uuid),
-            _ => {
-                return Err(crate::types::CausticsError::QueryValidation {
-                    message: format!("Unsupported id type for has_many create: {:?}", entity_id),
+            };
+            // Convert entity_id to CausticsKey dynamically
+            let parent_id_key = match &entity_id {
+                sea_orm::Value::Int(Some(id)) => crate::CausticsKey::I32(*id),
+                sea_orm::Value::String(Some(s)) => crate::CausticsKey::String(s.to_string()),
+                sea_orm::Value::Uuid(Some(uuid)) => crate::CausticsKey::Uuid(**uuid),
+                _ => {
+                    return Err(crate::types::CausticsError::QueryValidation {
+                        message: format!("Unsupported id type for has_many create: {:?}", entity_id),
+                    }
+                    .into())
                 }
-                .into())
-            }
-        };
+            };
 
-        // Run nested creates, set operations, and regular update in a single transaction
-        let txn: DatabaseTransaction = self.conn.begin().await?;
+            // Run nested creates, set operations, and regular update in a single transaction
+            let txn: DatabaseTransaction = self.conn.begin().await?;
 
-        if !has_many_create_changes.is_empty() {
-            for change in has_many_create_changes {
-                change
-                    .exec_has_many_create_on_txn(&txn, parent_id_key.clone())
+            if !has_many_create_changes.is_empty() {
+                for change in has_many_create_changes {
+                    change
+                        .exec_has_many_create_on_txn(&txn, parent_id_key.clone())
+                        .await?;
+                }
+            }
+
+            if !has_many_set_changes.is_empty() {
+                self.process_has_many_set_operations_in_txn(has_many_set_changes, entity_id, &txn)
                     .await?;
             }
-        }
 
-        if !has_many_set_changes.is_empty() {
-            self.process_has_many_set_operations_in_txn(has_many_set_changes, entity_id, &txn)
-                .await?;
-        }
+            // Then execute regular update within the same transaction
+            let update_builder = super::update::UpdateQueryBuilder {
+                condition: self.condition,
+                changes: regular_changes,
+                conn: self.conn,
+                deferred_lookups: Vec::new(),
+                relations_to_fetch: self.relations_to_fetch,
+                registry: self.registry,
+                scope: self.scope,
+                sql_comments: self.sql_comments,
+                // Mapped once by the enclosing `run`
+                error_mapper: crate::ErrorMapper::default(),
+                _phantom: std::marker::PhantomData,
+            };
 
-        // Then execute regular update within the same transaction
-        let update_builder = super::update::UpdateQueryBuilder {
-            condition: self.condition,
-            changes: regular_changes,
-            conn: self.conn,
-            deferred_lookups: Vec::new(),
-            relations_to_fetch: self.relations_to_fetch,
-            registry: self.registry,
-            _phantom: std::marker::PhantomData,
-        };
-
-        let result = update_builder.exec_in_txn(&txn).await?;
-        txn.commit().await?;
-        Ok(result)
+            let result = update_builder.exec_in_txn(&txn).await?;
+            txn.commit().await?;
+            Ok(result)
+        })
+        .await
     }
 
     /// Execute the has_many set operations and scalar update inside an existing transaction
@@ -125,73 +137,80 @@ where
         mut self,
         txn: &DatabaseTransaction,
     ) -> Result<ModelWithRelations, sea_orm::DbErr> {
-        // Separate has_many set operations from regular changes
-        let mut has_many_set_changes = Vec::new();
-        let mut has_many_create_changes = Vec::new();
-        let mut regular_changes = Vec::new();
-
-        for change in std::mem::take(&mut self.changes) {
-            if self.is_has_many_set_operation(&change) {
-                has_many_set_changes.push(change);
-            } else if change.is_has_many_create_operation() {
-                has_many_create_changes.push(change);
-            } else {
-                regular_changes.push(change);
+        self.error_mapper.clone().run(async move {
+            // Separate has_many set operations from regular changes
+            let mut has_many_set_changes = Vec::new();
+            let mut has_many_create_changes = Vec::new();
+            let mut regular_changes = Vec::new();
+
+            for change in std::mem::take(&mut self.changes) {
+                if self.is_has_many_set_operation(&change) {
+                    has_many_set_changes.push(change);
+                } else if change.is_has_many_create_operation() {
+                    has_many_create_changes.push(change);
+                } else {
+                    regular_changes.push(change);
+                }
             }
-        }
 
-        // Resolve entity ID using typed resolver
-        let entity_id = match &self.entity_id_resolver {
-            Some(resolver) => (resolver)(self.conn).await?,
-            None => {
-                return Err(crate::types::CausticsError::QueryValidation {
-                    message: "Missing entity id resolver for has_many set".to_string(),
+            // Resolve entity ID using typed resolver
+            let entity_id = match &self.entity_id_resolver {
+                Some(resolver) => (resolver)(self.conn).await?,
+                None => {
+                    return Err(crate::types::CausticsError::QueryValidation {
+                        message: "Missing entity id resolver for has_many set".to_string(),
+                    }
+                    .into())
                 }
-                .into())
-            }
-        };
-
-        // Convert entity_id to CausticsKey dynamically (for create path)
-        let parent_id_key = match &entity_id {
-            sea_orm::Value::Int(Some(id)) => crate::CausticsKey::I32(*id),
-            sea_orm::Value::String(Some(s)) => crate::CausticsKey::String(s.to_string()),
-            sea_orm::Value::Uuid(Some(uuid)) => crate::CausticsKey::Uuid(**uuid),
-            _ => {
-                return Err(crate::types::CausticsError::QueryValidation {
-                    message: format!("Unsupported id type for has_many create: {:?}", entity_id),
+            };
+
+            // Convert entity_id to CausticsKey dynamically (for create path)
+            let parent_id_key = match &entity_id {
+                sea_orm::Value::Int(Some(id)) => crate::CausticsKey::I32(*id),
+                sea_orm::Value::String(Some(s)) => crate::CausticsKey::String(s.to_string()),
+                sea_orm::Value::Uuid(Some(uuid)) => crate::CausticsKey::Uuid(**uuid),
+                _ => {
+                    return Err(crate::types::CausticsError::QueryValidation {
+                        message: format!("Unsupported id type for has_many create: {:?}", entity_id),
+                    }
+                    .into())
+                }
+            };
+
+            // Perform nested creates inside provided transaction
+            if !has_many_create_changes.is_empty() {
+                for change in has_many_create_changes {
+                    change
+                        .exec_has_many_create_on_txn(txn, parent_id_key.clone())
+                        .await?;
                 }
-                .into())
             }
-        };
 
-        // Perform nested creates inside provided transaction
-        if !has_many_create_changes.is_empty() {
-            for change in has_many_create_changes {
-                change
-                    .exec_has_many_create_on_txn(txn, parent_id_key.clone())
+            // Perform set operations inside provided transaction
+            if !has_many_set_changes.is_empty() {
+                self
+                    .process_has_many_set_operations_in_txn(has_many_set_changes, entity_id, txn)
                     .await?;
             }
-        }
 
-        // Perform set operations inside provided transaction
-        if !has_many_set_changes.is_empty() {
-            self
-                .process_has_many_set_operations_in_txn(has_many_set_changes, entity_id, txn)
-                .await?;
-        }
+            // Execute regular update within the same transaction
+            let update_builder = super::update::UpdateQueryBuilder {
+                condition: self.condition,
+                changes: regular_changes,
+                conn: self.conn,
+                deferred_lookups: Vec::new(),
+                relations_to_fetch: self.relations_to_fetch,
+                registry: self.registry,
+                scope: self.scope,
+                sql_comments: self.sql_comments,
+                // Mapped once by the enclosing `run`
+                error_mapper: crate::ErrorMapper::default(),
+                _phantom: std::marker::PhantomData,
+            };
 
-        // Execute regular update within the same transaction
-        let update_builder = super::update::UpdateQueryBuilder {
-            condition: self.condition,
-            changes: regular_changes,
-            conn: self.conn,
-            deferred_lookups: Vec::new(),
-            relations_to_fetch: self.relations_to_fetch,
-            registry: self.registry,
-            _phantom: std::marker::PhantomData,
-        };
-
-        update_builder.exec_in_txn(txn).await
+            update_builder.exec_in_txn(txn).await
+        })
+        .await
     }
 
     /// Check if a change is a has_many set operation
@@ -243,21 +262,46 @@ where
                 relation_metadata.target_primary_key_column.to_string()
             };
 
+            // Prefer the schema-qualified table name when the target entity is registered
+            let target_table_name = relation_metadata
+                .target_entity_name
+                .and_then(|name| self.metadata_provider.get_entity_metadata(name))
+                .map(|metadata| metadata.qualified_table_name())
+                .unwrap_or_else(|| relation_metadata.target_table_name.to_string());
+
             let handler = DefaultHasManySetHandler::new(
                 relation_metadata.foreign_key_column.to_string(),
-                relation_metadata.target_table_name.to_string(),
+                target_table_name,
                 relation_metadata.current_primary_key_column.to_string(),
                 target_primary_key_column,
-                relation_metadata.is_foreign_key_nullable,
+                (relation_metadata.is_foreign_key_nullable)(),
             );
 
-            <DefaultHasManySetHandler as HasManySetHandler<C>>::process_set_operation_in_txn(
-                &handler,
-                txn,
-                entity_id.clone(),
-                target_ids,
-            )
-            .await?;
+            if change.is_has_one_connect_operation() {
+                <DefaultHasManySetHandler as HasManySetHandler<C>>::process_connect_one_operation_in_txn(
+                    &handler,
+                    txn,
+                    entity_id.clone(),
+                    target_ids,
+                )
+                .await?;
+            } else if change.is_has_many_disconnect_operation() {
+                <DefaultHasManySetHandler as HasManySetHandler<C>>::process_disconnect_operation_in_txn(
+                    &handler,
+                    txn,
+                    entity_id.clone(),
+                    target_ids,
+                )
+                .await?;
+            } else {
+                <DefaultHasManySetHandler as HasManySetHandler<C>>::process_set_operation_in_txn(
+                    &handler,
+                    txn,
+                    entity_id.clone(),
+                    target_ids,
+                )
+                .await?;
+            }
         }
 
         Ok(())
@@ -299,6 +343,23 @@ where
         current_entity_id: sea_orm::Value,
         target_ids: Vec<sea_orm::Value>,
     ) -> impl std::future::Future<Output = Result<(), sea_orm::DbErr>> + Send;
+
+    /// Null the foreign key of the given children inside an existing transaction
+    fn process_disconnect_operation_in_txn(
+        &self,
+        txn: &DatabaseTransaction,
+        current_entity_id: sea_orm::Value,
+        target_ids: Vec<sea_orm::Value>,
+    ) -> impl std::future::Future<Output = Result<(), sea_orm::DbErr>> + Send;
+
+    /// Point a has_one relation at a single child inside an existing transaction,
+    /// detaching whichever child it pointed at before
+    fn process_connect_one_operation_in_txn(
+        &self,
+        txn: &DatabaseTransaction,
+        current_entity_id: sea_orm::Value,
+        target_ids: Vec<sea_orm::Value>,
+    ) -> impl std::future::Future<Output = Result<(), sea_orm::DbErr>> + Send;
 }
 
 /// Default implementation for has_many set operations
@@ -539,4 +600,155 @@ where
             Ok(())
         }
     }
+
+    fn process_disconnect_operation_in_txn(
+        &self,
+        txn: &DatabaseTransaction,
+        current_entity_id: sea_orm::Value,
+        target_ids: Vec<sea_orm::Value>,
+    ) -> impl std::future::Future<Output = Result<(), sea_orm::DbErr>> + Send {
+        let foreign_key_column = self.foreign_key_column.clone();
+        let target_table_name = self.target_table_name.clone();
+        let target_primary_key_column = self.target_primary_key_column.clone();
+        let is_fk_nullable = self.is_foreign_key_nullable;
+        async move {
+            if !is_fk_nullable {
+                return Err(crate::types::CausticsError::QueryValidation {
+                    message: format!(
+                        "Cannot disconnect from '{}': foreign key '{}' is not nullable",
+                        target_table_name, foreign_key_column
+                    ),
+                }
+                .into());
+            }
+            if target_ids.is_empty() {
+                return Ok(());
+            }
+
+            let db_backend: DatabaseBackend = sea_orm::ConnectionTrait::get_database_backend(txn);
+            let placeholder = |index: usize| match db_backend {
+                DatabaseBackend::Postgres => format!("${}", index),
+                _ => "?".to_string(),
+            };
+            // Only detach children that currently belong to this entity
+            let placeholders = (0..target_ids.len())
+                .map(|i| placeholder(i + 2))
+                .collect::<Vec<_>>()
+                .join(",");
+            let disconnect_stmt = sea_orm::Statement::from_sql_and_values(
+                db_backend,
+                format!(
+                    "UPDATE {} SET {} = NULL WHERE {} = {} AND {} IN ({})",
+                    target_table_name,
+                    foreign_key_column,
+                    foreign_key_column,
+                    placeholder(1),
+                    target_primary_key_column,
+                    placeholders
+                ),
+                {
+                    let mut values = vec![current_entity_id];
+                    values.extend(target_ids);
+                    values
+                },
+            );
+            <DatabaseTransaction as sea_orm::ConnectionTrait>::execute(txn, disconnect_stmt).await?;
+
+            Ok(())
+        }
+    }
+
+    fn process_connect_one_operation_in_txn(
+        &self,
+        txn: &DatabaseTransaction,
+        current_entity_id: sea_orm::Value,
+        target_ids: Vec<sea_orm::Value>,
+    ) -> impl std::future::Future<Output = Result<(), sea_orm::DbErr>> + Send {
+        let foreign_key_column = self.foreign_key_column.clone();
+        let target_table_name = self.target_table_name.clone();
+        let target_primary_key_column = self.target_primary_key_column.clone();
+        let is_fk_nullable = self.is_foreign_key_nullable;
+        async move {
+            let target_id = match <[sea_orm::Value; 1]>::try_from(target_ids) {
+                Ok([target_id]) => target_id,
+                Err(_) => {
+                    return Err(crate::types::CausticsError::QueryValidation {
+                        message: format!(
+                            "connect on '{}' needs the target's primary key",
+                            target_table_name
+                        ),
+                    }
+                    .into())
+                }
+            };
+
+            let db_backend: DatabaseBackend = sea_orm::ConnectionTrait::get_database_backend(txn);
+            let placeholder = |index: usize| match db_backend {
+                DatabaseBackend::Postgres => format!("${}", index),
+                _ => "?".to_string(),
+            };
+            let others = format!(
+                "{} = {} AND {} <> {}",
+                foreign_key_column,
+                placeholder(1),
+                target_primary_key_column,
+                placeholder(2)
+            );
+
+            // Detach the previous child; a required foreign key cannot be detached, so refuse instead
+            if is_fk_nullable {
+                let detach_stmt = sea_orm::Statement::from_sql_and_values(
+                    db_backend,
+                    format!(
+                        "UPDATE {} SET {} = NULL WHERE {}",
+                        target_table_name, foreign_key_column, others
+                    ),
+                    vec![current_entity_id.clone(), target_id.clone()],
+                );
+                <DatabaseTransaction as sea_orm::ConnectionTrait>::execute(txn, detach_stmt).await?;
+            } else {
+                let existing_stmt = sea_orm::Statement::from_sql_and_values(
+                    db_backend,
+                    format!("SELECT 1 FROM {} WHERE {}", target_table_name, others),
+                    vec![current_entity_id.clone(), target_id.clone()],
+                );
+                if <DatabaseTransaction as sea_orm::ConnectionTrait>::query_one(txn, existing_stmt)
+                    .await?
+                    .is_some()
+                {
+                    return Err(crate::types::CausticsError::QueryValidation {
+                        message: format!(
+                            "Cannot replace the connected '{}' row: foreign key '{}' is not nullable",
+                            target_table_name, foreign_key_column
+                        ),
+                    }
+                    .into());
+                }
+            }
+
+            let connect_stmt = sea_orm::Statement::from_sql_and_values(
+                db_backend,
+                format!(
+                    "UPDATE {} SET {} = {} WHERE {} = {}",
+                    target_table_name,
+                    foreign_key_column,
+                    placeholder(1),
+                    target_primary_key_column,
+                    placeholder(2)
+                ),
+                vec![current_entity_id, target_id.clone()],
+            );
+            let result =
+                <DatabaseTransaction as sea_orm::ConnectionTrait>::execute(txn, connect_stmt).await?;
+            if result.rows_affected() == 0 {
+                return Err(crate::types::CausticsError::NotFoundForCondition {
+                    entity: target_table_name,
+                    condition: format!("{} = {:?}", target_primary_key_column, target_id),
+                }
+                .into());
+            }
+
+            Ok(())
+        }
+    }
 }
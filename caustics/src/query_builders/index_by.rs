@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use sea_orm::{ConnectionTrait, EntityTrait};
+
+use super::ManyQueryBuilder;
+use crate::{FromModel, HasRelationMetadata};
+
+/// Runs a `find_many` and collects the rows into a map keyed by `key`.
+///
+/// When several rows share a key the last one returned wins, so pair this with an
+/// `order_by` if duplicates are possible and the winner matters.
+pub struct IndexByQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations, F> {
+    pub inner: ManyQueryBuilder<'a, C, Entity, ModelWithRelations>,
+    pub key: F,
+}
+
+impl<'a, C, Entity, ModelWithRelations, F, K> IndexByQueryBuilder<'a, C, Entity, ModelWithRelations, F>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+    F: Fn(&ModelWithRelations) -> K,
+    K: Eq + Hash,
+{
+    pub async fn exec(self) -> Result<HashMap<K, ModelWithRelations>, sea_orm::DbErr> {
+        let rows = self.inner.exec().await?;
+        Ok(rows.into_iter().map(|row| ((self.key)(&row), row)).collect())
+    }
+}
@@ -0,0 +1,134 @@
+use sea_orm::sea_query::{InsertStatement, Query};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, IdenStatic, Iterable, QuerySelect, QueryTrait};
+
+use super::ManyQueryBuilder;
+use crate::{FromModel, HasRelationMetadata};
+
+/// Copies the rows matched by a `find_many` into another table with a single
+/// `INSERT INTO target (...) SELECT ... FROM source WHERE ...` statement.
+///
+/// Target columns are matched to source columns by name and must agree on type. A target
+/// column without a source counterpart is left out of the insert, so it has to be nullable
+/// or have a database default. Mismatches are reported by `exec` as a `QueryValidation`
+/// error before anything is sent to the database.
+pub struct InsertSelectQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
+    pub conn: &'a C,
+    pub statement: Result<InsertStatement, String>,
+    pub error_mapper: crate::ErrorMapper,
+    pub _phantom: std::marker::PhantomData<Entity>,
+}
+
+impl<'a, C: ConnectionTrait, Entity: EntityTrait> InsertSelectQueryBuilder<'a, C, Entity> {
+    /// Internal constructor used by generated code
+    pub fn new<'b, SC, SourceEntity, SourceModelWithRelations>(
+        conn: &'a C,
+        source: ManyQueryBuilder<'b, SC, SourceEntity, SourceModelWithRelations>,
+    ) -> Self
+    where
+        SC: ConnectionTrait,
+        SourceEntity: EntityTrait,
+        SourceModelWithRelations: FromModel<SourceEntity::Model>
+            + HasRelationMetadata<SourceModelWithRelations>
+            + crate::types::ApplyNestedIncludes<SC>
+            + Send
+            + 'static,
+    {
+        Self {
+            conn,
+            error_mapper: source.error_mapper.clone(),
+            statement: insert_select_statement::<Entity, _, _, _>(source),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// The SQL this builder will run, with values inlined for debugging
+    pub fn to_sql(&self) -> String {
+        match &self.statement {
+            Ok(insert) => self.conn.get_database_backend().build(insert).to_string(),
+            Err(message) => format!("/* invalid: {} */", message),
+        }
+    }
+
+    /// Run the insert and return the number of rows copied
+    pub async fn exec(self) -> Result<i64, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            let insert = self
+                .statement
+                .map_err(|message| crate::types::CausticsError::QueryValidation { message })?;
+            let stmt = self.conn.get_database_backend().build(&insert);
+            let result = self.conn.execute(stmt).await?;
+            Ok(result.rows_affected() as i64)
+        })
+        .await
+    }
+}
+
+fn insert_select_statement<Entity, SC, SourceEntity, SourceModelWithRelations>(
+    source: ManyQueryBuilder<'_, SC, SourceEntity, SourceModelWithRelations>,
+) -> Result<InsertStatement, String>
+where
+    Entity: EntityTrait,
+    SC: ConnectionTrait,
+    SourceEntity: EntityTrait,
+    SourceModelWithRelations: FromModel<SourceEntity::Model>
+        + HasRelationMetadata<SourceModelWithRelations>
+        + crate::types::ApplyNestedIncludes<SC>
+        + Send
+        + 'static,
+{
+    if let Some(message) = source.invalid_pagination.clone() {
+        return Err(message);
+    }
+    if let Some(message) = source.invalid_filter.clone() {
+        return Err(message);
+    }
+    if !source.relations_to_fetch.is_empty() {
+        return Err("create_from_select does not support relations on the source query".to_string());
+    }
+
+    let mut target_columns = Vec::new();
+    let mut source_columns = Vec::new();
+    for target in Entity::Column::iter() {
+        let name = target.as_str();
+        match SourceEntity::Column::iter().find(|source| source.as_str() == name) {
+            Some(source) => {
+                let (target_type, source_type) = (target.def(), source.def());
+                if target_type.get_column_type() != source_type.get_column_type() {
+                    return Err(format!(
+                        "column `{}` is {:?} in the target but {:?} in the source",
+                        name,
+                        target_type.get_column_type(),
+                        source_type.get_column_type()
+                    ));
+                }
+                target_columns.push(target);
+                source_columns.push(source);
+            }
+            None => {
+                let def = target.def();
+                if !def.is_null() && def.get_column_default().is_none() {
+                    return Err(format!(
+                        "target column `{}` has no source column of the same name and is neither nullable nor defaulted",
+                        name
+                    ));
+                }
+            }
+        }
+    }
+    if target_columns.is_empty() {
+        return Err("no target column matches a source column".to_string());
+    }
+
+    let select = source
+        .built_select()
+        .select_only()
+        .columns(source_columns)
+        .into_query();
+    let mut insert = Query::insert();
+    insert
+        .into_table(Entity::default().table_ref())
+        .columns(target_columns)
+        .select_from(select)
+        .map_err(|e| e.to_string())?;
+    Ok(insert)
+}
@@ -1,14 +1,21 @@
 use crate::types::ApplyNestedIncludes;
 use crate::types::EntityRegistry;
 use crate::types::SelectionSpec;
-use crate::types::{IntoOrderSpec, NullsOrder};
+use crate::query_builders::order_by::{apply_order_bys, cursor_keyset, effective_descriptors};
+use crate::query_builders::OrderByDescriptor;
+use crate::types::IntoOrderSpec;
 use crate::EntitySelection;
 use crate::{FromModel, HasRelationMetadata, RelationFilter};
-use sea_orm::sea_query::{Condition, Expr, SimpleExpr};
+use sea_orm::sea_query::{Expr, SimpleExpr};
 use sea_orm::{
-    ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Select,
+    ConnectionTrait, DatabaseBackend, DatabaseTransaction, EntityTrait, QueryFilter, QuerySelect,
+    Select,
 };
 
+/// Largest `take`/`skip` accepted; anything bigger is a caller bug (or an overflowed
+/// computation) rather than a page size, so it fails with `InvalidPagination`
+const MAX_PAGINATION: u64 = i32::MAX as u64;
+
 /// Query builder for finding multiple entity records matching conditions
 pub struct ManyQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> {
     pub query: Select<Entity>,
@@ -17,13 +24,29 @@ pub struct ManyQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWi
     pub registry: &'a (dyn EntityRegistry<C> + Sync),
     pub database_backend: DatabaseBackend,
     pub reverse_order: bool,
-    pub pending_order_bys: Vec<(SimpleExpr, sea_orm::Order)>,
-    pub pending_nulls: Option<NullsOrder>,
+    pub pending_order_bys: Vec<OrderByDescriptor>,
     pub cursor: Option<Vec<(SimpleExpr, sea_orm::Value)>>,
     pub is_distinct: bool,
     pub distinct_on_fields: Option<Vec<SimpleExpr>>,
     pub distinct_on_columns: Option<Vec<<Entity as EntityTrait>::Column>>,
-    pub skip_is_negative: bool,
+    /// Set by `distinct_on`: any `order_by` must start with the distinct fields
+    pub distinct_on_ordered: bool,
+    /// Rejected `take`/`skip` input, reported when the query runs
+    pub invalid_pagination: Option<String>,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    /// Client-wide row cap for queries without an explicit `take`
+    pub max_rows: Option<u64>,
+    /// Whether `take`/`last` set an explicit limit
+    pub has_limit: bool,
+    /// Value-free fingerprint of how the query was built, keying the statement cache;
+    /// `None` keeps it out of the cache, e.g. after editing `query` directly
+    pub shape: Option<u64>,
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<ModelWithRelations>,
 }
 
@@ -58,12 +81,19 @@ where
             database_backend: self.database_backend,
             reverse_order: self.reverse_order,
             pending_order_bys: self.pending_order_bys,
-            pending_nulls: self.pending_nulls,
             cursor: self.cursor,
             is_distinct: self.is_distinct,
             distinct_on_fields: self.distinct_on_fields,
             distinct_on_columns: self.distinct_on_columns,
-            skip_is_negative: self.skip_is_negative,
+            distinct_on_ordered: self.distinct_on_ordered,
+            invalid_pagination: self.invalid_pagination,
+            invalid_filter: self.invalid_filter,
+            sql_comments: self.sql_comments,
+            max_rows: self.max_rows,
+            has_limit: self.has_limit,
+            shape: self.shape,
+            scope: self.scope,
+            error_mapper: self.error_mapper,
             _phantom: std::marker::PhantomData,
         };
         let aliases = spec.collect_aliases();
@@ -75,15 +105,32 @@ where
         }
         builder
     }
-    /// Limit the number of results (aligned with Prisma's i64 API)
-    pub fn take(mut self, limit: i64) -> Self {
-        let limit_u = if limit < 0 {
+    /// Limit the number of results (aligned with Prisma's i64 API).
+    ///
+    /// A negative value takes that many rows from the end, like Prisma: `take(-3)` runs the
+    /// query with every `order_by` (and cursor) reversed, then flips the rows back, so the
+    /// last three rows come back in the original order. `skip` then counts from the end too.
+    /// `None` removes any limit set earlier, for dynamic code; a client's `max_rows` cap
+    /// still applies. Sizes beyond `i32::MAX` either way fail with `InvalidPagination`.
+    pub fn take(mut self, limit: impl Into<Option<i64>>) -> Self {
+        let Some(limit) = limit.into() else {
+            self.query = self.query.limit(None);
+            self.reverse_order = false;
+            self.has_limit = false;
+            self.shape = crate::statement_cache::extend_shape(self.shape, "take(None)");
+            return self;
+        };
+        if limit < 0 {
             self.reverse_order = true;
-            (-limit) as u64
+        }
+        if limit.unsigned_abs() > MAX_PAGINATION {
+            // Defer error until exec to maintain builder signature
+            self.invalid_pagination = Some(format!("take({}) is out of range", limit));
         } else {
-            limit as u64
-        };
-        self.query = self.query.limit(limit_u);
+            self.query = self.query.limit(limit.unsigned_abs());
+        }
+        self.has_limit = true;
+        self.shape = crate::statement_cache::extend_shape(self.shape, "take");
         self
     }
 
@@ -91,23 +138,85 @@ where
     pub fn skip(mut self, offset: i64) -> Self {
         if offset < 0 {
             // Defer error until exec to maintain builder signature
-            self.skip_is_negative = true;
+            self.invalid_pagination = Some(format!("skip must be >= 0, got {}", offset));
+        } else if offset as u64 > MAX_PAGINATION {
+            self.invalid_pagination = Some(format!("skip({}) is out of range", offset));
         } else {
             self.query = self.query.offset(offset as u64);
+            self.shape = crate::statement_cache::extend_shape(self.shape, "skip");
         }
         self
     }
 
+    /// AND a raw SQL predicate into the filter, e.g. `.and_raw("age % 2 = 0")`.
+    ///
+    /// The fragment is inlined verbatim (not parameterized), so it must be trusted input; pass
+    /// user-supplied values through `and_raw_bind` instead. Column names are not checked.
+    pub fn and_raw(self, sql: &str) -> Self {
+        self.and_raw_bind(sql, Vec::new())
+    }
+
+    /// AND a raw SQL predicate with `?` placeholders bound to `params`, e.g.
+    /// `.and_raw_bind("created_at > ?", vec![since.into()])`. Placeholders are rewritten for the
+    /// backend; the SQL text itself must still be trusted input.
+    pub fn and_raw_bind(mut self, sql: &str, params: Vec<sea_orm::Value>) -> Self {
+        let param_count = params.len();
+        self.query = QueryFilter::filter(
+            self.query,
+            crate::raw::condition_expr(self.database_backend, sql, params),
+        );
+        self.shape = crate::statement_cache::extend_shape(self.shape, (sql, param_count));
+        self
+    }
+
     /// Order the results (supports scalar columns or relation aggregates via IntoOrderByExpr)
     pub fn order_by<T>(mut self, order_spec: T) -> Self
     where
         T: IntoOrderSpec,
     {
-        let (expr, order, nulls) = order_spec.into_order_spec();
-        self.pending_order_bys.push((expr, order));
-        if nulls.is_some() {
-            self.pending_nulls = nulls;
-        }
+        self.pending_order_bys.push(order_spec.into());
+        self
+    }
+
+    /// Order the results by a raw SQL fragment, e.g. `"LOWER(name) ASC"`.
+    ///
+    /// The fragment is appended verbatim (not parameterized), so it must be trusted input.
+    /// It composes with `order_by` in call order.
+    pub fn order_by_raw(self, fragment: impl Into<String>) -> Self {
+        self.order_by(crate::types::RawOrderBy(fragment.into()))
+    }
+
+    /// Order the results by a numeric value inside a JSON column, e.g.
+    /// `order_by_json(post::Column::CustomData, vec!["view_count"], SortOrder::Desc)`.
+    ///
+    /// The value at `path` is extracted and cast to a number per backend (see
+    /// `atomic::json_path_number`), so `10` sorts after `9`. Composes with `order_by` in call order.
+    pub fn order_by_json<P: Into<String>>(
+        mut self,
+        column: <Entity as EntityTrait>::Column,
+        path: impl IntoIterator<Item = P>,
+        order: crate::SortOrder,
+    ) -> Self {
+        let path: Vec<String> = path.into_iter().map(Into::into).collect();
+        let expr = crate::atomic::json_path_number(
+            self.database_backend,
+            sea_orm::IdenStatic::as_str(&column),
+            &path,
+        );
+        self.pending_order_bys.push(OrderByDescriptor::new(expr, order));
+        self
+    }
+
+    /// Order the results by several keys at once, in list order.
+    ///
+    /// Accepts anything convertible into an `OrderByDescriptor`, so a sort spec can be
+    /// built at runtime, e.g. `vec![(user::Column::Name, SortOrder::Asc), (user::Column::Age, SortOrder::Desc)]`.
+    pub fn order_by_many<I>(mut self, specs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<crate::query_builders::OrderByDescriptor>,
+    {
+        self.pending_order_bys.extend(specs.into_iter().map(Into::into));
         self
     }
 
@@ -115,22 +224,33 @@ where
     pub fn distinct_all(mut self) -> Self {
         self.query = self.query.distinct();
         self.is_distinct = true;
+        self.shape = crate::statement_cache::extend_shape(self.shape, "distinct");
         self
     }
 
-    /// EXPERIMENTAL: Distinct by specific fields (best-effort across backends)
-    pub fn distinct_on(mut self, fields: Vec<SimpleExpr>) -> Self {
-        self.distinct_on_fields = Some(fields);
-        // Fallback behavior: apply simple DISTINCT when backend doesn't support DISTINCT ON
-        self.query = self.query.distinct();
+    /// Postgres-style `DISTINCT ON`: keep one row per distinct combination of `fields`
+    /// (typed `ScalarField`s or column expressions).
+    ///
+    /// As on Postgres, an `order_by` must start with the distinct fields, and the keys after
+    /// them pick the row kept per group, e.g. `created_at` descending for the newest; anything
+    /// else fails with `QueryValidation` when the query runs. Other backends emulate it with
+    /// `ROW_NUMBER()`.
+    pub fn distinct_on<K: crate::types::IntoDistinctOnKey<Entity>>(mut self, fields: Vec<K>) -> Self {
+        let (exprs, columns): (Vec<_>, Vec<_>) =
+            fields.into_iter().map(|f| f.into_distinct_on_key()).unzip();
+        self.distinct_on_fields = Some(exprs);
+        self.distinct_on_columns = columns.into_iter().collect();
+        self.distinct_on_ordered = true;
         self.is_distinct = true;
         self
     }
 
-    /// Distinct on typed columns (enables native DISTINCT ON on Postgres)
+    /// Distinct on typed columns, keeping the first row per group like `distinct_on_fields`
+    /// (native DISTINCT ON on Postgres)
     pub fn distinct_on_columns(mut self, cols: Vec<<Entity as EntityTrait>::Column>) -> Self {
+        use sea_orm::IntoSimpleExpr;
+        self.distinct_on_fields = Some(cols.iter().map(|col| col.into_simple_expr()).collect());
         self.distinct_on_columns = Some(cols);
-        self.query = self.query.distinct();
         self.is_distinct = true;
         self
     }
@@ -144,178 +264,350 @@ where
         self
     }
 
+    /// Copy of this builder, so a paginator can run it once per page
+    pub(crate) fn clone_for_page(&self) -> Self {
+        Self {
+            query: self.query.clone(),
+            conn: self.conn,
+            relations_to_fetch: self.relations_to_fetch.clone(),
+            registry: self.registry,
+            database_backend: self.database_backend,
+            reverse_order: self.reverse_order,
+            pending_order_bys: self.pending_order_bys.clone(),
+            cursor: self.cursor.clone(),
+            is_distinct: self.is_distinct,
+            distinct_on_fields: self.distinct_on_fields.clone(),
+            distinct_on_columns: self.distinct_on_columns.clone(),
+            distinct_on_ordered: self.distinct_on_ordered,
+            invalid_pagination: self.invalid_pagination.clone(),
+            sql_comments: self.sql_comments,
+            max_rows: self.max_rows,
+            has_limit: self.has_limit,
+            shape: self.shape,
+            invalid_filter: self.invalid_filter.clone(),
+            scope: self.scope.clone(),
+            error_mapper: self.error_mapper.clone(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Stop waiting for the query after `timeout`; the deadline is client-side only, see
+    /// [`TimeoutQueryBuilder`](super::TimeoutQueryBuilder)
+    pub fn timeout(self, timeout: std::time::Duration) -> super::TimeoutQueryBuilder<Self> {
+        super::TimeoutQueryBuilder {
+            inner: self,
+            timeout,
+        }
+    }
+
+    /// Run the query with Postgres' `search_path` set to `schema`, e.g. a tenant's schema.
+    /// The name must be a plain identifier; anything else fails when the query runs.
+    pub fn with_schema(self, schema: impl Into<String>) -> super::SchemaQueryBuilder<'a, C, Entity, ModelWithRelations> {
+        super::SchemaQueryBuilder {
+            inner: self,
+            schema: schema.into(),
+        }
+    }
+
+    /// Collect the results into a `HashMap` keyed by `key`, e.g. `index_by(user::id::key)`.
+    /// Later rows win when keys collide.
+    pub fn index_by<F, K>(self, key: F) -> super::IndexByQueryBuilder<'a, C, Entity, ModelWithRelations, F>
+    where
+        F: Fn(&ModelWithRelations) -> K,
+        K: Eq + std::hash::Hash,
+    {
+        super::IndexByQueryBuilder { inner: self, key }
+    }
+
+    /// The SeaORM `Select` this builder would run: filters, cursor, ordering, paging and
+    /// distinct applied, relations not fetched.
+    ///
+    /// Use it to compose what caustics does not model (custom joins, extra columns) and run
+    /// it yourself. A negative `skip` is not applied, since it is only reported by `exec`.
+    pub fn into_select(self) -> Select<Entity> {
+        self.built_select()
+    }
+
+    /// SQL text this query would be sent as, e.g. for logging or assertions
+    pub fn to_sql(&self) -> String {
+        let mut query = self.built_select();
+        crate::statement_cache::to_sql(
+            self.database_backend,
+            sea_orm::QueryTrait::query(&mut query),
+            self.sql_comments,
+        )
+    }
+
+    pub(super) fn built_select(&self) -> Select<Entity> {
+        let mut query = self.query.clone();
+        // Apply cursor filtering if provided, ordering by the cursor columns as tie-breakers
+        let order_bys = match self.cursor.as_deref() {
+            Some(cursor_parts) if !cursor_parts.is_empty() => {
+                let (condition, keys) = cursor_keyset::<Entity>(
+                    &self.pending_order_bys,
+                    cursor_parts,
+                    self.reverse_order,
+                );
+                query = query.filter(condition);
+                keys
+            }
+            _ => effective_descriptors(&self.pending_order_bys, self.reverse_order),
+        };
+        // Apply orderings here, so reversal is respected regardless of call order
+        query = apply_order_bys(query, &order_bys);
+
+        // Apply per-field distinct if provided, keeping the first row per group
+        if let Some(fields) = &self.distinct_on_fields {
+            if !fields.is_empty() {
+                query = apply_distinct_on(
+                    query,
+                    self.database_backend,
+                    fields,
+                    self.distinct_on_columns.as_deref(),
+                    &effective_order_bys(&order_bys),
+                );
+            }
+        }
+
+        query
+    }
+
+    /// `shape` with what `built_select` and the row cap add at exec folded in
+    fn exec_shape(&self, row_cap: Option<u64>) -> Option<u64> {
+        exec_shape::<Entity>(
+            self.shape,
+            self.reverse_order,
+            &self.pending_order_bys,
+            self.cursor.as_deref(),
+            self.distinct_on_fields.as_deref(),
+            self.distinct_on_columns.as_deref(),
+            row_cap,
+        )
+    }
+
+    /// Execute the query on `conn` instead of the client's connection, e.g. a transaction
+    /// managed outside caustics
+    pub async fn exec_on<C2: ConnectionTrait + 'static>(self, conn: &C2) -> Result<Vec<ModelWithRelations>, sea_orm::DbErr>
+    where
+        ModelWithRelations: crate::types::ApplyNestedIncludes<C2> + crate::types::RegistryProvider<C2>,
+    {
+        ManyQueryBuilder {
+            query: self.query,
+            conn,
+            relations_to_fetch: self.relations_to_fetch,
+            registry: <ModelWithRelations as crate::types::RegistryProvider<C2>>::registry(),
+            database_backend: conn.get_database_backend(),
+            reverse_order: self.reverse_order,
+            pending_order_bys: self.pending_order_bys,
+            cursor: self.cursor,
+            is_distinct: self.is_distinct,
+            distinct_on_fields: self.distinct_on_fields,
+            distinct_on_columns: self.distinct_on_columns,
+            distinct_on_ordered: self.distinct_on_ordered,
+            invalid_pagination: self.invalid_pagination,
+            sql_comments: self.sql_comments,
+            max_rows: self.max_rows,
+            has_limit: self.has_limit,
+            shape: self.shape,
+            invalid_filter: self.invalid_filter,
+            scope: self.scope,
+            error_mapper: self.error_mapper,
+            _phantom: std::marker::PhantomData,
+        }
+        .exec()
+        .await
+    }
+
     /// Execute the query and return multiple results
     pub async fn exec(self) -> Result<Vec<ModelWithRelations>, sea_orm::DbErr>
     where
         ModelWithRelations: FromModel<Entity::Model>,
     {
-        if self.skip_is_negative {
-            return Err(crate::types::CausticsError::QueryValidation {
-                message: "skip must be >= 0".to_string(),
+        self.error_mapper.clone().run(async move {
+            if let Some(message) = self.invalid_pagination {
+                return Err(crate::types::CausticsError::InvalidPagination { message }.into());
             }
-            .into());
-        }
-        let mut query = self.query.clone();
-        // Apply cursor filtering if provided
-        if let Some(cursor_parts) = &self.cursor {
-            // Determine effective order to derive comparison operator
-            let first_order = self
-                .pending_order_bys
-                .first()
-                .map(|(_, ord)| ord.clone())
-                .unwrap_or(sea_orm::Order::Asc);
-            let effective_order = if self.reverse_order {
-                match first_order {
-                    sea_orm::Order::Asc => sea_orm::Order::Desc,
-                    sea_orm::Order::Desc => sea_orm::Order::Asc,
-                    other => other,
-                }
+            super::check_filter(&self.invalid_filter)?;
+            if self.distinct_on_ordered {
+                check_distinct_on_order(self.distinct_on_fields.as_deref(), &self.pending_order_bys)?;
+            }
+            let mut query = self.built_select();
+            // Rows were fetched in reversed order; hand them back in the requested one
+            let restore_order = self.reverse_order;
+            // Without an explicit take, fetch one row past the cap so exceeding it can be detected
+            let row_cap = self.max_rows.filter(|_| !self.has_limit);
+            if let Some(cap) = row_cap {
+                query = query.limit(cap.saturating_add(1));
+            }
+
+            // Emit before hook
+            let entity_name = core::any::type_name::<Entity>();
+            let details = if crate::hooks::has_hooks() {
+                let sql = sea_orm::QueryTrait::build(&query, self.database_backend).sql;
+                crate::hooks::compose_details_with_filter("select_many", entity_name, &sql)
             } else {
-                first_order
+                crate::hooks::compose_details("select_many", entity_name)
             };
-
-            // Exclusive comparator for proper cursor pagination (cursor row excluded)
-            // For composite cursors, apply lexicographic comparison over all parts
-            // WHERE (a > ca) OR (a = ca AND b > cb) OR (a = ca AND b = cb AND c > cc) ...
-            if !cursor_parts.is_empty() {
-                let mut disjunction = Condition::any();
-                for i in 0..cursor_parts.len() {
-                    let mut conjunction = Condition::all();
-                    // Prefix equals on earlier parts
-                    for j in 0..i {
-                        let (expr_eq, val_eq) = &cursor_parts[j];
-                        conjunction = conjunction.add(Expr::expr(expr_eq.clone()).eq(val_eq.clone()));
+            crate::hooks::emit_before(&crate::hooks::QueryEvent {
+                builder: "ManyQueryBuilder",
+                entity: entity_name,
+                details: details.clone(),
+            });
+            let start = std::time::Instant::now();
+            let shape = self.exec_shape(row_cap);
+            let res = if self.relations_to_fetch.is_empty() {
+                crate::statement_cache::all("ManyQueryBuilder", query, shape, self.conn, self.sql_comments).await.map(|models| {
+                    models
+                        .into_iter()
+                        .map(|model| ModelWithRelations::from_model(model))
+                        .collect()
+                })
+            } else {
+                self.exec_with_relations_with_query(query, shape).await
+            };
+            let res = match (res, row_cap) {
+                (Ok(rows), Some(cap)) if rows.len() as u64 > cap => {
+                    Err(crate::types::CausticsError::RowLimitExceeded {
+                        entity: entity_name.to_string(),
+                        limit: cap,
                     }
-                    // Strict comparator on current part
-                    let (expr_cmp, val_cmp) = &cursor_parts[i];
-                    let cmp = match effective_order {
-                        sea_orm::Order::Asc => Expr::expr(expr_cmp.clone()).gt(val_cmp.clone()),
-                        sea_orm::Order::Desc => Expr::expr(expr_cmp.clone()).lt(val_cmp.clone()),
-                        _ => Expr::expr(expr_cmp.clone()).gt(val_cmp.clone()),
-                    };
-                    conjunction = conjunction.add(cmp);
-                    disjunction = disjunction.add(conjunction);
+                    .into())
+                }
+                (res, _) => res,
+            };
+            let res = res.map(|mut rows| {
+                if restore_order {
+                    rows.reverse();
                 }
-                query = query.filter(disjunction);
+                rows
+            });
+            // Emit after hook
+            match &res {
+                Ok(rows) => crate::hooks::emit_after(
+                    &crate::hooks::QueryEvent {
+                        builder: "ManyQueryBuilder",
+                        entity: entity_name,
+                        details: details.clone(),
+                    },
+                    &crate::hooks::QueryResultMeta {
+                        row_count: Some(rows.len()),
+                        error: None,
+                        elapsed_ms: Some(start.elapsed().as_millis()),
+                    },
+                ),
+                Err(e) => crate::hooks::emit_after(
+                    &crate::hooks::QueryEvent {
+                        builder: "ManyQueryBuilder",
+                        entity: entity_name,
+                        details: details.clone(),
+                    },
+                    &crate::hooks::QueryResultMeta {
+                        row_count: None,
+                        error: Some(e.to_string()),
+                        elapsed_ms: Some(start.elapsed().as_millis()),
+                    },
+                ),
             }
+            res
+        })
+        .await
+    }
 
-            // If no explicit order_by was provided, order by the cursor column for stability
-            if self.pending_order_bys.is_empty() {
-                let ord = if self.reverse_order { sea_orm::Order::Desc } else { sea_orm::Order::Asc };
-                // Order by all cursor parts to preserve lexicographic ordering
-                for (expr, _) in cursor_parts.iter() {
-                    query = query.order_by(expr.clone(), ord.clone());
+    /// Execute and map each row into `T` through SeaORM's `FromQueryResult`, e.g. a DTO
+    /// deriving it with a subset of the entity's columns. Fields are matched by column name.
+    ///
+    /// Filters, ordering, paging and `max_rows` apply as in `exec`; includes (`with`) are
+    /// rejected, since there is no model to attach them to.
+    pub async fn exec_into<T: sea_orm::FromQueryResult>(self) -> Result<Vec<T>, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            if !self.relations_to_fetch.is_empty() {
+                return Err(crate::types::CausticsError::QueryValidation {
+                    message: "exec_into does not support includes".to_string(),
                 }
+                .into());
             }
-        }
-        // Apply any pending orderings here, so reversal is respected regardless of call order
-        if !self.pending_order_bys.is_empty() {
-            // Apply NULLS ordering for the primary order expression if requested
-            if let Some(n) = self.pending_nulls {
-                if let Some((first_expr, _)) = self.pending_order_bys.first() {
-                    let nulls_expr = Expr::expr(first_expr.clone()).is_null();
-                    match n {
-                        NullsOrder::First => {
-                            query = query.order_by(nulls_expr, sea_orm::Order::Desc);
-                        }
-                        NullsOrder::Last => {
-                            query = query.order_by(nulls_expr, sea_orm::Order::Asc);
-                        }
-                    }
-                }
+            if let Some(message) = self.invalid_pagination {
+                return Err(crate::types::CausticsError::InvalidPagination { message }.into());
             }
-
-            for (expr, order) in &self.pending_order_bys {
-                let effective = if self.reverse_order {
-                    match order {
-                        sea_orm::Order::Asc => sea_orm::Order::Desc,
-                        sea_orm::Order::Desc => sea_orm::Order::Asc,
-                        other => other.clone(),
-                    }
-                } else {
-                    order.clone()
-                };
-                query = query.order_by(expr.clone(), effective);
+            super::check_filter(&self.invalid_filter)?;
+            if self.distinct_on_ordered {
+                check_distinct_on_order(self.distinct_on_fields.as_deref(), &self.pending_order_bys)?;
+            }
+            let mut query = self.built_select();
+            let row_cap = self.max_rows.filter(|_| !self.has_limit);
+            if let Some(cap) = row_cap {
+                query = query.limit(cap.saturating_add(1));
             }
-        }
 
-        // Apply per-field distinct if provided:
-        // - Postgres: try to use DISTINCT ON natively when available
-        // - Others: best-effort emulation via GROUP BY
-        if let Some(fields) = &self.distinct_on_fields {
-            if !fields.is_empty() {
-                match self.database_backend {
-                    DatabaseBackend::Postgres => {
-                        if let Some(cols) = &self.distinct_on_columns {
-                            sea_orm::QueryTrait::query(&mut query).distinct_on(cols.clone());
-                        } else {
-                            for f in fields {
-                                sea_orm::QueryTrait::query(&mut query)
-                                    .add_group_by(std::iter::once(f.clone()));
-                            }
-                        }
-                    }
-                    _ => {
-                        for f in fields {
-                            sea_orm::QueryTrait::query(&mut query)
-                                .add_group_by(std::iter::once(f.clone()));
-                        }
+            let entity_name = core::any::type_name::<Entity>();
+            let event = crate::hooks::QueryEvent {
+                builder: "ManyQueryBuilder",
+                entity: entity_name,
+                details: crate::hooks::compose_details("select_many_into", entity_name),
+            };
+            crate::hooks::emit_before(&event);
+            let start = std::time::Instant::now();
+            let shape = self.exec_shape(row_cap);
+            let mut res = crate::statement_cache::all_into::<Entity, T, C>(
+                "ManyQueryBuilder::into",
+                query,
+                shape,
+                self.conn,
+                self.sql_comments,
+            )
+            .await;
+            if let (Ok(rows), Some(cap)) = (&res, row_cap) {
+                if rows.len() as u64 > cap {
+                    res = Err(crate::types::CausticsError::RowLimitExceeded {
+                        entity: entity_name.to_string(),
+                        limit: cap,
                     }
+                    .into());
                 }
             }
-        }
-
-        // Emit before hook
-        let entity_name = core::any::type_name::<Entity>();
-        crate::hooks::emit_before(&crate::hooks::QueryEvent {
-            builder: "ManyQueryBuilder",
-            entity: entity_name,
-            details: crate::hooks::compose_details("select_many", entity_name),
-        });
-        let start = std::time::Instant::now();
-        let res = if self.relations_to_fetch.is_empty() {
-            query.all(self.conn).await.map(|models| {
-                models
-                    .into_iter()
-                    .map(|model| ModelWithRelations::from_model(model))
-                    .collect()
-            })
-        } else {
-            self.exec_with_relations_with_query(query).await
-        };
-        // Emit after hook
-        match &res {
-            Ok(rows) => crate::hooks::emit_after(
-                &crate::hooks::QueryEvent {
-                    builder: "ManyQueryBuilder",
-                    entity: entity_name,
-                    details: crate::hooks::compose_details("select_many", entity_name),
-                },
-                &crate::hooks::QueryResultMeta {
-                    row_count: Some(rows.len()),
-                    error: None,
-                    elapsed_ms: Some(start.elapsed().as_millis()),
-                },
-            ),
-            Err(e) => crate::hooks::emit_after(
-                &crate::hooks::QueryEvent {
-                    builder: "ManyQueryBuilder",
-                    entity: entity_name,
-                    details: crate::hooks::compose_details("select_many", entity_name),
-                },
+            if let (Ok(rows), true) = (&mut res, self.reverse_order) {
+                rows.reverse();
+            }
+            crate::hooks::emit_after(
+                &event,
                 &crate::hooks::QueryResultMeta {
-                    row_count: None,
-                    error: Some(e.to_string()),
+                    row_count: res.as_ref().ok().map(Vec::len),
+                    error: res.as_ref().err().map(|e| e.to_string()),
                     elapsed_ms: Some(start.elapsed().as_millis()),
                 },
-            ),
+            );
+            res
+        })
+        .await
+    }
+
+    /// Execute with a limit of one and return the first row, if any
+    pub async fn first(self) -> Result<Option<ModelWithRelations>, sea_orm::DbErr> {
+        Ok(self.take(1).exec().await?.into_iter().next())
+    }
+
+    /// Execute as `take(-1)` and return the last row, if any.
+    ///
+    /// Errors when no `order_by` is set, since "last" would be ambiguous.
+    pub async fn last(self) -> Result<Option<ModelWithRelations>, sea_orm::DbErr> {
+        if self.pending_order_bys.is_empty() {
+            return Err(crate::types::CausticsError::QueryValidation {
+                message: "last() requires an order_by".to_string(),
+            }
+            .into());
         }
-        res
+        Ok(self.take(-1).exec().await?.into_iter().next())
     }
 
     /// Add a relation to fetch with the query
     pub fn with<T: Into<RelationFilter>>(mut self, relation: T) -> Self {
-        self.relations_to_fetch.push(relation.into());
+        self.relations_to_fetch.push(super::scoped_relation(&self.scope, relation.into()));
+        self
+    }
+
+    /// Fetch every relation declared on the entity, one level deep
+    pub fn with_all(mut self) -> Self {
+        super::push_all_relations::<ModelWithRelations>(&mut self.relations_to_fetch, &self.scope);
         self
     }
 
@@ -323,6 +615,7 @@ where
     async fn exec_with_relations_with_query(
         self,
         query: Select<Entity>,
+        shape: Option<u64>,
     ) -> Result<Vec<ModelWithRelations>, sea_orm::DbErr>
     where
         ModelWithRelations: FromModel<Entity::Model>,
@@ -331,9 +624,10 @@ where
             conn,
             relations_to_fetch,
             registry,
+            sql_comments,
             ..
         } = self;
-        let main_results = query.all(conn).await?;
+        let main_results = crate::statement_cache::all("ManyQueryBuilder", query, shape, conn, sql_comments).await?;
 
         let mut models_with_relations = Vec::new();
 
@@ -354,3 +648,174 @@ where
         Ok(models_with_relations)
     }
 }
+
+impl<'a, Entity: EntityTrait, ModelWithRelations>
+    ManyQueryBuilder<'a, DatabaseTransaction, Entity, ModelWithRelations>
+{
+    /// Lock the matched rows against concurrent writes until the transaction ends
+    /// (`SELECT ... FOR UPDATE`).
+    ///
+    /// Only transaction clients have this, since outside one the lock would be released as
+    /// soon as the statement finished. SQLite has no row locks and ignores it: its writers
+    /// already take the whole database.
+    pub fn for_update(mut self) -> Self {
+        self.query = self.query.lock_exclusive();
+        self.shape = crate::statement_cache::extend_shape(self.shape, "for_update");
+        self
+    }
+
+    /// Like `for_update`, but other transactions may still take shared locks on the rows
+    /// (`SELECT ... FOR SHARE`)
+    pub fn for_share(mut self) -> Self {
+        self.query = self.query.lock_shared();
+        self.shape = crate::statement_cache::extend_shape(self.shape, "for_share");
+        self
+    }
+}
+
+/// Statement-cache `shape` of a find-many query with the state `built_select` reads and the
+/// row cap folded in, shared with the `select` builder
+pub(crate) fn exec_shape<Entity: EntityTrait>(
+    shape: Option<u64>,
+    reverse_order: bool,
+    pending_order_bys: &[OrderByDescriptor],
+    cursor: Option<&[(SimpleExpr, sea_orm::Value)]>,
+    distinct_on_fields: Option<&[SimpleExpr]>,
+    distinct_on_columns: Option<&[<Entity as EntityTrait>::Column]>,
+    row_cap: Option<u64>,
+) -> Option<u64> {
+    let cursor: Option<Vec<_>> = cursor.map(|parts| {
+        parts
+            .iter()
+            .map(|(expr, value)| (format!("{:?}", expr), std::mem::discriminant(value)))
+            .collect()
+    });
+    let distinct_on_columns: Option<Vec<&str>> =
+        distinct_on_columns.map(|columns| columns.iter().map(sea_orm::IdenStatic::as_str).collect());
+    crate::statement_cache::extend_shape(
+        shape,
+        (
+            reverse_order,
+            format!("{:?}", pending_order_bys),
+            cursor,
+            format!("{:?}", distinct_on_fields),
+            distinct_on_columns,
+            row_cap.is_some(),
+        ),
+    )
+}
+
+/// Flattened `(expr, order)` list for effective ordering keys, NULL placement included
+pub(crate) fn effective_order_bys(order_bys: &[OrderByDescriptor]) -> Vec<(SimpleExpr, sea_orm::Order)> {
+    order_bys.iter().flat_map(|d| d.order_by_exprs()).collect()
+}
+
+/// Postgres' rule for `DISTINCT ON`: the leading `order_bys` must all be distinct fields
+pub(crate) fn check_distinct_on_order(
+    fields: Option<&[SimpleExpr]>,
+    order_bys: &[OrderByDescriptor],
+) -> Result<(), sea_orm::DbErr> {
+    let fields = fields.unwrap_or_default();
+    match order_bys.iter().take(fields.len()).position(|d| !fields.contains(&d.expr)) {
+        Some(index) => Err(crate::types::CausticsError::QueryValidation {
+            message: format!(
+                "distinct_on: order_by key {} is not one of the distinct fields; order by the distinct fields first",
+                index + 1
+            ),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Restrict `query` to the first row of each distinct `fields` combination.
+///
+/// "First" follows `order_bys` with the primary key as tie-breaker, so the kept row is
+/// deterministic. Postgres uses native `DISTINCT ON` when typed columns are available;
+/// other backends pick the row via `ROW_NUMBER() OVER (PARTITION BY ...)`.
+pub(crate) fn apply_distinct_on<Entity: EntityTrait>(
+    mut query: Select<Entity>,
+    backend: DatabaseBackend,
+    fields: &[SimpleExpr],
+    columns: Option<&[<Entity as EntityTrait>::Column]>,
+    order_bys: &[(SimpleExpr, sea_orm::Order)],
+) -> Select<Entity> {
+    use sea_orm::sea_query::{Alias, OverStatement, Query, WindowStatement};
+    use sea_orm::{IntoSimpleExpr, Iterable, PrimaryKeyToColumn};
+
+    let pk_exprs: Vec<SimpleExpr> = <Entity as EntityTrait>::PrimaryKey::iter()
+        .map(|pk| pk.into_column().into_simple_expr())
+        .collect();
+
+    // Same source and filters as the outer query, without projection, ordering or paging
+    let mut source = sea_orm::QueryTrait::query(&mut query).clone();
+    source.clear_selects().clear_order_by().reset_limit().reset_offset();
+
+    let candidates = match (backend, columns) {
+        (DatabaseBackend::Postgres, Some(cols)) => {
+            source.distinct_on(cols.to_vec());
+            for expr in &pk_exprs {
+                source.expr(expr.clone());
+            }
+            for f in fields {
+                source.order_by_expr(f.clone(), sea_orm::Order::Asc);
+            }
+            for (expr, order) in order_bys {
+                source.order_by_expr(expr.clone(), order.clone());
+            }
+            for expr in &pk_exprs {
+                source.order_by_expr(expr.clone(), sea_orm::Order::Asc);
+            }
+            source
+        }
+        _ => {
+            let mut window = WindowStatement::new();
+            for f in fields {
+                window.add_partition_by(f.clone());
+            }
+            for (expr, order) in order_bys {
+                window.order_by_expr(expr.clone(), order.clone());
+            }
+            for expr in &pk_exprs {
+                window.order_by_expr(expr.clone(), sea_orm::Order::Asc);
+            }
+            let pk_aliases: Vec<Alias> = (0..pk_exprs.len())
+                .map(|i| Alias::new(format!("caustics_pk{}", i)))
+                .collect();
+            for (expr, alias) in pk_exprs.iter().zip(&pk_aliases) {
+                source.expr_as(expr.clone(), alias.clone());
+            }
+            source.expr_window_as(Expr::cust("ROW_NUMBER()"), window, Alias::new("caustics_rn"));
+            Query::select()
+                .columns(pk_aliases)
+                .from_subquery(source, Alias::new("caustics_distinct"))
+                .and_where(Expr::col(Alias::new("caustics_rn")).eq(1))
+                .to_owned()
+        }
+    };
+
+    let key = if pk_exprs.len() == 1 {
+        Expr::expr(pk_exprs[0].clone())
+    } else {
+        Expr::tuple(pk_exprs)
+    };
+    query.filter(key.in_subquery(candidates))
+}
+
+impl<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> super::TimeoutExec
+    for ManyQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+{
+    type Output = Vec<ModelWithRelations>;
+
+    fn exec_future(
+        self,
+    ) -> impl std::future::Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        self.exec()
+    }
+}
@@ -0,0 +1,135 @@
+use futures_util::TryStreamExt;
+use sea_orm::{ConnectionTrait, EntityTrait, QuerySelect, StreamTrait};
+
+use super::ManyQueryBuilder;
+use crate::{FromModel, HasRelationMetadata};
+
+/// Per-row transform applied while the rows of a `find_many` are read; `None` drops the row
+type RowTransform<'a, M, T> = Box<dyn FnMut(M) -> Option<T> + Send + 'a>;
+
+/// A `find_many` whose rows pass through `map_rows`/`filter_rows` as they are read
+pub struct MapRowsQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations, T> {
+    inner: ManyQueryBuilder<'a, C, Entity, ModelWithRelations>,
+    transform: RowTransform<'a, ModelWithRelations, T>,
+}
+
+impl<'a, C, Entity, ModelWithRelations> ManyQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    C: ConnectionTrait + StreamTrait + Send,
+    Entity: EntityTrait,
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+{
+    /// Transform each row as it is read, e.g. `.map_rows(|user| user.email)`.
+    ///
+    /// Rows are streamed and mapped one at a time, so the models are never collected first.
+    pub fn map_rows<T, F>(self, mut f: F) -> MapRowsQueryBuilder<'a, C, Entity, ModelWithRelations, T>
+    where
+        F: FnMut(ModelWithRelations) -> T + Send + 'a,
+    {
+        MapRowsQueryBuilder {
+            inner: self,
+            transform: Box::new(move |row| Some(f(row))),
+        }
+    }
+
+    /// Keep only the rows for which `f` returns true, checked as each row is read
+    pub fn filter_rows<F>(
+        self,
+        mut f: F,
+    ) -> MapRowsQueryBuilder<'a, C, Entity, ModelWithRelations, ModelWithRelations>
+    where
+        F: FnMut(&ModelWithRelations) -> bool + Send + 'a,
+    {
+        MapRowsQueryBuilder {
+            inner: self,
+            transform: Box::new(move |row| f(&row).then_some(row)),
+        }
+    }
+}
+
+impl<'a, C, Entity, ModelWithRelations, T> MapRowsQueryBuilder<'a, C, Entity, ModelWithRelations, T>
+where
+    C: ConnectionTrait + StreamTrait + Send,
+    Entity: EntityTrait,
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+    T: 'a,
+{
+    /// Chain another transform after the previous ones
+    pub fn map_rows<U, F>(self, mut f: F) -> MapRowsQueryBuilder<'a, C, Entity, ModelWithRelations, U>
+    where
+        F: FnMut(T) -> U + Send + 'a,
+    {
+        let mut transform = self.transform;
+        MapRowsQueryBuilder {
+            inner: self.inner,
+            transform: Box::new(move |row| transform(row).map(&mut f)),
+        }
+    }
+
+    /// Drop the transformed rows for which `f` returns false
+    pub fn filter_rows<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(&T) -> bool + Send + 'a,
+    {
+        let mut transform = self.transform;
+        MapRowsQueryBuilder {
+            inner: self.inner,
+            transform: Box::new(move |row| transform(row).filter(|value| f(value))),
+        }
+    }
+
+    /// Run the query, transforming rows as they arrive.
+    ///
+    /// With `with`/`include` or a negative `take`, the rows are loaded as by `exec()` first,
+    /// since relations are fetched per batch and reversed rows only come back in order at the end.
+    pub async fn exec(self) -> Result<Vec<T>, sea_orm::DbErr> {
+        let MapRowsQueryBuilder { inner, mut transform } = self;
+        if !inner.relations_to_fetch.is_empty() || inner.reverse_order {
+            return Ok(inner.exec().await?.into_iter().filter_map(transform).collect());
+        }
+        inner.error_mapper.clone().run(async move {
+            if let Some(message) = inner.invalid_pagination {
+                return Err(crate::types::CausticsError::InvalidPagination { message }.into());
+            }
+            super::check_filter(&inner.invalid_filter)?;
+            if inner.distinct_on_ordered {
+                super::many::check_distinct_on_order(
+                    inner.distinct_on_fields.as_deref(),
+                    &inner.pending_order_bys,
+                )?;
+            }
+            let mut query = inner.built_select();
+            let row_cap = inner.max_rows.filter(|_| !inner.has_limit);
+            if let Some(cap) = row_cap {
+                query = query.limit(cap.saturating_add(1));
+            }
+
+            let mut rows = query.stream(inner.conn).await?;
+            let mut out = Vec::new();
+            let mut read = 0u64;
+            while let Some(model) = rows.try_next().await? {
+                read += 1;
+                if let Some(cap) = row_cap.filter(|cap| read > *cap) {
+                    return Err(crate::types::CausticsError::RowLimitExceeded {
+                        entity: core::any::type_name::<Entity>().to_string(),
+                        limit: cap,
+                    }
+                    .into());
+                }
+                if let Some(value) = transform(ModelWithRelations::from_model(model)) {
+                    out.push(value);
+                }
+            }
+            Ok(out)
+        })
+        .await
+    }
+}
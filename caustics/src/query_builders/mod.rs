@@ -4,41 +4,98 @@ pub mod connection_like;
 pub mod count;
 pub mod create;
 pub mod create_many;
+#[cfg(feature = "csv")]
+pub mod csv;
 pub mod deferred_lookup;
 pub mod delete;
 pub mod delete_many;
 pub mod first;
 pub mod group_by;
 pub mod has_many_set;
+pub mod index_by;
+pub mod insert_select;
 pub mod many;
+pub mod map_rows;
+pub mod order_by;
+pub mod paginator;
+pub mod profile;
 pub mod relation_fetcher;
+pub mod schema;
 pub mod select_first;
 pub mod select_many;
 pub mod select_unique;
+pub mod timeout;
 pub mod unique;
 pub mod update;
 pub mod update_many;
 pub mod upsert;
+pub mod upsert_many;
+pub mod window;
 
-pub use create::CreateQueryBuilder;
+pub use create::{CreateIgnoreQueryBuilder, CreateQueryBuilder, PrimaryKeyValue};
 pub use create_many::CreateManyQueryBuilder;
 pub use delete::DeleteQueryBuilder;
 pub use delete_many::DeleteManyQueryBuilder;
 pub use first::FirstQueryBuilder;
+pub use index_by::IndexByQueryBuilder;
+pub use insert_select::InsertSelectQueryBuilder;
 pub use many::ManyQueryBuilder;
+pub use map_rows::MapRowsQueryBuilder;
+pub use order_by::{case_position, OrderByDescriptor};
+pub use paginator::{ManyPaginator, RowCursor};
+pub use profile::{ColumnProfile, Profile, ProfileQueryBuilder};
+pub use schema::SchemaQueryBuilder;
 pub use unique::UniqueQueryBuilder;
 pub use update::UnifiedUpdateQueryBuilder;
 pub use update::UpdateQueryBuilder;
 pub use update_many::UpdateManyQueryBuilder;
 pub use upsert::UpsertQueryBuilder;
+pub use upsert_many::UpsertManyQueryBuilder;
+pub use window::{Window, WindowQueryBuilder};
 
 pub use aggregate::AggregateQueryBuilder;
-pub use batch::{BatchQuery, BatchResult};
-pub use count::CountQueryBuilder;
-pub use deferred_lookup::DeferredLookup;
-pub use group_by::GroupByQueryBuilder;
+pub use batch::{BatchQuery, BatchResult, MixedBatchContainer, MixedBatchElement};
+pub use count::{count_estimate, CountQueryBuilder};
+pub use deferred_lookup::{downcast_active_value, DeferredLookup};
+pub use group_by::{CountByQueryBuilder, GroupByQueryBuilder, ReadGroupKey};
 pub use has_many_set::{DefaultHasManySetHandler, HasManySetHandler, HasManySetUpdateQueryBuilder};
 pub use relation_fetcher::SeaOrmRelationFetcher;
 pub use select_first::SelectFirstQueryBuilder;
 pub use select_many::SelectManyQueryBuilder;
 pub use select_unique::SelectUniqueQueryBuilder;
+pub use timeout::{TimeoutExec, TimeoutQueryBuilder};
+
+/// `relation` restricted to the builder's row scope, so includes stay inside the tenant
+pub(crate) fn scoped_relation(
+    scope: &Option<crate::RowScope>,
+    mut relation: crate::RelationFilter,
+) -> crate::RelationFilter {
+    if let Some(scope) = scope {
+        scope.restrict(&mut relation);
+    }
+    relation
+}
+
+/// Add every relation declared on `Model` that is not fetched yet, scoped like `with()`
+pub(crate) fn push_all_relations<Model: crate::HasRelationMetadata<Model> + 'static>(
+    relations_to_fetch: &mut Vec<crate::RelationFilter>,
+    scope: &Option<crate::RowScope>,
+) {
+    for descriptor in Model::relation_descriptors() {
+        if !relations_to_fetch.iter().any(|r| r.relation == descriptor.name) {
+            let relation = crate::IncludeBuilderCore::new().build(descriptor.name);
+            relations_to_fetch.push(scoped_relation(scope, relation));
+        }
+    }
+}
+
+/// Fail with `QueryValidation` when the builder was given a filter its backend cannot run
+pub(crate) fn check_filter(invalid_filter: &Option<String>) -> Result<(), sea_orm::DbErr> {
+    match invalid_filter {
+        Some(message) => Err(crate::types::CausticsError::QueryValidation {
+            message: message.clone(),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,197 @@
+use crate::types::{IntoOrderSpec, NullsOrder, SortOrder};
+use sea_orm::sea_query::{Condition, Expr, SimpleExpr};
+use sea_orm::{EntityTrait, QueryOrder, QueryTrait, Select};
+
+/// A single ordering key that can be assembled at runtime (e.g. from API query params)
+/// and passed as part of a list to `order_by_many`
+#[derive(Clone, Debug)]
+pub struct OrderByDescriptor {
+    pub expr: SimpleExpr,
+    pub order: sea_orm::Order,
+    pub nulls: Option<NullsOrder>,
+}
+
+impl OrderByDescriptor {
+    pub fn new<E: sea_orm::IntoSimpleExpr>(expr: E, order: SortOrder) -> Self {
+        Self {
+            expr: expr.into_simple_expr(),
+            order: order.into(),
+            nulls: None,
+        }
+    }
+
+    /// Place NULLs first or last for this key
+    pub fn nulls(mut self, nulls: NullsOrder) -> Self {
+        self.nulls = Some(nulls);
+        self
+    }
+}
+
+impl<T: IntoOrderSpec> From<T> for OrderByDescriptor {
+    fn from(spec: T) -> Self {
+        let (expr, order, nulls) = spec.into_order_spec();
+        Self { expr, order, nulls }
+    }
+}
+
+impl OrderByDescriptor {
+    /// The same key with direction and NULL placement flipped, as used by `last` and negative `take`
+    pub(crate) fn reversed(&self) -> Self {
+        let order = match self.order {
+            sea_orm::Order::Asc => sea_orm::Order::Desc,
+            sea_orm::Order::Desc => sea_orm::Order::Asc,
+            ref other => other.clone(),
+        };
+        let nulls = self.nulls.map(|n| match n {
+            NullsOrder::First => NullsOrder::Last,
+            NullsOrder::Last => NullsOrder::First,
+        });
+        Self {
+            expr: self.expr.clone(),
+            order,
+            nulls,
+        }
+    }
+
+    /// `(expr, order)` pairs for ORDER BY, with an `expr IS NULL` key first when NULL placement is set
+    pub(crate) fn order_by_exprs(&self) -> Vec<(SimpleExpr, sea_orm::Order)> {
+        let mut out = Vec::with_capacity(2);
+        if let Some(n) = self.nulls {
+            let nulls_order = match n {
+                NullsOrder::First => sea_orm::Order::Desc,
+                NullsOrder::Last => sea_orm::Order::Asc,
+            };
+            out.push((Expr::expr(self.expr.clone()).is_null(), nulls_order));
+        }
+        out.push((self.expr.clone(), self.order.clone()));
+        out
+    }
+
+    /// Rows strictly after `cursor` on this key, honoring NULL placement
+    fn after(&self, cursor: &SimpleExpr) -> Condition {
+        let key = Expr::expr(self.expr.clone());
+        let beyond = match self.order {
+            sea_orm::Order::Desc => key.clone().lt(cursor.clone()),
+            _ => key.clone().gt(cursor.clone()),
+        };
+        match self.nulls {
+            None => Condition::all().add(beyond),
+            Some(NullsOrder::Last) => Condition::all()
+                .add(Expr::expr(cursor.clone()).is_not_null())
+                .add(Condition::any().add(beyond).add(key.is_null())),
+            Some(NullsOrder::First) => Condition::any().add(beyond).add(
+                Condition::all()
+                    .add(Expr::expr(cursor.clone()).is_null())
+                    .add(key.is_not_null()),
+            ),
+        }
+    }
+
+    /// Rows tied with `cursor` on this key; NULLs tie with each other when NULL placement is set
+    fn same(&self, cursor: &SimpleExpr) -> Condition {
+        let key = Expr::expr(self.expr.clone());
+        let equal = key.clone().eq(cursor.clone());
+        match self.nulls {
+            None => Condition::all().add(equal),
+            Some(_) => Condition::any().add(equal).add(
+                Condition::all()
+                    .add(key.is_null())
+                    .add(Expr::expr(cursor.clone()).is_null()),
+            ),
+        }
+    }
+}
+
+/// `CASE WHEN expr = v0 THEN 0 WHEN expr = v1 THEN 1 ... ELSE n END`: each row's position in
+/// `values`, with unlisted values (and NULL) after all listed ones
+pub fn case_position(expr: SimpleExpr, values: Vec<sea_orm::Value>) -> SimpleExpr {
+    let unlisted = values.len() as i64;
+    let mut positions = values.into_iter().enumerate();
+    let Some((_, first)) = positions.next() else {
+        return Expr::value(0i64);
+    };
+    let mut case = Expr::case(Expr::expr(expr.clone()).eq(first), 0i64);
+    for (position, value) in positions {
+        case = case.case(Expr::expr(expr.clone()).eq(value), position as i64);
+    }
+    case.finally(unlisted).into()
+}
+
+/// Effective ordering keys with `reverse_order` applied
+pub(crate) fn effective_descriptors(
+    order_bys: &[OrderByDescriptor],
+    reverse_order: bool,
+) -> Vec<OrderByDescriptor> {
+    order_bys
+        .iter()
+        .map(|d| if reverse_order { d.reversed() } else { d.clone() })
+        .collect()
+}
+
+/// Apply ordering keys (already reversed if needed) to `query`
+pub(crate) fn apply_order_bys<Entity: EntityTrait>(
+    mut query: Select<Entity>,
+    order_bys: &[OrderByDescriptor],
+) -> Select<Entity> {
+    for descriptor in order_bys {
+        for (expr, order) in descriptor.order_by_exprs() {
+            query = query.order_by(expr, order);
+        }
+    }
+    query
+}
+
+/// Keyset filter and full ordering for a cursor.
+///
+/// The cursor columns are appended as tie-breakers after `order_bys` (ascending, or
+/// descending when reversed). Keys that are not cursor columns are compared against the
+/// cursor row's value via a scalar subquery, so ordering by any column stays exact,
+/// including across the NULL boundary of nullable keys.
+pub(crate) fn cursor_keyset<Entity: EntityTrait>(
+    order_bys: &[OrderByDescriptor],
+    cursor_parts: &[(SimpleExpr, sea_orm::Value)],
+    reverse_order: bool,
+) -> (Condition, Vec<OrderByDescriptor>) {
+    let mut keys = effective_descriptors(order_bys, reverse_order);
+    let tie_order = if reverse_order {
+        sea_orm::Order::Desc
+    } else {
+        sea_orm::Order::Asc
+    };
+    for (expr, _) in cursor_parts {
+        if !keys.iter().any(|k| &k.expr == expr) {
+            keys.push(OrderByDescriptor {
+                expr: expr.clone(),
+                order: tie_order.clone(),
+                nulls: None,
+            });
+        }
+    }
+
+    let cursor_values: Vec<SimpleExpr> = keys
+        .iter()
+        .map(|key| match cursor_parts.iter().find(|(expr, _)| expr == &key.expr) {
+            Some((_, value)) => SimpleExpr::Value(value.clone()),
+            None => {
+                let mut row = Entity::find().into_query();
+                row.clear_selects().expr(key.expr.clone());
+                for (expr, value) in cursor_parts {
+                    row.and_where(Expr::expr(expr.clone()).eq(value.clone()));
+                }
+                SimpleExpr::SubQuery(None, Box::new(row.into_sub_query_statement()))
+            }
+        })
+        .collect();
+
+    // (k1 after c1) OR (k1 = c1 AND k2 after c2) OR ...
+    let mut disjunction = Condition::any();
+    for i in 0..keys.len() {
+        let mut conjunction = Condition::all();
+        for j in 0..i {
+            conjunction = conjunction.add(keys[j].same(&cursor_values[j]));
+        }
+        conjunction = conjunction.add(keys[i].after(&cursor_values[i]));
+        disjunction = disjunction.add(conjunction);
+    }
+    (disjunction, keys)
+}
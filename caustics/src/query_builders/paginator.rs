@@ -0,0 +1,95 @@
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::{ConnectionTrait, EntityTrait};
+
+use super::{ManyQueryBuilder, OrderByDescriptor};
+use crate::{FromModel, HasRelationMetadata};
+
+/// Cursor value for a row: one `(column, value)` pair per primary key column
+pub type RowCursor = Vec<(SimpleExpr, sea_orm::Value)>;
+
+/// Walks a `find_many` query page by page, using the last row of each page as the
+/// cursor for the next one.
+///
+/// Any ordering works: the primary key is appended as a tie-breaker, so rows with equal
+/// (or NULL) sort keys are neither skipped nor repeated across pages.
+pub struct ManyPaginator<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> {
+    builder: ManyQueryBuilder<'a, C, Entity, ModelWithRelations>,
+    page_size: i64,
+    cursor_of: fn(&ModelWithRelations) -> RowCursor,
+    cursor: Option<RowCursor>,
+    exhausted: bool,
+}
+
+impl<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations>
+    ManyPaginator<'a, C, Entity, ModelWithRelations>
+where
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+{
+    /// Internal constructor used by generated code, which knows how to read a row's cursor
+    pub fn new(
+        builder: ManyQueryBuilder<'a, C, Entity, ModelWithRelations>,
+        page_size: i64,
+        cursor_of: fn(&ModelWithRelations) -> RowCursor,
+    ) -> Self {
+        Self {
+            builder,
+            page_size,
+            cursor_of,
+            cursor: None,
+            exhausted: false,
+        }
+    }
+
+    /// Fetch the next page, or `None` once every row has been returned
+    pub async fn next_page(&mut self) -> Result<Option<Vec<ModelWithRelations>>, sea_orm::DbErr> {
+        if self.page_size <= 0 {
+            return Err(crate::types::CausticsError::QueryValidation {
+                message: "page_size must be > 0".to_string(),
+            }
+            .into());
+        }
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let mut page = self.builder.clone_for_page();
+        match &self.cursor {
+            Some(parts) => page.cursor = Some(parts.clone()),
+            // Later pages break ties on the primary key, so the first page must too
+            None => {
+                for expr in primary_key_exprs::<Entity>() {
+                    if !page.pending_order_bys.iter().any(|d| d.expr == expr) {
+                        page.pending_order_bys
+                            .push(OrderByDescriptor::new(expr, crate::SortOrder::Asc));
+                    }
+                }
+            }
+        }
+        let rows = page.take(self.page_size).exec().await?;
+
+        if (rows.len() as i64) < self.page_size {
+            self.exhausted = true;
+        }
+        match rows.last() {
+            Some(last) => {
+                self.cursor = Some((self.cursor_of)(last));
+                Ok(Some(rows))
+            }
+            None => {
+                self.exhausted = true;
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn primary_key_exprs<Entity: EntityTrait>() -> Vec<SimpleExpr> {
+    use sea_orm::{IntoSimpleExpr, Iterable, PrimaryKeyToColumn};
+    <Entity as EntityTrait>::PrimaryKey::iter()
+        .map(|pk| pk.into_column().into_simple_expr())
+        .collect()
+}
@@ -0,0 +1,108 @@
+use sea_orm::sea_query::{Expr, Func, SimpleExpr};
+use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, IntoSimpleExpr, QueryFilter, QuerySelect, QueryTrait};
+
+/// Summary of one numeric column, as returned by `profile()`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ColumnProfile {
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub avg: Option<f64>,
+    pub null_count: i64,
+}
+
+/// Result of `profile()`: the row count and a summary of each numeric field
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Profile {
+    /// Number of rows profiled
+    pub count: i64,
+    /// Stats per numeric field, keyed by field name
+    pub columns: std::collections::BTreeMap<String, ColumnProfile>,
+}
+
+/// Min/max/avg and NULL count of every numeric column, computed in a single query
+pub struct ProfileQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait> {
+    pub condition: sea_orm::sea_query::Condition,
+    pub conn: &'a C,
+    /// Field name and column of each numeric field
+    pub columns: Vec<(&'static str, Entity::Column)>,
+    pub error_mapper: crate::ErrorMapper,
+}
+
+impl<'a, C, Entity> ProfileQueryBuilder<'a, C, Entity>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+{
+    /// Stop waiting for the query after `timeout`; the deadline is client-side only, see
+    /// [`TimeoutQueryBuilder`](super::TimeoutQueryBuilder)
+    pub fn timeout(self, timeout: std::time::Duration) -> super::TimeoutQueryBuilder<Self> {
+        super::TimeoutQueryBuilder {
+            inner: self,
+            timeout,
+        }
+    }
+
+    fn build(&self) -> sea_orm::Statement {
+        let db_backend = self.conn.get_database_backend();
+        let mut select = Entity::find()
+            .filter(self.condition.clone())
+            .select_only()
+            .expr_as(Expr::cust("COUNT(*)"), "count");
+        for (idx, (_, column)) in self.columns.iter().enumerate() {
+            let expr = column.into_simple_expr();
+            // AVG is NUMERIC/DECIMAL on Postgres and MySQL; read it back as a float everywhere
+            let avg = SimpleExpr::FunctionCall(Func::avg(expr.clone()));
+            let avg = match db_backend {
+                DatabaseBackend::Postgres => avg.cast_as(sea_orm::sea_query::Alias::new("DOUBLE PRECISION")),
+                DatabaseBackend::MySql => avg.cast_as(sea_orm::sea_query::Alias::new("DOUBLE")),
+                DatabaseBackend::Sqlite => avg,
+            };
+            select = select
+                .expr_as(SimpleExpr::FunctionCall(Func::min(expr.clone())), format!("min_{}", idx))
+                .expr_as(SimpleExpr::FunctionCall(Func::max(expr.clone())), format!("max_{}", idx))
+                .expr_as(avg, format!("avg_{}", idx))
+                .expr_as(
+                    Expr::cust("COUNT(*)").sub(SimpleExpr::FunctionCall(Func::count(expr))),
+                    format!("nulls_{}", idx),
+                );
+        }
+        select.build(db_backend)
+    }
+
+    pub async fn exec(self) -> Result<Profile, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            let mut profile = Profile::default();
+            let Some(row) = self.conn.query_one(self.build()).await? else {
+                return Ok(profile);
+            };
+            profile.count = row.try_get::<i64>("", "count")?;
+            for (idx, (name, _)) in self.columns.iter().enumerate() {
+                profile.columns.insert(
+                    (*name).to_string(),
+                    ColumnProfile {
+                        min: crate::extract_db_value_as_string(&row, &format!("min_{}", idx)),
+                        max: crate::extract_db_value_as_string(&row, &format!("max_{}", idx)),
+                        avg: row.try_get::<Option<f64>>("", &format!("avg_{}", idx))?,
+                        null_count: row.try_get::<i64>("", &format!("nulls_{}", idx))?,
+                    },
+                );
+            }
+            Ok(profile)
+        })
+        .await
+    }
+}
+
+impl<'a, C, Entity> super::TimeoutExec for ProfileQueryBuilder<'a, C, Entity>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+{
+    type Output = Profile;
+
+    fn exec_future(
+        self,
+    ) -> impl std::future::Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        self.exec()
+    }
+}
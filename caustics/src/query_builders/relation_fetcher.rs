@@ -58,7 +58,9 @@ where
                             order_by: vec![],
                             cursor_id: None,
                             include_count: false,
+                            count_filters: vec![],
                             distinct: false,
+                            scope: None,
                         },
                     )
                     .await?;
@@ -0,0 +1,88 @@
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, DatabaseTransaction, EntityTrait, Statement, TransactionTrait,
+};
+
+use super::ManyQueryBuilder;
+use crate::types::{ApplyNestedIncludes, RegistryProvider};
+use crate::{FromModel, HasRelationMetadata};
+
+/// Runs a `find_many` with Postgres' `search_path` set to `schema`, so unqualified table
+/// names resolve there, e.g. one schema per tenant.
+///
+/// The query runs in its own transaction that starts with `SET LOCAL search_path`, so the
+/// setting never leaks to other queries on the pooled connection. Includes are fetched in
+/// the same transaction and read from the same schema. Inside a client transaction that
+/// transaction is only a savepoint, whose release keeps `SET LOCAL`, so the previous
+/// `search_path` is put back before it is released.
+pub struct SchemaQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> {
+    pub inner: ManyQueryBuilder<'a, C, Entity, ModelWithRelations>,
+    pub schema: String,
+}
+
+impl<'a, C, Entity, ModelWithRelations> SchemaQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    C: ConnectionTrait + TransactionTrait,
+    Entity: EntityTrait,
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + ApplyNestedIncludes<C>
+        + ApplyNestedIncludes<DatabaseTransaction>
+        + RegistryProvider<DatabaseTransaction>
+        + Send
+        + 'static,
+{
+    pub async fn exec(self) -> Result<Vec<ModelWithRelations>, sea_orm::DbErr> {
+        check_schema_name(&self.schema)?;
+        if self.inner.database_backend != DatabaseBackend::Postgres {
+            return Err(crate::types::CausticsError::QueryValidation {
+                message: "with_schema requires Postgres".to_string(),
+            }
+            .into());
+        }
+
+        let error_mapper = self.inner.error_mapper.clone();
+        let txn = error_mapper.run(self.inner.conn.begin()).await?;
+        let previous = error_mapper
+            .run(txn.query_one(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "SELECT current_setting('search_path') AS search_path",
+            )))
+            .await?
+            .map(|row| row.try_get::<String>("", "search_path"))
+            .transpose()?
+            .unwrap_or_default();
+        error_mapper
+            .run(txn.execute_unprepared(&format!(
+                "SET LOCAL search_path TO {}",
+                crate::raw::ident(&self.schema)
+            )))
+            .await?;
+        let rows = self.inner.exec_on(&txn).await?;
+        error_mapper
+            .run(txn.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "SELECT set_config('search_path', $1, true)",
+                [previous.into()],
+            )))
+            .await?;
+        error_mapper.run(txn.commit()).await?;
+        Ok(rows)
+    }
+}
+
+/// Only plain identifiers are accepted: a letter or `_`, then letters, digits, `_` or `$`,
+/// within Postgres' 63-byte limit
+pub(crate) fn check_schema_name(schema: &str) -> Result<(), sea_orm::DbErr> {
+    let mut chars = schema.chars();
+    let valid = schema.len() <= 63
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if valid {
+        Ok(())
+    } else {
+        Err(crate::types::CausticsError::QueryValidation {
+            message: format!("invalid schema name '{}'", schema),
+        }
+        .into())
+    }
+}
@@ -1,8 +1,10 @@
 use crate::types::{EntityRegistry, SelectionSpec};
-use crate::types::{IntoOrderSpec, NullsOrder};
+use crate::query_builders::order_by::apply_order_bys;
+use crate::query_builders::OrderByDescriptor;
+use crate::types::IntoOrderSpec;
 use crate::{EntitySelection, HasRelationMetadata, RelationFilter};
-use sea_orm::sea_query::{Expr, SimpleExpr};
-use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, QueryOrder, QuerySelect, QueryTrait, Select};
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, QuerySelect, QueryTrait, Select};
 
 /// Query builder for selected scalar fields on first
 pub struct SelectFirstQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, Selected>
@@ -16,8 +18,12 @@ where
     pub relations_to_fetch: Vec<RelationFilter>,
     pub registry: &'a dyn EntityRegistry<C>,
     pub database_backend: DatabaseBackend,
-    pub pending_order_bys: Vec<(SimpleExpr, sea_orm::Order)>,
-    pub pending_nulls: Option<NullsOrder>,
+    pub pending_order_bys: Vec<OrderByDescriptor>,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<Selected>,
 }
 
@@ -37,11 +43,7 @@ where
     where
         T: IntoOrderSpec,
     {
-        let (expr, order, nulls) = order_spec.into_order_spec();
-        self.pending_order_bys.push((expr, order));
-        if nulls.is_some() {
-            self.pending_nulls = nulls;
-        }
+        self.pending_order_bys.push(order_spec.into());
         self
     }
 
@@ -50,7 +52,7 @@ where
     where
         T: From<Selected>,
     {
-        match self.exec_internal().await? {
+        match self.error_mapper.clone().run(self.exec_internal()).await? {
             Some(selected) => Ok(Some(T::from(selected))),
             None => Ok(None),
         }
@@ -58,25 +60,11 @@ where
 
     /// Internal implementation for exec
     async fn exec_internal(self) -> Result<Option<Selected>, sea_orm::DbErr> {
+        super::check_filter(&self.invalid_filter)?;
         // Ensure required key columns for any requested relations are added implicitly via Selected::column_for_alias
         let mut query = self.query.clone();
         // Apply ordering if provided
-        if let Some(n) = self.pending_nulls {
-            if let Some((first_expr, _)) = self.pending_order_bys.first() {
-                let nulls_expr = Expr::expr(first_expr.clone()).is_null();
-                match n {
-                    NullsOrder::First => {
-                        query = query.order_by(nulls_expr, sea_orm::Order::Desc);
-                    }
-                    NullsOrder::Last => {
-                        query = query.order_by(nulls_expr, sea_orm::Order::Asc);
-                    }
-                }
-            }
-        }
-        for (expr, order) in &self.pending_order_bys {
-            query = query.order_by(expr.clone(), order.clone());
-        }
+        query = apply_order_bys(query, &self.pending_order_bys);
         let mut selected = self.selected_fields.clone();
         let mut defensive_fields = Vec::new();
 
@@ -118,21 +106,25 @@ where
             }
         }
 
+        let mut requested_aliases = self.requested_aliases.clone();
+        crate::types::retain_primary_key::<Selected>(&mut selected, &mut requested_aliases);
+
         let mut select = query.select_only();
         for (expr, alias) in &selected {
             select = select.expr_as(expr.clone(), alias.as_str());
         }
         let stmt = select.build(self.database_backend);
         let entity_name = core::any::type_name::<Entity>();
+        let details = crate::hooks::compose_details_with_filter("select_first", entity_name, &stmt.sql);
         crate::hooks::emit_before(&crate::hooks::QueryEvent {
             builder: "SelectFirstQueryBuilder",
             entity: entity_name,
-            details: crate::hooks::compose_details("select_first", entity_name),
+            details: details.clone(),
         });
         let start = std::time::Instant::now();
         if let Some(row) = self.conn.query_one(stmt).await? {
             let field_names: Vec<&str> =
-                self.requested_aliases.iter().map(|a| a.as_str()).collect();
+                requested_aliases.iter().map(|a| a.as_str()).collect();
             let mut s = Selected::fill_from_row(&row, &field_names);
 
             for rf in &self.relations_to_fetch {
@@ -171,7 +163,7 @@ where
                 &crate::hooks::QueryEvent {
                     builder: "SelectFirstQueryBuilder",
                     entity: entity_name,
-                    details: crate::hooks::compose_details("select_first", entity_name),
+                    details: details.clone(),
                 },
                 &crate::hooks::QueryResultMeta {
                     row_count: Some(1),
@@ -185,7 +177,7 @@ where
                 &crate::hooks::QueryEvent {
                     builder: "SelectFirstQueryBuilder",
                     entity: entity_name,
-                    details: crate::hooks::compose_details("select_first", entity_name),
+                    details: details.clone(),
                 },
                 &crate::hooks::QueryResultMeta {
                     row_count: Some(0),
@@ -209,7 +201,7 @@ where
     }
 
     pub fn with<T: Into<RelationFilter>>(mut self, relation: T) -> Self {
-        self.relations_to_fetch.push(relation.into());
+        self.relations_to_fetch.push(super::scoped_relation(&self.scope, relation.into()));
         self
     }
 }
@@ -232,7 +224,9 @@ where
             registry: src.registry,
             database_backend: src.database_backend,
             pending_order_bys: src.pending_order_bys,
-            pending_nulls: src.pending_nulls,
+            invalid_filter: src.invalid_filter,
+            scope: src.scope,
+            error_mapper: src.error_mapper,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -266,7 +260,9 @@ where
             registry: self.registry,
             database_backend: self.database_backend,
             pending_order_bys: self.pending_order_bys,
-            pending_nulls: self.pending_nulls,
+            invalid_filter: self.invalid_filter,
+            scope: self.scope,
+            error_mapper: self.error_mapper,
             _phantom: std::marker::PhantomData::<S::Data>,
         }
     }
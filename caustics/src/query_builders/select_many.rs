@@ -1,9 +1,11 @@
 use crate::types::SelectionSpec;
-use crate::types::{ApplyNestedIncludes, EntityRegistry, IntoOrderSpec, NullsOrder};
+use crate::query_builders::order_by::{apply_order_bys, cursor_keyset, effective_descriptors};
+use crate::query_builders::OrderByDescriptor;
+use crate::types::{ApplyNestedIncludes, EntityRegistry, IntoOrderSpec};
 use crate::{EntitySelection, HasRelationMetadata, RelationFilter};
-use sea_orm::sea_query::{Condition, Expr, SimpleExpr};
+use sea_orm::sea_query::SimpleExpr;
 use sea_orm::{
-    ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QuerySelect,
     QueryTrait, Select,
 };
 
@@ -20,13 +22,29 @@ where
     pub registry: &'a (dyn EntityRegistry<C> + Sync),
     pub database_backend: DatabaseBackend,
     pub reverse_order: bool,
-    pub pending_order_bys: Vec<(SimpleExpr, sea_orm::Order)>,
-    pub pending_nulls: Option<NullsOrder>,
+    pub pending_order_bys: Vec<OrderByDescriptor>,
     pub cursor: Option<Vec<(SimpleExpr, sea_orm::Value)>>,
     pub is_distinct: bool,
     pub distinct_on_fields: Option<Vec<SimpleExpr>>,
     pub distinct_on_columns: Option<Vec<<Entity as EntityTrait>::Column>>,
-    pub skip_is_negative: bool,
+    /// Set by `distinct_on`: any `order_by` must start with the distinct fields
+    pub distinct_on_ordered: bool,
+    /// Rejected `take`/`skip` input, reported when the query runs
+    pub invalid_pagination: Option<String>,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    /// Client-wide row cap for queries without an explicit `take`
+    pub max_rows: Option<u64>,
+    /// Whether `take`/`last` set an explicit limit
+    pub has_limit: bool,
+    /// Value-free fingerprint of how the query was built, keying the statement cache;
+    /// `None` keeps it out of the cache, e.g. after editing `query` directly
+    pub shape: Option<u64>,
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<Selected>,
 }
 
@@ -48,11 +66,49 @@ where
     where
         T: IntoOrderSpec,
     {
-        let (expr, order, nulls) = order_spec.into_order_spec();
-        self.pending_order_bys.push((expr, order));
-        if nulls.is_some() {
-            self.pending_nulls = nulls;
-        }
+        self.pending_order_bys.push(order_spec.into());
+        self
+    }
+
+    /// Order the selection by a raw SQL fragment, e.g. `"LOWER(name) ASC"`.
+    ///
+    /// The fragment is appended verbatim (not parameterized), so it must be trusted input.
+    /// It composes with `order_by` in call order.
+    pub fn order_by_raw(self, fragment: impl Into<String>) -> Self {
+        self.order_by(crate::types::RawOrderBy(fragment.into()))
+    }
+
+    /// Order the selection by a numeric value inside a JSON column, e.g.
+    /// `order_by_json(post::Column::CustomData, vec!["view_count"], SortOrder::Desc)`.
+    ///
+    /// The value at `path` is extracted and cast to a number per backend (see
+    /// `atomic::json_path_number`), so `10` sorts after `9`. Composes with `order_by` in call order.
+    pub fn order_by_json<P: Into<String>>(
+        mut self,
+        column: <Entity as EntityTrait>::Column,
+        path: impl IntoIterator<Item = P>,
+        order: crate::SortOrder,
+    ) -> Self {
+        let path: Vec<String> = path.into_iter().map(Into::into).collect();
+        let expr = crate::atomic::json_path_number(
+            self.database_backend,
+            sea_orm::IdenStatic::as_str(&column),
+            &path,
+        );
+        self.pending_order_bys.push(OrderByDescriptor::new(expr, order));
+        self
+    }
+
+    /// Order the selection by several keys at once, in list order.
+    ///
+    /// Accepts anything convertible into an `OrderByDescriptor`, so a sort spec can be
+    /// built at runtime, e.g. `vec![(user::Column::Name, SortOrder::Asc), (user::Column::Age, SortOrder::Desc)]`.
+    pub fn order_by_many<I>(mut self, specs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<crate::query_builders::OrderByDescriptor>,
+    {
+        self.pending_order_bys.extend(specs.into_iter().map(Into::into));
         self
     }
 
@@ -61,118 +117,61 @@ where
     where
         T: From<Selected>,
     {
-        let results = self.exec_internal().await?;
+        let results = self.error_mapper.clone().run(self.exec_internal()).await?;
         Ok(results.into_iter().map(T::from).collect())
     }
 
-    /// Internal implementation for exec
-    async fn exec_internal(self) -> Result<Vec<Selected>, sea_orm::DbErr> {
-        if self.skip_is_negative {
-            return Err(crate::types::CausticsError::QueryValidation {
-                message: "skip must be >= 0".to_string(),
-            }
-            .into());
-        }
+    /// The filtered, ordered query before the selected columns are added
+    fn built_select(&self) -> Select<Entity> {
         let mut query = self.query.clone();
 
-        // Apply cursor filtering if provided (copied from ManyQueryBuilder)
-        if let Some(cursor_parts) = &self.cursor {
-            let first_order = self
-                .pending_order_bys
-                .first()
-                .map(|(_, ord)| ord.clone())
-                .unwrap_or(sea_orm::Order::Asc);
-            let effective_order = if self.reverse_order {
-                match first_order {
-                    sea_orm::Order::Asc => sea_orm::Order::Desc,
-                    sea_orm::Order::Desc => sea_orm::Order::Asc,
-                    other => other,
-                }
-            } else {
-                first_order
-            };
-            if !cursor_parts.is_empty() {
-                let mut disjunction = Condition::any();
-                for i in 0..cursor_parts.len() {
-                    let mut conjunction = Condition::all();
-                    for j in 0..i {
-                        let (expr_eq, val_eq) = &cursor_parts[j];
-                        conjunction = conjunction.add(Expr::expr(expr_eq.clone()).eq(val_eq.clone()));
-                    }
-                    let (expr_cmp, val_cmp) = &cursor_parts[i];
-                    let cmp = match effective_order {
-                        sea_orm::Order::Asc => Expr::expr(expr_cmp.clone()).gt(val_cmp.clone()),
-                        sea_orm::Order::Desc => Expr::expr(expr_cmp.clone()).lt(val_cmp.clone()),
-                        _ => Expr::expr(expr_cmp.clone()).gt(val_cmp.clone()),
-                    };
-                    conjunction = conjunction.add(cmp);
-                    disjunction = disjunction.add(conjunction);
-                }
-                query = query.filter(disjunction);
-            }
-            if self.pending_order_bys.is_empty() {
-                let ord = if self.reverse_order { sea_orm::Order::Desc } else { sea_orm::Order::Asc };
-                for (expr, _) in cursor_parts.iter() {
-                    query = query.order_by(expr.clone(), ord.clone());
-                }
-            }
-        }
-
-        // Apply orderings
-        if !self.pending_order_bys.is_empty() {
-            if let Some(n) = self.pending_nulls {
-                if let Some((first_expr, _)) = self.pending_order_bys.first() {
-                    let nulls_expr = Expr::expr(first_expr.clone()).is_null();
-                    match n {
-                        NullsOrder::First => {
-                            query = query.order_by(nulls_expr, sea_orm::Order::Desc);
-                        }
-                        NullsOrder::Last => {
-                            query = query.order_by(nulls_expr, sea_orm::Order::Asc);
-                        }
-                    }
-                }
-            }
-            for (expr, order) in &self.pending_order_bys {
-                let effective = if self.reverse_order {
-                    match order {
-                        sea_orm::Order::Asc => sea_orm::Order::Desc,
-                        sea_orm::Order::Desc => sea_orm::Order::Asc,
-                        other => other.clone(),
-                    }
-                } else {
-                    order.clone()
-                };
-                query = query.order_by(expr.clone(), effective);
+        // Apply cursor filtering and orderings (same as ManyQueryBuilder)
+        let order_bys = match self.cursor.as_deref() {
+            Some(cursor_parts) if !cursor_parts.is_empty() => {
+                let (condition, keys) = cursor_keyset::<Entity>(
+                    &self.pending_order_bys,
+                    cursor_parts,
+                    self.reverse_order,
+                );
+                query = query.filter(condition);
+                keys
             }
-        }
+            _ => effective_descriptors(&self.pending_order_bys, self.reverse_order),
+        };
+        query = apply_order_bys(query, &order_bys);
 
-        // Apply per-field distinct:
-        // - Postgres: use native DISTINCT ON if available
-        // - Others: emulate via GROUP BY
+        // Apply per-field distinct, keeping the first row per group
         if let Some(fields) = &self.distinct_on_fields {
             if !fields.is_empty() {
-                match self.database_backend {
-                    DatabaseBackend::Postgres => {
-                        if let Some(cols) = &self.distinct_on_columns {
-                            sea_orm::QueryTrait::query(&mut query).distinct_on(cols.clone());
-                        } else {
-                            for f in fields {
-                                sea_orm::QueryTrait::query(&mut query)
-                                    .add_group_by(std::iter::once(f.clone()));
-                            }
-                        }
-                    }
-                    _ => {
-                        for f in fields {
-                            sea_orm::QueryTrait::query(&mut query)
-                                .add_group_by(std::iter::once(f.clone()));
-                        }
-                    }
-                }
+                query = super::many::apply_distinct_on(
+                    query,
+                    self.database_backend,
+                    fields,
+                    self.distinct_on_columns.as_deref(),
+                    &super::many::effective_order_bys(&order_bys),
+                );
             }
         }
 
+        query
+    }
+
+    /// Internal implementation for exec
+    async fn exec_internal(self) -> Result<Vec<Selected>, sea_orm::DbErr> {
+        if let Some(message) = self.invalid_pagination {
+            return Err(crate::types::CausticsError::InvalidPagination { message }.into());
+        }
+        super::check_filter(&self.invalid_filter)?;
+        if self.distinct_on_ordered {
+            super::many::check_distinct_on_order(self.distinct_on_fields.as_deref(), &self.pending_order_bys)?;
+        }
+        let mut query = self.built_select();
+        // Without an explicit take, fetch one row past the cap so exceeding it can be detected
+        let row_cap = self.max_rows.filter(|_| !self.has_limit);
+        if let Some(cap) = row_cap {
+            query = query.limit(cap.saturating_add(1));
+        }
+
         // Ensure required key columns for any requested relations are added implicitly by resolving alias to expr via Selected
         let mut selected = self.selected_fields.clone();
         let mut defensive_fields = Vec::new();
@@ -221,27 +220,61 @@ where
             // Debug logging can be enabled here if needed
         }
 
+        let mut requested_aliases = self.requested_aliases.clone();
+        // A plain DISTINCT compares every selected column, so an added key would make each row
+        // distinct; `DISTINCT ON` picks its rows by the distinct fields and is unaffected
+        if !self.is_distinct || self.distinct_on_fields.is_some() {
+            crate::types::retain_primary_key::<Selected>(&mut selected, &mut requested_aliases);
+        }
+
         let mut select = query.select_only();
         for (expr, alias) in &selected {
             select = select.expr_as(expr.clone(), alias.as_str());
         }
 
-        let stmt = select.build(self.database_backend);
+        // The selected columns (and keys added for includes) are part of the statement's shape
+        let shape = crate::statement_cache::extend_shape(
+            super::many::exec_shape::<Entity>(
+                self.shape,
+                self.reverse_order,
+                &self.pending_order_bys,
+                self.cursor.as_deref(),
+                self.distinct_on_fields.as_deref(),
+                self.distinct_on_columns.as_deref(),
+                row_cap,
+            ),
+            format!("{:?}", selected),
+        );
+        let stmt = crate::statement_cache::build::<Entity>(
+            "SelectManyQueryBuilder",
+            self.database_backend,
+            QueryTrait::query(&mut select),
+            shape,
+        );
         let entity_name = core::any::type_name::<Entity>();
+        let details = crate::hooks::compose_details_with_filter("select_many", entity_name, &stmt.sql);
         crate::hooks::emit_before(&crate::hooks::QueryEvent {
             builder: "SelectManyQueryBuilder",
             entity: entity_name,
-            details: crate::hooks::compose_details("select_many", entity_name),
+            details: details.clone(),
         });
         let start = std::time::Instant::now();
-        let rows_res = self.conn.query_all(stmt).await;
+        let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+        let rows_res = conn.query_all(stmt).await.and_then(|rows| match row_cap {
+            Some(cap) if rows.len() as u64 > cap => Err(crate::types::CausticsError::RowLimitExceeded {
+                entity: entity_name.to_string(),
+                limit: cap,
+            }
+            .into()),
+            _ => Ok(rows),
+        });
         match rows_res {
             Ok(rows) => {
                 crate::hooks::emit_after(
                     &crate::hooks::QueryEvent {
                         builder: "SelectManyQueryBuilder",
                         entity: entity_name,
-                        details: crate::hooks::compose_details("select_many", entity_name),
+                        details: details.clone(),
                     },
                     &crate::hooks::QueryResultMeta {
                         row_count: Some(rows.len()),
@@ -251,7 +284,7 @@ where
                 );
                 let mut out: Vec<Selected> = Vec::with_capacity(rows.len());
                 let field_names: Vec<&str> =
-                    self.requested_aliases.iter().map(|a| a.as_str()).collect();
+                    requested_aliases.iter().map(|a| a.as_str()).collect();
 
                 for row in rows.into_iter() {
                     let mut s = Selected::fill_from_row(&row, &field_names);
@@ -282,6 +315,10 @@ where
                     // clear_unselected no longer needed - fields are only populated if selected
                     out.push(s);
                 }
+                // A negative take fetched the rows in reversed order
+                if self.reverse_order {
+                    out.reverse();
+                }
                 Ok(out)
             }
             Err(e) => {
@@ -289,7 +326,7 @@ where
                     &crate::hooks::QueryEvent {
                         builder: "SelectManyQueryBuilder",
                         entity: entity_name,
-                        details: crate::hooks::compose_details("select_many", entity_name),
+                        details: details.clone(),
                     },
                     &crate::hooks::QueryResultMeta {
                         row_count: None,
@@ -313,7 +350,7 @@ where
 
     /// Add a relation to fetch with the selection
     pub fn with<T: Into<RelationFilter>>(mut self, relation: T) -> Self {
-        self.relations_to_fetch.push(relation.into());
+        self.relations_to_fetch.push(super::scoped_relation(&self.scope, relation.into()));
         self
     }
 }
@@ -353,25 +390,33 @@ where
             database_backend: self.database_backend,
             reverse_order: self.reverse_order,
             pending_order_bys: self.pending_order_bys,
-            pending_nulls: self.pending_nulls,
             cursor: self.cursor,
             is_distinct: self.is_distinct,
             distinct_on_fields: self.distinct_on_fields,
             distinct_on_columns: self.distinct_on_columns,
-            skip_is_negative: self.skip_is_negative,
+            distinct_on_ordered: self.distinct_on_ordered,
+            invalid_pagination: self.invalid_pagination,
+            invalid_filter: self.invalid_filter,
+            sql_comments: self.sql_comments,
+            max_rows: self.max_rows,
+            has_limit: self.has_limit,
+            shape: self.shape,
+            scope: self.scope,
+            error_mapper: self.error_mapper,
             _phantom: std::marker::PhantomData::<S::Data>,
         }
     }
 }
 
-impl<'a, C, Entity, Selected> From<crate::query_builders::ManyQueryBuilder<'a, C, Entity, Selected>>
+impl<'a, C, Entity, ModelWithRelations, Selected>
+    From<crate::query_builders::ManyQueryBuilder<'a, C, Entity, ModelWithRelations>>
     for SelectManyQueryBuilder<'a, C, Entity, Selected>
 where
     C: ConnectionTrait,
     Entity: EntityTrait,
     Selected: EntitySelection + HasRelationMetadata<Selected> + Send + 'static,
 {
-    fn from(src: crate::query_builders::ManyQueryBuilder<'a, C, Entity, Selected>) -> Self {
+    fn from(src: crate::query_builders::ManyQueryBuilder<'a, C, Entity, ModelWithRelations>) -> Self {
         SelectManyQueryBuilder {
             query: src.query,
             conn: src.conn,
@@ -382,13 +427,37 @@ where
             database_backend: src.database_backend,
             reverse_order: src.reverse_order,
             pending_order_bys: src.pending_order_bys,
-            pending_nulls: None,
             cursor: src.cursor,
             is_distinct: src.is_distinct,
             distinct_on_fields: src.distinct_on_fields,
             distinct_on_columns: src.distinct_on_columns,
-            skip_is_negative: false,
+            distinct_on_ordered: src.distinct_on_ordered,
+            invalid_pagination: src.invalid_pagination,
+            invalid_filter: src.invalid_filter,
+            sql_comments: src.sql_comments,
+            max_rows: src.max_rows,
+            has_limit: src.has_limit,
+            shape: src.shape,
+            scope: src.scope,
+            error_mapper: src.error_mapper,
             _phantom: std::marker::PhantomData,
         }
     }
 }
+
+impl<'a, C, Entity, Selected> crate::types::IntoSubquery for SelectManyQueryBuilder<'a, C, Entity, Selected>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    Selected:
+        EntitySelection + HasRelationMetadata<Selected> + ApplyNestedIncludes<C> + Send + 'static,
+{
+    /// `SELECT <selected fields> FROM ... WHERE ...`, without the primary key `exec` adds
+    fn into_subquery(self) -> sea_orm::sea_query::SelectStatement {
+        let mut select = self.built_select().select_only();
+        for (expr, alias) in &self.selected_fields {
+            select = select.expr_as(expr.clone(), alias.as_str());
+        }
+        select.into_query()
+    }
+}
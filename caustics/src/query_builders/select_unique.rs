@@ -15,6 +15,9 @@ where
     pub relations_to_fetch: Vec<RelationFilter>,
     pub registry: &'a (dyn EntityRegistry<C> + Sync),
     pub database_backend: DatabaseBackend,
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<Selected>,
 }
 
@@ -35,7 +38,7 @@ where
     where
         T: From<Selected>,
     {
-        match self.exec_internal().await? {
+        match self.error_mapper.clone().run(self.exec_internal()).await? {
             Some(selected) => Ok(Some(T::from(selected))),
             None => Ok(None),
         }
@@ -87,21 +90,25 @@ where
             }
         }
 
+        let mut requested_aliases = self.requested_aliases.clone();
+        crate::types::retain_primary_key::<Selected>(&mut selected, &mut requested_aliases);
+
         let mut select = query.select_only();
         for (expr, alias) in &selected {
             select = select.expr_as(expr.clone(), alias.as_str());
         }
         let stmt = select.build(self.database_backend);
         let entity_name = core::any::type_name::<Entity>();
+        let details = crate::hooks::compose_details_with_filter("select_unique", entity_name, &stmt.sql);
         crate::hooks::emit_before(&crate::hooks::QueryEvent {
             builder: "SelectUniqueQueryBuilder",
             entity: entity_name,
-            details: crate::hooks::compose_details("select_unique", entity_name),
+            details: details.clone(),
         });
         let start = std::time::Instant::now();
         if let Some(row) = self.conn.query_one(stmt).await? {
             let field_names: Vec<&str> =
-                self.requested_aliases.iter().map(|a| a.as_str()).collect();
+                requested_aliases.iter().map(|a| a.as_str()).collect();
             let mut s = Selected::fill_from_row(&row, &field_names);
 
             for rf in &self.relations_to_fetch {
@@ -120,7 +127,7 @@ where
                 &crate::hooks::QueryEvent {
                     builder: "SelectUniqueQueryBuilder",
                     entity: entity_name,
-                    details: crate::hooks::compose_details("select_unique", entity_name),
+                    details: details.clone(),
                 },
                 &crate::hooks::QueryResultMeta {
                     row_count: Some(1),
@@ -134,7 +141,7 @@ where
                 &crate::hooks::QueryEvent {
                     builder: "SelectUniqueQueryBuilder",
                     entity: entity_name,
-                    details: crate::hooks::compose_details("select_unique", entity_name),
+                    details: details.clone(),
                 },
                 &crate::hooks::QueryResultMeta {
                     row_count: Some(0),
@@ -158,7 +165,7 @@ where
     }
 
     pub fn with<T: Into<RelationFilter>>(mut self, relation: T) -> Self {
-        self.relations_to_fetch.push(relation.into());
+        self.relations_to_fetch.push(super::scoped_relation(&self.scope, relation.into()));
         self
     }
 }
@@ -180,6 +187,8 @@ where
             relations_to_fetch: src.relations_to_fetch,
             registry: src.registry,
             database_backend: src.conn.get_database_backend(),
+            scope: src.scope,
+            error_mapper: src.error_mapper,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -217,6 +226,8 @@ where
             relations_to_fetch: self.relations_to_fetch,
             registry: self.registry,
             database_backend: self.database_backend,
+            scope: self.scope,
+            error_mapper: self.error_mapper,
             _phantom: std::marker::PhantomData::<S::Data>,
         }
     }
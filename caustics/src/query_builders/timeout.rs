@@ -0,0 +1,41 @@
+use std::future::Future;
+use std::time::Duration;
+
+/// Query builder wrapper that stops waiting once `timeout` has elapsed, failing with
+/// [`CausticsError::Timeout`](crate::types::CausticsError::Timeout).
+///
+/// The deadline is enforced client-side only, by dropping the in-flight future: nothing is
+/// sent to the server, which keeps running the statement until it finishes. To have the
+/// database give up too, set `statement_timeout` on Postgres or `max_execution_time` on
+/// MySQL for the connection or role.
+pub struct TimeoutQueryBuilder<B> {
+    pub inner: B,
+    pub timeout: Duration,
+}
+
+/// Query builders that can run under a [`TimeoutQueryBuilder`]
+pub trait TimeoutExec {
+    type Output;
+
+    fn exec_future(self) -> impl Future<Output = Result<Self::Output, sea_orm::DbErr>>;
+}
+
+impl<B: TimeoutExec> TimeoutQueryBuilder<B> {
+    pub async fn exec(self) -> Result<B::Output, sea_orm::DbErr> {
+        run_with_timeout(self.timeout, self.inner.exec_future()).await
+    }
+}
+
+/// Await `future`, mapping an elapsed deadline to `CausticsError::Timeout`
+pub async fn run_with_timeout<T, F>(timeout: Duration, future: F) -> Result<T, sea_orm::DbErr>
+where
+    F: Future<Output = Result<T, sea_orm::DbErr>>,
+{
+    match tokio::time::timeout(timeout, future).await {
+        Ok(result) => result,
+        Err(_) => Err(crate::types::CausticsError::Timeout {
+            timeout_ms: timeout.as_millis(),
+        }
+        .into()),
+    }
+}
@@ -10,6 +10,14 @@ pub struct UniqueQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, Model
     pub conn: &'a C,
     pub relations_to_fetch: Vec<RelationFilter>,
     pub registry: &'a (dyn EntityRegistry<C> + Sync),
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    /// Value-free fingerprint of the lookup, keying the statement cache; `None` keeps it out
+    /// of the cache, e.g. after editing `query` directly
+    pub shape: Option<u64>,
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<ModelWithRelations>,
 }
 
@@ -42,6 +50,8 @@ where
             relations_to_fetch: self.relations_to_fetch,
             registry: self.registry,
             database_backend: self.conn.get_database_backend(),
+            scope: self.scope,
+            error_mapper: self.error_mapper,
             _phantom: std::marker::PhantomData,
         };
         let aliases = spec.collect_aliases();
@@ -53,21 +63,59 @@ where
         }
         builder
     }
+    /// Stop waiting for the query after `timeout`; the deadline is client-side only, see
+    /// [`TimeoutQueryBuilder`](super::TimeoutQueryBuilder)
+    pub fn timeout(self, timeout: std::time::Duration) -> super::TimeoutQueryBuilder<Self> {
+        super::TimeoutQueryBuilder {
+            inner: self,
+            timeout,
+        }
+    }
+
+    /// Execute the query on `conn` instead of the client's connection, e.g. a transaction
+    /// managed outside caustics
+    pub async fn exec_on<C2: ConnectionTrait + 'static>(self, conn: &C2) -> Result<Option<ModelWithRelations>, sea_orm::DbErr>
+    where
+        ModelWithRelations: ApplyNestedIncludes<C2> + crate::types::RegistryProvider<C2>,
+    {
+        UniqueQueryBuilder {
+            query: self.query,
+            conn,
+            relations_to_fetch: self.relations_to_fetch,
+            registry: <ModelWithRelations as crate::types::RegistryProvider<C2>>::registry(),
+            sql_comments: self.sql_comments,
+            shape: self.shape,
+            scope: self.scope,
+            error_mapper: self.error_mapper,
+            _phantom: std::marker::PhantomData,
+        }
+        .exec()
+        .await
+    }
+
     /// Execute the query and return a single result
     pub async fn exec(self) -> Result<Option<ModelWithRelations>, sea_orm::DbErr> {
-        if self.relations_to_fetch.is_empty() {
-            self.query
-                .one(self.conn)
-                .await
-                .map(|opt| opt.map(|model| ModelWithRelations::from_model(model)))
-        } else {
-            self.exec_with_relations().await
-        }
+        self.error_mapper.clone().run(async move {
+            if self.relations_to_fetch.is_empty() {
+                crate::statement_cache::one("UniqueQueryBuilder", self.query, self.shape, self.conn, self.sql_comments)
+                    .await
+                    .map(|opt| opt.map(|model| ModelWithRelations::from_model(model)))
+            } else {
+                self.exec_with_relations().await
+            }
+        })
+        .await
     }
 
     /// Add a relation to fetch with the query
     pub fn with<T: Into<RelationFilter>>(mut self, relation: T) -> Self {
-        self.relations_to_fetch.push(relation.into());
+        self.relations_to_fetch.push(super::scoped_relation(&self.scope, relation.into()));
+        self
+    }
+
+    /// Fetch every relation declared on the entity, one level deep
+    pub fn with_all(mut self) -> Self {
+        super::push_all_relations::<ModelWithRelations>(&mut self.relations_to_fetch, &self.scope);
         self
     }
 
@@ -81,9 +129,11 @@ where
             conn,
             relations_to_fetch,
             registry,
+            sql_comments,
+            shape,
             ..
         } = self;
-        let main_result = query.one(conn).await?;
+        let main_result = crate::statement_cache::one("UniqueQueryBuilder", query, shape, conn, sql_comments).await?;
 
         if let Some(main_model) = main_result {
             let mut model_with_relations = ModelWithRelations::from_model(main_model);
@@ -105,3 +155,21 @@ where
         }
     }
 }
+
+impl<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> super::TimeoutExec
+    for UniqueQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+{
+    type Output = Option<ModelWithRelations>;
+
+    fn exec_future(
+        self,
+    ) -> impl std::future::Future<Output = Result<Self::Output, sea_orm::DbErr>> {
+        self.exec()
+    }
+}
@@ -1,7 +1,10 @@
 use super::has_many_set::HasManySetUpdateQueryBuilder;
 use super::deferred_lookup::DeferredLookup;
 use crate::{FromModel, MergeInto, RelationFilter, ApplyNestedIncludes, HasRelationMetadata, EntityRegistry};
-use sea_orm::{ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, IntoActiveModel, QueryFilter};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryTrait, TransactionTrait,
+};
 
 /// Query builder for updating entity records
 pub struct UpdateQueryBuilder<
@@ -18,6 +21,11 @@ pub struct UpdateQueryBuilder<
     pub deferred_lookups: Vec<DeferredLookup>,
     pub relations_to_fetch: Vec<RelationFilter>,
     pub registry: &'a (dyn EntityRegistry<C> + Sync),
+    /// Row scope of the client, applied to `with` includes
+    pub scope: Option<crate::RowScope>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<(Entity, ActiveModel, ModelWithRelations)>,
 }
 
@@ -54,11 +62,11 @@ where
     pub fn with<R: Into<RelationFilter>>(self, relation: R) -> Self {
         match self {
             UnifiedUpdateQueryBuilder::Scalar(mut b) => {
-                b.relations_to_fetch.push(relation.into());
+                b.relations_to_fetch.push(super::scoped_relation(&b.scope, relation.into()));
                 UnifiedUpdateQueryBuilder::Scalar(b)
             }
             UnifiedUpdateQueryBuilder::Relations(mut b) => {
-                b.relations_to_fetch.push(relation.into());
+                b.relations_to_fetch.push(super::scoped_relation(&b.scope, relation.into()));
                 UnifiedUpdateQueryBuilder::Relations(b)
             }
         }
@@ -94,6 +102,35 @@ where
             UnifiedUpdateQueryBuilder::Relations(b) => b.exec().await,
         }
     }
+
+    /// Execute the update and return `(previous, updated)`, where `previous` is the row
+    /// as it was read before the changes were applied. Both reads and the write run in
+    /// a single transaction.
+    pub async fn exec_with_previous(
+        self,
+    ) -> Result<(ModelWithRelations, ModelWithRelations), sea_orm::DbErr> {
+        match self {
+            UnifiedUpdateQueryBuilder::Scalar(b) => b.exec_with_previous().await,
+            UnifiedUpdateQueryBuilder::Relations(b) => {
+                let cond_dbg = format!("{:?}", b.condition);
+                let txn = b.conn.begin().await?;
+                let previous = <Entity as EntityTrait>::find()
+                    .filter::<sea_orm::Condition>(b.condition.clone())
+                    .one(&txn)
+                    .await?
+                    .ok_or_else(|| -> sea_orm::DbErr {
+                        crate::types::CausticsError::NotFoundForCondition {
+                            entity: core::any::type_name::<Entity>().to_string(),
+                            condition: cond_dbg,
+                        }
+                        .into()
+                    })?;
+                let updated = b.exec_in_txn(&txn).await?;
+                txn.commit().await?;
+                Ok((ModelWithRelations::from_model(previous), updated))
+            }
+        }
+    }
 }
 
 impl<'a, C, Entity, ActiveModel, ModelWithRelations, T>
@@ -124,30 +161,47 @@ where
         self,
         txn: &DatabaseTransaction,
     ) -> Result<ModelWithRelations, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            let (_, updated) = self.exec_models_in_txn(txn).await?;
+            Ok(ModelWithRelations::from_model(updated))
+        })
+        .await
+    }
+
+    /// Apply the update inside `txn`, returning the row before and after the changes
+    async fn exec_models_in_txn(
+        self,
+        txn: &DatabaseTransaction,
+    ) -> Result<
+        (<Entity as EntityTrait>::Model, <Entity as EntityTrait>::Model),
+        sea_orm::DbErr,
+    > {
         let cond_dbg = format!("{:?}", self.condition);
         let changes = self.changes;
         let deferred_lookups = self.deferred_lookups;
+        let commented = crate::hooks::CommentedConnection::new(txn, self.sql_comments);
 
         let entity = <Entity as EntityTrait>::find()
             .filter::<sea_orm::Condition>(self.condition)
-            .one(txn)
+            .one(&commented)
             .await?;
         if let Some(entity) = entity {
+            let previous = entity.clone();
             let mut active_model = entity.into_active_model();
             
             // Resolve deferred lookups and assign to active model
             for lookup in &deferred_lookups {
                 let lookup_result = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
-                (lookup.assign)(&mut active_model as &mut (dyn std::any::Any + 'static), lookup_result);
+                (lookup.assign)(&mut active_model as &mut (dyn std::any::Any + 'static), lookup_result)?;
             }
             
             // Apply remaining changes
-            for change in changes {
+            for change in &changes {
                 change.merge_into(&mut active_model);
             }
             
-            let updated = active_model.update(txn).await?;
-            Ok(ModelWithRelations::from_model(updated))
+            let updated = update_with_column_exprs(&commented, active_model, &changes).await?;
+            Ok((previous, updated))
         } else {
             Err(crate::types::CausticsError::NotFoundForCondition {
                 entity: core::any::type_name::<Entity>().to_string(),
@@ -170,50 +224,115 @@ where
     <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
 {
     pub async fn exec(self) -> Result<ModelWithRelations, sea_orm::DbErr> {
-        let cond_dbg = format!("{:?}", self.condition);
-        let changes = self.changes;
-        let deferred_lookups = self.deferred_lookups;
+        self.error_mapper.clone().run(async move {
+            let cond_dbg = format!("{:?}", self.condition);
+            let changes = self.changes;
+            let deferred_lookups = self.deferred_lookups;
+            let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
 
-        let entity = <Entity as EntityTrait>::find()
-            .filter::<sea_orm::Condition>(self.condition)
-            .one(self.conn)
-            .await?;
-        if let Some(entity) = entity {
-            let mut active_model = entity.into_active_model();
+            let entity = <Entity as EntityTrait>::find()
+                .filter::<sea_orm::Condition>(self.condition)
+                .one(&commented)
+                .await?;
+            if let Some(entity) = entity {
+                let mut active_model = entity.into_active_model();
 
-            // Resolve deferred lookups and assign to active model
-            for lookup in &deferred_lookups {
-                let lookup_result = (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
-                (lookup.assign)(&mut active_model as &mut (dyn std::any::Any + 'static), lookup_result);
-            }
+                // Resolve deferred lookups and assign to active model
+                for lookup in &deferred_lookups {
+                    let lookup_result = (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
+                    (lookup.assign)(&mut active_model as &mut (dyn std::any::Any + 'static), lookup_result)?;
+                }
 
-            // Apply remaining changes
-            for change in changes {
-                change.merge_into(&mut active_model);
-            }
+                // Apply remaining changes
+                for change in &changes {
+                    change.merge_into(&mut active_model);
+                }
+
+                let updated = update_with_column_exprs(&commented, active_model, &changes).await?;
+                let mut model_with_relations = ModelWithRelations::from_model(updated);
 
-            let updated = active_model.update(self.conn).await?;
-            let mut model_with_relations = ModelWithRelations::from_model(updated);
-
-            if !self.relations_to_fetch.is_empty() {
-                for relation_filter in self.relations_to_fetch {
-                    ApplyNestedIncludes::apply_relation_filter(
-                        &mut model_with_relations,
-                        self.conn,
-                        &relation_filter,
-                        self.registry,
-                    )
-                    .await?;
+                if !self.relations_to_fetch.is_empty() {
+                    for relation_filter in self.relations_to_fetch {
+                        ApplyNestedIncludes::apply_relation_filter(
+                            &mut model_with_relations,
+                            self.conn,
+                            &relation_filter,
+                            self.registry,
+                        )
+                        .await?;
+                    }
                 }
-            }
 
-            Ok(model_with_relations)
-        } else {
-            Err(crate::types::CausticsError::NotFoundForCondition {
-                entity: core::any::type_name::<Entity>().to_string(),
-                condition: cond_dbg,
+                Ok(model_with_relations)
+            } else {
+                Err(crate::types::CausticsError::NotFoundForCondition {
+                    entity: core::any::type_name::<Entity>().to_string(),
+                    condition: cond_dbg,
+                }
+                .into())
             }
-            .into())
+        })
+        .await
+    }
+
+    /// Execute the update and return `(previous, updated)`, where `previous` is the row
+    /// as it was read before the changes were applied. The pre-read and the update run
+    /// in a single transaction.
+    pub async fn exec_with_previous(
+        mut self,
+    ) -> Result<(ModelWithRelations, ModelWithRelations), sea_orm::DbErr> {
+        let conn = self.conn;
+        let registry = self.registry;
+        let relations_to_fetch = std::mem::take(&mut self.relations_to_fetch);
+
+        let txn = conn.begin().await?;
+        let (previous, updated) = self.exec_models_in_txn(&txn).await?;
+        txn.commit().await?;
+
+        let mut model_with_relations = ModelWithRelations::from_model(updated);
+        for relation_filter in relations_to_fetch {
+            ApplyNestedIncludes::apply_relation_filter(
+                &mut model_with_relations,
+                conn,
+                &relation_filter,
+                registry,
+            )
+            .await?;
         }
+
+        Ok((ModelWithRelations::from_model(previous), model_with_relations))
+    }
+}
+
+/// Save `active_model` with a single `UPDATE` that also carries the server-side column
+/// expressions of `changes` (see [`MergeInto::column_expr`]), returning the updated row.
+pub(crate) async fn update_with_column_exprs<C, Entity, ActiveModel, T>(
+    conn: &C,
+    active_model: ActiveModel,
+    changes: &[T],
+) -> Result<<Entity as EntityTrait>::Model, sea_orm::DbErr>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel: sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send,
+    T: MergeInto<ActiveModel>,
+    <Entity as EntityTrait>::Model: IntoActiveModel<ActiveModel>,
+{
+    let backend = conn.get_database_backend();
+    let exprs: Vec<_> = changes
+        .iter()
+        .filter_map(|change| change.column_expr(backend))
+        .collect();
+    if exprs.is_empty() {
+        return active_model.update(conn).await;
+    }
+
+    // What `ActiveModelTrait::update` does, with the expressions added to its statement
+    let active_model = active_model.before_save(conn, false).await?;
+    let mut update = <Entity as EntityTrait>::update(active_model);
+    for (column, expr) in exprs {
+        QueryTrait::query(&mut update).value(column, expr);
     }
+    let updated = update.exec(conn).await?;
+    ActiveModel::after_save(updated, conn, false).await
 }
@@ -1,5 +1,7 @@
 use crate::MergeInto;
-use sea_orm::{ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter};
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, EntityTrait, IntoActiveModel, QueryFilter, QueryTrait,
+};
 
 /// Query builder for updating many records; returns affected row count
 pub struct UpdateManyQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ActiveModel, T>
@@ -10,6 +12,11 @@ where
     pub condition: sea_orm::Condition,
     pub changes: Vec<T>,
     pub conn: &'a C,
+    /// Message for a filter the backend cannot run, reported as `QueryValidation` on exec
+    pub invalid_filter: Option<String>,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<(Entity, ActiveModel)>,
 }
 
@@ -25,20 +32,96 @@ where
     where
         <Entity as EntityTrait>::Model: IntoActiveModel<ActiveModel>,
     {
-        // Select all matching rows, update individually for portability
+        self.error_mapper.clone().run(async move {
+            super::check_filter(&self.invalid_filter)?;
+            // When every change is a server-side expression (e.g. `set_from_column`), a single
+            // UPDATE covers all matching rows. ActiveModelBehavior hooks do not run on this path.
+            if let Some(update) = self.bulk_update() {
+                let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+                let result = update.exec(&conn).await?;
+                return Ok(result.rows_affected as i64);
+            }
+            Ok(self.update_each().await?.len() as i64)
+        })
+        .await
+    }
+
+    /// Update all matching records and return them as they are after the update, e.g. for
+    /// emitting change events.
+    ///
+    /// Needs `RETURNING` (Postgres, SQLite) for the single-statement path, so other backends
+    /// get a `QueryValidation` error.
+    pub async fn exec_returning(self) -> Result<Vec<<Entity as EntityTrait>::Model>, sea_orm::DbErr>
+    where
+        <Entity as EntityTrait>::Model: IntoActiveModel<ActiveModel>,
+    {
+        self.error_mapper.clone().run(async move {
+            super::check_filter(&self.invalid_filter)?;
+            let backend = self.conn.get_database_backend();
+            if !supports_returning(backend) {
+                return Err(crate::types::CausticsError::QueryValidation {
+                    message: format!("update_many exec_returning needs RETURNING, which {:?} lacks", backend),
+                }
+                .into());
+            }
+            if let Some(update) = self.bulk_update() {
+                // RETURNING is built by hand: SeaORM only emits it for SQLite behind a feature flag
+                let mut update = update.into_query();
+                update.returning_all();
+                let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+                return <Entity as EntityTrait>::find()
+                    .from_raw_sql(backend.build(&update))
+                    .all(&conn)
+                    .await;
+            }
+            self.update_each().await
+        })
+        .await
+    }
+
+    /// The single UPDATE for changes that are all column expressions, if they are
+    fn bulk_update(&self) -> Option<sea_orm::UpdateMany<Entity>> {
+        let backend = self.conn.get_database_backend();
+        let exprs: Vec<_> = self
+            .changes
+            .iter()
+            .filter_map(|change| change.column_expr(backend))
+            .collect();
+        if self.changes.is_empty() || exprs.len() != self.changes.len() {
+            return None;
+        }
+        let mut update = <Entity as EntityTrait>::update_many().filter(self.condition.clone());
+        for (column, expr) in exprs {
+            QueryTrait::query(&mut update).value(column, expr);
+        }
+        Some(update)
+    }
+
+    /// Select all matching rows, update individually for portability
+    async fn update_each(self) -> Result<Vec<<Entity as EntityTrait>::Model>, sea_orm::DbErr>
+    where
+        <Entity as EntityTrait>::Model: IntoActiveModel<ActiveModel>,
+    {
+        let conn = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
         let rows = <Entity as EntityTrait>::find()
             .filter::<sea_orm::Condition>(self.condition)
-            .all(self.conn)
+            .all(&conn)
             .await?;
-        let mut affected: i64 = 0;
+        let mut updated_rows = Vec::with_capacity(rows.len());
         for row in rows {
             let mut am: ActiveModel = row.into_active_model();
             for change in &self.changes {
                 change.merge_into(&mut am);
             }
-            let _ = am.update(self.conn).await?;
-            affected += 1;
+            updated_rows.push(
+                super::update::update_with_column_exprs(&conn, am, &self.changes).await?,
+            );
         }
-        Ok(affected)
+        Ok(updated_rows)
     }
 }
+
+/// Backends where caustics can append `RETURNING *` to a statement
+pub(crate) fn supports_returning(backend: DatabaseBackend) -> bool {
+    matches!(backend, DatabaseBackend::Postgres | DatabaseBackend::Sqlite)
+}
@@ -1,8 +1,9 @@
 use super::deferred_lookup::DeferredLookup;
 use crate::{FromModel, MergeInto, PostInsertOp};
+use sea_orm::sea_query::OnConflict;
 use sea_orm::{
-    ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, IntoActiveModel,
-    QueryFilter,
+    ConnectionTrait, DatabaseBackend, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    IntoActiveModel, Iterable, QueryFilter, QueryTrait,
 };
 use std::any::Any;
 
@@ -16,18 +17,131 @@ pub struct UpsertQueryBuilder<
     T: MergeInto<ActiveModel>,
 > {
     pub condition: sea_orm::Condition,
+    /// Unique constraint columns for a single-statement `INSERT ... ON CONFLICT` upsert on
+    /// Postgres and SQLite; `None` (or another backend) falls back to find-then-write
+    pub native_conflict: Option<Vec<<Entity as EntityTrait>::Column>>,
+    /// `WHERE` of a partial unique index the conflict target must match, e.g.
+    /// `deleted_at IS NULL`; also narrows the fallback's lookup of the existing row
+    pub conflict_predicate: Option<sea_orm::Condition>,
     #[allow(clippy::type_complexity)]
     pub create: (
         ActiveModel,
         Vec<DeferredLookup>,
         Vec<PostInsertOp<'a>>,
-        fn(&<Entity as EntityTrait>::Model) -> crate::CausticsKey,
+        fn(&<Entity as EntityTrait>::Model) -> Result<crate::CausticsKey, sea_orm::DbErr>,
     ),
     pub update: Vec<T>,
+    /// Columns a conflict overwrites with the incoming row (`col = EXCLUDED.col`); `None`
+    /// overwrites just the columns the update sets
+    pub update_only: Option<Vec<<Entity as EntityTrait>::Column>>,
     pub conn: &'a C,
+    /// Prefix the SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    pub error_mapper: crate::ErrorMapper,
     pub _phantom: std::marker::PhantomData<(Entity, ModelWithRelations)>,
 }
 
+/// Insert `active_model` with the update changes applied, or on a conflict over
+/// `conflict_columns` (restricted to rows matching `conflict_predicate`) overwrite just the
+/// columns those changes set (or `update_only`), in one statement
+async fn native_upsert<Conn, Entity, ActiveModel, T>(
+    conn: &Conn,
+    conflict_columns: Vec<<Entity as EntityTrait>::Column>,
+    conflict_predicate: Option<sea_orm::Condition>,
+    mut active_model: ActiveModel,
+    update: Vec<T>,
+    update_only: Option<Vec<<Entity as EntityTrait>::Column>>,
+) -> Result<<Entity as EntityTrait>::Model, sea_orm::DbErr>
+where
+    Conn: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    T: MergeInto<ActiveModel>,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    let update_columns =
+        merge_native_changes(&conflict_columns, &mut active_model, &update, update_only);
+    let on_conflict = conflict_clause(conflict_columns, conflict_predicate, update_columns);
+    // RETURNING is built by hand: SeaORM only emits it for SQLite behind a feature flag, and
+    // its fallback re-reads by the inserted key, which is wrong when the conflict branch ran
+    let mut insert = Entity::insert(active_model).on_conflict(on_conflict).into_query();
+    insert.returning_all();
+    let stmt = conn.get_database_backend().build(&insert);
+    Entity::find()
+        .from_raw_sql(stmt)
+        .one(conn)
+        .await?
+        .ok_or_else(|| sea_orm::DbErr::RecordNotFound("Upsert returned no row".to_string()))
+}
+
+/// `ON CONFLICT (columns) [WHERE predicate] DO UPDATE SET col = EXCLUDED.col, ...`
+pub(crate) fn conflict_clause<Column: sea_orm::ColumnTrait>(
+    conflict_columns: Vec<Column>,
+    conflict_predicate: Option<sea_orm::Condition>,
+    update_columns: Vec<Column>,
+) -> OnConflict {
+    let mut on_conflict = OnConflict::columns(conflict_columns);
+    if let Some(predicate) = conflict_predicate {
+        on_conflict.target_cond_where(predicate);
+    }
+    on_conflict.update_columns(update_columns).to_owned()
+}
+
+/// Apply `update` to the row to insert and return the columns a conflict overwrites.
+///
+/// `update_only` columns the row leaves unset are dropped: `EXCLUDED.col` would be the
+/// column default there, whereas the fallback path keeps the existing value.
+pub(crate) fn merge_native_changes<ActiveModel, T>(
+    conflict_columns: &[<ActiveModel::Entity as EntityTrait>::Column],
+    active_model: &mut ActiveModel,
+    update: &[T],
+    update_only: Option<Vec<<ActiveModel::Entity as EntityTrait>::Column>>,
+) -> Vec<<ActiveModel::Entity as EntityTrait>::Column>
+where
+    ActiveModel: sea_orm::ActiveModelTrait,
+    T: MergeInto<ActiveModel>,
+{
+    let mut changed = <ActiveModel as sea_orm::ActiveModelTrait>::default();
+    for change in update {
+        change.merge_into(&mut changed);
+        change.merge_into(active_model);
+    }
+    let update_columns: Vec<_> = match update_only {
+        Some(columns) => columns
+            .into_iter()
+            .filter(|col| active_model.get(*col).is_set())
+            .collect(),
+        None => <ActiveModel::Entity as EntityTrait>::Column::iter()
+            .filter(|col| changed.get(*col).is_set())
+            .collect(),
+    };
+    if update_columns.is_empty() {
+        // A no-op update rather than DO NOTHING, so RETURNING still yields the existing row
+        conflict_columns.to_vec()
+    } else {
+        update_columns
+    }
+}
+
+/// Copy the `columns` the incoming row sets onto the existing one, mirroring what the
+/// native path's `col = EXCLUDED.col` does on conflict; unset columns keep their value
+fn apply_update_only<ActiveModel: sea_orm::ActiveModelTrait>(
+    existing: &mut ActiveModel,
+    incoming: &ActiveModel,
+    columns: &[<ActiveModel::Entity as EntityTrait>::Column],
+) {
+    for col in columns {
+        if let Some(value) = incoming.get(*col).into_value() {
+            existing.set(*col, value);
+        }
+    }
+}
+
+pub(crate) fn supports_native_upsert(backend: DatabaseBackend) -> bool {
+    matches!(backend, DatabaseBackend::Postgres | DatabaseBackend::Sqlite)
+}
+
 impl<'a, C, Entity, ActiveModel, ModelWithRelations, T>
     UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>
 where
@@ -39,47 +153,116 @@ where
     T: MergeInto<ActiveModel>,
     <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
 {
+    /// On conflict, overwrite only `columns` with the values the insert would have written,
+    /// instead of the columns the update sets
+    pub fn update_only(mut self, columns: Vec<<Entity as EntityTrait>::Column>) -> Self {
+        self.update_only = Some(columns);
+        self
+    }
+
+    /// Conflict on `columns` of a partial unique index, e.g. `UNIQUE (email) WHERE deleted_at
+    /// IS NULL`, whose predicate the `ON CONFLICT` target has to repeat. Rows outside the
+    /// predicate never count as the existing row, on the fallback path too.
+    ///
+    /// Only changes the target when the upsert could run natively in the first place; entity
+    /// modules add `on_conflict`, which takes the predicate as `WhereParam`s.
+    pub fn on_conflict_condition(
+        mut self,
+        columns: Vec<<Entity as EntityTrait>::Column>,
+        predicate: Option<sea_orm::Condition>,
+    ) -> Self {
+        if self.native_conflict.is_some() && !columns.is_empty() {
+            self.native_conflict = Some(columns);
+        }
+        if let Some(predicate) = &predicate {
+            self.condition = sea_orm::Condition::all()
+                .add(self.condition)
+                .add(predicate.clone());
+        }
+        self.conflict_predicate = predicate;
+        self
+    }
+
     /// Execute the upsert within a transaction
     pub async fn exec_in_txn(
         self,
         txn: &DatabaseTransaction,
     ) -> Result<ModelWithRelations, sea_orm::DbErr> {
-        let existing = Entity::find()
-            .filter::<sea_orm::Condition>(self.condition.clone())
-            .one(txn)
-            .await?;
-
-        match existing {
-            Some(active_model) => {
-                let mut active_model = active_model.into_active_model();
-                for change in self.update {
-                    change.merge_into(&mut active_model);
-                }
-                active_model
-                    .update(txn)
+        self.error_mapper.clone().run(async move {
+            let commented = crate::hooks::CommentedConnection::new(txn, self.sql_comments);
+            if let Some(conflict_columns) = self.native_conflict {
+                if supports_native_upsert(txn.get_database_backend()) {
+                    let (mut active_model, deferred_lookups, _, _) = self.create;
+                    for lookup in &deferred_lookups {
+                        let lookup_result = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
+                        (lookup.assign)(&mut active_model as &mut (dyn Any + 'static), lookup_result)?;
+                    }
+                    return native_upsert(
+                        &commented,
+                        conflict_columns,
+                        self.conflict_predicate,
+                        active_model,
+                        self.update,
+                        self.update_only,
+                    )
                     .await
-                    .map(ModelWithRelations::from_model)
-            }
-            None => {
-                let (mut active_model, deferred_lookups, post_ops, id_extractor) = self.create;
-                for lookup in &deferred_lookups {
-                    let lookup_result = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
-                    (lookup.assign)(
-                        &mut active_model as &mut (dyn std::any::Any + 'static),
-                        lookup_result,
-                    );
+                    .map(ModelWithRelations::from_model);
                 }
-                for change in self.update {
-                    change.merge_into(&mut active_model);
+            }
+            let existing = Entity::find()
+                .filter::<sea_orm::Condition>(self.condition.clone())
+                .one(&commented)
+                .await?;
+
+            match existing {
+                Some(active_model) => {
+                    let mut active_model = active_model.into_active_model();
+                    match &self.update_only {
+                        Some(columns) => {
+                            let (mut incoming, deferred_lookups, _, _) = self.create;
+                            for lookup in &deferred_lookups {
+                                let lookup_result =
+                                    (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
+                                (lookup.assign)(&mut incoming as &mut (dyn Any + 'static), lookup_result)?;
+                            }
+                            for change in self.update {
+                                change.merge_into(&mut incoming);
+                            }
+                            apply_update_only(&mut active_model, &incoming, columns);
+                        }
+                        None => {
+                            for change in self.update {
+                                change.merge_into(&mut active_model);
+                            }
+                        }
+                    }
+                    active_model
+                        .update(&commented)
+                        .await
+                        .map(ModelWithRelations::from_model)
                 }
-                let inserted = active_model.insert(txn).await?;
-                let parent_id = (id_extractor)(&inserted);
-                for op in post_ops {
-                    (op.run_on_txn)(txn, parent_id.clone()).await?;
+                None => {
+                    let (mut active_model, deferred_lookups, post_ops, id_extractor) = self.create;
+                    for lookup in &deferred_lookups {
+                        let lookup_result = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
+                        (lookup.assign)(
+                            &mut active_model as &mut (dyn std::any::Any + 'static),
+                            lookup_result,
+                        )?;
+                    }
+                    for change in self.update {
+                        change.merge_into(&mut active_model);
+                    }
+                    let inserted = active_model.insert(&commented).await?;
+                    let parent_id = (id_extractor)(&inserted)?;
+                    for op in post_ops {
+                        (op.run_on_txn)(txn, parent_id.clone()).await?;
+                    }
+                    Ok(ModelWithRelations::from_model(inserted))
                 }
-                Ok(ModelWithRelations::from_model(inserted))
             }
-        }
+        })
+        .await
     }
 }
 
@@ -94,40 +277,80 @@ where
     <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
 {
     pub async fn exec(self) -> Result<ModelWithRelations, sea_orm::DbErr> {
-        let existing = Entity::find()
-            .filter::<sea_orm::Condition>(self.condition.clone())
-            .one(self.conn)
-            .await?;
-
-        match existing {
-            Some(active_model) => {
-                let mut active_model = active_model.into_active_model();
-                for change in self.update {
-                    change.merge_into(&mut active_model);
-                }
-                active_model
-                    .update(self.conn)
+        self.error_mapper.clone().run(async move {
+            let commented = crate::hooks::CommentedConnection::new(self.conn, self.sql_comments);
+            if let Some(conflict_columns) = self.native_conflict {
+                if supports_native_upsert(self.conn.get_database_backend()) {
+                    let (mut active_model, deferred_lookups, _, _) = self.create;
+                    for lookup in &deferred_lookups {
+                        let lookup_result =
+                            (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
+                        (lookup.assign)(&mut active_model as &mut (dyn Any + 'static), lookup_result)?;
+                    }
+                    return native_upsert(
+                        &commented,
+                        conflict_columns,
+                        self.conflict_predicate,
+                        active_model,
+                        self.update,
+                        self.update_only,
+                    )
                     .await
-                    .map(ModelWithRelations::from_model)
-            }
-            None => {
-                let (mut active_model, deferred_lookups, post_ops, id_extractor) = self.create;
-                // Execute all deferred lookups in batch (if needed)
-                for lookup in &deferred_lookups {
-                    let lookup_result =
-                        (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
-                    (lookup.assign)(&mut active_model as &mut (dyn Any + 'static), lookup_result);
+                    .map(ModelWithRelations::from_model);
                 }
-                for change in self.update {
-                    change.merge_into(&mut active_model);
+            }
+            let existing = Entity::find()
+                .filter::<sea_orm::Condition>(self.condition.clone())
+                .one(&commented)
+                .await?;
+
+            match existing {
+                Some(active_model) => {
+                    let mut active_model = active_model.into_active_model();
+                    match &self.update_only {
+                        Some(columns) => {
+                            let (mut incoming, deferred_lookups, _, _) = self.create;
+                            for lookup in &deferred_lookups {
+                                let lookup_result =
+                                    (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
+                                (lookup.assign)(&mut incoming as &mut (dyn Any + 'static), lookup_result)?;
+                            }
+                            for change in self.update {
+                                change.merge_into(&mut incoming);
+                            }
+                            apply_update_only(&mut active_model, &incoming, columns);
+                        }
+                        None => {
+                            for change in self.update {
+                                change.merge_into(&mut active_model);
+                            }
+                        }
+                    }
+                    active_model
+                        .update(&commented)
+                        .await
+                        .map(ModelWithRelations::from_model)
                 }
-                let inserted = active_model.insert(self.conn).await?;
-                let parent_id = (id_extractor)(&inserted);
-                for op in post_ops {
-                    (op.run_on_conn)(self.conn, parent_id.clone()).await?;
+                None => {
+                    let (mut active_model, deferred_lookups, post_ops, id_extractor) = self.create;
+                    // Execute all deferred lookups in batch (if needed)
+                    for lookup in &deferred_lookups {
+                        let lookup_result =
+                            (lookup.resolve_on_conn)(self.conn, &*lookup.unique_param).await?;
+                        (lookup.assign)(&mut active_model as &mut (dyn Any + 'static), lookup_result)?;
+                    }
+                    for change in self.update {
+                        change.merge_into(&mut active_model);
+                    }
+                    let inserted = active_model.insert(&commented).await?;
+                    let parent_id = (id_extractor)(&inserted)?;
+                    for op in post_ops {
+                        (op.run_on_conn)(self.conn, parent_id.clone()).await?;
+                    }
+                    Ok(ModelWithRelations::from_model(inserted))
                 }
-                Ok(ModelWithRelations::from_model(inserted))
             }
-        }
+        })
+        .await
     }
 }
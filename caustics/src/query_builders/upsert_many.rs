@@ -0,0 +1,186 @@
+use super::upsert::{
+    conflict_clause, merge_native_changes, supports_native_upsert, UpsertQueryBuilder,
+};
+use crate::{FromModel, MergeInto};
+use sea_orm::{
+    ConnectionTrait, DatabaseTransaction, EntityTrait, IdenStatic, Iterable, QueryTrait,
+    TransactionTrait,
+};
+use std::any::Any;
+
+/// Query builder for upserting many records; returns affected row count
+///
+/// On Postgres and SQLite a batch whose rows share one unique constraint, set the same
+/// columns and overwrite the same columns on conflict runs as a single multi-row
+/// `INSERT ... ON CONFLICT DO UPDATE`; any other batch upserts row by row. Either way the
+/// batch runs in one transaction. Two rows with the same unique key in one native batch
+/// are rejected by the database.
+pub struct UpsertManyQueryBuilder<
+    'a,
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel: sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations,
+    T: MergeInto<ActiveModel>,
+> {
+    pub items: Vec<UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>>,
+    pub conn: &'a C,
+    pub error_mapper: crate::ErrorMapper,
+}
+
+impl<'a, C, Entity, ActiveModel, ModelWithRelations, T>
+    UpsertManyQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>
+where
+    C: ConnectionTrait + TransactionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+    T: MergeInto<ActiveModel>,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    /// Execute the upserts and return the number of rows inserted or updated
+    pub async fn exec(self) -> Result<i64, sea_orm::DbErr> {
+        self.exec_returning().await.map(|rows| rows.len() as i64)
+    }
+
+    /// Execute the upserts and return every inserted or updated row, in input order
+    pub async fn exec_returning(self) -> Result<Vec<ModelWithRelations>, sea_orm::DbErr> {
+        self.error_mapper.clone().run(async move {
+            if self.items.is_empty() {
+                return Ok(Vec::new());
+            }
+            let txn = self.conn.begin().await?;
+            let rows = upsert_all(self.items, &txn).await?;
+            txn.commit().await?;
+            Ok(rows)
+        })
+        .await
+    }
+}
+
+async fn upsert_all<'a, C, Entity, ActiveModel, ModelWithRelations, T>(
+    mut items: Vec<UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>>,
+    txn: &DatabaseTransaction,
+) -> Result<Vec<ModelWithRelations>, sea_orm::DbErr>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+    T: MergeInto<ActiveModel>,
+    <Entity as EntityTrait>::Model: sea_orm::IntoActiveModel<ActiveModel>,
+{
+    if supports_native_upsert(txn.get_database_backend())
+        && items.iter().all(|item| item.native_conflict.is_some())
+    {
+        // Resolve connected foreign keys up front so every row's column set is known;
+        // the row-by-row fallback then has nothing left to look up
+        for item in &mut items {
+            let (active_model, deferred_lookups, _, _) = &mut item.create;
+            for lookup in deferred_lookups.drain(..) {
+                let lookup_result = (lookup.resolve_on_txn)(txn, &*lookup.unique_param).await?;
+                (lookup.assign)(active_model as &mut (dyn Any + 'static), lookup_result)?;
+            }
+        }
+        if let Some(shape) = native_batch_shape(&items) {
+            return native_upsert_many(txn, shape, items).await;
+        }
+    }
+    let mut rows = Vec::with_capacity(items.len());
+    for mut item in items {
+        // Mapped once by `exec_returning`
+        item.error_mapper = crate::ErrorMapper::default();
+        rows.push(item.exec_in_txn(txn).await?);
+    }
+    Ok(rows)
+}
+
+/// Conflict target and overwritten columns shared by every row, or `None` when the rows
+/// differ in those or in the columns they set and so cannot share one `INSERT`
+#[allow(clippy::type_complexity)]
+fn native_batch_shape<'a, C, Entity, ActiveModel, ModelWithRelations, T>(
+    items: &[UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>],
+) -> Option<(
+    Vec<<Entity as EntityTrait>::Column>,
+    Option<sea_orm::Condition>,
+    Vec<<Entity as EntityTrait>::Column>,
+)>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    T: MergeInto<ActiveModel>,
+{
+    let names = |columns: &[<Entity as EntityTrait>::Column]| {
+        columns.iter().map(|col| col.as_str().to_owned()).collect::<Vec<_>>()
+    };
+    let mut shape = None;
+    for item in items {
+        let conflict_columns = item.native_conflict.clone()?;
+        let mut active_model = item.create.0.clone();
+        let update_columns = merge_native_changes(
+            &conflict_columns,
+            &mut active_model,
+            &item.update,
+            item.update_only.clone(),
+        );
+        let set_columns: Vec<bool> = <Entity as EntityTrait>::Column::iter()
+            .map(|col| active_model.get(col).is_set())
+            .collect();
+        let predicate = item.conflict_predicate.clone();
+        match &shape {
+            None => shape = Some((conflict_columns, predicate, update_columns, set_columns)),
+            Some((first_conflict, first_predicate, first_update, first_set)) => {
+                if names(first_conflict) != names(&conflict_columns)
+                    || *first_predicate != predicate
+                    || names(first_update) != names(&update_columns)
+                    || *first_set != set_columns
+                {
+                    return None;
+                }
+            }
+        }
+    }
+    shape.map(|(conflict_columns, predicate, update_columns, _)| {
+        (conflict_columns, predicate, update_columns)
+    })
+}
+
+#[allow(clippy::type_complexity)]
+async fn native_upsert_many<'a, C, Entity, ActiveModel, ModelWithRelations, T>(
+    txn: &DatabaseTransaction,
+    (conflict_columns, conflict_predicate, update_columns): (
+        Vec<<Entity as EntityTrait>::Column>,
+        Option<sea_orm::Condition>,
+        Vec<<Entity as EntityTrait>::Column>,
+    ),
+    items: Vec<UpsertQueryBuilder<'a, C, Entity, ActiveModel, ModelWithRelations, T>>,
+) -> Result<Vec<ModelWithRelations>, sea_orm::DbErr>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ActiveModel:
+        sea_orm::ActiveModelTrait<Entity = Entity> + sea_orm::ActiveModelBehavior + Send + 'static,
+    ModelWithRelations: FromModel<<Entity as EntityTrait>::Model>,
+    T: MergeInto<ActiveModel>,
+{
+    let sql_comments = items.iter().any(|item| item.sql_comments);
+    let rows: Vec<ActiveModel> = items
+        .into_iter()
+        .map(|item| {
+            let (mut active_model, _, _, _) = item.create;
+            merge_native_changes(&conflict_columns, &mut active_model, &item.update, None);
+            active_model
+        })
+        .collect();
+    let on_conflict = conflict_clause(conflict_columns, conflict_predicate, update_columns);
+    let mut insert = Entity::insert_many(rows).on_conflict(on_conflict).into_query();
+    insert.returning_all();
+    let stmt = txn.get_database_backend().build(&insert);
+    let conn = crate::hooks::CommentedConnection::new(txn, sql_comments);
+    let models = Entity::find().from_raw_sql(stmt).all(&conn).await?;
+    Ok(models.into_iter().map(ModelWithRelations::from_model).collect())
+}
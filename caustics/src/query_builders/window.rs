@@ -0,0 +1,183 @@
+use sea_orm::sea_query::{Alias, Expr, OverStatement, SimpleExpr, WindowStatement};
+use sea_orm::{ConnectionTrait, EntityTrait, QuerySelect, QueryTrait};
+
+use super::{ManyQueryBuilder, OrderByDescriptor};
+use crate::{FromModel, HasRelationMetadata};
+
+/// A window function computed per row, e.g. `Window::rank().order_by(user::age::order(SortOrder::Desc))`
+/// for `RANK() OVER (ORDER BY age DESC)`.
+///
+/// These are the ranking functions Postgres and SQLite (3.25+) both support.
+#[derive(Clone, Debug)]
+pub struct Window {
+    function: SimpleExpr,
+    partition_by: Vec<SimpleExpr>,
+    order_by: Vec<OrderByDescriptor>,
+}
+
+impl Window {
+    fn new(function: &str) -> Self {
+        Self {
+            function: Expr::cust(function),
+            partition_by: Vec::new(),
+            order_by: Vec::new(),
+        }
+    }
+
+    /// `ROW_NUMBER()`: 1, 2, 3, ... with ties numbered arbitrarily
+    pub fn row_number() -> Self {
+        Self::new("ROW_NUMBER()")
+    }
+
+    /// `RANK()`: ties share a rank and leave a gap after them
+    pub fn rank() -> Self {
+        Self::new("RANK()")
+    }
+
+    /// `DENSE_RANK()`: ties share a rank without leaving gaps
+    pub fn dense_rank() -> Self {
+        Self::new("DENSE_RANK()")
+    }
+
+    /// `NTILE(buckets)`: the 1-based bucket of the row when split into `buckets` even groups
+    pub fn ntile(buckets: u32) -> Self {
+        Self::new(&format!("NTILE({})", buckets))
+    }
+
+    /// Restart the numbering for each distinct value of `expr`
+    pub fn partition_by<E: sea_orm::IntoSimpleExpr>(mut self, expr: E) -> Self {
+        self.partition_by.push(expr.into_simple_expr());
+        self
+    }
+
+    /// Order rows within each partition; accepts the same specs as `find_many().order_by`
+    pub fn order_by<T: Into<OrderByDescriptor>>(mut self, spec: T) -> Self {
+        self.order_by.push(spec.into());
+        self
+    }
+
+    fn over(&self) -> WindowStatement {
+        let mut window = WindowStatement::new();
+        for expr in &self.partition_by {
+            window.add_partition_by(expr.clone());
+        }
+        for (expr, order) in super::many::effective_order_bys(&self.order_by) {
+            window.order_by_expr(expr, order);
+        }
+        window
+    }
+}
+
+/// A `find_many` with window function columns selected next to the model's columns.
+///
+/// Rows are read with `exec_as` into any `FromQueryResult` type, typically a
+/// `select_struct!(Name { ... })` whose fields name model columns and window aliases.
+pub struct WindowQueryBuilder<'a, C: ConnectionTrait, Entity: EntityTrait, ModelWithRelations> {
+    pub inner: ManyQueryBuilder<'a, C, Entity, ModelWithRelations>,
+    pub windows: Vec<(&'static str, Window)>,
+}
+
+impl<'a, C, Entity, ModelWithRelations> ManyQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+{
+    /// Select `window` as an extra column named `alias`, e.g.
+    /// `.window("rank", Window::row_number().order_by(user::age::order(SortOrder::Desc)))`
+    pub fn window(
+        self,
+        alias: &'static str,
+        window: Window,
+    ) -> WindowQueryBuilder<'a, C, Entity, ModelWithRelations> {
+        WindowQueryBuilder {
+            inner: self,
+            windows: vec![(alias, window)],
+        }
+    }
+}
+
+impl<'a, C, Entity, ModelWithRelations> WindowQueryBuilder<'a, C, Entity, ModelWithRelations>
+where
+    C: ConnectionTrait,
+    Entity: EntityTrait,
+    ModelWithRelations: FromModel<Entity::Model>
+        + HasRelationMetadata<ModelWithRelations>
+        + crate::types::ApplyNestedIncludes<C>
+        + Send
+        + 'static,
+{
+    /// Select another window function as `alias`
+    pub fn window(mut self, alias: &'static str, window: Window) -> Self {
+        self.windows.push((alias, window));
+        self
+    }
+
+    fn built_select(&self) -> sea_orm::Select<Entity> {
+        let mut query = self.inner.built_select();
+        for (alias, window) in &self.windows {
+            QueryTrait::query(&mut query).expr_window_as(
+                window.function.clone(),
+                window.over(),
+                Alias::new(*alias),
+            );
+        }
+        query
+    }
+
+    /// SQL text this query would be sent as
+    pub fn to_sql(&self) -> String {
+        let mut query = self.built_select();
+        crate::statement_cache::to_sql(
+            self.inner.database_backend,
+            QueryTrait::query(&mut query),
+            self.inner.sql_comments,
+        )
+    }
+
+    /// Run the query and map each row into `T`, reading model columns and window aliases by name
+    pub async fn exec_as<T: sea_orm::FromQueryResult>(self) -> Result<Vec<T>, sea_orm::DbErr> {
+        self.inner.error_mapper.clone().run(async move {
+            if let Some(message) = self.inner.invalid_pagination.clone() {
+                return Err(crate::types::CausticsError::InvalidPagination { message }.into());
+            }
+            super::check_filter(&self.inner.invalid_filter)?;
+            if !self.inner.relations_to_fetch.is_empty() {
+                return Err(crate::types::CausticsError::QueryValidation {
+                    message: "window does not support relations; drop with/include".to_string(),
+                }
+                .into());
+            }
+            if self.inner.distinct_on_ordered {
+                super::many::check_distinct_on_order(
+                    self.inner.distinct_on_fields.as_deref(),
+                    &self.inner.pending_order_bys,
+                )?;
+            }
+            let mut query = self.built_select();
+            // Without an explicit take, fetch one row past the cap so exceeding it can be detected
+            let row_cap = self.inner.max_rows.filter(|_| !self.inner.has_limit);
+            if let Some(cap) = row_cap {
+                query = query.limit(cap.saturating_add(1));
+            }
+            let mut rows = query.into_model::<T>().all(self.inner.conn).await?;
+            if let Some(cap) = row_cap.filter(|cap| rows.len() as u64 > *cap) {
+                return Err(crate::types::CausticsError::RowLimitExceeded {
+                    entity: core::any::type_name::<Entity>().to_string(),
+                    limit: cap,
+                }
+                .into());
+            }
+            // Rows were fetched in reversed order; hand them back in the requested one
+            if self.inner.reverse_order {
+                rows.reverse();
+            }
+            Ok(rows)
+        })
+        .await
+    }
+}
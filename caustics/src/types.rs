@@ -37,6 +37,16 @@ pub enum CausticsError {
     QueryValidation {
         message: String,
     },
+    Timeout {
+        timeout_ms: u128,
+    },
+    RowLimitExceeded {
+        entity: String,
+        limit: u64,
+    },
+    InvalidPagination {
+        message: String,
+    },
 
     // Client initialization errors
     NewClientError {
@@ -99,6 +109,14 @@ pub enum CausticsError {
         operation: String,
         message: String,
     },
+    /// A failure the database itself reported, with its error code when the driver exposes
+    /// one (the SQLSTATE on Postgres and MySQL, the extended result code on SQLite) and the
+    /// original error
+    Database {
+        code: Option<String>,
+        message: String,
+        source: std::sync::Arc<sea_orm::DbErr>,
+    },
 }
 
 impl core::fmt::Display for CausticsError {
@@ -150,6 +168,23 @@ impl core::fmt::Display for CausticsError {
             CausticsError::QueryValidation { message } => {
                 write!(f, "CausticsError::QueryValidation: {}", message)
             }
+            CausticsError::Timeout { timeout_ms } => {
+                write!(
+                    f,
+                    "CausticsError::Timeout: query exceeded {}ms",
+                    timeout_ms
+                )
+            }
+            CausticsError::RowLimitExceeded { entity, limit } => {
+                write!(
+                    f,
+                    "CausticsError::RowLimitExceeded: entity='{}' limit={} (add take() to fetch more)",
+                    entity, limit
+                )
+            }
+            CausticsError::InvalidPagination { message } => {
+                write!(f, "CausticsError::InvalidPagination: {}", message)
+            }
 
             // Client initialization errors
             CausticsError::NewClientError { message, cause } => {
@@ -258,13 +293,60 @@ impl core::fmt::Display for CausticsError {
                     operation, message
                 )
             }
+            CausticsError::Database { code, message, .. } => {
+                write!(
+                    f,
+                    "CausticsError::Database: code='{}' message='{}'",
+                    code.as_deref().unwrap_or(""),
+                    message
+                )
+            }
         }
     }
 }
 
 impl From<CausticsError> for sea_orm::DbErr {
     fn from(err: CausticsError) -> Self {
-        sea_orm::DbErr::Custom(err.to_string())
+        match err {
+            // A database failure passed through unchanged keeps its original error
+            CausticsError::Database { source, .. } => std::sync::Arc::try_unwrap(source)
+                .unwrap_or_else(|source| sea_orm::DbErr::Custom(source.to_string())),
+            err => sea_orm::DbErr::Custom(err.to_string()),
+        }
+    }
+}
+
+/// Translates database failures into application errors: receives a `CausticsError::Database`
+/// and returns the error the exec call should surface (`err.into()` keeps the original)
+pub type DatabaseErrorMapper =
+    std::sync::Arc<dyn Fn(CausticsError) -> sea_orm::DbErr + Send + Sync>;
+
+/// A client's database error mapper, carried by every query builder it creates
+#[derive(Clone, Default)]
+pub struct ErrorMapper(Option<DatabaseErrorMapper>);
+
+impl ErrorMapper {
+    pub fn new(mapper: Option<DatabaseErrorMapper>) -> Self {
+        Self(mapper)
+    }
+
+    /// Pass `err` through the mapper when it is a database failure
+    pub fn map(&self, err: sea_orm::DbErr) -> sea_orm::DbErr {
+        let Some(mapper) = &self.0 else {
+            return err;
+        };
+        match CausticsError::from_db_err(err) {
+            Ok(database_error) => mapper(database_error),
+            Err(err) => err,
+        }
+    }
+
+    /// Await `exec`, mapping the database failure it may return
+    pub async fn run<T>(
+        &self,
+        exec: impl std::future::Future<Output = Result<T, sea_orm::DbErr>>,
+    ) -> Result<T, sea_orm::DbErr> {
+        exec.await.map_err(|err| self.map(err))
     }
 }
 
@@ -403,6 +485,25 @@ impl CausticsError {
         }
     }
 
+    /// `err` as `CausticsError::Database` when the database reported it, or `err` back when it
+    /// was raised before a statement reached the database (validation, missing rows, custom errors)
+    pub fn from_db_err(err: sea_orm::DbErr) -> Result<Self, sea_orm::DbErr> {
+        let runtime = match &err {
+            sea_orm::DbErr::Exec(runtime)
+            | sea_orm::DbErr::Query(runtime)
+            | sea_orm::DbErr::Conn(runtime) => runtime,
+            _ => return Err(err),
+        };
+        match runtime {
+            sea_orm::RuntimeErr::SqlxError(sea_orm::sqlx::Error::Database(db)) => Ok(Self::Database {
+                code: db.code().map(|code| code.into_owned()),
+                message: db.message().to_string(),
+                source: std::sync::Arc::new(err),
+            }),
+            _ => Err(err),
+        }
+    }
+
     /// Check if this is a recoverable error
     pub fn is_recoverable(&self) -> bool {
         match self {
@@ -506,6 +607,9 @@ impl CausticsError {
             Self::DatabaseError { operation, message } => {
                 format!("Database {} operation failed: {}", operation, message)
             }
+            Self::Database { message, .. } => {
+                format!("Database error: {}", message)
+            }
             _ => self.to_string(),
         }
     }
@@ -544,6 +648,15 @@ pub enum SortOrder {
     Desc,
 }
 
+impl From<SortOrder> for sea_orm::Order {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Asc => sea_orm::Order::Asc,
+            SortOrder::Desc => sea_orm::Order::Desc,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum NullsOrder {
     First,
@@ -568,8 +681,12 @@ pub enum FieldOp {
     InVec(Vec<sea_orm::Value>),
     NotInVec(Vec<sea_orm::Value>),
     Contains(String),
+    /// Matches if any of the substrings is present
+    ContainsAny(Vec<String>),
     StartsWith(String),
     EndsWith(String),
+    /// Raw LIKE pattern: `%` and `_` are wildcards, `\` escapes them
+    Like(String),
     IsNull,
     IsNotNull,
     // JSON-specific operations
@@ -581,12 +698,18 @@ pub enum FieldOp {
     JsonArrayStartsWith(serde_json::Value),
     JsonArrayEndsWith(serde_json::Value),
     JsonObjectContains(String),
+    /// Postgres JSONPath predicate (`jsonb_path_exists`)
+    JsonPathMatch(String),
     // JSON null handling flags
     JsonNull(JsonNullValueFilter),
     // Relation operations
     Some(()),
     Every(()),
     None(()),
+    /// Compare the number of related records using the wrapped comparison
+    Count(Box<FieldOp>),
+    /// Match values returned by a single-column subquery (`col IN (SELECT ...)`)
+    InSubquery(Box<sea_orm::sea_query::SelectStatement>),
 }
 
 // Keeping type for future, but not used by FieldOp right now
@@ -597,6 +720,30 @@ pub enum JsonNullValueFilter {
     AnyNull,
 }
 
+/// Rewrites a timestamp read from the database to the UTC offset, keeping the instant;
+/// applied to fields marked `#[caustics(utc)]`
+pub trait NormalizeUtc {
+    fn normalize_utc(self) -> Self;
+}
+
+impl NormalizeUtc for chrono::DateTime<chrono::FixedOffset> {
+    fn normalize_utc(self) -> Self {
+        self.with_timezone(&chrono::Utc).fixed_offset()
+    }
+}
+
+impl NormalizeUtc for chrono::DateTime<chrono::Utc> {
+    fn normalize_utc(self) -> Self {
+        self
+    }
+}
+
+impl<T: NormalizeUtc> NormalizeUtc for Option<T> {
+    fn normalize_utc(self) -> Self {
+        self.map(NormalizeUtc::normalize_utc)
+    }
+}
+
 /// Trait for converting any type to sea_orm::Value
 pub trait ToSeaOrmValue {
     fn to_sea_orm_value(&self) -> sea_orm::Value;
@@ -912,6 +1059,36 @@ impl ToSeaOrmValue for &sea_orm::Value {
     }
 }
 
+/// Trait for custom column types (newtypes, domain wrappers) used in filters and keys.
+///
+/// Field types the code generator does not recognise are bound through this trait
+/// instead of being assumed to be `String`.
+pub trait ToCausticsValue {
+    fn to_caustics_value(&self) -> sea_orm::Value;
+
+    /// Key used when this value links two entities.
+    ///
+    /// There is no default: a type that can't be keyed would otherwise end up compared
+    /// as its display string.
+    fn to_caustics_key(&self) -> crate::CausticsKey;
+}
+
+/// Counterpart of [`ToCausticsValue`]: rebuilds a custom column type from a key
+pub trait FromCausticsValue: Sized {
+    fn from_caustics_value(value: sea_orm::Value) -> Option<Self>;
+
+    fn from_caustics_key(key: crate::CausticsKey) -> Option<Self> {
+        Self::from_caustics_value(key.to_db_value())
+    }
+}
+
+// Custom types only implement ToCausticsValue; filters take ToSeaOrmValue
+impl<T: ToCausticsValue> ToSeaOrmValue for T {
+    fn to_sea_orm_value(&self) -> sea_orm::Value {
+        self.to_caustics_value()
+    }
+}
+
 // Helper function for converting enums to sea_orm::Value
 pub fn enum_to_sea_orm_value<T: ToString>(value: &T) -> sea_orm::Value {
     sea_orm::Value::String(Some(Box::new(value.to_string())))
@@ -957,6 +1134,130 @@ impl FieldOp {
     pub fn not_in_vec<T: ToSeaOrmValue>(values: Vec<T>) -> Self {
         Self::NotInVec(values.into_iter().map(|v| v.to_sea_orm_value()).collect())
     }
+
+    /// The LIKE pattern for `contains`/`starts_with`/`ends_with` (value escaped) or `like` (as given)
+    pub fn like_pattern(&self) -> Option<String> {
+        match self {
+            Self::Contains(s) => Some(format!("%{}%", escape_like(s))),
+            Self::StartsWith(s) => Some(format!("{}%", escape_like(s))),
+            Self::EndsWith(s) => Some(format!("%{}", escape_like(s))),
+            Self::Like(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    /// The escaped `%value%` patterns of a `contains_any`
+    pub fn like_any_patterns(&self) -> Option<Vec<String>> {
+        match self {
+            Self::ContainsAny(values) => Some(values.iter().map(|s| format!("%{}%", escape_like(s))).collect()),
+            _ => None,
+        }
+    }
+
+    /// Hash what decides the SQL text of this operation, leaving out the values bound into it,
+    /// so filters that differ only in their values share a statement-cache entry.
+    ///
+    /// Operations whose SQL is built from their arguments hash those arguments in full.
+    pub fn hash_shape<H: std::hash::Hasher>(&self, state: &mut H) {
+        use std::hash::Hash;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Equals(v) | Self::NotEquals(v) | Self::Gt(v) | Self::Lt(v) | Self::Gte(v) | Self::Lte(v) => {
+                std::mem::discriminant(v).hash(state)
+            }
+            Self::InVec(values) | Self::NotInVec(values) => values.len().hash(state),
+            Self::ContainsAny(values) => values.len().hash(state),
+            Self::Contains(_) | Self::StartsWith(_) | Self::EndsWith(_) | Self::Like(_) => {}
+            Self::IsNull | Self::IsNotNull => {}
+            Self::JsonNull(flag) => std::mem::discriminant(flag).hash(state),
+            Self::Count(inner) => inner.hash_shape(state),
+            other => format!("{:?}", other).hash(state),
+        }
+    }
+}
+
+/// Escape `%`, `_` and `\` so `value` matches literally in a LIKE pattern with `ESCAPE '\'`
+pub fn escape_like(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// A LIKE pattern with `\` as its escape character
+pub fn like_expr(pattern: String) -> sea_query::LikeExpr {
+    sea_query::LikeExpr::new(pattern).escape('\\')
+}
+
+/// `expr LIKE pattern ESCAPE '\'`; case-insensitively `ILIKE` on Postgres and
+/// `UPPER(expr) LIKE UPPER(pattern)` elsewhere
+pub fn like_condition(
+    expr: sea_query::SimpleExpr,
+    pattern: String,
+    mode: QueryMode,
+    backend: sea_orm::DatabaseBackend,
+) -> sea_query::SimpleExpr {
+    use sea_query::{BinOper, Expr, ExprTrait, Func, SimpleExpr};
+
+    match (mode, backend) {
+        (QueryMode::Default, _) => Expr::expr(expr).like(like_expr(pattern)),
+        // Written out since sea-query parenthesizes `ILIKE`'s right side, splitting off the ESCAPE
+        (QueryMode::Insensitive, sea_orm::DatabaseBackend::Postgres) => {
+            Expr::cust_with_exprs("$1 ILIKE $2 ESCAPE '\\'", [expr, Expr::val(pattern).into()])
+        }
+        (QueryMode::Insensitive, _) => {
+            let pattern = SimpleExpr::Binary(
+                Box::new(Func::upper(Expr::val(pattern)).into()),
+                BinOper::Escape,
+                Box::new(SimpleExpr::Constant('\\'.into())),
+            );
+            Func::upper(expr).binary(BinOper::Like, pattern)
+        }
+    }
+}
+
+/// `expr LIKE p1 OR expr LIKE p2 ...`; never matches when `patterns` is empty
+pub fn like_any_expr(expr: sea_query::SimpleExpr, patterns: Vec<String>) -> sea_query::SimpleExpr {
+    use sea_query::Expr;
+
+    patterns
+        .into_iter()
+        .map(|pattern| Expr::expr(expr.clone()).like(like_expr(pattern)))
+        .reduce(|acc, like| acc.or(like))
+        .unwrap_or_else(|| Expr::cust("1 = 2"))
+}
+
+/// Match any of `patterns`: `expr LIKE ANY ('{...}'::text[])` (or `ILIKE ANY`) on Postgres,
+/// OR'd [`like_condition`]s elsewhere
+pub fn like_any_condition(
+    expr: sea_query::SimpleExpr,
+    patterns: Vec<String>,
+    mode: QueryMode,
+    backend: sea_orm::DatabaseBackend,
+) -> sea_query::SimpleExpr {
+    use sea_query::Expr;
+
+    if patterns.is_empty() {
+        return Expr::cust("1 = 2");
+    }
+    match (mode, backend) {
+        (_, sea_orm::DatabaseBackend::Postgres) => {
+            // Bound as one `text[]` literal; Postgres' default LIKE escape is already `\`
+            let op = if mode == QueryMode::Insensitive { "ILIKE" } else { "LIKE" };
+            let patterns = Expr::val(crate::atomic::pg_text_array(&patterns)).into();
+            Expr::cust_with_exprs(format!("$1 {} ANY ($2::text[])", op), [expr, patterns])
+        }
+        (QueryMode::Default, _) => like_any_expr(expr, patterns),
+        (QueryMode::Insensitive, _) => patterns
+            .into_iter()
+            .map(|pattern| like_condition(expr.clone(), pattern, mode, backend))
+            .reduce(|acc, like| acc.or(like))
+            .expect("patterns is non-empty"),
+    }
 }
 
 /// Trait for converting a model to a model with relations
@@ -967,6 +1268,16 @@ pub trait FromModel<M> {
 /// Trait for merging values into an ActiveModel
 pub trait MergeInto<AM> {
     fn merge_into(&self, model: &mut AM);
+
+    /// Server-side column expression for atomic updates (e.g. `col = col || ?`).
+    /// These are set in the same SQL `UPDATE` as the regular changes, avoiding a
+    /// read-modify-write round trip.
+    fn column_expr(
+        &self,
+        _backend: sea_orm::DatabaseBackend,
+    ) -> Option<(sea_query::DynIden, sea_query::SimpleExpr)> {
+        None
+    }
 }
 
 // Default implementation for unit type
@@ -1001,7 +1312,11 @@ pub struct RelationFilter {
     pub order_by: Vec<(String, SortOrder)>,
     pub cursor_id: Option<CausticsKey>,
     pub include_count: bool,
+    /// Extra filters applied only to the `_count` query, on top of `filters`
+    pub count_filters: Vec<Filter>,
     pub distinct: bool,
+    /// Row scope of the client that asked for the include, applied to targets with the field
+    pub scope: Option<RowScope>,
 }
 
 /// Central PCR-like include builder that accumulates generic include state
@@ -1015,6 +1330,7 @@ pub struct IncludeBuilderCore {
     pub order_by: Vec<(String, SortOrder)>,
     pub cursor_id: Option<CausticsKey>,
     pub include_count: bool,
+    pub count_filters: Vec<Filter>,
     pub distinct: bool,
 }
 
@@ -1046,6 +1362,11 @@ impl IncludeBuilderCore {
     pub fn enable_count(&mut self) {
         self.include_count = true;
     }
+    /// Count only the related rows matching `filters`
+    pub fn enable_count_where(&mut self, filters: Vec<Filter>) {
+        self.include_count = true;
+        self.count_filters.extend(filters);
+    }
     pub fn enable_distinct(&mut self) {
         self.distinct = true;
     }
@@ -1060,7 +1381,9 @@ impl IncludeBuilderCore {
             order_by: self.order_by,
             cursor_id: self.cursor_id,
             include_count: self.include_count,
+            count_filters: self.count_filters,
             distinct: self.distinct,
+            scope: None,
         }
     }
 }
@@ -1075,6 +1398,45 @@ impl RelationFilterTrait for RelationFilter {
     }
 }
 
+/// Column of entity `E` holding values of type `T`, so column-to-column updates
+/// (`user::age::set_from_column(user::tenant_id::column())`) only accept same-typed sources.
+pub struct TypedColumn<E: sea_orm::EntityTrait, T> {
+    pub column: E::Column,
+    _value: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<E: sea_orm::EntityTrait, T> TypedColumn<E, T> {
+    pub fn new(column: E::Column) -> Self {
+        Self {
+            column,
+            _value: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Foreign key of a belongs_to relation, returned by a function named after the relation
+/// (e.g. `post::author`). `Target` is the referenced entity and `W` the holding entity's
+/// `WhereParam`, so `user::id::in_related(post::author, filters)` selects the keys
+/// referenced by the matching posts.
+pub struct RelationLink<Target, W> {
+    /// Build `SELECT <fk> FROM <holder> WHERE <filters>`
+    pub key_subquery: fn(Vec<W>) -> sea_orm::sea_query::SelectStatement,
+    pub _target: std::marker::PhantomData<fn() -> Target>,
+}
+
+/// A query usable as the right-hand side of `IN (SELECT ...)`, e.g.
+/// `client.post().find_many(filters).select(post::select!(user_id))` passed to `user::id::in_subquery`.
+/// It should select exactly one column.
+pub trait IntoSubquery {
+    fn into_subquery(self) -> sea_orm::sea_query::SelectStatement;
+}
+
+impl IntoSubquery for sea_orm::sea_query::SelectStatement {
+    fn into_subquery(self) -> sea_orm::sea_query::SelectStatement {
+        self
+    }
+}
+
 /// Advanced relation operations for filtering on relations
 /// These follow the Prisma Client Rust pattern for relation filtering
 #[derive(Debug, Clone)]
@@ -1120,6 +1482,17 @@ impl RelationCondition {
             relation_table: None,
         }
     }
+
+    pub fn count(relation_name: &'static str, comparison: FieldOp) -> Self {
+        Self {
+            relation_name,
+            operation: FieldOp::Count(Box::new(comparison)),
+            filters: vec![],
+            foreign_key_column: None,
+            current_table: None,
+            relation_table: None,
+        }
+    }
 }
 
 /// Trait for dynamic relation fetching
@@ -1170,8 +1543,8 @@ pub struct RelationDescriptor<Selected> {
     pub target_primary_key_column: &'static str,
     // The target entity name extracted from "to" attribute (for runtime primary key resolution)
     pub target_entity_name: Option<&'static str>,
-    // Whether the foreign key is nullable
-    pub is_foreign_key_nullable: bool,
+    // Whether the foreign key column accepts NULL
+    pub is_foreign_key_nullable: fn() -> bool,
     // Whether this relation is has_many
     pub is_has_many: bool,
     // Whether this relation is has_one
@@ -1198,6 +1571,28 @@ pub trait EntitySelection: Sized {
         let _ = alias;
         None
     }
+    /// Aliases of the primary key fields, which every selection fetches so a row can be reloaded
+    fn primary_key_aliases() -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// Add the primary key columns a selection is missing, both to the SELECT list and to the
+/// aliases read back from each row
+pub(crate) fn retain_primary_key<S: EntitySelection>(
+    selected: &mut Vec<(sea_query::SimpleExpr, String)>,
+    requested: &mut Vec<String>,
+) {
+    for alias in S::primary_key_aliases() {
+        if !selected.iter().any(|(_, a)| a == alias) {
+            if let Some(expr) = S::column_for_alias(alias) {
+                selected.push((expr, alias.to_string()));
+            }
+        }
+        if !requested.iter().any(|a| a == alias) {
+            requested.push(alias.to_string());
+        }
+    }
 }
 
 /// Helper trait to extract primary key value generically from ModelWithRelations
@@ -1354,6 +1749,32 @@ pub trait EntityFetcher<C: sea_orm::ConnectionTrait> {
                 + 'a,
         >,
     >;
+
+    /// Fetch the entities with the given primary keys in one query, each paired with its key
+    /// (used to resolve polymorphic relations), keeping only rows inside `scope`. Fetchers
+    /// that can't look rows up by a single key, e.g. for composite primary keys, keep this
+    /// default and report `QueryValidation`.
+    #[allow(clippy::type_complexity)]
+    fn fetch_by_primary_keys<'a>(
+        &'a self,
+        conn: &'a C,
+        keys: Vec<CausticsKey>,
+        scope: Option<&'a RowScope>,
+    ) -> std::pin::Pin<
+        Box<
+            dyn std::future::Future<Output = Result<Vec<(CausticsKey, Box<dyn Any + Send>)>, sea_orm::DbErr>>
+                + Send
+                + 'a,
+        >,
+    > {
+        let _ = (conn, keys, scope);
+        Box::pin(async {
+            Err(CausticsError::QueryValidation {
+                message: "this entity can't be fetched by a single primary key".to_string(),
+            }
+            .into())
+        })
+    }
 }
 
 /// Registry for mapping entity names to their fetchers
@@ -1362,6 +1783,11 @@ pub trait EntityRegistry<C: sea_orm::ConnectionTrait> {
     fn get_fetcher(&self, entity_name: &str) -> Option<&dyn EntityFetcher<C>>;
 }
 
+/// The generated registry a row type resolves its includes through, on any connection
+pub trait RegistryProvider<C: sea_orm::ConnectionTrait> {
+    fn registry() -> &'static (dyn EntityRegistry<C> + Sync);
+}
+
 /// Trait for entity type information and key conversion
 pub trait EntityTypeRegistry {
     /// Get the primary key type for a given entity
@@ -1416,6 +1842,27 @@ where
     }
 }
 
+impl<Col> IntoOrderByExpr for (Col, SortOrder)
+where
+    Col: sea_orm::IntoSimpleExpr,
+{
+    fn into_order_by_expr(self) -> (sea_query::SimpleExpr, sea_orm::Order) {
+        (self.0.into_simple_expr(), self.1.into())
+    }
+}
+
+/// A `distinct_on` key: its column expression, plus the typed column when known (which
+/// enables native `DISTINCT ON` on Postgres)
+pub trait IntoDistinctOnKey<Entity: sea_orm::EntityTrait> {
+    fn into_distinct_on_key(self) -> (sea_query::SimpleExpr, Option<Entity::Column>);
+}
+
+impl<Entity: sea_orm::EntityTrait> IntoDistinctOnKey<Entity> for sea_query::SimpleExpr {
+    fn into_distinct_on_key(self) -> (sea_query::SimpleExpr, Option<Entity::Column>) {
+        (self, None)
+    }
+}
+
 /// Combined order spec that can optionally carry a NullsOrder hint
 pub trait IntoOrderSpec {
     fn into_order_spec(self) -> (sea_query::SimpleExpr, sea_orm::Order, Option<NullsOrder>);
@@ -1441,6 +1888,82 @@ where
     }
 }
 
+/// Raw SQL ordering fragment such as `LOWER(name) DESC`, appended without parameters.
+///
+/// Only pass trusted input. A trailing `ASC`/`DESC` sets the direction (ascending
+/// otherwise) so the fragment still flips when the query order is reversed; a
+/// `NULLS FIRST`/`NULLS LAST` after it sets the NULL placement.
+#[derive(Clone, Debug)]
+pub struct RawOrderBy(pub String);
+
+/// `fragment` split before its last whitespace-separated word
+fn split_last_word(fragment: &str) -> Option<(&str, &str)> {
+    fragment
+        .trim_end()
+        .rsplit_once(char::is_whitespace)
+        .map(|(head, word)| (head.trim_end(), word))
+}
+
+impl IntoOrderSpec for RawOrderBy {
+    fn into_order_spec(self) -> (sea_query::SimpleExpr, sea_orm::Order, Option<NullsOrder>) {
+        let fragment = self.0.trim();
+        let placement = split_last_word(fragment).and_then(|(head, word)| {
+            let nulls = if word.eq_ignore_ascii_case("first") {
+                NullsOrder::First
+            } else if word.eq_ignore_ascii_case("last") {
+                NullsOrder::Last
+            } else {
+                return None;
+            };
+            match split_last_word(head) {
+                Some((head, keyword)) if keyword.eq_ignore_ascii_case("nulls") => Some((head, nulls)),
+                _ => None,
+            }
+        });
+        let (fragment, nulls) = match placement {
+            Some((head, nulls)) => (head, Some(nulls)),
+            None => (fragment, None),
+        };
+        let (expr, order) = match split_last_word(fragment) {
+            Some((expr, dir)) if dir.eq_ignore_ascii_case("asc") => (expr, sea_orm::Order::Asc),
+            Some((expr, dir)) if dir.eq_ignore_ascii_case("desc") => (expr, sea_orm::Order::Desc),
+            _ => (fragment, sea_orm::Order::Asc),
+        };
+        (sea_query::Expr::cust(expr), order, nulls)
+    }
+}
+
+/// Column/value pair that a scoped client ANDs into every filter and stamps on every create,
+/// e.g. `tenant_id = 42` for multi-tenant tables
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowScope {
+    /// Model field name, e.g. `tenant_id`
+    pub field: String,
+    pub value: sea_orm::Value,
+}
+
+/// Settings a generated client passes on to its transactions and entity clients
+#[derive(Clone, Default)]
+pub struct ClientOptions {
+    /// Rows every query is restricted to, set by `scoped`
+    pub scope: Option<RowScope>,
+    /// Prefix SQL with the thread's correlation id as a comment
+    pub sql_comments: bool,
+    /// Row cap for `find_many` without `take`
+    pub max_rows: Option<u64>,
+    pub error_mapper: ErrorMapper,
+}
+
+impl RowScope {
+    /// Restrict `relation` and its nested includes to this scope
+    pub fn restrict(&self, relation: &mut RelationFilter) {
+        relation.scope = Some(self.clone());
+        for nested in &mut relation.nested_includes {
+            self.restrict(nested);
+        }
+    }
+}
+
 /// Trait for models capable of applying nested relation filters/includes
 pub trait ApplyNestedIncludes<C: sea_orm::ConnectionTrait> {
     fn apply_relation_filter<'a>(
@@ -1480,7 +2003,9 @@ impl<C: sea_orm::ConnectionTrait> EntityResolver<C> {
                 order_by: vec![],
                 cursor_id: None,
                 include_count: false,
+                count_filters: vec![],
                 distinct: false,
+                scope: None,
             };
             fetcher
                 .fetch_by_foreign_key_with_selection(
@@ -1879,6 +2404,21 @@ pub trait SetParamInfo {
     /// Check if this is a has_many set operation
     fn is_has_many_set_operation(&self) -> bool;
 
+    /// Check if this is a has_many disconnect operation (a subset of set operations)
+    fn is_has_many_disconnect_operation(&self) -> bool {
+        false
+    }
+
+    /// Check if this is a has_one connect, which replaces the current child (a subset of set operations)
+    fn is_has_one_connect_operation(&self) -> bool {
+        false
+    }
+
+    /// Check if this is a plain column assignment (no atomic or relation operation)
+    fn is_scalar_set(&self) -> bool {
+        false
+    }
+
     /// Extract the relation name from a has_many set operation
     fn extract_relation_name(&self) -> Option<&'static str>;
 
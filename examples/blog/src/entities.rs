@@ -19,6 +19,8 @@ pub mod user {
         pub updated_at: DateTime<FixedOffset>,
         #[sea_orm(nullable)]
         pub deleted_at: Option<DateTime<FixedOffset>>,
+        #[sea_orm(nullable)]
+        pub tenant_id: Option<i32>,
     }
 
     #[derive(Caustics, Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -54,8 +56,10 @@ pub mod post {
         pub title: String,
         #[sea_orm(nullable)]
         pub content: Option<String>,
+        /// #[caustics(utc)]
         #[sea_orm(created_at)]
         pub created_at: DateTime<FixedOffset>,
+        /// #[caustics(utc)]
         #[sea_orm(updated_at)]
         pub updated_at: DateTime<FixedOffset>,
         #[sea_orm(column_name = "user_id")]
@@ -95,3 +99,58 @@ pub mod post {
 
     impl ActiveModelBehavior for ActiveModel {}
 }
+
+#[caustics]
+pub mod archived_user {
+    use caustics_macros::Caustics;
+    use sea_orm::entity::prelude::*;
+
+    /// Soft-deleted users moved out of `users`, see `create_from_select`
+    #[derive(Caustics, Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "archived_users")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub email: String,
+        pub name: String,
+        #[sea_orm(nullable)]
+        pub age: Option<i32>,
+        pub created_at: DateTime<FixedOffset>,
+        pub updated_at: DateTime<FixedOffset>,
+        #[sea_orm(nullable)]
+        pub deleted_at: Option<DateTime<FixedOffset>>,
+        #[sea_orm(nullable)]
+        pub archive_reason: Option<String>,
+    }
+
+    #[derive(Caustics, Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[caustics]
+pub mod comment {
+    use caustics_macros::Caustics;
+    use sea_orm::entity::prelude::*;
+
+    /// Comments attached to either a post or a user through `commentable_type`
+    #[derive(Caustics, Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "comments")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false, caustics_default)]
+        pub id: Uuid,
+        pub body: String,
+        /// #[caustics(polymorphic(name = "commentable", id = "commentable_id", targets(post = "super::post", user = "super::user")))]
+        pub commentable_type: String,
+        pub commentable_id: Uuid,
+        /// #[caustics(db_default)]
+        #[sea_orm(default_expr = "Expr::current_timestamp()")]
+        pub created_at: DateTimeUtc,
+    }
+
+    #[derive(Caustics, Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
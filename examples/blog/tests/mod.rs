@@ -3,7 +3,7 @@
 pub mod helpers {
     use sea_orm::{Database, DatabaseConnection, Schema};
 
-    use blog::entities::{post, user};
+    use blog::entities::{archived_user, comment, post, user};
 
     pub async fn setup_test_db() -> DatabaseConnection {
         use sea_orm::ConnectionTrait;
@@ -26,6 +26,18 @@ pub mod helpers {
         let create_posts_sql = db.get_database_backend().build(create_posts);
         db.execute(create_posts_sql).await.unwrap();
 
+        // Create archived_users table
+        let mut archived_user_table = schema.create_table_from_entity(archived_user::Entity);
+        let create_archived_users = archived_user_table.if_not_exists();
+        let create_archived_users_sql = db.get_database_backend().build(create_archived_users);
+        db.execute(create_archived_users_sql).await.unwrap();
+
+        // Create comments table
+        let mut comment_table = schema.create_table_from_entity(comment::Entity);
+        let create_comments = comment_table.if_not_exists();
+        let create_comments_sql = db.get_database_backend().build(create_comments);
+        db.execute(create_comments_sql).await.unwrap();
+
         db
     }
 }
@@ -42,6 +54,62 @@ mod client_tests {
         // Test client creation
         assert!(client.db().ping().await.is_ok());
     }
+
+    #[test]
+    fn test_entity_metadata_lookup() {
+        let user = blog::CausticsClient::entity_metadata("User").expect("User metadata");
+        assert_eq!(user.table_name, "users");
+        assert_eq!(user.primary_key_field, "id");
+        let posts = user
+            .relations
+            .iter()
+            .find(|relation| relation.name == "Posts")
+            .unwrap_or_else(|| panic!("{:?}", user.relations));
+        assert_eq!(posts.target_entity, "post");
+        assert_eq!(posts.target_table_name, "posts");
+        assert_eq!(posts.relation_kind, "HasMany");
+        assert_eq!(posts.foreign_key_field, Some("user_id"));
+
+        // Lookup accepts snake_case and namespaced names
+        assert_eq!(blog::CausticsClient::entity_metadata("user").unwrap().name, user.name);
+        assert_eq!(blog::CausticsClient::entity_metadata("blog::User").unwrap().name, user.name);
+        assert!(blog::CausticsClient::entity_metadata("Missing").is_none());
+
+        let post = blog::CausticsClient::entity_metadata("Post").unwrap();
+        assert!(post.foreign_key_fields.contains(&"user_id"), "{:?}", post.foreign_key_fields);
+        let names: Vec<_> = blog::CausticsClient::entities_metadata().iter().map(|meta| meta.name).collect();
+        assert!(names.contains(&"User") && names.contains(&"Post"), "{:?}", names);
+    }
+
+    #[test]
+    fn test_entity_fields_reflection() {
+        use blog::entities::{post, user};
+
+        let fields: Vec<_> = user::fields()
+            .iter()
+            .map(|f| (f.name, f.column_name, f.rust_type, f.nullable, f.is_primary_key, f.is_foreign_key))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("id", "id", "Uuid", false, true, false),
+                ("email", "email", "String", false, false, false),
+                ("name", "name", "String", false, false, false),
+                ("age", "age", "Option<i32>", true, false, false),
+                ("created_at", "created_at", "DateTime<FixedOffset>", false, false, false),
+                ("updated_at", "updated_at", "DateTime<FixedOffset>", false, false, false),
+                ("deleted_at", "deleted_at", "Option<DateTime<FixedOffset>>", true, false, false),
+                ("tenant_id", "tenant_id", "Option<i32>", true, false, false),
+            ]
+        );
+
+        // Foreign keys and renamed columns
+        let find = |name: &str| post::fields().iter().find(|f| f.name == name).copied().unwrap();
+        assert!(find("user_id").is_foreign_key && !find("user_id").nullable);
+        assert!(find("reviewer_user_id").is_foreign_key && find("reviewer_user_id").nullable);
+        assert_eq!(find("custom_data").column_name, "customData");
+        assert!(!find("title").is_foreign_key);
+    }
 }
 
 mod query_builder_tests {
@@ -520,1894 +588,2612 @@ mod query_builder_tests {
     }
 
     #[tokio::test]
-    async fn test_order_nulls_first_and_last_many() {
-        use chrono::TimeZone;
+    async fn test_order_by_raw_expression() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 2, 1, 0, 0, 0)
-            .unwrap();
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for (email, name, age) in [
+            ("bob@example.com", "bob", 30),
+            ("alice@example.com", "Alice", 25),
+            ("carol@example.com", "Carol", 30),
+            ("dave@example.com", "dave", 25),
+        ] {
+            client
+                .user()
+                .create(
+                    email.to_string(),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
 
-        // Seed users with some null ages
-        let _u1 = client
-            .user()
-            .create(
-                "nulls1@example.com".to_string(),
-                "Nulls1".to_string(),
-                now,
-                now,
-                vec![user::age::set(None), user::deleted_at::set(None)],
-            )
-            .exec()
-            .await
-            .unwrap();
-        let _u2 = client
+        // Case-insensitive ordering (plain ORDER BY name would put uppercase first)
+        let users = client
             .user()
-            .create(
-                "nulls2@example.com".to_string(),
-                "Nulls2".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(10)), user::deleted_at::set(None)],
-            )
+            .find_many(vec![])
+            .order_by_raw("LOWER(name) ASC")
             .exec()
             .await
             .unwrap();
-        let _u3 = client
+        let names: Vec<_> = users.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "bob", "Carol", "dave"]);
+
+        // Composes with column-based ordering, in call order
+        let users = client
             .user()
-            .create(
-                "nulls3@example.com".to_string(),
-                "Nulls3".to_string(),
-                now,
-                now,
-                vec![user::age::set(None), user::deleted_at::set(None)],
-            )
+            .find_many(vec![])
+            .order_by(user::age::order(SortOrder::Desc))
+            .order_by_raw("LOWER(name) DESC")
             .exec()
             .await
             .unwrap();
-        let _u4 = client
+        let names: Vec<_> = users.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["Carol", "bob", "dave", "Alice"]);
+
+        // The trailing direction flips with a negative take
+        let last = client
             .user()
-            .create(
-                "nulls4@example.com".to_string(),
-                "Nulls4".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(20)), user::deleted_at::set(None)],
-            )
+            .find_many(vec![])
+            .order_by_raw("LOWER(name)")
+            .take(-1)
             .exec()
             .await
             .unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].name, "dave");
 
-        // Nulls first with ascending order
-        let users_nulls_first = client
+        // A trailing NULLS FIRST/LAST is the NULL placement, not part of the expression
+        client
             .user()
-            .find_many(vec![])
-            .order_by((
-                user::age::order(SortOrder::Asc),
-                caustics::NullsOrder::First,
-            ))
+            .create("eve@example.com".to_string(), "eve".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-        let ages_first: Vec<Option<i32>> = users_nulls_first.iter().map(|u| u.age).collect();
-        // Expect the first two to be None, followed by 10 then 20
-        assert!(ages_first.len() >= 4);
-        assert_eq!(ages_first[0], None);
-        assert_eq!(ages_first[1], None);
-        assert_eq!(ages_first[2], Some(10));
-        assert_eq!(ages_first[3], Some(20));
-
-        // Nulls last with ascending order
-        let users_nulls_last = client
+        let query = || client.user().find_many(vec![]).order_by_raw("age DESC NULLS LAST");
+        assert!(!query().to_sql().contains("NULLS LAST ASC"), "{}", query().to_sql());
+        let users = query().exec().await.unwrap();
+        let ages: Vec<_> = users.iter().map(|u| u.age).collect();
+        assert_eq!(ages, vec![Some(30), Some(30), Some(25), Some(25), None]);
+        let users = client
             .user()
             .find_many(vec![])
-            .order_by((user::age::order(SortOrder::Asc), caustics::NullsOrder::Last))
+            .order_by_raw("age asc nulls first")
             .exec()
             .await
             .unwrap();
-        let ages_last: Vec<Option<i32>> = users_nulls_last.iter().map(|u| u.age).collect();
-        assert!(ages_last.len() >= 4);
-        assert_eq!(ages_last[0], Some(10));
-        assert_eq!(ages_last[1], Some(20));
-        assert_eq!(ages_last[ages_last.len() - 2], None);
-        assert_eq!(ages_last[ages_last.len() - 1], None);
+        let ages: Vec<_> = users.iter().map(|u| u.age).collect();
+        assert_eq!(ages, vec![None, Some(25), Some(25), Some(30), Some(30)]);
     }
 
     #[tokio::test]
-    async fn test_cursor_pagination_basic() {
-        use chrono::TimeZone;
+    async fn test_order_by_cases_value_priority() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        // Seed deterministic users with ascending ages
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
-        let mut created = Vec::new();
-        for i in 0..5 {
-            let u = client
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for (email, name, age) in [
+            ("a@example.com", "low", 1),
+            ("b@example.com", "archived", 2),
+            ("c@example.com", "urgent", 3),
+            ("d@example.com", "normal", 4),
+            ("e@example.com", "low", 5),
+        ] {
+            client
                 .user()
                 .create(
-                    format!("cursor{}@example.com", i),
-                    format!("Cursor {}", i),
-                    now,
-                    now,
-                    vec![user::age::set(Some(20 + i)), user::deleted_at::set(None)],
+                    email.to_string(),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![user::age::set(Some(age))],
                 )
                 .exec()
                 .await
                 .unwrap();
-            created.push(u);
         }
 
-        // Order by id ascending, take first 2
-        let first_page = client
+        // Listed values in list order, unlisted ones last; ties broken by the next key
+        let users = client
             .user()
             .find_many(vec![])
-            .order_by(user::id::order(SortOrder::Asc))
-            .take(2)
+            .order_by(user::name::order_by_cases(vec!["urgent", "normal", "low"]))
+            .order_by(user::age::order(SortOrder::Desc))
             .exec()
             .await
             .unwrap();
-        assert_eq!(first_page.len(), 2);
+        let names: Vec<_> = users.iter().map(|u| (u.name.as_str(), u.age.unwrap())).collect();
+        assert_eq!(
+            names,
+            vec![("urgent", 3), ("normal", 4), ("low", 5), ("low", 1), ("archived", 2)]
+        );
 
-        // Next page using cursor: last id from previous page
-        let cursor_id = first_page.last().unwrap().id;
-        let second_page = client
+        // The priority flips with a negative take
+        let last = client
             .user()
             .find_many(vec![])
-            .order_by(user::id::order(SortOrder::Asc))
-            .cursor(user::id::equals(cursor_id))
-            .skip(1)
-            .take(2)
+            .order_by(user::name::order_by_cases(vec!["urgent", "normal", "low"]))
+            .take(-1)
             .exec()
             .await
             .unwrap();
-
-        // Should skip the cursor row (via skip(1)) and return the next two
-        assert_eq!(second_page.len(), 2);
-        assert!(second_page.iter().all(|u| u.id > cursor_id));
+        assert_eq!(last[0].name, "archived");
     }
 
     #[tokio::test]
-    async fn test_distinct_compiles_and_runs() {
-        use chrono::TimeZone;
+    async fn test_order_by_json_path_number() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        let author = client
+            .user()
+            .create("views@example.com".to_string(), "Views".to_string(), now, now, vec![])
+            .exec()
+            .await
             .unwrap();
-
-        // Seed a few users
-        for i in 0..3 {
-            let _ = client
-                .user()
+        for (title, views) in [("Five", 5), ("Twelve", 12), ("Nine", 9)] {
+            client
+                .post()
                 .create(
-                    format!("distinct{}@example.com", i),
-                    format!("Distinct {}", i),
+                    title.to_string(),
                     now,
                     now,
-                    vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+                    user::id::equals(author.id),
+                    vec![post::custom_data::set(Some(serde_json::json!({
+                        "stats": { "view_count": views }
+                    })))],
                 )
                 .exec()
                 .await
                 .unwrap();
         }
 
-        // Using distinct() should still return rows successfully
-        let rows = client
-            .user()
+        // Numeric, not lexical: 12 sorts above 9
+        let posts = client
+            .post()
             .find_many(vec![])
-            .order_by(user::id::order(SortOrder::Asc))
-            .distinct_all()
+            .order_by_json(post::Column::CustomData, vec!["stats", "view_count"], SortOrder::Desc)
             .exec()
             .await
             .unwrap();
+        let titles: Vec<_> = posts.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, vec!["Twelve", "Nine", "Five"]);
 
-        // Since we select all columns (including unique id), distinct currently behaves as no-op
-        // Assert we got the inserted rows (3)
-        assert!(rows.len() >= 3);
+        // Flips with a negative take like any other key
+        let lowest = client
+            .post()
+            .find_many(vec![])
+            .order_by_json(post::Column::CustomData, vec!["stats", "view_count"], SortOrder::Desc)
+            .take(-1)
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(lowest[0].title, "Five");
     }
 
     #[tokio::test]
-    async fn test_count_server_side() {
-        use chrono::{DateTime, FixedOffset};
-        use std::str::FromStr;
-
+    async fn test_group_by_json_path_key() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
-
-        // Count on empty table
-        let total0 = client.user().count(vec![]).exec().await.unwrap();
-        assert_eq!(total0, 0);
-
         let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        // Seed users
-        let _u1 = client
+        let author = client
             .user()
-            .create(
-                "c1@example.com".to_string(),
-                "C1".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(20)), user::deleted_at::set(None)],
-            )
+            .create("categories@example.com".to_string(), "Categories".to_string(), now, now, vec![])
             .exec()
             .await
             .unwrap();
+        for (title, category) in [("Rust", "tech"), ("Go", "tech"), ("Bread", "food"), ("Sql", "tech")] {
+            client
+                .post()
+                .create(
+                    title.to_string(),
+                    now,
+                    now,
+                    user::id::equals(author.id),
+                    vec![post::custom_data::set(Some(serde_json::json!({ "category": category })))],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
 
-        let _u2 = client
-            .user()
-            .create(
-                "c2@example.com".to_string(),
-                "C2".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
-            )
+        let groups = client
+            .post()
+            .group_by_json(post::Column::CustomData, vec!["category"], vec![])
+            .count("count")
             .exec()
             .await
             .unwrap();
+        let mut counts: Vec<(String, String)> = groups
+            .iter()
+            .map(|g| (g.keys["category"].clone(), g.aggregates["count"].clone()))
+            .collect();
+        counts.sort();
+        assert_eq!(
+            counts,
+            vec![("food".to_string(), "1".to_string()), ("tech".to_string(), "3".to_string())]
+        );
+    }
 
-        let _u3 = client
+    #[tokio::test]
+    async fn test_map_rows_while_streaming() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        for (email, name, age) in [
+            ("ann@example.com", "ann", 31),
+            ("bob@example.com", "bob", 17),
+            ("cid@example.com", "cid", 45),
+        ] {
+            client
+                .user()
+                .create(email.to_string(), name.to_string(), now, now, vec![user::age::set(Some(age))])
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let labels = client
             .user()
-            .create(
-                "c3@example.com".to_string(),
-                "C3".to_string(),
-                now,
-                now,
-                vec![user::age::set(None), user::deleted_at::set(None)],
-            )
+            .find_many(vec![])
+            .order_by(user::name::order(SortOrder::Asc))
+            .map_rows(|u| format!("{}:{}", u.name.to_uppercase(), u.age.unwrap_or_default()))
             .exec()
             .await
             .unwrap();
+        assert_eq!(labels, vec!["ANN:31", "BOB:17", "CID:45"]);
 
-        // Count all
-        let total_all = client.user().count(vec![]).exec().await.unwrap();
-        assert_eq!(total_all, 3);
-
-        // Count with filter: age > 25
-        let total_adults = client
+        // Filters and maps compose in call order
+        let adults = client
             .user()
-            .count(vec![user::age::gt(Some(25))])
+            .find_many(vec![])
+            .order_by(user::name::order(SortOrder::Asc))
+            .filter_rows(|u| u.age.unwrap_or_default() >= 18)
+            .map_rows(|u| u.email)
+            .filter_rows(|email| !email.starts_with("cid"))
             .exec()
             .await
             .unwrap();
-        assert_eq!(total_adults, 1);
+        assert_eq!(adults, vec!["ann@example.com"]);
 
-        // Count with filter: age is null
-        let total_null_age = client
+        // A negative take keeps its order through the buffered fallback
+        let last = client
             .user()
-            .count(vec![user::age::is_null()])
+            .find_many(vec![])
+            .order_by(user::name::order(SortOrder::Asc))
+            .take(-2)
+            .map_rows(|u| u.name)
             .exec()
             .await
             .unwrap();
-        assert_eq!(total_null_age, 1);
+        assert_eq!(last, vec!["bob", "cid"]);
     }
 
     #[tokio::test]
-    async fn test_delete_operations() {
+    async fn test_take_skip_validation_and_no_limit() {
         let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
+        let client = blog::CausticsClient::new(db);
 
-        // Create user with unique email
-        let email = format!("john_{}@example.com", chrono::Utc::now().timestamp());
-        let user = client
-            .user()
-            .create(
-                email.clone(),
-                "John".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
-            )
-            .exec()
-            .await
-            .unwrap();
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for i in 0..3 {
+            client
+                .user()
+                .create(format!("page{}@example.com", i), format!("Page {}", i), created, created, vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
 
-        // Delete user
-        client
+        // Bad values are rejected before any SQL runs
+        let err = client.user().find_many(vec![]).skip(-1).exec().await.unwrap_err();
+        assert!(err.to_string().contains("CausticsError::InvalidPagination"), "{}", err);
+        for take in [i64::MIN, i64::MAX, -(1 << 40)] {
+            let err = client.user().find_many(vec![]).take(take).exec().await.unwrap_err();
+            assert!(err.to_string().contains("CausticsError::InvalidPagination"), "{}", err);
+        }
+        let err = client.user().find_many(vec![]).skip(i64::MAX).exec().await.unwrap_err();
+        assert!(err.to_string().contains("CausticsError::InvalidPagination"), "{}", err);
+        // ... and survive conversion into a selection builder
+        let selection: caustics::SelectManyQueryBuilder<_, _, user::Selected> =
+            client.user().find_many(vec![]).take(i64::MAX).into();
+        let err = selection.exec::<user::Selected>().await.unwrap_err();
+        assert!(err.to_string().contains("CausticsError::InvalidPagination"), "{}", err);
+
+        // None means no limit, and clears one set earlier
+        let page_size: Option<i64> = None;
+        let all = client.user().find_many(vec![]).take(page_size).exec().await.unwrap();
+        assert_eq!(all.len(), 3);
+        let cleared = client.user().find_many(vec![]).take(1).take(None).exec().await.unwrap();
+        assert_eq!(cleared.len(), 3);
+        let some = client.user().find_many(vec![]).take(Some(2)).exec().await.unwrap();
+        assert_eq!(some.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_negative_take_returns_last_rows_in_order() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for i in 1..=5 {
+            client
+                .user()
+                .create(format!("tail{}@example.com", i), format!("Tail {}", i), created, created, vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let last = client
             .user()
-            .delete(user::id::equals(user.id))
+            .find_many(vec![])
+            .order_by(user::name::order(SortOrder::Asc))
+            .take(-3)
             .exec()
             .await
             .unwrap();
+        let names: Vec<_> = last.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["Tail 3", "Tail 4", "Tail 5"]);
 
-        // Verify deletion
-        let deleted_user = client
+        // skip counts from the end as well
+        let before_last = client
             .user()
-            .find_unique(user::id::equals(user.id))
+            .find_many(vec![])
+            .order_by(user::name::order(SortOrder::Asc))
+            .skip(1)
+            .take(-2)
             .exec()
             .await
             .unwrap();
-        assert!(deleted_user.is_none());
+        let names: Vec<_> = before_last.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["Tail 3", "Tail 4"]);
     }
 
     #[tokio::test]
-    async fn test_delete_many_returns_count() {
+    async fn test_find_many_exec_into_custom_struct() {
+        #[derive(Debug, PartialEq, sea_orm::FromQueryResult)]
+        struct UserSummary {
+            id: Uuid,
+            name: String,
+        }
+
         let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
+        let client = blog::CausticsClient::new(db);
 
-        // Create three users
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
-        for i in 0..3 {
-            let _ = client
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let mut ids = Vec::new();
+        for name in ["Ada", "Bea", "Cal"] {
+            let user = client
                 .user()
-                .create(
-                    format!("delmany{}@example.com", i),
-                    format!("DelMany{}", i),
-                    now,
-                    now,
-                    vec![user::age::set(Some(20 + i)), user::deleted_at::set(None)],
-                )
+                .create(format!("{}@example.com", name.to_lowercase()), name.to_string(), created, created, vec![])
                 .exec()
                 .await
                 .unwrap();
+            ids.push(user.id);
         }
 
-        // Delete where age > 20 (two rows)
-        let deleted = client
+        let summaries: Vec<UserSummary> = client
             .user()
-            .delete_many(vec![user::age::gt(Some(20))])
-            .exec()
+            .find_many(vec![user::name::not_equals("Bea")])
+            .order_by(user::name::order(SortOrder::Asc))
+            .exec_into()
             .await
             .unwrap();
-        assert_eq!(deleted, 2);
+        assert_eq!(
+            summaries,
+            vec![
+                UserSummary { id: ids[0], name: "Ada".to_string() },
+                UserSummary { id: ids[2], name: "Cal".to_string() },
+            ]
+        );
 
-        // Remaining count should be 1
-        let remaining = client.user().count(vec![]).exec().await.unwrap();
-        assert_eq!(remaining, 1);
+        // Includes have nowhere to go on a custom struct
+        let err = client
+            .user()
+            .find_many(vec![])
+            .with(user::posts::fetch(vec![]))
+            .exec_into::<UserSummary>()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exec_into does not support includes"), "{}", err);
     }
 
     #[tokio::test]
-    async fn test_upsert_operations() {
+    async fn test_order_by_many_runtime_sort_spec() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        // Upsert user
-        let user = client
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for (email, name, age) in [
+            ("a1@example.com", "Ann", 40),
+            ("b1@example.com", "Ben", 30),
+            ("a2@example.com", "Ann", 20),
+            ("c1@example.com", "Cid", 30),
+        ] {
+            client
+                .user()
+                .create(
+                    email.to_string(),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        // Sort spec as it would arrive from an API query string: ?sort=name:asc,age:desc
+        let sort_param = "name:asc,age:desc";
+        let sort_spec: Vec<(user::Column, SortOrder)> = sort_param
+            .split(',')
+            .map(|part| {
+                let (field, direction) = part.split_once(':').unwrap();
+                let order = if direction == "desc" { SortOrder::Desc } else { SortOrder::Asc };
+                (user::Column::from_str(field).unwrap(), order)
+            })
+            .collect();
+
+        let users = client
             .user()
-            .upsert(
-                user::email::equals("john@example.com"),
-                user::Create {
-                    name: "John".to_string(),
-                    email: "john@example.com".to_string(),
-                    created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    _params: vec![],
-                },
-                vec![user::name::set("John"), user::age::set(25)],
-            )
+            .find_many(vec![])
+            .order_by_many(sort_spec)
             .exec()
             .await
             .unwrap();
-        assert_eq!(user.name, "John");
-        assert_eq!(user.age, Some(25));
+        let keys: Vec<_> = users.iter().map(|u| (u.name.as_str(), u.age)).collect();
+        assert_eq!(
+            keys,
+            vec![("Ann", Some(40)), ("Ann", Some(20)), ("Ben", Some(30)), ("Cid", Some(30))]
+        );
 
-        // Update existing user
-        let updated_user = client
+        // Mixed descriptor kinds in one list
+        let users = client
             .user()
-            .upsert(
-                user::email::equals("john@example.com"),
-                user::Create {
-                    name: "John".to_string(),
-                    email: "john@example.com".to_string(),
-                    created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    _params: vec![],
-                },
-                vec![user::name::set("John Doe"), user::age::set(26)],
-            )
+            .find_many(vec![])
+            .order_by_many(vec![
+                caustics::OrderByDescriptor::from(user::age::order(SortOrder::Asc)),
+                caustics::OrderByDescriptor::new(user::Column::Email, SortOrder::Desc),
+            ])
             .exec()
             .await
             .unwrap();
-        assert_eq!(updated_user.name, "John Doe");
-        assert_eq!(updated_user.age, Some(26));
+        let emails: Vec<_> = users.iter().map(|u| u.email.as_str()).collect();
+        assert_eq!(
+            emails,
+            vec!["a2@example.com", "c1@example.com", "b1@example.com", "a1@example.com"]
+        );
     }
 
     #[tokio::test]
-    async fn test_transaction_commit() {
+    async fn test_find_many_first_and_last() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        let email = format!("john_{}@example.com", chrono::Utc::now().timestamp());
-        let email_for_check = email.clone();
-        let result = client
-            ._transaction()
-            .run(|tx| {
-                Box::pin(async move {
-                    // Create user
-                    let user = tx
-                        .user()
-                        .create(
-                            email.clone(),
-                            "John".to_string(),
-                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                            vec![],
-                        )
-                        .exec()
-                        .await?;
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for (name, age) in [("Mid", 30), ("Young", 18), ("Old", 65), ("Teen", 16)] {
+            client
+                .user()
+                .create(
+                    format!("{}@example.com", name.to_lowercase()),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
 
-                    // Create post
-                    let post = tx
-                        .post()
-                        .create(
-                            "Hello World".to_string(),
-                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                            user::id::equals(user.id),
-                            vec![post::content::set("This is my first post".to_string())],
-                        )
-                        .exec()
-                        .await?;
+        let youngest = client
+            .user()
+            .find_many(vec![])
+            .order_by(user::age::order(SortOrder::Asc))
+            .first()
+            .await
+            .unwrap()
+            .expect("first() should return a row");
+        assert_eq!(youngest.name, "Teen");
 
-                    Ok((user, post))
-                })
-            })
+        let oldest = client
+            .user()
+            .find_many(vec![])
+            .order_by(user::age::order(SortOrder::Asc))
+            .last()
             .await
-            .expect("Transaction failed");
+            .unwrap()
+            .expect("last() should return a row");
+        assert_eq!(oldest.name, "Old");
 
-        assert_eq!(result.0.name, "John");
-        assert_eq!(result.1.title, "Hello World");
+        // Respects filters and multi-key ordering
+        let last_adult = client
+            .user()
+            .find_many(vec![user::age::gte(18)])
+            .order_by(user::age::order(SortOrder::Desc))
+            .order_by(user::name::order(SortOrder::Asc))
+            .last()
+            .await
+            .unwrap()
+            .expect("last() should return a row");
+        assert_eq!(last_adult.name, "Young");
 
-        // Verify data is persisted
-        let found_user = client
+        let none = client
             .user()
-            .find_first(vec![user::email::equals(&email_for_check)])
-            .exec()
+            .find_many(vec![user::age::gt(100)])
+            .order_by(user::age::order(SortOrder::Asc))
+            .first()
             .await
-            .expect("Failed to query user")
-            .expect("User not found");
-        assert_eq!(found_user.name, "John");
+            .unwrap();
+        assert!(none.is_none());
+
+        // Without an ordering "last" is ambiguous
+        let unordered = client.user().find_many(vec![]).last().await;
+        assert!(unordered.is_err());
     }
 
     #[tokio::test]
-    async fn test_transaction_rollback() {
+    async fn test_update_exec_with_previous() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        let email = format!("rollback_{}@example.com", chrono::Utc::now().timestamp());
-        let email_for_check = email.clone();
-        let result: Result<(), QueryError> = client
-            ._transaction()
-            .run(|tx| {
-                Box::pin(async move {
-                    // Create user
-                    let _user = tx
-                        .user()
-                        .create(
-                            email.clone(),
-                            "Rollback".to_string(),
-                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                            vec![],
-                        )
-                        .exec()
-                        .await?;
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let user = client
+            .user()
+            .create(
+                "audit@example.com".to_string(),
+                "Before".to_string(),
+                created,
+                created,
+                vec![user::age::set(Some(20))],
+            )
+            .exec()
+            .await
+            .unwrap();
 
-                    // Intentionally return an error to trigger rollback
-                    Err(QueryError::Custom("Intentional rollback".into()))
-                })
-            })
-            .await;
+        let (previous, updated) = client
+            .user()
+            .update(
+                user::id::equals(user.id),
+                vec![user::name::set("After"), user::age::increment(5)],
+            )
+            .exec_with_previous()
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
+        assert_eq!(previous.id, user.id);
+        assert_eq!(previous.name, "Before");
+        assert_eq!(previous.age, Some(20));
+        assert_eq!(updated.name, "After");
+        assert_eq!(updated.age, Some(25));
 
-        // Verify data is NOT persisted
-        let found_user = client
+        // The stored row matches the returned updated snapshot
+        let stored = client
             .user()
-            .find_first(vec![user::email::equals(&email_for_check)])
+            .find_unique(user::id::equals(user.id))
             .exec()
             .await
-            .expect("Failed to query user");
-        assert!(found_user.is_none());
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.name, "After");
+        assert_eq!(stored.age, Some(25));
+
+        // Missing rows surface the usual not-found error
+        let missing = client
+            .user()
+            .update(
+                user::id::equals(Uuid::new_v4()),
+                vec![user::name::set("Nobody")],
+            )
+            .exec_with_previous()
+            .await;
+        assert!(missing.is_err());
     }
 
     #[tokio::test]
-    async fn test_relations() {
+    async fn test_atomic_string_append_prepend() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        // Create users
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
         let author = client
             .user()
             .create(
-                "john@example.com".to_string(),
-                "John".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![],
-            )
-            .exec()
-            .await
-            .unwrap();
-        assert!(author.posts.is_none());
-
-        let reviewer = client
-            .user()
-            .create(
-                "jane@example.com".to_string(),
-                "Jane".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                "log@example.com".to_string(),
+                "Logger".to_string(),
+                created,
+                created,
                 vec![],
             )
             .exec()
             .await
             .unwrap();
-
-        // Create posts - one with reviewer, one without
-        let post_with_reviewer = client
+        let post_id = Uuid::new_v4();
+        client
             .post()
             .create(
-                "Reviewed Post".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                "Log".to_string(),
+                created,
+                created,
                 user::id::equals(author.id),
-                vec![
-                    post::content::set("This post has been reviewed".to_string()),
-                    post::reviewer::connect(user::id::equals(reviewer.id)),
-                ],
+                vec![post::id::set(post_id), post::content::set(None)],
             )
             .exec()
             .await
             .unwrap();
 
-        let post_without_reviewer = client
+        // NULL content is treated as the empty string
+        let updated = client
             .post()
-            .create(
-                "Unreviewed Post".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                user::id::equals(author.id),
-                vec![post::content::set(
-                    "This post hasn't been reviewed yet".to_string(),
-                )],
-            )
+            .update(post::id::equals(post_id), vec![post::content::append("start")])
             .exec()
             .await
             .unwrap();
+        assert_eq!(updated.content.as_deref(), Some("start"));
 
-        // Test fetching user with posts
-        let user_with_posts = client
-            .user()
-            .find_unique(user::id::equals(author.id))
-            .with(user::posts::fetch(vec![]))
+        // Concurrent appends are applied server-side, so neither is lost
+        let (a, b) = tokio::join!(
+            client
+                .post()
+                .update(post::id::equals(post_id), vec![post::content::append(";a")])
+                .exec(),
+            client
+                .post()
+                .update(post::id::equals(post_id), vec![post::content::append(";b")])
+                .exec(),
+        );
+        a.unwrap();
+        b.unwrap();
+
+        let updated = client
+            .post()
+            .update(
+                post::id::equals(post_id),
+                vec![post::title::set("Log v2"), post::content::prepend("> ")],
+            )
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        let posts = user_with_posts.posts.unwrap();
-        assert_eq!(posts.len(), 2);
-        assert_eq!(posts[0].title, "Reviewed Post");
-        assert_eq!(posts[1].title, "Unreviewed Post");
+        assert_eq!(updated.title, "Log v2");
+        let content = updated.content.unwrap();
+        assert!(content.starts_with("> start;"));
+        assert!(content.contains(";a") && content.contains(";b"));
+        assert_eq!(content.len(), "> start;a;b".len());
 
-        // Test fetching post with reviewer
-        let post_with_reviewer = client
+        // update_many applies the expression per matched row
+        let affected = client
             .post()
-            .find_unique(post::id::equals(post_with_reviewer.id))
-            .with(post::reviewer::fetch())
+            .update_many(
+                vec![post::user_id::equals(author.id)],
+                vec![post::content::append("!")],
+            )
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        let reviewer = post_with_reviewer.reviewer.unwrap().unwrap();
-        assert_eq!(reviewer.name, "Jane");
-        assert_eq!(reviewer.email, "jane@example.com");
-
-        // Test fetching post without reviewer
-        let post_without_reviewer = client
+        assert_eq!(affected, 1);
+        let stored = client
             .post()
-            .find_unique(post::id::equals(post_without_reviewer.id))
-            .with(post::reviewer::fetch())
+            .find_unique(post::id::equals(post_id))
             .exec()
             .await
             .unwrap()
             .unwrap();
-        assert!(
-            post_without_reviewer.reviewer.is_none()
-                || post_without_reviewer.reviewer.as_ref().unwrap().is_none()
-        );
-    }
-
-    #[tokio::test]
-    async fn test_batch_insert_operations() {
-        let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
-
-        // Create multiple users in a batch
-        let timestamp = chrono::Utc::now().timestamp();
-        let (user1, user2) = client
-            ._batch((
-                client.user().create(
-                    format!("john_{}@example.com", timestamp),
-                    "John".to_string(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    vec![user::age::set(Some(25)), user::deleted_at::set(None)],
-                ),
-                client.user().create(
-                    format!("jane_{}@example.com", timestamp),
-                    "Jane".to_string(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    vec![user::age::set(Some(30))],
-                ),
-            ))
-            .await
-            .expect("Batch operation failed");
-
-        assert_eq!(user1.name, "John");
-        assert_eq!(user2.name, "Jane");
-
-        let found_users = client.user().find_many(vec![]).exec().await.unwrap();
-        assert_eq!(found_users.len(), 2);
+        assert!(stored.content.unwrap().ends_with('!'));
     }
 
     #[tokio::test]
-    async fn test_batch_update_operations() {
-        // no explicit caustics types here
-        use chrono::DateTime;
-        use std::str::FromStr;
-
-        let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
+    async fn test_update_many_set_from_column() {
+        let mut db = setup_test_db().await;
+        let statements = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let recorded = statements.clone();
+        db.set_metric_callback(move |info| {
+            recorded.lock().unwrap().push(info.statement.sql.clone());
+        });
+        let client = blog::CausticsClient::new(db);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for (email, name, age) in [
+            ("copy1@example.com", "Copy", 21),
+            ("copy2@example.com", "Copy", 34),
+            ("keep@example.com", "Keep", 50),
+        ] {
+            client
+                .user()
+                .create(
+                    email.to_string(),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
 
-        // Seed users
-        let u1 = client
+        statements.lock().unwrap().clear();
+        let affected = client
             .user()
-            .create(
-                "u1@example.com".to_string(),
-                "U1".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(20))],
+            .update_many(
+                vec![user::name::equals("Copy")],
+                vec![user::tenant_id::set_from_column(user::age::column())],
             )
             .exec()
             .await
             .unwrap();
-        let u2 = client
+        assert_eq!(affected, 2);
+        {
+            // One UPDATE for all matched rows, no per-row reads
+            let statements = statements.lock().unwrap();
+            assert_eq!(statements.len(), 1, "{:?}", statements);
+            assert!(statements[0].starts_with("UPDATE"));
+        }
+
+        let users = client
             .user()
-            .create(
-                "u2@example.com".to_string(),
-                "U2".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(30))],
-            )
+            .find_many(vec![])
+            .order_by(user::email::order(SortOrder::Asc))
             .exec()
             .await
             .unwrap();
+        let copied: Vec<_> = users.iter().map(|u| (u.email.as_str(), u.age, u.tenant_id)).collect();
+        assert_eq!(
+            copied,
+            vec![
+                ("copy1@example.com", Some(21), Some(21)),
+                ("copy2@example.com", Some(34), Some(34)),
+                ("keep@example.com", Some(50), None),
+            ]
+        );
 
-        // Batch update both (tuple style, fluent)
-        // Execute sequential updates to match the new API shape
-        let _u1_after = client
+        // Also usable on a single-row update
+        let keep = users.iter().find(|u| u.name == "Keep").unwrap();
+        let updated = client
             .user()
-            .update(user::id::equals(u1.id), vec![user::age::increment(5)])
+            .update(
+                user::id::equals(keep.id),
+                vec![user::tenant_id::set_from_column(user::age::column())],
+            )
             .exec()
             .await
             .unwrap();
-        let _u2_after = client
+        assert_eq!(updated.tenant_id, Some(50));
+    }
+
+    #[tokio::test]
+    async fn test_atomic_json_merge() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let author = client
             .user()
-            .update(
-                user::id::equals(u2.id),
-                vec![user::name::set("U2-upd"), user::age::decrement(10)],
+            .create(
+                "json@example.com".to_string(),
+                "Json".to_string(),
+                created,
+                created,
+                vec![],
             )
             .exec()
             .await
             .unwrap();
+        let post_id = Uuid::new_v4();
+        let empty_id = Uuid::new_v4();
+        for (id, data) in [
+            (
+                post_id,
+                Some(serde_json::json!({"published": true, "metadata": {"priority": 1}})),
+            ),
+            (empty_id, None),
+        ] {
+            client
+                .post()
+                .create(
+                    "Json".to_string(),
+                    created,
+                    created,
+                    user::id::equals(author.id),
+                    vec![post::id::set(id), post::custom_data::set(data)],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
 
-        // Verify
-        let u1_after = client
-            .user()
-            .find_unique(user::id::equals(u1.id))
+        let updated = client
+            .post()
+            .update(
+                post::id::equals(post_id),
+                vec![post::custom_data::merge(serde_json::json!({"published": false, "tags": ["a"]}))],
+            )
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(u1_after.age, Some(25));
+        assert_eq!(
+            updated.custom_data,
+            Some(serde_json::json!({
+                "published": false,
+                "tags": ["a"],
+                "metadata": {"priority": 1}
+            }))
+        );
 
-        let u2_after = client
-            .user()
-            .find_unique(user::id::equals(u2.id))
+        // Merging into NULL treats the column as an empty object
+        let updated = client
+            .post()
+            .update(
+                post::id::equals(empty_id),
+                vec![post::custom_data::merge(serde_json::json!({"published": false}))],
+            )
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(u2_after.name, "U2-upd");
-        assert_eq!(u2_after.age, Some(20));
+        assert_eq!(updated.custom_data, Some(serde_json::json!({"published": false})));
     }
 
     #[tokio::test]
-    async fn test_batch_update_with_vec() {
-        use chrono::DateTime;
-        use std::str::FromStr;
-
+    async fn test_atomic_json_remove_key() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        // Create test users
-        let u1 = client
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let author = client
             .user()
             .create(
-                "batch_u1@example.com".to_string(),
-                "BatchU1".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(25))],
+                "jsonrm@example.com".to_string(),
+                "Json".to_string(),
+                created,
+                created,
+                vec![],
             )
             .exec()
             .await
             .unwrap();
-
-        let u2 = client
-            .user()
+        let post_id = Uuid::new_v4();
+        client
+            .post()
             .create(
-                "batch_u2@example.com".to_string(),
-                "BatchU2".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(35))],
+                "Json".to_string(),
+                created,
+                created,
+                user::id::equals(author.id),
+                vec![
+                    post::id::set(post_id),
+                    post::custom_data::set(Some(serde_json::json!({
+                        "published": true,
+                        "metadata": {"priority": 1, "owner": "ops"}
+                    }))),
+                ],
             )
             .exec()
             .await
             .unwrap();
 
-        let u3 = client
-            .user()
-            .create(
-                "batch_u3@example.com".to_string(),
-                "BatchU3".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(45))],
+        let updated = client
+            .post()
+            .update(
+                post::id::equals(post_id),
+                vec![post::custom_data::remove_key(vec!["metadata", "priority"])],
             )
             .exec()
             .await
             .unwrap();
-
-        // Create a vector of update operations (similar to the user's use case)
-        let mut update_operations = Vec::new();
-        
-        update_operations.push(
-            client
-                .user()
-                .update(
-                    user::id::equals(u1.id),
-                    vec![user::name::set("UpdatedU1"), user::age::increment(5)],
-                )
-        );
-        
-        update_operations.push(
-            client
-                .user()
-                .update(
-                    user::id::equals(u2.id),
-                    vec![user::name::set("UpdatedU2"), user::age::decrement(10)],
-                )
-        );
-        
-        update_operations.push(
-            client
-                .user()
-                .update(
-                    user::id::equals(u3.id),
-                    vec![user::name::set("UpdatedU3"), user::age::set(50)],
-                )
+        assert_eq!(
+            updated.custom_data,
+            Some(serde_json::json!({"published": true, "metadata": {"owner": "ops"}}))
         );
 
-        // Execute batch update using Vec<UpdateQueryBuilder>
-        let results = client
-            ._batch(update_operations)
+        // Removing a missing key leaves the object unchanged
+        let updated = client
+            .post()
+            .update(
+                post::id::equals(post_id),
+                vec![post::custom_data::remove_key(vec!["missing"])],
+            )
+            .exec()
             .await
-            .expect("Batch update operation failed");
+            .unwrap();
+        assert_eq!(
+            updated.custom_data,
+            Some(serde_json::json!({"published": true, "metadata": {"owner": "ops"}}))
+        );
+    }
 
-        // Verify we got the expected number of results
-        assert_eq!(results.len(), 3);
+    #[tokio::test]
+    async fn test_order_nulls_first_and_last_many() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
 
-        // Verify the updates were applied correctly
-        let updated_u1 = client
-            .user()
-            .find_unique(user::id::equals(u1.id))
-            .exec()
-            .await
+        let now = chrono::FixedOffset::east_opt(0)
             .unwrap()
+            .with_ymd_and_hms(2024, 2, 1, 0, 0, 0)
             .unwrap();
-        assert_eq!(updated_u1.name, "UpdatedU1");
-        assert_eq!(updated_u1.age, Some(30)); // 25 + 5
 
-        let updated_u2 = client
+        // Seed users with some null ages
+        let _u1 = client
             .user()
-            .find_unique(user::id::equals(u2.id))
+            .create(
+                "nulls1@example.com".to_string(),
+                "Nulls1".to_string(),
+                now,
+                now,
+                vec![user::age::set(None), user::deleted_at::set(None)],
+            )
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(updated_u2.name, "UpdatedU2");
-        assert_eq!(updated_u2.age, Some(25)); // 35 - 10
-
-        let updated_u3 = client
+        let _u2 = client
             .user()
-            .find_unique(user::id::equals(u3.id))
+            .create(
+                "nulls2@example.com".to_string(),
+                "Nulls2".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(10)), user::deleted_at::set(None)],
+            )
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(updated_u3.name, "UpdatedU3");
-        assert_eq!(updated_u3.age, Some(50)); // Set to 50
-    }
-
-    #[tokio::test]
-    async fn test_batch_create_with_vec() {
-        use chrono::DateTime;
-        use std::str::FromStr;
-
-        let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
-
-        // Create a vector of create operations
-        let mut create_operations = Vec::new();
-        
-        create_operations.push(
-            client
-                .user()
-                .create(
-                    "batch_create_1@example.com".to_string(),
-                    "BatchCreate1".to_string(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    vec![user::age::set(Some(25))],
-                )
-        );
-        
-        create_operations.push(
-            client
-                .user()
-                .create(
-                    "batch_create_2@example.com".to_string(),
-                    "BatchCreate2".to_string(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    vec![user::age::set(Some(35))],
-                )
-        );
-        
-        create_operations.push(
-            client
-                .user()
-                .create(
-                    "batch_create_3@example.com".to_string(),
-                    "BatchCreate3".to_string(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    vec![user::age::set(Some(45))],
-                )
-        );
-
-        // Execute batch create using Vec<CreateQueryBuilder>
-        let results = client
-            ._batch(create_operations)
+        let _u3 = client
+            .user()
+            .create(
+                "nulls3@example.com".to_string(),
+                "Nulls3".to_string(),
+                now,
+                now,
+                vec![user::age::set(None), user::deleted_at::set(None)],
+            )
+            .exec()
             .await
-            .expect("Batch create operation failed");
-
-        // Verify we got the expected number of results
-        assert_eq!(results.len(), 3);
-
-        // Verify the creates were applied correctly
-        let created_user1 = client
+            .unwrap();
+        let _u4 = client
             .user()
-            .find_unique(user::email::equals("batch_create_1@example.com"))
+            .create(
+                "nulls4@example.com".to_string(),
+                "Nulls4".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(20)), user::deleted_at::set(None)],
+            )
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(created_user1.name, "BatchCreate1");
-        assert_eq!(created_user1.age, Some(25));
 
-        let created_user2 = client
+        // Nulls first with ascending order
+        let users_nulls_first = client
             .user()
-            .find_unique(user::email::equals("batch_create_2@example.com"))
+            .find_many(vec![])
+            .order_by((
+                user::age::order(SortOrder::Asc),
+                caustics::NullsOrder::First,
+            ))
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(created_user2.name, "BatchCreate2");
-        assert_eq!(created_user2.age, Some(35));
+        let ages_first: Vec<Option<i32>> = users_nulls_first.iter().map(|u| u.age).collect();
+        // Expect the first two to be None, followed by 10 then 20
+        assert!(ages_first.len() >= 4);
+        assert_eq!(ages_first[0], None);
+        assert_eq!(ages_first[1], None);
+        assert_eq!(ages_first[2], Some(10));
+        assert_eq!(ages_first[3], Some(20));
 
-        let created_user3 = client
+        // Nulls last with ascending order
+        let users_nulls_last = client
             .user()
-            .find_unique(user::email::equals("batch_create_3@example.com"))
+            .find_many(vec![])
+            .order_by((user::age::order(SortOrder::Asc), caustics::NullsOrder::Last))
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(created_user3.name, "BatchCreate3");
-        assert_eq!(created_user3.age, Some(45));
+        let ages_last: Vec<Option<i32>> = users_nulls_last.iter().map(|u| u.age).collect();
+        assert!(ages_last.len() >= 4);
+        assert_eq!(ages_last[0], Some(10));
+        assert_eq!(ages_last[1], Some(20));
+        assert_eq!(ages_last[ages_last.len() - 2], None);
+        assert_eq!(ages_last[ages_last.len() - 1], None);
     }
 
     #[tokio::test]
-    async fn test_batch_delete_with_vec() {
-        use chrono::DateTime;
-        use std::str::FromStr;
-
+    async fn test_cursor_pagination_basic() {
+        use chrono::TimeZone;
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        // Create test users first
-        let u1 = client
-            .user()
-            .create(
-                "batch_delete_1@example.com".to_string(),
-                "BatchDelete1".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(25))],
-            )
-            .exec()
-            .await
+        // Seed deterministic users with ascending ages
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
             .unwrap();
+        let mut created = Vec::new();
+        for i in 0..5 {
+            let u = client
+                .user()
+                .create(
+                    format!("cursor{}@example.com", i),
+                    format!("Cursor {}", i),
+                    now,
+                    now,
+                    vec![user::age::set(Some(20 + i)), user::deleted_at::set(None)],
+                )
+                .exec()
+                .await
+                .unwrap();
+            created.push(u);
+        }
 
-        let u2 = client
+        // Order by id ascending, take first 2
+        let first_page = client
             .user()
-            .create(
-                "batch_delete_2@example.com".to_string(),
-                "BatchDelete2".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(35))],
-            )
+            .find_many(vec![])
+            .order_by(user::id::order(SortOrder::Asc))
+            .take(2)
             .exec()
             .await
             .unwrap();
+        assert_eq!(first_page.len(), 2);
 
-        let u3 = client
+        // Next page using cursor: last id from previous page
+        let cursor_id = first_page.last().unwrap().id;
+        let second_page = client
             .user()
-            .create(
-                "batch_delete_3@example.com".to_string(),
-                "BatchDelete3".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(45))],
-            )
+            .find_many(vec![])
+            .order_by(user::id::order(SortOrder::Asc))
+            .cursor(user::id::equals(cursor_id))
+            .skip(1)
+            .take(2)
             .exec()
             .await
             .unwrap();
 
-        // Create a vector of delete operations
-        let mut delete_operations = Vec::new();
-        
-        delete_operations.push(
-            client
+        // Should skip the cursor row (via skip(1)) and return the next two
+        assert_eq!(second_page.len(), 2);
+        assert!(second_page.iter().all(|u| u.id > cursor_id));
+    }
+
+    #[tokio::test]
+    async fn test_paginate_walks_all_pages() {
+        use blog::entities::user::ManyPaginateExt;
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Empty table yields no pages
+        let mut empty = client
+            .user()
+            .find_many(vec![])
+            .order_by(user::id::order(SortOrder::Asc))
+            .paginate(2);
+        assert!(empty.next_page().await.unwrap().is_none());
+
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+        let mut created = Vec::new();
+        for i in 0..5 {
+            let u = client
                 .user()
-                .delete(user::id::equals(u1.id))
-        );
-        
-        delete_operations.push(
-            client
+                .create(
+                    format!("page{}@example.com", i),
+                    format!("Page {}", i),
+                    now,
+                    now,
+                    vec![],
+                )
+                .exec()
+                .await
+                .unwrap();
+            created.push(u.id);
+        }
+
+        let mut paginator = client
+            .user()
+            .find_many(vec![])
+            .order_by(user::id::order(SortOrder::Asc))
+            .paginate(2);
+        let mut page_sizes = Vec::new();
+        let mut seen = Vec::new();
+        while let Some(page) = paginator.next_page().await.unwrap() {
+            page_sizes.push(page.len());
+            seen.extend(page.into_iter().map(|u| u.id));
+        }
+
+        // Final partial page is returned, and nothing is fetched after it
+        assert_eq!(page_sizes, vec![2, 2, 1]);
+        assert!(seen.windows(2).all(|w| w[0] < w[1]));
+        created.sort();
+        assert_eq!(seen, created);
+        assert!(paginator.next_page().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pagination_across_null_boundary() {
+        use blog::entities::user::ManyPaginateExt;
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+        let ages = [None, Some(30), None, Some(10), Some(30), Some(20), None];
+        let mut created = Vec::new();
+        for (i, age) in ages.iter().enumerate() {
+            let u = client
                 .user()
-                .delete(user::id::equals(u2.id))
+                .create(
+                    format!("nullpage{}@example.com", i),
+                    format!("NullPage {}", i),
+                    now,
+                    now,
+                    vec![user::age::set(*age)],
+                )
+                .exec()
+                .await
+                .unwrap();
+            created.push(u.id);
+        }
+        created.sort();
+
+        // Ascending with NULLS LAST, pages of two split both the tie on 30 and the NULL run
+        let mut paginator = client
+            .user()
+            .find_many(vec![])
+            .order_by((user::age::order(SortOrder::Asc), caustics::NullsOrder::Last))
+            .paginate(2);
+        let mut seen = Vec::new();
+        while let Some(page) = paginator.next_page().await.unwrap() {
+            seen.extend(page.into_iter().map(|u| (u.age, u.id)));
+        }
+        let seen_ages: Vec<_> = seen.iter().map(|(age, _)| *age).collect();
+        assert_eq!(
+            seen_ages,
+            vec![Some(10), Some(20), Some(30), Some(30), None, None, None]
         );
-        
-        delete_operations.push(
-            client
+        let mut seen_ids: Vec<_> = seen.iter().map(|(_, id)| *id).collect();
+        seen_ids.sort();
+        assert_eq!(seen_ids, created);
+
+        // Descending with NULLS FIRST, stepping one row at a time with an explicit cursor;
+        // the id key makes the first row deterministic among the tied NULLs
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let mut query = client
                 .user()
-                .delete(user::id::equals(u3.id))
+                .find_many(vec![])
+                .order_by((user::age::order(SortOrder::Desc), caustics::NullsOrder::First))
+                .order_by(user::id::order(SortOrder::Asc))
+                .take(1);
+            if let Some(id) = cursor {
+                query = query.cursor(user::id::equals(id));
+            }
+            let page = query.exec().await.unwrap();
+            let Some(row) = page.into_iter().next() else {
+                break;
+            };
+            cursor = Some(row.id);
+            seen.push((row.age, row.id));
+        }
+        let seen_ages: Vec<_> = seen.iter().map(|(age, _)| *age).collect();
+        assert_eq!(
+            seen_ages,
+            vec![None, None, None, Some(30), Some(30), Some(20), Some(10)]
         );
+        let mut seen_ids: Vec<_> = seen.iter().map(|(_, id)| *id).collect();
+        seen_ids.sort();
+        assert_eq!(seen_ids, created);
+    }
 
-        // Execute batch delete using Vec<DeleteQueryBuilder>
-        let results = client
-            ._batch(delete_operations)
+    #[tokio::test]
+    async fn test_distinct_compiles_and_runs() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Seed a few users
+        for i in 0..3 {
+            let _ = client
+                .user()
+                .create(
+                    format!("distinct{}@example.com", i),
+                    format!("Distinct {}", i),
+                    now,
+                    now,
+                    vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        // Using distinct() should still return rows successfully
+        let rows = client
+            .user()
+            .find_many(vec![])
+            .order_by(user::id::order(SortOrder::Asc))
+            .distinct_all()
+            .exec()
             .await
-            .expect("Batch delete operation failed");
+            .unwrap();
 
-        // Verify we got the expected number of results
-        assert_eq!(results.len(), 3);
+        // Since we select all columns (including unique id), distinct currently behaves as no-op
+        // Assert we got the inserted rows (3)
+        assert!(rows.len() >= 3);
+    }
 
-        // Verify the deletes were applied correctly
-        let deleted_user1 = client
+    #[tokio::test]
+    async fn test_count_server_side() {
+        use chrono::{DateTime, FixedOffset};
+        use std::str::FromStr;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Count on empty table
+        let total0 = client.user().count(vec![]).exec().await.unwrap();
+        assert_eq!(total0, 0);
+
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        // Seed users
+        let _u1 = client
             .user()
-            .find_unique(user::id::equals(u1.id))
+            .create(
+                "c1@example.com".to_string(),
+                "C1".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(20)), user::deleted_at::set(None)],
+            )
             .exec()
             .await
             .unwrap();
-        assert!(deleted_user1.is_none());
 
-        let deleted_user2 = client
+        let _u2 = client
             .user()
-            .find_unique(user::id::equals(u2.id))
+            .create(
+                "c2@example.com".to_string(),
+                "C2".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+            )
             .exec()
             .await
             .unwrap();
-        assert!(deleted_user2.is_none());
 
-        let deleted_user3 = client
+        let _u3 = client
             .user()
-            .find_unique(user::id::equals(u3.id))
+            .create(
+                "c3@example.com".to_string(),
+                "C3".to_string(),
+                now,
+                now,
+                vec![user::age::set(None), user::deleted_at::set(None)],
+            )
             .exec()
             .await
             .unwrap();
-        assert!(deleted_user3.is_none());
+
+        // Count all
+        let total_all = client.user().count(vec![]).exec().await.unwrap();
+        assert_eq!(total_all, 3);
+
+        // Count with filter: age > 25
+        let total_adults = client
+            .user()
+            .count(vec![user::age::gt(Some(25))])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(total_adults, 1);
+
+        // Count with filter: age is null
+        let total_null_age = client
+            .user()
+            .count(vec![user::age::is_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(total_null_age, 1);
     }
 
     #[tokio::test]
-    async fn test_batch_delete_operations() {
-        use chrono::DateTime;
-        use std::str::FromStr;
-
+    async fn test_delete_operations() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        // Seed users
-        let u1 = client
+        // Create user with unique email
+        let email = format!("john_{}@example.com", chrono::Utc::now().timestamp());
+        let user = client
             .user()
             .create(
-                "del1@example.com".to_string(),
-                "Del1".to_string(),
+                email.clone(),
+                "John".to_string(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![],
+                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
             )
             .exec()
             .await
             .unwrap();
 
-        let u2 = client
+        // Delete user
+        client
             .user()
-            .create(
-                "del2@example.com".to_string(),
-                "Del2".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![],
-            )
+            .delete(user::id::equals(user.id))
             .exec()
             .await
             .unwrap();
 
-        // Batch delete both (tuple style, fluent; no local query var)
-        let (_d1, _d2) = client
-            ._batch::<user::Entity, user::ActiveModel, user::ModelWithRelations, (), (
-                caustics::query_builders::DeleteQueryBuilder<
-                    '_,
-                    sea_orm::DatabaseConnection,
-                    user::Entity,
-                    user::ModelWithRelations,
-                >,
-                caustics::query_builders::DeleteQueryBuilder<
-                    '_,
-                    sea_orm::DatabaseConnection,
-                    user::Entity,
-                    user::ModelWithRelations,
-                >,
-            )>((
-                client.user().delete(user::id::equals(u1.id)),
-                client.user().delete(user::id::equals(u2.id)),
-            ))
+        // Verify deletion
+        let deleted_user = client
+            .user()
+            .find_unique(user::id::equals(user.id))
+            .exec()
             .await
             .unwrap();
-
-        // Verify deletion
-        let left = client.user().find_many(vec![]).exec().await.unwrap();
-        assert!(left.is_empty());
+        assert!(deleted_user.is_none());
     }
 
     #[tokio::test]
-    async fn test_batch_upsert_operations() {
-        // no explicit caustics types here
-        use chrono::DateTime;
-        use std::str::FromStr;
-
+    async fn test_connect_with_mismatched_key_type_is_an_error() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        // First upserts will insert (tuple style, fluent)
-        let (_ins1, _ins2) = client
-            ._batch((
-                client.user().upsert(
-                    user::email::equals("bus1@example.com"),
-                    user::Create {
-                        name: "Bus1".to_string(),
-                        email: "bus1@example.com".to_string(),
-                        created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z")
-                            .unwrap(),
-                        updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z")
-                            .unwrap(),
-                        _params: vec![],
-                    },
-                    vec![user::age::set(10)],
-                ),
-                client.user().upsert(
-                    user::email::equals("bus2@example.com"),
-                    user::Create {
-                        name: "Bus2".to_string(),
-                        email: "bus2@example.com".to_string(),
-                        created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z")
-                            .unwrap(),
-                        updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z")
-                            .unwrap(),
-                        _params: vec![],
-                    },
-                    vec![user::age::set(20)],
-                ),
-            ))
+        let author = client
+            .user()
+            .create("keys@example.com".to_string(), "Keys".to_string(), now, now, vec![])
+            .exec()
             .await
             .unwrap();
 
-        // Second upserts will update (tuple arity 1 supported)
-        let (_upd1,) = client
-            ._batch((client.user().upsert(
-                user::email::equals("bus1@example.com"),
-                user::Create {
-                    name: "Bus1".to_string(),
-                    email: "bus1@example.com".to_string(),
-                    created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                    _params: vec![],
-                },
-                vec![user::name::set("Bus1-upd"), user::age::increment(5)],
-            ),))
+        // User ids are UUIDs: an integer key is reported, not coerced or panicked on
+        let err = client
+            .post()
+            .create(
+                "Mismatched".to_string(),
+                now,
+                now,
+                user::UniqueWhereParam::IdEquals(caustics::CausticsKey::I32(7)),
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("InvalidFieldType"), "{}", err);
+        assert_eq!(client.post().count(vec![]).exec().await.unwrap(), 0);
+
+        let post = client
+            .post()
+            .create("Valid".to_string(), now, now, user::id::equals(author.id), vec![])
+            .exec()
             .await
             .unwrap();
+        let err = client
+            .post()
+            .update(
+                post::id::equals(post.id),
+                vec![post::reviewer::connect(user::UniqueWhereParam::IdEquals(
+                    caustics::CausticsKey::String("not-a-uuid".to_string()),
+                ))],
+            )
+            .exec()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("InvalidFieldType"), "{}", err);
 
-        // Verify
-        let u1 = client
-            .user()
-            .find_unique(user::email::equals("bus1@example.com"))
+        let unchanged = client
+            .post()
+            .find_unique(post::id::equals(post.id))
             .exec()
             .await
             .unwrap()
             .unwrap();
-        assert_eq!(u1.name, "Bus1-upd");
-        assert_eq!(u1.age, Some(15));
+        assert_eq!(unchanged.reviewer_user_id, None);
     }
 
     #[tokio::test]
-    async fn test_string_operators() {
-        use chrono::TimeZone;
+    async fn test_delete_guard_prevents_deletion() {
+        use blog::entities::user::DeleteFilterExt;
+
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        // Create test users
-        let _user1 = client
+        let archived = client
             .user()
             .create(
-                "john.doe@example.com".to_string(),
-                "John Doe".to_string(),
+                "archived@example.com".to_string(),
+                "Archived".to_string(),
                 now,
                 now,
-                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+                vec![user::deleted_at::set(Some(now))],
             )
             .exec()
             .await
             .unwrap();
-
-        let _user2 = client
+        let active = client
             .user()
-            .create(
-                "jane.smith@example.com".to_string(),
-                "Jane Smith".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(28)), user::deleted_at::set(None)],
-            )
+            .create("active@example.com".to_string(), "Active".to_string(), now, now, vec![])
             .exec()
             .await
             .unwrap();
 
-        let _user3 = client
+        // The guard excludes the soft-deleted row, so it is reported missing and kept
+        let err = client
             .user()
-            .create(
-                "bob.johnson@test.org".to_string(),
-                "Bob Johnson".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(40)), user::deleted_at::set(None)],
-            )
+            .delete(user::id::equals(archived.id))
+            .filter(vec![user::deleted_at::is_null()])
             .exec()
             .await
-            .unwrap();
-
-        // Test contains operator
-        let users_with_doe = client
+            .unwrap_err();
+        assert!(matches!(err, sea_orm::DbErr::RecordNotFound(_)), "{:?}", err);
+        let kept = client
             .user()
-            .find_many(vec![user::name::contains("Doe")])
+            .find_unique(user::id::equals(archived.id))
             .exec()
             .await
             .unwrap();
-        assert_eq!(users_with_doe.len(), 1);
-        assert_eq!(users_with_doe[0].name, "John Doe");
+        assert!(kept.is_some());
 
-        // Test starts_with operator
-        let users_starting_with_j = client
+        let deleted = client
             .user()
-            .find_many(vec![user::name::starts_with("J")])
+            .delete(user::id::equals(active.id))
+            .filter(vec![user::deleted_at::is_null()])
             .exec()
             .await
             .unwrap();
-        assert_eq!(users_starting_with_j.len(), 2);
-        assert!(users_starting_with_j
-            .iter()
-            .all(|u| u.name.starts_with("J")));
+        assert_eq!(deleted.id, active.id);
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 1);
+    }
 
-        // Test ends_with operator
-        let users_ending_with_son = client
-            .user()
-            .find_many(vec![user::name::ends_with("son")])
-            .exec()
-            .await
-            .unwrap();
-        assert_eq!(users_ending_with_son.len(), 1);
-        assert_eq!(users_ending_with_son[0].name, "Bob Johnson");
+    #[tokio::test]
+    async fn test_delete_many_returns_count() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
 
-        // Test email contains
-        let users_with_example_email = client
-            .user()
-            .find_many(vec![user::email::contains("example.com")])
-            .exec()
-            .await
+        // Create three users
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
             .unwrap();
-        assert_eq!(users_with_example_email.len(), 2);
+        for i in 0..3 {
+            let _ = client
+                .user()
+                .create(
+                    format!("delmany{}@example.com", i),
+                    format!("DelMany{}", i),
+                    now,
+                    now,
+                    vec![user::age::set(Some(20 + i)), user::deleted_at::set(None)],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
 
-        let users_with_test_email = client
+        // Delete where age > 20 (two rows)
+        let deleted = client
             .user()
-            .find_many(vec![user::email::ends_with("test.org")])
+            .delete_many(vec![user::age::gt(Some(20))])
             .exec()
             .await
             .unwrap();
-        assert_eq!(users_with_test_email.len(), 1);
-        assert_eq!(users_with_test_email[0].email, "bob.johnson@test.org");
+        assert_eq!(deleted, 2);
+
+        // Remaining count should be 1
+        let remaining = client.user().count(vec![]).exec().await.unwrap();
+        assert_eq!(remaining, 1);
     }
 
     #[tokio::test]
-    async fn test_comparison_operators() {
-        use chrono::TimeZone;
+    async fn test_upsert_operations() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
 
-        // Create test users with different ages for comparison
-        let _user1 = client
+        // Upsert user
+        let user = client
             .user()
-            .create(
-                "young@example.com".to_string(),
-                "Young User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(18)), user::deleted_at::set(None)],
+            .upsert(
+                user::email::equals("john@example.com"),
+                user::Create {
+                    name: "John".to_string(),
+                    email: "john@example.com".to_string(),
+                    created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    _params: vec![],
+                },
+                vec![user::name::set("John"), user::age::set(25)],
             )
             .exec()
             .await
             .unwrap();
+        assert_eq!(user.name, "John");
+        assert_eq!(user.age, Some(25));
 
-        let _user2 = client
+        // Update existing user
+        let updated_user = client
             .user()
-            .create(
-                "middle@example.com".to_string(),
-                "Middle User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+            .upsert(
+                user::email::equals("john@example.com"),
+                user::Create {
+                    name: "John".to_string(),
+                    email: "john@example.com".to_string(),
+                    created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    _params: vec![],
+                },
+                vec![user::name::set("John Doe"), user::age::set(26)],
             )
             .exec()
             .await
             .unwrap();
+        assert_eq!(updated_user.name, "John Doe");
+        assert_eq!(updated_user.age, Some(26));
+    }
 
-        let _user3 = client
+    #[tokio::test]
+    async fn test_upsert_is_single_statement_on_unique_email() {
+        let mut db = setup_test_db().await;
+        let statements = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let recorded = statements.clone();
+        db.set_metric_callback(move |info| {
+            recorded.lock().unwrap().push(info.statement.sql.clone());
+        });
+        let client = blog::CausticsClient::new(db);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let create = || user::Create {
+            name: "Native".to_string(),
+            email: "native@example.com".to_string(),
+            created_at: created,
+            updated_at: created,
+            _params: vec![],
+        };
+
+        let inserted = client
             .user()
-            .create(
-                "old@example.com".to_string(),
-                "Old User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(45)), user::deleted_at::set(None)],
-            )
+            .upsert(user::email::equals("native@example.com"), create(), vec![user::age::set(Some(40))])
             .exec()
             .await
             .unwrap();
+        assert_eq!(inserted.age, Some(40));
+        {
+            let mut statements = statements.lock().unwrap();
+            assert_eq!(statements.len(), 1, "{:?}", statements);
+            assert!(statements[0].contains("ON CONFLICT"));
+            statements.clear();
+        }
 
-        // Test greater than operator
-        let older_users = client
+        let updated = client
             .user()
-            .find_many(vec![user::age::gt(Some(25))])
+            .upsert(user::email::equals("native@example.com"), create(), vec![user::age::set(Some(41))])
             .exec()
             .await
             .unwrap();
-        assert_eq!(older_users.len(), 2);
-        assert!(older_users.iter().all(|u| u.age.unwrap_or(0) > 25));
+        assert_eq!(updated.id, inserted.id);
+        assert_eq!(updated.age, Some(41));
+        assert_eq!(statements.lock().unwrap().len(), 1);
 
-        // Test less than operator
-        let younger_users = client
+        // Conflicts only touch the columns the update sets
+        let untouched = client
             .user()
-            .find_many(vec![user::age::lt(Some(25))])
+            .upsert(
+                user::email::equals("native@example.com"),
+                user::Create { name: "Renamed".to_string(), ..create() },
+                vec![],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(younger_users.len(), 1);
-        assert_eq!(younger_users[0].age, Some(18));
+        assert_eq!(untouched.id, inserted.id);
+        assert_eq!(untouched.name, "Native");
+        assert_eq!(untouched.age, Some(41));
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 1);
+    }
 
-        // Test greater than or equal operator
-        let adult_users = client
+    #[tokio::test]
+    async fn test_create_on_conflict_ignore() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let first = client
             .user()
-            .find_many(vec![user::age::gte(Some(18))])
+            .create("idem@example.com".to_string(), "First".to_string(), created, created, vec![])
+            .on_conflict_ignore()
             .exec()
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(adult_users.len(), 3);
+        assert_eq!(first.name, "First");
 
-        // Test less than or equal operator
-        let max_30_users = client
+        // The duplicate email is skipped without an error and the original row is untouched
+        let skipped = client
             .user()
-            .find_many(vec![user::age::lte(Some(30))])
+            .create("idem@example.com".to_string(), "Second".to_string(), created, created, vec![])
+            .on_conflict_ignore()
             .exec()
             .await
             .unwrap();
-        assert_eq!(max_30_users.len(), 2);
-
-        // Test in_vec operator
-        let specific_ages = client
+        assert!(skipped.is_none());
+        let stored = client
             .user()
-            .find_many(vec![user::age::in_vec(vec![Some(18), Some(45)])])
+            .find_unique(user::email::equals("idem@example.com"))
             .exec()
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(specific_ages.len(), 2);
+        assert_eq!(stored.id, first.id);
+        assert_eq!(stored.name, "First");
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 1);
+    }
 
-        // Test not_in_vec operator
-        let not_specific_ages = client
+    #[tokio::test]
+    async fn test_upsert_many_inserts_and_updates() {
+        let mut db = setup_test_db().await;
+        let statements = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let recorded = statements.clone();
+        db.set_metric_callback(move |info| {
+            recorded.lock().unwrap().push(info.statement.sql.clone());
+        });
+        let client = blog::CausticsClient::new(db);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let existing = client
             .user()
-            .find_many(vec![user::age::not_in_vec(vec![Some(18), Some(45)])])
+            .create("old@example.com".to_string(), "Old".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-        assert_eq!(not_specific_ages.len(), 1);
-        assert_eq!(not_specific_ages[0].age, Some(30));
-    }
+        statements.lock().unwrap().clear();
 
-    #[tokio::test]
-    async fn test_logical_operators() {
-        use chrono::TimeZone;
-        let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        let create = |name: &str, email: &str| user::Create {
+            name: name.to_string(),
+            email: email.to_string(),
+            created_at: created,
+            updated_at: created,
+            _params: vec![],
+        };
+        let rows = client
+            .user()
+            .upsert_many(vec![
+                (
+                    user::email::equals("old@example.com"),
+                    create("Old", "old@example.com"),
+                    vec![user::age::set(Some(50))],
+                ),
+                (
+                    user::email::equals("new@example.com"),
+                    create("New", "new@example.com"),
+                    vec![user::age::set(Some(20))],
+                ),
+            ])
+            .exec_returning()
+            .await
             .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].id, existing.id);
+        assert_eq!(rows[0].age, Some(50));
+        assert_eq!(rows[1].email, "new@example.com");
+        assert_eq!(rows[1].age, Some(20));
+        let upserts: Vec<_> = statements
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|sql| sql.contains("ON CONFLICT"))
+            .cloned()
+            .collect();
+        assert_eq!(upserts.len(), 1, "{:?}", upserts);
 
-        // Create test users with varied data for logical testing
-        let _user1 = client
+        // Rows that set different columns fall back to one upsert each, still in one call
+        let count = client
             .user()
-            .create(
-                "young.john@example.com".to_string(),
-                "John Young".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(16)), user::deleted_at::set(None)],
-            )
+            .upsert_many(vec![
+                (
+                    user::email::equals("new@example.com"),
+                    create("New", "new@example.com"),
+                    vec![user::name::set("Renamed")],
+                ),
+                (
+                    user::email::equals("third@example.com"),
+                    create("Third", "third@example.com"),
+                    vec![user::age::set(Some(33))],
+                ),
+            ])
             .exec()
             .await
             .unwrap();
-
-        let _user2 = client
+        assert_eq!(count, 2);
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 3);
+        let renamed = client
             .user()
-            .create(
-                "adult.jane@example.com".to_string(),
-                "Jane Adult".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
-            )
+            .find_unique(user::email::equals("new@example.com"))
             .exec()
             .await
+            .unwrap()
             .unwrap();
-
-        let _user3 = client
+        assert_eq!(renamed.name, "Renamed");
+        assert_eq!(renamed.age, Some(20));
+        let third = client
             .user()
-            .create(
-                "senior.bob@test.org".to_string(),
-                "Bob Senior".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(70)), user::deleted_at::set(None)],
-            )
+            .find_unique(user::email::equals("third@example.com"))
             .exec()
             .await
+            .unwrap()
             .unwrap();
+        assert_eq!(third.age, Some(33));
+    }
 
-        let _user4 = client
+    #[tokio::test]
+    async fn test_database_error_code_and_mapper() {
+        use sea_orm::{ConnectionTrait, Database, Schema};
+        use sea_query::Expr;
+
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let schema = Schema::new(db.get_database_backend());
+        let mut user_table = schema.create_table_from_entity(user::Entity);
+        user_table.check(Expr::col(user::Column::Age).gte(0));
+        db.execute(db.get_database_backend().build(&user_table)).await.unwrap();
+        let client = blog::CausticsClient::new(db);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let err = client
             .user()
             .create(
-                "middle.alice@example.com".to_string(),
-                "Alice Middle".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(35)), user::deleted_at::set(None)],
+                "negative@example.com".to_string(),
+                "Negative".to_string(),
+                created,
+                created,
+                vec![user::age::set(Some(-1))],
             )
             .exec()
             .await
-            .unwrap();
+            .unwrap_err();
+        // SQLITE_CONSTRAINT_CHECK; Postgres reports SQLSTATE 23514
+        match caustics::CausticsError::from_db_err(err) {
+            Ok(caustics::CausticsError::Database { code, message, .. }) => {
+                assert_eq!(code.as_deref(), Some("275"));
+                assert!(message.contains("CHECK"), "{}", message);
+            }
+            other => panic!("expected a database error, got {:?}", other.err()),
+        }
+        assert!(caustics::CausticsError::from_db_err(QueryError::Custom("x".into())).is_err());
 
-        // Test AND operator - users who are adults AND have "example.com" email
-        let adult_example_users = client
+        // Only check violations are translated; other failures pass through untouched
+        let mapped = client.with_error_mapper(|database_error| match &database_error {
+            caustics::CausticsError::Database { code: Some(code), .. } if code == "275" || code == "23514" => {
+                caustics::CausticsError::QueryValidation {
+                    message: "age must not be negative".to_string(),
+                }
+                .into()
+            }
+            _ => database_error.into(),
+        });
+        let user = mapped
             .user()
-            .find_many(vec![operator::and(vec![
-                user::age::gte(Some(18)),
-                user::email::contains("example.com"),
-            ])])
+            .create("valid@example.com".to_string(), "Valid".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-        assert_eq!(adult_example_users.len(), 2); // Jane and Alice
-        assert!(adult_example_users
-            .iter()
-            .all(|u| u.age.unwrap_or(0) >= 18 && u.email.contains("example.com")));
+        let err = mapped
+            .user()
+            .update(user::id::equals(user.id), vec![user::age::set(Some(-5))])
+            .exec()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("CausticsError::QueryValidation: age must not be negative"), "{}", err);
+        let err = mapped
+            ._execute_raw(caustics::raw!("UPDATE users SET age = -5"))
+            .exec()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("age must not be negative"), "{}", err);
+        let err = mapped
+            ._transaction()
+            .run(|tx| async move {
+                tx.user()
+                    .create(
+                        "tx@example.com".to_string(),
+                        "Tx".to_string(),
+                        created,
+                        created,
+                        vec![user::age::set(Some(-5))],
+                    )
+                    .exec()
+                    .await
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("age must not be negative"), "{}", err);
 
-        // Test OR operator - users who are either very young OR very old
-        let young_or_old_users = client
+        // The mapper belongs to `mapped`; the client it was derived from is unaffected
+        let err = client
             .user()
-            .find_many(vec![operator::or(vec![
-                user::age::lt(Some(18)),
-                user::age::gt(Some(65)),
-            ])])
+            .update(user::id::equals(user.id), vec![user::age::set(Some(-5))])
             .exec()
             .await
-            .unwrap();
-        assert_eq!(young_or_old_users.len(), 2); // John (16) and Bob (70)
-        assert!(young_or_old_users.iter().all(|u| {
-            let age = u.age.unwrap_or(0);
-            !(18..=65).contains(&age)
-        }));
+            .unwrap_err();
+        assert!(!err.to_string().contains("age must not be negative"), "{}", err);
+        let duplicate = mapped
+            .user()
+            .create("valid@example.com".to_string(), "Again".to_string(), created, created, vec![])
+            .exec()
+            .await
+            .unwrap_err();
+        match caustics::CausticsError::from_db_err(duplicate) {
+            Ok(caustics::CausticsError::Database { code, .. }) => assert_eq!(code.as_deref(), Some("2067")),
+            other => panic!("expected a database error, got {:?}", other.err()),
+        }
+    }
 
-        // Test NOT operator - users who are NOT minors
-        let not_minors = client
+    #[tokio::test]
+    async fn test_upsert_update_only_columns() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let later = DateTime::<FixedOffset>::from_str("2022-06-01T00:00:00Z").unwrap();
+        let original = client
             .user()
-            .find_many(vec![operator::not(vec![user::age::lt(Some(18))])])
+            .create(
+                "only@example.com".to_string(),
+                "Original".to_string(),
+                created,
+                created,
+                vec![user::age::set(Some(30))],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(not_minors.len(), 3); // Jane, Bob, and Alice
-        assert!(not_minors.iter().all(|u| u.age.unwrap_or(0) >= 18));
 
-        // Test complex nested logical operations -
-        // (adults with example.com email) OR (seniors regardless of email)
-        let complex_query_users = client
+        // The conflict takes name and updated_at from the incoming row; age is left alone
+        // even though the update sets it
+        let upserted = client
             .user()
-            .find_many(vec![operator::or(vec![
-                operator::and(vec![
-                    user::age::gte(Some(18)),
-                    user::age::lt(Some(65)),
-                    user::email::contains("example.com"),
-                ]),
-                user::age::gte(Some(65)),
-            ])])
+            .upsert(
+                user::email::equals("only@example.com"),
+                user::Create {
+                    name: "Incoming".to_string(),
+                    email: "only@example.com".to_string(),
+                    created_at: later,
+                    updated_at: later,
+                    _params: vec![],
+                },
+                vec![user::age::set(Some(99))],
+            )
+            .update_only(vec![user::Column::Name, user::Column::UpdatedAt])
             .exec()
             .await
             .unwrap();
-        assert_eq!(complex_query_users.len(), 3); // Jane, Alice (adults with example.com), and Bob (senior)
+        assert_eq!(upserted.id, original.id);
+        assert_eq!(upserted.name, "Incoming");
+        assert_eq!(upserted.updated_at, later);
+        assert_eq!(upserted.created_at, created);
+        assert_eq!(upserted.age, Some(30));
 
-        // Test NOT with AND - users who are NOT (young AND have example.com email)
-        let not_young_example = client
+        let reloaded = client
             .user()
-            .find_many(vec![operator::not(vec![operator::and(vec![
-                user::age::lt(Some(25)),
-                user::email::contains("example.com"),
-            ])])])
+            .find_unique(user::id::equals(original.id))
             .exec()
             .await
+            .unwrap()
             .unwrap();
-        // Should exclude no one since John is young but has example.com, but John is <25 and doesn't have example.com
-        // Actually, John has example.com but is young, so NOT(young AND example.com) excludes John... wait let me think about this
-        // John: age=16, email="young.john@example.com" -> young=true, has_example=true -> AND=true -> NOT=false (excluded)
-        // Jane: age=25, email="adult.jane@example.com" -> young=false, has_example=true -> AND=false -> NOT=true (included)
-        // Bob: age=70, email="senior.bob@test.org" -> young=false, has_example=false -> AND=false -> NOT=true (included)
-        // Alice: age=35, email="middle.alice@example.com" -> young=false, has_example=true -> AND=false -> NOT=true (included)
-        assert_eq!(not_young_example.len(), 3); // Jane, Bob, Alice (John is excluded)
+        assert_eq!(reloaded.name, "Incoming");
+        assert_eq!(reloaded.age, Some(30));
+
+        // A listed column the incoming row leaves unset keeps its value, whether the upsert
+        // runs as one ON CONFLICT statement or falls back to find-then-update
+        for native in [true, false] {
+            let mut upsert = client
+                .user()
+                .upsert(
+                    user::email::equals("only@example.com"),
+                    user::Create {
+                        name: format!("Native {}", native),
+                        email: "only@example.com".to_string(),
+                        created_at: later,
+                        updated_at: later,
+                        _params: vec![],
+                    },
+                    vec![],
+                )
+                .update_only(vec![user::Column::Name, user::Column::Age]);
+            assert!(upsert.native_conflict.is_some());
+            if !native {
+                upsert.native_conflict = None;
+            }
+            let upserted = upsert.exec().await.unwrap();
+            assert_eq!(upserted.id, original.id);
+            assert_eq!(upserted.name, format!("Native {}", native));
+            assert_eq!(upserted.age, Some(30));
+        }
     }
 
     #[tokio::test]
-    async fn test_basic_functionality() {
-        use chrono::TimeZone;
-        let db = setup_test_db().await;
+    async fn test_diff_updates_only_changed_fields() {
+        use sea_orm::EntityTrait;
+
+        let mut db = setup_test_db().await;
+        let statements = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let recorded = statements.clone();
+        db.set_metric_callback(move |info| {
+            recorded.lock().unwrap().push(info.statement.sql.clone());
+        });
         let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
 
-        // Test basic create and find functionality
-        let _user = client
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let inserted = client
             .user()
             .create(
-                "test@example.com".to_string(),
-                "Test User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+                "diff@example.com".to_string(),
+                "Before".to_string(),
+                created,
+                created,
+                vec![user::age::set(Some(30))],
             )
             .exec()
             .await
             .unwrap();
 
-        // Verify existing functionality still works
-        let found_user = client
+        let old = user::Entity::find_by_id(inserted.id).one(&db).await.unwrap().unwrap();
+        assert!(user::diff(&old, &old).is_empty());
+
+        let mut new = old.clone();
+        new.name = "After".to_string();
+        new.age = None;
+        let changes = user::diff(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert!(matches!(&changes[0], user::SetParam::Name(sea_orm::ActiveValue::Set(name)) if name == "After"));
+        assert!(matches!(&changes[1], user::SetParam::Age(sea_orm::ActiveValue::Set(None))));
+
+        statements.lock().unwrap().clear();
+        let updated = client
             .user()
-            .find_first(vec![user::email::equals("test@example.com")])
+            .update(user::id::equals(inserted.id), changes)
             .exec()
             .await
             .unwrap();
+        assert_eq!(updated.name, "After");
+        assert_eq!(updated.age, None);
+        assert_eq!(updated.email, "diff@example.com");
 
-        assert!(found_user.is_some());
-        let found = found_user.unwrap();
-        assert_eq!(found.name, "Test User");
-        assert_eq!(found.age, Some(25));
+        let statements = statements.lock().unwrap();
+        let update = statements
+            .iter()
+            .find(|sql| sql.starts_with("UPDATE"))
+            .expect("update statement");
+        assert!(update.contains("\"name\"") && update.contains("\"age\""), "{}", update);
+        assert!(!update.contains("\"email\"") && !update.contains("\"created_at\""), "{}", update);
     }
 
     #[tokio::test]
-    async fn test_collection_operators_readme_examples() {
-        use chrono::TimeZone;
-        let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
+    async fn test_create_exec_returning_id() {
+        let mut db = setup_test_db().await;
+        let statements = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let recorded = statements.clone();
+        db.set_metric_callback(move |info| {
+            recorded.lock().unwrap().push(info.statement.sql.clone());
+        });
+        let client = blog::CausticsClient::new(db);
 
-        // Create test users to match README examples
-        let user1 = client
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let id: Uuid = client
             .user()
-            .create(
-                "user1@example.com".to_string(),
-                "User One".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(13)), user::deleted_at::set(None)],
-            )
-            .exec()
+            .create("ids@example.com".to_string(), "Ids".to_string(), created, created, vec![])
+            .exec_returning_id()
             .await
             .unwrap();
+        // Just the INSERT: the row is not read back
+        assert_eq!(statements.lock().unwrap().len(), 1);
 
-        let user2 = client
+        let found = client
             .user()
-            .create(
-                "user2@example.com".to_string(),
-                "User Two".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(14)), user::deleted_at::set(None)],
-            )
+            .find_unique(user::id::equals(id))
             .exec()
             .await
+            .unwrap()
+            .expect("user should exist under the returned id");
+        assert_eq!(found.email, "ids@example.com");
+
+        let post_id = client
+            .post()
+            .create("Returning".to_string(), created, created, user::id::equals(id), vec![])
+            .exec_returning_id()
+            .await
             .unwrap();
+        let post = client.post().find_unique(post::id::equals(post_id)).exec().await.unwrap().unwrap();
+        assert_eq!(post.title, "Returning");
+        assert_eq!(post.user_id, id);
+    }
 
-        let user3 = client
+    #[tokio::test]
+    async fn test_create_with_explicit_primary_key() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        let fixed = Uuid::parse_str("6f1c1a52-3c1e-4a8e-9d1f-0b5e2f6a7c11").unwrap();
+        let user = client
             .user()
             .create(
-                "user3@example.com".to_string(),
-                "User Three".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+                "fixed@example.com".to_string(),
+                "Fixed".to_string(),
+                created,
+                created,
+                vec![user::id::set(fixed)],
             )
             .exec()
             .await
             .unwrap();
-
-        let user5 = client
+        assert_eq!(user.id, fixed);
+        let found = client
+            .user()
+            .find_unique(user::id::equals(fixed))
+            .exec()
+            .await
+            .unwrap()
+            .expect("user should exist under the explicit id");
+        assert_eq!(found.email, "fixed@example.com");
+        let returned: Uuid = client
             .user()
             .create(
-                "user5@example.com".to_string(),
-                "User Five".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(15)), user::deleted_at::set(None)],
+                "fixed2@example.com".to_string(),
+                "Fixed2".to_string(),
+                created,
+                created,
+                vec![user::id::set(Uuid::nil())],
             )
-            .exec()
+            .exec_returning_id()
             .await
             .unwrap();
+        assert_eq!(returned, Uuid::nil());
+    }
 
-        let user8 = client
+    #[tokio::test]
+    async fn test_exec_on_external_transaction() {
+        use sea_orm::TransactionTrait;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let outside = client
             .user()
-            .create(
-                "user8@example.com".to_string(),
-                "User Eight".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
-            )
+            .create("outside@example.com".to_string(), "Outside".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
 
-        // Test README example: user::id::in_vec(vec![1, 2, 3, 5, 8])
-        // Using actual IDs from created users
-        let users_by_ids = client
+        // A transaction caustics does not manage
+        let txn = db.begin().await.unwrap();
+        let inside = client
             .user()
-            .find_many(vec![user::id::in_vec(vec![
-                user1.id, user2.id, user3.id, user5.id, user8.id,
-            ])])
-            .exec()
+            .create("inside@example.com".to_string(), "Inside".to_string(), created, created, vec![])
+            .exec_in_txn(&txn)
+            .await
+            .unwrap();
+        client
+            .post()
+            .create("Uncommitted".to_string(), created, created, user::id::equals(inside.id), vec![])
+            .exec_in_txn(&txn)
             .await
             .unwrap();
-        assert_eq!(users_by_ids.len(), 5);
-        let found_ids: Vec<Uuid> = users_by_ids.iter().map(|u| u.id).collect();
-        assert!(found_ids.contains(&user1.id));
-        assert!(found_ids.contains(&user2.id));
-        assert!(found_ids.contains(&user3.id));
-        assert!(found_ids.contains(&user5.id));
-        assert!(found_ids.contains(&user8.id));
 
-        // Test README example: user::age::not_in_vec(vec![Some(13), Some(14), Some(15)])
-        let users_excluding_young_ages = client
+        let users = client
             .user()
-            .find_many(vec![user::age::not_in_vec(vec![
-                Some(13),
-                Some(14),
-                Some(15),
-            ])])
-            .exec()
+            .find_many(vec![])
+            .order_by(user::email::order(SortOrder::Asc))
+            .exec_on(&txn)
             .await
             .unwrap();
-        assert_eq!(users_excluding_young_ages.len(), 2); // user3 (25) and user8 (30)
-        assert!(users_excluding_young_ages.iter().all(|u| {
-            let age = u.age.unwrap_or(0);
-            age != 13 && age != 14 && age != 15
-        }));
+        assert_eq!(
+            users.iter().map(|u| u.email.as_str()).collect::<Vec<_>>(),
+            vec!["inside@example.com", "outside@example.com"]
+        );
+        assert_eq!(client.user().count(vec![]).exec_on(&txn).await.unwrap(), 2);
+        let with_posts = client
+            .user()
+            .find_unique(user::id::equals(inside.id))
+            .with(user::posts::fetch(vec![]))
+            .exec_on(&txn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(with_posts.posts.as_ref().map(Vec::len), Some(1));
+        let first = client
+            .user()
+            .find_first(vec![user::name::equals("Inside")])
+            .exec_on(&txn)
+            .await
+            .unwrap();
+        assert_eq!(first.map(|u| u.id), Some(inside.id));
+        txn.rollback().await.unwrap();
 
-        // Verify the excluded users have the expected ages
-        let included_ages: Vec<Option<i32>> =
-            users_excluding_young_ages.iter().map(|u| u.age).collect();
-        assert!(included_ages.contains(&Some(25)));
-        assert!(included_ages.contains(&Some(30)));
+        // Rolled back along with the caller's transaction
+        let users = client.user().find_many(vec![]).exec().await.unwrap();
+        assert_eq!(users.iter().map(|u| u.id).collect::<Vec<_>>(), vec![outside.id]);
     }
 
     #[tokio::test]
-    async fn test_null_operators() {
-        use chrono::TimeZone;
+    async fn test_create_from_model_duplicates_row() {
+        use sea_orm::EntityTrait;
+
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
 
-        // Create test users with various null combinations
-        let user_with_age = client
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let author = client
             .user()
             .create(
-                "with_age@example.com".to_string(),
-                "User With Age".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+                "dup@example.com".to_string(),
+                "Dup".to_string(),
+                created,
+                created,
+                vec![],
             )
             .exec()
             .await
             .unwrap();
-
-        let user_without_age = client
-            .user()
+        let original = client
+            .post()
             .create(
-                "without_age@example.com".to_string(),
-                "User Without Age".to_string(),
-                now,
-                now,
-                vec![user::age::set(None), user::deleted_at::set(None)],
+                "Original".to_string(),
+                created,
+                created,
+                user::id::equals(author.id),
+                vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        let user_deleted = client
+        let model = post::Entity::find_by_id(original.id).one(&db).await.unwrap().unwrap();
+        let create = post::Create::try_from(model.clone()).unwrap();
+        assert!(create._params.is_empty());
+        let copy = client.post().create_from(create).exec().await.unwrap();
+
+        // Auto-generated key is fresh, everything else is carried over
+        assert_ne!(copy.id, model.id);
+        assert_eq!(copy.title, model.title);
+        assert_eq!(copy.content, model.content);
+        assert_eq!(copy.created_at, model.created_at);
+        assert_eq!(copy.user_id, model.user_id);
+        assert_eq!(copy.reviewer_user_id, model.reviewer_user_id);
+        assert_eq!(client.post().count(vec![]).exec().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_into_select_custom_join() {
+        use sea_orm::{ColumnTrait, QueryFilter, QuerySelect};
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for (email, title) in [
+            ("join-a@example.com", "Keep"),
+            ("join-b@example.com", "Keep"),
+            ("join-c@example.com", "Skip"),
+        ] {
+            let author = client
+                .user()
+                .create(email.to_string(), "Join".to_string(), created, created, vec![])
+                .exec()
+                .await
+                .unwrap();
+            client
+                .post()
+                .create(
+                    title.to_string(),
+                    created,
+                    created,
+                    user::id::equals(author.id),
+                    vec![],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        // Filter, ordering and limit come from caustics; the join is added on the SeaORM side
+        let select = client
             .user()
-            .create(
-                "deleted@example.com".to_string(),
-                "Deleted User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(25)), user::deleted_at::set(Some(now))],
-            )
-            .exec()
+            .find_many(vec![user::name::equals("Join")])
+            .order_by(user::email::order(SortOrder::Desc))
+            .take(1)
+            .into_select();
+        let users = select
+            .inner_join(post::Entity)
+            .filter(post::Column::Title.eq("Keep"))
+            .distinct()
+            .all(&db)
             .await
             .unwrap();
+        let emails: Vec<_> = users.iter().map(|u| u.email.as_str()).collect();
+        assert_eq!(emails, vec!["join-b@example.com"]);
+    }
 
-        let user_no_deletions = client
+    #[tokio::test]
+    async fn test_correlation_id_sql_comment() {
+        let mut db = setup_test_db().await;
+        let statements = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let recorded = statements.clone();
+        db.set_metric_callback(move |info| {
+            recorded.lock().unwrap().push(info.statement.sql.clone());
+        });
+        let client = blog::CausticsClient::new(db);
+        let commented = client.with_sql_comments(true);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        client
             .user()
-            .create(
-                "no_deletions@example.com".to_string(),
-                "No Deletions User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(35)), user::deleted_at::set(None)],
-            )
+            .create("corr@example.com".to_string(), "Corr".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
 
-        // Create posts with various null combinations
-        let post_with_content = client
-            .post()
-            .create(
-                "Post with content".to_string(),
-                now,
-                now,
-                user::id::equals(user_with_age.id),
-                vec![
-                    post::content::set(Some("This post has content".to_string())),
-                    post::reviewer_user_id::set(None),
-                ],
-            )
+        // Opt-in, and only while a correlation id is set
+        let query = || commented.user().find_many(vec![user::name::equals("Corr")]);
+        assert!(query().to_sql().starts_with("SELECT"));
+        caustics::hooks::set_thread_correlation_id(Some("req-42".to_string()));
+        assert!(client.user().find_many(vec![]).to_sql().starts_with("SELECT"));
+        let sql = query().to_sql();
+        assert!(sql.starts_with("/* corr_id=req-42 */ SELECT"), "{}", sql);
+
+        // The comment reaches the database and the bound filter still applies
+        statements.lock().unwrap().clear();
+        let users = query().exec().await.unwrap();
+        assert_eq!(users.len(), 1);
+        let found = commented
+            .user()
+            .find_unique(user::email::equals("corr@example.com"))
             .exec()
             .await
             .unwrap();
+        assert!(found.is_some());
+        {
+            let statements = statements.lock().unwrap();
+            assert_eq!(statements.len(), 2);
+            assert!(statements.iter().all(|sql| sql.starts_with("/* corr_id=req-42 */ SELECT")));
+        }
 
-        let post_without_content = client
-            .post()
-            .create(
-                "Post without content".to_string(),
-                now,
-                now,
-                user::id::equals(user_without_age.id),
-                vec![
-                    post::content::set(None),
-                    post::reviewer_user_id::set(Some(user_with_age.id)),
-                ],
-            )
+        // Writes, counts and aggregates carry it too
+        statements.lock().unwrap().clear();
+        let second = commented
+            .user()
+            .create("corr-2@example.com".to_string(), "Corr".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-
-        // Test is_null operator for age field
-        let users_without_age = client
+        commented
             .user()
-            .find_many(vec![user::age::is_null()])
+            .update(user::id::equals(second.id), vec![user::age::set(Some(40))])
             .exec()
             .await
             .unwrap();
-        assert_eq!(users_without_age.len(), 1);
-        assert_eq!(users_without_age[0].id, user_without_age.id);
-        assert_eq!(users_without_age[0].age, None);
+        assert_eq!(commented.user().count(vec![user::name::equals("Corr")]).exec().await.unwrap(), 2);
+        commented.user().aggregate(vec![]).count().exec().await.unwrap();
+        commented.user().delete(user::id::equals(second.id)).exec().await.unwrap();
+        {
+            let statements = statements.lock().unwrap();
+            assert!(statements.len() >= 5);
+            assert!(statements.iter().all(|sql| sql.starts_with("/* corr_id=req-42 */ ")), "{:?}", statements);
+        }
 
-        // Test is_not_null operator for age field
-        let users_with_age = client
+        // Characters that could end the comment are dropped from the id
+        caustics::hooks::set_thread_correlation_id(Some("a*/ DROP TABLE users; --".to_string()));
+        assert!(query().to_sql().starts_with("/* corr_id=aDROPTABLEusers-- */ SELECT"));
+        caustics::hooks::set_thread_correlation_id(None);
+    }
+
+    #[cfg(feature = "select")]
+    #[tokio::test]
+    async fn test_correlation_id_sql_comment_on_select() {
+        let mut db = setup_test_db().await;
+        let statements = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let recorded = statements.clone();
+        db.set_metric_callback(move |info| {
+            recorded.lock().unwrap().push(info.statement.sql.clone());
+        });
+        let client = blog::CausticsClient::new(db);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        client
             .user()
-            .find_many(vec![user::age::is_not_null()])
+            .create("corr@example.com".to_string(), "Corr".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-        assert_eq!(users_with_age.len(), 3);
-        let user_ids_with_age: Vec<Uuid> = users_with_age.iter().map(|u| u.id).collect();
-        assert!(user_ids_with_age.contains(&user_with_age.id));
-        assert!(user_ids_with_age.contains(&user_deleted.id));
-        assert!(user_ids_with_age.contains(&user_no_deletions.id));
-        assert!(!user_ids_with_age.contains(&user_without_age.id));
 
-        // Test is_null operator for deleted_at field
-        let non_deleted_users = client
+        caustics::hooks::set_thread_correlation_id(Some("req-7".to_string()));
+        statements.lock().unwrap().clear();
+        let selected: Vec<user::Selected> = client
+            .with_sql_comments(true)
             .user()
-            .find_many(vec![user::deleted_at::is_null()])
+            .find_many(vec![user::name::equals("Corr")])
+            .select(user::select!(id, name))
             .exec()
             .await
             .unwrap();
-        assert_eq!(non_deleted_users.len(), 3);
-        let non_deleted_ids: Vec<Uuid> = non_deleted_users.iter().map(|u| u.id).collect();
-        assert!(non_deleted_ids.contains(&user_with_age.id));
-        assert!(non_deleted_ids.contains(&user_without_age.id));
-        assert!(non_deleted_ids.contains(&user_no_deletions.id));
-        assert!(!non_deleted_ids.contains(&user_deleted.id));
+        caustics::hooks::set_thread_correlation_id(None);
+        assert_eq!(selected.len(), 1);
+        let statements = statements.lock().unwrap();
+        assert_eq!(statements.len(), 1);
+        assert!(statements[0].starts_with("/* corr_id=req-7 */ SELECT"), "{}", statements[0]);
+    }
 
-        // Test is_not_null operator for deleted_at field
-        let deleted_users = client
+    #[tokio::test]
+    async fn test_max_rows_guards_uncapped_find_many() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let seed: Vec<_> = (0..200)
+            .map(|i| user::Create {
+                email: format!("bulk{}@example.com", i),
+                name: if i < 10 { "Few".to_string() } else { "Many".to_string() },
+                created_at: created,
+                updated_at: created,
+                _params: vec![],
+            })
+            .collect();
+        client.user().create_many(seed).exec().await.unwrap();
+
+        let guarded = client.max_rows(Some(10));
+        let err = guarded.user().find_many(vec![]).exec().await.unwrap_err();
+        assert!(err.to_string().contains("CausticsError::RowLimitExceeded"), "{}", err);
+        assert!(err.to_string().contains("limit=10"), "{}", err);
+
+        // Exactly at the cap is fine, as is an explicit take above it
+        let few = guarded.user().find_many(vec![user::name::equals("Few")]).exec().await.unwrap();
+        assert_eq!(few.len(), 10);
+        let taken = guarded.user().find_many(vec![]).take(150).exec().await.unwrap();
+        assert_eq!(taken.len(), 150);
+
+        // The unguarded client is unaffected
+        assert_eq!(client.user().find_many(vec![]).exec().await.unwrap().len(), 200);
+    }
+
+    #[cfg(feature = "select")]
+    #[tokio::test]
+    async fn test_max_rows_guards_uncapped_select() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let seed: Vec<_> = (0..20)
+            .map(|i| user::Create {
+                email: format!("bulk{}@example.com", i),
+                name: if i < 10 { "Few".to_string() } else { "Many".to_string() },
+                created_at: created,
+                updated_at: created,
+                _params: vec![],
+            })
+            .collect();
+        client.user().create_many(seed).exec().await.unwrap();
+
+        let guarded = client.max_rows(Some(10));
+        let err = guarded
             .user()
-            .find_many(vec![user::deleted_at::is_not_null()])
+            .find_many(vec![])
+            .select(user::select!(id, name))
+            .exec::<user::Selected>()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("CausticsError::RowLimitExceeded"), "{}", err);
+        assert!(err.to_string().contains("limit=10"), "{}", err);
+
+        // Exactly at the cap is fine, as is an explicit take above it
+        let few: Vec<user::Selected> = guarded
+            .user()
+            .find_many(vec![user::name::equals("Few")])
+            .select(user::select!(id, name))
             .exec()
             .await
             .unwrap();
-        assert_eq!(deleted_users.len(), 1);
-        assert_eq!(deleted_users[0].id, user_deleted.id);
-        assert!(deleted_users[0].deleted_at.is_some());
-
-        // Test is_null operator for post content field
-        let posts_without_content = client
-            .post()
-            .find_many(vec![post::content::is_null()])
+        assert_eq!(few.len(), 10);
+        let taken: Vec<user::Selected> = guarded
+            .user()
+            .find_many(vec![])
+            .take(15)
+            .select(user::select!(id))
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_without_content.len(), 1);
-        assert_eq!(posts_without_content[0].id, post_without_content.id);
-        assert_eq!(posts_without_content[0].content, None);
+        assert_eq!(taken.len(), 15);
+    }
 
-        // Test is_not_null operator for post content field
-        let posts_with_content = client
-            .post()
-            .find_many(vec![post::content::is_not_null()])
+    #[tokio::test]
+    async fn test_case_insensitive_unique_lookup() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let john = client
+            .user()
+            .create(
+                "john@example.com".to_string(),
+                "John".to_string(),
+                created,
+                created,
+                vec![],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_with_content.len(), 1);
-        assert_eq!(posts_with_content[0].id, post_with_content.id);
-        assert!(posts_with_content[0].content.is_some());
 
-        // Test is_null operator for reviewer_user_id field
-        let posts_without_reviewer = client
-            .post()
-            .find_many(vec![post::reviewer_user_id::is_null()])
+        let found = client
+            .user()
+            .find_unique(user::email::equals_insensitive("JOHN@Example.COM"))
             .exec()
             .await
-            .unwrap();
-        assert_eq!(posts_without_reviewer.len(), 1);
-        assert_eq!(posts_without_reviewer[0].id, post_with_content.id);
-        assert_eq!(posts_without_reviewer[0].reviewer_user_id, None);
+            .unwrap()
+            .expect("user found by differently-cased email");
+        assert_eq!(found.id, john.id);
 
-        // Test is_not_null operator for reviewer_user_id field
-        let posts_with_reviewer = client
-            .post()
-            .find_many(vec![post::reviewer_user_id::is_not_null()])
+        // The exact-match lookup stays case-sensitive
+        let exact = client
+            .user()
+            .find_unique(user::email::equals("JOHN@EXAMPLE.COM"))
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_with_reviewer.len(), 1);
-        assert_eq!(posts_with_reviewer[0].id, post_without_content.id);
-        assert!(posts_with_reviewer[0].reviewer_user_id.is_some());
+        assert!(exact.is_none());
 
-        // Test combining null operators with logical operators
-        let users_with_age_not_deleted = client
+        // upsert matches the existing row instead of inserting a second one
+        let upserted = client
             .user()
-            .find_many(vec![operator::and(vec![
-                user::age::is_not_null(),
-                user::deleted_at::is_null(),
-            ])])
+            .upsert(
+                user::email::equals_insensitive("John@Example.com"),
+                user::Create {
+                    email: "John@Example.com".to_string(),
+                    name: "Duplicate".to_string(),
+                    created_at: created,
+                    updated_at: created,
+                    _params: vec![],
+                },
+                vec![user::age::set(Some(33))],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(users_with_age_not_deleted.len(), 2);
-        let filtered_ids: Vec<Uuid> = users_with_age_not_deleted.iter().map(|u| u.id).collect();
-        assert!(filtered_ids.contains(&user_with_age.id));
-        assert!(filtered_ids.contains(&user_no_deletions.id));
+        assert_eq!(upserted.id, john.id);
+        assert_eq!(upserted.age, Some(33));
+        assert_eq!(upserted.email, "john@example.com");
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 1);
+    }
 
-        // Test combining null operators with OR
-        let users_missing_data = client
+    #[tokio::test]
+    async fn test_transaction_commit() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let email = format!("john_{}@example.com", chrono::Utc::now().timestamp());
+        let email_for_check = email.clone();
+        let result = client
+            ._transaction()
+            .run(|tx| {
+                Box::pin(async move {
+                    // Create user
+                    let user = tx
+                        .user()
+                        .create(
+                            email.clone(),
+                            "John".to_string(),
+                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                            vec![],
+                        )
+                        .exec()
+                        .await?;
+
+                    // Create post
+                    let post = tx
+                        .post()
+                        .create(
+                            "Hello World".to_string(),
+                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                            user::id::equals(user.id),
+                            vec![post::content::set("This is my first post".to_string())],
+                        )
+                        .exec()
+                        .await?;
+
+                    Ok((user, post))
+                })
+            })
+            .await
+            .expect("Transaction failed");
+
+        assert_eq!(result.0.name, "John");
+        assert_eq!(result.1.title, "Hello World");
+
+        // Verify data is persisted
+        let found_user = client
             .user()
-            .find_many(vec![operator::or(vec![
-                user::age::is_null(),
-                user::deleted_at::is_not_null(),
-            ])])
+            .find_first(vec![user::email::equals(&email_for_check)])
             .exec()
             .await
-            .unwrap();
-        assert_eq!(users_missing_data.len(), 2);
-        let missing_data_ids: Vec<Uuid> = users_missing_data.iter().map(|u| u.id).collect();
-        assert!(missing_data_ids.contains(&user_without_age.id));
-        assert!(missing_data_ids.contains(&user_deleted.id));
+            .expect("Failed to query user")
+            .expect("User not found");
+        assert_eq!(found_user.name, "John");
     }
 
     #[tokio::test]
-    async fn test_json_field_operations() {
+    async fn test_transaction_rollback() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        // Create a user for the posts
-        let user = client
+        let email = format!("rollback_{}@example.com", chrono::Utc::now().timestamp());
+        let email_for_check = email.clone();
+        let result: Result<(), QueryError> = client
+            ._transaction()
+            .run(|tx| {
+                Box::pin(async move {
+                    // Create user
+                    let _user = tx
+                        .user()
+                        .create(
+                            email.clone(),
+                            "Rollback".to_string(),
+                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                            DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                            vec![],
+                        )
+                        .exec()
+                        .await?;
+
+                    // Intentionally return an error to trigger rollback
+                    Err(QueryError::Custom("Intentional rollback".into()))
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+
+        // Verify data is NOT persisted
+        let found_user = client
+            .user()
+            .find_first(vec![user::email::equals(&email_for_check)])
+            .exec()
+            .await
+            .expect("Failed to query user");
+        assert!(found_user.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_relations() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Create users
+        let author = client
             .user()
             .create(
-                format!("jsonuser_{}@example.com", chrono::Utc::now().timestamp()),
-                "JSON User".to_string(),
+                "john@example.com".to_string(),
+                "John".to_string(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
                 vec![],
@@ -2415,1012 +3201,4998 @@ mod query_builder_tests {
             .exec()
             .await
             .unwrap();
+        assert!(author.posts.is_none());
 
-        // Create posts with various JSON data for testing
-        let post_with_simple_json = client
-            .post()
+        let reviewer = client
+            .user()
             .create(
-                "Post with Simple JSON".to_string(),
+                "jane@example.com".to_string(),
+                "Jane".to_string(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                user::id::equals(user.id),
-                vec![
-                    post::content::set(Some("A post with simple JSON".to_string())),
-                    post::custom_data::set(Some(serde_json::json!({
-                        "category": "technology",
-                        "tags": ["rust", "json", "database"],
-                        "metadata": {
-                            "author_notes": "This is a test post",
-                            "priority": "high"
-                        },
-                        "view_count": 42,
-                        "published": true
-                    }))),
-                ],
+                vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        let post_with_array_json = client
+        // Create posts - one with reviewer, one without
+        let post_with_reviewer = client
             .post()
             .create(
-                "Post with Array JSON".to_string(),
+                "Reviewed Post".to_string(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                user::id::equals(user.id),
+                user::id::equals(author.id),
                 vec![
-                    post::content::set(Some("A post with array JSON".to_string())),
-                    post::custom_data::set(Some(serde_json::json!({
-                        "categories": ["programming", "tutorial", "beginner"],
-                        "scores": [85, 90, 78],
-                        "settings": {
-                            "notifications": true,
-                            "public": false
-                        }
-                    }))),
+                    post::content::set("This post has been reviewed".to_string()),
+                    post::reviewer::connect(user::id::equals(reviewer.id)),
                 ],
             )
             .exec()
             .await
             .unwrap();
 
-        let post_with_string_json = client
+        let post_without_reviewer = client
             .post()
             .create(
-                "Post with String JSON".to_string(),
+                "Unreviewed Post".to_string(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
                 DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                user::id::equals(user.id),
-                vec![
-                    post::content::set(Some("A post with string JSON".to_string())),
-                    post::custom_data::set(Some(serde_json::json!({
-                        "description": "This is a comprehensive guide to JSON operations in databases",
-                        "author": "John Developer",
-                        "status": "published"
-                    }))),
-                ],
+                user::id::equals(author.id),
+                vec![post::content::set(
+                    "This post hasn't been reviewed yet".to_string(),
+                )],
             )
             .exec()
             .await
             .unwrap();
 
-        let post_without_json = client
-            .post()
-            .create(
-                "Post without JSON".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                user::id::equals(user.id),
-                vec![
-                    post::content::set(Some("A post without JSON data".to_string())),
-                    post::custom_data::set(None),
-                ],
-            )
+        // Test fetching user with posts
+        let user_with_posts = client
+            .user()
+            .find_unique(user::id::equals(author.id))
+            .with(user::posts::fetch(vec![]))
             .exec()
             .await
+            .unwrap()
             .unwrap();
+        let posts = user_with_posts.posts.unwrap();
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].title, "Reviewed Post");
+        assert_eq!(posts[1].title, "Unreviewed Post");
 
-        // Create a post with explicit JSON null value (JSON = null)
-        let post_with_json_null = client
+        // Test fetching post with reviewer
+        let post_with_reviewer = client
             .post()
-            .create(
-                "Post with JSON null".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                user::id::equals(user.id),
-                vec![
-                    post::content::set(Some("A post with JSON null".to_string())),
-                    post::custom_data::set(Some(serde_json::Value::Null)),
-                ],
-            )
+            .find_unique(post::id::equals(post_with_reviewer.id))
+            .with(post::reviewer::fetch())
             .exec()
             .await
+            .unwrap()
             .unwrap();
+        let reviewer = post_with_reviewer.reviewer.unwrap().unwrap();
+        assert_eq!(reviewer.name, "Jane");
+        assert_eq!(reviewer.email, "jane@example.com");
 
-        // Test basic JSON equals operation
-        let posts_with_specific_category = client
+        // Test fetching post without reviewer
+        let post_without_reviewer = client
             .post()
-            .find_many(vec![post::custom_data::equals(Some(serde_json::json!({
-                "category": "technology",
-                "tags": ["rust", "json", "database"],
-                "metadata": {
-                    "author_notes": "This is a test post",
-                    "priority": "high"
-                },
-                "view_count": 42,
-                "published": true
-            })))])
+            .find_unique(post::id::equals(post_without_reviewer.id))
+            .with(post::reviewer::fetch())
             .exec()
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(posts_with_specific_category.len(), 1);
-        assert_eq!(posts_with_specific_category[0].id, post_with_simple_json.id);
+        assert!(
+            post_without_reviewer.reviewer.is_none()
+                || post_without_reviewer.reviewer.as_ref().unwrap().is_none()
+        );
+    }
 
-        // Test JSON null operations
-        let posts_without_custom_data = client
+    #[tokio::test]
+    async fn test_with_all_relations() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let author = client
+            .user()
+            .create(
+                "author@example.com".to_string(),
+                "Author".to_string(),
+                created,
+                created,
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+        let reviewer = client
+            .user()
+            .create(
+                "reviewer@example.com".to_string(),
+                "Reviewer".to_string(),
+                created,
+                created,
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+        let post = client
             .post()
-            .find_many(vec![post::custom_data::is_null()])
+            .create(
+                "Everything".to_string(),
+                created,
+                created,
+                user::id::equals(author.id),
+                vec![post::reviewer::connect(user::id::equals(reviewer.id))],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_without_custom_data.len(), 1);
-        assert_eq!(posts_without_custom_data[0].id, post_without_json.id);
 
-        // JSON null parity helpers
-        // DB NULL (column is NULL)
-        let posts_db_null = client
+        // has_many relations are populated
+        let author_with_all = client
+            .user()
+            .find_unique(user::id::equals(author.id))
+            .with_all()
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        let posts = author_with_all.posts.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, post.id);
+        // Only one level deep: the fetched posts carry no relations of their own
+        assert!(posts[0].user.is_none());
+
+        // Every belongs_to relation is populated
+        let post_with_all = client
             .post()
-            .find_many(vec![post::custom_data::db_null()])
+            .find_unique(post::id::equals(post.id))
+            .with_all()
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(post_with_all.user.unwrap().name, "Author");
+        assert_eq!(post_with_all.reviewer.unwrap().unwrap().name, "Reviewer");
+
+        // Explicit includes are kept alongside with_all
+        let posts = client
+            .post()
+            .find_many(vec![])
+            .with(post::reviewer::fetch())
+            .with_all()
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+        assert!(posts[0].user.is_some());
+        assert!(posts[0].reviewer.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_batch_insert_operations() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Create multiple users in a batch
+        let timestamp = chrono::Utc::now().timestamp();
+        let (user1, user2) = client
+            ._batch((
+                client.user().create(
+                    format!("john_{}@example.com", timestamp),
+                    "John".to_string(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+                ),
+                client.user().create(
+                    format!("jane_{}@example.com", timestamp),
+                    "Jane".to_string(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    vec![user::age::set(Some(30))],
+                ),
+            ))
+            .await
+            .expect("Batch operation failed");
+
+        assert_eq!(user1.name, "John");
+        assert_eq!(user2.name, "Jane");
+
+        let found_users = client.user().find_many(vec![]).exec().await.unwrap();
+        assert_eq!(found_users.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_filter_and_order_by_parent_fields() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        let mut users = Vec::new();
+        for (name, age) in [("Alice", 25), ("Bob", 35), ("Carol", 40)] {
+            let user = client
+                .user()
+                .create(
+                    format!("{}@example.com", name.to_lowercase()),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
+            users.push(user);
+        }
+        let (alice, bob) = (&users[0], &users[1]);
+        for (title, author, reviewer) in [
+            ("Alice A", alice.id, None),
+            ("Bob A", bob.id, Some(alice.id)),
+            ("Bob B", bob.id, None),
+        ] {
+            let mut params = vec![];
+            if let Some(reviewer) = reviewer {
+                params.push(post::reviewer::connect(user::id::equals(reviewer)));
+            }
+            client
+                .post()
+                .create(title.to_string(), created, created, user::id::equals(author), params)
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let titles = |posts: Vec<post::ModelWithRelations>| {
+            let mut titles: Vec<String> = posts.into_iter().map(|p| p.title).collect();
+            titles.sort();
+            titles
+        };
+
+        let older = client
+            .post()
+            .find_many(vec![post::user::is(vec![user::age::gt(Some(30))])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(titles(older), vec!["Bob A", "Bob B"]);
+
+        let not_older = client
+            .post()
+            .find_many(vec![post::user::is_not(vec![user::age::gt(Some(30))])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(titles(not_older), vec!["Alice A"]);
+
+        // Optional parent: posts without a reviewer never match `is` but do match `is_not`
+        let reviewed_by_alice = client
+            .post()
+            .find_many(vec![post::reviewer::is(vec![user::name::equals("Alice")])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(titles(reviewed_by_alice), vec!["Bob A"]);
+        let not_reviewed_by_alice = client
+            .post()
+            .find_many(vec![post::reviewer::is_not(vec![user::name::equals("Alice")])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(titles(not_reviewed_by_alice), vec!["Alice A", "Bob B"]);
+
+        // Parent filters combine with the post's own filters
+        let combined = client
+            .post()
+            .find_many(vec![
+                post::user::is(vec![user::name::equals("Bob")]),
+                post::title::contains("B"),
+            ])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(titles(combined), vec!["Bob A", "Bob B"]);
+
+        let by_author_name = client
+            .post()
+            .find_many(vec![])
+            .order_by(post::user::order_by(user::name::order(SortOrder::Desc)))
+            .order_by(post::title::order(SortOrder::Asc))
+            .exec()
+            .await
+            .unwrap();
+        let ordered: Vec<_> = by_author_name.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(ordered, vec!["Bob A", "Bob B", "Alice A"]);
+
+        let by_author_age = client
+            .post()
+            .find_many(vec![post::user::is(vec![user::age::lt(Some(40))])])
+            .order_by(post::user::order_by(user::age::order(SortOrder::Asc)))
+            .order_by(post::title::order(SortOrder::Desc))
+            .exec()
+            .await
+            .unwrap();
+        let ordered: Vec<_> = by_author_age.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(ordered, vec!["Alice A", "Bob B", "Bob A"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_many_index_by() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        let mut ids = Vec::new();
+        for (name, age) in [("Ann", 30), ("Ben", 30), ("Cid", 41)] {
+            let user = client
+                .user()
+                .create(
+                    format!("{}@example.com", name.to_lowercase()),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
+            ids.push(user.id);
+        }
+
+        let by_id: std::collections::HashMap<Uuid, user::ModelWithRelations> = client
+            .user()
+            .find_many(vec![])
+            .index_by(user::id::key)
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(by_id.len(), 3);
+        assert_eq!(by_id[&ids[1]].name, "Ben");
+
+        // Duplicate keys keep the last row returned
+        let by_age = client
+            .user()
+            .find_many(vec![user::age::lt(Some(40))])
+            .order_by(user::name::order(SortOrder::Asc))
+            .index_by(user::age::key)
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(by_age.len(), 1);
+        assert_eq!(by_age[&Some(30)].name, "Ben");
+
+        // Any closure over the row works as a key
+        let by_email = client
+            .user()
+            .find_many(vec![])
+            .index_by(|u| u.email.clone())
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(by_email["cid@example.com"].id, ids[2]);
+    }
+
+    #[tokio::test]
+    async fn test_utc_fields_read_back_normalized() {
+        use sea_orm::{ConnectionTrait, Statement};
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let at = DateTime::<FixedOffset>::from_str("2023-03-01T12:00:00+05:30").unwrap();
+        let author = client
+            .user()
+            .create("utc@example.com".to_string(), "Utc".to_string(), at, at, vec![])
+            .exec()
+            .await
+            .unwrap();
+
+        // A row written outside Caustics keeps its local offset in the column
+        let post_id = Uuid::new_v4();
+        db.execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "INSERT INTO posts (id, title, created_at, updated_at, user_id) VALUES (?, ?, ?, ?, ?)",
+            [
+                post_id.into(),
+                "Offset".into(),
+                "2023-03-01T12:00:00+05:30".into(),
+                "2023-03-01T12:00:00+05:30".into(),
+                author.id.into(),
+            ],
+        ))
+        .await
+        .unwrap();
+
+        // post timestamps are marked #[caustics(utc)]
+        let post = client
+            .post()
+            .find_unique(post::id::equals(post_id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(post.created_at, at);
+        assert_eq!(post.created_at.offset().local_minus_utc(), 0);
+        assert_eq!(post.created_at.to_rfc3339(), "2023-03-01T06:30:00+00:00");
+
+        let with_posts = client
+            .user()
+            .find_unique(user::id::equals(author.id))
+            .with(user::posts::fetch(vec![]))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        let post = &with_posts.posts.unwrap()[0];
+        assert_eq!(post.updated_at.offset().local_minus_utc(), 0);
+        assert_eq!(post.updated_at, at);
+
+        // Materialization normalizes whatever offset the driver hands back
+        let materialized = post::ModelWithRelations::from_model(post::Model {
+            id: post_id,
+            title: "Offset".to_string(),
+            content: None,
+            created_at: at,
+            updated_at: at,
+            user_id: author.id,
+            reviewer_user_id: None,
+            custom_data: None,
+        });
+        assert_eq!(materialized.created_at.to_rfc3339(), "2023-03-01T06:30:00+00:00");
+        assert_eq!(materialized.updated_at.offset().local_minus_utc(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_find_latest_and_earliest() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+
+        for (email, at) in [
+            ("middle@example.com", "2022-01-01T00:00:00Z"),
+            ("newest@example.com", "2023-01-01T00:00:00Z"),
+            ("oldest@example.com", "2021-01-01T00:00:00Z"),
+        ] {
+            let at = DateTime::<FixedOffset>::from_str(at).unwrap();
+            client
+                .user()
+                .create(email.to_string(), "Timed".to_string(), at, at, vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let latest = client
+            .user()
+            .find_latest(vec![], user::created_at::column())
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.email, "newest@example.com");
+
+        let earliest = client
+            .user()
+            .find_earliest(vec![], user::created_at::column())
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(earliest.email, "oldest@example.com");
+
+        // Filters still apply, and NULLs never win
+        let latest_filtered = client
+            .user()
+            .find_latest(vec![user::email::not_equals("newest@example.com")], user::created_at::column())
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest_filtered.email, "middle@example.com");
+        let latest_deleted = client
+            .user()
+            .find_latest(vec![], user::deleted_at::column())
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(latest_deleted.deleted_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_mixed_entities() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let author_id = uuid::Uuid::new_v4();
+        let (author, post) = client
+            ._batch_mixed((
+                client.user().create(
+                    "mixed@example.com".to_string(),
+                    "Mixed".to_string(),
+                    created,
+                    created,
+                    vec![user::id::set(author_id)],
+                ),
+                client.post().create(
+                    "Mixed post".to_string(),
+                    created,
+                    created,
+                    user::id::equals(author_id),
+                    vec![],
+                ),
+            ))
+            .await
+            .expect("Mixed batch failed");
+        assert_eq!(author.id, author_id);
+        assert_eq!(post.user_id, author_id);
+
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 1);
+        let posts = client.post().find_many(vec![post::user_id::equals(author_id)]).exec().await.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].title, "Mixed post");
+
+        // A failing element rolls back the earlier ones
+        let result = client
+            ._batch_mixed((
+                client.user().create(
+                    "rollback@example.com".to_string(),
+                    "Rollback".to_string(),
+                    created,
+                    created,
+                    vec![],
+                ),
+                client.post().delete(post::id::equals(uuid::Uuid::new_v4())),
+            ))
+            .await;
+        assert!(result.is_err());
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_update_operations() {
+        // no explicit caustics types here
+        use chrono::DateTime;
+        use std::str::FromStr;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Seed users
+        let u1 = client
+            .user()
+            .create(
+                "u1@example.com".to_string(),
+                "U1".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(20))],
+            )
+            .exec()
+            .await
+            .unwrap();
+        let u2 = client
+            .user()
+            .create(
+                "u2@example.com".to_string(),
+                "U2".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(30))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Batch update both (tuple style, fluent)
+        // Execute sequential updates to match the new API shape
+        let _u1_after = client
+            .user()
+            .update(user::id::equals(u1.id), vec![user::age::increment(5)])
+            .exec()
+            .await
+            .unwrap();
+        let _u2_after = client
+            .user()
+            .update(
+                user::id::equals(u2.id),
+                vec![user::name::set("U2-upd"), user::age::decrement(10)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Verify
+        let u1_after = client
+            .user()
+            .find_unique(user::id::equals(u1.id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(u1_after.age, Some(25));
+
+        let u2_after = client
+            .user()
+            .find_unique(user::id::equals(u2.id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(u2_after.name, "U2-upd");
+        assert_eq!(u2_after.age, Some(20));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::vec_init_then_push)]
+    async fn test_batch_update_with_vec() {
+        use chrono::DateTime;
+        use std::str::FromStr;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Create test users
+        let u1 = client
+            .user()
+            .create(
+                "batch_u1@example.com".to_string(),
+                "BatchU1".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(25))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let u2 = client
+            .user()
+            .create(
+                "batch_u2@example.com".to_string(),
+                "BatchU2".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(35))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let u3 = client
+            .user()
+            .create(
+                "batch_u3@example.com".to_string(),
+                "BatchU3".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(45))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create a vector of update operations (similar to the user's use case)
+        let mut update_operations = Vec::new();
+        
+        update_operations.push(
+            client
+                .user()
+                .update(
+                    user::id::equals(u1.id),
+                    vec![user::name::set("UpdatedU1"), user::age::increment(5)],
+                )
+        );
+        
+        update_operations.push(
+            client
+                .user()
+                .update(
+                    user::id::equals(u2.id),
+                    vec![user::name::set("UpdatedU2"), user::age::decrement(10)],
+                )
+        );
+        
+        update_operations.push(
+            client
+                .user()
+                .update(
+                    user::id::equals(u3.id),
+                    vec![user::name::set("UpdatedU3"), user::age::set(50)],
+                )
+        );
+
+        // Execute batch update using Vec<UpdateQueryBuilder>
+        let results = client
+            ._batch(update_operations)
+            .await
+            .expect("Batch update operation failed");
+
+        // Verify we got the expected number of results
+        assert_eq!(results.len(), 3);
+
+        // Verify the updates were applied correctly
+        let updated_u1 = client
+            .user()
+            .find_unique(user::id::equals(u1.id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated_u1.name, "UpdatedU1");
+        assert_eq!(updated_u1.age, Some(30)); // 25 + 5
+
+        let updated_u2 = client
+            .user()
+            .find_unique(user::id::equals(u2.id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated_u2.name, "UpdatedU2");
+        assert_eq!(updated_u2.age, Some(25)); // 35 - 10
+
+        let updated_u3 = client
+            .user()
+            .find_unique(user::id::equals(u3.id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated_u3.name, "UpdatedU3");
+        assert_eq!(updated_u3.age, Some(50)); // Set to 50
+    }
+
+    #[tokio::test]
+    #[allow(clippy::vec_init_then_push)]
+    async fn test_batch_create_with_vec() {
+        use chrono::DateTime;
+        use std::str::FromStr;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Create a vector of create operations
+        let mut create_operations = Vec::new();
+        
+        create_operations.push(
+            client
+                .user()
+                .create(
+                    "batch_create_1@example.com".to_string(),
+                    "BatchCreate1".to_string(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    vec![user::age::set(Some(25))],
+                )
+        );
+        
+        create_operations.push(
+            client
+                .user()
+                .create(
+                    "batch_create_2@example.com".to_string(),
+                    "BatchCreate2".to_string(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    vec![user::age::set(Some(35))],
+                )
+        );
+        
+        create_operations.push(
+            client
+                .user()
+                .create(
+                    "batch_create_3@example.com".to_string(),
+                    "BatchCreate3".to_string(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    vec![user::age::set(Some(45))],
+                )
+        );
+
+        // Execute batch create using Vec<CreateQueryBuilder>
+        let results = client
+            ._batch(create_operations)
+            .await
+            .expect("Batch create operation failed");
+
+        // Verify we got the expected number of results
+        assert_eq!(results.len(), 3);
+
+        // Verify the creates were applied correctly
+        let created_user1 = client
+            .user()
+            .find_unique(user::email::equals("batch_create_1@example.com"))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(created_user1.name, "BatchCreate1");
+        assert_eq!(created_user1.age, Some(25));
+
+        let created_user2 = client
+            .user()
+            .find_unique(user::email::equals("batch_create_2@example.com"))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(created_user2.name, "BatchCreate2");
+        assert_eq!(created_user2.age, Some(35));
+
+        let created_user3 = client
+            .user()
+            .find_unique(user::email::equals("batch_create_3@example.com"))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(created_user3.name, "BatchCreate3");
+        assert_eq!(created_user3.age, Some(45));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::vec_init_then_push)]
+    async fn test_batch_delete_with_vec() {
+        use chrono::DateTime;
+        use std::str::FromStr;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Create test users first
+        let u1 = client
+            .user()
+            .create(
+                "batch_delete_1@example.com".to_string(),
+                "BatchDelete1".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(25))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let u2 = client
+            .user()
+            .create(
+                "batch_delete_2@example.com".to_string(),
+                "BatchDelete2".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(35))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let u3 = client
+            .user()
+            .create(
+                "batch_delete_3@example.com".to_string(),
+                "BatchDelete3".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(45))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create a vector of delete operations
+        let mut delete_operations = Vec::new();
+        
+        delete_operations.push(
+            client
+                .user()
+                .delete(user::id::equals(u1.id))
+        );
+        
+        delete_operations.push(
+            client
+                .user()
+                .delete(user::id::equals(u2.id))
+        );
+        
+        delete_operations.push(
+            client
+                .user()
+                .delete(user::id::equals(u3.id))
+        );
+
+        // Execute batch delete using Vec<DeleteQueryBuilder>
+        let results = client
+            ._batch(delete_operations)
+            .await
+            .expect("Batch delete operation failed");
+
+        // Verify we got the expected number of results
+        assert_eq!(results.len(), 3);
+
+        // Verify the deletes were applied correctly
+        let deleted_user1 = client
+            .user()
+            .find_unique(user::id::equals(u1.id))
+            .exec()
+            .await
+            .unwrap();
+        assert!(deleted_user1.is_none());
+
+        let deleted_user2 = client
+            .user()
+            .find_unique(user::id::equals(u2.id))
+            .exec()
+            .await
+            .unwrap();
+        assert!(deleted_user2.is_none());
+
+        let deleted_user3 = client
+            .user()
+            .find_unique(user::id::equals(u3.id))
+            .exec()
+            .await
+            .unwrap();
+        assert!(deleted_user3.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_delete_operations() {
+        use chrono::DateTime;
+        use std::str::FromStr;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Seed users
+        let u1 = client
+            .user()
+            .create(
+                "del1@example.com".to_string(),
+                "Del1".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let u2 = client
+            .user()
+            .create(
+                "del2@example.com".to_string(),
+                "Del2".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Batch delete both (tuple style, fluent; no local query var)
+        let (_d1, _d2) = client
+            ._batch::<user::Entity, user::ActiveModel, user::ModelWithRelations, (), (
+                caustics::query_builders::DeleteQueryBuilder<
+                    '_,
+                    sea_orm::DatabaseConnection,
+                    user::Entity,
+                    user::ModelWithRelations,
+                >,
+                caustics::query_builders::DeleteQueryBuilder<
+                    '_,
+                    sea_orm::DatabaseConnection,
+                    user::Entity,
+                    user::ModelWithRelations,
+                >,
+            )>((
+                client.user().delete(user::id::equals(u1.id)),
+                client.user().delete(user::id::equals(u2.id)),
+            ))
+            .await
+            .unwrap();
+
+        // Verify deletion
+        let left = client.user().find_many(vec![]).exec().await.unwrap();
+        assert!(left.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_upsert_operations() {
+        // no explicit caustics types here
+        use chrono::DateTime;
+        use std::str::FromStr;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // First upserts will insert (tuple style, fluent)
+        let (_ins1, _ins2) = client
+            ._batch((
+                client.user().upsert(
+                    user::email::equals("bus1@example.com"),
+                    user::Create {
+                        name: "Bus1".to_string(),
+                        email: "bus1@example.com".to_string(),
+                        created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z")
+                            .unwrap(),
+                        updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z")
+                            .unwrap(),
+                        _params: vec![],
+                    },
+                    vec![user::age::set(10)],
+                ),
+                client.user().upsert(
+                    user::email::equals("bus2@example.com"),
+                    user::Create {
+                        name: "Bus2".to_string(),
+                        email: "bus2@example.com".to_string(),
+                        created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z")
+                            .unwrap(),
+                        updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z")
+                            .unwrap(),
+                        _params: vec![],
+                    },
+                    vec![user::age::set(20)],
+                ),
+            ))
+            .await
+            .unwrap();
+
+        // Second upserts will update (tuple arity 1 supported)
+        let (_upd1,) = client
+            ._batch((client.user().upsert(
+                user::email::equals("bus1@example.com"),
+                user::Create {
+                    name: "Bus1".to_string(),
+                    email: "bus1@example.com".to_string(),
+                    created_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    updated_at: DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                    _params: vec![],
+                },
+                vec![user::name::set("Bus1-upd"), user::age::increment(5)],
+            ),))
+            .await
+            .unwrap();
+
+        // Verify
+        let u1 = client
+            .user()
+            .find_unique(user::email::equals("bus1@example.com"))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(u1.name, "Bus1-upd");
+        assert_eq!(u1.age, Some(15));
+    }
+
+    #[tokio::test]
+    async fn test_string_operators() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Create test users
+        let _user1 = client
+            .user()
+            .create(
+                "john.doe@example.com".to_string(),
+                "John Doe".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _user2 = client
+            .user()
+            .create(
+                "jane.smith@example.com".to_string(),
+                "Jane Smith".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(28)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _user3 = client
+            .user()
+            .create(
+                "bob.johnson@test.org".to_string(),
+                "Bob Johnson".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(40)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Test contains operator
+        let users_with_doe = client
+            .user()
+            .find_many(vec![user::name::contains("Doe")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_with_doe.len(), 1);
+        assert_eq!(users_with_doe[0].name, "John Doe");
+
+        // Test starts_with operator
+        let users_starting_with_j = client
+            .user()
+            .find_many(vec![user::name::starts_with("J")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_starting_with_j.len(), 2);
+        assert!(users_starting_with_j
+            .iter()
+            .all(|u| u.name.starts_with("J")));
+
+        // Test ends_with operator
+        let users_ending_with_son = client
+            .user()
+            .find_many(vec![user::name::ends_with("son")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_ending_with_son.len(), 1);
+        assert_eq!(users_ending_with_son[0].name, "Bob Johnson");
+
+        // Test email contains
+        let users_with_example_email = client
+            .user()
+            .find_many(vec![user::email::contains("example.com")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_with_example_email.len(), 2);
+
+        let users_with_test_email = client
+            .user()
+            .find_many(vec![user::email::ends_with("test.org")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_with_test_email.len(), 1);
+        assert_eq!(users_with_test_email[0].email, "bob.johnson@test.org");
+    }
+
+    #[tokio::test]
+    async fn test_string_operators_match_wildcards_literally() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        for (email, name) in [
+            ("percent@example.com", "Scored 100% today"),
+            ("thousand@example.com", "Scored 1000 today"),
+            ("under@example.com", "snake_case fan"),
+            ("plain@example.com", "snakeXcase fan"),
+        ] {
+            client
+                .user()
+                .create(email.to_string(), name.to_string(), now, now, vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
+        let names = |users: Vec<user::ModelWithRelations>| {
+            let mut names: Vec<String> = users.into_iter().map(|u| u.name).collect();
+            names.sort();
+            names
+        };
+
+        // `%` and `_` in the value are matched as themselves
+        let percent = client
+            .user()
+            .find_many(vec![user::name::contains("100%")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(percent), vec!["Scored 100% today"]);
+        let underscore = client
+            .user()
+            .find_many(vec![user::name::starts_with("snake_")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(underscore), vec!["snake_case fan"]);
+        let insensitive = client
+            .user()
+            .find_many(vec![
+                user::name::mode(caustics::QueryMode::Insensitive),
+                user::name::ends_with("100% TODAY"),
+            ])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(insensitive), vec!["Scored 100% today"]);
+
+        // `like` keeps the wildcards, with `\` to escape one
+        let wildcard = client
+            .user()
+            .find_many(vec![user::name::like("Scored 100%")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(wildcard), vec!["Scored 100% today", "Scored 1000 today"]);
+        let escaped = client
+            .user()
+            .find_many(vec![user::name::like("snake\\_%")])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(escaped), vec!["snake_case fan"]);
+    }
+
+    #[tokio::test]
+    async fn test_contains_any_matches_either_substring() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        for (email, name) in [
+            ("doe@example.com", "John Doe"),
+            ("smith@example.com", "Jane Smith"),
+            ("johnson@example.com", "Bob Johnson"),
+            ("percent@example.com", "100% Smithy"),
+        ] {
+            client
+                .user()
+                .create(email.to_string(), name.to_string(), now, now, vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
+        let names = |users: Vec<user::ModelWithRelations>| {
+            let mut names: Vec<String> = users.into_iter().map(|u| u.name).collect();
+            names.sort();
+            names
+        };
+
+        let matched = client
+            .user()
+            .find_many(vec![user::name::contains_any(vec!["Doe", "Smith"])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(matched), vec!["100% Smithy", "Jane Smith", "John Doe"]);
+
+        // Each value matches literally
+        let literal = client
+            .user()
+            .find_many(vec![user::name::contains_any(vec!["0%", "nobody"])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(literal), vec!["100% Smithy"]);
+
+        let insensitive = client
+            .user()
+            .find_many(vec![
+                user::name::mode(caustics::QueryMode::Insensitive),
+                user::name::contains_any(vec!["DOE", "johnSON"]),
+            ])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(insensitive), vec!["Bob Johnson", "John Doe"]);
+
+        let none = client
+            .user()
+            .find_many(vec![user::name::contains_any(Vec::<String>::new())])
+            .exec()
+            .await
+            .unwrap();
+        assert!(none.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_comparison_operators() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Create test users with different ages for comparison
+        let _user1 = client
+            .user()
+            .create(
+                "young@example.com".to_string(),
+                "Young User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(18)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _user2 = client
+            .user()
+            .create(
+                "middle@example.com".to_string(),
+                "Middle User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _user3 = client
+            .user()
+            .create(
+                "old@example.com".to_string(),
+                "Old User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(45)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Test greater than operator
+        let older_users = client
+            .user()
+            .find_many(vec![user::age::gt(Some(25))])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(older_users.len(), 2);
+        assert!(older_users.iter().all(|u| u.age.unwrap_or(0) > 25));
+
+        // Test less than operator
+        let younger_users = client
+            .user()
+            .find_many(vec![user::age::lt(Some(25))])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(younger_users.len(), 1);
+        assert_eq!(younger_users[0].age, Some(18));
+
+        // Test greater than or equal operator
+        let adult_users = client
+            .user()
+            .find_many(vec![user::age::gte(Some(18))])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(adult_users.len(), 3);
+
+        // Test less than or equal operator
+        let max_30_users = client
+            .user()
+            .find_many(vec![user::age::lte(Some(30))])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(max_30_users.len(), 2);
+
+        // Test in_vec operator
+        let specific_ages = client
+            .user()
+            .find_many(vec![user::age::in_vec(vec![Some(18), Some(45)])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(specific_ages.len(), 2);
+
+        // Test not_in_vec operator
+        let not_specific_ages = client
+            .user()
+            .find_many(vec![user::age::not_in_vec(vec![Some(18), Some(45)])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(not_specific_ages.len(), 1);
+        assert_eq!(not_specific_ages[0].age, Some(30));
+    }
+
+    #[tokio::test]
+    async fn test_logical_operators() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Create test users with varied data for logical testing
+        let _user1 = client
+            .user()
+            .create(
+                "young.john@example.com".to_string(),
+                "John Young".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(16)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _user2 = client
+            .user()
+            .create(
+                "adult.jane@example.com".to_string(),
+                "Jane Adult".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _user3 = client
+            .user()
+            .create(
+                "senior.bob@test.org".to_string(),
+                "Bob Senior".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(70)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _user4 = client
+            .user()
+            .create(
+                "middle.alice@example.com".to_string(),
+                "Alice Middle".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(35)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Test AND operator - users who are adults AND have "example.com" email
+        let adult_example_users = client
+            .user()
+            .find_many(vec![operator::and(vec![
+                user::age::gte(Some(18)),
+                user::email::contains("example.com"),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(adult_example_users.len(), 2); // Jane and Alice
+        assert!(adult_example_users
+            .iter()
+            .all(|u| u.age.unwrap_or(0) >= 18 && u.email.contains("example.com")));
+
+        // Test OR operator - users who are either very young OR very old
+        let young_or_old_users = client
+            .user()
+            .find_many(vec![operator::or(vec![
+                user::age::lt(Some(18)),
+                user::age::gt(Some(65)),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(young_or_old_users.len(), 2); // John (16) and Bob (70)
+        assert!(young_or_old_users.iter().all(|u| {
+            let age = u.age.unwrap_or(0);
+            !(18..=65).contains(&age)
+        }));
+
+        // Test NOT operator - users who are NOT minors
+        let not_minors = client
+            .user()
+            .find_many(vec![operator::not(vec![user::age::lt(Some(18))])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(not_minors.len(), 3); // Jane, Bob, and Alice
+        assert!(not_minors.iter().all(|u| u.age.unwrap_or(0) >= 18));
+
+        // Test complex nested logical operations -
+        // (adults with example.com email) OR (seniors regardless of email)
+        let complex_query_users = client
+            .user()
+            .find_many(vec![operator::or(vec![
+                operator::and(vec![
+                    user::age::gte(Some(18)),
+                    user::age::lt(Some(65)),
+                    user::email::contains("example.com"),
+                ]),
+                user::age::gte(Some(65)),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(complex_query_users.len(), 3); // Jane, Alice (adults with example.com), and Bob (senior)
+
+        // Test NOT with AND - users who are NOT (young AND have example.com email)
+        let not_young_example = client
+            .user()
+            .find_many(vec![operator::not(vec![operator::and(vec![
+                user::age::lt(Some(25)),
+                user::email::contains("example.com"),
+            ])])])
+            .exec()
+            .await
+            .unwrap();
+        // Should exclude no one since John is young but has example.com, but John is <25 and doesn't have example.com
+        // Actually, John has example.com but is young, so NOT(young AND example.com) excludes John... wait let me think about this
+        // John: age=16, email="young.john@example.com" -> young=true, has_example=true -> AND=true -> NOT=false (excluded)
+        // Jane: age=25, email="adult.jane@example.com" -> young=false, has_example=true -> AND=false -> NOT=true (included)
+        // Bob: age=70, email="senior.bob@test.org" -> young=false, has_example=false -> AND=false -> NOT=true (included)
+        // Alice: age=35, email="middle.alice@example.com" -> young=false, has_example=true -> AND=false -> NOT=true (included)
+        assert_eq!(not_young_example.len(), 3); // Jane, Bob, Alice (John is excluded)
+    }
+
+    #[tokio::test]
+    async fn test_filter_builder_from_runtime_params() {
+        use caustics::operator::FilterBuilder;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+        let now = DateTime::<FixedOffset>::from_str("2024-01-01T00:00:00Z").unwrap();
+        for (email, name, age) in [
+            ("ann@example.com", "Ann", 30),
+            ("bea@example.com", "Bea", 15),
+            ("cal@test.org", "Cal", 40),
+            ("dee@example.com", "Dee", 50),
+        ] {
+            client
+                .user()
+                .create(email.to_string(), name.to_string(), now, now, vec![user::age::set(Some(age))])
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        // As if parsed from `?min_age=18&name=Ann&name=Cal&exclude_domain=test.org`
+        let min_age: Option<i32> = Some(18);
+        let names = ["Ann", "Cal"];
+        let exclude_domain: Option<&str> = Some("test.org");
+
+        let mut filter = FilterBuilder::all();
+        if let Some(min_age) = min_age {
+            filter = filter.push(user::age::gte(Some(min_age)));
+        }
+        // (name in the list) OR (age >= 50)
+        filter = filter.push_group(
+            FilterBuilder::any()
+                .extend(names.iter().map(|name| user::name::equals(*name)))
+                .push(user::age::gte(Some(50))),
+        );
+        if let Some(domain) = exclude_domain {
+            filter = filter.push_not(user::email::ends_with(domain));
+        }
+        // Empty groups are dropped rather than matching nothing
+        filter = filter.push_group(FilterBuilder::any());
+
+        let mut found: Vec<_> = client
+            .user()
+            .find_many(vec![filter.build()])
+            .exec()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|u| u.name)
+            .collect();
+        found.sort();
+        assert_eq!(found, vec!["Ann", "Dee"]);
+
+        let everyone = client
+            .user()
+            .find_many(vec![FilterBuilder::<user::WhereParam>::default().build()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(everyone.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_update_many_and_delete_many_exec_returning() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+        let now = DateTime::<FixedOffset>::from_str("2024-01-01T00:00:00Z").unwrap();
+        let mut ids = Vec::new();
+        for (i, age) in [18, 31, 45, 52].into_iter().enumerate() {
+            let created = client
+                .user()
+                .create(
+                    format!("returning{}@example.com", i),
+                    format!("User {}", i),
+                    now,
+                    now,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
+            ids.push(created.id);
+        }
+        let sorted = |mut rows: Vec<user::Model>| {
+            rows.sort_by_key(|row| row.age);
+            rows
+        };
+
+        // Plain values go row by row; every affected row comes back updated
+        let updated = sorted(
+            client
+                .user()
+                .update_many(vec![user::age::gte(Some(30))], vec![user::name::set("Senior")])
+                .exec_returning()
+                .await
+                .unwrap(),
+        );
+        assert_eq!(updated.iter().map(|u| u.id).collect::<Vec<_>>(), ids[1..].to_vec());
+        assert!(updated.iter().all(|u| u.name == "Senior"));
+
+        // Column expressions run as one UPDATE ... RETURNING
+        let copied = sorted(
+            client
+                .user()
+                .update_many(
+                    vec![user::age::gte(Some(40))],
+                    vec![user::tenant_id::set_from_column(user::age::column())],
+                )
+                .exec_returning()
+                .await
+                .unwrap(),
+        );
+        assert_eq!(copied.iter().map(|u| u.id).collect::<Vec<_>>(), ids[2..].to_vec());
+        assert_eq!(copied.iter().map(|u| u.tenant_id).collect::<Vec<_>>(), vec![Some(45), Some(52)]);
+
+        let deleted = sorted(
+            client
+                .user()
+                .delete_many(vec![user::name::equals("Senior")])
+                .exec_returning()
+                .await
+                .unwrap(),
+        );
+        assert_eq!(deleted.iter().map(|u| u.id).collect::<Vec<_>>(), ids[1..].to_vec());
+        let remaining = client.user().find_many(vec![]).exec().await.unwrap();
+        assert_eq!(remaining.iter().map(|u| u.id).collect::<Vec<_>>(), vec![ids[0]]);
+    }
+
+    #[tokio::test]
+    async fn test_create_from_select_copies_filtered_rows() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+        let now = DateTime::<FixedOffset>::from_str("2024-01-01T00:00:00Z").unwrap();
+        for (i, deleted) in [true, false, true, false, false].into_iter().enumerate() {
+            client
+                .user()
+                .create(
+                    format!("archive{}@example.com", i),
+                    format!("User {}", i),
+                    now,
+                    now,
+                    vec![
+                        user::age::set(Some(20 + i as i32)),
+                        user::deleted_at::set(deleted.then_some(now)),
+                    ],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let copy = client
+            .archived_user()
+            .create_from_select(client.user().find_many(vec![user::deleted_at::is_not_null()]));
+        let sql = copy.to_sql();
+        assert!(sql.starts_with(r#"INSERT INTO "archived_users""#), "{}", sql);
+        assert!(sql.contains(r#"SELECT "users"."id""#) && sql.contains("WHERE"), "{}", sql);
+        assert!(!sql.contains("tenant_id") && !sql.contains("archive_reason"), "{}", sql);
+        assert_eq!(copy.exec().await.unwrap(), 2);
+
+        let source_count = client
+            .user()
+            .count(vec![user::deleted_at::is_not_null()])
+            .exec()
+            .await
+            .unwrap();
+        let archived = client
+            .archived_user()
+            .find_many(vec![])
+            .order_by(archived_user::name::order(SortOrder::Asc))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(archived.len() as i64, source_count);
+        let names: Vec<_> = archived.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["User 0", "User 2"]);
+        assert_eq!(archived[1].age, Some(22));
+        assert!(archived.iter().all(|u| u.deleted_at.is_some() && u.archive_reason.is_none()));
+
+        // Required target columns (users.email) must have a source column
+        let err = client
+            .user()
+            .create_from_select(client.post().find_many(vec![]))
+            .exec()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no source column"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_find_many_write_csv() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+        let now = DateTime::<FixedOffset>::from_str("2024-01-01T00:00:00Z").unwrap();
+        let user_id = Uuid::new_v4();
+        client
+            .user()
+            .create("csv@example.com".to_string(), "Csv".to_string(), now, now, vec![user::id::set(user_id)])
+            .exec()
+            .await
+            .unwrap();
+        let quoted_id = Uuid::new_v4();
+        let plain_id = Uuid::new_v4();
+        client
+            .post()
+            .create(
+                "Hello, \"world\"".to_string(),
+                now,
+                now,
+                user::id::equals(user_id),
+                vec![
+                    post::id::set(quoted_id),
+                    post::content::set(Some("line one\nline two".to_string())),
+                    post::custom_data::set(Some(serde_json::json!({"k": 1}))),
+                ],
+            )
+            .exec()
+            .await
+            .unwrap();
+        client
+            .post()
+            .create("Plain".to_string(), now, now, user::id::equals(user_id), vec![post::id::set(plain_id)])
+            .exec()
+            .await
+            .unwrap();
+
+        let mut out: Vec<u8> = Vec::new();
+        let written = client
+            .post()
+            .find_many(vec![])
+            .order_by(post::title::order(SortOrder::Asc))
+            .write_csv(&mut out)
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+
+        let expected = format!(
+            "id,title,content,created_at,updated_at,user_id,reviewer_user_id,custom_data\r\n\
+             {quoted_id},\"Hello, \"\"world\"\"\",\"line one\nline two\",2024-01-01T00:00:00+00:00,2024-01-01T00:00:00+00:00,{user_id},,\"{{\"\"k\"\":1}}\"\r\n\
+             {plain_id},Plain,,2024-01-01T00:00:00+00:00,2024-01-01T00:00:00+00:00,{user_id},,\r\n"
+        );
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+        // Relations can't be flattened into rows
+        let err = client
+            .post()
+            .find_many(vec![])
+            .with(post::user::fetch())
+            .write_csv(&mut Vec::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("write_csv does not support relations"));
+
+        // A negative take reads the rows reversed but writes them in the requested order
+        let mut out: Vec<u8> = Vec::new();
+        client
+            .post()
+            .find_many(vec![])
+            .order_by(post::title::order(SortOrder::Asc))
+            .take(-2)
+            .write_csv(&mut out)
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+        // The client's row cap applies as it does to exec
+        let err = client
+            .max_rows(Some(1))
+            .post()
+            .find_many(vec![])
+            .write_csv(&mut Vec::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("CausticsError::RowLimitExceeded"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_basic_functionality() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Test basic create and find functionality
+        let _user = client
+            .user()
+            .create(
+                "test@example.com".to_string(),
+                "Test User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Verify existing functionality still works
+        let found_user = client
+            .user()
+            .find_first(vec![user::email::equals("test@example.com")])
+            .exec()
+            .await
+            .unwrap();
+
+        assert!(found_user.is_some());
+        let found = found_user.unwrap();
+        assert_eq!(found.name, "Test User");
+        assert_eq!(found.age, Some(25));
+    }
+
+    #[tokio::test]
+    async fn test_collection_operators_readme_examples() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Create test users to match README examples
+        let user1 = client
+            .user()
+            .create(
+                "user1@example.com".to_string(),
+                "User One".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(13)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user2 = client
+            .user()
+            .create(
+                "user2@example.com".to_string(),
+                "User Two".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(14)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user3 = client
+            .user()
+            .create(
+                "user3@example.com".to_string(),
+                "User Three".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user5 = client
+            .user()
+            .create(
+                "user5@example.com".to_string(),
+                "User Five".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(15)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user8 = client
+            .user()
+            .create(
+                "user8@example.com".to_string(),
+                "User Eight".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Test README example: user::id::in_vec(vec![1, 2, 3, 5, 8])
+        // Using actual IDs from created users
+        let users_by_ids = client
+            .user()
+            .find_many(vec![user::id::in_vec(vec![
+                user1.id, user2.id, user3.id, user5.id, user8.id,
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_by_ids.len(), 5);
+        let found_ids: Vec<Uuid> = users_by_ids.iter().map(|u| u.id).collect();
+        assert!(found_ids.contains(&user1.id));
+        assert!(found_ids.contains(&user2.id));
+        assert!(found_ids.contains(&user3.id));
+        assert!(found_ids.contains(&user5.id));
+        assert!(found_ids.contains(&user8.id));
+
+        // Test README example: user::age::not_in_vec(vec![Some(13), Some(14), Some(15)])
+        let users_excluding_young_ages = client
+            .user()
+            .find_many(vec![user::age::not_in_vec(vec![
+                Some(13),
+                Some(14),
+                Some(15),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_excluding_young_ages.len(), 2); // user3 (25) and user8 (30)
+        assert!(users_excluding_young_ages.iter().all(|u| {
+            let age = u.age.unwrap_or(0);
+            age != 13 && age != 14 && age != 15
+        }));
+
+        // Verify the excluded users have the expected ages
+        let included_ages: Vec<Option<i32>> =
+            users_excluding_young_ages.iter().map(|u| u.age).collect();
+        assert!(included_ages.contains(&Some(25)));
+        assert!(included_ages.contains(&Some(30)));
+    }
+
+    #[tokio::test]
+    async fn test_null_operators() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Create test users with various null combinations
+        let user_with_age = client
+            .user()
+            .create(
+                "with_age@example.com".to_string(),
+                "User With Age".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user_without_age = client
+            .user()
+            .create(
+                "without_age@example.com".to_string(),
+                "User Without Age".to_string(),
+                now,
+                now,
+                vec![user::age::set(None), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user_deleted = client
+            .user()
+            .create(
+                "deleted@example.com".to_string(),
+                "Deleted User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(25)), user::deleted_at::set(Some(now))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user_no_deletions = client
+            .user()
+            .create(
+                "no_deletions@example.com".to_string(),
+                "No Deletions User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(35)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create posts with various null combinations
+        let post_with_content = client
+            .post()
+            .create(
+                "Post with content".to_string(),
+                now,
+                now,
+                user::id::equals(user_with_age.id),
+                vec![
+                    post::content::set(Some("This post has content".to_string())),
+                    post::reviewer_user_id::set(None),
+                ],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let post_without_content = client
+            .post()
+            .create(
+                "Post without content".to_string(),
+                now,
+                now,
+                user::id::equals(user_without_age.id),
+                vec![
+                    post::content::set(None),
+                    post::reviewer_user_id::set(Some(user_with_age.id)),
+                ],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Test is_null operator for age field
+        let users_without_age = client
+            .user()
+            .find_many(vec![user::age::is_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_without_age.len(), 1);
+        assert_eq!(users_without_age[0].id, user_without_age.id);
+        assert_eq!(users_without_age[0].age, None);
+
+        // Test is_not_null operator for age field
+        let users_with_age = client
+            .user()
+            .find_many(vec![user::age::is_not_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_with_age.len(), 3);
+        let user_ids_with_age: Vec<Uuid> = users_with_age.iter().map(|u| u.id).collect();
+        assert!(user_ids_with_age.contains(&user_with_age.id));
+        assert!(user_ids_with_age.contains(&user_deleted.id));
+        assert!(user_ids_with_age.contains(&user_no_deletions.id));
+        assert!(!user_ids_with_age.contains(&user_without_age.id));
+
+        // Test is_null operator for deleted_at field
+        let non_deleted_users = client
+            .user()
+            .find_many(vec![user::deleted_at::is_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(non_deleted_users.len(), 3);
+        let non_deleted_ids: Vec<Uuid> = non_deleted_users.iter().map(|u| u.id).collect();
+        assert!(non_deleted_ids.contains(&user_with_age.id));
+        assert!(non_deleted_ids.contains(&user_without_age.id));
+        assert!(non_deleted_ids.contains(&user_no_deletions.id));
+        assert!(!non_deleted_ids.contains(&user_deleted.id));
+
+        // Test is_not_null operator for deleted_at field
+        let deleted_users = client
+            .user()
+            .find_many(vec![user::deleted_at::is_not_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(deleted_users.len(), 1);
+        assert_eq!(deleted_users[0].id, user_deleted.id);
+        assert!(deleted_users[0].deleted_at.is_some());
+
+        // Test is_null operator for post content field
+        let posts_without_content = client
+            .post()
+            .find_many(vec![post::content::is_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_without_content.len(), 1);
+        assert_eq!(posts_without_content[0].id, post_without_content.id);
+        assert_eq!(posts_without_content[0].content, None);
+
+        // Test is_not_null operator for post content field
+        let posts_with_content = client
+            .post()
+            .find_many(vec![post::content::is_not_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_content.len(), 1);
+        assert_eq!(posts_with_content[0].id, post_with_content.id);
+        assert!(posts_with_content[0].content.is_some());
+
+        // Test is_null operator for reviewer_user_id field
+        let posts_without_reviewer = client
+            .post()
+            .find_many(vec![post::reviewer_user_id::is_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_without_reviewer.len(), 1);
+        assert_eq!(posts_without_reviewer[0].id, post_with_content.id);
+        assert_eq!(posts_without_reviewer[0].reviewer_user_id, None);
+
+        // Test is_not_null operator for reviewer_user_id field
+        let posts_with_reviewer = client
+            .post()
+            .find_many(vec![post::reviewer_user_id::is_not_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_reviewer.len(), 1);
+        assert_eq!(posts_with_reviewer[0].id, post_without_content.id);
+        assert!(posts_with_reviewer[0].reviewer_user_id.is_some());
+
+        // Test combining null operators with logical operators
+        let users_with_age_not_deleted = client
+            .user()
+            .find_many(vec![operator::and(vec![
+                user::age::is_not_null(),
+                user::deleted_at::is_null(),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_with_age_not_deleted.len(), 2);
+        let filtered_ids: Vec<Uuid> = users_with_age_not_deleted.iter().map(|u| u.id).collect();
+        assert!(filtered_ids.contains(&user_with_age.id));
+        assert!(filtered_ids.contains(&user_no_deletions.id));
+
+        // Test combining null operators with OR
+        let users_missing_data = client
+            .user()
+            .find_many(vec![operator::or(vec![
+                user::age::is_null(),
+                user::deleted_at::is_not_null(),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users_missing_data.len(), 2);
+        let missing_data_ids: Vec<Uuid> = users_missing_data.iter().map(|u| u.id).collect();
+        assert!(missing_data_ids.contains(&user_without_age.id));
+        assert!(missing_data_ids.contains(&user_deleted.id));
+    }
+
+    #[tokio::test]
+    async fn test_json_field_operations() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // Create a user for the posts
+        let user = client
+            .user()
+            .create(
+                format!("jsonuser_{}@example.com", chrono::Utc::now().timestamp()),
+                "JSON User".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create posts with various JSON data for testing
+        let post_with_simple_json = client
+            .post()
+            .create(
+                "Post with Simple JSON".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                user::id::equals(user.id),
+                vec![
+                    post::content::set(Some("A post with simple JSON".to_string())),
+                    post::custom_data::set(Some(serde_json::json!({
+                        "category": "technology",
+                        "tags": ["rust", "json", "database"],
+                        "metadata": {
+                            "author_notes": "This is a test post",
+                            "priority": "high"
+                        },
+                        "view_count": 42,
+                        "published": true
+                    }))),
+                ],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let post_with_array_json = client
+            .post()
+            .create(
+                "Post with Array JSON".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                user::id::equals(user.id),
+                vec![
+                    post::content::set(Some("A post with array JSON".to_string())),
+                    post::custom_data::set(Some(serde_json::json!({
+                        "categories": ["programming", "tutorial", "beginner"],
+                        "scores": [85, 90, 78],
+                        "settings": {
+                            "notifications": true,
+                            "public": false
+                        }
+                    }))),
+                ],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let post_with_string_json = client
+            .post()
+            .create(
+                "Post with String JSON".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                user::id::equals(user.id),
+                vec![
+                    post::content::set(Some("A post with string JSON".to_string())),
+                    post::custom_data::set(Some(serde_json::json!({
+                        "description": "This is a comprehensive guide to JSON operations in databases",
+                        "author": "John Developer",
+                        "status": "published"
+                    }))),
+                ],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let post_without_json = client
+            .post()
+            .create(
+                "Post without JSON".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                user::id::equals(user.id),
+                vec![
+                    post::content::set(Some("A post without JSON data".to_string())),
+                    post::custom_data::set(None),
+                ],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create a post with explicit JSON null value (JSON = null)
+        let post_with_json_null = client
+            .post()
+            .create(
+                "Post with JSON null".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                user::id::equals(user.id),
+                vec![
+                    post::content::set(Some("A post with JSON null".to_string())),
+                    post::custom_data::set(Some(serde_json::Value::Null)),
+                ],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Test basic JSON equals operation
+        let posts_with_specific_category = client
+            .post()
+            .find_many(vec![post::custom_data::equals(Some(serde_json::json!({
+                "category": "technology",
+                "tags": ["rust", "json", "database"],
+                "metadata": {
+                    "author_notes": "This is a test post",
+                    "priority": "high"
+                },
+                "view_count": 42,
+                "published": true
+            })))])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_specific_category.len(), 1);
+        assert_eq!(posts_with_specific_category[0].id, post_with_simple_json.id);
+
+        // Test JSON null operations
+        let posts_without_custom_data = client
+            .post()
+            .find_many(vec![post::custom_data::is_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_without_custom_data.len(), 1);
+        assert_eq!(posts_without_custom_data[0].id, post_without_json.id);
+
+        // JSON null parity helpers
+        // DB NULL (column is NULL)
+        let posts_db_null = client
+            .post()
+            .find_many(vec![post::custom_data::db_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_db_null.len(), 1);
+        assert_eq!(posts_db_null[0].id, post_without_json.id);
+
+        // JSON null (value is JSON null)
+        let posts_json_null = client
+            .post()
+            .find_many(vec![post::custom_data::json_null()])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_json_null.len(), 1);
+        assert_eq!(posts_json_null[0].id, post_with_json_null.id);
+
+        // Any null (DB NULL or JSON null)
+        let posts_any_null = client
+            .post()
+            .find_many(vec![post::custom_data::any_null()])
+            .exec()
+            .await
+            .unwrap();
+        let any_ids: std::collections::HashSet<_> = posts_any_null.iter().map(|p| p.id).collect();
+        assert!(any_ids.contains(&post_without_json.id));
+        assert!(any_ids.contains(&post_with_json_null.id));
+
+        let posts_with_custom_data = client
+            .post()
+            .find_many(vec![post::custom_data::is_not_null()])
+            .exec()
+            .await
+            .unwrap();
+        // Includes posts with JSON values (including JSON null), excludes DB NULL
+        assert_eq!(posts_with_custom_data.len(), 4);
+
+        // Test JSON path access - simple key
+        let posts_with_category_key = client
+            .post()
+            .find_many(vec![post::custom_data::path(vec!["category".to_string()])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_category_key.len(), 1);
+        assert_eq!(posts_with_category_key[0].id, post_with_simple_json.id);
+
+        // Test JSON object contains key operations
+        let posts_with_metadata_key = client
+            .post()
+            .find_many(vec![post::custom_data::json_object_contains(
+                "metadata".to_string(),
+            )])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_metadata_key.len(), 1);
+        assert_eq!(posts_with_metadata_key[0].id, post_with_simple_json.id);
+
+        let posts_with_settings_key = client
+            .post()
+            .find_many(vec![post::custom_data::json_object_contains(
+                "settings".to_string(),
+            )])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_settings_key.len(), 1);
+        assert_eq!(posts_with_settings_key[0].id, post_with_array_json.id);
+
+        let posts_with_description_key = client
+            .post()
+            .find_many(vec![post::custom_data::json_object_contains(
+                "description".to_string(),
+            )])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_description_key.len(), 1);
+        assert_eq!(posts_with_description_key[0].id, post_with_string_json.id);
+
+        // Test JSON string contains operations
+        let posts_with_rust_anywhere = client
+            .post()
+            .find_many(vec![post::custom_data::json_string_contains(
+                "rust".to_string(),
+            )])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_rust_anywhere.len(), 1);
+        assert_eq!(posts_with_rust_anywhere[0].id, post_with_simple_json.id);
+
+        let posts_with_guide_description = client
+            .post()
+            .find_many(vec![post::custom_data::json_string_contains(
+                "comprehensive guide".to_string(),
+            )])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_guide_description.len(), 1);
+        assert_eq!(posts_with_guide_description[0].id, post_with_string_json.id);
+
+        // Test JSON operations with logical operators (AND)
+        let posts_with_category_and_metadata = client
+            .post()
+            .find_many(vec![operator::and(vec![
+                post::custom_data::json_object_contains("category".to_string()),
+                post::custom_data::json_object_contains("metadata".to_string()),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_category_and_metadata.len(), 1);
+        assert_eq!(
+            posts_with_category_and_metadata[0].id,
+            post_with_simple_json.id
+        );
+
+        // Test JSON operations with logical operators (OR)
+        let posts_with_description_or_settings = client
+            .post()
+            .find_many(vec![operator::or(vec![
+                post::custom_data::json_object_contains("description".to_string()),
+                post::custom_data::json_object_contains("settings".to_string()),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(posts_with_description_or_settings.len(), 2);
+        let found_ids: Vec<Uuid> = posts_with_description_or_settings
+            .iter()
+            .map(|p| p.id)
+            .collect();
+        assert!(found_ids.contains(&post_with_string_json.id));
+        assert!(found_ids.contains(&post_with_array_json.id));
+
+        // Test JSON operations with NOT operator
+        let posts_without_metadata = client
+            .post()
+            .find_many(vec![post::not(vec![
+                post::custom_data::json_object_contains("metadata".to_string()),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        // Should find posts without JSON data and posts without metadata key
+        assert!(posts_without_metadata.len() >= 3);
+        let found_ids: Vec<Uuid> = posts_without_metadata.iter().map(|p| p.id).collect();
+        assert!(found_ids.contains(&post_without_json.id));
+        assert!(found_ids.contains(&post_with_array_json.id));
+        assert!(found_ids.contains(&post_with_string_json.id));
+        assert!(!found_ids.contains(&post_with_simple_json.id));
+    }
+
+    #[tokio::test]
+    async fn test_atomic_operations() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Create a user with an age to test atomic operations
+        let user = client
+            .user()
+            .create(
+                "atomic@example.com".to_string(),
+                "Atomic User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        assert_eq!(user.age, Some(25));
+
+        // Test increment operation
+
+        let updated_user = client
+            .user()
+            .update(user::id::equals(user.id), vec![user::age::increment(5)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(updated_user.age, Some(30));
+
+        // Test decrement operation
+        let updated_user = client
+            .user()
+            .update(user::id::equals(user.id), vec![user::age::decrement(3)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(updated_user.age, Some(27));
+
+        // Test multiply operation
+        let updated_user = client
+            .user()
+            .update(user::id::equals(user.id), vec![user::age::multiply(2)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(updated_user.age, Some(54));
+
+        // Test divide operation
+        let updated_user = client
+            .user()
+            .update(user::id::equals(user.id), vec![user::age::divide(3)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(updated_user.age, Some(18));
+
+        // Test multiple atomic operations in one update
+        let updated_user = client
+            .user()
+            .update(
+                user::id::equals(user.id),
+                vec![user::age::increment(10), user::age::multiply(2)],
+            )
+            .exec()
+            .await
+            .unwrap();
+        // Should be (18 + 10) * 2 = 56
+        assert_eq!(updated_user.age, Some(56));
+    }
+
+    #[tokio::test]
+    async fn test_atomic_operations_simple() {
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Create a user with an age to test atomic operations
+        let user = client
+            .user()
+            .create(
+                "atomic@example.com".to_string(),
+                "Atomic User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        assert_eq!(user.age, Some(25));
+
+        // Test that atomic operations exist and can be called
+        let _increment_op = user::age::increment(5);
+        let _decrement_op = user::age::decrement(3);
+        let _multiply_op = user::age::multiply(2);
+        let _divide_op = user::age::divide(3);
+
+        // Test increment operation
+        let updated_user = client
+            .user()
+            .update(user::id::equals(user.id), vec![user::age::increment(5)])
+            .exec()
+            .await
+            .unwrap();
+
+        // The atomic operation should work
+        assert_eq!(updated_user.age, Some(30));
+    }
+
+    #[tokio::test]
+    async fn test_advanced_relation_operations() {
+        let _ = env_logger::try_init();
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        // Create a user with some posts
+        let user = client
+            .user()
+            .create(
+                "relation@example.com".to_string(),
+                "Relation User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create some posts for the user
+        let _post1 = client
+            .post()
+            .create(
+                "First Post".to_string(),
+                now,
+                now,
+                user::id::equals(user.id),
+                vec![post::content::set(Some("First post content".to_string()))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _post2 = client
+            .post()
+            .create(
+                "Second Post".to_string(),
+                now,
+                now,
+                user::id::equals(user.id),
+                vec![post::content::set(Some("Second post content".to_string()))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Test that the advanced relation operations exist and can be called
+        let _some_condition =
+            user::posts::some(vec![post::title::equals("First Post".to_string())]);
+
+        let _every_condition = user::posts::every(vec![post::title::contains("Post".to_string())]);
+
+        let _none_condition =
+            user::posts::none(vec![post::title::equals("Non-existent Post".to_string())]);
+
+        // Test that the conditions can be used in queries (they should work now!)
+        let users_with_some_posts = client
+            .user()
+            .find_many(vec![user::posts::some(vec![post::title::equals(
+                "First Post".to_string(),
+            )])])
+            .exec()
+            .await
+            .unwrap();
+
+        // The query should return the user since they have a post with title "First Post"
+        assert_eq!(users_with_some_posts.len(), 1);
+        assert_eq!(users_with_some_posts[0].id, user.id);
+
+        // Test every condition
+        let users_with_every_post_containing_post = client
+            .user()
+            .find_many(vec![user::posts::every(vec![post::title::contains(
+                "Post".to_string(),
+            )])])
+            .exec()
+            .await
+            .unwrap();
+
+        // The query should return the user since all their posts contain "Post"
+        assert_eq!(users_with_every_post_containing_post.len(), 1);
+        assert_eq!(users_with_every_post_containing_post[0].id, user.id);
+
+        // Test none condition
+        let users_with_no_nonexistent_posts = client
+            .user()
+            .find_many(vec![user::posts::none(vec![post::title::equals(
+                "Non-existent Post".to_string(),
+            )])])
+            .exec()
+            .await
+            .unwrap();
+
+        // The query should return the user since they don't have a post with title "Non-existent Post"
+        assert_eq!(users_with_no_nonexistent_posts.len(), 1);
+        assert_eq!(users_with_no_nonexistent_posts[0].id, user.id);
+    }
+
+    #[tokio::test]
+    async fn test_in_related_subquery_filter() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for (name, titles) in [
+            ("Ferris", vec!["Learning Rust", "Rust tips"]),
+            ("Gopher", vec!["Go channels"]),
+            ("Mixed", vec!["Go vs Rust"]),
+            ("Silent", vec![]),
+        ] {
+            let author = client
+                .user()
+                .create(
+                    format!("{}@example.com", name.to_lowercase()),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![],
+                )
+                .exec()
+                .await
+                .unwrap();
+            for title in titles {
+                client
+                    .post()
+                    .create(
+                        title.to_string(),
+                        created,
+                        created,
+                        user::id::equals(author.id),
+                        vec![],
+                    )
+                    .exec()
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let mut authors: Vec<String> = client
+            .user()
+            .find_many(vec![user::id::in_related(
+                post::user,
+                vec![post::title::contains("Rust")],
+            )])
+            .exec()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|u| u.name)
+            .collect();
+        authors.sort();
+        assert_eq!(authors, vec!["Ferris", "Mixed"]);
+
+        // Composes with other filters on the outer query
+        let authors = client
+            .user()
+            .find_many(vec![
+                user::id::in_related(post::user, vec![post::title::starts_with("Go")]),
+                user::name::not_equals("Mixed"),
+            ])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "Gopher");
+    }
+
+    #[tokio::test]
+    async fn test_relation_count_threshold_filters() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        // Users with 0, 1, 3 and 5 posts respectively
+        for (name, post_count) in [("Zero", 0), ("One", 1), ("Three", 3), ("Five", 5)] {
+            let author = client
+                .user()
+                .create(
+                    format!("{}@example.com", name.to_lowercase()),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![],
+                )
+                .exec()
+                .await
+                .unwrap();
+            for i in 0..post_count {
+                client
+                    .post()
+                    .create(
+                        format!("{} post {}", name, i),
+                        created,
+                        created,
+                        user::id::equals(author.id),
+                        vec![],
+                    )
+                    .exec()
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let names = |users: Vec<user::ModelWithRelations>| {
+            let mut names: Vec<String> = users.into_iter().map(|u| u.name).collect();
+            names.sort();
+            names
+        };
+
+        let users = client
+            .user()
+            .find_many(vec![user::posts::count_gte(3)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["Five", "Three"]);
+
+        let users = client
+            .user()
+            .find_many(vec![user::posts::count_gt(3)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["Five"]);
+
+        let users = client
+            .user()
+            .find_many(vec![user::posts::count_lt(3)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["One", "Zero"]);
+
+        let users = client
+            .user()
+            .find_many(vec![user::posts::count_lte(1)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["One", "Zero"]);
+
+        let users = client
+            .user()
+            .find_many(vec![user::posts::count_eq(0)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["Zero"]);
+
+        // Combines with regular filters and count()
+        let count = client
+            .user()
+            .count(vec![
+                user::posts::count_gte(1),
+                user::name::not_equals("Five".to_string()),
+            ])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_complex_relation_filtering_with_subqueries() {
+        let _ = env_logger::try_init();
+        use chrono::TimeZone;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+        let future_date = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 12, 31, 23, 59, 59)
+            .unwrap();
+
+        // Create multiple users with different post patterns
+        let user1 = client
+            .user()
+            .create(
+                "user1@example.com".to_string(),
+                "User One".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user2 = client
+            .user()
+            .create(
+                "user2@example.com".to_string(),
+                "User Two".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let user3 = client
+            .user()
+            .create(
+                "user3@example.com".to_string(),
+                "User Three".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(35)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create posts for user1: Has "Hello" posts with content, all created in 2024
+        let _post1_1 = client
+            .post()
+            .create(
+                "Hello World".to_string(),
+                now,
+                now,
+                user::id::equals(user1.id),
+                vec![post::content::set(Some("Hello post content".to_string()))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _post1_2 = client
+            .post()
+            .create(
+                "Hello Again".to_string(),
+                now,
+                now,
+                user::id::equals(user1.id),
+                vec![post::content::set(Some("Another hello post".to_string()))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create posts for user2: Has "Hello" posts but some without content, all created in 2024
+        let _post2_1 = client
+            .post()
+            .create(
+                "Hello from User2".to_string(),
+                now,
+                now,
+                user::id::equals(user2.id),
+                vec![post::content::set(Some("User2 hello content".to_string()))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _post2_2 = client
+            .post()
+            .create(
+                "Hello without content".to_string(),
+                now,
+                now,
+                user::id::equals(user2.id),
+                vec![post::content::set(None)], // No content
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create posts for user3: Has "Hello" posts but some created in future, some spam
+        let _post3_1 = client
+            .post()
+            .create(
+                "Hello from User3".to_string(),
+                now,
+                now,
+                user::id::equals(user3.id),
+                vec![post::content::set(Some("User3 hello content".to_string()))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _post3_2 = client
+            .post()
+            .create(
+                "Future Hello".to_string(),
+                future_date,
+                future_date,
+                user::id::equals(user3.id),
+                vec![post::content::set(Some("Future hello content".to_string()))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        let _post3_3 = client
+            .post()
+            .create(
+                "Spam Post".to_string(),
+                now,
+                now,
+                user::id::equals(user3.id),
+                vec![post::content::set(Some("Spam content".to_string()))],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Test 1: Complex relation filtering with multiple conditions
+        // Find users who:
+        // - Have SOME posts with "Hello" in title
+        // - Have EVERY post with "Hello" in title
+        // - Have NO posts with "Spam" in title
+        let complex_filtered_users = client
+            .user()
+            .find_many(vec![
+                user::posts::some(vec![post::title::contains("Hello".to_string())]),
+                user::posts::every(vec![post::title::contains("Hello".to_string())]),
+                user::posts::none(vec![post::title::contains("Spam".to_string())]),
+            ])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return user1 and user2, but not user3
+        // user1: ✅ has "Hello" posts, ✅ all posts have "Hello", ✅ no spam
+        // user2: ✅ has "Hello" posts, ✅ all posts have "Hello", ✅ no spam
+        // user3: ✅ has "Hello" posts, ❌ has "Spam" post, ❌ has spam
+        assert_eq!(complex_filtered_users.len(), 2);
+        let user_ids: Vec<Uuid> = complex_filtered_users.iter().map(|u| u.id).collect();
+        assert!(user_ids.contains(&user1.id));
+        assert!(user_ids.contains(&user2.id));
+        assert!(!user_ids.contains(&user3.id));
+
+        // Test 2: More specific filtering with different conditions
+        // Find users who have posts with "World" in title
+        let users_with_world = client
+            .user()
+            .find_many(vec![user::posts::some(vec![post::title::contains(
+                "World".to_string(),
+            )])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return user1 (has "Hello World" post)
+        assert_eq!(users_with_world.len(), 1);
+        assert_eq!(users_with_world[0].id, user1.id);
+
+        // Test 3: Every post must have "Hello" in title
+        let users_with_all_hello_posts = client
+            .user()
+            .find_many(vec![user::posts::every(vec![post::title::contains(
+                "Hello".to_string(),
+            )])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return user1 and user2, but not user3 (has "Spam Post")
+        assert_eq!(users_with_all_hello_posts.len(), 2);
+        let all_hello_user_ids: Vec<Uuid> =
+            users_with_all_hello_posts.iter().map(|u| u.id).collect();
+        assert!(all_hello_user_ids.contains(&user1.id));
+        assert!(all_hello_user_ids.contains(&user2.id));
+        assert!(!all_hello_user_ids.contains(&user3.id));
+
+        // Test 4: No spam posts
+        let users_without_spam_posts = client
+            .user()
+            .find_many(vec![user::posts::none(vec![post::title::contains(
+                "Spam".to_string(),
+            )])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return user1 and user2, but not user3 (has spam post)
+        assert_eq!(users_without_spam_posts.len(), 2);
+        let no_spam_user_ids: Vec<Uuid> = users_without_spam_posts.iter().map(|u| u.id).collect();
+        assert!(no_spam_user_ids.contains(&user1.id));
+        assert!(no_spam_user_ids.contains(&user2.id));
+        assert!(!no_spam_user_ids.contains(&user3.id));
+
+        // Test 5: Combined with logical operators
+        let combined_filtered_users = client
+            .user()
+            .find_many(vec![operator::and(vec![
+                user::posts::some(vec![post::title::contains("Hello".to_string())]),
+                user::posts::none(vec![post::title::contains("Spam".to_string())]),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return user1 and user2, but not user3
+        assert_eq!(combined_filtered_users.len(), 2);
+        let combined_user_ids: Vec<Uuid> = combined_filtered_users.iter().map(|u| u.id).collect();
+        assert!(combined_user_ids.contains(&user1.id));
+        assert!(combined_user_ids.contains(&user2.id));
+        assert!(!combined_user_ids.contains(&user3.id));
+
+        // Test 6: Edge case - user with no posts
+        let user_no_posts = client
+            .user()
+            .create(
+                "noposts@example.com".to_string(),
+                "No Posts User".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(40)), user::deleted_at::set(None)],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // User with no posts should match "none" conditions
+        let users_with_no_spam = client
+            .user()
+            .find_many(vec![user::posts::none(vec![post::title::contains(
+                "Spam".to_string(),
+            )])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should include the user with no posts
+        let no_spam_user_ids_updated: Vec<Uuid> = users_with_no_spam.iter().map(|u| u.id).collect();
+        assert!(no_spam_user_ids_updated.contains(&user_no_posts.id));
+
+        // User with no posts should NOT match "some" conditions
+        let users_with_hello_final = client
+            .user()
+            .find_many(vec![user::posts::some(vec![post::title::contains(
+                "Hello".to_string(),
+            )])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should NOT include the user with no posts
+        let hello_user_ids: Vec<Uuid> = users_with_hello_final.iter().map(|u| u.id).collect();
+        assert!(!hello_user_ids.contains(&user_no_posts.id));
+
+        // Test 7: Nullable field filtering in relations
+        // Find users who have posts with content (is_not_null)
+        let users_with_content_posts = client
+            .user()
+            .find_many(vec![user::posts::some(vec![post::content::is_not_null()])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return all 3 users because:
+        // user1: has 2 posts with content ✅
+        // user2: has 1 post with content (and 1 without) ✅
+        // user3: has 3 posts with content ✅
+        assert_eq!(users_with_content_posts.len(), 3);
+        let content_user_ids: Vec<Uuid> = users_with_content_posts.iter().map(|u| u.id).collect();
+        assert!(content_user_ids.contains(&user1.id));
+        assert!(content_user_ids.contains(&user2.id));
+        assert!(content_user_ids.contains(&user3.id));
+
+        // Find users who have posts without content (is_null)
+        let users_with_null_content_posts = client
+            .user()
+            .find_many(vec![user::posts::some(vec![post::content::is_null()])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return user2 (has post without content)
+        assert_eq!(users_with_null_content_posts.len(), 1);
+        assert_eq!(users_with_null_content_posts[0].id, user2.id);
+
+        // Test 8: Every post must have content
+        let users_with_all_content = client
+            .user()
+            .find_many(vec![user::posts::every(vec![post::content::is_not_null()])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return user1, user3, and user4 (no posts - vacuous truth), but not user2 (has post without content)
+        assert_eq!(users_with_all_content.len(), 3);
+        let all_content_user_ids: Vec<Uuid> = users_with_all_content.iter().map(|u| u.id).collect();
+        assert!(all_content_user_ids.contains(&user1.id));
+        assert!(all_content_user_ids.contains(&user3.id));
+        assert!(all_content_user_ids.contains(&user_no_posts.id)); // user with no posts
+        assert!(!all_content_user_ids.contains(&user2.id)); // has post without content
+
+        // Test 9: No posts without content
+        let users_with_no_null_content = client
+            .user()
+            .find_many(vec![user::posts::none(vec![post::content::is_null()])])
+            .exec()
+            .await
+            .unwrap();
+
+        // Should return user1, user3, and user4 (no posts - vacuous truth), but not user2 (has post without content)
+        assert_eq!(users_with_no_null_content.len(), 3);
+        let no_null_content_user_ids: Vec<Uuid> =
+            users_with_no_null_content.iter().map(|u| u.id).collect();
+        assert!(no_null_content_user_ids.contains(&user1.id));
+        assert!(no_null_content_user_ids.contains(&user3.id));
+        assert!(no_null_content_user_ids.contains(&user_no_posts.id)); // user with no posts
+        assert!(!no_null_content_user_ids.contains(&user2.id));
+    }
+
+    #[tokio::test]
+    async fn test_relation_filter_negation() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        for (email, titles) in [
+            ("clean@example.com", vec!["Hello draft", "Hello world"]),
+            ("mixed@example.com", vec!["Hello there", "Spam offer"]),
+            ("spam@example.com", vec!["Spam offer"]),
+            ("empty@example.com", vec![]),
+        ] {
+            let user = client
+                .user()
+                .create(email.to_string(), email.to_string(), now, now, vec![])
+                .exec()
+                .await
+                .unwrap();
+            for title in titles {
+                client
+                    .post()
+                    .create(title.to_string(), now, now, user::id::equals(user.id), vec![])
+                    .exec()
+                    .await
+                    .unwrap();
+            }
+        }
+        let emails = |found: Vec<user::ModelWithRelations>| {
+            let mut emails: Vec<String> = found.into_iter().map(|u| u.email).collect();
+            emails.sort();
+            emails
+        };
+
+        // `none` and `not(some(...))` are the same NOT EXISTS
+        let none = client
+            .user()
+            .find_many(vec![user::posts::none(vec![post::title::contains("Spam")])])
+            .exec()
+            .await
+            .unwrap();
+        let not_some = client
+            .user()
+            .find_many(vec![user::not(vec![user::posts::some(vec![post::title::contains("Spam")])])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(emails(none), vec!["clean@example.com", "empty@example.com"]);
+        assert_eq!(emails(not_some), vec!["clean@example.com", "empty@example.com"]);
+
+        // `every` negates its filters together, so a post must match all of them
+        let every = client
+            .user()
+            .find_many(vec![user::posts::every(vec![
+                post::title::starts_with("Hello"),
+                post::title::contains("world"),
+            ])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(emails(every), vec!["empty@example.com"]);
+
+        // `not_every` is the complement of `every`; users without posts satisfy `every`
+        let not_every = client
+            .user()
+            .find_many(vec![user::posts::not_every(vec![post::title::starts_with("Hello")])])
+            .exec()
+            .await
+            .unwrap();
+        let not_every_wrapped = client
+            .user()
+            .find_many(vec![user::not(vec![user::posts::every(vec![post::title::starts_with("Hello")])])])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(emails(not_every), vec!["mixed@example.com", "spam@example.com"]);
+        assert_eq!(emails(not_every_wrapped), vec!["mixed@example.com", "spam@example.com"]);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_recorder_counts_queries() {
+        use caustics::hooks::metrics::{QUERIES_TOTAL, QUERY_DURATION_SECONDS, QUERY_ERRORS_TOTAL};
+        use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, SharedString, Unit};
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::{Arc, Mutex};
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        /// Counters by `(name, entity, op)`, and how many durations each histogram saw
+        type CounterId = (String, String, String);
+        #[derive(Default)]
+        struct TestRecorder {
+            counters: Mutex<Vec<(CounterId, Arc<AtomicU64>)>>,
+            histograms: Mutex<Vec<(String, Arc<AtomicU64>)>>,
+        }
+        struct Samples(Arc<AtomicU64>);
+        impl metrics::HistogramFn for Samples {
+            fn record(&self, value: f64) {
+                assert!(value >= 0.0);
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        impl metrics::Recorder for TestRecorder {
+            fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+                let label = |name: &str| {
+                    key.labels().find(|l| l.key() == name).map(|l| l.value().to_string()).unwrap_or_default()
+                };
+                let id = (key.name().to_string(), label("entity"), label("op"));
+                let mut counters = self.counters.lock().unwrap();
+                let value = match counters.iter().find(|(k, _)| *k == id) {
+                    Some((_, value)) => value.clone(),
+                    None => {
+                        let value = Arc::new(AtomicU64::new(0));
+                        counters.push((id, value.clone()));
+                        value
+                    }
+                };
+                Counter::from_arc(value)
+            }
+            fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+                Gauge::noop()
+            }
+            fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+                let value = Arc::new(AtomicU64::new(0));
+                self.histograms.lock().unwrap().push((key.name().to_string(), value.clone()));
+                Histogram::from_arc(Arc::new(Samples(value)))
+            }
+        }
+        let recorder = TestRecorder::default();
+        let count = |name: &str, entity: &str| -> u64 {
+            recorder
+                .counters
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|((n, e, _), _)| n == name && e.contains(&format!("::{}::", entity)))
+                .map(|(_, value)| value.load(Ordering::Relaxed))
+                .sum()
+        };
+
+        {
+            let _guard = metrics::set_default_local_recorder(&recorder);
+            client.user().find_many(vec![]).exec().await.unwrap();
+            client.post().find_many(vec![]).exec().await.unwrap();
+            client.user().find_many(vec![]).exec().await.unwrap();
+            let failed: Result<Vec<sea_orm::JsonValue>, _> = client
+                ._query_raw::<sea_orm::JsonValue>(caustics::raw!("SELECT * FROM missing_table"))
+                .exec()
+                .await;
+            assert!(failed.is_err());
+        }
+
+        assert_eq!(count(QUERIES_TOTAL, "user"), 2);
+        assert_eq!(count(QUERIES_TOTAL, "post"), 1);
+        assert_eq!(count(QUERY_ERRORS_TOTAL, "user"), 0);
+        let errors: Vec<_> = recorder
+            .counters
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((n, _, _), _)| n == QUERY_ERRORS_TOTAL)
+            .map(|((_, _, op), value)| (op.clone(), value.load(Ordering::Relaxed)))
+            .collect();
+        assert_eq!(errors, vec![("RawQuery".to_string(), 1)]);
+        let histograms: Vec<_> = recorder
+            .histograms
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, value)| (name.clone(), value.load(Ordering::Relaxed)))
+            .collect();
+        assert!(histograms.iter().all(|(name, _)| name == QUERY_DURATION_SECONDS));
+        assert_eq!(histograms.iter().map(|(_, samples)| samples).sum::<u64>(), 4);
+
+        // Without a recorder nothing is recorded
+        client.user().find_many(vec![]).exec().await.unwrap();
+        assert_eq!(count(QUERIES_TOTAL, "user"), 2);
+    }
+
+    #[test]
+    fn test_raw_ident_quoting_per_backend() {
+        use caustics::raw::ident_for;
+        use sea_orm::DatabaseBackend;
+
+        assert_eq!(ident_for(DatabaseBackend::MySql, "users"), "`users`");
+        assert_eq!(ident_for(DatabaseBackend::MySql, "odd`name"), "`odd``name`");
+        assert_eq!(ident_for(DatabaseBackend::Postgres, "users"), "\"users\"");
+        assert_eq!(ident_for(DatabaseBackend::Sqlite, "odd\"name"), "\"odd\"\"name\"");
+        assert_eq!(caustics::ident!(DatabaseBackend::MySql, "users").0, "`users`");
+        assert_eq!(caustics::ident!("users").0, "\"users\"");
+    }
+
+    #[test]
+    fn test_backend_placeholders_skip_quoted_regions() {
+        use caustics::raw::backend_placeholders;
+        use sea_orm::DatabaseBackend;
+        let pg = |sql: &str| backend_placeholders(DatabaseBackend::Postgres, sql.to_string());
+
+        assert_eq!(pg("a = ? AND b = ?"), "a = $1 AND b = $2");
+        assert_eq!(pg("a = 'what?' AND b = ?"), "a = 'what?' AND b = $1");
+        assert_eq!(pg("a = 'it''s?' AND b = ?"), "a = 'it''s?' AND b = $1");
+        assert_eq!(pg(r"a = E'it\'s?' AND b = ?"), r"a = E'it\'s?' AND b = $1");
+        assert_eq!(pg(r#""odd?col" = ?"#), r#""odd?col" = $1"#);
+        assert_eq!(pg("a = ? -- why?\nAND b = ?"), "a = $1 -- why?\nAND b = $2");
+        assert_eq!(pg("a = ? /* why? /* really? */ */ AND b = ?"), "a = $1 /* why? /* really? */ */ AND b = $2");
+        assert_eq!(pg("a = $$what?$$ AND b = $fn$ ? $fn$ AND c = ?"), "a = $$what?$$ AND b = $fn$ ? $fn$ AND c = $1");
+        assert_eq!(pg("data ?| ? AND data ?& ? AND data ?? ?"), "data ?| $1 AND data ?& $2 AND data ? $3");
+        assert_eq!(pg("? || 'x'"), "$1 || 'x'");
+        assert_eq!(pg("?||'x'"), "$1||'x'");
+        // Other backends take `?` natively
+        assert_eq!(backend_placeholders(DatabaseBackend::Sqlite, "a = 'what?' AND b = ?".to_string()), "a = 'what?' AND b = ?");
+    }
+
+    #[tokio::test]
+    async fn test_raw_in_list_chunks() {
+        use caustics::raw::{in_list_chunks, max_params, SQLITE_MAX_PARAMS};
+        use sea_orm::{DatabaseBackend, FromQueryResult};
+
+        assert_eq!(max_params(DatabaseBackend::Sqlite), SQLITE_MAX_PARAMS);
+        assert_eq!(max_params(DatabaseBackend::Postgres), 65_535);
+
+        let empty: Vec<String> = vec![];
+        assert_eq!(in_list_chunks(&empty, 10), vec![("NULL".to_string(), vec![])]);
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        for i in 0..3 {
+            client
+                .user()
+                .create(format!("chunk{}@example.com", i), "Chunk".to_string(), created, created, vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let mut emails: Vec<String> = (0..2500).map(|i| format!("missing{}@example.com", i)).collect();
+        emails.extend((0..3).map(|i| format!("chunk{}@example.com", i)));
+
+        let chunks = in_list_chunks(&emails, 1000);
+        assert_eq!(
+            chunks.iter().map(|(_, params)| params.len()).collect::<Vec<_>>(),
+            vec![1000, 1000, 503]
+        );
+        assert_eq!(chunks[2].0.matches('?').count(), 503);
+
+        #[derive(Debug, FromQueryResult)]
+        struct Cnt {
+            c: i64,
+        }
+        let mut found = 0;
+        for (placeholders, params) in chunks {
+            let rows: Vec<Cnt> = client
+                ._query_raw::<Cnt>(
+                    caustics::raw!(
+                        "SELECT COUNT(*) as c FROM users WHERE email IN ({})",
+                        caustics::raw::Inline(placeholders)
+                    )
+                    .with_params(params),
+                )
+                .exec()
+                .await
+                .unwrap();
+            found += rows[0].c;
+        }
+        assert_eq!(found, 3);
+    }
+
+    #[tokio::test]
+    async fn test_raw_ident_uses_client_backend() {
+        use sea_orm::FromQueryResult;
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+
+        #[derive(Debug, FromQueryResult)]
+        struct Cnt {
+            c: i64,
+        }
+        let rows: Vec<Cnt> = client
+            ._query_raw::<Cnt>(caustics::raw!(
+                "SELECT COUNT(*) as c FROM {}",
+                caustics::ident!(client.database_backend(), "users")
+            ))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(rows[0].c, 0);
+    }
+
+    #[tokio::test]
+    async fn test_hooks_observe_transaction_boundaries() {
+        use caustics::hooks::TxEvent;
+        use std::sync::{Arc, Mutex};
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+
+        struct TxRecorder {
+            events: Mutex<Vec<TxEvent>>,
+            query_details: Mutex<Vec<Option<String>>>,
+        }
+        impl caustics::hooks::QueryHook for TxRecorder {
+            fn before(&self, e: &caustics::hooks::QueryEvent) {
+                self.query_details.lock().unwrap().push(e.details.clone());
+            }
+            fn on_transaction(&self, event: &TxEvent) {
+                self.events.lock().unwrap().push(event.clone());
+            }
+        }
+        let recorder = Arc::new(TxRecorder {
+            events: Mutex::new(Vec::new()),
+            query_details: Mutex::new(Vec::new()),
+        });
+        // Thread-scoped so transactions from tests running in parallel are not recorded
+        caustics::hooks::set_thread_hook(Some(recorder.clone()));
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let result: Result<(), _> = client
+            .transaction()
+            .run(|tx| async move {
+                tx.user()
+                    .create("tx_hook@example.com".to_string(), "Tx".to_string(), created, created, vec![])
+                    .exec()
+                    .await?;
+                tx.user().find_many(vec![]).exec().await?;
+                Err(sea_orm::DbErr::Custom("abort".to_string()))
+            })
+            .await;
+        assert!(result.is_err());
+
+        client
+            .transaction()
+            .run(|tx| async move { tx.user().count(vec![]).exec().await })
+            .await
+            .unwrap();
+        caustics::hooks::set_thread_hook(None);
+
+        let events = recorder.events.lock().unwrap().clone();
+        assert_eq!(events.len(), 4, "{:?}", events);
+        let rollback_id = match (&events[0], &events[1]) {
+            (TxEvent::Begin { correlation_id: begin }, TxEvent::Rollback { correlation_id, error, .. }) => {
+                assert_eq!(begin, correlation_id);
+                assert!(error.contains("abort"));
+                correlation_id.clone().unwrap()
+            }
+            other => panic!("expected begin + rollback, got {:?}", other),
+        };
+        match (&events[2], &events[3]) {
+            (TxEvent::Begin { correlation_id: begin }, TxEvent::Commit { correlation_id, .. }) => {
+                assert_eq!(begin, correlation_id);
+                assert_ne!(correlation_id.as_deref(), Some(rollback_id.as_str()));
+            }
+            other => panic!("expected begin + commit, got {:?}", other),
+        }
+        // Queries inside the transaction carry its correlation id
+        assert!(recorder
+            .query_details
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|d| d.as_deref().is_some_and(|d| d.contains(&rollback_id))));
+        // The transaction's own id does not outlive it
+        assert!(caustics::hooks::current_correlation_id().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_single_query_batch_skips_transaction() {
+        use caustics::hooks::TxEvent;
+        use std::sync::{Arc, Mutex};
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+
+        struct TxRecorder(Mutex<Vec<TxEvent>>);
+        impl caustics::hooks::QueryHook for TxRecorder {
+            fn on_transaction(&self, event: &TxEvent) {
+                self.0.lock().unwrap().push(event.clone());
+            }
+        }
+        let recorder = Arc::new(TxRecorder(Mutex::new(Vec::new())));
+        caustics::hooks::set_thread_hook(Some(recorder.clone()));
+
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let (single,) = client
+            ._batch((client.user().create(
+                "batch_one@example.com".to_string(),
+                "One".to_string(),
+                now,
+                now,
+                vec![],
+            ),))
+            .await
+            .unwrap();
+        assert_eq!(single.name, "One");
+        assert!(recorder.0.lock().unwrap().is_empty());
+
+        let (first, second) = client
+            ._batch((
+                client.user().create("batch_two@example.com".to_string(), "Two".to_string(), now, now, vec![]),
+                client.user().create("batch_three@example.com".to_string(), "Three".to_string(), now, now, vec![]),
+            ))
+            .await
+            .unwrap();
+        caustics::hooks::set_thread_hook(None);
+        assert_eq!((first.name.as_str(), second.name.as_str()), ("Two", "Three"));
+
+        let events = std::mem::take(&mut *recorder.0.lock().unwrap());
+        assert_eq!(events.len(), 2, "{:?}", events);
+        assert!(matches!(events[0], TxEvent::Begin { .. }));
+        assert!(matches!(events[1], TxEvent::Commit { .. }));
+
+        // A nested create writes more than once, so even alone it runs in a transaction
+        caustics::hooks::set_thread_hook(Some(recorder.clone()));
+        let (nested,) = client
+            ._batch((client.user().create(
+                "batch_nested@example.com".to_string(),
+                "Nested".to_string(),
+                now,
+                now,
+                vec![user::posts::create(vec![post::Create {
+                    title: "Nested post".to_string(),
+                    created_at: now,
+                    updated_at: now,
+                    user: user::email::equals("batch_nested@example.com".to_string()),
+                    _params: vec![],
+                }])],
+            ),))
+            .await
+            .unwrap();
+        // So does an update, which reads the row before writing it
+        let (renamed,) = client
+            ._batch((client.user().update(user::id::equals(single.id), vec![user::name::set("Uno")]),))
+            .await
+            .unwrap();
+        caustics::hooks::set_thread_hook(None);
+        assert_eq!(renamed.name, "Uno");
+        let posts = client.post().find_many(vec![post::user_id::equals(nested.id)]).exec().await.unwrap();
+        assert_eq!(posts.len(), 1);
+
+        let events = recorder.0.lock().unwrap().clone();
+        assert_eq!(events.len(), 4, "{:?}", events);
+        assert!(matches!(events[0], TxEvent::Begin { .. }));
+        assert!(matches!(events[1], TxEvent::Commit { .. }));
+        assert!(matches!(events[2], TxEvent::Begin { .. }));
+        assert!(matches!(events[3], TxEvent::Commit { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_hook_details_describe_filter_without_values() {
+        use std::sync::{Arc, Mutex};
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+
+        struct DetailsRecorder(Mutex<Vec<Option<String>>>);
+        impl caustics::hooks::QueryHook for DetailsRecorder {
+            fn before(&self, e: &caustics::hooks::QueryEvent) {
+                self.0.lock().unwrap().push(e.details.clone());
+            }
+        }
+        let recorder = Arc::new(DetailsRecorder(Mutex::new(Vec::new())));
+        caustics::hooks::set_thread_hook(Some(recorder.clone()));
+        client
+            .user()
+            .find_many(vec![
+                user::age::gte(Some(42)),
+                user::email::contains("secret-domain"),
+            ])
+            .order_by(user::name::order(SortOrder::Asc))
+            .take(5)
+            .exec()
+            .await
+            .unwrap();
+        client.user().find_many(vec![]).exec().await.unwrap();
+        caustics::hooks::set_thread_hook(None);
+
+        let details = recorder.0.lock().unwrap().clone();
+        assert_eq!(details.len(), 2, "{:?}", details);
+        let filtered = details[0].clone().unwrap();
+        assert!(filtered.contains("op=select_many"), "{}", filtered);
+        assert!(filtered.contains("filter=("), "{}", filtered);
+        assert!(filtered.contains("age >= ?"), "{}", filtered);
+        assert!(filtered.contains("email LIKE ?"), "{}", filtered);
+        assert!(!filtered.contains("42") && !filtered.contains("secret-domain"), "{}", filtered);
+        // Neither the ordering nor the limit leak into the filter summary
+        assert!(!filtered.contains("ORDER BY") && !filtered.contains("LIMIT"), "{}", filtered);
+        // No WHERE clause, no filter summary
+        assert!(!details[1].clone().unwrap().contains("filter="), "{:?}", details[1]);
+    }
+
+    #[tokio::test]
+    async fn test_raw_sql_query_and_execute() {
+        use sea_orm::FromQueryResult;
+        use std::sync::{Arc, Mutex};
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        #[derive(Debug, FromQueryResult)]
+        struct OneRow {
+            value: i32,
+        }
+
+        // Install a temporary hook to assert emissions
+        struct TestHook {
+            hits: Arc<Mutex<usize>>,
+        }
+        impl caustics::hooks::QueryHook for TestHook {
+            fn before(&self, _e: &caustics::hooks::QueryEvent) {
+                *self.hits.lock().unwrap() += 1;
+            }
+            fn after(
+                &self,
+                _e: &caustics::hooks::QueryEvent,
+                _m: &caustics::hooks::QueryResultMeta,
+            ) {
+                *self.hits.lock().unwrap() += 1;
+            }
+        }
+        let hits = Arc::new(Mutex::new(0usize));
+        caustics::hooks::set_query_hook(Some(Arc::new(TestHook { hits: hits.clone() })));
+
+        let rows: Vec<OneRow> = client
+            ._query_raw::<OneRow>(caustics::raw!("SELECT 1 as value"))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].value, 1);
+
+        // Create a small row to affect count
+        let user = client
+            .user()
+            .create(
+                format!("raw_{}@example.com", chrono::Utc::now().timestamp()),
+                "Raw User".to_string(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+        // Uuid is always valid, no need to check > 0
+
+        // Another typed query
+        #[derive(Debug, FromQueryResult)]
+        struct Cnt {
+            c: i64,
+        }
+        let rows: Vec<Cnt> = client
+            ._query_raw::<Cnt>(caustics::raw!("SELECT {} as c", 42))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].c, 42);
+
+        // Execute raw (no result set)
+        // Execute DDL via raw
+        let _res = client
+            ._execute_raw(caustics::raw!(
+                "CREATE TEMP TABLE {} (id int)",
+                caustics::ident!("__raw_tmp")
+            ))
+            .exec()
+            .await
+            .unwrap();
+        let _res = client
+            ._execute_raw(caustics::raw!(
+                "DROP TABLE {}",
+                caustics::ident!("__raw_tmp")
+            ))
+            .exec()
+            .await
+            .unwrap();
+
+        // Advanced: IN list and JSON binding
+        #[derive(Debug, FromQueryResult)]
+        struct U {
+            id: Uuid,
+            name: String,
+        }
+        let users: Vec<U> = client
+            ._query_raw::<U>(caustics::raw!(
+                "SELECT id, name FROM {} WHERE id = {} ORDER BY id",
+                caustics::ident!("users"),
+                user.id
+            ))
+            .exec()
+            .await
+            .unwrap();
+        assert!(!users.is_empty());
+        assert!(users.first().is_some_and(|u| u.id == user.id));
+        assert!(users.first().is_some_and(|u| u.name == "Raw User"));
+
+        // Injection protection: user-provided string is bound, not inlined
+        let evil = "1); DROP TABLE User; --".to_string();
+        let rows: Vec<Cnt> = client
+            ._query_raw::<Cnt>(caustics::raw!(
+                "SELECT COUNT(*) as c FROM {} WHERE name = {}",
+                caustics::ident!("users"),
+                evil
+            ))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+
+        // Multiple bound params of different types
+        #[derive(Debug, FromQueryResult)]
+        struct Multi {
+            s: String,
+            n: i32,
+        }
+        let rows: Vec<Multi> = client
+            ._query_raw::<Multi>(caustics::raw!("SELECT {} as s, {} as n", "hello", 7))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(rows[0].s, "hello");
+        assert_eq!(rows[0].n, 7);
+
+        // Hooks should have recorded events
+        caustics::hooks::set_query_hook(None);
+        assert!(*hits.lock().unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        client.health_check().await.unwrap();
+
+        // Once the pool is closed the check reports the failure instead of panicking
+        db.close().await.unwrap();
+        assert!(client.health_check().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout() {
+        use sea_orm::FromQueryResult;
+        use std::time::Duration;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        client
+            .user()
+            .create(
+                "timeout@example.com".to_string(),
+                "Timeout".to_string(),
+                created,
+                created,
+                vec![],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_db_null.len(), 1);
-        assert_eq!(posts_db_null[0].id, post_without_json.id);
 
-        // JSON null (value is JSON null)
-        let posts_json_null = client
-            .post()
-            .find_many(vec![post::custom_data::json_null()])
+        // Queries that finish in time behave exactly like a plain exec
+        let users = client
+            .user()
+            .find_many(vec![])
+            .timeout(Duration::from_secs(5))
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_json_null.len(), 1);
-        assert_eq!(posts_json_null[0].id, post_with_json_null.id);
-
-        // Any null (DB NULL or JSON null)
-        let posts_any_null = client
-            .post()
-            .find_many(vec![post::custom_data::any_null()])
+        assert_eq!(users.len(), 1);
+        let count = client
+            .user()
+            .count(vec![])
+            .timeout(Duration::from_secs(5))
             .exec()
             .await
             .unwrap();
-        let any_ids: std::collections::HashSet<_> = posts_any_null.iter().map(|p| p.id).collect();
-        assert!(any_ids.contains(&post_without_json.id));
-        assert!(any_ids.contains(&post_with_json_null.id));
+        assert_eq!(count, 1);
 
-        let posts_with_custom_data = client
-            .post()
-            .find_many(vec![post::custom_data::is_not_null()])
+        // A long-running query is abandoned once the deadline passes
+        #[derive(Debug, FromQueryResult)]
+        struct Cnt {
+            #[allow(dead_code)]
+            c: i64,
+        }
+        let err = client
+            ._query_raw::<Cnt>(caustics::raw!(
+                "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 50000000) SELECT COUNT(*) as c FROM n"
+            ))
+            .timeout(Duration::from_millis(50))
             .exec()
             .await
-            .unwrap();
-        // Includes posts with JSON values (including JSON null), excludes DB NULL
-        assert_eq!(posts_with_custom_data.len(), 4);
+            .unwrap_err();
+        assert!(err.to_string().contains("CausticsError::Timeout"));
+    }
 
-        // Test JSON path access - simple key
-        let posts_with_category_key = client
-            .post()
-            .find_many(vec![post::custom_data::path(vec!["category".to_string()])])
+    #[tokio::test]
+    async fn test_scoped_client_isolates_tenants() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let acme = client.scoped("tenant_id", 1);
+        let globex = client.scoped("tenant_id", 2);
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        let alice = acme
+            .user()
+            .create("alice@acme.com".to_string(), "Alice".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_with_category_key.len(), 1);
-        assert_eq!(posts_with_category_key[0].id, post_with_simple_json.id);
-
-        // Test JSON object contains key operations
-        let posts_with_metadata_key = client
-            .post()
-            .find_many(vec![post::custom_data::json_object_contains(
-                "metadata".to_string(),
-            )])
+        let bob = globex
+            .user()
+            .create("bob@globex.com".to_string(), "Bob".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_with_metadata_key.len(), 1);
-        assert_eq!(posts_with_metadata_key[0].id, post_with_simple_json.id);
 
-        let posts_with_settings_key = client
-            .post()
-            .find_many(vec![post::custom_data::json_object_contains(
-                "settings".to_string(),
-            )])
+        // Creates are stamped with the tenant, overriding any value passed in
+        assert_eq!(alice.tenant_id, Some(1));
+        assert_eq!(bob.tenant_id, Some(2));
+        let carol = acme
+            .user()
+            .create(
+                "carol@acme.com".to_string(),
+                "Carol".to_string(),
+                created,
+                created,
+                vec![user::tenant_id::set(Some(2))],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_with_settings_key.len(), 1);
-        assert_eq!(posts_with_settings_key[0].id, post_with_array_json.id);
+        assert_eq!(carol.tenant_id, Some(1));
 
-        let posts_with_description_key = client
-            .post()
-            .find_many(vec![post::custom_data::json_object_contains(
-                "description".to_string(),
-            )])
+        // Cross-tenant reads are invisible
+        let acme_users = acme.user().find_many(vec![]).exec().await.unwrap();
+        let mut names: Vec<_> = acme_users.iter().map(|u| u.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Alice", "Carol"]);
+        assert!(acme.user().find_unique(user::id::equals(bob.id)).exec().await.unwrap().is_none());
+        assert!(acme
+            .user()
+            .find_first(vec![user::email::equals("bob@globex.com".to_string())])
             .exec()
             .await
-            .unwrap();
-        assert_eq!(posts_with_description_key.len(), 1);
-        assert_eq!(posts_with_description_key[0].id, post_with_string_json.id);
+            .unwrap()
+            .is_none());
+        assert_eq!(acme.user().count(vec![]).exec().await.unwrap(), 2);
+        assert_eq!(globex.user().count(vec![]).exec().await.unwrap(), 1);
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 3);
 
-        // Test JSON string contains operations
-        let posts_with_rust_anywhere = client
-            .post()
-            .find_many(vec![post::custom_data::json_string_contains(
-                "rust".to_string(),
-            )])
+        // Writes cannot reach another tenant's rows
+        assert!(acme
+            .user()
+            .update(user::id::equals(bob.id), vec![user::name::set("Hijacked".to_string())])
             .exec()
             .await
-            .unwrap();
-        assert_eq!(posts_with_rust_anywhere.len(), 1);
-        assert_eq!(posts_with_rust_anywhere[0].id, post_with_simple_json.id);
-
-        let posts_with_guide_description = client
-            .post()
-            .find_many(vec![post::custom_data::json_string_contains(
-                "comprehensive guide".to_string(),
-            )])
+            .is_err());
+        acme.user()
+            .update_many(vec![], vec![user::age::set(Some(30))])
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_with_guide_description.len(), 1);
-        assert_eq!(posts_with_guide_description[0].id, post_with_string_json.id);
+        let bob_after = client.user().find_unique(user::id::equals(bob.id)).exec().await.unwrap().unwrap();
+        assert_eq!(bob_after.name, "Bob");
+        assert_eq!(bob_after.age, None);
+        assert!(acme.user().delete(user::id::equals(bob.id)).exec().await.is_err());
+        acme.user().delete_many(vec![]).exec().await.unwrap();
+        let remaining = client.user().find_many(vec![]).exec().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, bob.id);
 
-        // Test JSON operations with logical operators (AND)
-        let posts_with_category_and_metadata = client
+        // Entities without the scope field are unaffected
+        globex
             .post()
-            .find_many(vec![operator::and(vec![
-                post::custom_data::json_object_contains("category".to_string()),
-                post::custom_data::json_object_contains("metadata".to_string()),
-            ])])
+            .create(
+                "Globex news".to_string(),
+                created,
+                created,
+                user::id::equals(bob.id),
+                vec![],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_with_category_and_metadata.len(), 1);
-        assert_eq!(
-            posts_with_category_and_metadata[0].id,
-            post_with_simple_json.id
-        );
+        assert_eq!(acme.post().count(vec![]).exec().await.unwrap(), 1);
 
-        // Test JSON operations with logical operators (OR)
-        let posts_with_description_or_settings = client
-            .post()
-            .find_many(vec![operator::or(vec![
-                post::custom_data::json_object_contains("description".to_string()),
-                post::custom_data::json_object_contains("settings".to_string()),
-            ])])
+        // Includes only load related rows inside the scope, at every nesting level
+        let dave = acme
+            .user()
+            .create("dave@acme.com".to_string(), "Dave".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-        assert_eq!(posts_with_description_or_settings.len(), 2);
-        let found_ids: Vec<Uuid> = posts_with_description_or_settings
-            .iter()
-            .map(|p| p.id)
-            .collect();
-        assert!(found_ids.contains(&post_with_string_json.id));
-        assert!(found_ids.contains(&post_with_array_json.id));
-
-        // Test JSON operations with NOT operator
-        let posts_without_metadata = client
+        client
             .post()
-            .find_many(vec![post::not(vec![
-                post::custom_data::json_object_contains("metadata".to_string()),
-            ])])
+            .update_many(vec![], vec![post::reviewer_user_id::set(Some(dave.id))])
             .exec()
             .await
             .unwrap();
-        // Should find posts without JSON data and posts without metadata key
-        assert!(posts_without_metadata.len() >= 3);
-        let found_ids: Vec<Uuid> = posts_without_metadata.iter().map(|p| p.id).collect();
-        assert!(found_ids.contains(&post_without_json.id));
-        assert!(found_ids.contains(&post_with_array_json.id));
-        assert!(found_ids.contains(&post_with_string_json.id));
-        assert!(!found_ids.contains(&post_with_simple_json.id));
-    }
-
-    #[tokio::test]
-    async fn test_atomic_operations() {
-        use chrono::TimeZone;
-        let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
-
-        // Create a user with an age to test atomic operations
-        let user = client
-            .user()
-            .create(
-                "atomic@example.com".to_string(),
-                "Atomic User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
-            )
+        let posts = globex
+            .post()
+            .find_many(vec![])
+            .with(post::user::fetch())
+            .with(post::reviewer::fetch())
             .exec()
             .await
             .unwrap();
-
-        assert_eq!(user.age, Some(25));
-
-        // Test increment operation
-
-        let updated_user = client
-            .user()
-            .update(user::id::equals(user.id), vec![user::age::increment(5)])
+        assert_eq!(posts[0].user.as_ref().map(|u| u.id), Some(bob.id));
+        assert!(posts[0].reviewer.as_ref().is_none_or(|reviewer| reviewer.is_none()));
+        let unscoped = client
+            .post()
+            .find_many(vec![])
+            .with(post::reviewer::fetch())
             .exec()
             .await
             .unwrap();
-        assert_eq!(updated_user.age, Some(30));
-
-        // Test decrement operation
-        let updated_user = client
+        assert_eq!(unscoped[0].reviewer.clone().flatten().map(|u| u.id), Some(dave.id));
+        let bob_with_posts = globex
             .user()
-            .update(user::id::equals(user.id), vec![user::age::decrement(3)])
+            .find_unique(user::id::equals(bob.id))
+            .with(user::posts::include(|posts| posts.with(post::reviewer::fetch())))
             .exec()
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(updated_user.age, Some(27));
+        let bob_posts = bob_with_posts.posts.unwrap();
+        assert_eq!(bob_posts.len(), 1);
+        assert!(bob_posts[0].reviewer.as_ref().is_none_or(|reviewer| reviewer.is_none()));
 
-        // Test multiply operation
-        let updated_user = client
-            .user()
-            .update(user::id::equals(user.id), vec![user::age::multiply(2)])
-            .exec()
+        // Transactions started from a scoped client stay scoped
+        let visible = globex
+            .transaction()
+            .run(|tx| async move { tx.user().count(vec![]).exec().await })
             .await
             .unwrap();
-        assert_eq!(updated_user.age, Some(54));
+        assert_eq!(visible, 1);
+    }
 
-        // Test divide operation
-        let updated_user = client
+    #[tokio::test]
+    async fn test_read_replica_routing() {
+        let primary = setup_test_db().await;
+        let replica_a = setup_test_db().await;
+        let replica_b = setup_test_db().await;
+
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+        // Seed each database with a different user so reads reveal where they ran
+        for (db, name) in [(&primary, "Primary"), (&replica_a, "ReplicaA"), (&replica_b, "ReplicaB")] {
+            blog::CausticsClient::new(db.clone())
+                .user()
+                .create(
+                    format!("{}@example.com", name.to_lowercase()),
+                    name.to_string(),
+                    created,
+                    created,
+                    vec![],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let client = blog::CausticsClient::with_replicas(
+            primary.clone(),
+            vec![replica_a.clone(), replica_b.clone()],
+        )
+        .unwrap();
+
+        // Reads alternate between the replicas and never touch the primary
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            let users = client.user().find_many(vec![]).exec().await.unwrap();
+            assert_eq!(users.len(), 1);
+            seen.push(users[0].name.clone());
+        }
+        assert_eq!(seen, vec!["ReplicaA", "ReplicaB", "ReplicaA", "ReplicaB"]);
+        let found = client
             .user()
-            .update(user::id::equals(user.id), vec![user::age::divide(3)])
+            .find_first(vec![user::name::equals("Primary")])
             .exec()
             .await
             .unwrap();
-        assert_eq!(updated_user.age, Some(18));
+        assert!(found.is_none());
 
-        // Test multiple atomic operations in one update
-        let updated_user = client
+        // Writes land on the primary only
+        client
             .user()
-            .update(
-                user::id::equals(user.id),
-                vec![user::age::increment(10), user::age::multiply(2)],
+            .create(
+                "written@example.com".to_string(),
+                "Written".to_string(),
+                created,
+                created,
+                vec![],
             )
             .exec()
             .await
             .unwrap();
-        // Should be (18 + 10) * 2 = 56
-        assert_eq!(updated_user.age, Some(56));
+        let primary_client = blog::CausticsClient::new(primary.clone());
+        assert_eq!(primary_client.user().count(vec![]).exec().await.unwrap(), 2);
+        for replica in [&replica_a, &replica_b] {
+            let replica_client = blog::CausticsClient::new(replica.clone());
+            assert_eq!(replica_client.user().count(vec![]).exec().await.unwrap(), 1);
+        }
+        assert_eq!(client.user().count(vec![]).exec().await.unwrap(), 1);
     }
 
     #[tokio::test]
-    async fn test_atomic_operations_simple() {
-        use chrono::TimeZone;
+    async fn test_has_many_set_operation_structure() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+
+        // Create a user
+        let user = client
+            .user()
+            .create(
+                "user@example.com".to_string(),
+                "User".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+
+        // Create some posts
+        let post1 = client
+            .post()
+            .create(
+                "Post 1".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                user::id::equals(user.id),
+                vec![],
+            )
+            .exec()
+            .await
             .unwrap();
 
-        // Create a user with an age to test atomic operations
-        let user = client
-            .user()
+        let post2 = client
+            .post()
             .create(
-                "atomic@example.com".to_string(),
-                "Atomic User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
+                "Post 2".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                user::id::equals(user.id),
+                vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        assert_eq!(user.age, Some(25));
-
-        // Test that atomic operations exist and can be called
-        let _increment_op = user::age::increment(5);
-        let _decrement_op = user::age::decrement(3);
-        let _multiply_op = user::age::multiply(2);
-        let _divide_op = user::age::divide(3);
+        // Test that the has_many set operation structure compiles and runs
+        // This should work even though the actual set operation is not implemented yet
 
-        // Test increment operation
         let updated_user = client
             .user()
-            .update(user::id::equals(user.id), vec![user::age::increment(5)])
+            .update(
+                user::id::equals(user.id),
+                vec![user::posts::set(vec![
+                    post::id::equals(post1.id),
+                    post::id::equals(post2.id),
+                ])],
+            )
             .exec()
-            .await
-            .unwrap();
+            .await;
 
-        // The atomic operation should work
-        assert_eq!(updated_user.age, Some(30));
+        // Has_many set is executed transactionally and returns updated user
+        assert!(updated_user.is_ok());
     }
 
     #[tokio::test]
-    async fn test_advanced_relation_operations() {
-        let _ = env_logger::try_init();
-        use chrono::TimeZone;
+    async fn test_has_many_set_operation_functionality() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
 
-        // Create a user with some posts
+        // Create a user
         let user = client
             .user()
             .create(
-                "relation@example.com".to_string(),
-                "Relation User".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+                "user@example.com".to_string(),
+                "User".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        // Create some posts for the user
-        let _post1 = client
+        // Create some posts initially not associated with the user
+        let post1 = client
             .post()
             .create(
-                "First Post".to_string(),
-                now,
-                now,
-                user::id::equals(user.id),
-                vec![post::content::set(Some("First post content".to_string()))],
+                "Post 1".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                user::id::equals(user.id), // Initially associated
+                vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        let _post2 = client
+        let post2 = client
             .post()
             .create(
-                "Second Post".to_string(),
-                now,
-                now,
-                user::id::equals(user.id),
-                vec![post::content::set(Some("Second post content".to_string()))],
+                "Post 2".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                user::id::equals(user.id), // Initially associated
+                vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        // Test that the advanced relation operations exist and can be called
-        let _some_condition =
-            user::posts::some(vec![post::title::equals("First Post".to_string())]);
-
-        let _every_condition = user::posts::every(vec![post::title::contains("Post".to_string())]);
-
-        let _none_condition =
-            user::posts::none(vec![post::title::equals("Non-existent Post".to_string())]);
-
-        // Test that the conditions can be used in queries (they should work now!)
-        let users_with_some_posts = client
-            .user()
-            .find_many(vec![user::posts::some(vec![post::title::equals(
-                "First Post".to_string(),
-            )])])
+        let _post3 = client
+            .post()
+            .create(
+                "Post 3".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                user::id::equals(user.id), // Initially associated
+                vec![],
+            )
             .exec()
             .await
             .unwrap();
 
-        // The query should return the user since they have a post with title "First Post"
-        assert_eq!(users_with_some_posts.len(), 1);
-        assert_eq!(users_with_some_posts[0].id, user.id);
-
-        // Test every condition
-        let users_with_every_post_containing_post = client
-            .user()
-            .find_many(vec![user::posts::every(vec![post::title::contains(
-                "Post".to_string(),
-            )])])
+        // Verify initial state
+        let initial_posts = client
+            .post()
+            .find_many(vec![post::user_id::equals(user.id)])
             .exec()
             .await
             .unwrap();
+        assert_eq!(initial_posts.len(), 3);
 
-        // The query should return the user since all their posts contain "Post"
-        assert_eq!(users_with_every_post_containing_post.len(), 1);
-        assert_eq!(users_with_every_post_containing_post[0].id, user.id);
-
-        // Test none condition
-        let users_with_no_nonexistent_posts = client
+        // Test the has_many set operation
+        // This should set the user's posts to only post1 and post2
+        let updated_user = client
             .user()
-            .find_many(vec![user::posts::none(vec![post::title::equals(
-                "Non-existent Post".to_string(),
-            )])])
+            .update(
+                user::id::equals(user.id),
+                vec![user::posts::set(vec![
+                    post::id::equals(post1.id),
+                    post::id::equals(post2.id),
+                ])],
+            )
+            .exec()
+            .await;
+
+        if let Err(_e) = &updated_user {}
+        assert!(updated_user.is_ok());
+
+        // Verify the result
+        let final_posts = client
+            .post()
+            .find_many(vec![post::user_id::equals(user.id)])
             .exec()
             .await
             .unwrap();
 
-        // The query should return the user since they don't have a post with title "Non-existent Post"
-        assert_eq!(users_with_no_nonexistent_posts.len(), 1);
-        assert_eq!(users_with_no_nonexistent_posts[0].id, user.id);
+        // Now we expect exactly 2 posts since the set operation should replace all associations
+        // The set operation should have removed post3 and kept only post1 and post2
+        assert_eq!(final_posts.len(), 2);
+
+        // Verify that only post1 and post2 are associated with the user
+        let final_post_ids: Vec<Uuid> = final_posts.iter().map(|p| p.id).collect();
+        assert!(final_post_ids.contains(&post1.id));
+        assert!(final_post_ids.contains(&post2.id));
+        assert!(!final_post_ids.contains(&_post3.id));
     }
 
     #[tokio::test]
-    async fn test_complex_relation_filtering_with_subqueries() {
-        let _ = env_logger::try_init();
-        use chrono::TimeZone;
+    async fn test_agnostic_implementation_compiles() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
-        let future_date = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 12, 31, 23, 59, 59)
-            .unwrap();
-
-        // Create multiple users with different post patterns
-        let user1 = client
-            .user()
-            .create(
-                "user1@example.com".to_string(),
-                "User One".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(25)), user::deleted_at::set(None)],
-            )
-            .exec()
-            .await
-            .unwrap();
-
-        let user2 = client
-            .user()
-            .create(
-                "user2@example.com".to_string(),
-                "User Two".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
-            )
-            .exec()
-            .await
-            .unwrap();
 
-        let user3 = client
+        // Create a user
+        let user = client
             .user()
             .create(
-                "user3@example.com".to_string(),
-                "User Three".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(35)), user::deleted_at::set(None)],
+                "test@example.com".to_string(),
+                "Test User".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        // Create posts for user1: Has "Hello" posts with content, all created in 2024
-        let _post1_1 = client
+        // Create a post with a different user to avoid conflicts
+        let post = client
             .post()
             .create(
-                "Hello World".to_string(),
-                now,
-                now,
-                user::id::equals(user1.id),
-                vec![post::content::set(Some("Hello post content".to_string()))],
+                "Test Post".to_string(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                chrono::FixedOffset::east_opt(0)
+                    .unwrap()
+                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
+                    .unwrap(),
+                user::id::equals(user.id),
+                vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        let _post1_2 = client
-            .post()
-            .create(
-                "Hello Again".to_string(),
-                now,
-                now,
-                user::id::equals(user1.id),
-                vec![post::content::set(Some("Another hello post".to_string()))],
+        // Test that the agnostic implementation compiles and runs
+        // This should work with any relation name, not just hardcoded ones
+        let result = client
+            .user()
+            .update(
+                user::id::equals(user.id),
+                vec![user::posts::set(vec![post::id::equals(post.id)])],
             )
             .exec()
-            .await
-            .unwrap();
+            .await;
 
-        // Create posts for user2: Has "Hello" posts but some without content, all created in 2024
-        let _post2_1 = client
-            .post()
-            .create(
-                "Hello from User2".to_string(),
-                now,
-                now,
-                user::id::equals(user2.id),
-                vec![post::content::set(Some("User2 hello content".to_string()))],
-            )
+        // Verify the update succeeded and the record matches
+        assert!(
+            result.is_ok(),
+            "agnostic has_many set update failed: {:?}",
+            result
+        );
+        let updated = result.unwrap();
+        assert_eq!(updated.id, user.id);
+
+        // Verify via relation fetch that exactly the specified post is connected
+        let user_with_posts = client
+            .user()
+            .find_unique(user::id::equals(user.id))
+            .with(user::posts::fetch(vec![]))
             .exec()
             .await
+            .unwrap()
             .unwrap();
+        let posts = user_with_posts.posts.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, post.id);
+    }
 
-        let _post2_2 = client
-            .post()
-            .create(
-                "Hello without content".to_string(),
-                now,
-                now,
-                user::id::equals(user2.id),
-                vec![post::content::set(None)], // No content
-            )
-            .exec()
-            .await
+    #[tokio::test]
+    #[cfg(feature = "select")]
+    async fn test_aggregate_and_group_by_smoke() {
+        use blog::entities::user::{AggregateAggExt, GroupByHavingAggExt};
+        use chrono::TimeZone;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
             .unwrap();
 
-        // Create posts for user3: Has "Hello" posts but some created in future, some spam
-        let _post3_1 = client
-            .post()
+        // Seed
+        let _u1 = client
+            .user()
             .create(
-                "Hello from User3".to_string(),
+                "agg1@example.com".to_string(),
+                "Agg1".to_string(),
                 now,
                 now,
-                user::id::equals(user3.id),
-                vec![post::content::set(Some("User3 hello content".to_string()))],
-            )
-            .exec()
-            .await
-            .unwrap();
-
-        let _post3_2 = client
-            .post()
-            .create(
-                "Future Hello".to_string(),
-                future_date,
-                future_date,
-                user::id::equals(user3.id),
-                vec![post::content::set(Some("Future hello content".to_string()))],
+                vec![user::age::set(Some(10)), user::deleted_at::set(None)],
             )
             .exec()
             .await
             .unwrap();
-
-        let _post3_3 = client
-            .post()
+        let _u2 = client
+            .user()
             .create(
-                "Spam Post".to_string(),
+                "agg2@example.com".to_string(),
+                "Agg2".to_string(),
                 now,
                 now,
-                user::id::equals(user3.id),
-                vec![post::content::set(Some("Spam content".to_string()))],
+                vec![user::age::set(Some(20)), user::deleted_at::set(None)],
             )
             .exec()
             .await
             .unwrap();
 
-        // Test 1: Complex relation filtering with multiple conditions
-        // Find users who:
-        // - Have SOME posts with "Hello" in title
-        // - Have EVERY post with "Hello" in title
-        // - Have NO posts with "Spam" in title
-        let complex_filtered_users = client
-            .user()
-            .find_many(vec![
-                user::posts::some(vec![post::title::contains("Hello".to_string())]),
-                user::posts::every(vec![post::title::contains("Hello".to_string())]),
-                user::posts::none(vec![post::title::contains("Spam".to_string())]),
-            ])
-            .exec()
-            .await
-            .unwrap();
-
-        // Should return user1 and user2, but not user3
-        // user1: ✅ has "Hello" posts, ✅ all posts have "Hello", ✅ no spam
-        // user2: ✅ has "Hello" posts, ✅ all posts have "Hello", ✅ no spam
-        // user3: ✅ has "Hello" posts, ❌ has "Spam" post, ❌ has spam
-        assert_eq!(complex_filtered_users.len(), 2);
-        let user_ids: Vec<Uuid> = complex_filtered_users.iter().map(|u| u.id).collect();
-        assert!(user_ids.contains(&user1.id));
-        assert!(user_ids.contains(&user2.id));
-        assert!(!user_ids.contains(&user3.id));
-
-        // Test 2: More specific filtering with different conditions
-        // Find users who have posts with "World" in title
-        let users_with_world = client
+        // Aggregate: count + typed aggregates
+        let agg = client
             .user()
-            .find_many(vec![user::posts::some(vec![post::title::contains(
-                "World".to_string(),
-            )])])
+            .aggregate(vec![])
+            .count()
+            .avg(user::select!(age), "age_avg")
+            .min(user::select!(age), "age_min")
+            .max(user::select!(age), "age_max")
             .exec()
             .await
             .unwrap();
+        assert_eq!(agg.count, Some(2));
+        assert!(agg.avg.contains_key("age_avg"));
+        assert!(agg.min.contains_key("age_min"));
+        assert!(agg.max.contains_key("age_max"));
 
-        // Should return user1 (has "Hello World" post)
-        assert_eq!(users_with_world.len(), 1);
-        assert_eq!(users_with_world[0].id, user1.id);
-
-        // Test 3: Every post must have "Hello" in title
-        let users_with_all_hello_posts = client
+        // Group by age with count
+        let rows = client
             .user()
-            .find_many(vec![user::posts::every(vec![post::title::contains(
-                "Hello".to_string(),
-            )])])
+            .group_by(
+                vec![user::GroupByFieldParam::Age],
+                vec![],
+                vec![],
+                None,
+                None,
+                None,
+            )
+            .count("cnt")
+            .sum(user::select!(age), "age_sum")
+            .having_sum_gte(user::select!(age), 20)
             .exec()
             .await
             .unwrap();
+        assert!(!rows.is_empty());
+        assert!(rows.len() <= 2);
+    }
 
-        // Should return user1 and user2, but not user3 (has "Spam Post")
-        assert_eq!(users_with_all_hello_posts.len(), 2);
-        let all_hello_user_ids: Vec<Uuid> =
-            users_with_all_hello_posts.iter().map(|u| u.id).collect();
-        assert!(all_hello_user_ids.contains(&user1.id));
-        assert!(all_hello_user_ids.contains(&user2.id));
-        assert!(!all_hello_user_ids.contains(&user3.id));
+    #[tokio::test]
+    #[cfg(feature = "select")]
+    async fn test_aggregate_typed_and_group_by_typed() {
+        use chrono::TimeZone;
+        // Bring typed aggregate extension traits into scope
+        use blog::entities::user::AggregateAggExt;
 
-        // Test 4: No spam posts
-        let users_without_spam_posts = client
-            .user()
-            .find_many(vec![user::posts::none(vec![post::title::contains(
-                "Spam".to_string(),
-            )])])
-            .exec()
-            .await
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 2, 0, 0, 0)
             .unwrap();
 
-        // Should return user1 and user2, but not user3 (has spam post)
-        assert_eq!(users_without_spam_posts.len(), 2);
-        let no_spam_user_ids: Vec<Uuid> = users_without_spam_posts.iter().map(|u| u.id).collect();
-        assert!(no_spam_user_ids.contains(&user1.id));
-        assert!(no_spam_user_ids.contains(&user2.id));
-        assert!(!no_spam_user_ids.contains(&user3.id));
-
-        // Test 5: Combined with logical operators
-        let combined_filtered_users = client
+        let _u1 = client
             .user()
-            .find_many(vec![operator::and(vec![
-                user::posts::some(vec![post::title::contains("Hello".to_string())]),
-                user::posts::none(vec![post::title::contains("Spam".to_string())]),
-            ])])
+            .create(
+                format!("t1-{}@example.com", now.timestamp()).to_string(),
+                "T1".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(10)), user::deleted_at::set(None)],
+            )
             .exec()
             .await
             .unwrap();
-
-        // Should return user1 and user2, but not user3
-        assert_eq!(combined_filtered_users.len(), 2);
-        let combined_user_ids: Vec<Uuid> = combined_filtered_users.iter().map(|u| u.id).collect();
-        assert!(combined_user_ids.contains(&user1.id));
-        assert!(combined_user_ids.contains(&user2.id));
-        assert!(!combined_user_ids.contains(&user3.id));
-
-        // Test 6: Edge case - user with no posts
-        let user_no_posts = client
+        let _u2 = client
             .user()
             .create(
-                "noposts@example.com".to_string(),
-                "No Posts User".to_string(),
+                format!("t2-{}@example.com", now.timestamp()).to_string(),
+                "T2".to_string(),
                 now,
                 now,
-                vec![user::age::set(Some(40)), user::deleted_at::set(None)],
+                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
             )
             .exec()
             .await
             .unwrap();
 
-        // User with no posts should match "none" conditions
-        let users_with_no_spam = client
+        // Aggregate typed
+        let agg_typed = client
             .user()
-            .find_many(vec![user::posts::none(vec![post::title::contains(
-                "Spam".to_string(),
-            )])])
+            .aggregate(vec![])
+            .count()
+            .avg(user::select!(age), "age_avg")
+            .min(user::select!(age), "age_min")
+            .max(user::select!(age), "age_max")
             .exec()
             .await
             .unwrap();
+        assert_eq!(agg_typed.count, Some(2));
+        assert!(agg_typed.avg.contains_key("age_avg"));
+        assert!(agg_typed.min.contains_key("age_min"));
+        assert!(agg_typed.max.contains_key("age_max"));
 
-        // Should include the user with no posts
-        let no_spam_user_ids_updated: Vec<Uuid> = users_with_no_spam.iter().map(|u| u.id).collect();
-        assert!(no_spam_user_ids_updated.contains(&user_no_posts.id));
-
-        // User with no posts should NOT match "some" conditions
-        let users_with_hello_final = client
+        // Group by typed
+        let rows = client
             .user()
-            .find_many(vec![user::posts::some(vec![post::title::contains(
-                "Hello".to_string(),
-            )])])
+            .group_by(
+                vec![user::GroupByFieldParam::Age],
+                vec![],
+                vec![],
+                None,
+                None,
+                None,
+            )
+            .count("cnt")
+            .sum(user::select!(age), "age_sum")
             .exec()
             .await
             .unwrap();
+        assert!(!rows.is_empty());
+        for row in rows {
+            assert!(row.keys.contains_key("Age"));
+            assert!(row.aggregates.contains_key("age_sum"));
+            assert!(row.aggregates.contains_key("cnt"));
+        }
+    }
 
-        // Should NOT include the user with no posts
-        let hello_user_ids: Vec<Uuid> = users_with_hello_final.iter().map(|u| u.id).collect();
-        assert!(!hello_user_ids.contains(&user_no_posts.id));
+    #[tokio::test]
+    async fn test_count_by_posts_per_user() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db);
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        // Test 7: Nullable field filtering in relations
-        // Find users who have posts with content (is_not_null)
-        let users_with_content_posts = client
-            .user()
-            .find_many(vec![user::posts::some(vec![post::content::is_not_null()])])
+        let mut users = Vec::new();
+        for (email, posts) in [("two@example.com", 2), ("three@example.com", 3), ("none@example.com", 0)] {
+            let user = client
+                .user()
+                .create(email.to_string(), "Author".to_string(), created, created, vec![])
+                .exec()
+                .await
+                .unwrap();
+            for i in 0..posts {
+                client
+                    .post()
+                    .create(
+                        format!("Post {}", i),
+                        created,
+                        created,
+                        user::id::equals(user.id),
+                        vec![post::reviewer_user_id::set(if i == 0 { Some(user.id) } else { None })],
+                    )
+                    .exec()
+                    .await
+                    .unwrap();
+            }
+            users.push((user.id, posts));
+        }
+
+        let counts: std::collections::HashMap<caustics::CausticsKey, i64> = client
+            .post()
+            .count_by(post::ScalarField::UserId, vec![])
             .exec()
             .await
-            .unwrap();
-
-        // Should return all 3 users because:
-        // user1: has 2 posts with content ✅
-        // user2: has 1 post with content (and 1 without) ✅
-        // user3: has 3 posts with content ✅
-        assert_eq!(users_with_content_posts.len(), 3);
-        let content_user_ids: Vec<Uuid> = users_with_content_posts.iter().map(|u| u.id).collect();
-        assert!(content_user_ids.contains(&user1.id));
-        assert!(content_user_ids.contains(&user2.id));
-        assert!(content_user_ids.contains(&user3.id));
+            .unwrap()
+            .into_iter()
+            .collect();
+        // Users without posts have no group
+        assert_eq!(counts.len(), 2);
+        for (id, posts) in users.iter().filter(|(_, posts)| *posts > 0) {
+            assert_eq!(counts[&caustics::CausticsKey::Uuid(*id)], *posts);
+        }
 
-        // Find users who have posts without content (is_null)
-        let users_with_null_content_posts = client
-            .user()
-            .find_many(vec![user::posts::some(vec![post::content::is_null()])])
+        // Filters apply before grouping
+        let filtered = client
+            .post()
+            .count_by(post::ScalarField::UserId, vec![post::title::equals("Post 2".to_string())])
             .exec()
             .await
             .unwrap();
+        assert_eq!(filtered, vec![(caustics::CausticsKey::Uuid(users[1].0), 1)]);
 
-        // Should return user2 (has post without content)
-        assert_eq!(users_with_null_content_posts.len(), 1);
-        assert_eq!(users_with_null_content_posts[0].id, user2.id);
-
-        // Test 8: Every post must have content
-        let users_with_all_content = client
-            .user()
-            .find_many(vec![user::posts::every(vec![post::content::is_not_null()])])
+        // NULL values are left out
+        let reviewed = client
+            .post()
+            .count_by(post::ScalarField::ReviewerUserId, vec![])
             .exec()
             .await
             .unwrap();
+        assert_eq!(reviewed.len(), 2);
+        assert!(reviewed.iter().all(|(_, count)| *count == 1));
+    }
 
-        // Should return user1, user3, and user4 (no posts - vacuous truth), but not user2 (has post without content)
-        assert_eq!(users_with_all_content.len(), 3);
-        let all_content_user_ids: Vec<Uuid> = users_with_all_content.iter().map(|u| u.id).collect();
-        assert!(all_content_user_ids.contains(&user1.id));
-        assert!(all_content_user_ids.contains(&user3.id));
-        assert!(all_content_user_ids.contains(&user_no_posts.id)); // user with no posts
-        assert!(!all_content_user_ids.contains(&user2.id)); // has post without content
+    #[test]
+    fn test_field_enums_sort_and_hash() {
+        use std::collections::{BTreeSet, HashMap};
+
+        // Ordered by field declaration, duplicates collapse
+        let fields: BTreeSet<user::ScalarField> = [
+            user::ScalarField::Age,
+            user::ScalarField::Email,
+            user::ScalarField::Age,
+            user::ScalarField::Id,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            fields.into_iter().collect::<Vec<_>>(),
+            vec![user::ScalarField::Id, user::ScalarField::Email, user::ScalarField::Age]
+        );
 
-        // Test 9: No posts without content
-        let users_with_no_null_content = client
+        let mut labels: HashMap<user::GroupByFieldParam, &str> = HashMap::new();
+        labels.insert(user::GroupByFieldParam::Name, "name");
+        labels.insert(user::GroupByFieldParam::Name, "display name");
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[&user::GroupByFieldParam::Name], "display name");
+        assert!(user::DistinctFieldParam::Id < user::DistinctFieldParam::Name);
+    }
+
+    #[tokio::test]
+    async fn test_group_by_exec_as_typed_struct() {
+        use caustics::typed_selection;
+        use chrono::TimeZone;
+
+        caustics_macros::select_struct!(AgeBucket {
+            age: Option<i32>,
+            cnt: i64,
+            age_sum: i64
+        });
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 2, 0, 0, 0)
+            .unwrap();
+
+        for (email, age) in [
+            ("bucket1@example.com", 20),
+            ("bucket2@example.com", 20),
+            ("bucket3@example.com", 30),
+        ] {
+            client
+                .user()
+                .create(
+                    email.to_string(),
+                    "Bucket".to_string(),
+                    now,
+                    now,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let buckets: Vec<AgeBucket> = client
             .user()
-            .find_many(vec![user::posts::none(vec![post::content::is_null()])])
-            .exec()
+            .group_by(
+                vec![user::GroupByFieldParam::Age],
+                vec![],
+                vec![(user::GroupByFieldParam::Age, SortOrder::Asc)],
+                None,
+                None,
+                None,
+            )
+            .count("cnt")
+            .sum(
+                typed_selection::<user::Entity, user::Selected>(vec!["age".to_string()]),
+                "age_sum",
+            )
+            .exec_as::<AgeBucket>()
             .await
             .unwrap();
 
-        // Should return user1, user3, and user4 (no posts - vacuous truth), but not user2 (has post without content)
-        assert_eq!(users_with_no_null_content.len(), 3);
-        let no_null_content_user_ids: Vec<Uuid> =
-            users_with_no_null_content.iter().map(|u| u.id).collect();
-        assert!(no_null_content_user_ids.contains(&user1.id));
-        assert!(no_null_content_user_ids.contains(&user3.id));
-        assert!(no_null_content_user_ids.contains(&user_no_posts.id)); // user with no posts
-        assert!(!no_null_content_user_ids.contains(&user2.id));
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].age, Some(20));
+        assert_eq!(buckets[0].cnt, 2);
+        assert_eq!(buckets[0].age_sum, 40);
+        assert_eq!(buckets[1].age, Some(30));
+        assert_eq!(buckets[1].cnt, 1);
+        assert_eq!(buckets[1].age_sum, 30);
     }
 
     #[tokio::test]
-    async fn test_raw_sql_query_and_execute() {
-        use sea_orm::FromQueryResult;
-        use std::sync::{Arc, Mutex};
+    async fn test_profile_numeric_columns() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        #[derive(Debug, FromQueryResult)]
-        struct OneRow {
-            value: i32,
+        for (email, age) in [
+            ("young@example.com", Some(20)),
+            ("older@example.com", Some(30)),
+            ("unknown@example.com", None),
+        ] {
+            client
+                .user()
+                .create(email.to_string(), "Profiled".to_string(), now, now, vec![user::age::set(age)])
+                .exec()
+                .await
+                .unwrap();
         }
 
-        // Install a temporary hook to assert emissions
-        struct TestHook {
-            hits: Arc<Mutex<usize>>,
-        }
-        impl caustics::hooks::QueryHook for TestHook {
-            fn before(&self, _e: &caustics::hooks::QueryEvent) {
-                *self.hits.lock().unwrap() += 1;
-            }
-            fn after(
-                &self,
-                _e: &caustics::hooks::QueryEvent,
-                _m: &caustics::hooks::QueryResultMeta,
-            ) {
-                *self.hits.lock().unwrap() += 1;
-            }
+        let profile = client.user().profile().exec().await.unwrap();
+        assert_eq!(profile.count, 3);
+        let age = &profile.columns["age"];
+        assert_eq!(age.min.as_deref(), Some("20"));
+        assert_eq!(age.max.as_deref(), Some("30"));
+        assert_eq!(age.avg, Some(25.0));
+        assert_eq!(age.null_count, 1);
+        // Only numeric fields are profiled
+        assert!(!profile.columns.contains_key("email"));
+    }
+
+    #[tokio::test]
+    async fn test_find_many_window_rank_by_age() {
+        use caustics::Window;
+        use chrono::TimeZone;
+
+        caustics_macros::select_struct!(RankedUser {
+            name: String,
+            age: Option<i32>,
+            rank: i64
+        });
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 2, 0, 0, 0)
+            .unwrap();
+
+        for (email, name, age) in [
+            ("middle@example.com", "Middle", 30),
+            ("oldest@example.com", "Oldest", 40),
+            ("youngest@example.com", "Youngest", 20),
+        ] {
+            client
+                .user()
+                .create(
+                    email.to_string(),
+                    name.to_string(),
+                    now,
+                    now,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
         }
-        let hits = Arc::new(Mutex::new(0usize));
-        caustics::hooks::set_query_hook(Some(Arc::new(TestHook { hits: hits.clone() })));
 
-        let rows: Vec<OneRow> = client
-            ._query_raw::<OneRow>(caustics::raw!("SELECT 1 as value"))
-            .exec()
+        let query = client
+            .user()
+            .find_many(vec![])
+            .order_by(user::name::order(SortOrder::Asc))
+            .window("rank", Window::row_number().order_by(user::age::order(SortOrder::Desc)));
+        assert!(query.to_sql().contains("ROW_NUMBER() OVER"), "{}", query.to_sql());
+        let ranked: Vec<RankedUser> = query.exec_as().await.unwrap();
+
+        // Rows come back in the query's own order, each carrying its rank by age
+        let ranks: Vec<(&str, Option<i32>, i64)> = ranked
+            .iter()
+            .map(|row| (row.name.as_str(), row.age, row.rank))
+            .collect();
+        assert_eq!(
+            ranks,
+            vec![("Middle", Some(30), 2), ("Oldest", Some(40), 1), ("Youngest", Some(20), 3)]
+        );
+
+        // A negative take returns the last rows, still in the requested order
+        let last_two: Vec<RankedUser> = client
+            .user()
+            .find_many(vec![])
+            .order_by(user::name::order(SortOrder::Asc))
+            .take(-2)
+            .window("rank", Window::row_number().order_by(user::age::order(SortOrder::Desc)))
+            .exec_as()
             .await
             .unwrap();
-        assert_eq!(rows.len(), 1);
-        assert_eq!(rows[0].value, 1);
+        let names: Vec<&str> = last_two.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, vec!["Oldest", "Youngest"]);
 
-        // Create a small row to affect count
-        let user = client
+        // The client's row cap applies as it does to exec
+        let err = client
+            .max_rows(Some(2))
             .user()
-            .create(
-                format!("raw_{}@example.com", chrono::Utc::now().timestamp()),
-                "Raw User".to_string(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap(),
-                vec![],
-            )
-            .exec()
+            .find_many(vec![])
+            .window("rank", Window::row_number())
+            .exec_as::<RankedUser>()
             .await
+            .unwrap_err();
+        assert!(err.to_string().contains("CausticsError::RowLimitExceeded"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_find_many_and_raw_filter() {
+        use chrono::TimeZone;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 2, 0, 0, 0)
             .unwrap();
-        // Uuid is always valid, no need to check > 0
 
-        // Another typed query
-        #[derive(Debug, FromQueryResult)]
-        struct Cnt {
-            c: i64,
+        for (email, name, age) in [
+            ("a20@example.com", "A20", 20),
+            ("a21@example.com", "A21", 21),
+            ("a30@example.com", "A30", 30),
+            ("a42@example.com", "A42", 42),
+        ] {
+            client
+                .user()
+                .create(
+                    email.to_string(),
+                    name.to_string(),
+                    now,
+                    now,
+                    vec![user::age::set(Some(age))],
+                )
+                .exec()
+                .await
+                .unwrap();
         }
-        let rows: Vec<Cnt> = client
-            ._query_raw::<Cnt>(caustics::raw!("SELECT {} as c", 42))
+
+        // Structured filter plus a raw modulo condition
+        let even: Vec<user::ModelWithRelations> = client
+            .user()
+            .find_many(vec![user::age::gte(21)])
+            .and_raw("age % 2 = 0")
+            .order_by(user::age::order(SortOrder::Asc))
             .exec()
             .await
             .unwrap();
-        assert_eq!(rows.len(), 1);
-        assert_eq!(rows[0].c, 42);
+        let names: Vec<&str> = even.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["A30", "A42"]);
 
-        // Execute raw (no result set)
-        // Execute DDL via raw
-        let _res = client
-            ._execute_raw(caustics::raw!(
-                "CREATE TEMP TABLE {} (id int)",
-                caustics::ident!("__raw_tmp")
-            ))
+        // Bound parameters compose with the raw fragment and the structured filter
+        let bound: Vec<user::ModelWithRelations> = client
+            .user()
+            .find_many(vec![user::age::gte(21)])
+            .and_raw("age % 2 = 0")
+            .and_raw_bind("age < ?", vec![40.into()])
             .exec()
             .await
             .unwrap();
-        let _res = client
-            ._execute_raw(caustics::raw!(
-                "DROP TABLE {}",
-                caustics::ident!("__raw_tmp")
-            ))
+        assert_eq!(bound.len(), 1);
+        assert_eq!(bound[0].name, "A30");
+
+        let first = client
+            .user()
+            .find_first(vec![])
+            .and_raw_bind("age % ? = 1", vec![2.into()])
             .exec()
             .await
+            .unwrap()
             .unwrap();
+        assert_eq!(first.name, "A21");
+    }
 
-        // Advanced: IN list and JSON binding
-        #[derive(Debug, FromQueryResult)]
-        struct U {
-            id: Uuid,
-            name: String,
-        }
-        let users: Vec<U> = client
-            ._query_raw::<U>(caustics::raw!(
-                "SELECT id, name FROM {} WHERE id = {} ORDER BY id",
-                caustics::ident!("users"),
-                user.id
-            ))
+    #[tokio::test]
+    async fn test_distinct_on_basic() {
+        use chrono::TimeZone;
+
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 3, 0, 0, 0)
+            .unwrap();
+
+        // Two users with same name, different emails (email is unique)
+        let _u1 = client
+            .user()
+            .create(
+                format!("dup-{}@example.com", now.timestamp()).to_string(),
+                "DupName".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(21))],
+            )
             .exec()
             .await
             .unwrap();
-        assert!(!users.is_empty());
-        assert!(users.first().is_some_and(|u| u.id == user.id));
-        assert!(users.first().is_some_and(|u| u.name == "Raw User"));
-
-        // Injection protection: user-provided string is bound, not inlined
-        let evil = "1); DROP TABLE User; --".to_string();
-        let rows: Vec<Cnt> = client
-            ._query_raw::<Cnt>(caustics::raw!(
-                "SELECT COUNT(*) as c FROM {} WHERE name = {}",
-                caustics::ident!("users"),
-                evil
-            ))
+        let _u2 = client
+            .user()
+            .create(
+                format!("dup2-{}@example.com", now.timestamp()).to_string(),
+                "DupName".to_string(),
+                now,
+                now,
+                vec![user::age::set(Some(22))],
+            )
             .exec()
             .await
             .unwrap();
-        assert_eq!(rows.len(), 1);
 
-        // Multiple bound params of different types
-        #[derive(Debug, FromQueryResult)]
-        struct Multi {
-            s: String,
-            n: i32,
-        }
-        let rows: Vec<Multi> = client
-            ._query_raw::<Multi>(caustics::raw!("SELECT {} as s, {} as n", "hello", 7))
+        // Use PCR-style typed distinct on builder
+        let users = client
+            .user()
+            .find_many(vec![])
+            .distinct(vec![user::ScalarField::Name])
             .exec()
             .await
             .unwrap();
-        assert_eq!(rows[0].s, "hello");
-        assert_eq!(rows[0].n, 7);
-
-        // Hooks should have recorded events
-        caustics::hooks::set_query_hook(None);
-        assert!(*hits.lock().unwrap() > 0);
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].name, "DupName");
     }
 
     #[tokio::test]
-    async fn test_has_many_set_operation_structure() {
+    async fn test_dynamic_foreign_key_column_extraction() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
@@ -3428,8 +8200,8 @@ mod query_builder_tests {
         let user = client
             .user()
             .create(
-                "user@example.com".to_string(),
-                "User".to_string(),
+                "test@example.com".to_string(),
+                "Test User".to_string(),
                 chrono::FixedOffset::east_opt(0)
                     .unwrap()
                     .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
@@ -3444,7 +8216,7 @@ mod query_builder_tests {
             .await
             .unwrap();
 
-        // Create some posts
+        // Create posts with different foreign key columns
         let post1 = client
             .post()
             .create(
@@ -3483,9 +8255,8 @@ mod query_builder_tests {
             .await
             .unwrap();
 
-        // Test that the has_many set operation structure compiles and runs
-        // This should work even though the actual set operation is not implemented yet
-
+        // Test that the set operation works with the dynamically extracted foreign key column
+        // This should use "user_id" (converted from "UserId" in the relation definition)
         let updated_user = client
             .user()
             .update(
@@ -3498,478 +8269,484 @@ mod query_builder_tests {
             .exec()
             .await;
 
-        // Has_many set is executed transactionally and returns updated user
         assert!(updated_user.is_ok());
-    }
-
-    #[tokio::test]
-    async fn test_has_many_set_operation_functionality() {
-        let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
 
-        // Create a user
-        let user = client
+        // Verify the result
+        let final_user = client
             .user()
-            .create(
-                "user@example.com".to_string(),
-                "User".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                vec![],
-            )
+            .find_unique(user::id::equals(user.id))
+            .with(user::posts::fetch(vec![]))
             .exec()
             .await
+            .unwrap()
             .unwrap();
 
-        // Create some posts initially not associated with the user
-        let post1 = client
-            .post()
-            .create(
-                "Post 1".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                user::id::equals(user.id), // Initially associated
-                vec![],
-            )
-            .exec()
-            .await
-            .unwrap();
+        let final_posts = final_user.posts.unwrap();
+        assert_eq!(final_posts.len(), 2);
 
-        let post2 = client
-            .post()
-            .create(
-                "Post 2".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                user::id::equals(user.id), // Initially associated
-                vec![],
-            )
-            .exec()
-            .await
-            .unwrap();
+        // Verify that only post1 and post2 are associated with the user
+        let final_post_ids: Vec<Uuid> = final_posts.iter().map(|p| p.id).collect();
+        assert!(final_post_ids.contains(&post1.id));
+        assert!(final_post_ids.contains(&post2.id));
+    }
 
-        let _post3 = client
-            .post()
-            .create(
-                "Post 3".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                user::id::equals(user.id), // Initially associated
-                vec![],
-            )
-            .exec()
-            .await
-            .unwrap();
+    #[tokio::test]
+    async fn test_create_many_users() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        use chrono::{DateTime, FixedOffset};
 
-        // Verify initial state
-        let initial_posts = client
-            .post()
-            .find_many(vec![post::user_id::equals(user.id)])
+        let ts = chrono::Utc::now().timestamp();
+        let count = client
+            .user()
+            .create_many(vec![
+                user::Create {
+                    email: format!("cm1_{}@example.com", ts),
+                    name: "CM1".to_string(),
+                    created_at: DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z")
+                        .unwrap(),
+                    updated_at: DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z")
+                        .unwrap(),
+                    _params: vec![user::age::set(Some(21)), user::deleted_at::set(None)],
+                },
+                user::Create {
+                    email: format!("cm2_{}@example.com", ts),
+                    name: "CM2".to_string(),
+                    created_at: DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z")
+                        .unwrap(),
+                    updated_at: DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z")
+                        .unwrap(),
+                    _params: vec![user::age::set(Some(22)), user::deleted_at::set(None)],
+                },
+            ])
             .exec()
             .await
             .unwrap();
-        assert_eq!(initial_posts.len(), 3);
 
-        // Test the has_many set operation
-        // This should set the user's posts to only post1 and post2
-        let updated_user = client
-            .user()
-            .update(
-                user::id::equals(user.id),
-                vec![user::posts::set(vec![
-                    post::id::equals(post1.id),
-                    post::id::equals(post2.id),
-                ])],
-            )
-            .exec()
-            .await;
+        assert_eq!(count, 2);
+    }
 
-        if let Err(_e) = &updated_user {}
-        assert!(updated_user.is_ok());
+    #[tokio::test]
+    async fn test_create_from_deserialized_payload() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        let payload = serde_json::json!({
+            "email": "payload@example.com",
+            "name": "Payload",
+            "age": 33,
+            "created_at": "2021-01-01T00:00:00Z",
+            "updated_at": "2021-01-02T00:00:00Z",
+        });
+        let create: user::Create = serde_json::from_value(payload).unwrap();
+        let user = client.user().create_from(create).exec().await.unwrap();
+        assert_eq!(user.email, "payload@example.com");
+        assert_eq!(user.name, "Payload");
+        assert_eq!(user.age, Some(33));
+        assert_eq!(user.deleted_at, None);
+        assert_eq!(
+            user.updated_at,
+            DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-02T00:00:00Z").unwrap()
+        );
+
+        // Required relations are given by their foreign key; explicit nulls set NULL
+        let payload = serde_json::json!({
+            "title": "From JSON",
+            "content": null,
+            "created_at": "2021-01-01T00:00:00Z",
+            "updated_at": "2021-01-01T00:00:00Z",
+            "user_id": user.id,
+        });
+        let create: post::Create = serde_json::from_value(payload).unwrap();
+        let post = client.post().create_from(create).exec().await.unwrap();
+        assert_eq!(post.user_id, user.id);
+        assert_eq!(post.content, None);
 
-        // Verify the result
-        let final_posts = client
-            .post()
-            .find_many(vec![post::user_id::equals(user.id)])
-            .exec()
-            .await
-            .unwrap();
+        let missing = serde_json::from_value::<user::Create>(serde_json::json!({
+            "email": "incomplete@example.com",
+            "created_at": "2021-01-01T00:00:00Z",
+            "updated_at": "2021-01-01T00:00:00Z",
+        }));
+        assert!(missing.unwrap_err().to_string().contains("missing field `name`"));
+
+        let unknown = serde_json::from_value::<user::Create>(serde_json::json!({
+            "email": "unknown@example.com",
+            "name": "Unknown",
+            "nickname": "unk",
+            "created_at": "2021-01-01T00:00:00Z",
+            "updated_at": "2021-01-01T00:00:00Z",
+        }));
+        assert!(unknown.unwrap_err().to_string().contains("unknown field `nickname`"));
+    }
 
-        // Now we expect exactly 2 posts since the set operation should replace all associations
-        // The set operation should have removed post3 and kept only post1 and post2
-        assert_eq!(final_posts.len(), 2);
+    #[test]
+    fn test_model_try_from_json() {
+        let id = uuid::Uuid::new_v4();
+        let model = user::Model::try_from(serde_json::json!({
+            "id": id,
+            "email": "imported@example.com",
+            "name": "Imported",
+            "age": 41,
+            "created_at": "2021-01-01T00:00:00Z",
+            "updated_at": "2021-01-02T00:00:00Z",
+            "deleted_at": null,
+        }))
+        .unwrap();
+        assert_eq!(model.id, id);
+        assert_eq!(model.email, "imported@example.com");
+        assert_eq!(model.age, Some(41));
+        assert_eq!(model.deleted_at, None);
+        // Absent optional fields default to None
+        assert_eq!(model.tenant_id, None);
+        assert_eq!(
+            model.updated_at,
+            DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-02T00:00:00Z").unwrap()
+        );
 
-        // Verify that only post1 and post2 are associated with the user
-        let final_post_ids: Vec<Uuid> = final_posts.iter().map(|p| p.id).collect();
-        assert!(final_post_ids.contains(&post1.id));
-        assert!(final_post_ids.contains(&post2.id));
-        assert!(!final_post_ids.contains(&_post3.id));
+        let missing = user::Model::try_from(serde_json::json!({
+            "id": id,
+            "email": "incomplete@example.com",
+            "created_at": "2021-01-01T00:00:00Z",
+            "updated_at": "2021-01-01T00:00:00Z",
+        }))
+        .unwrap_err();
+        assert!(matches!(
+            &missing,
+            caustics::CausticsError::InvalidFieldType { field, actual, .. } if field == "name" && actual == "missing"
+        ));
+
+        let mismatched = user::Model::try_from(serde_json::json!({
+            "id": id,
+            "email": "mismatch@example.com",
+            "name": "Mismatch",
+            "age": "forty",
+            "created_at": "2021-01-01T00:00:00Z",
+            "updated_at": "2021-01-01T00:00:00Z",
+        }))
+        .unwrap_err();
+        assert!(matches!(
+            &mismatched,
+            caustics::CausticsError::InvalidFieldType { field, expected, actual }
+                if field == "age" && expected == "Option<i32>" && actual.starts_with("string")
+        ));
+
+        assert!(matches!(
+            user::Model::try_from(serde_json::json!([1, 2])),
+            Err(caustics::CausticsError::TypeConversionError { .. })
+        ));
     }
 
     #[tokio::test]
-    async fn test_agnostic_implementation_compiles() {
+    async fn test_update_many_users() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
+        use chrono::{DateTime, FixedOffset};
 
-        // Create a user
-        let user = client
+        // seed two users with deleted_at null and different ages
+        let u1 = client
             .user()
             .create(
-                "test@example.com".to_string(),
-                "Test User".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                vec![],
+                format!("um1_{}@example.com", chrono::Utc::now().timestamp()),
+                "UM1".to_string(),
+                DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(19)), user::deleted_at::set(None)],
             )
             .exec()
             .await
             .unwrap();
-
-        // Create a post with a different user to avoid conflicts
-        let post = client
-            .post()
+        let _u2 = client
+            .user()
             .create(
-                "Test Post".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                user::id::equals(user.id),
-                vec![],
+                format!("um2_{}@example.com", chrono::Utc::now().timestamp()),
+                "UM2".to_string(),
+                DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap(),
+                DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap(),
+                vec![user::age::set(Some(31)), user::deleted_at::set(None)],
             )
             .exec()
             .await
             .unwrap();
 
-        // Test that the agnostic implementation compiles and runs
-        // This should work with any relation name, not just hardcoded ones
-        let result = client
+        // updateMany: set deleted_at for users age >= 30
+        let affected = client
             .user()
-            .update(
-                user::id::equals(user.id),
-                vec![user::posts::set(vec![post::id::equals(post.id)])],
+            .update_many(
+                vec![user::age::gte(Some(30))],
+                vec![user::deleted_at::set(Some(
+                    DateTime::<FixedOffset>::parse_from_rfc3339("2021-12-31T00:00:00Z").unwrap(),
+                ))],
             )
             .exec()
-            .await;
+            .await
+            .unwrap();
 
-        // Verify the update succeeded and the record matches
-        assert!(
-            result.is_ok(),
-            "agnostic has_many set update failed: {:?}",
-            result
-        );
-        let updated = result.unwrap();
-        assert_eq!(updated.id, user.id);
+        assert_eq!(affected, 1);
 
-        // Verify via relation fetch that exactly the specified post is connected
-        let user_with_posts = client
+        // verify the younger user remains not deleted
+        let still_active = client
             .user()
-            .find_unique(user::id::equals(user.id))
-            .with(user::posts::fetch(vec![]))
+            .find_unique(user::id::equals(u1.id))
             .exec()
             .await
             .unwrap()
             .unwrap();
-        let posts = user_with_posts.posts.unwrap();
-        assert_eq!(posts.len(), 1);
-        assert_eq!(posts[0].id, post.id);
+        assert!(still_active.deleted_at.is_none());
     }
 
     #[tokio::test]
     #[cfg(feature = "select")]
-    async fn test_aggregate_and_group_by_smoke() {
-        use blog::entities::user::{AggregateAggExt, GroupByHavingAggExt};
+    async fn test_nested_select_functionality() {
         use chrono::TimeZone;
-
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
+
+        // Create test data
         let now = chrono::FixedOffset::east_opt(0)
             .unwrap()
             .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
             .unwrap();
 
-        // Seed
-        let _u1 = client
+        // Create a user with a post
+        let user = client
             .user()
             .create(
-                "agg1@example.com".to_string(),
-                "Agg1".to_string(),
+                "nested_select@example.com".to_string(),
+                "Nested Select Test".to_string(),
                 now,
                 now,
-                vec![user::age::set(Some(10)), user::deleted_at::set(None)],
+                vec![],
             )
             .exec()
             .await
             .unwrap();
-        let _u2 = client
-            .user()
+
+        let _post = client
+            .post()
             .create(
-                "agg2@example.com".to_string(),
-                "Agg2".to_string(),
+                "Test Post".to_string(),
                 now,
                 now,
-                vec![user::age::set(Some(20)), user::deleted_at::set(None)],
+                user::id::equals(user.id),
+                vec![post::content::set(Some("Test content".to_string()))],
             )
             .exec()
             .await
             .unwrap();
 
-        // Aggregate: count + typed aggregates
-        let agg = client
+        // Test that nested select functionality is available (even if not fully implemented)
+        // This test verifies that the macro generates the select functionality
+        let user_with_posts = client
             .user()
-            .aggregate(vec![])
-            .count()
-            .avg(user::select!(age), "age_avg")
-            .min(user::select!(age), "age_min")
-            .max(user::select!(age), "age_max")
+            .find_unique(user::id::equals(user.id))
+            .with(user::posts::include(|posts| posts.take(1)))
             .exec()
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(agg.count, Some(2));
-        assert!(agg.avg.contains_key("age_avg"));
-        assert!(agg.min.contains_key("age_min"));
-        assert!(agg.max.contains_key("age_max"));
 
-        // Group by age with count
-        let rows = client
-            .user()
-            .group_by(
-                vec![user::GroupByFieldParam::Age],
-                vec![],
-                vec![],
-                None,
-                None,
-                None,
-            )
-            .count("cnt")
-            .sum(user::select!(age), "age_sum")
-            .having_sum_gte(user::select!(age), 20)
-            .exec()
-            .await
-            .unwrap();
-        assert!(!rows.is_empty());
-        assert!(rows.len() <= 2);
+        // Verify the user and posts are fetched
+        assert_eq!(user_with_posts.name, "Nested Select Test");
+
+        // Verify the post is included
+        if let Some(posts) = user_with_posts.posts {
+            assert_eq!(posts.len(), 1);
+            assert_eq!(posts[0].title, "Test Post");
+        } else {
+            panic!("Posts should be included");
+        }
     }
 
     #[tokio::test]
     #[cfg(feature = "select")]
-    async fn test_aggregate_typed_and_group_by_typed() {
+    async fn test_field_selection_optimization() {
         use chrono::TimeZone;
-        // Bring typed aggregate extension traits into scope
-        use blog::entities::user::AggregateAggExt;
-
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
+
+        // Create test data
         let now = chrono::FixedOffset::east_opt(0)
             .unwrap()
-            .with_ymd_and_hms(2024, 1, 2, 0, 0, 0)
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
             .unwrap();
 
-        let _u1 = client
+        // Create a user with a post
+        let user = client
             .user()
             .create(
-                format!("t1-{}@example.com", now.timestamp()).to_string(),
-                "T1".to_string(),
+                "field_selection@example.com".to_string(),
+                "Field Selection Test".to_string(),
                 now,
                 now,
-                vec![user::age::set(Some(10)), user::deleted_at::set(None)],
+                vec![],
             )
             .exec()
             .await
             .unwrap();
-        let _u2 = client
-            .user()
+
+        let _post = client
+            .post()
             .create(
-                format!("t2-{}@example.com", now.timestamp()).to_string(),
-                "T2".to_string(),
+                "Field Selection Post".to_string(),
                 now,
                 now,
-                vec![user::age::set(Some(30)), user::deleted_at::set(None)],
+                user::id::equals(user.id),
+                vec![post::content::set(Some(
+                    "Field selection content".to_string(),
+                ))],
             )
             .exec()
             .await
             .unwrap();
 
-        // Aggregate typed
-        let agg_typed = client
-            .user()
-            .aggregate(vec![])
-            .count()
-            .avg(user::select!(age), "age_avg")
-            .min(user::select!(age), "age_min")
-            .max(user::select!(age), "age_max")
-            .exec()
-            .await
-            .unwrap();
-        assert_eq!(agg_typed.count, Some(2));
-        assert!(agg_typed.avg.contains_key("age_avg"));
-        assert!(agg_typed.min.contains_key("age_min"));
-        assert!(agg_typed.max.contains_key("age_max"));
+        select_struct!(UserWithSelectedPosts from user::Selected {
+            id: Uuid,
+            name: String,
+            posts: Vec<PostTitle from post::Selected {
+                title: String
+            }>
+        });
 
-        // Group by typed
-        let rows = client
+        // Test field selection functionality with automatic conversion!
+        let user_with_selected_posts: UserWithSelectedPosts = client
             .user()
-            .group_by(
-                vec![user::GroupByFieldParam::Age],
-                vec![],
-                vec![],
-                None,
-                None,
-                None,
-            )
-            .count("cnt")
-            .sum(user::select!(age), "age_sum")
+            .find_unique(user::id::equals(user.id))
+            .select(user::select!(id, name))
+            .with(user::posts::include(|posts| {
+                posts.select(post::select!(title))
+            }))
             .exec()
             .await
+            .unwrap()
             .unwrap();
-        assert!(!rows.is_empty());
-        for row in rows {
-            assert!(row.keys.contains_key("Age"));
-            assert!(row.aggregates.contains_key("age_sum"));
-            assert!(row.aggregates.contains_key("cnt"));
-        }
+
+        // Verify the user is fetched
+        assert_eq!(user_with_selected_posts.name, "Field Selection Test");
+
+        // Verify the post is included
+        assert_eq!(user_with_selected_posts.posts.len(), 1);
+        assert_eq!(
+            user_with_selected_posts.posts[0].title,
+            "Field Selection Post"
+        );
     }
 
     #[tokio::test]
-    async fn test_distinct_on_basic() {
-        use chrono::TimeZone;
-
+    async fn test_include_orders_children_per_parent() {
         let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 3, 0, 0, 0)
-            .unwrap();
+        let client = blog::CausticsClient::new(db);
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        // Two users with same name, different emails (email is unique)
-        let _u1 = client
-            .user()
-            .create(
-                format!("dup-{}@example.com", now.timestamp()).to_string(),
-                "DupName".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(21))],
-            )
-            .exec()
-            .await
-            .unwrap();
-        let _u2 = client
+        let mut authors = Vec::new();
+        for email in ["first@example.com", "second@example.com"] {
+            let user = client
+                .user()
+                .create(email.to_string(), "Author".to_string(), created, created, vec![])
+                .exec()
+                .await
+                .unwrap();
+            authors.push(user.id);
+        }
+        // Interleave the authors' posts so neither insertion nor id order matches recency
+        for (author, title, at) in [
+            (0, "a-2021", "2021-06-01T00:00:00Z"),
+            (1, "b-2023", "2023-06-01T00:00:00Z"),
+            (0, "a-2024", "2024-06-01T00:00:00Z"),
+            (1, "b-2020", "2020-06-01T00:00:00Z"),
+            (0, "a-2022", "2022-06-01T00:00:00Z"),
+            (1, "b-2025", "2025-06-01T00:00:00Z"),
+        ] {
+            let at = DateTime::<FixedOffset>::from_str(at).unwrap();
+            client
+                .post()
+                .create(title.to_string(), at, at, user::id::equals(authors[author]), vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let users = client
             .user()
-            .create(
-                format!("dup2-{}@example.com", now.timestamp()).to_string(),
-                "DupName".to_string(),
-                now,
-                now,
-                vec![user::age::set(Some(22))],
-            )
+            .find_many(vec![])
+            .with(user::posts::include(|posts| {
+                posts.order_by(post::created_at::order(SortOrder::Desc))
+            }))
             .exec()
             .await
             .unwrap();
+        assert_eq!(users.len(), 2);
+        for user in &users {
+            let titles: Vec<_> = user
+                .posts
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|p| p.title.as_str())
+                .collect();
+            if user.id == authors[0] {
+                assert_eq!(titles, vec!["a-2024", "a-2022", "a-2021"]);
+            } else {
+                assert_eq!(titles, vec!["b-2025", "b-2023", "b-2020"]);
+            }
+        }
 
-        // Use PCR-style typed distinct on builder
-        let users = client
+        // Same through find_unique, and combined with take
+        let newest = client
             .user()
-            .find_many(vec![])
-            .distinct(vec![user::ScalarField::Name])
+            .find_unique(user::id::equals(authors[1]))
+            .with(user::posts::include(|posts| {
+                posts.order_by(post::created_at::order(SortOrder::Desc)).take(2)
+            }))
             .exec()
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(users.len(), 1);
-        assert_eq!(users[0].name, "DupName");
+        let titles: Vec<_> = newest.posts.unwrap().into_iter().map(|p| p.title).collect();
+        assert_eq!(titles, vec!["b-2025", "b-2023"]);
     }
 
     #[tokio::test]
-    async fn test_dynamic_foreign_key_column_extraction() {
+    async fn test_relation_counts_on_has_many_include() {
+        use chrono::TimeZone;
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
 
-        // Create a user
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
         let user = client
             .user()
             .create(
-                "test@example.com".to_string(),
-                "Test User".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
+                "count_relation@example.com".to_string(),
+                "Count Relation".to_string(),
+                now,
+                now,
                 vec![],
             )
             .exec()
             .await
             .unwrap();
 
-        // Create posts with different foreign key columns
-        let post1 = client
+        let _p1 = client
             .post()
             .create(
-                "Post 1".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
+                "P1".to_string(),
+                now,
+                now,
                 user::id::equals(user.id),
                 vec![],
             )
             .exec()
             .await
             .unwrap();
-
-        let post2 = client
+        let _p2 = client
             .post()
             .create(
-                "Post 2".to_string(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
-                chrono::FixedOffset::east_opt(0)
-                    .unwrap()
-                    .with_ymd_and_hms(2022, 1, 1, 12, 0, 0)
-                    .unwrap(),
+                "P2".to_string(),
+                now,
+                now,
                 user::id::equals(user.id),
                 vec![],
             )
@@ -3977,333 +8754,353 @@ mod query_builder_tests {
             .await
             .unwrap();
 
-        // Test that the set operation works with the dynamically extracted foreign key column
-        // This should use "user_id" (converted from "UserId" in the relation definition)
-        let updated_user = client
-            .user()
-            .update(
-                user::id::equals(user.id),
-                vec![user::posts::set(vec![
-                    post::id::equals(post1.id),
-                    post::id::equals(post2.id),
-                ])],
-            )
-            .exec()
-            .await;
-
-        assert!(updated_user.is_ok());
-
-        // Verify the result
-        let final_user = client
+        let user_with_posts = client
             .user()
             .find_unique(user::id::equals(user.id))
-            .with(user::posts::fetch(vec![]))
+            .with(user::posts::include(|rel| rel.count()))
             .exec()
             .await
             .unwrap()
             .unwrap();
 
-        let final_posts = final_user.posts.unwrap();
-        assert_eq!(final_posts.len(), 2);
+        assert!(user_with_posts._count.is_some());
+        let counts = user_with_posts._count.unwrap();
+        assert_eq!(counts.posts, Some(2));
 
-        // Verify that only post1 and post2 are associated with the user
-        let final_post_ids: Vec<Uuid> = final_posts.iter().map(|p| p.id).collect();
-        assert!(final_post_ids.contains(&post1.id));
-        assert!(final_post_ids.contains(&post2.id));
+        let pairs: Vec<(&'static str, Option<i64>)> = counts.iter().collect();
+        assert!(pairs.contains(&("posts", Some(2))));
+        assert!(pairs.iter().all(|(name, count)| *name == "posts" || count.is_none()));
     }
 
     #[tokio::test]
-    async fn test_create_many_users() {
+    async fn test_filtered_relation_count_on_include() {
+        use chrono::TimeZone;
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
-        use chrono::{DateTime, FixedOffset};
 
-        let ts = chrono::Utc::now().timestamp();
-        let count = client
+        let now = chrono::FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap();
+
+        let user = client
             .user()
-            .create_many(vec![
-                user::Create {
-                    email: format!("cm1_{}@example.com", ts),
-                    name: "CM1".to_string(),
-                    created_at: DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z")
-                        .unwrap(),
-                    updated_at: DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z")
-                        .unwrap(),
-                    _params: vec![user::age::set(Some(21)), user::deleted_at::set(None)],
-                },
-                user::Create {
-                    email: format!("cm2_{}@example.com", ts),
-                    name: "CM2".to_string(),
-                    created_at: DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z")
-                        .unwrap(),
-                    updated_at: DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z")
-                        .unwrap(),
-                    _params: vec![user::age::set(Some(22)), user::deleted_at::set(None)],
-                },
-            ])
+            .create(
+                "count_where@example.com".to_string(),
+                "Count Where".to_string(),
+                now,
+                now,
+                vec![],
+            )
             .exec()
             .await
             .unwrap();
 
-        assert_eq!(count, 2);
+        for (title, content) in [
+            ("Drafted", Some("Body".to_string())),
+            ("Empty", None),
+            ("Written", Some("More".to_string())),
+        ] {
+            client
+                .post()
+                .create(
+                    title.to_string(),
+                    now,
+                    now,
+                    user::id::equals(user.id),
+                    vec![post::content::set(content)],
+                )
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let total = client
+            .user()
+            .find_unique(user::id::equals(user.id))
+            .with(user::posts::include(|rel| rel.count()))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(total._count.unwrap().posts, Some(3));
+
+        let with_content = client
+            .user()
+            .find_unique(user::id::equals(user.id))
+            .with(user::posts::include(|rel| {
+                rel.count_where(vec![post::content::is_not_null()])
+            }))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(with_content._count.unwrap().posts, Some(2));
     }
 
     #[tokio::test]
-    async fn test_update_many_users() {
+    #[cfg(feature = "select")]
+    async fn test_select_relation_existence_field() {
+        use caustics_macros::select_struct;
         let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
-        use chrono::{DateTime, FixedOffset};
+        let client = blog::CausticsClient::new(db);
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        // seed two users with deleted_at null and different ages
-        let u1 = client
+        let author = client
             .user()
-            .create(
-                format!("um1_{}@example.com", chrono::Utc::now().timestamp()),
-                "UM1".to_string(),
-                DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(19)), user::deleted_at::set(None)],
-            )
+            .create("author@example.com".to_string(), "Author".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-        let _u2 = client
+        let reader = client
             .user()
-            .create(
-                format!("um2_{}@example.com", chrono::Utc::now().timestamp()),
-                "UM2".to_string(),
-                DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap(),
-                DateTime::<FixedOffset>::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap(),
-                vec![user::age::set(Some(31)), user::deleted_at::set(None)],
-            )
+            .create("reader@example.com".to_string(), "Reader".to_string(), created, created, vec![])
             .exec()
             .await
             .unwrap();
-
-        // updateMany: set deleted_at for users age >= 30
-        let affected = client
-            .user()
-            .update_many(
-                vec![user::age::gte(Some(30))],
-                vec![user::deleted_at::set(Some(
-                    DateTime::<FixedOffset>::parse_from_rfc3339("2021-12-31T00:00:00Z").unwrap(),
-                ))],
-            )
+        client
+            .post()
+            .create("Hello".to_string(), created, created, user::id::equals(author.id), vec![])
             .exec()
             .await
             .unwrap();
 
-        assert_eq!(affected, 1);
+        select_struct!(UserPostFlag from user::Selected {
+            id: Uuid,
+            has_posts: bool
+        });
 
-        // verify the younger user remains not deleted
-        let still_active = client
+        let flags: Vec<UserPostFlag> = client
             .user()
-            .find_unique(user::id::equals(u1.id))
+            .find_many(vec![])
+            .select(user::select!(id, has_posts))
             .exec()
             .await
-            .unwrap()
             .unwrap();
-        assert!(still_active.deleted_at.is_none());
+        assert_eq!(flags.len(), 2);
+        for flag in flags {
+            assert_eq!(flag.has_posts, flag.id == author.id);
+            assert!(flag.id == author.id || flag.id == reader.id);
+        }
     }
 
     #[tokio::test]
     #[cfg(feature = "select")]
-    async fn test_nested_select_functionality() {
-        use chrono::TimeZone;
+    async fn test_selected_refetch_full_model() {
         let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
-
-        // Create test data
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
+        let client = blog::CausticsClient::new(db);
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        // Create a user with a post
         let user = client
             .user()
             .create(
-                "nested_select@example.com".to_string(),
-                "Nested Select Test".to_string(),
-                now,
-                now,
-                vec![],
+                "refetch@example.com".to_string(),
+                "Refetch".to_string(),
+                created,
+                created,
+                vec![user::age::set(Some(41))],
             )
             .exec()
             .await
             .unwrap();
 
-        let _post = client
-            .post()
-            .create(
-                "Test Post".to_string(),
-                now,
-                now,
-                user::id::equals(user.id),
-                vec![post::content::set(Some("Test content".to_string()))],
-            )
+        let selected: user::Selected = client
+            .user()
+            .find_unique(user::id::equals(user.id))
+            .select(user::select!(name))
             .exec()
             .await
+            .unwrap()
             .unwrap();
+        assert_eq!(selected.name.as_deref(), Some("Refetch"));
+        // The primary key is kept even though only `name` was selected
+        assert_eq!(selected.id, Some(user.id));
+        assert_eq!(selected.email, None);
 
-        // Test that nested select functionality is available (even if not fully implemented)
-        // This test verifies that the macro generates the select functionality
-        let user_with_posts = client
+        let full = selected.refetch(&client).await.unwrap().unwrap();
+        assert_eq!(full.id, user.id);
+        assert_eq!(full.email, "refetch@example.com");
+        assert_eq!(full.age, Some(41));
+
+        let many: Vec<user::Selected> = client
             .user()
-            .find_unique(user::id::equals(user.id))
-            .with(user::posts::include(|posts| posts.take(1)))
+            .find_many(vec![])
+            .select(user::select!(email))
             .exec()
             .await
-            .unwrap()
             .unwrap();
+        assert_eq!(many[0].id, Some(user.id));
 
-        // Verify the user and posts are fetched
-        assert_eq!(user_with_posts.name, "Nested Select Test");
-
-        // Verify the post is included
-        if let Some(posts) = user_with_posts.posts {
-            assert_eq!(posts.len(), 1);
-            assert_eq!(posts[0].title, "Test Post");
-        } else {
-            panic!("Posts should be included");
-        }
+        // Works inside a transaction too, and reports a deleted row as missing
+        let tx_result = client
+            .transaction()
+            .run(|tx| {
+                let selected = selected.clone();
+                async move {
+                    tx.user().delete(user::id::equals(user.id)).exec().await?;
+                    selected.refetch(&tx).await
+                }
+            })
+            .await
+            .unwrap();
+        assert!(tx_result.is_none());
     }
 
     #[tokio::test]
     #[cfg(feature = "select")]
-    async fn test_field_selection_optimization() {
-        use chrono::TimeZone;
+    async fn test_distinct_all_select_keeps_only_selected_columns() {
         let db = setup_test_db().await;
-        let client = blog::CausticsClient::new(db.clone());
+        let client = blog::CausticsClient::new(db);
+        let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        // Create test data
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
+        for email in ["twin1@example.com", "twin2@example.com", "solo@example.com"] {
+            let name = if email.starts_with("twin") { "Twin" } else { "Solo" };
+            client
+                .user()
+                .create(email.to_string(), name.to_string(), created, created, vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
 
-        // Create a user with a post
-        let user = client
+        // Adding the primary key would make every row distinct
+        let names: Vec<user::Selected> = client
             .user()
-            .create(
-                "field_selection@example.com".to_string(),
-                "Field Selection Test".to_string(),
-                now,
-                now,
-                vec![],
-            )
+            .find_many(vec![])
+            .order_by(user::name::order(SortOrder::Asc))
+            .distinct_all()
+            .select(user::select!(name))
             .exec()
             .await
             .unwrap();
+        let names: Vec<_> = names.iter().map(|s| s.name.as_deref()).collect();
+        assert_eq!(names, vec![Some("Solo"), Some("Twin")]);
+    }
 
-        let _post = client
-            .post()
-            .create(
-                "Field Selection Post".to_string(),
-                now,
-                now,
-                user::id::equals(user.id),
-                vec![post::content::set(Some(
-                    "Field selection content".to_string(),
-                ))],
-            )
+    #[tokio::test]
+    #[cfg(feature = "select")]
+    async fn test_in_subquery_from_selected_query() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        let mut authors = Vec::new();
+        for (email, title) in [
+            ("rustacean@example.com", Some("Rust tips")),
+            ("gopher@example.com", Some("Go tips")),
+            ("lurker@example.com", None),
+        ] {
+            let author = client
+                .user()
+                .create(email.to_string(), email.to_string(), now, now, vec![])
+                .exec()
+                .await
+                .unwrap();
+            if let Some(title) = title {
+                client
+                    .post()
+                    .create(title.to_string(), now, now, user::id::equals(author.id), vec![])
+                    .exec()
+                    .await
+                    .unwrap();
+            }
+            authors.push(author);
+        }
+
+        // Authors of posts mentioning Rust, resolved by the database in one statement
+        let rust_authors = client
+            .user()
+            .find_many(vec![user::id::in_subquery(
+                client
+                    .post()
+                    .find_many(vec![post::title::contains("Rust")])
+                    .select(post::select!(user_id)),
+            )])
             .exec()
             .await
             .unwrap();
+        assert_eq!(rust_authors.len(), 1);
+        assert_eq!(rust_authors[0].id, authors[0].id);
 
-        select_struct!(UserWithSelectedPosts from user::Selected {
-            id: Uuid,
-            name: String,
-            posts: Vec<PostTitle from post::Selected {
-                title: String
-            }>
-        });
-
-        // Test field selection functionality with automatic conversion!
-        let user_with_selected_posts: UserWithSelectedPosts = client
+        // Composes with `not` for anti-membership
+        let without_posts = client
             .user()
-            .find_unique(user::id::equals(user.id))
-            .select(user::select!(id, name))
-            .with(user::posts::include(|posts| {
-                posts.select(post::select!(title))
-            }))
+            .find_many(vec![user::not(vec![user::id::in_subquery(
+                client.post().find_many(vec![]).select(post::select!(user_id)),
+            )])])
             .exec()
             .await
-            .unwrap()
             .unwrap();
-
-        // Verify the user is fetched
-        assert_eq!(user_with_selected_posts.name, "Field Selection Test");
-
-        // Verify the post is included
-        assert_eq!(user_with_selected_posts.posts.len(), 1);
-        assert_eq!(
-            user_with_selected_posts.posts[0].title,
-            "Field Selection Post"
-        );
+        assert_eq!(without_posts.len(), 1);
+        assert_eq!(without_posts[0].email, "lurker@example.com");
     }
 
     #[tokio::test]
-    async fn test_relation_counts_on_has_many_include() {
-        use chrono::TimeZone;
+    #[cfg(feature = "select")]
+    async fn test_include_fk_only_loads_parent_key() {
         let db = setup_test_db().await;
         let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
 
-        let now = chrono::FixedOffset::east_opt(0)
-            .unwrap()
-            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
-            .unwrap();
-
-        let user = client
+        let author = client
             .user()
-            .create(
-                "count_relation@example.com".to_string(),
-                "Count Relation".to_string(),
-                now,
-                now,
-                vec![],
-            )
+            .create("fk_only@example.com".to_string(), "Fk Only".to_string(), now, now, vec![user::age::set(Some(40))])
             .exec()
             .await
             .unwrap();
-
-        let _p1 = client
+        client
             .post()
-            .create(
-                "P1".to_string(),
-                now,
-                now,
-                user::id::equals(user.id),
-                vec![],
-            )
+            .create("Presence".to_string(), now, now, user::id::equals(author.id), vec![])
             .exec()
             .await
             .unwrap();
-        let _p2 = client
+
+        let posts: Vec<post::Selected> = client
             .post()
-            .create(
-                "P2".to_string(),
-                now,
-                now,
-                user::id::equals(user.id),
-                vec![],
-            )
+            .find_many(vec![post::title::equals("Presence")])
+            .select(post::select!(title, user_id))
+            .with(post::user::include_fk_only())
             .exec()
             .await
             .unwrap();
+        let post = posts.into_iter().next().unwrap();
 
-        let user_with_posts = client
+        // The parent is present, with only its primary key read
+        let parent = post.user.expect("user relation should be loaded");
+        assert_eq!(parent.id, Some(author.id));
+        assert_eq!(parent.email, None);
+        assert_eq!(parent.name, None);
+        assert_eq!(parent.age, None);
+    }
+
+    #[tokio::test]
+    async fn test_include_fk_only_rejected_on_full_rows() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        let author = client
             .user()
-            .find_unique(user::id::equals(user.id))
-            .with(user::posts::include(|rel| rel.count()))
+            .create("fk_full@example.com".to_string(), "Fk Full".to_string(), now, now, vec![])
+            .exec()
+            .await
+            .unwrap();
+        client
+            .post()
+            .create("Whole parent".to_string(), now, now, user::id::equals(author.id), vec![])
             .exec()
             .await
-            .unwrap()
             .unwrap();
 
-        assert!(user_with_posts._count.is_some());
-        let counts = user_with_posts._count.unwrap();
-        assert_eq!(counts.posts, Some(2));
+        // A full row has nowhere to leave the parent's other columns unread
+        let result = client
+            .post()
+            .find_first(vec![post::title::equals("Whole parent")])
+            .with(post::user::include_fk_only())
+            .exec()
+            .await;
+        let err = result.expect_err("include_fk_only on a full row should be rejected");
+        assert!(err.to_string().contains("CausticsError::QueryValidation"), "{}", err);
     }
 
     #[tokio::test]
@@ -4878,6 +9675,147 @@ mod query_builder_tests {
         assert_eq!(users_complex_order[1].name, "Bob"); // 1 post
         assert_eq!(users_complex_order[2].name, "Alice"); // 2 posts
     }
+
+    #[tokio::test]
+    async fn test_polymorphic_comment_targets() {
+        let mut db = setup_test_db().await;
+        let statements = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let recorded = statements.clone();
+        db.set_metric_callback(move |info| {
+            recorded.lock().unwrap().push(info.statement.sql.clone());
+        });
+        let client = blog::CausticsClient::new(db.clone());
+        let now = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+
+        let author = client
+            .user()
+            .create("poly@example.com".to_string(), "Poly".to_string(), now, now, vec![])
+            .exec()
+            .await
+            .unwrap();
+        let post = client
+            .post()
+            .create("Tagged".to_string(), now, now, user::id::equals(author.id), vec![])
+            .exec()
+            .await
+            .unwrap();
+
+        for (body, commentable_type, commentable_id) in [
+            ("on post", "post", post.id),
+            ("on user", "user", author.id),
+            ("dangling", "post", Uuid::new_v4()),
+        ] {
+            client
+                .comment()
+                .create(body.to_string(), commentable_type.to_string(), commentable_id, vec![])
+                .exec()
+                .await
+                .unwrap();
+        }
+
+        let comments = client
+            .comment()
+            .find_many(vec![])
+            .order_by(comment::body::order(SortOrder::Desc))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(comments.len(), 3);
+
+        // One query per target entity, however many comments point at it
+        statements.lock().unwrap().clear();
+        let targets = client.comment().fetch_commentable_many(&comments).await.unwrap();
+        assert_eq!(targets.len(), 3);
+        assert_eq!(statements.lock().unwrap().len(), 2);
+        match &targets[0] {
+            Some(comment::Commentable::User(user)) => assert_eq!(user.id, author.id),
+            other => panic!("expected a user target, got {:?}", other),
+        }
+        match &targets[1] {
+            Some(comment::Commentable::Post(found)) => assert_eq!(found.title, "Tagged"),
+            other => panic!("expected a post target, got {:?}", other),
+        }
+        assert!(targets[2].is_none());
+        assert_eq!(client.comment().fetch_commentable(&comments[1]).await.unwrap(), targets[1]);
+
+        // Scoped clients only resolve targets inside their scope
+        let scoped = client.scoped("tenant_id", 7).comment().fetch_commentable_many(&comments).await.unwrap();
+        assert!(scoped[0].is_none());
+        assert_eq!(scoped[1], targets[1]);
+
+        // An unknown tag is rejected rather than silently resolving to nothing
+        let unknown = client
+            .comment()
+            .create("lost".to_string(), "video".to_string(), post.id, vec![])
+            .exec()
+            .await
+            .unwrap();
+        assert!(client.comment().fetch_commentable(&unknown).await.is_err());
+
+        let metadata = blog::CausticsClient::entity_metadata("Comment").unwrap();
+        let mut polymorphic = metadata
+            .relations
+            .iter()
+            .filter(|relation| relation.relation_kind == "Polymorphic")
+            .map(|relation| {
+                (
+                    relation.name,
+                    relation.discriminator_column,
+                    relation.discriminator_value,
+                    relation.target_table_name,
+                    relation.foreign_key_field,
+                )
+            })
+            .collect::<Vec<_>>();
+        polymorphic.sort();
+        assert_eq!(
+            polymorphic,
+            vec![
+                ("commentable", Some("commentable_type"), Some("post"), "posts", Some("commentable_id")),
+                ("commentable", Some("commentable_type"), Some("user"), "users", Some("commentable_id")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_returns_database_default() {
+        let db = setup_test_db().await;
+        let client = blog::CausticsClient::new(db.clone());
+
+        // `created_at` is not a create argument; the column's DEFAULT CURRENT_TIMESTAMP fills it
+        let created = client
+            .comment()
+            .create("first".to_string(), "post".to_string(), Uuid::new_v4(), vec![])
+            .exec()
+            .await
+            .unwrap();
+        let stored = client
+            .comment()
+            .find_unique(comment::id::equals(created.id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(created.created_at, stored.created_at);
+        // CURRENT_TIMESTAMP on SQLite has whole-second precision, unlike a client-side `Utc::now()`
+        assert_eq!(created.created_at.timestamp_subsec_nanos(), 0);
+        assert!((chrono::Utc::now() - created.created_at).num_seconds().abs() < 60);
+
+        // An explicit value still wins over the default
+        let fixed = DateTime::<FixedOffset>::from_str("2020-05-01T10:00:00Z").unwrap().to_utc();
+        let backdated = client
+            .comment()
+            .create(
+                "backdated".to_string(),
+                "post".to_string(),
+                Uuid::new_v4(),
+                vec![comment::created_at::set(fixed)],
+            )
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(backdated.created_at, fixed);
+    }
 }
 
 mod create_with_tests {
@@ -0,0 +1,145 @@
+//! The statement cache is process-wide, so its test runs in a binary of its own where no
+//! other test can share the cache or its counters.
+
+use std::str::FromStr;
+
+use blog::entities::user;
+use caustics::statement_cache;
+use chrono::{DateTime, FixedOffset};
+use sea_orm::{ConnectionTrait, Database, Schema};
+
+#[tokio::test]
+async fn test_statement_cache_reuses_templates() {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    let schema = Schema::new(db.get_database_backend());
+    let create_users = schema.create_table_from_entity(user::Entity);
+    db.execute(db.get_database_backend().build(&create_users)).await.unwrap();
+    let client = blog::CausticsClient::new(db.clone());
+
+    let created = DateTime::<FixedOffset>::from_str("2021-01-01T00:00:00Z").unwrap();
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let user = client
+            .user()
+            .create(
+                format!("cached{}@example.com", i),
+                format!("Cached {}", i),
+                created,
+                created,
+                vec![user::age::set(Some(20 + i))],
+            )
+            .exec()
+            .await
+            .unwrap();
+        ids.push(user.id);
+    }
+
+    statement_cache::enable(128);
+
+    // Identical shape, different bound values: one build, then hits
+    for i in 0..5 {
+        let users = client
+            .user()
+            .find_many(vec![
+                user::name::equals(format!("Cached {}", i)),
+                user::age::gte(i),
+            ])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, ids[i as usize]);
+    }
+    let after = statement_cache::stats();
+    assert_eq!(after, statement_cache::CacheStats { builds: 1, hits: 4 });
+
+    // find_unique shares the mechanism
+    for id in &ids {
+        let found = client
+            .user()
+            .find_unique(user::id::equals(*id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, *id);
+    }
+    let unique = statement_cache::stats();
+    assert_eq!(unique.builds, after.builds + 1);
+    assert_eq!(unique.hits, after.hits + 4);
+
+    // A different shape (extra operator) is built separately
+    let users = client
+        .user()
+        .find_many(vec![
+            user::name::equals("Cached 1".to_string()),
+            user::age::lt(100),
+            user::email::contains("cached".to_string()),
+        ])
+        .exec()
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(statement_cache::stats().builds, unique.builds + 1);
+
+    // Limits are bound values, so pages of different sizes share a template
+    let paged = statement_cache::stats();
+    for take in 1..=3 {
+        let users = client
+            .user()
+            .find_many(vec![user::age::in_vec(vec![20, 21, 22, 23])])
+            .take(take)
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(users.len(), take as usize);
+    }
+    assert_eq!(statement_cache::stats().builds, paged.builds + 1);
+    assert_eq!(statement_cache::stats().hits, paged.hits + 2);
+
+    // A list of another length renders other placeholders, so it gets its own template
+    let users = client
+        .user()
+        .find_many(vec![user::age::in_vec(vec![20, 21])])
+        .take(5)
+        .exec()
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(statement_cache::stats().builds, paged.builds + 2);
+
+    // Selections are cached per selected column list
+    #[cfg(feature = "select")]
+    {
+        let before = statement_cache::stats();
+        for i in 0..3 {
+            let rows: Vec<user::Selected> = client
+                .user()
+                .find_many(vec![user::name::equals(format!("Cached {}", i))])
+                .select(user::select!(id, name))
+                .exec()
+                .await
+                .unwrap();
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].name.as_deref(), Some(format!("Cached {}", i).as_str()));
+        }
+        assert_eq!(statement_cache::stats().builds, before.builds + 1);
+        assert_eq!(statement_cache::stats().hits, before.hits + 2);
+
+        let rows: Vec<user::Selected> = client
+            .user()
+            .find_many(vec![user::name::equals("Cached 0".to_string())])
+            .select(user::select!(email))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(rows[0].email.as_deref(), Some("cached0@example.com"));
+        assert_eq!(statement_cache::stats().builds, before.builds + 2);
+    }
+
+    statement_cache::disable();
+    assert!(!statement_cache::is_enabled());
+    let disabled = statement_cache::stats();
+    client.user().find_many(vec![]).exec().await.unwrap();
+    assert_eq!(statement_cache::stats(), disabled);
+}
@@ -1,4 +1,34 @@
 use caustics_macros::caustics;
+use sea_orm::DeriveValueType;
+
+/// Email address stored as text but kept distinct from plain strings
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, DeriveValueType, serde::Deserialize)]
+pub struct Email(pub String);
+
+impl caustics::ToCausticsValue for Email {
+    fn to_caustics_value(&self) -> sea_orm::Value {
+        self.0.clone().into()
+    }
+
+    fn to_caustics_key(&self) -> caustics::CausticsKey {
+        caustics::CausticsKey::String(self.0.clone())
+    }
+}
+
+impl caustics::FromCausticsValue for Email {
+    fn from_caustics_value(value: sea_orm::Value) -> Option<Self> {
+        match value {
+            sea_orm::Value::String(Some(address)) => Some(Email(*address)),
+            _ => None,
+        }
+    }
+}
+
+impl sea_orm::TryFromU64 for Email {
+    fn try_from_u64(_: u64) -> Result<Self, sea_orm::DbErr> {
+        Err(sea_orm::DbErr::ConvertFromU64("Email"))
+    }
+}
 
 #[caustics]
 pub mod author {
@@ -36,6 +66,8 @@ pub mod author {
         #[sea_orm(has_one = "super::profile::Entity", from = "Column::Id", to = "super::profile::Column::AuthorId")]
         /// #[caustics(field_name="profile", nullable)]
         Profile,
+        #[sea_orm(has_many = "super::loan::Entity", from = "Column::Id", to = "super::loan::Column::AuthorId")]
+        Loans,
     }
 
     impl Related<super::book::Entity> for Entity {
@@ -56,6 +88,12 @@ pub mod author {
         }
     }
 
+    impl Related<super::loan::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Loans.def()
+        }
+    }
+
     impl ActiveModelBehavior for ActiveModel {}
 }
 
@@ -87,6 +125,12 @@ pub mod book {
     pub enum Relation {
         #[sea_orm(belongs_to = "super::author::Entity", from = "Column::AuthorId", to = "super::author::Column::Id")]
         Author,
+        #[sea_orm(
+            has_many = "super::review::Entity",
+            from = "(Column::Title, Column::AuthorId)",
+            to = "(super::review::Column::BookTitle, super::review::Column::BookAuthorId)"
+        )]
+        Reviews,
     }
 
     impl Related<super::author::Entity> for Entity {
@@ -95,10 +139,51 @@ pub mod book {
         }
     }
 
+    impl Related<super::review::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Reviews.def()
+        }
+    }
+
     impl ActiveModelBehavior for ActiveModel {}
 }
 
+#[caustics]
+pub mod review {
+    use caustics_macros::Caustics;
+    use caustics::prelude::*;
+
+    #[derive(Caustics, Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "reviews")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = true)]
+        pub id: i32,
+        #[sea_orm(column_name = "bookTitle")]
+        pub book_title: String,
+        #[sea_orm(column_name = "bookAuthorId")]
+        pub book_author_id: i32,
+        pub rating: i32,
+        pub body: String,
+    }
+
+    #[derive(Caustics, Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "super::book::Entity",
+            from = "(Column::BookTitle, Column::BookAuthorId)",
+            to = "(super::book::Column::Title, super::book::Column::AuthorId)"
+        )]
+        Book,
+    }
+
+    impl Related<super::book::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Book.def()
+        }
+    }
 
+    impl ActiveModelBehavior for ActiveModel {}
+}
 
 #[caustics]
 pub mod api_key {
@@ -119,6 +204,7 @@ pub mod api_key {
         pub updated_at: NaiveDateTime,
         pub deleted: bool,
         pub deleted_at: Option<NaiveDateTime>,
+        pub verified: Option<bool>,
     }
 
     #[derive(Caustics, Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -170,4 +256,106 @@ pub mod profile {
     }
 
     impl ActiveModelBehavior for ActiveModel {}
-}
\ No newline at end of file
+}
+
+#[caustics]
+pub mod loan {
+    use caustics_macros::Caustics;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Caustics, Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "archive", table_name = "loans")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = true)]
+        pub id: i32,
+        pub borrower: String,
+        pub author_id: i32,
+    }
+
+    #[derive(Caustics, Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "super::author::Entity",
+            from = "Column::AuthorId",
+            to = "super::author::Column::Id"
+        )]
+        Author,
+    }
+
+    impl Related<super::author::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Author.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[caustics]
+pub mod subscriber {
+    use super::Email;
+    use caustics_macros::Caustics;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Caustics, Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "subscribers")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub email: Email,
+        pub name: String,
+        #[sea_orm(nullable)]
+        pub backup_email: Option<Email>,
+    }
+
+    #[derive(Caustics, Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            has_many = "super::newsletter::Entity",
+            from = "Column::Email",
+            to = "super::newsletter::Column::SubscriberEmail"
+        )]
+        Newsletters,
+    }
+
+    impl Related<super::newsletter::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Newsletters.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[caustics]
+pub mod newsletter {
+    use super::Email;
+    use caustics_macros::Caustics;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Caustics, Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "newsletters")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = true)]
+        pub id: i32,
+        pub topic: String,
+        pub subscriber_email: Email,
+    }
+
+    #[derive(Caustics, Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "super::subscriber::Entity",
+            from = "Column::SubscriberEmail",
+            to = "super::subscriber::Column::Email"
+        )]
+        Subscriber,
+    }
+
+    impl Related<super::subscriber::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Subscriber.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
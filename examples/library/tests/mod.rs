@@ -1,8 +1,15 @@
 use library::*;
 use sea_orm::{Database, DatabaseConnection, DbErr, ConnectionTrait};
-use library::entities::{author, book, api_key, profile};
+use library::entities::{author, book, api_key, profile, review, loan, subscriber, newsletter, Email};
 use caustics::SortOrder;
 
+/// Database for the `#[ignore]`d Postgres tests, run with
+/// `CAUSTICS_TEST_POSTGRES_URL=postgres://... cargo test -- --ignored`
+fn postgres_url() -> String {
+    std::env::var("CAUSTICS_TEST_POSTGRES_URL")
+        .expect("CAUSTICS_TEST_POSTGRES_URL must point at a Postgres database")
+}
+
 async fn setup_db() -> Result<DatabaseConnection, DbErr> {
     use sea_orm::Schema;
     
@@ -32,6 +39,20 @@ async fn setup_db() -> Result<DatabaseConnection, DbErr> {
     profile_table.if_not_exists();
     db.execute(db.get_database_backend().build(&profile_table)).await?;
 
+    // Create reviews table
+    let mut review_table = schema.create_table_from_entity(library::entities::review::Entity);
+    review_table.if_not_exists();
+    db.execute(db.get_database_backend().build(&review_table)).await?;
+
+    // Create subscribers and newsletters tables (custom Email key type)
+    let mut subscriber_table = schema.create_table_from_entity(library::entities::subscriber::Entity);
+    subscriber_table.if_not_exists();
+    db.execute(db.get_database_backend().build(&subscriber_table)).await?;
+
+    let mut newsletter_table = schema.create_table_from_entity(library::entities::newsletter::Entity);
+    newsletter_table.if_not_exists();
+    db.execute(db.get_database_backend().build(&newsletter_table)).await?;
+
     Ok(db)
 }
 
@@ -854,6 +875,7 @@ async fn test_custom_field_name_verification() -> Result<(), DbErr> {
 }
 
 #[tokio::test]
+#[allow(clippy::bool_assert_comparison)]
 async fn test_has_one_relation_compilation() -> Result<(), DbErr> {
     let db = setup_db().await?;
     let client = CausticsClient::new(db.clone());
@@ -1128,3 +1150,1920 @@ async fn test_nullable_has_one_with_optional_target_fk() -> Result<(), DbErr> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_composite_primary_key_id_equals_and_relations() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    let author_client = client.author();
+    let book_client = client.book();
+    let review_client = client.review();
+
+    let now = chrono::Utc::now();
+    let author = author_client.create(
+        "Ursula".to_string(),
+        "Le Guin".to_string(),
+        "ursula@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+
+    book_client.create(
+        "The Dispossessed".to_string(),
+        author.id,
+        1974,
+        serde_json::json!(["Science Fiction"]),
+        vec![]
+    ).exec().await?;
+    book_client.create(
+        "The Lathe of Heaven".to_string(),
+        author.id,
+        1971,
+        serde_json::json!(["Science Fiction"]),
+        vec![]
+    ).exec().await?;
+
+    // Unique lookup through the composite `id` helper
+    let found = book_client
+        .find_unique(book::id::equals(("The Dispossessed", author.id)))
+        .exec()
+        .await?
+        .expect("Book should be found by composite id");
+    assert_eq!(found.title, "The Dispossessed");
+    assert_eq!(found.author_id, author.id);
+
+    let missing = book_client
+        .find_unique(book::id::equals(("The Dispossessed", author.id + 1)))
+        .exec()
+        .await?;
+    assert!(missing.is_none());
+
+    // The composite id also works as a regular filter
+    let filtered = book_client
+        .find_many(vec![book::id::equals(("The Lathe of Heaven", author.id))])
+        .exec()
+        .await?;
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].publication_year, 1971);
+
+    // Reviews reference their book through both key columns
+    review_client.create(
+        5,
+        "An ambiguous utopia".to_string(),
+        book::id::equals(("The Dispossessed", author.id)),
+        vec![]
+    ).exec().await?;
+    review_client.create(
+        4,
+        "Still thinking about it".to_string(),
+        book::id::equals(("The Dispossessed", author.id)),
+        vec![]
+    ).exec().await?;
+    review_client.create(
+        3,
+        "Dreamy".to_string(),
+        book::id::equals(("The Lathe of Heaven", author.id)),
+        vec![]
+    ).exec().await?;
+
+    // has_many joined on (title, author_id)
+    let book_with_reviews = book_client
+        .find_unique(book::id::equals(("The Dispossessed", author.id)))
+        .with(book::reviews::include(|rel| rel))
+        .exec()
+        .await?
+        .expect("Book should exist");
+    let reviews = book_with_reviews.reviews.expect("Reviews should be fetched");
+    assert_eq!(reviews.len(), 2);
+    assert!(reviews.iter().all(|r| r.book_title == "The Dispossessed"));
+
+    // Child filters beyond equality apply to composite-key children too
+    for filters in [
+        vec![review::body::contains("utopia".to_string())],
+        vec![review::rating::gt(4)],
+        vec![review::rating::in_vec(vec![5, 3])],
+    ] {
+        let book_with_reviews = book_client
+            .find_unique(book::id::equals(("The Dispossessed", author.id)))
+            .with(book::reviews::include(|rel| rel.filter(filters)))
+            .exec()
+            .await?
+            .expect("Book should exist");
+        let reviews = book_with_reviews.reviews.expect("Reviews should be fetched");
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].body, "An ambiguous utopia");
+    }
+
+    // belongs_to joined on (book_title, book_author_id)
+    let review_with_book = review_client
+        .find_first(vec![review::rating::equals(3)])
+        .with(review::book::include(|rel| rel))
+        .exec()
+        .await?
+        .expect("Review should exist");
+    let parent = review_with_book.book.expect("Book should be fetched");
+    assert_eq!(parent.title, "The Lathe of Heaven");
+    assert_eq!(parent.author_id, author.id);
+
+    Ok(())
+}
+
+#[test]
+fn test_composite_primary_key_metadata() {
+    let book_metadata = get_entity_metadata("Book").expect("Book metadata should exist");
+    assert!(book_metadata.is_composite_primary_key());
+    assert_eq!(book_metadata.primary_key_field, "title");
+    assert_eq!(book_metadata.primary_key_fields, &["title", "author_id"]);
+    assert_eq!(book_metadata.primary_key_type_of("author_id"), Some("i32"));
+
+    let review_metadata = get_entity_metadata("Review").expect("Review metadata should exist");
+    assert!(!review_metadata.is_composite_primary_key());
+    assert_eq!(review_metadata.primary_key_fields, &["id"]);
+    assert_eq!(review_metadata.foreign_key_fields, &["book_title", "book_author_id"]);
+}
+
+#[test]
+fn test_schema_qualified_table_metadata() {
+    let loan_metadata = get_entity_metadata("Loan").expect("Loan metadata should exist");
+    assert_eq!(loan_metadata.table_name, "loans");
+    assert_eq!(loan_metadata.schema_name, Some("archive"));
+    assert_eq!(loan_metadata.qualified_table_name(), "\"archive\".\"loans\"");
+
+    let author_metadata = get_entity_metadata("Author").expect("Author metadata should exist");
+    assert_eq!(author_metadata.schema_name, None);
+    assert_eq!(author_metadata.qualified_table_name(), "\"authors\"");
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_schema_qualified_table_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    let db = Database::connect(url.as_str()).await?;
+    db.execute_unprepared("DROP SCHEMA IF EXISTS archive CASCADE").await?;
+    db.execute_unprepared("DROP TABLE IF EXISTS authors CASCADE").await?;
+    db.execute_unprepared("CREATE SCHEMA archive").await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let loan_table = schema.create_table_from_entity(loan::Entity);
+    db.execute(db.get_database_backend().build(&loan_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    let reader = client.author().create(
+        "Avid".to_string(),
+        "Reader".to_string(),
+        "avid@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+    let casual = client.author().create(
+        "Casual".to_string(),
+        "Reader".to_string(),
+        "casual@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+
+    for borrower in ["alice", "bob"] {
+        client.loan().create(borrower.to_string(), author::id::equals(reader.id), vec![]).exec().await?;
+    }
+    client.loan().create("carol".to_string(), author::id::equals(casual.id), vec![]).exec().await?;
+
+    let loans = client
+        .loan()
+        .find_many(vec![loan::author_id::equals(reader.id)])
+        .exec()
+        .await?;
+    assert_eq!(loans.len(), 2);
+
+    // Relation count ordering reads from "archive"."loans"
+    let authors = client
+        .author()
+        .find_many(vec![])
+        .order_by(author::loans::count(SortOrder::Desc))
+        .exec()
+        .await?;
+    assert_eq!(authors[0].id, reader.id);
+    assert_eq!(authors[1].id, casual.id);
+
+    let author_with_loans = client
+        .author()
+        .find_unique(author::id::equals(casual.id))
+        .with(author::loans::include(|rel| rel))
+        .exec()
+        .await?
+        .expect("Author should exist");
+    let loans = author_with_loans.loans.expect("Loans should be fetched");
+    assert_eq!(loans.len(), 1);
+    assert_eq!(loans[0].borrower, "carol");
+
+    let loan_with_author = client
+        .loan()
+        .find_first(vec![loan::borrower::equals("carol")])
+        .with(loan::author::include(|rel| rel))
+        .exec()
+        .await?
+        .expect("Loan should exist");
+    assert_eq!(loan_with_author.author.expect("Author should be fetched").id, casual.id);
+
+    db.execute_unprepared("DROP SCHEMA archive CASCADE").await?;
+    db.execute_unprepared("DROP TABLE authors CASCADE").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_atomic_list_push_and_remove() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Shirley".to_string(),
+        "Jackson".to_string(),
+        "shirley@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+    client.book().create(
+        "The Haunting of Hill House".to_string(),
+        author.id,
+        1959,
+        serde_json::json!(["Gothic", "Fiction"]),
+        vec![]
+    ).exec().await?;
+    let key = book::UniqueWhereParam::TitleAndAuthorId("The Haunting of Hill House".to_string(), author.id);
+
+    let updated = client
+        .book()
+        .update(key.clone(), vec![book::genres::push("Horror")])
+        .exec()
+        .await?;
+    assert_eq!(updated.genres, serde_json::json!(["Gothic", "Fiction", "Horror"]));
+
+    // Every occurrence is removed and the remaining order is kept
+    let updated = client
+        .book()
+        .update(key.clone(), vec![book::genres::push("Gothic")])
+        .exec()
+        .await?;
+    assert_eq!(updated.genres, serde_json::json!(["Gothic", "Fiction", "Horror", "Gothic"]));
+    let updated = client
+        .book()
+        .update(key.clone(), vec![book::genres::remove_element("Gothic")])
+        .exec()
+        .await?;
+    assert_eq!(updated.genres, serde_json::json!(["Fiction", "Horror"]));
+
+    // Removing the last element leaves an empty array
+    let updated = client
+        .book()
+        .update(key.clone(), vec![book::genres::remove_element("Fiction"), book::publication_year::set(1960)])
+        .exec()
+        .await?;
+    assert_eq!(updated.publication_year, 1960);
+    let updated = client
+        .book()
+        .update(key, vec![book::genres::remove_element("Horror")])
+        .exec()
+        .await?;
+    assert_eq!(updated.genres, serde_json::json!([]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_custom_scalar_type_keys_and_filters() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+
+    let ada = client.subscriber().create(
+        Email("ada@example.com".to_string()),
+        "Ada".to_string(),
+        vec![subscriber::backup_email::set(Some(Email("ada@backup.example.com".to_string())))]
+    ).exec().await?;
+    assert_eq!(ada.email, Email("ada@example.com".to_string()));
+    client.subscriber().create(
+        Email("grace@example.com".to_string()),
+        "Grace".to_string(),
+        vec![]
+    ).exec().await?;
+
+    // Primary key lookups bind the newtype as its underlying text
+    let found = client
+        .subscriber()
+        .find_unique(subscriber::email::equals(Email("grace@example.com".to_string())))
+        .exec()
+        .await?
+        .expect("Subscriber should exist");
+    assert_eq!(found.name, "Grace");
+
+    // Filters accept the newtype directly
+    let with_backup = client
+        .subscriber()
+        .find_many(vec![subscriber::backup_email::equals(Some(Email("ada@backup.example.com".to_string())))])
+        .exec()
+        .await?;
+    assert_eq!(with_backup.len(), 1);
+    assert_eq!(with_backup[0].name, "Ada");
+
+    let listed = client
+        .subscriber()
+        .find_many(vec![subscriber::name::in_vec(vec!["Ada".to_string(), "Grace".to_string()])])
+        .order_by(subscriber::email::order(SortOrder::Asc))
+        .exec()
+        .await?;
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0].email, Email("ada@example.com".to_string()));
+
+    // Foreign keys of the custom type are converted back from the relation key
+    client.newsletter().create(
+        "Compilers".to_string(),
+        subscriber::email::equals(Email("ada@example.com".to_string())),
+        vec![]
+    ).exec().await?;
+    client.newsletter().create(
+        "Engines".to_string(),
+        subscriber::email::equals(Email("ada@example.com".to_string())),
+        vec![]
+    ).exec().await?;
+
+    let newsletters = client
+        .newsletter()
+        .find_many(vec![newsletter::subscriber_email::equals(Email("ada@example.com".to_string()))])
+        .exec()
+        .await?;
+    assert_eq!(newsletters.len(), 2);
+
+    let ada_with_newsletters = client
+        .subscriber()
+        .find_unique(subscriber::email::equals(Email("ada@example.com".to_string())))
+        .with(subscriber::newsletters::include(|rel| rel))
+        .exec()
+        .await?
+        .expect("Subscriber should exist");
+    assert_eq!(ada_with_newsletters.newsletters.expect("Newsletters should be fetched").len(), 2);
+
+    let newsletter_with_subscriber = client
+        .newsletter()
+        .find_first(vec![newsletter::topic::equals("Engines")])
+        .with(newsletter::subscriber::include(|rel| rel))
+        .exec()
+        .await?
+        .expect("Newsletter should exist");
+    let subscriber = newsletter_with_subscriber.subscriber.expect("Subscriber should be fetched");
+    assert_eq!(subscriber.email, Email("ada@example.com".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_scalar_type_conversions() {
+    use caustics::{FromCausticsValue, ToCausticsValue};
+
+    let email = Email("linus@example.com".to_string());
+    let key = email.to_caustics_key();
+    assert_eq!(key, caustics::CausticsKey::String("linus@example.com".to_string()));
+    assert_eq!(Email::from_caustics_key(key), Some(email));
+    assert_eq!(Email::from_caustics_key(caustics::CausticsKey::I32(7)), None);
+
+    // Metadata keeps the custom type's name instead of assuming String
+    let subscriber_metadata = get_entity_metadata("Subscriber").expect("Subscriber metadata should exist");
+    assert_eq!(subscriber_metadata.primary_key_type, "Email");
+    let newsletter_metadata = get_entity_metadata("Newsletter").expect("Newsletter metadata should exist");
+    assert_eq!(newsletter_metadata.primary_key_type_of("id"), Some("i32"));
+    assert!(newsletter_metadata
+        .foreign_key_types
+        .contains(&("subscriber_email", "Email")));
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_atomic_updates_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS atomic_updates CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA atomic_updates").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Datomic_updates", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let api_key_table = schema.create_table_from_entity(api_key::Entity);
+    db.execute(db.get_database_backend().build(&api_key_table)).await?;
+    let book_table = schema.create_table_from_entity(book::Entity);
+    db.execute(db.get_database_backend().build(&book_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Ada".to_string(),
+        "Lovelace".to_string(),
+        "ada@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+
+    // Camel-cased column names are quoted in the generated expressions
+    let author = client
+        .author()
+        .update(author::id::equals(author.id), vec![author::last_name::append(" II"), author::first_name::prepend("Lady ")])
+        .exec()
+        .await?;
+    assert_eq!(author.first_name, "Lady Ada");
+    assert_eq!(author.last_name, "Lovelace II");
+
+    client.api_key().create(
+        "key-1".to_string(),
+        "secret".to_string(),
+        "*".to_string(),
+        serde_json::json!({"rate_limit": 10, "scopes": ["read"]}),
+        now.naive_utc(),
+        now.naive_utc(),
+        false,
+        author::id::equals(author.id),
+        vec![]
+    ).exec().await?;
+
+    let key = client
+        .api_key()
+        .update(api_key::id::equals("key-1".to_string()), vec![api_key::options::merge(serde_json::json!({"rate_limit": 20}))])
+        .exec()
+        .await?;
+    assert_eq!(key.options, serde_json::json!({"rate_limit": 20, "scopes": ["read"]}));
+
+    let key = client
+        .api_key()
+        .update(api_key::id::equals("key-1".to_string()), vec![api_key::options::remove_key(vec!["scopes"])])
+        .exec()
+        .await?;
+    assert_eq!(key.options, serde_json::json!({"rate_limit": 20}));
+
+    client.book().create(
+        "Notes".to_string(),
+        author.id,
+        1843,
+        serde_json::json!(["Essay"]),
+        vec![]
+    ).exec().await?;
+    let book_key = book::UniqueWhereParam::TitleAndAuthorId("Notes".to_string(), author.id);
+    let book = client
+        .book()
+        .update(book_key.clone(), vec![book::genres::push("Mathematics")])
+        .exec()
+        .await?;
+    assert_eq!(book.genres, serde_json::json!(["Essay", "Mathematics"]));
+    let book = client
+        .book()
+        .update(book_key, vec![book::genres::remove_element("Essay")])
+        .exec()
+        .await?;
+    assert_eq!(book.genres, serde_json::json!(["Mathematics"]));
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS atomic_updates CASCADE").await?;
+    Ok(())
+}
+
+/// Books per author, distinct on author: the kept row follows the current ordering
+async fn assert_distinct_on_fields(client: &CausticsClient) -> Result<(), DbErr> {
+    use library::entities::book::DistinctFieldsExt;
+
+    let now = chrono::Utc::now();
+    let mut authors = Vec::new();
+    for (first, email) in [("Ada", "ada@example.com"), ("Bob", "bob@example.com")] {
+        let author = client.author().create(
+            first.to_string(),
+            "Writer".to_string(),
+            email.to_string(),
+            now,
+            now,
+            vec![]
+        ).exec().await?;
+        authors.push(author.id);
+    }
+    for (title, author_id, year) in [
+        ("A", authors[0], 1990),
+        ("B", authors[0], 2000),
+        ("C", authors[1], 1980),
+        ("D", authors[1], 2010),
+        ("E", authors[1], 1995),
+    ] {
+        client.book().create(title.to_string(), author_id, year, serde_json::json!([]), vec![]).exec().await?;
+    }
+
+    let titles = |books: Vec<book::ModelWithRelations>| books.into_iter().map(|b| b.title).collect::<Vec<_>>();
+
+    // Newest book per author
+    let newest = client
+        .book()
+        .find_many(vec![])
+        .order_by(book::publication_year::order(SortOrder::Desc))
+        .distinct_on_fields(vec![book::ScalarField::AuthorId])
+        .exec()
+        .await?;
+    assert_eq!(titles(newest), vec!["D", "B"]);
+
+    // Oldest book per author; take applies after de-duplication
+    let oldest = client
+        .book()
+        .find_many(vec![])
+        .order_by(book::publication_year::order(SortOrder::Asc))
+        .distinct_on_fields(vec![book::ScalarField::AuthorId])
+        .exec()
+        .await?;
+    assert_eq!(titles(oldest), vec!["C", "A"]);
+    let first_only = client
+        .book()
+        .find_many(vec![])
+        .order_by(book::publication_year::order(SortOrder::Asc))
+        .take(1)
+        .distinct_on_fields(vec![book::ScalarField::AuthorId])
+        .exec()
+        .await?;
+    assert_eq!(titles(first_only), vec!["C"]);
+
+    // Filters still apply before picking the row per group
+    let filtered = client
+        .book()
+        .find_many(vec![book::publication_year::lt(2000)])
+        .order_by(book::publication_year::order(SortOrder::Desc))
+        .distinct_on_fields(vec![book::ScalarField::AuthorId])
+        .exec()
+        .await?;
+    assert_eq!(titles(filtered), vec!["E", "A"]);
+
+    // Without an order_by the primary key decides, so repeated runs agree
+    let unordered = client
+        .book()
+        .find_many(vec![])
+        .distinct_on_fields(vec![book::ScalarField::AuthorId])
+        .exec()
+        .await?;
+    let mut unordered = titles(unordered);
+    unordered.sort();
+    assert_eq!(unordered, vec!["A", "C"]);
+
+    // Typed columns pick the same rows
+    let by_column = client
+        .book()
+        .find_many(vec![])
+        .order_by(book::publication_year::order(SortOrder::Desc))
+        .distinct_on_columns(vec![book::Column::AuthorId])
+        .exec()
+        .await?;
+    assert_eq!(titles(by_column), vec!["D", "B"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_distinct_on_fields_keeps_first_row_per_group() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    assert_distinct_on_fields(&client).await
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_distinct_on_fields_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS distinct_on_fields CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA distinct_on_fields").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Ddistinct_on_fields", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let book_table = schema.create_table_from_entity(book::Entity);
+    db.execute(db.get_database_backend().build(&book_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    assert_distinct_on_fields(&client).await?;
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS distinct_on_fields CASCADE").await?;
+    Ok(())
+}
+
+async fn assert_distinct_on_latest_per_name(client: &CausticsClient) -> Result<(), DbErr> {
+    use chrono::TimeZone;
+
+    for (first, email, year) in [
+        ("Ada", "ada1@example.com", 2020),
+        ("Ada", "ada2@example.com", 2023),
+        ("Bob", "bob1@example.com", 2022),
+        ("Bob", "bob2@example.com", 2021),
+        ("Cy", "cy@example.com", 2019),
+    ] {
+        let at = chrono::Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
+        client.author().create(
+            first.to_string(),
+            "Writer".to_string(),
+            email.to_string(),
+            at,
+            at,
+            vec![]
+        ).exec().await?;
+    }
+
+    // Newest author per first name: the distinct field leads, created_at picks the winner
+    let latest = client
+        .author()
+        .find_many(vec![])
+        .distinct_on(vec![author::ScalarField::FirstName])
+        .order_by(author::first_name::order(SortOrder::Asc))
+        .order_by(author::created_at::order(SortOrder::Desc))
+        .exec()
+        .await?;
+    let emails: Vec<_> = latest.into_iter().map(|a| a.email).collect();
+    assert_eq!(emails, vec!["ada2@example.com", "bob1@example.com", "cy@example.com"]);
+
+    // Without an order_by any row per group may win, but there is still one per name
+    let any = client
+        .author()
+        .find_many(vec![])
+        .distinct_on(vec![author::ScalarField::FirstName])
+        .exec()
+        .await?;
+    assert_eq!(any.len(), 3);
+
+    // Ordering by anything but the distinct fields first is rejected
+    let err = client
+        .author()
+        .find_many(vec![])
+        .distinct_on(vec![author::ScalarField::FirstName])
+        .order_by(author::created_at::order(SortOrder::Desc))
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("CausticsError::QueryValidation"), "{}", err);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_distinct_on_latest_per_name() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    assert_distinct_on_latest_per_name(&client).await
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_distinct_on_latest_per_name_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS distinct_on_latest CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA distinct_on_latest").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Ddistinct_on_latest", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    assert_distinct_on_latest_per_name(&client).await?;
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS distinct_on_latest CASCADE").await?;
+    Ok(())
+}
+
+async fn create_truncate_authors(client: &CausticsClient) -> Result<Vec<i32>, DbErr> {
+    let now = chrono::Utc::now();
+    let mut ids = Vec::new();
+    for name in ["Ann", "Ben"] {
+        let author = client.author().create(
+            name.to_string(),
+            "Reset".to_string(),
+            format!("{}@example.com", name.to_lowercase()),
+            now,
+            now,
+            vec![],
+        ).exec().await?;
+        ids.push(author.id);
+    }
+    Ok(ids)
+}
+
+#[tokio::test]
+async fn test_primary_keys_collect_into_hash_set() -> Result<(), DbErr> {
+    use caustics::CausticsKey;
+    use std::collections::HashSet;
+
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+
+    let now = chrono::Utc::now();
+    let author = client
+        .author()
+        .create("Iain".to_string(), "Banks".to_string(), "iain@example.com".to_string(), now, now, vec![])
+        .exec()
+        .await?;
+    for (title, year) in [("Excession", 1996), ("Look to Windward", 2000)] {
+        client
+            .book()
+            .create(title.to_string(), author.id, year, serde_json::json!([]), vec![])
+            .exec()
+            .await?;
+    }
+
+    // Fetching the same rows twice dedups down to one key per book
+    let mut keys: HashSet<CausticsKey> = HashSet::new();
+    for _ in 0..2 {
+        for book in client.book().find_many(vec![]).exec().await? {
+            keys.insert(book.primary_key()?);
+        }
+    }
+    assert_eq!(keys.len(), 2);
+    assert!(keys.contains(&CausticsKey::Composite(vec![
+        ("title".to_string(), CausticsKey::String("Excession".to_string())),
+        ("author_id".to_string(), CausticsKey::I32(author.id)),
+    ])));
+
+    let author_keys: HashSet<CausticsKey> = client
+        .author()
+        .find_many(vec![])
+        .exec()
+        .await?
+        .iter()
+        .map(|a| a.primary_key())
+        .collect::<Result<_, _>>()?;
+    assert_eq!(author_keys, HashSet::from([CausticsKey::I32(author.id)]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_all_tables_then_insert_into_each() -> Result<(), DbErr> {
+    let db = Database::connect("sqlite::memory:?mode=rwc").await?;
+    // Loans live in the "archive" schema, which SQLite only has once a database is attached under that name
+    db.execute_unprepared("ATTACH DATABASE ':memory:' AS archive").await?;
+    let client = CausticsClient::new(db.clone());
+    client.create_all_tables().await?;
+    // Tables that already exist are left alone
+    client.create_all_tables().await?;
+
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Table".to_string(),
+        "Setup".to_string(),
+        "tables@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+    client.book().create("Schema".to_string(), author.id, 2024, serde_json::json!([]), vec![]).exec().await?;
+    client.review().create(5, "Saves typing".to_string(), book::id::equals(("Schema", author.id)), vec![]).exec().await?;
+    client.api_key().create(
+        "key-tables".to_string(),
+        "secret".to_string(),
+        "*".to_string(),
+        serde_json::json!({}),
+        now.naive_utc(),
+        now.naive_utc(),
+        false,
+        author::id::equals(author.id),
+        vec![]
+    ).exec().await?;
+    client.profile().create(now.naive_utc(), now.naive_utc(), author::id::equals(author.id), vec![]).exec().await?;
+    // SQLite resolves the foreign key of "archive"."loans" inside "archive", so no loan
+    // can point at an author here; the table itself is still created
+    assert!(client.loan().create("reader".to_string(), author::id::equals(author.id), vec![]).exec().await.is_err());
+    client.subscriber().create(Email("tables@example.com".to_string()), "Tables".to_string(), vec![]).exec().await?;
+    client.newsletter().create(
+        "Setup".to_string(),
+        subscriber::email::equals(Email("tables@example.com".to_string())),
+        vec![]
+    ).exec().await?;
+
+    assert_eq!(client.author().count(vec![]).exec().await?, 1);
+    assert_eq!(client.book().count(vec![]).exec().await?, 1);
+    assert_eq!(client.review().count(vec![]).exec().await?, 1);
+    assert_eq!(client.api_key().count(vec![]).exec().await?, 1);
+    assert_eq!(client.profile().count(vec![]).exec().await?, 1);
+    assert_eq!(client.loan().count(vec![]).exec().await?, 0);
+    assert_eq!(client.subscriber().count(vec![]).exec().await?, 1);
+    assert_eq!(client.newsletter().count(vec![]).exec().await?, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_truncate_empties_table() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+
+    create_truncate_authors(&client).await?;
+    assert_eq!(client.author().count(vec![]).exec().await?, 2);
+
+    client.author().truncate().await?;
+    assert_eq!(client.author().count(vec![]).exec().await?, 0);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_truncate_resets_ids_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS truncate_reset CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA truncate_reset").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dtruncate_reset", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let first_ids = create_truncate_authors(&client).await?;
+
+    client.author().truncate().await?;
+    assert_eq!(client.author().count(vec![]).exec().await?, 0);
+
+    // The id sequence starts over
+    let ids = create_truncate_authors(&client).await?;
+    assert_eq!(ids, first_ids);
+    assert_eq!(ids[0], 1);
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS truncate_reset CASCADE").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_boolean_is_true_is_false_is_unknown() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+
+    let now = chrono::Utc::now();
+    let keys = [
+        ("key-verified", false, Some(true)),
+        ("key-rejected", true, Some(false)),
+        ("key-pending", false, None),
+    ];
+    for (index, (id, deleted, verified)) in keys.iter().enumerate() {
+        let author = client.author().create(
+            format!("Owner{}", index),
+            "Keys".to_string(),
+            format!("owner{}@example.com", index),
+            now,
+            now,
+            vec![],
+        ).exec().await?;
+        client.api_key().create(
+            id.to_string(),
+            format!("secret-{}", index),
+            "https://example.com".to_string(),
+            serde_json::json!({}),
+            now.naive_utc(),
+            now.naive_utc(),
+            *deleted,
+            author::id::equals(author.id),
+            vec![api_key::verified::set(*verified)],
+        ).exec().await?;
+    }
+
+    let deleted = client.api_key().find_many(vec![api_key::deleted::is_true()]).exec().await?;
+    assert_eq!(deleted.iter().map(|k| k.id.as_str()).collect::<Vec<_>>(), vec!["key-rejected"]);
+
+    let mut active: Vec<String> = client.api_key().find_many(vec![api_key::deleted::is_false()]).exec().await?
+        .into_iter().map(|k| k.id).collect();
+    active.sort();
+    assert_eq!(active, vec!["key-pending".to_string(), "key-verified".to_string()]);
+
+    let verified = client.api_key().find_many(vec![api_key::verified::is_true()]).exec().await?;
+    assert_eq!(verified.iter().map(|k| k.id.as_str()).collect::<Vec<_>>(), vec!["key-verified"]);
+
+    // An unknown value is neither true nor false
+    let rejected = client.api_key().find_many(vec![api_key::verified::is_false()]).exec().await?;
+    assert_eq!(rejected.iter().map(|k| k.id.as_str()).collect::<Vec<_>>(), vec!["key-rejected"]);
+
+    let pending = client.api_key().find_many(vec![api_key::verified::is_unknown()]).exec().await?;
+    assert_eq!(pending.iter().map(|k| k.id.as_str()).collect::<Vec<_>>(), vec!["key-pending"]);
+    assert!(pending[0].verified.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_json_path_match_requires_postgres() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+
+    let err = client
+        .book()
+        .find_many(vec![book::genres::json_path_match(r#"$[*] ? (@ == "Fantasy")"#)])
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("CausticsError::QueryValidation: json_path_match requires Postgres"),
+        "{}",
+        err
+    );
+
+    // Nested in a logical filter, and on the count path as well
+    let err = client
+        .book()
+        .count(vec![book::or(vec![book::genres::json_path_match("$[*]")])])
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("json_path_match requires Postgres"), "{}", err);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_json_path_match_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS json_path_match CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA json_path_match").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Djson_path_match", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let book_table = schema.create_table_from_entity(book::Entity);
+    db.execute(db.get_database_backend().build(&book_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    let author = client
+        .author()
+        .create("Path".to_string(), "Author".to_string(), "path@example.com".to_string(), now, now, vec![])
+        .exec()
+        .await?;
+    for (title, genres) in [
+        ("Earthsea", serde_json::json!(["Fantasy", "Young Adult"])),
+        ("Solaris", serde_json::json!(["Science Fiction"])),
+        ("Untagged", serde_json::json!([])),
+    ] {
+        client.book().create(title.to_string(), author.id, 1970, genres, vec![]).exec().await?;
+    }
+
+    let fantasy = client
+        .book()
+        .find_many(vec![book::genres::json_path_match(r#"$[*] ? (@ == "Fantasy")"#)])
+        .exec()
+        .await?;
+    assert_eq!(fantasy.len(), 1);
+    assert_eq!(fantasy[0].title, "Earthsea");
+
+    // Predicates compose with the other filters
+    let tagged = client
+        .book()
+        .find_many(vec![
+            book::genres::json_path_match("$[0]"),
+            book::title::not_equals("Solaris".to_string()),
+        ])
+        .exec()
+        .await?;
+    assert_eq!(tagged.len(), 1);
+    assert_eq!(tagged[0].title, "Earthsea");
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS json_path_match CASCADE").await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_native_upsert_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS native_upsert CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA native_upsert").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dnative_upsert", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let api_key_table = schema.create_table_from_entity(api_key::Entity);
+    db.execute(db.get_database_backend().build(&api_key_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Upsert".to_string(),
+        "Author".to_string(),
+        "upsert@example.com".to_string(),
+        now,
+        now,
+        vec![],
+    ).exec().await?;
+    let create = |key: &str| api_key::Create {
+        id: "native-key".to_string(),
+        key: key.to_string(),
+        allowed_origins: "*".to_string(),
+        options: serde_json::json!({}),
+        created_at: now.naive_utc(),
+        updated_at: now.naive_utc(),
+        deleted: false,
+        author: author::id::equals(author.id),
+        _params: vec![],
+    };
+
+    let inserted = client.api_key()
+        .upsert(api_key::id::equals("native-key".to_string()), create("first"), vec![])
+        .exec().await?;
+    assert_eq!(inserted.key, "first");
+
+    let updated = client.api_key()
+        .upsert(
+            api_key::id::equals("native-key".to_string()),
+            create("ignored"),
+            vec![api_key::key::set("second".to_string()), api_key::verified::set(Some(true))],
+        )
+        .exec().await?;
+    assert_eq!(updated.key, "second");
+    assert_eq!(updated.verified, Some(true));
+    assert_eq!(updated.allowed_origins, "*");
+    assert_eq!(client.api_key().count(vec![]).exec().await?, 1);
+
+    // A conflicting single insert is skipped rather than failing
+    let skipped = client.api_key()
+        .create(
+            "native-key".to_string(),
+            "third".to_string(),
+            "*".to_string(),
+            serde_json::json!({}),
+            now.naive_utc(),
+            now.naive_utc(),
+            false,
+            author::id::equals(author.id),
+            vec![],
+        )
+        .on_conflict_ignore()
+        .exec().await?;
+    assert!(skipped.is_none());
+    assert_eq!(client.api_key().count(vec![]).exec().await?, 1);
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS native_upsert CASCADE").await?;
+    Ok(())
+}
+
+async fn assert_array_param_lookup(client: &CausticsClient, db: &DatabaseConnection) -> Result<(), DbErr> {
+    #[derive(Debug, sea_orm::FromQueryResult)]
+    struct Cnt {
+        c: i64,
+    }
+
+    let now = chrono::Utc::now();
+    let mut ids = Vec::new();
+    for name in ["Ann", "Ben", "Cat"] {
+        let author = client.author().create(
+            name.to_string(),
+            "Array".to_string(),
+            format!("{}@example.com", name.to_lowercase()),
+            now,
+            now,
+            vec![]
+        ).exec().await?;
+        ids.push(author.id);
+    }
+
+    let backend = db.get_database_backend();
+    let predicate = match backend {
+        sea_orm::DatabaseBackend::Postgres => "= ANY({})",
+        _ => "IN ({})",
+    };
+    let count = |items: Vec<i32>| {
+        let (placeholder, params) = caustics::raw::array_param(backend, &items);
+        let sql = format!("SELECT COUNT(*) AS c FROM authors WHERE \"authorId\" {}", predicate.replace("{}", &placeholder));
+        client._query_raw::<Cnt>(caustics::Raw::new(sql, params))
+    };
+
+    // Postgres binds one array value whatever the item count; elsewhere the list is expanded
+    let (placeholder, params) = caustics::raw::array_param(backend, &ids[..2]);
+    if backend == sea_orm::DatabaseBackend::Postgres {
+        assert_eq!(placeholder, "?");
+        assert_eq!(params.len(), 1);
+        assert!(matches!(params[0], sea_orm::Value::Array(_, _)));
+    } else {
+        assert_eq!(placeholder, "?, ?");
+        assert_eq!(params.len(), 2);
+    }
+
+    assert_eq!(count(ids[..2].to_vec()).exec().await?[0].c, 2);
+    assert_eq!(count(ids.clone()).exec().await?[0].c, 3);
+    assert_eq!(count(vec![ids[2], -1]).exec().await?[0].c, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_array_param_expands_in_list_on_sqlite() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    assert_array_param_lookup(&client, &db).await
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_array_param_binds_array_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS array_param CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA array_param").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Darray_param", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    assert_array_param_lookup(&client, &db).await?;
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS array_param CASCADE").await?;
+    Ok(())
+}
+
+async fn assert_group_by_rollup(client: &CausticsClient) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    let mut author_ids = Vec::new();
+    for (first, email) in [("Ann", "ann@example.com"), ("Ben", "ben@example.com")] {
+        let author = client.author().create(
+            first.to_string(),
+            "Rollup".to_string(),
+            email.to_string(),
+            now,
+            now,
+            vec![]
+        ).exec().await?;
+        author_ids.push(author.id);
+    }
+    for (title, author_id, year) in [
+        ("First", author_ids[0], 2000),
+        ("Second", author_ids[0], 2000),
+        ("Third", author_ids[0], 2001),
+        ("Fourth", author_ids[1], 2000),
+    ] {
+        client.book().create(title.to_string(), author_id, year, serde_json::json!([]), vec![]).exec().await?;
+    }
+
+    let rows = client
+        .book()
+        .group_by(
+            vec![book::GroupByFieldParam::AuthorId, book::GroupByFieldParam::PublicationYear],
+            vec![],
+            vec![],
+            None,
+            None,
+            None,
+        )
+        .count("books")
+        .with_rollup()
+        .exec()
+        .await?;
+    let count_where = |author: Option<i32>, year: Option<i32>, is_total: bool| {
+        rows.iter()
+            .filter(|row| {
+                row.is_total == is_total
+                    && row.keys.get("AuthorId") == author.map(|id| id.to_string()).as_ref()
+                    && row.keys.get("PublicationYear") == year.map(|y| y.to_string()).as_ref()
+            })
+            .map(|row| row.aggregates["books"].clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(rows.len(), 6, "{:?}", rows);
+    assert_eq!(count_where(Some(author_ids[0]), Some(2000), false), vec!["2"]);
+    assert_eq!(count_where(Some(author_ids[0]), None, true), vec!["3"]);
+    assert_eq!(count_where(Some(author_ids[1]), None, true), vec!["1"]);
+    // The grand total has every key rolled up
+    assert_eq!(count_where(None, None, true), vec!["4"]);
+
+    let err = client
+        .book()
+        .group_by(vec![book::GroupByFieldParam::AuthorId], vec![], vec![], Some(1), None, None)
+        .count("books")
+        .with_rollup()
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("CausticsError::QueryValidation"), "{}", err);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_group_by_rollup_emulated() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    assert_group_by_rollup(&client).await
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_group_by_rollup_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS group_by_rollup CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA group_by_rollup").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dgroup_by_rollup", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let book_table = schema.create_table_from_entity(book::Entity);
+    db.execute(db.get_database_backend().build(&book_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    assert_group_by_rollup(&client).await?;
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS group_by_rollup CASCADE").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_for_update_is_ignored_on_sqlite() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Lock".to_string(),
+        "Free".to_string(),
+        "lock@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+
+    // SQLite has no row locks, so the clause is dropped rather than rejected
+    let found = client
+        .transaction()
+        .run(|tx| async move {
+            let query = tx.author().find_many(vec![author::id::equals(author.id)]).for_update();
+            assert!(!query.to_sql().contains("FOR UPDATE"), "{}", query.to_sql());
+            query.exec().await
+        })
+        .await?;
+    assert_eq!(found.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_for_update_blocks_other_transactions_postgres() -> Result<(), DbErr> {
+    use sea_orm::{DatabaseTransaction, Schema, TransactionTrait};
+    use std::sync::Arc;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS row_locking CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA row_locking").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Drow_locking", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Locked".to_string(),
+        "Row".to_string(),
+        "locked@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+    let backend = db.get_database_backend();
+    let finish = |tx: Arc<DatabaseTransaction>| {
+        Arc::try_unwrap(tx).map_err(|_| DbErr::Custom("transaction still shared".to_string()))
+    };
+
+    let holder = Arc::new(db.begin().await?);
+    let holder_client = TransactionCausticsClient::new(holder.clone(), backend);
+    let locked = holder_client
+        .author()
+        .find_many(vec![author::id::equals(author.id)])
+        .for_update();
+    assert!(locked.to_sql().contains("FOR UPDATE"), "{}", locked.to_sql());
+    assert_eq!(locked.exec().await?.len(), 1);
+
+    // While the row is held, another transaction can take neither lock on it
+    for share in [false, true] {
+        let waiter = Arc::new(db.begin().await?);
+        waiter.execute_unprepared("SET LOCAL lock_timeout = '200ms'").await?;
+        let waiter_client = TransactionCausticsClient::new(waiter.clone(), backend);
+        let query = waiter_client.author().find_many(vec![author::id::equals(author.id)]);
+        let query = if share { query.for_share() } else { query.for_update() };
+        let err = query.exec().await.unwrap_err();
+        assert!(err.to_string().contains("lock timeout"), "{}", err);
+        drop(waiter_client);
+        finish(waiter)?.rollback().await?;
+    }
+    // Plain reads are not blocked
+    assert_eq!(client.author().find_many(vec![author::id::equals(author.id)]).exec().await?.len(), 1);
+
+    // Committing releases the lock
+    drop(holder_client);
+    finish(holder)?.commit().await?;
+    let next = Arc::new(db.begin().await?);
+    next.execute_unprepared("SET LOCAL lock_timeout = '200ms'").await?;
+    let next_client = TransactionCausticsClient::new(next.clone(), backend);
+    let relocked = next_client
+        .author()
+        .find_many(vec![author::id::equals(author.id)])
+        .for_update()
+        .exec()
+        .await?;
+    assert_eq!(relocked.len(), 1);
+    drop(next_client);
+    finish(next)?.commit().await?;
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS row_locking CASCADE").await?;
+    Ok(())
+}
+
+/// Creates authors inside a transaction that is then rolled back, checking that `count`,
+/// `aggregate` and `group_by` on the transaction client see the uncommitted rows. With
+/// `check_outside`, the plain client is also asked mid-transaction and must not see them.
+async fn assert_reads_inside_transaction(client: &CausticsClient, check_outside: bool) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    let result: Result<(), DbErr> = client
+        .transaction()
+        .run(|tx| async move {
+            for (first, email) in [("Tx", "tx1@example.com"), ("Tx", "tx2@example.com")] {
+                tx.author().create(
+                    first.to_string(),
+                    "Uncommitted".to_string(),
+                    email.to_string(),
+                    now,
+                    now,
+                    vec![]
+                ).exec().await?;
+            }
+            let filter = || vec![author::last_name::equals("Uncommitted")];
+            assert_eq!(tx.author().count(filter()).exec().await?, 2);
+            let agg = tx.author().aggregate(filter()).count().exec().await?;
+            assert_eq!(agg.count, Some(2));
+            let rows = tx
+                .author()
+                .group_by(vec![author::GroupByFieldParam::FirstName], filter(), vec![], None, None, None)
+                .count("authors")
+                .exec()
+                .await?;
+            assert_eq!(rows.len(), 1, "{:?}", rows);
+            assert_eq!(rows[0].aggregates["authors"], "2");
+
+            if check_outside {
+                assert_eq!(client.author().count(filter()).exec().await?, 0);
+                assert_eq!(client.author().aggregate(filter()).count().exec().await?.count, Some(0));
+            }
+            Err(DbErr::Custom("roll back".to_string()))
+        })
+        .await;
+    assert!(result.is_err());
+
+    let filter = vec![author::last_name::equals("Uncommitted")];
+    assert_eq!(client.author().count(filter).exec().await?, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reads_inside_transaction_see_uncommitted_rows() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    // In-memory SQLite has a single connection, held by the transaction, so the outside
+    // check would wait on it
+    assert_reads_inside_transaction(&client, false).await
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_reads_inside_transaction_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS tx_reads CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA tx_reads").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dtx_reads", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    assert_reads_inside_transaction(&client, true).await?;
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS tx_reads CASCADE").await?;
+    Ok(())
+}
+
+async fn assert_partial_unique_upsert(client: &CausticsClient, db: &DatabaseConnection) -> Result<(), DbErr> {
+    use api_key::UpsertConflictExt;
+
+    db.execute_unprepared(r#"CREATE UNIQUE INDEX "api_key_live_key" ON "ApiKey" ("key") WHERE "deleted_at" IS NULL"#)
+        .await?;
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Partial".to_string(),
+        "Index".to_string(),
+        "partial@example.com".to_string(),
+        now,
+        now,
+        vec![],
+    ).exec().await?;
+    let create = |id: &str, origins: &str| api_key::Create {
+        id: id.to_string(),
+        key: "shared-key".to_string(),
+        allowed_origins: origins.to_string(),
+        options: serde_json::json!({}),
+        created_at: now.naive_utc(),
+        updated_at: now.naive_utc(),
+        deleted: false,
+        author: author::id::equals(author.id),
+        _params: vec![],
+    };
+    let upsert = |id: &str, origins: &str| {
+        client.api_key()
+            .upsert(
+                api_key::id::equals(id.to_string()),
+                create(id, origins),
+                vec![api_key::allowed_origins::set(origins.to_string())],
+            )
+            .on_conflict(vec![api_key::Column::Key], Some(vec![api_key::deleted_at::is_null()]))
+    };
+
+    let first = upsert("key-a", "a.example").exec().await?;
+    assert_eq!(first.id, "key-a");
+    // Same live key under another id: the partial index conflicts and the live row is updated
+    let second = upsert("key-b", "b.example").exec().await?;
+    assert_eq!(second.id, "key-a");
+    assert_eq!(second.allowed_origins, "b.example");
+    assert_eq!(client.api_key().count(vec![]).exec().await?, 1);
+
+    // Without the predicate the target matches no unique index
+    let err = client.api_key()
+        .upsert(api_key::id::equals("key-x".to_string()), create("key-x", "x.example"), vec![])
+        .on_conflict(vec![api_key::Column::Key], None)
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().to_lowercase().contains("conflict"), "{}", err);
+
+    // Once the live row is retired the same key is free again
+    client.api_key()
+        .update(api_key::id::equals("key-a".to_string()), vec![api_key::deleted_at::set(Some(now.naive_utc()))])
+        .exec()
+        .await?;
+    let third = upsert("key-c", "c.example").exec().await?;
+    assert_eq!(third.id, "key-c");
+    assert_eq!(client.api_key().count(vec![]).exec().await?, 2);
+    assert_eq!(client.api_key().count(vec![api_key::deleted_at::is_null()]).exec().await?, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_upsert_partial_unique_index_sqlite() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    assert_partial_unique_upsert(&client, &db).await
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_upsert_partial_unique_index_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS partial_unique CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA partial_unique").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dpartial_unique", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let api_key_table = schema.create_table_from_entity(api_key::Entity);
+    db.execute(db.get_database_backend().build(&api_key_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    assert_partial_unique_upsert(&client, &db).await?;
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS partial_unique CASCADE").await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_contains_any_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS contains_any CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA contains_any").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dcontains_any", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    for (first, last) in [("Jane", "Doe"), ("Sam", "Smith"), ("Ada", "Lovelace"), ("Al", "100%")] {
+        client.author().create(
+            first.to_string(),
+            last.to_string(),
+            format!("{}@example.com", first.to_lowercase()),
+            now,
+            now,
+            vec![],
+        ).exec().await?;
+    }
+    let last_names = |authors: Vec<author::ModelWithRelations>| {
+        let mut names: Vec<String> = authors.into_iter().map(|a| a.last_name).collect();
+        names.sort();
+        names
+    };
+
+    // `LIKE ANY (ARRAY[...])`
+    let matched = client.author().find_many(vec![author::last_name::contains_any(vec!["oe", "mit", "0%"])]).exec().await?;
+    assert_eq!(last_names(matched), vec!["100%", "Doe", "Smith"]);
+
+    // `ILIKE ANY (ARRAY[...])`
+    let insensitive = client.author().find_many(vec![
+        author::email::mode(caustics::QueryMode::Insensitive),
+        author::email::contains_any(vec!["JANE@", "ADA@"]),
+    ]).exec().await?;
+    assert_eq!(last_names(insensitive), vec!["Doe", "Lovelace"]);
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS contains_any CASCADE").await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_group_by_json_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS group_by_json CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA group_by_json").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dgroup_by_json", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let api_key_table = schema.create_table_from_entity(api_key::Entity);
+    db.execute(db.get_database_backend().build(&api_key_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Ada".to_string(),
+        "Lovelace".to_string(),
+        "ada@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+    for (id, tier) in [("key-1", "free"), ("key-2", "pro"), ("key-3", "free")] {
+        client.api_key().create(
+            id.to_string(),
+            "secret".to_string(),
+            "*".to_string(),
+            serde_json::json!({"plan": {"tier": tier}}),
+            now.naive_utc(),
+            now.naive_utc(),
+            false,
+            author::id::equals(author.id),
+            vec![]
+        ).exec().await?;
+    }
+
+    // The extracted key must match between the select list and GROUP BY
+    let groups = client
+        .api_key()
+        .group_by_json(api_key::Column::Options, vec!["plan", "tier"], vec![])
+        .count("count")
+        .exec()
+        .await?;
+    let mut counts: Vec<(String, String)> = groups
+        .iter()
+        .map(|g| (g.keys["plan_tier"].clone(), g.aggregates["count"].clone()))
+        .collect();
+    counts.sort();
+    assert_eq!(counts, vec![("free".to_string(), "2".to_string()), ("pro".to_string(), "1".to_string())]);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_connect_postgres_statement_cache_reuses_plans() -> Result<(), DbErr> {
+    use caustics::statement_cache;
+    use sea_orm::{ConnectOptions, Schema};
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS prepared_statements CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA prepared_statements").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    // One pooled connection, so every query lands in the session holding the statements
+    let mut options = ConnectOptions::new(format!(
+        "{}{}options=-c%20search_path%3Dprepared_statements",
+        url, separator
+    ));
+    options.max_connections(1);
+
+    let prepared_author_queries = |db: DatabaseConnection| async move {
+        let rows = db.query_all(sea_orm::Statement::from_string(
+            sea_orm::DatabaseBackend::Postgres,
+            "SELECT generic_plans FROM pg_prepared_statements \
+             WHERE statement LIKE '%\"firstName\" = $1%' \
+             AND statement NOT LIKE '%pg_prepared_statements%'",
+        )).await?;
+        rows.iter().map(|row| row.try_get::<i64>("", "generic_plans")).collect::<Result<Vec<_>, _>>()
+    };
+
+    let db = statement_cache::connect_postgres(options.clone(), 100).await?;
+    let schema = Schema::new(db.get_database_backend());
+    db.execute(db.get_database_backend().build(&schema.create_table_from_entity(author::Entity))).await?;
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    for i in 0..20 {
+        client.author().create(
+            format!("Prepared{}", i),
+            "Statements".to_string(),
+            format!("prepared{}@example.com", i),
+            now,
+            now,
+            vec![],
+        ).exec().await?;
+    }
+    for i in 0..20 {
+        let authors = client.author().find_many(vec![author::first_name::equals(format!("Prepared{}", i))]).exec().await?;
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].first_name, format!("Prepared{}", i));
+    }
+    // One statement for the shape, and after a few custom plans the server settles on its generic plan
+    let plans = prepared_author_queries(db.clone()).await?;
+    assert_eq!(plans.len(), 1);
+    assert!(plans[0] > 0, "generic plans {:?}", plans);
+    db.close().await?;
+
+    // With room for one statement, preparing the query that reads the view evicts the author query
+    let db = statement_cache::connect_postgres(options, 1).await?;
+    let client = CausticsClient::new(db.clone());
+    for i in 0..5 {
+        assert_eq!(client.author().find_many(vec![author::first_name::equals(format!("Prepared{}", i))]).exec().await?.len(), 1);
+    }
+    assert!(prepared_author_queries(db.clone()).await?.is_empty());
+    db.close().await?;
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS prepared_statements CASCADE").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_with_explicit_auto_increment_key() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+
+    let seeded = client.author().create(
+        "Seeded".to_string(),
+        "Author".to_string(),
+        "seeded@example.com".to_string(),
+        now,
+        now,
+        vec![author::id::set(500)],
+    ).exec().await?;
+    assert_eq!(seeded.id, 500);
+    let found = client.author().find_unique(author::id::equals(500)).exec().await?.expect("seeded author");
+    assert_eq!(found.email, "seeded@example.com");
+
+    // Without the param the key is still generated
+    let generated = client.author().create(
+        "Generated".to_string(),
+        "Author".to_string(),
+        "generated@example.com".to_string(),
+        now,
+        now,
+        vec![],
+    ).exec().await?;
+    assert_ne!(generated.id, 500);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_profile_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS profile CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA profile").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dprofile", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+    let book_table = schema.create_table_from_entity(book::Entity);
+    db.execute(db.get_database_backend().build(&book_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    let now = chrono::Utc::now();
+    let author = client.author().create(
+        "Ada".to_string(),
+        "Lovelace".to_string(),
+        "ada@example.com".to_string(),
+        now,
+        now,
+        vec![]
+    ).exec().await?;
+    for (title, year) in [("A", 1990), ("B", 2000), ("C", 2001)] {
+        client.book().create(title.to_string(), author.id, year, serde_json::json!([]), vec![]).exec().await?;
+    }
+
+    // AVG over integers is NUMERIC on Postgres; it comes back as a float
+    let profile = client.book().profile().exec().await?;
+    assert_eq!(profile.count, 3);
+    let year = &profile.columns["publication_year"];
+    assert_eq!(year.min.as_deref(), Some("1990"));
+    assert_eq!(year.max.as_deref(), Some("2001"));
+    assert_eq!(year.avg, Some(1997.0));
+    assert_eq!(year.null_count, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_schema_rejects_bad_names_and_backends() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+
+    let err = client
+        .author()
+        .find_many(vec![])
+        .with_schema("tenant; DROP TABLE authors")
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("invalid schema name"), "{}", err);
+
+    let err = client.author().find_many(vec![]).with_schema("tenant_42").exec().await.unwrap_err();
+    assert!(err.to_string().contains("with_schema requires Postgres"), "{}", err);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_with_schema_routes_to_tenant_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    let admin = Database::connect(url.as_str()).await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let now = chrono::Utc::now();
+
+    // Same table in two tenant schemas, each seeded with its own authors
+    for (tenant, names) in [("tenant_a", vec!["Ann"]), ("tenant_b", vec!["Ben", "Bea"])] {
+        admin.execute_unprepared(&format!("DROP SCHEMA IF EXISTS {} CASCADE", tenant)).await?;
+        admin.execute_unprepared(&format!("CREATE SCHEMA {}", tenant)).await?;
+        let db = Database::connect(format!("{}{}options=-c%20search_path%3D{}", url, separator, tenant)).await?;
+        let schema = Schema::new(db.get_database_backend());
+        let author_table = schema.create_table_from_entity(author::Entity);
+        db.execute(db.get_database_backend().build(&author_table)).await?;
+
+        let client = CausticsClient::new(db.clone());
+        for name in names {
+            client.author().create(
+                name.to_string(),
+                "Tenant".to_string(),
+                format!("{}@{}.example.com", name.to_lowercase(), tenant),
+                now,
+                now,
+                vec![],
+            ).exec().await?;
+        }
+    }
+
+    let client = CausticsClient::new(admin.clone());
+    let names = |authors: Vec<author::ModelWithRelations>| {
+        authors.into_iter().map(|a| a.first_name).collect::<Vec<_>>()
+    };
+    let query = || client.author().find_many(vec![author::last_name::equals("Tenant")]).order_by(author::first_name::order(SortOrder::Asc));
+    assert_eq!(names(query().with_schema("tenant_a").exec().await?), vec!["Ann"]);
+    assert_eq!(names(query().with_schema("tenant_b").exec().await?), vec!["Bea", "Ben"]);
+
+    // Inside a client transaction the schema switch only lasts for its own query
+    #[derive(Debug, sea_orm::FromQueryResult)]
+    struct SearchPath {
+        search_path: String,
+    }
+    let show = caustics::raw!("SELECT current_setting('search_path') AS search_path");
+    let (before, tenant, after) = client
+        .transaction()
+        .run(|tx| async move {
+            let before = tx._query_raw::<SearchPath>(show.clone()).exec().await?;
+            let tenant = tx
+                .author()
+                .find_many(vec![author::last_name::equals("Tenant")])
+                .with_schema("tenant_a")
+                .exec()
+                .await?;
+            let after = tx._query_raw::<SearchPath>(show).exec().await?;
+            Ok((before, tenant, after))
+        })
+        .await?;
+    assert_eq!(names(tenant), vec!["Ann"]);
+    assert_eq!(after[0].search_path, before[0].search_path);
+
+    for tenant in ["tenant_a", "tenant_b"] {
+        admin.execute_unprepared(&format!("DROP SCHEMA IF EXISTS {} CASCADE", tenant)).await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_count_estimate_is_exact_off_postgres() -> Result<(), DbErr> {
+    let db = setup_db().await?;
+    let client = CausticsClient::new(db.clone());
+
+    assert_eq!(client.author().count_estimate().await?, 0);
+    create_truncate_authors(&client).await?;
+    assert_eq!(client.author().count_estimate().await?, 2);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at CAUSTICS_TEST_POSTGRES_URL"]
+async fn test_count_estimate_postgres() -> Result<(), DbErr> {
+    use sea_orm::Schema;
+
+    let url = postgres_url();
+    // Keep these tables apart from the other Postgres tests, which share the database
+    let admin = Database::connect(url.as_str()).await?;
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS count_estimate CASCADE").await?;
+    admin.execute_unprepared("CREATE SCHEMA count_estimate").await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let db = Database::connect(format!("{}{}options=-c%20search_path%3Dcount_estimate", url, separator)).await?;
+
+    let schema = Schema::new(db.get_database_backend());
+    let author_table = schema.create_table_from_entity(author::Entity);
+    db.execute(db.get_database_backend().build(&author_table)).await?;
+
+    let client = CausticsClient::new(db.clone());
+    create_truncate_authors(&client).await?;
+
+    // Never analyzed: no planner estimate yet, so the rows are counted
+    assert_eq!(client.author().count_estimate().await?, 2);
+
+    // After ANALYZE the estimate comes from pg_class
+    db.execute_unprepared("ANALYZE authors").await?;
+    let estimate = client.author().count_estimate().await?;
+    assert!(estimate >= 0);
+    assert_eq!(estimate, 2);
+
+    admin.execute_unprepared("DROP SCHEMA IF EXISTS count_estimate CASCADE").await?;
+    Ok(())
+}
@@ -2,8 +2,9 @@ use caustics::ToSeaOrmValue;
 use caustics_macros::caustics;
 use sea_orm::entity::prelude::*;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, EnumIter, DeriveActiveEnum)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, EnumIter, DeriveActiveEnum, serde::Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ActivityStatus {
     #[sea_orm(string_value = "PENDING")]
     #[default]
@@ -67,6 +68,13 @@ pub mod student {
             to = "super::grade::Column::StudentId"
         )]
         Grades,
+        #[sea_orm(
+            has_one = "super::student_profile::Entity",
+            from = "Column::Id",
+            to = "super::student_profile::Column::StudentId"
+        )]
+        /// #[caustics(field_name="profile")]
+        Profile,
     }
 
     impl Related<super::enrollment::Entity> for Entity {
@@ -81,6 +89,12 @@ pub mod student {
         }
     }
 
+    impl Related<super::student_profile::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Profile.def()
+        }
+    }
+
     impl ActiveModelBehavior for ActiveModel {}
 }
 
@@ -3,7 +3,9 @@
 pub mod helpers {
     use sea_orm::{Database, DatabaseConnection, Schema};
 
-    use school::entities::{course, department, enrollment, grade, semester, student, teacher};
+    use school::entities::{
+        course, department, enrollment, grade, semester, student, student_profile, teacher,
+    };
 
     pub async fn setup_test_db() -> DatabaseConnection {
         use sea_orm::ConnectionTrait;
@@ -55,6 +57,12 @@ pub mod helpers {
         let create_semesters_sql = db.get_database_backend().build(create_semesters);
         db.execute(create_semesters_sql).await.unwrap();
 
+        // Create student profiles table
+        let mut student_profile_table = schema.create_table_from_entity(student_profile::Entity);
+        let create_student_profiles = student_profile_table.if_not_exists();
+        let create_student_profiles_sql = db.get_database_backend().build(create_student_profiles);
+        db.execute(create_student_profiles_sql).await.unwrap();
+
         db
     }
 }
@@ -2207,4 +2215,270 @@ mod caustics_school_advanced_tests {
         assert!(children.len() >= 2);
         assert!(children.iter().all(|e| e.status == "s1"));
     }
+
+    #[tokio::test]
+    async fn test_has_many_disconnect_and_clear_nullable_relation() {
+        let db = setup_test_db().await;
+        let client = CausticsClient::new(db.clone());
+
+        let dept = client
+            .department()
+            .create(
+                "CS".to_string(),
+                "Computer Science".to_string(),
+                fixed_now(),
+                fixed_now(),
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+        let teacher = client
+            .teacher()
+            .create(
+                "T100".to_string(),
+                "Ada".to_string(),
+                "Lovelace".to_string(),
+                "ada@school.edu".to_string(),
+                fixed_now(),
+                ActivityStatus::Active,
+                fixed_now(),
+                fixed_now(),
+                department::id::equals(dept.id),
+                vec![],
+            )
+            .exec()
+            .await
+            .unwrap();
+        let mut semesters = Vec::new();
+        for code in ["2024F", "2025S"] {
+            let semester = client
+                .semester()
+                .create(
+                    code.to_string(),
+                    code.to_string(),
+                    fixed_now(),
+                    fixed_now(),
+                    ActivityStatus::Active,
+                    fixed_now(),
+                    fixed_now(),
+                    vec![],
+                )
+                .exec()
+                .await
+                .unwrap();
+            semesters.push(semester);
+        }
+        let (fall, spring) = (&semesters[0], &semesters[1]);
+
+        // Three courses in the fall semester, one in the spring semester
+        let mut courses = Vec::new();
+        for (code, semester_id) in [
+            ("CS101", fall.id),
+            ("CS102", fall.id),
+            ("CS103", fall.id),
+            ("CS201", spring.id),
+        ] {
+            let course = client
+                .course()
+                .create(
+                    code.to_string(),
+                    code.to_string(),
+                    3,
+                    30,
+                    ActivityStatus::Active,
+                    fixed_now(),
+                    fixed_now(),
+                    teacher::id::equals(teacher.id),
+                    department::id::equals(dept.id),
+                    vec![course::semester::connect(semester::id::equals(semester_id))],
+                )
+                .exec()
+                .await
+                .unwrap();
+            courses.push(course);
+        }
+
+        let semester_of = |code: &'static str| {
+            let client = &client;
+            async move {
+                client
+                    .course()
+                    .find_unique(course::code::equals(code.to_string()))
+                    .exec()
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .semester_id
+            }
+        };
+
+        // Selective disconnect only detaches the listed children of this parent
+        client
+            .semester()
+            .update(
+                semester::id::equals(fall.id),
+                vec![semester::courses::disconnect(vec![
+                    course::id::equals(courses[0].id),
+                    course::id::equals(courses[3].id),
+                ])],
+            )
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(semester_of("CS101").await, None);
+        assert_eq!(semester_of("CS102").await, Some(fall.id));
+        assert_eq!(semester_of("CS103").await, Some(fall.id));
+        assert_eq!(semester_of("CS201").await, Some(spring.id));
+
+        // A course's department is required, so it cannot be disconnected from one
+        let required = client
+            .department()
+            .update(
+                department::id::equals(dept.id),
+                vec![department::courses::disconnect(vec![course::id::equals(courses[1].id)])],
+            )
+            .exec()
+            .await;
+        assert!(required.is_err());
+        let in_department = client
+            .course()
+            .count(vec![course::department_id::equals(dept.id)])
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(in_department, 4);
+
+        // set(vec![]) clears every remaining association without deleting children
+        client
+            .semester()
+            .update(
+                semester::id::equals(fall.id),
+                vec![semester::courses::set(vec![])],
+            )
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(semester_of("CS102").await, None);
+        assert_eq!(semester_of("CS103").await, None);
+        assert_eq!(semester_of("CS201").await, Some(spring.id));
+        assert_eq!(client.course().count(vec![]).exec().await.unwrap(), 4);
+
+        let fall_courses = client
+            .semester()
+            .find_unique(semester::id::equals(fall.id))
+            .with(semester::courses::fetch(vec![]))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap()
+            .courses
+            .unwrap();
+        assert!(fall_courses.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_has_one_connect_moves_the_target() {
+        let db = setup_test_db().await;
+        let client = CausticsClient::new(db.clone());
+
+        let mut students = Vec::new();
+        for (number, first_name) in [("S777", "Iris"), ("S778", "Jonah")] {
+            let student = client
+                .student()
+                .create(
+                    number.to_string(),
+                    first_name.to_string(),
+                    "West".to_string(),
+                    fixed_now(),
+                    fixed_now(),
+                    ActivityStatus::Active,
+                    fixed_now(),
+                    fixed_now(),
+                    vec![],
+                )
+                .exec()
+                .await
+                .unwrap();
+            students.push(student);
+        }
+        let (iris, jonah) = (&students[0], &students[1]);
+
+        // Both profiles start out on Jonah
+        let mut profiles = Vec::new();
+        for bio in ["first", "second"] {
+            let profile = client
+                .student_profile()
+                .create(
+                    ActivityStatus::Active,
+                    fixed_now(),
+                    fixed_now(),
+                    student::id::equals(jonah.id),
+                    vec![student_profile::bio::set(Some(bio.to_string()))],
+                )
+                .exec()
+                .await
+                .unwrap();
+            profiles.push(profile);
+        }
+        let (first, second) = (&profiles[0], &profiles[1]);
+
+        // Connecting a missing profile fails
+        let missing = client
+            .student()
+            .update(
+                student::id::equals(iris.id),
+                vec![student::profile::connect(student_profile::id::equals(second.id + 100))],
+            )
+            .exec()
+            .await;
+        assert!(missing.is_err());
+
+        client
+            .student()
+            .update(
+                student::id::equals(iris.id),
+                vec![student::profile::connect(student_profile::id::equals(first.id))],
+            )
+            .exec()
+            .await
+            .unwrap();
+        let connected = client
+            .student()
+            .find_unique(student::id::equals(iris.id))
+            .with(student::profile::include(|rel| rel))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(connected.profile.unwrap().id, first.id);
+
+        // The current profile's foreign key is required, so it cannot be detached to make room
+        // for another one; nothing changes
+        let replaced = client
+            .student()
+            .update(
+                student::id::equals(iris.id),
+                vec![student::profile::connect(student_profile::id::equals(second.id))],
+            )
+            .exec()
+            .await;
+        assert!(replaced.is_err());
+        let first_after = client
+            .student_profile()
+            .find_unique(student_profile::id::equals(first.id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first_after.student_id, iris.id);
+        let second_after = client
+            .student_profile()
+            .find_unique(student_profile::id::equals(second.id))
+            .exec()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second_after.student_id, jonah.id);
+    }
 }